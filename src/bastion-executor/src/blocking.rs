@@ -55,7 +55,7 @@ use std::collections::VecDeque;
 use std::future::Future;
 use std::io::ErrorKind;
 use std::iter::Iterator;
-use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
 use std::sync::Mutex;
 use std::time::Duration;
 use std::{env, thread};
@@ -94,6 +94,13 @@ static FREQUENCY: AtomicU64 = AtomicU64::new(0);
 /// Possible max threads (without OS contract).
 static MAX_THREADS: AtomicU64 = AtomicU64::new(10_000);
 
+/// Low watermark set using [set_low_watermark](fn.set_low_watermark.html),
+/// or `0` to use the `BASTION_BLOCKING_THREADS` env var instead.
+static CONFIGURED_LOW_WATERMARK: AtomicU64 = AtomicU64::new(0);
+
+/// Whether the low watermark was already fixed.
+static LOW_WATERMARK_FIXED: AtomicBool = AtomicBool::new(false);
+
 /// Pool interface between the scheduler and thread pool
 struct Pool {
     sender: Sender<LightProc>,
@@ -333,20 +340,58 @@ where
 ///
 /// Low watermark value, defines the bare minimum of the pool.
 /// Spawns initial thread set.
-/// Can be configurable with env var `BASTION_BLOCKING_THREADS` at runtime.
+/// Can be configurable with env var `BASTION_BLOCKING_THREADS` at runtime,
+/// or with [set_low_watermark](fn.set_low_watermark.html) before it is first used.
 #[inline]
 pub fn low_watermark() -> &'static u64 {
     lazy_static! {
         static ref LOW_WATERMARK: u64 = {
-            env::var_os("BASTION_BLOCKING_THREADS")
-                .map(|x| x.to_str().unwrap().parse::<u64>().unwrap())
-                .unwrap_or(DEFAULT_LOW_WATERMARK)
+            LOW_WATERMARK_FIXED.store(true, Ordering::SeqCst);
+            match CONFIGURED_LOW_WATERMARK.load(Ordering::SeqCst) {
+                0 => env::var_os("BASTION_BLOCKING_THREADS")
+                    .map(|x| x.to_str().unwrap().parse::<u64>().unwrap())
+                    .unwrap_or(DEFAULT_LOW_WATERMARK),
+                threads => threads,
+            }
         };
     }
 
     &*LOW_WATERMARK
 }
 
+///
+/// Sets the low watermark of the pool (the number of threads it
+/// spawns initially and keeps once the load decreased), taking
+/// precedence over the `BASTION_BLOCKING_THREADS` env var.
+///
+/// This has no effect once the low watermark was used, which
+/// happens when the first task is spawned onto the pool, in which
+/// case `false` is returned.
+///
+/// # Example
+/// ```rust
+/// use bastion_executor::blocking::*;
+/// use bastion_executor::run::run;
+/// use lightproc::prelude::*;
+///
+/// assert!(set_low_watermark(4));
+///
+/// let handle = spawn_blocking(async { 42 }, ProcStack::default());
+/// run(handle, ProcStack::default());
+///
+/// // The pool keeps the low watermark it was initialized with.
+/// assert!(!set_low_watermark(8));
+/// assert_eq!(*low_watermark(), 4);
+/// ```
+pub fn set_low_watermark(threads: u64) -> bool {
+    if LOW_WATERMARK_FIXED.load(Ordering::SeqCst) {
+        return false;
+    }
+
+    CONFIGURED_LOW_WATERMARK.store(threads.max(1), Ordering::SeqCst);
+    true
+}
+
 ///
 /// Affinity pinner for blocking pool
 /// Pinning isn't going to be enabled for single core systems.
@@ -4,26 +4,33 @@
 //! Distributor provides a fair distribution of threads and pinning them to cores for fair execution.
 //! It assigns threads in round-robin fashion to all cores.
 use crate::placement::{self, CoreId};
+use crate::pool;
 use crate::run_queue::{Stealer, Worker};
 use crate::worker;
 use lightproc::prelude::*;
 use std::thread;
 
 pub(crate) struct Distributor {
+    // The core each thread is pinned to, the index of a thread
+    // being the affinity it uses for scheduling.
     pub(crate) cores: Vec<CoreId>,
 }
 
 impl Distributor {
     pub(crate) fn new() -> Self {
+        let cores = placement::get_core_ids().expect("Core mapping couldn't be fetched");
+
         Distributor {
-            cores: placement::get_core_ids().expect("Core mapping couldn't be fetched"),
+            cores: (0..pool::threads())
+                .map(|thread| cores[thread % cores.len()])
+                .collect(),
         }
     }
 
     pub(crate) fn assign(self) -> Vec<Stealer<LightProc>> {
         let mut stealers = Vec::<Stealer<LightProc>>::new();
 
-        for core in self.cores {
+        for (affinity, core) in self.cores.into_iter().enumerate() {
             let wrk = Worker::new_fifo();
             stealers.push(wrk.stealer());
 
@@ -34,9 +41,9 @@ impl Distributor {
                     placement::set_for_current(core);
 
                     // run initial stats generation for cores
-                    worker::stats_generator(core.id, &wrk);
+                    worker::stats_generator(affinity, &wrk);
                     // actual execution
-                    worker::main_loop(core.id, wrk);
+                    worker::main_loop(affinity, wrk);
                 })
                 .expect("cannot start the thread for running proc");
         }
@@ -39,6 +39,7 @@ pub mod allocator;
 pub mod blocking;
 pub mod distributor;
 pub mod load_balancer;
+pub mod metrics;
 pub mod placement;
 pub mod pool;
 pub mod run;
@@ -5,7 +5,7 @@
 //! to all runtime.
 //!
 use crate::load_balancer;
-use crate::placement;
+use crate::pool;
 use lazy_static::*;
 use std::mem::MaybeUninit;
 use std::sync::atomic::{AtomicUsize, Ordering};
@@ -142,10 +142,8 @@ impl SmpStats for Stats {
             }
             break;
         }
-        self.mean_level.store(
-            sum.wrapping_div(placement::get_core_ids().unwrap().len()),
-            Ordering::SeqCst,
-        );
+        self.mean_level
+            .store(sum.wrapping_div(*core_retrieval()), Ordering::SeqCst);
     }
 }
 
@@ -160,11 +158,11 @@ pub fn stats() -> &'static Stats {
 }
 
 ///
-/// Retrieve core count for the runtime scheduling purposes
+/// Retrieve the number of threads (one per core by default) for the runtime scheduling purposes
 #[inline]
 pub fn core_retrieval() -> &'static usize {
     lazy_static! {
-        static ref CORE_COUNT: usize = placement::get_core_ids().unwrap().len();
+        static ref CORE_COUNT: usize = pool::threads();
     }
 
     &*CORE_COUNT
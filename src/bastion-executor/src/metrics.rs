@@ -0,0 +1,174 @@
+//!
+//! Counters about the pool's workers and the processes spawned onto it
+//!
+//! Counters are updated with relaxed atomics, and can be disabled entirely with [set_enabled]
+//! to avoid even this overhead.
+use crate::load_balancer::{self, SmpStats};
+use crate::pool;
+use std::sync::atomic::{AtomicBool, AtomicU64, AtomicUsize, Ordering};
+
+static ENABLED: AtomicBool = AtomicBool::new(true);
+static SPAWNED: AtomicU64 = AtomicU64::new(0);
+static COMPLETED: AtomicU64 = AtomicU64::new(0);
+static PARKED: AtomicUsize = AtomicUsize::new(0);
+
+///
+/// Snapshot of the pool's counters, as returned by [stats].
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ExecutorStats {
+    ///
+    /// Counters of each worker thread of the pool, indexed by their affinity
+    /// (empty if the pool wasn't initialized yet)
+    pub workers: Vec<WorkerStats>,
+    ///
+    /// Number of workers currently parked because they had nothing to run
+    pub parked: usize,
+    ///
+    /// Number of processes spawned onto the pool
+    pub spawned: u64,
+    ///
+    /// Number of processes spawned onto the pool which completed, panicked or were dropped
+    pub completed: u64,
+}
+
+///
+/// Counters of a worker thread of the pool.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct WorkerStats {
+    ///
+    /// Number of processes in the worker's run queue, as last sampled by it
+    pub run_queue: usize,
+    ///
+    /// Number of times the worker took processes from the global run queue or from the
+    /// run queue of another worker
+    pub steals: u64,
+}
+
+///
+/// Enables or disables the counters (which are enabled by default).
+///
+/// The counters keep their values while disabled.
+///
+/// # Example
+/// ```rust
+/// use bastion_executor::metrics;
+///
+/// metrics::set_enabled(false);
+/// assert!(!metrics::enabled());
+/// ```
+pub fn set_enabled(enabled: bool) {
+    ENABLED.store(enabled, Ordering::Relaxed);
+}
+
+///
+/// Returns whether the counters are enabled.
+pub fn enabled() -> bool {
+    ENABLED.load(Ordering::Relaxed)
+}
+
+///
+/// Returns a snapshot of the pool's counters.
+///
+/// # Example
+/// ```rust
+/// use bastion_executor::metrics;
+/// use bastion_executor::prelude::*;
+/// use lightproc::prelude::*;
+///
+/// let handle = spawn(async { 42 }, ProcStack::default());
+/// run(handle, ProcStack::default());
+///
+/// let stats = metrics::stats();
+/// assert_eq!(stats.workers.len(), threads());
+/// assert!(stats.spawned >= 1);
+/// ```
+pub fn stats() -> ExecutorStats {
+    let workers = if pool::initialized() {
+        let pool = pool::get();
+        let mut workers: Vec<_> = pool
+            .steals
+            .iter()
+            .map(|steals| WorkerStats {
+                run_queue: 0,
+                steals: steals.load(Ordering::Relaxed),
+            })
+            .collect();
+        for (affinity, load) in load_balancer::stats().get_sorted_load() {
+            if let Some(worker) = workers.get_mut(affinity) {
+                worker.run_queue = load;
+            }
+        }
+
+        workers
+    } else {
+        Vec::new()
+    };
+
+    // Loaded first for processes completing meanwhile to be counted
+    // as spawned too.
+    let completed = COMPLETED.load(Ordering::Relaxed);
+    ExecutorStats {
+        workers,
+        parked: PARKED.load(Ordering::Relaxed),
+        spawned: SPAWNED.load(Ordering::Relaxed),
+        completed,
+    }
+}
+
+///
+/// Counts a spawned process as completed once dropped, along with the process' future.
+#[derive(Debug)]
+pub(crate) struct Completion(bool);
+
+impl Drop for Completion {
+    fn drop(&mut self) {
+        if self.0 {
+            COMPLETED.fetch_add(1, Ordering::Relaxed);
+        }
+    }
+}
+
+///
+/// Counts a worker as parked until dropped.
+#[derive(Debug)]
+pub(crate) struct Parked(bool);
+
+impl Drop for Parked {
+    fn drop(&mut self) {
+        if self.0 {
+            PARKED.fetch_sub(1, Ordering::Relaxed);
+        }
+    }
+}
+
+///
+/// Counts a process spawned onto the pool.
+pub(crate) fn spawned() -> Completion {
+    let enabled = enabled();
+    if enabled {
+        SPAWNED.fetch_add(1, Ordering::Relaxed);
+    }
+
+    Completion(enabled)
+}
+
+///
+/// Counts a worker as parked.
+pub(crate) fn parked() -> Parked {
+    let enabled = enabled();
+    if enabled {
+        PARKED.fetch_add(1, Ordering::Relaxed);
+    }
+
+    Parked(enabled)
+}
+
+///
+/// Counts a steal made by the worker with the given affinity.
+pub(crate) fn stole(affinity: usize) {
+    if enabled() {
+        if let Some(steals) = pool::get().steals.get(affinity) {
+            steals.fetch_add(1, Ordering::Relaxed);
+        }
+    }
+}
@@ -5,16 +5,53 @@
 //! We spawn futures onto the pool with [spawn] method of global run queue or
 //! with corresponding [Worker]'s spawn method.
 use crate::distributor::Distributor;
+use crate::metrics;
+use crate::placement;
 use crate::run_queue::{Injector, Stealer};
 use crate::sleepers::Sleepers;
 use crate::worker;
 use lazy_static::lazy_static;
 use lightproc::prelude::*;
+use std::error::Error;
+use std::fmt::{self, Display, Formatter};
 use std::future::Future;
+use std::pin::Pin;
+use std::sync::atomic::{AtomicBool, AtomicU64, AtomicUsize, Ordering};
+use std::sync::Arc;
+use std::task::{Context, Poll};
+use std::thread;
+use std::time::{Duration, Instant};
+
+// The number of threads the pool should spawn, or `0` to spawn
+// one per core, fixed once the pool gets initialized.
+static THREADS: AtomicUsize = AtomicUsize::new(0);
+static INITIALIZED: AtomicBool = AtomicBool::new(false);
+// Whether the pool was shut down, until it gets reopened.
+static SHUT_DOWN: AtomicBool = AtomicBool::new(false);
+
+///
+/// Error returned when spawning a process onto the pool (see [try_spawn]) or in a [Scope]
+/// (see [Scope::try_spawn]) after it was shut down.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ShutDown;
+
+impl Display for ShutDown {
+    fn fmt(&self, fmt: &mut Formatter) -> fmt::Result {
+        fmt.write_str("the pool or scope was shut down")
+    }
+}
+
+impl Error for ShutDown {}
 
 ///
 /// Spawn a process (which contains future + process stack) onto the executor from the global level.
 ///
+/// Processes whose stack has a high priority (see [ProcStack::with_high_priority]) are
+/// run by the workers before the other ones, every time they get scheduled.
+///
+/// If the pool was shut down (see [shutdown]), the process is cancelled without being
+/// run and its handle resolves to `None`. Use [try_spawn] to get an error instead.
+///
 /// # Example
 /// ```rust
 /// use bastion_executor::prelude::*;
@@ -45,6 +82,186 @@ where
     self::get().spawn(future, stack)
 }
 
+///
+/// Spawn a process (which contains future + process stack) onto the executor from the global level,
+/// unless the pool was shut down (see [shutdown]).
+///
+/// # Example
+/// ```rust
+/// use bastion_executor::prelude::*;
+/// use lightproc::prelude::*;
+///
+/// let handle = try_spawn(async { 42 }, ProcStack::default()).unwrap();
+///
+/// run(
+///     async {
+///         assert_eq!(handle.await, Some(42));
+///     },
+///     ProcStack::default(),
+/// );
+/// ```
+pub fn try_spawn<F, T>(future: F, stack: ProcStack) -> Result<RecoverableHandle<T>, ShutDown>
+where
+    F: Future<Output = T> + Send + 'static,
+    T: Send + 'static,
+{
+    if is_shut_down() {
+        return Err(ShutDown);
+    }
+
+    Ok(spawn(future, stack))
+}
+
+///
+/// Spawn a process (which contains future + process stack) onto the given thread of the executor.
+/// The process is never stolen by the other threads and is always run by this one.
+///
+/// The thread index wraps around the number of threads of the pool.
+///
+/// # Example
+/// ```rust
+/// use bastion_executor::prelude::*;
+/// use lightproc::prelude::*;
+///
+/// let stack = ProcStack::default();
+///
+/// let handle = spawn_pinned(
+///     async {
+///         42
+///     },
+///     stack.clone(),
+///     0,
+/// );
+///
+/// run(
+///     async {
+///         assert_eq!(handle.await, Some(42));
+///     },
+///     stack.clone(),
+/// );
+/// ```
+pub fn spawn_pinned<F, T>(future: F, stack: ProcStack, thread: usize) -> RecoverableHandle<T>
+where
+    F: Future<Output = T> + Send + 'static,
+    T: Send + 'static,
+{
+    self::get().spawn_pinned(future, stack, thread)
+}
+
+///
+/// Spawn a process (which contains future + process stack) onto the given thread of the
+/// executor, like [spawn_pinned] does, unless the pool was shut down (see [shutdown]).
+pub fn try_spawn_pinned<F, T>(
+    future: F,
+    stack: ProcStack,
+    thread: usize,
+) -> Result<RecoverableHandle<T>, ShutDown>
+where
+    F: Future<Output = T> + Send + 'static,
+    T: Send + 'static,
+{
+    if is_shut_down() {
+        return Err(ShutDown);
+    }
+
+    Ok(spawn_pinned(future, stack, thread))
+}
+
+///
+/// Spawn a process (which contains future + process stack) onto a dedicated thread,
+/// outside of the pool, which runs it until it completes.
+///
+/// # Example
+/// ```rust
+/// use bastion_executor::prelude::*;
+/// use lightproc::prelude::*;
+///
+/// let stack = ProcStack::default();
+///
+/// let handle = spawn_dedicated(
+///     async {
+///         42
+///     },
+///     stack.clone(),
+/// );
+///
+/// run(
+///     async {
+///         assert_eq!(handle.await, Some(42));
+///     },
+///     stack.clone(),
+/// );
+/// ```
+pub fn spawn_dedicated<F, T>(future: F, stack: ProcStack) -> RecoverableHandle<T>
+where
+    F: Future<Output = T> + Send + 'static,
+    T: Send + 'static,
+{
+    spawn_dedicated_in(future, stack, None)
+}
+
+///
+/// Spawn a process (which contains future + process stack) onto a dedicated thread, like
+/// [spawn_dedicated] does, unless the pool was shut down (see [shutdown]).
+pub fn try_spawn_dedicated<F, T>(
+    future: F,
+    stack: ProcStack,
+) -> Result<RecoverableHandle<T>, ShutDown>
+where
+    F: Future<Output = T> + Send + 'static,
+    T: Send + 'static,
+{
+    if is_shut_down() {
+        return Err(ShutDown);
+    }
+
+    Ok(spawn_dedicated(future, stack))
+}
+
+///
+/// Spawn a process onto a dedicated thread, in the given scope if any.
+fn spawn_dedicated_in<F, T>(
+    future: F,
+    stack: ProcStack,
+    scope: Option<Scope>,
+) -> RecoverableHandle<T>
+where
+    F: Future<Output = T> + Send + 'static,
+    T: Send + 'static,
+{
+    let (sender, receiver) = crossbeam_channel::unbounded();
+    let send = move |proc| {
+        // The thread exits once the process completed.
+        sender.send(proc).ok();
+    };
+    let (task, handle) = match scope {
+        Some(scope) => {
+            let (future, queued) = scope.scoped(future);
+            let schedule = move |proc| scope.schedule(proc, &queued, &send);
+            LightProc::recoverable(future, schedule, stack)
+        }
+        None => LightProc::recoverable(future, send, stack),
+    };
+    // Dropping the process cancels it.
+    if is_shut_down() {
+        return handle;
+    }
+
+    thread::Builder::new()
+        .name("bastion-dedicated".to_string())
+        .spawn(move || {
+            // The channel gets disconnected when the process is
+            // dropped (along with its schedule function).
+            for proc in receiver {
+                worker::set_stack(proc.stack(), || proc.run());
+            }
+        })
+        .expect("cannot start the thread for running proc");
+
+    task.schedule();
+    handle
+}
+
 ///
 /// Pool that global run queue, stealers of the workers, and parked threads.
 #[derive(Debug)]
@@ -53,11 +270,21 @@ pub struct Pool {
     /// Global run queue implementation
     pub(crate) injector: Injector<LightProc>,
     ///
+    /// Run queue of the high priority processes, checked by the workers
+    /// before their other run queues
+    pub(crate) priority: Injector<LightProc>,
+    ///
     /// Stealers of the workers
     pub(crate) stealers: Vec<Stealer<LightProc>>,
     ///
+    /// Run queues of the processes pinned to each worker
+    pub(crate) pinned: Vec<Injector<LightProc>>,
+    ///
     /// Container of parked threads
     pub(crate) sleepers: Sleepers,
+    ///
+    /// Number of steals made by each worker
+    pub(crate) steals: Vec<AtomicU64>,
 }
 
 impl Pool {
@@ -72,10 +299,404 @@ impl Pool {
         let _child_id = stack.get_pid() as u64;
         let _parent_id = worker::get_proc_stack(|t| t.get_pid() as u64).unwrap_or(0);
 
-        let (task, handle) = LightProc::recoverable(future, worker::schedule, stack);
-        task.schedule();
+        self.spawn_scheduled(future, stack, worker::schedule)
+    }
+
+    ///
+    /// Spawn a process (which contains future + process stack) onto the given thread via [Pool] interface.
+    pub fn spawn_pinned<F, T>(
+        &self,
+        future: F,
+        stack: ProcStack,
+        thread: usize,
+    ) -> RecoverableHandle<T>
+    where
+        F: Future<Output = T> + Send + 'static,
+        T: Send + 'static,
+    {
+        let thread = thread % self.pinned.len();
+        let schedule = move |proc| worker::schedule_pinned(thread, proc);
+
+        self.spawn_scheduled(future, stack, schedule)
+    }
+
+    ///
+    /// Spawn a process onto the pool using the given schedule function.
+    fn spawn_scheduled<F, T, S>(
+        &self,
+        future: F,
+        stack: ProcStack,
+        schedule: S,
+    ) -> RecoverableHandle<T>
+    where
+        F: Future<Output = T> + Send + 'static,
+        T: Send + 'static,
+        S: Fn(LightProc) + Send + Sync + 'static,
+    {
+        let completion = metrics::spawned();
+        let future = async move {
+            let _completion = completion;
+            future.await
+        };
+        let (task, handle) = LightProc::recoverable(future, schedule, stack);
+        // Dropping the process cancels it.
+        if !is_shut_down() {
+            task.schedule();
+        }
         handle
     }
+
+    ///
+    /// Returns whether no process is running or waiting to be run on the pool, all its
+    /// workers being parked.
+    fn is_quiesced(&self) -> bool {
+        self.sleepers.sleeping() == self.stealers.len()
+            && self.priority.is_empty()
+            && self.injector.is_empty()
+            && self.pinned.iter().all(|pinned| pinned.is_empty())
+            && self.stealers.iter().all(|stealer| stealer.is_empty())
+    }
+}
+
+///
+/// A set of processes spawned onto the pool which can be shut down together, without
+/// affecting the other processes of the pool (unlike [shutdown]): once the scope was shut
+/// down, processes can't be spawned in it anymore (see [ShutDown]) and the ones of the
+/// scope which get scheduled afterwards are cancelled instead of being run, their handles
+/// resolving to `None`, until it is reopened.
+///
+/// The clones of a scope share its processes.
+///
+/// # Example
+/// ```rust
+/// use bastion_executor::prelude::*;
+/// use lightproc::prelude::*;
+/// use std::time::Duration;
+///
+/// let scope = Scope::new();
+/// let handle = scope.try_spawn(async { 42 }, ProcStack::default()).unwrap();
+/// assert_eq!(run(handle, ProcStack::default()), Some(42));
+///
+/// scope.shutdown();
+/// assert!(scope.try_spawn(async { 42 }, ProcStack::default()).is_err());
+/// assert!(scope.block_until_quiesced(Duration::from_secs(5)));
+///
+/// // The processes spawned outside of the scope still run.
+/// let handle = spawn(async { 42 }, ProcStack::default());
+/// assert_eq!(run(handle, ProcStack::default()), Some(42));
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct Scope(Arc<ScopeState>);
+
+#[derive(Debug, Default)]
+struct ScopeState {
+    shut_down: AtomicBool,
+    // The number of runs of the scope's processes which were
+    // scheduled and didn't end yet.
+    runs: AtomicUsize,
+}
+
+///
+/// The future of a process spawned in a [Scope], ending the run of the process it was
+/// scheduled for every time it gets polled (or once dropped, if it was cancelled).
+struct Scoped<F> {
+    future: Pin<Box<F>>,
+    state: Arc<ScopeState>,
+    // Whether the process was scheduled and wasn't run since.
+    queued: Arc<AtomicBool>,
+}
+
+///
+/// Ends a run of a process of a [Scope] once dropped, even if the process panicked.
+struct RunEnd<'a>(&'a ScopeState);
+
+impl Scope {
+    ///
+    /// Creates a new scope, which isn't shut down.
+    pub fn new() -> Self {
+        Scope::default()
+    }
+
+    ///
+    /// Spawn a process (which contains future + process stack) onto the executor in this
+    /// scope, like [spawn] does, unless the scope was shut down.
+    pub fn try_spawn<F, T>(
+        &self,
+        future: F,
+        stack: ProcStack,
+    ) -> Result<RecoverableHandle<T>, ShutDown>
+    where
+        F: Future<Output = T> + Send + 'static,
+        T: Send + 'static,
+    {
+        if self.is_shut_down() {
+            return Err(ShutDown);
+        }
+
+        let (future, queued) = self.scoped(future);
+        let scope = self.clone();
+        let schedule = move |proc| scope.schedule(proc, &queued, worker::schedule);
+        Ok(get().spawn_scheduled(future, stack, schedule))
+    }
+
+    ///
+    /// Spawn a process (which contains future + process stack) onto the given thread of
+    /// the executor in this scope, like [spawn_pinned] does, unless the scope was shut down.
+    pub fn try_spawn_pinned<F, T>(
+        &self,
+        future: F,
+        stack: ProcStack,
+        thread: usize,
+    ) -> Result<RecoverableHandle<T>, ShutDown>
+    where
+        F: Future<Output = T> + Send + 'static,
+        T: Send + 'static,
+    {
+        if self.is_shut_down() {
+            return Err(ShutDown);
+        }
+
+        let pool = get();
+        let thread = thread % pool.pinned.len();
+        let (future, queued) = self.scoped(future);
+        let scope = self.clone();
+        let schedule =
+            move |proc| scope.schedule(proc, &queued, |proc| worker::schedule_pinned(thread, proc));
+        Ok(pool.spawn_scheduled(future, stack, schedule))
+    }
+
+    ///
+    /// Spawn a process (which contains future + process stack) onto a dedicated thread in
+    /// this scope, like [spawn_dedicated] does, unless the scope was shut down.
+    pub fn try_spawn_dedicated<F, T>(
+        &self,
+        future: F,
+        stack: ProcStack,
+    ) -> Result<RecoverableHandle<T>, ShutDown>
+    where
+        F: Future<Output = T> + Send + 'static,
+        T: Send + 'static,
+    {
+        if self.is_shut_down() {
+            return Err(ShutDown);
+        }
+
+        Ok(spawn_dedicated_in(future, stack, Some(self.clone())))
+    }
+
+    ///
+    /// Shuts the scope down, without waiting for its processes to be cancelled (see
+    /// [Scope::block_until_quiesced]).
+    pub fn shutdown(&self) {
+        self.0.shut_down.store(true, Ordering::SeqCst);
+    }
+
+    ///
+    /// Reopens the scope after it was shut down, its processes being accepted and run again.
+    pub fn reopen(&self) {
+        self.0.shut_down.store(false, Ordering::SeqCst);
+    }
+
+    ///
+    /// Returns whether the scope was shut down and wasn't reopened since.
+    pub fn is_shut_down(&self) -> bool {
+        self.0.shut_down.load(Ordering::SeqCst)
+    }
+
+    ///
+    /// Blocks the current thread until none of the scope's processes is running or waiting
+    /// to be run (which happens once it was shut down, unless a process never yields),
+    /// returning `false` if it didn't happen before the timeout elapsed.
+    pub fn block_until_quiesced(&self, timeout: Duration) -> bool {
+        let deadline = Instant::now() + timeout;
+        while self.0.runs.load(Ordering::SeqCst) > 0 {
+            if Instant::now() >= deadline {
+                return false;
+            }
+
+            thread::sleep(Duration::from_millis(1));
+        }
+
+        true
+    }
+
+    ///
+    /// Wraps the future of a process of the scope, returning it along with the flag its
+    /// schedule function must pass to [Scope::schedule].
+    fn scoped<F: Future>(&self, future: F) -> (Scoped<F>, Arc<AtomicBool>) {
+        let queued = Arc::new(AtomicBool::new(false));
+        let scoped = Scoped {
+            future: Box::pin(future),
+            state: self.0.clone(),
+            queued: queued.clone(),
+        };
+
+        (scoped, queued)
+    }
+
+    ///
+    /// Schedules a process of the scope using the given schedule function or, if the scope
+    /// was shut down, cancels it by dropping it.
+    fn schedule(&self, proc: LightProc, queued: &AtomicBool, schedule: impl Fn(LightProc)) {
+        if self.is_shut_down() {
+            return;
+        }
+
+        // A process woken up again before being run is still scheduled for a single run.
+        if !queued.swap(true, Ordering::SeqCst) {
+            self.0.runs.fetch_add(1, Ordering::SeqCst);
+        }
+        schedule(proc);
+    }
+}
+
+impl<F: Future> Future for Scoped<F> {
+    type Output = F::Output;
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context) -> Poll<Self::Output> {
+        let this = &mut *self;
+        let _run_end = if this.queued.swap(false, Ordering::SeqCst) {
+            Some(RunEnd(&this.state))
+        } else {
+            None
+        };
+        this.future.as_mut().poll(cx)
+    }
+}
+
+impl<F> Drop for Scoped<F> {
+    fn drop(&mut self) {
+        // The process was cancelled while waiting to be run.
+        if self.queued.swap(false, Ordering::SeqCst) {
+            self.state.runs.fetch_sub(1, Ordering::SeqCst);
+        }
+    }
+}
+
+impl Drop for RunEnd<'_> {
+    fn drop(&mut self) {
+        self.0.runs.fetch_sub(1, Ordering::SeqCst);
+    }
+}
+
+///
+/// Sets the number of threads the pool spawns to run processes
+/// (one per core by default), which are assigned to the cores in
+/// a round-robin fashion.
+///
+/// This has no effect once the pool was initialized, which
+/// happens when the first process is spawned onto it, in which
+/// case `false` is returned.
+///
+/// # Example
+/// ```rust
+/// use bastion_executor::prelude::*;
+/// use lightproc::prelude::*;
+///
+/// assert!(set_threads(4));
+///
+/// let handle = spawn(async { 42 }, ProcStack::default());
+/// run(handle, ProcStack::default());
+///
+/// // The pool keeps the threads it was initialized with.
+/// assert!(!set_threads(8));
+/// assert_eq!(threads(), 4);
+/// ```
+pub fn set_threads(threads: usize) -> bool {
+    if INITIALIZED.load(Ordering::SeqCst) {
+        return false;
+    }
+
+    THREADS.store(threads, Ordering::SeqCst);
+    true
+}
+
+///
+/// Returns the number of threads the pool spawns (or spawned) to
+/// run processes.
+pub fn threads() -> usize {
+    match THREADS.load(Ordering::SeqCst) {
+        0 => placement::get_core_ids()
+            .map(|cores| cores.len())
+            .unwrap_or(1)
+            .max(1),
+        threads => threads,
+    }
+}
+
+///
+/// Shuts the pool down: processes can't be spawned onto it anymore (see [try_spawn]) and
+/// the ones which get scheduled afterwards (including the ones already waiting to be run)
+/// are cancelled instead of being run, their handles resolving to `None`. The workers
+/// then get parked until the pool is reopened (see [reopen]).
+///
+/// This doesn't wait for the pool to quiesce, see [block_until_quiesced].
+///
+/// # Example
+/// ```rust
+/// use bastion_executor::prelude::*;
+/// use lightproc::prelude::*;
+/// use std::time::Duration;
+///
+/// shutdown();
+/// assert!(try_spawn(async { 42 }, ProcStack::default()).is_err());
+/// assert!(block_until_quiesced(Duration::from_secs(5)));
+///
+/// reopen();
+/// let handle = spawn(async { 42 }, ProcStack::default());
+/// assert_eq!(run(handle, ProcStack::default()), Some(42));
+/// ```
+pub fn shutdown() {
+    SHUT_DOWN.store(true, Ordering::SeqCst);
+    if initialized() {
+        // The workers cancel the processes waiting to be run
+        // before parking again.
+        get().sleepers.notify_all();
+    }
+}
+
+///
+/// Reopens the pool after it was shut down (see [shutdown]), processes being accepted
+/// and run again.
+pub fn reopen() {
+    SHUT_DOWN.store(false, Ordering::SeqCst);
+}
+
+///
+/// Returns whether the pool was shut down (see [shutdown]) and wasn't reopened since.
+pub fn is_shut_down() -> bool {
+    SHUT_DOWN.load(Ordering::SeqCst)
+}
+
+///
+/// Blocks the current thread until no process is running or waiting to be run on the
+/// pool (which happens once it was shut down, unless a process never yields), returning
+/// `false` if it didn't happen before the timeout elapsed.
+///
+/// Processes spawned onto dedicated threads aren't waited for.
+pub fn block_until_quiesced(timeout: Duration) -> bool {
+    if !initialized() {
+        return true;
+    }
+
+    let pool = get();
+    let deadline = Instant::now() + timeout;
+    while !pool.is_quiesced() {
+        if Instant::now() >= deadline {
+            return false;
+        }
+
+        thread::sleep(Duration::from_millis(1));
+    }
+
+    true
+}
+
+///
+/// Returns whether the pool was initialized, which happens when the first process is
+/// spawned onto it.
+pub(crate) fn initialized() -> bool {
+    INITIALIZED.load(Ordering::SeqCst)
 }
 
 ///
@@ -84,13 +705,23 @@ impl Pool {
 pub fn get() -> &'static Pool {
     lazy_static! {
         static ref POOL: Pool = {
+            // Every part of the pool uses the same number of threads,
+            // whatever `set_threads` gets called with afterwards.
+            THREADS.store(threads(), Ordering::SeqCst);
+            INITIALIZED.store(true, Ordering::SeqCst);
+
             let distributor = Distributor::new();
             let stealers = distributor.assign();
+            let pinned = stealers.iter().map(|_| Injector::new()).collect();
+            let steals = stealers.iter().map(|_| AtomicU64::new(0)).collect();
 
             Pool {
                 injector: Injector::new(),
+                priority: Injector::new(),
                 stealers,
+                pinned,
                 sleepers: Sleepers::new(),
+                steals,
             }
         };
     }
@@ -51,6 +51,23 @@ impl Sleepers {
         }
     }
 
+    /// Returns how many threads are currently asleep.
+    pub fn sleeping(&self) -> usize {
+        *self.sleep.lock().unwrap()
+    }
+
+    /// Notifies all the sleeping threads.
+    pub fn notify_all(&self) {
+        let mut sleep = self.sleep.lock().unwrap();
+
+        if *sleep > 0 {
+            *sleep = 0;
+            self.wake.notify_all();
+        } else {
+            self.notified.store(true, Ordering::SeqCst);
+        }
+    }
+
     /// Notifies one thread.
     pub fn notify_one(&self) {
         if !self.notified.load(Ordering::SeqCst) {
@@ -4,6 +4,7 @@
 //! This worker implementation relies on worker run queue statistics which are hold in the pinned global memory
 //! where workload distribution calculated and amended to their own local queues.
 use crate::load_balancer;
+use crate::metrics;
 use crate::pool::{self, Pool};
 use crate::run_queue::{Steal, Worker};
 use lightproc::prelude::*;
@@ -60,6 +61,13 @@ thread_local! {
 }
 
 pub(crate) fn schedule(proc: LightProc) {
+    if proc.stack().is_high_priority() {
+        let pool = pool::get();
+        pool.priority.push(proc);
+        pool.sleepers.notify_one();
+        return;
+    }
+
     QUEUE.with(|queue| {
         let local = unsafe { (*queue.get()).as_ref() };
 
@@ -72,16 +80,44 @@ pub(crate) fn schedule(proc: LightProc) {
     pool::get().sleepers.notify_one();
 }
 
+pub(crate) fn schedule_pinned(affinity: usize, proc: LightProc) {
+    let pool = pool::get();
+    pool.pinned[affinity].push(proc);
+
+    // Only the worker with the given affinity can run it.
+    pool.sleepers.notify_all();
+}
+
 ///
 /// Fetch the process from the run queue.
+/// High priority processes are fetched first, whatever the number of processes waiting
+/// in the other run queues.
 /// Does the work of work-stealing if process doesn't exist in the local run queue.
 pub fn fetch_proc(affinity: usize) -> Option<LightProc> {
     let pool = pool::get();
 
-    QUEUE.with(|queue| {
-        let local = unsafe { (*queue.get()).as_ref().unwrap() };
-        local.pop().or_else(|| affine_steal(pool, local, affinity))
-    })
+    fetch_priority(pool)
+        .or_else(|| fetch_pinned(pool, affinity))
+        .or_else(|| {
+            QUEUE.with(|queue| {
+                let local = unsafe { (*queue.get()).as_ref().unwrap() };
+                local.pop().or_else(|| affine_steal(pool, local, affinity))
+            })
+        })
+}
+
+fn fetch_priority(pool: &Pool) -> Option<LightProc> {
+    iter::repeat_with(|| pool.priority.steal())
+        .find(|s| !s.is_retry())
+        .and_then(|s| s.success())
+}
+
+fn fetch_pinned(pool: &Pool, affinity: usize) -> Option<LightProc> {
+    let pinned = pool.pinned.get(affinity)?;
+
+    iter::repeat_with(|| pinned.steal())
+        .find(|s| !s.is_retry())
+        .and_then(|s| s.success())
 }
 
 fn affine_steal(pool: &Pool, local: &Worker<LightProc>, affinity: usize) -> Option<LightProc> {
@@ -90,10 +126,21 @@ fn affine_steal(pool: &Pool, local: &Worker<LightProc>, affinity: usize) -> Opti
     local.pop().or_else(|| {
         // Otherwise, we need to look for a task elsewhere.
         iter::repeat_with(|| {
+            // High priority procs can get scheduled while looking for a task
+            if let Steal::Success(proc) = pool.priority.steal() {
+                return Steal::Success(proc);
+            }
+
             let core_vec = load_balancer::stats().get_sorted_load();
 
             // First try to get procs from global queue
             pool.injector.steal_batch_and_pop(&local).or_else(|| {
+                // Once the pool is shut down, only the global queue gets drained
+                // (cancelling its processes) for the workers to get parked
+                if pool::is_shut_down() {
+                    return Steal::Empty;
+                }
+
                 match core_vec.get(0) {
                     Some((core, _)) => {
                         // If affinity is the one with the highest let other's do the stealing
@@ -127,6 +174,7 @@ fn affine_steal(pool: &Pool, local: &Worker<LightProc>, affinity: usize) -> Opti
         .find(|s| !s.is_retry())
         // Extract the stolen task, if there is one.
         .and_then(|s| s.success())
+        .inspect(|_| metrics::stole(affinity))
     })
 }
 
@@ -144,8 +192,13 @@ pub(crate) fn main_loop(affinity: usize, local: Worker<LightProc>) {
         });
 
         match fetch_proc(affinity) {
+            // Dropping the process cancels it.
+            Some(proc) if pool::is_shut_down() => drop(proc),
             Some(proc) => set_stack(proc.stack(), || proc.run()),
-            None => pool::get().sleepers.wait(),
+            None => {
+                let _parked = metrics::parked();
+                pool::get().sleepers.wait()
+            }
         }
     }
 }
@@ -0,0 +1,78 @@
+use bastion_executor::prelude::*;
+use futures::future::{self, join_all};
+use lightproc::proc_stack::ProcStack;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+use std::task::Poll;
+use std::time::{Duration, Instant};
+
+// The number of steps each busy process takes.
+const STEPS: usize = 20;
+
+// Spins for the given duration, then yields for the other
+// processes to run.
+async fn busy(duration: Duration) {
+    let start = Instant::now();
+    while start.elapsed() < duration {}
+
+    let mut yielded = false;
+    future::poll_fn(|cx| {
+        if yielded {
+            return Poll::Ready(());
+        }
+
+        yielded = true;
+        cx.waker().wake_by_ref();
+        Poll::Pending
+    })
+    .await
+}
+
+// Returns how many steps busy processes saturating the pool took
+// before a process with the given stack got polled, along with the
+// total number of steps they took.
+fn steps_before_polled(stack: ProcStack) -> (usize, usize) {
+    let steps = Arc::new(AtomicUsize::new(0));
+    let busy_procs = (0..threads() * 50)
+        .map(|_| {
+            let steps = steps.clone();
+            spawn(
+                async move {
+                    for _ in 0..STEPS {
+                        busy(Duration::from_millis(1)).await;
+                        steps.fetch_add(1, Ordering::SeqCst);
+                    }
+                },
+                ProcStack::default(),
+            )
+        })
+        .collect::<Vec<_>>();
+    let total = busy_procs.len() * STEPS;
+
+    // Lets the workers take the busy processes in their own queues.
+    std::thread::sleep(Duration::from_millis(200));
+
+    let polled = {
+        let steps = steps.clone();
+        run(
+            spawn(async move { steps.load(Ordering::SeqCst) }, stack),
+            ProcStack::default(),
+        )
+        .unwrap()
+    };
+    run(join_all(busy_procs), ProcStack::default());
+
+    (polled, total)
+}
+
+#[test]
+fn high_priority_latency() {
+    let (normal, total) = steps_before_polled(ProcStack::default());
+    let (high, _) = steps_before_polled(ProcStack::default().with_high_priority(true));
+
+    // The high priority process only waits for the busy processes
+    // already running, while the other one waits for all of them to
+    // complete.
+    assert_eq!(normal, total);
+    assert!(high < total / 2);
+}
@@ -0,0 +1,82 @@
+use bastion::prelude::*;
+use std::thread;
+use std::time::Duration;
+
+///
+/// Supervision tree inspection example.
+///
+/// Prologue:
+/// This example builds a small supervision tree, then asks the system
+/// supervisor for a report of it and renders it as a tree diagram, e.g.:
+///
+/// System
+/// ├── Children(...) running
+/// └── Supervisor(...) OneForAll [2 launched, 1 stopped, 0 killed]
+///     ├── Children(...) running
+///     ├── Supervisor(...) OneForOne [1 launched, 0 stopped, 0 killed]
+///     │   └── Children(...) running
+///     └── Children(...) Stopped
+///
+fn main() {
+    Bastion::init();
+
+    Bastion::children(|children| children.with_exec(idle))
+        .expect("Couldn't create the children group.");
+    let sp_ref = Bastion::supervisor(|sp| {
+        sp.with_strategy(SupervisionStrategy::OneForAll)
+            .children(|children| children.with_exec(idle))
+            .supervisor(|sp| sp.children(|children| children.with_exec(idle)))
+    })
+    .expect("Couldn't create the supervisor.");
+    let stopped_ref = sp_ref
+        .children(|children| children.with_exec(idle))
+        .expect("Couldn't create the children group.");
+
+    Bastion::start();
+    // Stopping a children group leaves it listed as stopped by its
+    // supervisor...
+    stopped_ref
+        .stop()
+        .expect("Couldn't stop the children group.");
+    // ...once it had the time to do so.
+    thread::sleep(Duration::from_millis(200));
+
+    let report = run!(Bastion::inspect()).expect("Couldn't inspect the system.");
+    println!("System");
+    render(&report, "");
+
+    Bastion::stop();
+    Bastion::block_until_stopped();
+}
+
+async fn idle(ctx: BastionContext) -> Result<(), ()> {
+    loop {
+        ctx.recv().await?;
+    }
+}
+
+// Prints the supervised elements of the report, one per line, each
+// prefixed with the branches leading to it.
+fn render(report: &InspectReport, prefix: &str) {
+    for (index, elem) in report.children.iter().enumerate() {
+        let last = index == report.children.len() - 1;
+        let branch = if last { "└── " } else { "├── " };
+        let status = match (&elem.stop_reason, &elem.report) {
+            (Some(reason), _) => format!("{:?}", reason),
+            (None, Some(report)) => format!(
+                "{:?} [{} launched, {} stopped, {} killed]",
+                report.strategy, report.launched, report.stopped, report.killed
+            ),
+            (None, None) => "running".to_string(),
+        };
+        println!(
+            "{}{}{:?}({}) {}",
+            prefix, branch, elem.kind, elem.id, status
+        );
+
+        if let Some(report) = &elem.report {
+            let prefix = format!("{}{}", prefix, if last { "    " } else { "│   " });
+            render(report, &prefix);
+        }
+    }
+}
@@ -1,21 +1,43 @@
-use crate::broadcast::{Broadcast, Parent};
 use crate::children::Children;
 use crate::children_ref::ChildrenRef;
-use crate::config::Config;
+use crate::config::{self, Config};
 use crate::context::{BastionContext, BastionId};
-use crate::envelope::Envelope;
-use crate::message::{BastionMessage, Message};
-use crate::path::BastionPathElement;
-use crate::supervisor::{Supervisor, SupervisorRef};
-use crate::system::SYSTEM;
+use crate::events::SystemEvent;
+use crate::executor::{self, BastionExecutor, ExecutorStats};
+use crate::logger::{self, BastionLogger};
+use crate::message::{self, Message, Msg, Recipients};
+use crate::metrics::MetricsSnapshot;
+use crate::panic_handler::{self, PanicInfo};
+#[cfg(feature = "remote")]
+use crate::remote::RemoteChildrenRef;
+use crate::runtime::BastionRuntime;
+use crate::storm::StormReport;
+use crate::supervisor::{InspectReport, ShutdownReport, Supervisor, SupervisorRef};
 
 use core::future::Future;
-use tracing::{debug, trace};
+use futures::Stream;
+use tracing::debug;
 
 use std::fmt::{self, Debug, Formatter};
+#[cfg(any(feature = "http-health", feature = "remote"))]
+use std::net::SocketAddr;
+use std::sync::Arc;
+use std::time::Duration;
+
+#[cfg(any(feature = "signals", all(unix, feature = "unix-signals")))]
+use std::sync::atomic::{AtomicBool, Ordering};
+#[cfg(any(feature = "signals", all(unix, feature = "unix-signals")))]
+use std::thread;
+#[cfg(any(feature = "signals", all(unix, feature = "unix-signals")))]
+use tracing::{info, warn};
+
+// Whether `Bastion::handle_signals` or `Bastion::watch_signals`
+// installed signal handlers, which both handle `SIGINT` (and
+// `SIGTERM`), so that only one of them can.
+#[cfg(any(feature = "signals", all(unix, feature = "unix-signals")))]
+static HANDLING_SIGNALS: AtomicBool = AtomicBool::new(false);
 
 distributed_api! {
-    use std::sync::Arc;
     use crate::distributed::*;
     use artillery_core::cluster::ap::*;
 }
@@ -155,8 +177,9 @@ pub struct Bastion {
 }
 
 impl Bastion {
-    /// Initializes the system if it hasn't already been done, using
-    /// the default [`Config`].
+    /// Initializes the system if it hasn't already been done (or
+    /// if it stopped, see [`Bastion::start`]), using the default
+    /// [`Config`].
     ///
     /// **It is required that you call `Bastion::init` or
     /// [`Bastion::init_with`] at least once before using any of
@@ -177,14 +200,16 @@ impl Bastion {
     /// ```
     ///
     /// [`Config`]: struct.Config.html
+    /// [`Bastion::start`]: #method.start
     /// [`Bastion::init_with`]: #method.init_with
     pub fn init() {
         let config = Config::default();
         Bastion::init_with(config)
     }
 
-    /// Initializes the system if it hasn't already been done, using
-    /// the specified [`Config`].
+    /// Initializes the system if it hasn't already been done (or
+    /// if it stopped, see [`Bastion::start`]), using the specified
+    /// [`Config`].
     ///
     /// **It is required that you call [`Bastion::init`] or
     /// `Bastion::init_with` at least once before using any of
@@ -212,15 +237,247 @@ impl Bastion {
     /// ```
     ///
     /// [`Config`]: struct.Config.html
+    /// [`Bastion::start`]: #method.start
     /// [`Bastion::init`]: #method.init
     pub fn init_with(config: Config) {
         debug!("Bastion: Initializing with config: {:?}", config);
-        if config.backtraces().is_hide() {
-            debug!("Bastion: Hiding backtraces.");
-            std::panic::set_hook(Box::new(|_| ()));
-        }
+        config.apply();
+
+        config::set_current(config);
+        BastionRuntime::relaunched_default_runtime();
+    }
+
+    /// Sets the handler that will get called every time a child
+    /// panics, right before the panic gets caught and the child's
+    /// supervisor starts recovering it.
+    ///
+    /// The handler receives the [`BastionId`] of the child that
+    /// panicked and the [`PanicInfo`] describing the panic
+    /// (its payload and location). Calling this method again
+    /// replaces the previously set handler.
+    ///
+    /// Independently of this handler, every panic happening inside
+    /// a child is reported with a `tracing` event at the `ERROR`
+    /// level (which `log` users can receive by enabling the `log`
+    /// feature of `tracing`).
+    ///
+    /// Note that the handler is called from the panicking thread
+    /// (from inside the process-wide panic hook installed by
+    /// [`Bastion::init`]), so it shouldn't block or panic itself.
+    ///
+    /// # Arguments
+    ///
+    /// * `handler` - The closure that will get called with the
+    ///     identifier of the panicking child and the panic's
+    ///     information.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use bastion::prelude::*;
+    /// #
+    /// # Bastion::init();
+    /// #
+    /// Bastion::set_panic_handler(|id, info| {
+    ///     eprintln!("Child({}) panicked: {}", id, info);
+    /// });
+    /// #
+    /// # Bastion::start();
+    /// # Bastion::stop();
+    /// # Bastion::block_until_stopped();
+    /// ```
+    ///
+    /// [`BastionId`]: context/struct.BastionId.html
+    /// [`PanicInfo`]: https://doc.rust-lang.org/std/panic/struct.PanicInfo.html
+    /// [`Bastion::init`]: #method.init
+    pub fn set_panic_handler<H>(handler: H)
+    where
+        H: Fn(BastionId, &PanicInfo) + Send + Sync + 'static,
+    {
+        debug!("Bastion: Setting panic handler.");
+        panic_handler::set_handler(Arc::new(handler));
+    }
+
+    /// Sets the hook that will get called every time a panic
+    /// happens while the process-wide panic hook installed by
+    /// [`Bastion::init`] is used, replacing the previously set one.
+    ///
+    /// The hook receives the [`PanicInfo`] describing the panic
+    /// and the [`BastionId`] of the child that panicked, if it
+    /// happened inside one. The panics of children are then only
+    /// reported to this hook (and to the handler set with
+    /// [`Bastion::set_panic_handler`]), while the other ones are
+    /// still reported to the panic hook that was set before Bastion
+    /// got initialized (e.g. printing them to the standard error).
+    ///
+    /// Like the panic handler, the hook is called from the
+    /// panicking thread, so it shouldn't block or panic itself.
+    ///
+    /// # Arguments
+    ///
+    /// * `hook` - The closure that will get called with the panic's
+    ///     information and the identifier of the panicking child,
+    ///     if any.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use bastion::prelude::*;
+    /// #
+    /// Bastion::init();
+    /// Bastion::with_panic_hook(|info, id| match id {
+    ///     Some(id) => eprintln!("Child({}) faulted: {}", id, info),
+    ///     None => eprintln!("Unsupervised panic: {}", info),
+    /// });
+    /// #
+    /// # Bastion::start();
+    /// # Bastion::stop();
+    /// # Bastion::block_until_stopped();
+    /// ```
+    ///
+    /// [`PanicInfo`]: https://doc.rust-lang.org/std/panic/struct.PanicInfo.html
+    /// [`BastionId`]: context/struct.BastionId.html
+    /// [`Bastion::init`]: #method.init
+    /// [`Bastion::set_panic_handler`]: #method.set_panic_handler
+    pub fn with_panic_hook<H>(hook: H)
+    where
+        H: Fn(&PanicInfo, Option<&BastionId>) + Send + Sync + 'static,
+    {
+        debug!("Bastion: Setting panic hook.");
+        panic_handler::set_hook(Arc::new(hook));
+    }
+
+    /// Sets the hook that will get called every time a message
+    /// doesn't match any of the cases of a [`msg!`] macro but its
+    /// default one (see [`Msg::send_error_log`]).
+    ///
+    /// The hook receives the unhandled [`Msg`] and the [`BastionId`]
+    /// of the child that received it. Calling this method again
+    /// replaces the previously set hook.
+    ///
+    /// # Arguments
+    ///
+    /// * `hook` - The closure that will get called with the
+    ///     unhandled message and the identifier of its receiver.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use bastion::prelude::*;
+    /// #
+    /// # Bastion::init();
+    /// #
+    /// Bastion::set_unhandled_message_hook(|msg, id| {
+    ///     eprintln!("Child({}) didn't handle: {:?}", id, msg);
+    /// });
+    /// #
+    /// # Bastion::start();
+    /// # Bastion::stop();
+    /// # Bastion::block_until_stopped();
+    /// ```
+    ///
+    /// [`msg!`]: macro.msg.html
+    /// [`Msg::send_error_log`]: message/struct.Msg.html#method.send_error_log
+    /// [`Msg`]: message/struct.Msg.html
+    /// [`BastionId`]: context/struct.BastionId.html
+    pub fn set_unhandled_message_hook<H>(hook: H)
+    where
+        H: Fn(&Msg, &BastionId) + Send + Sync + 'static,
+    {
+        debug!("Bastion: Setting unhandled message hook.");
+        message::set_unhandled_message_hook(Arc::new(hook));
+    }
+
+    /// Sets the [`BastionLogger`] that will get notified of the
+    /// lifecycle events of the children's elements (when they
+    /// start, stop, fault, restart or drop a message), replacing the
+    /// previously set one.
+    ///
+    /// No logger is set by default, in which case those events are
+    /// only reported with `tracing` events. [`StderrLogger`] can be
+    /// used to print them to the standard error.
+    ///
+    /// # Arguments
+    ///
+    /// * `logger` - The logger to notify of the lifecycle events.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use bastion::prelude::*;
+    /// #
+    /// Bastion::init();
+    /// Bastion::with_logger(Box::new(StderrLogger));
+    /// #
+    /// # Bastion::start();
+    /// # Bastion::stop();
+    /// # Bastion::block_until_stopped();
+    /// ```
+    ///
+    /// [`BastionLogger`]: logger/trait.BastionLogger.html
+    /// [`StderrLogger`]: logger/struct.StderrLogger.html
+    pub fn with_logger(logger: Box<dyn BastionLogger>) {
+        debug!("Bastion: Setting logger: {:?}", logger);
+        logger::set_logger(Arc::from(logger));
+    }
 
-        lazy_static::initialize(&SYSTEM);
+    /// Makes Bastion run its supervisors, children groups and their
+    /// elements (and the futures given to the `spawn!`, `blocking!`
+    /// and `run!` macros) on the given executor instead of its own
+    /// one, replacing the previously set one.
+    ///
+    /// This should be called before [`Bastion::init`] since the
+    /// supervisors and children groups that were already spawned
+    /// keep running on the executor they were spawned onto. The
+    /// executor can also be set using [`Config::with_executor`].
+    ///
+    /// A [`TokioExecutor`] is available with the `tokio-executor`
+    /// feature.
+    ///
+    /// # Arguments
+    ///
+    /// * `executor` - The executor to run everything on.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use bastion::executor::BastionExecutor;
+    /// use bastion::prelude::*;
+    /// use futures::future::{BoxFuture, LocalBoxFuture};
+    /// use std::thread;
+    ///
+    /// #[derive(Debug)]
+    /// struct ThreadExecutor;
+    ///
+    /// impl BastionExecutor for ThreadExecutor {
+    ///     // ...
+    /// #     fn spawn(&self, future: BoxFuture<'static, ()>) {
+    /// #         thread::spawn(move || futures::executor::block_on(future));
+    /// #     }
+    /// #
+    /// #     fn spawn_blocking(&self, future: BoxFuture<'static, ()>) {
+    /// #         self.spawn(future)
+    /// #     }
+    /// #
+    /// #     fn block_on(&self, future: LocalBoxFuture<'_, ()>) {
+    /// #         futures::executor::block_on(future)
+    /// #     }
+    /// }
+    ///
+    /// Bastion::with_custom_executor(ThreadExecutor);
+    /// Bastion::init();
+    /// #
+    /// # Bastion::start();
+    /// # Bastion::stop();
+    /// # Bastion::block_until_stopped();
+    /// ```
+    ///
+    /// [`Bastion::init`]: #method.init
+    /// [`Config::with_executor`]: struct.Config.html#method.with_executor
+    /// [`TokioExecutor`]: executor/struct.TokioExecutor.html
+    pub fn with_custom_executor(executor: impl BastionExecutor) {
+        debug!("Bastion: Setting executor: {:?}", executor);
+        executor::set_executor(Arc::new(executor));
     }
 
     /// Creates a new [`Supervisor`], passes it through the specified
@@ -260,23 +517,7 @@ impl Bastion {
     where
         S: FnOnce(Supervisor) -> Supervisor,
     {
-        debug!("Bastion: Creating supervisor.");
-        let parent = Parent::system();
-        let bcast = Broadcast::new(parent, BastionPathElement::Supervisor(BastionId::new()));
-
-        debug!("Bastion: Initializing Supervisor({}).", bcast.id());
-        let supervisor = Supervisor::new(bcast);
-        let supervisor = init(supervisor);
-        debug!("Supervisor({}): Initialized.", supervisor.id());
-        let supervisor_ref = supervisor.as_ref();
-
-        debug!("Bastion: Deploying Supervisor({}).", supervisor.id());
-        let msg = BastionMessage::deploy_supervisor(supervisor);
-        let envelope = Envelope::new(msg, SYSTEM.path().clone(), SYSTEM.sender().clone());
-        trace!("Bastion: Sending envelope: {:?}", envelope);
-        SYSTEM.sender().unbounded_send(envelope).map_err(|_| ())?;
-
-        Ok(supervisor_ref)
+        BastionRuntime::relaunched_default_runtime().supervisor(init)
     }
 
     /// Creates a new [`Children`], passes it through the specified
@@ -329,8 +570,7 @@ impl Bastion {
     where
         C: FnOnce(Children) -> Children,
     {
-        debug!("Bastion: Creating children group.");
-        SYSTEM.supervisor().children(init)
+        BastionRuntime::relaunched_default_runtime().children(init)
     }
 
     /// Creates a new [`Children`] which will have the given closure
@@ -375,6 +615,98 @@ impl Bastion {
     {
         Bastion::children(|ch| ch.with_redundancy(1).with_exec(action))
     }
+
+    /// Spawns the given future onto the executor, cancelling it
+    /// (dropping it without polling it again) if it didn't complete
+    /// once `timeout` elapsed, or if the system stops before.
+    ///
+    /// This method returns a [`Future`] resolving to `Some` with the
+    /// future's output if it completed in time, or to `None` if it
+    /// got cancelled (or panicked). Dropping the returned future
+    /// doesn't cancel the spawned one.
+    ///
+    /// # Arguments
+    ///
+    /// * `future` - The future to spawn.
+    /// * `timeout` - How long to wait for the future to complete.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use bastion::prelude::*;
+    /// use std::time::Duration;
+    /// #
+    /// # Bastion::init();
+    /// # Bastion::start();
+    ///
+    /// let answer = Bastion::spawn_with_timeout(async { 42 }, Duration::from_secs(1));
+    /// assert_eq!(run!(answer), Some(42));
+    ///
+    /// let never = Bastion::spawn_with_timeout(
+    ///     futures::future::pending::<()>(),
+    ///     Duration::from_millis(10),
+    /// );
+    /// assert_eq!(run!(never), None);
+    /// #
+    /// # Bastion::stop();
+    /// # Bastion::block_until_stopped();
+    /// ```
+    ///
+    /// [`Future`]: https://doc.rust-lang.org/std/future/trait.Future.html
+    pub fn spawn_with_timeout<F, T>(future: F, timeout: Duration) -> impl Future<Output = Option<T>>
+    where
+        F: Future<Output = T> + Send + 'static,
+        T: Send + 'static,
+    {
+        BastionRuntime::default_runtime().spawn_with_timeout(future, timeout)
+    }
+
+    /// Returns a [`ChildrenRef`] referencing the running children
+    /// group that was given the specified name (using
+    /// [`Children::with_name`]), if any, allowing to find it from
+    /// anywhere without passing its reference around.
+    ///
+    /// The registry of names is updated as children groups get
+    /// deployed, restarted and stopped. A children group being
+    /// restarted by its supervisor stays registered until its
+    /// restarted incarnation replaces it, so the returned reference
+    /// might be the one of a faulted group, sending to which fails
+    /// (returning the message) instead of panicking.
+    ///
+    /// Children groups can also be looked up from inside their
+    /// elements using [`BastionContext::lookup`].
+    ///
+    /// # Arguments
+    ///
+    /// * `name` - The name of the children group to look up.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use bastion::prelude::*;
+    /// #
+    /// # Bastion::init();
+    /// #
+    /// Bastion::children(|children| {
+    ///     children.with_name("workers")
+    /// }).expect("Couldn't create the children group.");
+    ///
+    /// // Somewhere else...
+    /// if let Some(workers) = Bastion::children_named("workers") {
+    ///     workers.broadcast("work").ok();
+    /// }
+    /// #
+    /// # Bastion::start();
+    /// # Bastion::stop();
+    /// # Bastion::block_until_stopped();
+    /// ```
+    ///
+    /// [`ChildrenRef`]: children_ref/struct.ChildrenRef.html
+    /// [`Children::with_name`]: children/struct.Children.html#method.with_name
+    /// [`BastionContext::lookup`]: context/struct.BastionContext.html#method.lookup
+    pub fn children_named(name: &str) -> Option<ChildrenRef> {
+        BastionRuntime::default_runtime().children_named(name)
+    }
     distributed_api! {
         // FIXME!
         #[allow(missing_docs)]
@@ -433,143 +765,1125 @@ impl Bastion {
     /// # }
     /// ```
     pub fn broadcast<M: Message>(msg: M) -> Result<(), M> {
-        debug!("Bastion: Broadcasting message: {:?}", msg);
-        let msg = BastionMessage::broadcast(msg);
-        let envelope = Envelope::from_dead_letters(msg);
-        trace!("Bastion: Sending envelope: {:?}", envelope);
-        // FIXME: panics?
-        SYSTEM
-            .sender()
-            .unbounded_send(envelope)
-            .map_err(|err| err.into_inner().into_msg().unwrap())
+        BastionRuntime::default_runtime().broadcast(msg)
     }
 
-    /// Sends a message to the system to tell it to start
-    /// handling messages and running children.
+    /// Sends a message to the system which will then send it to all
+    /// the root-level supervisors and their supervised children and
+    /// supervisors, etc., like [`Bastion::broadcast`] does, and counts
+    /// the children groups elements' mailboxes the message was
+    /// enqueued into.
+    ///
+    /// Like other messages, it is only sent once the system started
+    /// if it wasn't already (see [`Bastion::start`]).
+    ///
+    /// This method returns a [`Recipients`] which resolves to the
+    /// number of mailboxes the message reached once every children
+    /// group it was sent to handled it, or `Err(msg)` if it failed.
+    ///
+    /// # Arguments
+    ///
+    /// * `msg` - The message to send.
     ///
     /// # Example
     ///
     /// ```rust
-    /// use bastion::prelude::*;
-    ///
-    /// Bastion::init();
+    /// # use bastion::prelude::*;
+    /// #
+    /// # Bastion::init();
+    /// #
+    /// Bastion::children(|children| {
+    ///     children
+    ///         .with_redundancy(4)
+    ///         .with_exec(|ctx: BastionContext| {
+    ///             async move {
+    ///                 loop {
+    ///                     // Reload the configuration...
+    ///                     ctx.recv().await?;
+    ///                 }
+    ///             }
+    ///         })
+    /// }).expect("Couldn't create the children group.");
     ///
-    /// // Use bastion, spawn children and supervisors...
+    /// let recipients = Bastion::broadcast_counted("Configuration changed.")
+    ///     .expect("Couldn't send the message.");
     ///
     /// Bastion::start();
-    ///
-    /// // The system will soon start, messages will
-    /// // now be handled...
+    /// assert_eq!(run!(recipients), 4);
     /// #
     /// # Bastion::stop();
     /// # Bastion::block_until_stopped();
     /// ```
-    pub fn start() {
-        debug!("Bastion: Starting.");
-        let msg = BastionMessage::start();
-        let envelope = Envelope::from_dead_letters(msg);
-        trace!("Bastion: Sending envelope: {:?}", envelope);
-        // FIXME: Err(Error)
-        SYSTEM.sender().unbounded_send(envelope).ok();
+    ///
+    /// [`Bastion::broadcast`]: #method.broadcast
+    /// [`Bastion::start`]: #method.start
+    /// [`Recipients`]: message/struct.Recipients.html
+    pub fn broadcast_counted<M: Message>(msg: M) -> Result<Recipients, M> {
+        BastionRuntime::default_runtime().broadcast_counted(msg)
     }
 
-    /// Sends a message to the system to tell it to stop
-    /// every running children groups and supervisors.
+    /// Sends a message to every element that subscribed to the
+    /// given topic using [`BastionContext::subscribe`], wherever
+    /// it lives in the supervision tree.
     ///
-    /// # Example
+    /// Delivery is best-effort: elements that are stopping or
+    /// restarting might miss the message.
     ///
-    /// ```rust
-    /// use bastion::prelude::*;
+    /// This method returns `()` if at least one element was
+    /// subscribed to the topic, otherwise it returns the message
+    /// that was supposed to be published.
     ///
-    /// Bastion::init();
+    /// # Arguments
     ///
-    /// // Use bastion, spawn children and supervisors...
+    /// * `topic` - The name of the topic to publish the message to.
+    /// * `msg` - The message to send.
     ///
-    /// Bastion::start();
+    /// # Example
     ///
-    /// // Send messages to children and/or do some
-    /// // work until you decide to stop the system...
+    /// ```rust
+    /// # use bastion::prelude::*;
+    /// #
+    /// # Bastion::init();
+    /// #
+    /// Bastion::children(|children| {
+    ///     children.with_exec(|ctx: BastionContext| {
+    ///         async move {
+    ///             ctx.subscribe("invoices");
+    ///             // Receive the published invoices...
     ///
-    /// Bastion::stop();
+    ///             Ok(())
+    ///         }
+    ///     })
+    /// }).expect("Couldn't create the children group.");
+    ///
+    /// # Bastion::start();
+    /// // Later, from anywhere...
+    /// let msg = "An invoice.";
+    /// Bastion::publish("invoices", msg).ok();
+    /// #
+    /// # Bastion::stop();
     /// # Bastion::block_until_stopped();
     /// ```
-    pub fn stop() {
-        debug!("Bastion: Stopping.");
-        let msg = BastionMessage::stop();
-        let envelope = Envelope::from_dead_letters(msg);
-        trace!("Bastion: Sending envelope: {:?}", envelope);
-        // FIXME: Err(Error)
-        SYSTEM.sender().unbounded_send(envelope).ok();
+    ///
+    /// [`BastionContext::subscribe`]: context/struct.BastionContext.html#method.subscribe
+    pub fn publish<M: Message>(topic: &str, msg: M) -> Result<(), M> {
+        BastionRuntime::default_runtime().publish(topic, msg)
     }
 
-    /// Sends a message to the system to tell it to kill every
-    /// running children groups and supervisors
+    /// Returns the number of children groups elements that are
+    /// currently running, across all supervisors.
+    ///
+    /// An element is counted from the moment it is launched
+    /// (even if the system isn't started yet) until it stops,
+    /// faults, panics or is killed.
     ///
     /// # Example
     ///
     /// ```rust
-    /// use bastion::prelude::*;
-    ///
-    /// Bastion::init();
-    ///
-    /// // Use bastion, spawn children and supervisors...
+    /// # use bastion::prelude::*;
+    /// #
+    /// # Bastion::init();
+    /// #
+    /// Bastion::children(|children| {
+    ///     children
+    ///         .with_redundancy(4)
+    ///         .with_exec(|ctx: BastionContext| {
+    ///             async move {
+    ///                 loop {
+    ///                     ctx.recv().await?;
+    ///                 }
+    ///             }
+    ///         })
+    /// }).expect("Couldn't create the children group.");
     ///
     /// Bastion::start();
-    /// // Send messages to children and/or do some
-    /// // work until you decide to kill the system...
-    ///
-    /// Bastion::kill();
+    /// # while Bastion::num_actors() < 4 {
+    /// #     std::thread::sleep(std::time::Duration::from_millis(10));
+    /// # }
+    /// assert_eq!(Bastion::num_actors(), 4);
+    /// #
+    /// # Bastion::stop();
     /// # Bastion::block_until_stopped();
     /// ```
-    pub fn kill() {
-        debug!("Bastion: Killing.");
-        let msg = BastionMessage::kill();
-        let envelope = Envelope::from_dead_letters(msg);
-        trace!("Bastion: Sending envelope: {:?}", envelope);
-        // FIXME: Err(Error)
-        SYSTEM.sender().unbounded_send(envelope).ok();
-
-        let handle = SYSTEM.handle();
-        let system = crate::executor::run(async { handle.lock().await.take() });
-        if let Some(system) = system {
-            debug!("Bastion: Cancelling system handle.");
-            system.cancel();
-        }
-
-        SYSTEM.notify_stopped();
+    pub fn num_actors() -> usize {
+        BastionRuntime::default_runtime().num_actors()
     }
 
-    /// Blocks the current thread until the system is stopped
-    /// (either by calling [`Bastion::stop()`] or
-    /// [`Bastion::kill`]).
+    /// Returns the number of supervisors that are currently
+    /// running, without counting the system's own supervisor.
     ///
     /// # Example
     ///
     /// ```rust
-    /// use bastion::prelude::*;
-    ///
-    /// Bastion::init();
+    /// # use bastion::prelude::*;
+    /// #
+    /// # Bastion::init();
+    /// #
+    /// Bastion::supervisor(|sp| sp).expect("Couldn't create the supervisor.");
     ///
-    /// // Use bastion, spawn children and supervisors...
+    /// Bastion::start();
+    /// # while Bastion::num_supervisors() < 1 {
+    /// #     std::thread::sleep(std::time::Duration::from_millis(10));
+    /// # }
+    /// assert_eq!(Bastion::num_supervisors(), 1);
+    /// #
+    /// # Bastion::stop();
+    /// # Bastion::block_until_stopped();
+    /// ```
+    pub fn num_supervisors() -> usize {
+        BastionRuntime::default_runtime().num_supervisors()
+    }
+
+    /// Asks the system for a report of the whole supervision
+    /// tree, rooted at the system supervisor: the top-level
+    /// children groups and supervisors, and recursively the ones
+    /// supervised by the running supervisors (see
+    /// [`SupervisorRef::inspect`]).
+    ///
+    /// This method returns a [`Future`] resolving to the report
+    /// once every running supervisor answered, or to `Err(())` if
+    /// the system stopped.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use bastion::prelude::*;
+    /// #
+    /// # Bastion::init();
+    /// #
+    /// Bastion::supervisor(|sp| sp).expect("Couldn't create the supervisor.");
+    ///
+    /// Bastion::start();
+    ///
+    /// # run!(async {
+    /// let report = Bastion::inspect().await.expect("The system stopped.");
+    /// for elem in report.children {
+    ///     println!("{:?}({})", elem.kind, elem.id);
+    /// }
+    /// # });
+    /// #
+    /// # Bastion::stop();
+    /// # Bastion::block_until_stopped();
+    /// ```
+    ///
+    /// [`SupervisorRef::inspect`]: supervisor/struct.SupervisorRef.html#method.inspect
+    /// [`Future`]: https://doc.rust-lang.org/std/future/trait.Future.html
+    pub fn inspect() -> impl Future<Output = Result<InspectReport, ()>> {
+        BastionRuntime::default_runtime().inspect()
+    }
+
+    /// Asks the system for a report of the whole supervision
+    /// tree (see [`Bastion::inspect`]) and renders it as a
+    /// Graphviz DOT graph (see [`InspectReport::to_dot`]).
+    ///
+    /// This method returns a [`Future`] resolving to the graph, or
+    /// to `Err(())` if the system stopped.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use bastion::prelude::*;
+    /// #
+    /// # Bastion::init();
+    /// #
+    /// Bastion::supervisor(|sp| {
+    ///     sp.children(|children| children.with_name("workers").with_redundancy(2))
+    /// }).expect("Couldn't create the supervisor.");
+    ///
+    /// Bastion::start();
+    ///
+    /// # run!(async {
+    /// let dot = Bastion::tree_dot().await.expect("The system stopped.");
+    /// println!("{}", dot);
+    /// # });
+    /// #
+    /// # Bastion::stop();
+    /// # Bastion::block_until_stopped();
+    /// ```
+    ///
+    /// [`Bastion::inspect`]: #method.inspect
+    /// [`InspectReport::to_dot`]: supervisor/struct.InspectReport.html#method.to_dot
+    /// [`Future`]: https://doc.rust-lang.org/std/future/trait.Future.html
+    pub fn tree_dot() -> impl Future<Output = Result<String, ()>> {
+        BastionRuntime::default_runtime().tree_dot()
+    }
+
+    /// Walks the whole supervision tree (see [`Bastion::inspect`])
+    /// and renders it as a Graphviz DOT graph where the nodes are
+    /// colored depending on the health of the supervisors and
+    /// children groups: green when running, yellow when stopped,
+    /// red when faulted and grey when killed (see
+    /// [`InspectReport::to_health_dot`]).
+    ///
+    /// The graph can be rendered by piping it to e.g.
+    /// `dot -Tpng -o tree.png`.
+    ///
+    /// This method returns a [`Future`] resolving to the graph,
+    /// which is empty if the system stopped.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use bastion::prelude::*;
+    /// #
+    /// # Bastion::init();
+    /// #
+    /// Bastion::supervisor(|sp| {
+    ///     sp.children(|children| children.with_name("workers"))
+    /// }).expect("Couldn't create the supervisor.");
+    ///
+    /// Bastion::start();
+    ///
+    /// # run!(async {
+    /// let dot = Bastion::export_dot().await;
+    /// std::fs::write("tree.dot", dot).expect("Couldn't write the graph.");
+    /// # std::fs::remove_file("tree.dot").ok();
+    /// # });
+    /// #
+    /// # Bastion::stop();
+    /// # Bastion::block_until_stopped();
+    /// ```
+    ///
+    /// [`Bastion::inspect`]: #method.inspect
+    /// [`InspectReport::to_health_dot`]: supervisor/struct.InspectReport.html#method.to_health_dot
+    /// [`Future`]: https://doc.rust-lang.org/std/future/trait.Future.html
+    pub fn export_dot() -> impl Future<Output = String> {
+        BastionRuntime::default_runtime().export_dot()
+    }
+
+    /// Asks the system for a textual report of the internal state
+    /// of its whole supervision tree, to diagnose a wedged system:
+    /// each supervisor with its strategy, whether it was started,
+    /// the number of messages it received before that, and the
+    /// supervised elements it added, runs, stopped and killed;
+    /// each children group with the mailbox lengths of its
+    /// elements.
+    ///
+    /// Each supervisor waits for the ones it supervises for a
+    /// bounded time, after which they are reported as
+    /// unresponsive, the whole report being answered within one
+    /// second (see [`dump_state_with_timeout`]).
+    ///
+    /// This method returns a [`Future`] resolving to the report,
+    /// or to `Err(())` if the system stopped or didn't answer in
+    /// time.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use bastion::prelude::*;
+    /// #
+    /// # Bastion::init();
+    /// #
+    /// Bastion::children(|children| children.with_name("workers"))
+    ///     .expect("Couldn't create the children group.");
+    ///
+    /// Bastion::start();
+    ///
+    /// # run!(async {
+    /// let report = Bastion::dump_state().await.expect("The system stopped.");
+    /// assert!(report.contains("workers"));
+    /// eprintln!("{}", report);
+    /// # });
+    /// #
+    /// # Bastion::stop();
+    /// # Bastion::block_until_stopped();
+    /// ```
+    ///
+    /// [`dump_state_with_timeout`]: #method.dump_state_with_timeout
+    /// [`Future`]: https://doc.rust-lang.org/std/future/trait.Future.html
+    pub fn dump_state() -> impl Future<Output = Result<String, ()>> {
+        BastionRuntime::default_runtime().dump_state()
+    }
+
+    /// Asks the system for a textual report of the internal state
+    /// of its whole supervision tree like [`dump_state`] does,
+    /// waiting for it at most `timeout`.
+    ///
+    /// # Arguments
+    ///
+    /// * `timeout` - How long to wait for the report, the deeper
+    ///     supervisors being given less time to answer.
+    ///
+    /// [`dump_state`]: #method.dump_state
+    pub fn dump_state_with_timeout(timeout: Duration) -> impl Future<Output = Result<String, ()>> {
+        BastionRuntime::default_runtime().dump_state_with_timeout(timeout)
+    }
+
+    /// Asks the system for the counters kept by every running
+    /// supervisor (deployments, faults and restarts by strategy)
+    /// and children group (messages received and processed,
+    /// elements restarted), including the system supervisor's.
+    ///
+    /// This method returns a [`Future`] resolving to a plain-data
+    /// [`MetricsSnapshot`], meant to be exported to a metrics
+    /// system, or to `Err(())` if the system stopped.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use bastion::prelude::*;
+    /// #
+    /// # Bastion::init();
+    /// #
+    /// Bastion::children(|children| children.with_name("workers"))
+    ///     .expect("Couldn't create the children group.");
+    ///
+    /// Bastion::start();
+    ///
+    /// # run!(async {
+    /// let snapshot = Bastion::metrics().await.expect("The system stopped.");
+    /// for group in &snapshot.children {
+    ///     println!("{:?}: {} restarts", group.name, group.restarts);
+    /// }
+    /// # assert!(snapshot.children_named("workers").is_some());
+    /// # });
+    /// #
+    /// # Bastion::stop();
+    /// # Bastion::block_until_stopped();
+    /// ```
     ///
+    /// [`Future`]: https://doc.rust-lang.org/std/future/trait.Future.html
+    /// [`MetricsSnapshot`]: metrics/struct.MetricsSnapshot.html
+    pub fn metrics() -> impl Future<Output = Result<MetricsSnapshot, ()>> {
+        BastionRuntime::default_runtime().metrics()
+    }
+
+    /// Returns process-wide counters about the system: how many
+    /// supervisors, children groups and elements are running, how
+    /// many restarts happened and messages were delivered, and
+    /// for how long the system has been running.
+    ///
+    /// The counters are kept up to date as the system runs, which
+    /// makes calling this method cheap (e.g. to feed them to a
+    /// metrics system periodically).
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use bastion::prelude::*;
+    /// #
+    /// # Bastion::init();
+    /// #
+    /// Bastion::children(|children| {
+    ///     children
+    ///         .with_redundancy(2)
+    ///         .with_exec(|ctx: BastionContext| async move {
+    ///             loop {
+    ///                 ctx.recv().await?;
+    ///             }
+    ///         })
+    /// }).expect("Couldn't create the children group.");
+    ///
+    /// Bastion::start();
+    /// # while Bastion::stats().elements < 2 || Bastion::stats().children_groups < 1 {
+    /// #     std::thread::sleep(std::time::Duration::from_millis(10));
+    /// # }
+    ///
+    /// let stats = Bastion::stats();
+    /// assert_eq!(stats.children_groups, 1);
+    /// assert_eq!(stats.elements, 2);
+    /// #
+    /// # Bastion::stop();
+    /// # Bastion::block_until_stopped();
+    /// ```
+    pub fn stats() -> SystemStats {
+        BastionRuntime::default_runtime().stats()
+    }
+
+    /// Subscribes to the lifecycle events of the whole supervision
+    /// tree: supervisors and children groups being deployed,
+    /// started, stopped, restarted or faulting, and elements being
+    /// restarted or faulting, each tagged with the entity's
+    /// identifier, its parent's one, its name and when it
+    /// happened.
+    ///
+    /// This method returns a [`Stream`] buffering up to 1024
+    /// events (see [`Bastion::events_with_capacity`]), which ends
+    /// once the system stopped. Publishing events never waits for
+    /// a subscriber: when its buffer is full, the events get
+    /// dropped and a [`SystemEvent::Lagged`] item telling how many
+    /// of them were is streamed once there is room again.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use bastion::prelude::*;
+    /// # use futures::StreamExt;
+    /// #
+    /// # Bastion::init();
+    /// #
+    /// let mut events = Bastion::events();
+    ///
+    /// Bastion::children(|children| children.with_name("workers"))
+    ///     .expect("Couldn't create the children group.");
+    ///
+    /// Bastion::start();
+    ///
+    /// # run!(async {
+    /// while let Some(event) = events.next().await {
+    ///     match event {
+    ///         SystemEvent::Lifecycle(event) => {
+    ///             println!("{:?} {:?}({})", event.transition, event.entity, event.id);
+    ///             # if event.transition == Transition::Started { break; }
+    ///         }
+    ///         SystemEvent::Lagged(missed) => println!("Missed {} events.", missed),
+    ///     }
+    /// }
+    /// # });
+    /// #
+    /// # Bastion::stop();
+    /// # Bastion::block_until_stopped();
+    /// ```
+    ///
+    /// [`Stream`]: https://docs.rs/futures/0.3/futures/stream/trait.Stream.html
+    /// [`Bastion::events_with_capacity`]: #method.events_with_capacity
+    /// [`SystemEvent::Lagged`]: events/enum.SystemEvent.html#variant.Lagged
+    pub fn events() -> impl Stream<Item = SystemEvent> {
+        BastionRuntime::default_runtime().events()
+    }
+
+    /// Subscribes to the lifecycle events of the whole supervision
+    /// tree like [`Bastion::events`], buffering at most the given
+    /// number of them.
+    ///
+    /// # Arguments
+    ///
+    /// * `capacity` - The number of events buffered before the
+    ///     next ones get dropped until the stream catches up.
+    ///
+    /// [`Bastion::events`]: #method.events
+    pub fn events_with_capacity(capacity: usize) -> impl Stream<Item = SystemEvent> {
+        BastionRuntime::default_runtime().events_with_capacity(capacity)
+    }
+
+    /// Calls the given hook whenever the whole supervision tree
+    /// restarts more than `max_restarts` times within a sliding
+    /// `window`, with a [`StormReport`] listing the supervisors and
+    /// children groups that restarted the most (the restarts of
+    /// elements being attributed to their children group).
+    ///
+    /// This is a soft detection, independent of the restart limits
+    /// of the supervisors (see [`Supervisor::with_restart_window`]):
+    /// the supervision tree keeps restarting as usual, while the
+    /// hook can e.g. page a human. It is called once per storm,
+    /// and only called again once the number of restarts within
+    /// the window went back to at most `max_restarts`.
+    ///
+    /// The restarts are counted from the lifecycle events (see
+    /// [`Bastion::events`]) and the hook runs on the blocking pool,
+    /// so that a slow hook can't delay restarts. Calling this
+    /// method again adds another hook.
+    ///
+    /// # Arguments
+    ///
+    /// * `max_restarts` - The number of restarts within `window`
+    ///     above which the hook gets called.
+    /// * `window` - The duration of the sliding window.
+    /// * `hook` - The closure that will get called with the report
+    ///     of the storm.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use bastion::prelude::*;
+    /// # use std::time::Duration;
+    /// #
+    /// # Bastion::init();
+    /// #
+    /// Bastion::on_restart_storm(100, Duration::from_secs(60), |report| {
+    ///     for offender in report.offenders.iter().take(3) {
+    ///         eprintln!(
+    ///             "{:?}({}) restarted {} times.",
+    ///             offender.kind, offender.id, offender.restarts
+    ///         );
+    ///     }
+    /// });
+    /// #
+    /// # Bastion::start();
+    /// # Bastion::stop();
+    /// # Bastion::block_until_stopped();
+    /// ```
+    ///
+    /// [`StormReport`]: storm/struct.StormReport.html
+    /// [`Supervisor::with_restart_window`]: supervisor/struct.Supervisor.html#method.with_restart_window
+    /// [`Bastion::events`]: #method.events
+    pub fn on_restart_storm<H>(max_restarts: usize, window: Duration, hook: H)
+    where
+        H: Fn(StormReport) + Send + Sync + 'static,
+    {
+        BastionRuntime::default_runtime().on_restart_storm(max_restarts, window, hook)
+    }
+
+    /// Returns counters about bastion's executor, shared by all the
+    /// runtimes of the process: the length of the run queue of each
+    /// of its worker threads and how many times they stole work
+    /// from the others, how many of them are parked, and how many
+    /// processes were spawned onto it and completed.
+    ///
+    /// This allows to tell whether the executor is saturated or
+    /// an element is hogging a worker thread. The counters are
+    /// updated using relaxed atomics (and can be disabled using
+    /// [`Config::without_executor_stats`]), and aren't updated
+    /// when using another executor (see [`Config::with_executor`]).
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use bastion::prelude::*;
+    /// #
+    /// # Bastion::init();
+    /// #
     /// Bastion::start();
+    ///
+    /// let stats: ExecutorStats = Bastion::executor_stats();
+    /// for (thread, worker) in stats.workers.iter().enumerate() {
+    ///     println!("{}: {} queued, {} steals", thread, worker.run_queue, worker.steals);
+    /// }
+    /// println!("{} processes running", stats.spawned.saturating_sub(stats.completed));
+    /// #
+    /// # Bastion::stop();
+    /// # Bastion::block_until_stopped();
+    /// ```
+    ///
+    /// [`Config::without_executor_stats`]: struct.Config.html#method.without_executor_stats
+    /// [`Config::with_executor`]: struct.Config.html#method.with_executor
+    pub fn executor_stats() -> ExecutorStats {
+        bastion_executor::metrics::stats()
+    }
+
+    /// Sends a message to the system to tell it to start
+    /// handling messages and running children.
+    ///
+    /// If the system already stopped (see [`Bastion::stop`] and
+    /// [`Bastion::block_until_stopped`]), a new one is created from
+    /// scratch using the last configuration it was initialized with
+    /// and started instead. The supervisors and children groups
+    /// the stopped system ran aren't rebuilt: sending messages
+    /// using their references fails.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use bastion::prelude::*;
+    ///
+    /// Bastion::init();
+    ///
+    /// // Use bastion, spawn children and supervisors...
+    ///
+    /// Bastion::start();
+    ///
+    /// // The system will soon start, messages will
+    /// // now be handled...
+    ///
+    /// Bastion::stop();
+    /// Bastion::block_until_stopped();
+    ///
+    /// // A new system can then be started...
+    /// Bastion::start();
+    /// #
+    /// # Bastion::stop();
+    /// # Bastion::block_until_stopped();
+    /// ```
+    ///
+    /// [`Bastion::stop`]: #method.stop
+    /// [`Bastion::block_until_stopped`]: #method.block_until_stopped
+    pub fn start() {
+        BastionRuntime::relaunched_default_runtime().start()
+    }
+
+    /// Sends a message to the system to tell it to stop
+    /// every running children groups and supervisors.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use bastion::prelude::*;
+    ///
+    /// Bastion::init();
+    ///
+    /// // Use bastion, spawn children and supervisors...
+    ///
+    /// Bastion::start();
+    ///
     /// // Send messages to children and/or do some
-    /// // work...
+    /// // work until you decide to stop the system...
+    ///
+    /// Bastion::stop();
+    /// # Bastion::block_until_stopped();
+    /// ```
+    pub fn stop() {
+        BastionRuntime::default_runtime().stop()
+    }
+
+    /// Stops the system like [`stop`], waiting for every running
+    /// children group and supervisor to stop until `timeout`
+    /// elapsed, and then kills the system like [`kill`] if some of
+    /// them didn't stop yet (e.g. because an element never yields).
+    ///
+    /// This method returns a [`ShutdownReport`] listing which
+    /// children groups and supervisors stopped cleanly and which
+    /// ones had to be killed. Once it returned, the system is
+    /// stopped.
+    ///
+    /// # Arguments
+    ///
+    /// * `timeout` - How long to wait for the children groups and
+    ///     supervisors to stop.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use bastion::prelude::*;
+    /// use std::time::Duration;
+    ///
+    /// Bastion::init();
+    ///
+    /// // Use bastion, spawn children and supervisors...
+    ///
+    /// Bastion::start();
+    ///
+    /// // Send messages to children and/or do some
+    /// // work until you decide to stop the system...
+    ///
+    /// let report = Bastion::stop_with_timeout(Duration::from_secs(5));
+    /// assert!(report.is_clean());
+    /// ```
+    ///
+    /// [`stop`]: #method.stop
+    /// [`kill`]: #method.kill
+    /// [`ShutdownReport`]: supervisor/struct.ShutdownReport.html
+    pub fn stop_with_timeout(timeout: Duration) -> ShutdownReport {
+        BastionRuntime::default_runtime().stop_with_timeout(timeout)
+    }
+
+    /// Makes the system stop gracefully when the process receives
+    /// `SIGINT` or `SIGTERM` (or `CTRL_C` on Windows), like
+    /// [`stop`] or, if a `timeout` is given, like
+    /// [`stop_with_timeout`]. If a second signal is received while
+    /// the system is stopping, it gets killed like [`kill`]
+    /// instead.
     ///
+    /// The signal handlers can only be installed once: this method
+    /// returns an error if it (or [`watch_signals`]) was already
+    /// called or if the application already installed its own
+    /// handlers using the `ctrlc` crate.
+    ///
+    /// This method is only available with the `signals` feature.
+    ///
+    /// # Arguments
+    ///
+    /// * `timeout` - How long to wait for the children groups and
+    ///     supervisors to stop before killing them, if they should
+    ///     be.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use bastion::prelude::*;
+    /// use std::time::Duration;
+    ///
+    /// Bastion::init();
+    /// Bastion::handle_signals(Some(Duration::from_secs(5)))
+    ///     .expect("Couldn't install the signal handlers.");
+    ///
+    /// // Use bastion, spawn children and supervisors...
+    ///
+    /// Bastion::start();
     /// # Bastion::stop();
+    /// // Until a signal is received...
     /// Bastion::block_until_stopped();
+    /// ```
+    ///
+    /// [`stop`]: #method.stop
+    /// [`stop_with_timeout`]: #method.stop_with_timeout
+    /// [`kill`]: #method.kill
+    /// [`watch_signals`]: #method.watch_signals
+    #[cfg(feature = "signals")]
+    #[cfg_attr(feature = "docs", doc(cfg(feature = "signals")))]
+    pub fn handle_signals(timeout: Option<Duration>) -> Result<(), ()> {
+        static STOPPING: AtomicBool = AtomicBool::new(false);
+
+        if HANDLING_SIGNALS.swap(true, Ordering::SeqCst) {
+            warn!("Bastion: Signal handlers already installed.");
+            return Err(());
+        }
+
+        debug!("Bastion: Installing signal handlers.");
+        let installed = ctrlc::set_handler(move || {
+            if STOPPING.swap(true, Ordering::SeqCst) {
+                warn!("Bastion: Received a signal while stopping, killing.");
+                Bastion::kill();
+                return;
+            }
+
+            info!("Bastion: Received a signal, stopping.");
+            match timeout {
+                // Waiting on another thread to still be able to
+                // handle a second signal in the meantime.
+                Some(timeout) => {
+                    thread::spawn(move || Bastion::stop_with_timeout(timeout));
+                }
+                None => Bastion::stop(),
+            }
+        });
+
+        if let Err(err) = installed {
+            warn!("Bastion: Couldn't install signal handlers: {}", err);
+            // Letting the handlers be installed on a retry.
+            HANDLING_SIGNALS.store(false, Ordering::SeqCst);
+            return Err(());
+        }
+
+        Ok(())
+    }
+
+    /// Makes the system handle the Unix signals the process
+    /// receives: `SIGTERM` stops it like [`stop`], `SIGINT` kills
+    /// it like [`kill`] and `SIGUSR1` logs its [`stats`].
+    ///
+    /// The signals are handled by a dedicated thread (the signal
+    /// handlers only wake it up), and the handlers the application
+    /// installed for them before are still called. This method
+    /// returns an error if it (or [`handle_signals`]) was already
+    /// called or if the handlers couldn't be installed.
+    ///
+    /// This method is only available with the `unix-signals`
+    /// feature, and does nothing on platforms other than Unix.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use bastion::prelude::*;
+    ///
+    /// Bastion::init();
+    /// Bastion::watch_signals().expect("Couldn't install the signal handlers.");
+    ///
+    /// // Use bastion, spawn children and supervisors...
+    ///
+    /// Bastion::start();
+    /// # Bastion::stop();
+    /// // Until `SIGTERM` or `SIGINT` is received...
+    /// Bastion::block_until_stopped();
+    /// ```
+    ///
+    /// [`stop`]: #method.stop
+    /// [`kill`]: #method.kill
+    /// [`stats`]: #method.stats
+    /// [`handle_signals`]: #method.handle_signals
+    #[cfg(feature = "unix-signals")]
+    #[cfg_attr(feature = "docs", doc(cfg(feature = "unix-signals")))]
+    pub fn watch_signals() -> Result<(), ()> {
+        #[cfg(unix)]
+        {
+            use signal_hook::consts::{SIGINT, SIGTERM, SIGUSR1};
+            use signal_hook::iterator::Signals;
+
+            if HANDLING_SIGNALS.swap(true, Ordering::SeqCst) {
+                warn!("Bastion: Signal handlers already installed.");
+                return Err(());
+            }
+
+            debug!("Bastion: Watching signals.");
+            let mut signals = match Signals::new(&[SIGTERM, SIGINT, SIGUSR1]) {
+                Ok(signals) => signals,
+                Err(err) => {
+                    warn!("Bastion: Couldn't install signal handlers: {}", err);
+                    // Letting the handlers be installed on a retry.
+                    HANDLING_SIGNALS.store(false, Ordering::SeqCst);
+                    return Err(());
+                }
+            };
+
+            thread::spawn(move || {
+                for signal in signals.forever() {
+                    match signal {
+                        SIGTERM => {
+                            info!("Bastion: Received SIGTERM, stopping.");
+                            Bastion::stop();
+                        }
+                        SIGINT => {
+                            warn!("Bastion: Received SIGINT, killing.");
+                            Bastion::kill();
+                        }
+                        SIGUSR1 => info!("Bastion: {:?}", Bastion::stats()),
+                        _ => unreachable!(),
+                    }
+                }
+            });
+        }
+
+        Ok(())
+    }
+
+    /// Starts serving an HTTP health-check endpoint on the given
+    /// port (on all interfaces), for e.g. the liveness and
+    /// readiness probes of a container orchestrator:
+    ///
+    /// - `GET /health` answers `{"status": "ok", "actors": N}`,
+    ///     where `N` is the number of running elements (see
+    ///     [`num_actors`]).
+    /// - `GET /ready` answers with a `200` status once the system
+    ///     got started (see [`start`]), or `503` before.
+    ///
+    /// The endpoint is served by a dedicated thread, and stops
+    /// listening once the system stopped (e.g. after [`stop`]).
+    /// This method returns the address it listens on (allowing to
+    /// use `0` as the port to get any free one), or an error if it
+    /// couldn't listen on it.
+    ///
+    /// This method is only available with the `http-health`
+    /// feature.
+    ///
+    /// # Arguments
+    ///
+    /// * `port` - The port to listen on.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use bastion::prelude::*;
+    ///
+    /// Bastion::init();
+    /// let addr = Bastion::register_health_endpoint(0)
+    ///     .expect("Couldn't listen for health checks.");
+    /// println!("Serving health checks on {}.", addr);
+    ///
+    /// // Use bastion, spawn children and supervisors...
+    ///
+    /// Bastion::start();
+    /// # Bastion::stop();
+    /// Bastion::block_until_stopped();
+    /// ```
+    ///
+    /// [`num_actors`]: #method.num_actors
+    /// [`start`]: #method.start
+    /// [`stop`]: #method.stop
+    #[cfg(feature = "http-health")]
+    #[cfg_attr(feature = "docs", doc(cfg(feature = "http-health")))]
+    pub fn register_health_endpoint(port: u16) -> Result<SocketAddr, ()> {
+        BastionRuntime::default_runtime().register_health_endpoint(port)
+    }
+
+    /// Starts accepting connections on the given address on a
+    /// dedicated thread (until the system stops), letting other
+    /// systems send messages to the children groups with the
+    /// given names (see [`Children::with_name`]) using
+    /// [`remote_children`].
+    ///
+    /// The groups are looked up by name when a message is received
+    /// for them, so that it is delivered to their current
+    /// incarnation. The messages that can't be delivered (because
+    /// the group isn't exposed or isn't running) are sent to the
+    /// dead letters.
+    ///
+    /// This method returns the address it is listening on (which
+    /// is useful when asking for port `0`), or `Err(())` if it
+    /// couldn't listen on the given address.
+    ///
+    /// This method is only available with the `remote` feature.
+    ///
+    /// # Arguments
+    ///
+    /// * `addr` - The address to listen on.
+    /// * `names` - The names of the exposed children groups.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use bastion::prelude::*;
+    ///
+    /// Bastion::init();
+    /// Bastion::children(|children| {
+    ///     children.with_name("orders")
+    /// }).expect("Couldn't create the children group.");
+    ///
+    /// let addr = Bastion::serve_remote("0.0.0.0:0".parse().unwrap(), &["orders"])
+    ///     .expect("Couldn't listen for remote messages.");
+    /// println!("Serving remote messages on {}.", addr);
+    ///
+    /// Bastion::start();
+    /// # Bastion::stop();
+    /// Bastion::block_until_stopped();
+    /// ```
+    ///
+    /// [`Children::with_name`]: children/struct.Children.html#method.with_name
+    /// [`remote_children`]: #method.remote_children
+    #[cfg(feature = "remote")]
+    #[cfg_attr(feature = "docs", doc(cfg(feature = "remote")))]
+    pub fn serve_remote(addr: SocketAddr, names: &[&str]) -> Result<SocketAddr, ()> {
+        BastionRuntime::default_runtime().serve_remote(addr, names)
+    }
+
+    /// Returns a [`RemoteChildrenRef`] referencing the children
+    /// group with the given name of the system listening on the
+    /// given address (see [`serve_remote`]), allowing to send it
+    /// messages.
+    ///
+    /// No connection is established until a message is sent.
+    ///
+    /// This method is only available with the `remote` feature.
+    ///
+    /// # Arguments
+    ///
+    /// * `addr` - The address the other system is listening on.
+    /// * `name` - The name of the children group.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use bastion::prelude::*;
+    ///
+    /// Bastion::init();
+    ///
+    /// let orders = Bastion::remote_children("10.0.0.2:4200".parse().unwrap(), "orders");
+    /// assert_eq!(orders.name(), "orders");
+    /// #
+    /// # Bastion::start();
+    /// # Bastion::stop();
+    /// # Bastion::block_until_stopped();
+    /// ```
+    ///
+    /// [`RemoteChildrenRef`]: remote/struct.RemoteChildrenRef.html
+    /// [`serve_remote`]: #method.serve_remote
+    #[cfg(feature = "remote")]
+    #[cfg_attr(feature = "docs", doc(cfg(feature = "remote")))]
+    pub fn remote_children(addr: SocketAddr, name: &str) -> RemoteChildrenRef {
+        BastionRuntime::default_runtime().remote_children(addr, name)
+    }
+
+    /// Sends a message to the system to tell it to kill every
+    /// running children groups and supervisors
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use bastion::prelude::*;
+    ///
+    /// Bastion::init();
+    ///
+    /// // Use bastion, spawn children and supervisors...
+    ///
+    /// Bastion::start();
+    /// // Send messages to children and/or do some
+    /// // work until you decide to kill the system...
+    ///
+    /// Bastion::kill();
+    /// # Bastion::block_until_stopped();
+    /// ```
+    pub fn kill() {
+        BastionRuntime::default_runtime().kill()
+    }
+
+    /// Blocks the current thread until the system is stopped
+    /// (either by calling [`Bastion::stop()`] or
+    /// [`Bastion::kill`], or because of a fault it couldn't
+    /// recover from).
+    ///
+    /// This method returns a [`SystemExit`] telling how the system
+    /// stopped, allowing to e.g. exit with a non-zero status if it
+    /// faulted.
+    ///
+    /// Once no system is running anymore (see [`BastionRuntime`]),
+    /// the processes bastion spawned onto its own executor (e.g.
+    /// using `spawn!`) and still running are cancelled the next time
+    /// they get woken up, and the ones spawned afterwards are
+    /// cancelled right away, until another system gets launched.
+    /// The processes spawned directly onto the executor's pool keep
+    /// running. This method then only returns once none of the
+    /// former is running anymore.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use bastion::prelude::*;
+    ///
+    /// Bastion::init();
+    ///
+    /// // Use bastion, spawn children and supervisors...
+    ///
+    /// Bastion::start();
+    /// // Send messages to children and/or do some
+    /// // work...
+    ///
+    /// # Bastion::stop();
+    /// let exit = Bastion::block_until_stopped();
     /// // The system is now stopped. A child might have
     /// // stopped or killed it...
+    /// if let SystemExit::Faulted { .. } = exit {
+    ///     std::process::exit(1);
+    /// }
     /// ```
     ///
     /// [`Bastion::stop()`]: #method.stop
-    /// [`Bastion::kill()`]: #method.kill
-    pub fn block_until_stopped() {
-        debug!("Bastion: Blocking until system is stopped.");
-        SYSTEM.wait_until_stopped();
+    /// [`Bastion::kill`]: #method.kill
+    /// [`SystemExit`]: enum.SystemExit.html
+    /// [`BastionRuntime`]: struct.BastionRuntime.html
+    pub fn block_until_stopped() -> SystemExit {
+        BastionRuntime::default_runtime().block_until_stopped()
     }
 }
 
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+/// Process-wide counters about the system, as returned by
+/// [`Bastion::stats`].
+///
+/// [`Bastion::stats`]: struct.Bastion.html#method.stats
+pub struct SystemStats {
+    /// The number of supervisors currently running, without
+    /// counting the system's own supervisor.
+    pub supervisors: usize,
+    /// The number of children groups currently running, without
+    /// counting the dead letters' one.
+    pub children_groups: usize,
+    /// The number of children groups elements currently running.
+    pub elements: usize,
+    /// The number of times children groups elements and
+    /// top-level supervisors were restarted since the system
+    /// started.
+    pub restarts: usize,
+    /// The number of messages delivered to the mailboxes of
+    /// children groups elements since the system started.
+    pub messages_delivered: usize,
+    /// How long ago the system started, or zero if it didn't.
+    pub uptime: Duration,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+/// How the system stopped, as returned by
+/// [`Bastion::block_until_stopped`].
+///
+/// [`Bastion::block_until_stopped`]: struct.Bastion.html#method.block_until_stopped
+pub enum SystemExit {
+    /// The system was stopped using [`Bastion::stop`] (or
+    /// [`Bastion::stop_with_timeout`], if every children group
+    /// and supervisor stopped in time).
+    ///
+    /// [`Bastion::stop`]: struct.Bastion.html#method.stop
+    /// [`Bastion::stop_with_timeout`]: struct.Bastion.html#method.stop_with_timeout
+    Stopped,
+    /// The system was killed using [`Bastion::kill`] (or
+    /// [`Bastion::stop_with_timeout`], if some children groups
+    /// or supervisors didn't stop in time).
+    ///
+    /// [`Bastion::kill`]: struct.Bastion.html#method.kill
+    /// [`Bastion::stop_with_timeout`]: struct.Bastion.html#method.stop_with_timeout
+    Killed,
+    /// The system terminated because of a fault it couldn't
+    /// recover from (e.g. a supervisor using
+    /// [`SupervisionStrategy::PanicForward`] panicked).
+    ///
+    /// [`SupervisionStrategy::PanicForward`]: supervisor/enum.SupervisionStrategy.html#variant.PanicForward
+    Faulted {
+        /// The identifier of the top-level supervisor which
+        /// faulted, if it is known.
+        culprit: Option<BastionId>,
+        /// The message of the panic the culprit forwarded, if it
+        /// is known.
+        panic: Option<String>,
+    },
+}
+
 impl Debug for Bastion {
     fn fmt(&self, fmt: &mut Formatter) -> fmt::Result {
         fmt.debug_struct("Bastion").finish()
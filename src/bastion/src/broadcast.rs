@@ -1,19 +1,93 @@
 use crate::children_ref::ChildrenRef;
+use crate::config::BroadcastConfig;
 use crate::context::BastionId;
 use crate::envelope::Envelope;
 use crate::message::BastionMessage;
 use crate::path::{BastionPath, BastionPathElement};
 use crate::supervisor::SupervisorRef;
-use crate::system::SYSTEM;
+use crate::system::GlobalSystem;
 use futures::channel::mpsc::{self, UnboundedReceiver, UnboundedSender};
 use futures::prelude::*;
 use fxhash::FxHashMap;
 use std::pin::Pin;
-use std::sync::Arc;
-use std::task::{Context, Poll};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex};
+use std::task::{Context, Poll, Waker};
 
-pub(crate) type Sender = UnboundedSender<Envelope>;
-pub(crate) type Receiver = UnboundedReceiver<Envelope>;
+// The sending half of a mailbox, which counts the envelopes the
+// mailbox holds (see `BastionContext::mailbox_len`).
+#[derive(Debug, Clone)]
+pub(crate) struct Sender {
+    inner: UnboundedSender<Envelope>,
+    len: Arc<AtomicUsize>,
+    // The number of envelopes above which `try_send` fails, if
+    // the mailbox is bounded.
+    capacity: Option<usize>,
+}
+
+// Why an envelope couldn't be sent using `Sender::try_send` or
+// `Broadcast::try_send_child`, along with the envelope.
+#[derive(Debug)]
+pub(crate) enum TrySendError<T> {
+    // The mailbox is bounded and full.
+    Full(T),
+    // The mailbox was closed (or the child isn't registered).
+    Disconnected(T),
+}
+
+#[derive(Debug)]
+pub(crate) struct Receiver {
+    inbox: Inbox,
+}
+
+// The receiving half of a mailbox, shared with the context of the
+// element owning it (see `BastionContext::try_recv_sync`).
+#[derive(Debug, Clone)]
+pub(crate) struct Inbox {
+    inner: Arc<Mutex<InboxInner>>,
+    len: Arc<AtomicUsize>,
+}
+
+#[derive(Debug)]
+struct InboxInner {
+    recver: UnboundedReceiver<Envelope>,
+    // The envelope taken from the channel by `Inbox::try_take`
+    // without being taken out of the inbox, received next.
+    peeked: Option<Envelope>,
+    // Wakes up the task receiving from the channel once an
+    // envelope was peeked, since the channel won't anymore.
+    waker: Option<Waker>,
+}
+
+pub(crate) fn channel() -> (Sender, Receiver) {
+    channel_with(BroadcastConfig::Unbounded)
+}
+
+pub(crate) fn channel_with(config: BroadcastConfig) -> (Sender, Receiver) {
+    let (sender, recver) = mpsc::unbounded();
+    let len = Arc::new(AtomicUsize::new(0));
+    let capacity = match config {
+        BroadcastConfig::Unbounded => None,
+        BroadcastConfig::Bounded(capacity) => Some(capacity),
+    };
+    let sender = Sender {
+        inner: sender,
+        len: len.clone(),
+        capacity,
+    };
+    let inner = InboxInner {
+        recver,
+        peeked: None,
+        waker: None,
+    };
+    let inbox = Inbox {
+        inner: Arc::new(Mutex::new(inner)),
+        len,
+    };
+    let recver = Receiver { inbox };
+
+    (sender, recver)
+}
 
 #[derive(Debug)]
 pub(crate) struct Broadcast {
@@ -22,43 +96,33 @@ pub(crate) struct Broadcast {
     path: Arc<BastionPath>, // Arc is needed because we put path to Envelope
     parent: Parent,
     children: FxHashMap<BastionId, Sender>,
+    // The system of the runtime this broadcast belongs to.
+    system: Arc<GlobalSystem>,
 }
 
 #[derive(Debug, Clone)]
 pub(crate) enum Parent {
-    None,
-    System,
+    System(Arc<GlobalSystem>),
     Supervisor(SupervisorRef),
     Children(ChildrenRef),
 }
 
-impl Parent {
-    pub(super) fn is_none(&self) -> bool {
-        match self {
-            Parent::None => true,
-            _ => false,
-        }
-    }
-
-    pub(super) fn is_system(&self) -> bool {
-        match self {
-            Parent::System => true,
-            _ => false,
-        }
-    }
-}
-
 impl Broadcast {
     pub(crate) fn new(parent: Parent, element: BastionPathElement) -> Self {
-        let (sender, recver) = mpsc::unbounded();
         let children = FxHashMap::default();
 
-        let parent_path: BastionPath = match &parent {
-            Parent::None | Parent::System => BastionPath::root(),
-            Parent::Supervisor(sv_ref) => BastionPath::clone(sv_ref.path()),
-            Parent::Children(ch_ref) => BastionPath::clone(ch_ref.path()),
+        let (parent_path, system): (BastionPath, _) = match &parent {
+            Parent::System(system) => (BastionPath::root(), system.clone()),
+            Parent::Supervisor(sv_ref) => {
+                (BastionPath::clone(sv_ref.path()), sv_ref.system().clone())
+            }
+            Parent::Children(ch_ref) => {
+                (BastionPath::clone(ch_ref.path()), ch_ref.system().clone())
+            }
         };
 
+        let (sender, recver) = channel_with(system.config().broadcast_config());
+
         // FIXME: unwrap
         let path = parent_path
             .append(element)
@@ -71,14 +135,15 @@ impl Broadcast {
             recver,
             path,
             children,
+            system,
         }
     }
 
-    pub(crate) fn new_root(parent: Parent) -> Self {
-        // FIXME
-        assert!(parent.is_none() || parent.is_system());
-
-        let (sender, recver) = mpsc::unbounded();
+    pub(crate) fn new_root(system: Arc<GlobalSystem>) -> Self {
+        // The system has no parent: what it would send to one is
+        // handled by itself instead.
+        let parent = Parent::system(system.clone());
+        let (sender, recver) = channel();
         let children = FxHashMap::default();
         let path = BastionPath::root();
         let path = Arc::new(path);
@@ -89,6 +154,7 @@ impl Broadcast {
             recver,
             path,
             children,
+            system,
         }
     }
 
@@ -96,6 +162,10 @@ impl Broadcast {
         self.path.id()
     }
 
+    pub(crate) fn inbox(&self) -> &Inbox {
+        &self.recver.inbox
+    }
+
     pub(crate) fn sender(&self) -> &Sender {
         &self.sender
     }
@@ -108,6 +178,10 @@ impl Broadcast {
         &self.parent
     }
 
+    pub(crate) fn system(&self) -> &Arc<GlobalSystem> {
+        &self.system
+    }
+
     pub(crate) fn register(&mut self, child: &Self) {
         self.children
             .insert(child.id().clone(), child.sender.clone());
@@ -117,6 +191,21 @@ impl Broadcast {
         self.children.remove(id);
     }
 
+    // Registers a child that was created by another broadcast
+    // (e.g. adopted from a faulted supervisor).
+    pub(crate) fn register_sender(&mut self, id: BastionId, sender: Sender) {
+        self.children.insert(id, sender);
+    }
+
+    // Unregisters the child, returning its sender.
+    pub(crate) fn take_child(&mut self, id: &BastionId) -> Option<Sender> {
+        self.children.remove(id)
+    }
+
+    pub(crate) fn set_parent(&mut self, parent: Parent) {
+        self.parent = parent;
+    }
+
     pub(crate) fn clear_children(&mut self) {
         self.children.clear();
     }
@@ -153,6 +242,12 @@ impl Broadcast {
         self.clear_children();
     }
 
+    // Stops receiving messages, dropping those that weren't
+    // received yet.
+    pub(crate) fn close(&mut self) {
+        self.recver.close();
+    }
+
     pub(crate) fn stopped(&mut self) {
         self.stop_children();
 
@@ -162,6 +257,15 @@ impl Broadcast {
         self.send_parent(env).ok();
     }
 
+    pub(crate) fn idle_stopped(&mut self) {
+        self.stop_children();
+
+        let msg = BastionMessage::idle_stop(self.id().clone());
+        let env = Envelope::new(msg, self.path.clone(), self.sender.clone());
+        // FIXME: Err(msg)
+        self.send_parent(env).ok();
+    }
+
     pub(crate) fn faulted(&mut self) {
         self.kill_children();
 
@@ -171,7 +275,7 @@ impl Broadcast {
         self.send_parent(env).ok();
     }
 
-    pub(crate) fn send_parent(&self, envelope: Envelope) -> Result<(), Envelope> {
+    pub(crate) fn send_parent(&self, envelope: Envelope) -> Result<(), Box<Envelope>> {
         self.parent.send(envelope)
     }
 
@@ -183,14 +287,48 @@ impl Broadcast {
         }
     }
 
-    pub(crate) fn send_children(&self, env: Envelope) {
+    // Sends the envelope to the child unless its mailbox is full,
+    // returning the envelope back otherwise.
+    pub(crate) fn try_send_child(
+        &self,
+        id: &BastionId,
+        envelope: Envelope,
+    ) -> Result<(), TrySendError<Box<Envelope>>> {
+        match self.children.get(id) {
+            Some(child) => child.try_send(envelope),
+            None => Err(TrySendError::Disconnected(Box::new(envelope))),
+        }
+    }
+
+    // Returns the number of children the envelope was sent to,
+    // skipping the ones whose mailbox is full.
+    pub(crate) fn try_send_children(&self, env: Envelope) -> usize {
+        let mut sent = 0;
+        for child in self.children.values() {
+            if let Some(env) = env.try_clone() {
+                if child.try_send(env).is_ok() {
+                    sent += 1;
+                }
+            }
+        }
+
+        sent
+    }
+
+    // Returns the number of children the envelope was sent to.
+    pub(crate) fn send_children(&self, env: Envelope) -> usize {
+        let mut sent = 0;
         for child in self.children.values() {
             // FIXME: Err(Error) if None
             if let Some(env) = env.try_clone() {
                 // FIXME: handle errors
-                child.unbounded_send(env).ok();
+                if child.unbounded_send(env).is_ok() {
+                    sent += 1;
+                }
             }
         }
+
+        sent
     }
 
     pub(crate) fn send_self(&self, env: Envelope) {
@@ -200,12 +338,8 @@ impl Broadcast {
 }
 
 impl Parent {
-    pub(crate) fn none() -> Self {
-        Parent::None
-    }
-
-    pub(crate) fn system() -> Self {
-        Parent::System
+    pub(crate) fn system(system: Arc<GlobalSystem>) -> Self {
+        Parent::System(system)
     }
 
     pub(crate) fn supervisor(supervisor: SupervisorRef) -> Self {
@@ -216,6 +350,16 @@ impl Parent {
         Parent::Children(children)
     }
 
+    // Returns the identifier of the supervisor or children group,
+    // or `None` for the system.
+    pub(crate) fn id(&self) -> Option<&BastionId> {
+        match self {
+            Parent::System(_) => None,
+            Parent::Supervisor(supervisor) => Some(supervisor.id()),
+            Parent::Children(children) => Some(children.id()),
+        }
+    }
+
     pub(crate) fn into_supervisor(self) -> Option<SupervisorRef> {
         if let Parent::Supervisor(supervisor) = self {
             Some(supervisor)
@@ -232,20 +376,126 @@ impl Parent {
         }
     }
 
-    fn send(&self, env: Envelope) -> Result<(), Envelope> {
+    fn send(&self, env: Envelope) -> Result<(), Box<Envelope>> {
         match self {
-            // FIXME
-            Parent::None => unimplemented!(),
-            Parent::System => SYSTEM
-                .sender()
-                .unbounded_send(env)
-                .map_err(|err| err.into_inner()),
+            Parent::System(system) => system.sender().unbounded_send(env),
             Parent::Supervisor(supervisor) => supervisor.send(env),
             Parent::Children(children) => children.send(env),
         }
     }
 }
 
+impl Sender {
+    pub(crate) fn is_closed(&self) -> bool {
+        self.inner.is_closed()
+    }
+
+    // Returns the envelope back (boxed, the error staying small) if
+    // the receiver stopped.
+    pub(crate) fn unbounded_send(&self, env: Envelope) -> Result<(), Box<Envelope>> {
+        // The envelope is counted before being sent so that the
+        // count can't underflow if it is received right away.
+        self.len.fetch_add(1, Ordering::SeqCst);
+        self.inner.unbounded_send(env).map_err(|err| {
+            self.len.fetch_sub(1, Ordering::SeqCst);
+            Box::new(err.into_inner())
+        })
+    }
+
+    // Sends the envelope unless the mailbox is bounded and full,
+    // which `unbounded_send` ignores.
+    pub(crate) fn try_send(&self, env: Envelope) -> Result<(), TrySendError<Box<Envelope>>> {
+        if let Some(capacity) = self.capacity {
+            if self.len() >= capacity {
+                return Err(TrySendError::Full(Box::new(env)));
+            }
+        }
+
+        self.unbounded_send(env).map_err(TrySendError::Disconnected)
+    }
+
+    // Returns the number of envelopes that were sent but not
+    // received yet.
+    pub(crate) fn len(&self) -> usize {
+        self.len.load(Ordering::SeqCst)
+    }
+}
+
+impl Receiver {
+    // Stops receiving envelopes, dropping those that weren't
+    // received yet.
+    pub(crate) fn close(&mut self) {
+        // FIXME: panics?
+        let mut inner = self.inbox.inner.lock().unwrap();
+        inner.recver.close();
+        let mut dropped = inner.peeked.take().map_or(0, |_| 1);
+        while inner.recver.try_recv().is_ok() {
+            dropped += 1;
+        }
+        self.inbox.len.fetch_sub(dropped, Ordering::SeqCst);
+    }
+}
+
+impl Inbox {
+    /// Takes the next envelope received by the mailbox if `take`
+    /// returns `true` for it, without waiting (and thus without
+    /// registering any waker). The envelopes that aren't taken are
+    /// left to be received next, in order.
+    ///
+    /// This returns `None` if no envelope was received, or if the
+    /// mailbox is being received from concurrently.
+    pub(crate) fn try_take<F>(&self, take: F) -> Option<Envelope>
+    where
+        F: FnOnce(&Envelope) -> bool,
+    {
+        let mut inner = self.inner.try_lock().ok()?;
+        if inner.peeked.is_none() {
+            inner.peeked = Some(inner.recver.try_recv().ok()?);
+            if let Some(waker) = inner.waker.take() {
+                waker.wake();
+            }
+        }
+
+        if !take(inner.peeked.as_ref()?) {
+            return None;
+        }
+
+        self.len.fetch_sub(1, Ordering::SeqCst);
+        inner.peeked.take()
+    }
+}
+
+impl Stream for Receiver {
+    type Item = Envelope;
+
+    fn poll_next(self: Pin<&mut Self>, ctx: &mut Context) -> Poll<Option<Self::Item>> {
+        let inbox = &self.get_mut().inbox;
+        // FIXME: panics?
+        let mut inner = inbox.inner.lock().unwrap();
+        let polled = match inner.peeked.take() {
+            Some(env) => Poll::Ready(Some(env)),
+            None => Pin::new(&mut inner.recver).poll_next(ctx),
+        };
+        match polled {
+            Poll::Ready(Some(_)) => {
+                inbox.len.fetch_sub(1, Ordering::SeqCst);
+            }
+            Poll::Pending => inner.waker = Some(ctx.waker().clone()),
+            Poll::Ready(None) => (),
+        }
+
+        polled
+    }
+}
+
+impl Drop for Receiver {
+    fn drop(&mut self) {
+        // The envelopes left are dropped along with the channel,
+        // so they shouldn't be counted anymore.
+        self.close();
+    }
+}
+
 impl Stream for Broadcast {
     type Item = Envelope;
 
@@ -260,7 +510,7 @@ mod tests {
     use crate::context::{BastionId, NIL_ID};
     use crate::envelope::Envelope;
     use crate::path::{BastionPath, BastionPathElement};
-    use futures::channel::mpsc;
+    use crate::system::SYSTEM;
     use futures::executor;
     use futures::poll;
     use futures::prelude::*;
@@ -269,12 +519,12 @@ mod tests {
 
     #[test]
     fn send_children() {
-        let mut parent = Broadcast::new_root(Parent::System);
+        let mut parent = Broadcast::new_root(SYSTEM.clone());
 
         let mut children = vec![];
         for _ in 0..4 {
             let child = Broadcast::new(
-                Parent::System,
+                Parent::system(SYSTEM.clone()),
                 BastionPathElement::Supervisor(BastionId::new()),
             );
             parent.register(&child);
@@ -283,8 +533,8 @@ mod tests {
 
         let msg = BastionMessage::start();
 
-        // need manual construction because SYSTEM is not running in this test
-        let (sender, _) = mpsc::unbounded();
+        // need manual construction because SYSTEM is not started in this test
+        let (sender, _) = super::channel();
         let env = Envelope::new(
             msg,
             Arc::new(
@@ -1,3 +1,4 @@
+use crate::context::BastionId;
 use std::fmt::{self, Debug, Formatter};
 use std::sync::Arc;
 
@@ -50,6 +51,9 @@ pub struct Callbacks {
     before_restart: Option<Arc<dyn Fn() + Send + Sync>>,
     after_restart: Option<Arc<dyn Fn() + Send + Sync>>,
     after_stop: Option<Arc<dyn Fn() + Send + Sync>>,
+    on_child_start: Option<Arc<dyn Fn(BastionId) + Send + Sync>>,
+    on_child_stop: Option<Arc<dyn Fn(BastionId) + Send + Sync>>,
+    on_child_fault: Option<Arc<dyn Fn(BastionId) + Send + Sync>>,
 }
 
 impl Callbacks {
@@ -287,6 +291,11 @@ impl Callbacks {
     ///     stopped or killed but did not have a callback defined using
     ///     [`with_before_restart`]
     ///
+    /// The parent of the supervised element only learns that it
+    /// stopped once this callback returned, so once
+    /// [`Bastion::block_until_stopped`] returned after stopping the
+    /// system, all of them did.
+    ///
     /// # Example
     ///
     /// ```rust
@@ -328,6 +337,7 @@ impl Callbacks {
     /// [`Supervisor`]: supervisor/struct.Supervisor.html
     /// [`Children`]: children/struct.Children.html
     /// [`with_before_restart`]: #method.with_before_restart
+    /// [`Bastion::block_until_stopped`]: struct.Bastion.html#method.block_until_stopped
     pub fn with_after_stop<C>(mut self, after_stop: C) -> Self
     where
         C: Fn() + Send + Sync + 'static,
@@ -337,6 +347,116 @@ impl Callbacks {
         self
     }
 
+    /// Sets the method that will get called with the identifier of
+    /// a children group or supervisor each time the supervisor
+    /// using this callback deploys it.
+    ///
+    /// This callback only gets called for supervisors (see
+    /// [`Supervisor::with_callbacks`]).
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use bastion::prelude::*;
+    /// #
+    /// # Bastion::init();
+    /// #
+    /// Bastion::supervisor(|supervisor| {
+    ///     let callbacks = Callbacks::new()
+    ///         .with_on_child_start(|id: BastionId| println!("Supervised({}) deployed.", id));
+    ///
+    ///     supervisor.with_callbacks(callbacks)
+    /// }).expect("Couldn't create the supervisor.");
+    /// #
+    /// # Bastion::start();
+    /// # Bastion::stop();
+    /// # Bastion::block_until_stopped();
+    /// ```
+    ///
+    /// [`Supervisor::with_callbacks`]: supervisor/struct.Supervisor.html#method.with_callbacks
+    pub fn with_on_child_start<C>(mut self, on_child_start: C) -> Self
+    where
+        C: Fn(BastionId) + Send + Sync + 'static,
+    {
+        let on_child_start = Arc::new(on_child_start);
+        self.on_child_start = Some(on_child_start);
+        self
+    }
+
+    /// Sets the method that will get called with the identifier of
+    /// a children group or supervisor each time it stops while
+    /// being supervised by the supervisor using this callback.
+    ///
+    /// This callback only gets called for supervisors (see
+    /// [`Supervisor::with_callbacks`]).
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use bastion::prelude::*;
+    /// #
+    /// # Bastion::init();
+    /// #
+    /// Bastion::supervisor(|supervisor| {
+    ///     let callbacks = Callbacks::new()
+    ///         .with_on_child_stop(|id: BastionId| println!("Supervised({}) stopped.", id));
+    ///
+    ///     supervisor.with_callbacks(callbacks)
+    /// }).expect("Couldn't create the supervisor.");
+    /// #
+    /// # Bastion::start();
+    /// # Bastion::stop();
+    /// # Bastion::block_until_stopped();
+    /// ```
+    ///
+    /// [`Supervisor::with_callbacks`]: supervisor/struct.Supervisor.html#method.with_callbacks
+    pub fn with_on_child_stop<C>(mut self, on_child_stop: C) -> Self
+    where
+        C: Fn(BastionId) + Send + Sync + 'static,
+    {
+        let on_child_stop = Arc::new(on_child_stop);
+        self.on_child_stop = Some(on_child_stop);
+        self
+    }
+
+    /// Sets the method that will get called with the identifier of
+    /// an element of a children group (when it faulted and needs
+    /// to be recovered), of a children group or of a supervisor (when
+    /// it faulted because it couldn't recover) each time it faults
+    /// while being supervised by the supervisor using this callback.
+    ///
+    /// This callback only gets called for supervisors (see
+    /// [`Supervisor::with_callbacks`]).
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use bastion::prelude::*;
+    /// #
+    /// # Bastion::init();
+    /// #
+    /// Bastion::supervisor(|supervisor| {
+    ///     let callbacks = Callbacks::new()
+    ///         .with_on_child_fault(|id: BastionId| println!("Supervised({}) faulted.", id));
+    ///
+    ///     supervisor.with_callbacks(callbacks)
+    /// }).expect("Couldn't create the supervisor.");
+    /// #
+    /// # Bastion::start();
+    /// # Bastion::stop();
+    /// # Bastion::block_until_stopped();
+    /// ```
+    ///
+    /// [`Supervisor::with_callbacks`]: supervisor/struct.Supervisor.html#method.with_callbacks
+    pub fn with_on_child_fault<C>(mut self, on_child_fault: C) -> Self
+    where
+        C: Fn(BastionId) + Send + Sync + 'static,
+    {
+        let on_child_fault = Arc::new(on_child_fault);
+        self.on_child_fault = Some(on_child_fault);
+        self
+    }
+
     /// Returns whether a callback was defined using [`with_before_start`].
     ///
     /// # Example
@@ -436,6 +556,24 @@ impl Callbacks {
             after_stop()
         }
     }
+
+    pub(crate) fn on_child_start(&self, id: &BastionId) {
+        if let Some(on_child_start) = &self.on_child_start {
+            on_child_start(id.clone())
+        }
+    }
+
+    pub(crate) fn on_child_stop(&self, id: &BastionId) {
+        if let Some(on_child_stop) = &self.on_child_stop {
+            on_child_stop(id.clone())
+        }
+    }
+
+    pub(crate) fn on_child_fault(&self, id: &BastionId) {
+        if let Some(on_child_fault) = &self.on_child_fault {
+            on_child_fault(id.clone())
+        }
+    }
 }
 
 impl Debug for Callbacks {
@@ -445,6 +583,9 @@ impl Debug for Callbacks {
             .field("before_restart", &self.before_start.is_some())
             .field("after_restart", &self.before_start.is_some())
             .field("after_stop", &self.before_start.is_some())
+            .field("on_child_start", &self.on_child_start.is_some())
+            .field("on_child_stop", &self.on_child_stop.is_some())
+            .field("on_child_fault", &self.on_child_fault.is_some())
             .finish()
     }
 }
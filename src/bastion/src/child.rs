@@ -1,36 +1,65 @@
 //!
 //! Child is a element of Children group executing user-defined computation
-use crate::broadcast::Broadcast;
+use crate::broadcast::{Broadcast, Inbox};
 use crate::callbacks::{CallbackType, Callbacks};
 use crate::child_ref::ChildRef;
-use crate::context::{BastionContext, BastionId, ContextState};
+use crate::children::{FaultPolicy, SpawnStrategy};
+use crate::context::{BastionContext, BastionId, ContextState, TerminationReason, NIL_ID};
 use crate::envelope::Envelope;
+use crate::executor;
+use crate::interceptor::{InterceptCtx, InterceptDecision, Interceptors};
+use crate::logger;
 use crate::message::BastionMessage;
-use crate::system::SYSTEM;
+use crate::panic_handler;
+use crate::scheduler::Ticker;
+use crate::supervisor::SupervisionStrategy;
+use crate::system::{GlobalSystem, RunningGuard};
 use anyhow::Result as AnyResult;
 use async_mutex::Mutex;
-use bastion_executor::pool;
 use futures::pending;
 use futures::poll;
 use futures::prelude::*;
+use futures_timer::Delay;
 use lightproc::prelude::*;
 use lightproc::proc_state::EmptyProcState;
 use std::fmt::{self, Debug, Formatter};
 use std::future::Future;
 use std::pin::Pin;
+use std::sync::atomic::Ordering;
 use std::sync::Arc;
 use std::task::{Context, Poll};
+use std::time::{Duration, Instant};
 use tracing::{debug, error, trace, warn};
 
 pub(crate) struct Init(pub(crate) Box<dyn Fn(BastionContext) -> Exec + Send>);
-pub(crate) struct Exec(pub(crate) Pin<Box<dyn Future<Output = Result<(), ()>> + Send>>);
+pub(crate) struct Exec(pub(crate) Pin<Box<dyn Future<Output = FaultPolicy> + Send>>);
+
+#[derive(Debug, Clone)]
+// Places the messages received by an element into its mailbox,
+// shared with its context so that it can do so while the element's
+// future doesn't yield (see `BastionContext::try_recv_sync`).
+pub(crate) struct Mailbox {
+    id: BastionId,
+    inbox: Inbox,
+    // The interceptors deciding what to do with the messages.
+    interceptors: Interceptors,
+    system: Arc<GlobalSystem>,
+    // Whether the delivered messages are counted in the system's
+    // stats.
+    counted: bool,
+}
 
 #[derive(Debug)]
 pub(crate) struct Child {
     bcast: Broadcast,
+    // The pid set on the child's process.
+    pid: usize,
     // The callbacks called at the group's different lifecycle
     // events.
     callbacks: Callbacks,
+    // Places the messages received by this child into its
+    // mailbox.
+    mailbox: Mailbox,
     // The future that this child is executing.
     exec: Exec,
     // A lock behind which is the child's context state.
@@ -44,17 +73,27 @@ pub(crate) struct Child {
     pre_start_msgs: Vec<Envelope>,
     // A shortcut for accessing to this actor by others.
     child_ref: ChildRef,
+    // Periodically sends `Tick` messages to this child once
+    // it started.
+    ticker: Arc<Ticker>,
     started: bool,
+    // Whether the child's future shouldn't be polled (and thus
+    // receive messages) until the child gets resumed.
+    paused: bool,
+    // Counts this child as running in its children group until
+    // its future gets dropped.
+    _running: RunningGuard,
 }
 
 impl Init {
-    pub(crate) fn new<C, F>(init: C) -> Self
+    pub(crate) fn new<C, F, R>(init: C) -> Self
     where
         C: Fn(BastionContext) -> F + Send + 'static,
-        F: Future<Output = Result<(), ()>> + Send + 'static,
+        F: Future<Output = R> + Send + 'static,
+        R: Into<FaultPolicy> + 'static,
     {
         let init = Box::new(move |ctx: BastionContext| {
-            let fut = init(ctx);
+            let fut = init(ctx).map(Into::into);
             let exec = Box::pin(fut);
 
             Exec(exec)
@@ -64,26 +103,113 @@ impl Init {
     }
 }
 
+impl Mailbox {
+    pub(crate) fn new(bcast: &Broadcast, interceptors: Interceptors) -> Self {
+        // The messages delivered to the dead letters' element
+        // aren't counted.
+        let counted = bcast
+            .parent()
+            .clone()
+            .into_children()
+            .map(|parent| parent.id() != &NIL_ID)
+            .unwrap_or(true);
+
+        Mailbox {
+            id: bcast.id().clone(),
+            inbox: bcast.inbox().clone(),
+            interceptors,
+            system: bcast.system().clone(),
+            counted,
+        }
+    }
+
+    pub(crate) fn inbox(&self) -> &Inbox {
+        &self.inbox
+    }
+
+    /// Places the message contained in `env` into the element's
+    /// mailbox, unless its group's interceptors decide otherwise.
+    pub(crate) fn deliver(&self, state: &mut ContextState, env: Envelope) {
+        let (msg, sign, trace, ack, deadline) = match env {
+            Envelope {
+                msg: BastionMessage::Message(msg),
+                sign,
+                trace,
+                ack,
+                deadline,
+            } => (msg, sign, trace, ack, deadline),
+            _ => unreachable!(),
+        };
+        match trace {
+            Some(trace) => debug!(
+                "Child({}): Received a message (trace={}): {:?}",
+                self.id, trace, msg
+            ),
+            None => debug!("Child({}): Received a message: {:?}", self.id, msg),
+        }
+
+        let ctx = InterceptCtx::new(&self.id, &sign, trace);
+        match self.interceptors.intercept(&msg, &ctx) {
+            InterceptDecision::Deliver => (),
+            InterceptDecision::Drop => {
+                debug!("Child({}): Dropping intercepted message.", self.id);
+                return;
+            }
+            InterceptDecision::DeadLetter => {
+                debug!(
+                    "Child({}): Sending intercepted message to the dead letters.",
+                    self.id
+                );
+                let msg = BastionMessage::Message(msg);
+                let env = Envelope::new_with_sign(msg, sign);
+                self.system.dead_letters().sender().unbounded_send(env).ok();
+                return;
+            }
+        }
+
+        state.push_message(msg, sign, trace, deadline);
+        if self.counted {
+            self.system.delivered().fetch_add(1, Ordering::SeqCst);
+        }
+
+        if let Some(ack) = ack {
+            trace!("Child({}): Acknowledging the message.", self.id);
+            ack.send(()).ok();
+        }
+    }
+}
+
 impl Child {
+    #[allow(clippy::too_many_arguments)]
     pub(crate) fn new(
         exec: Exec,
+        pid: usize,
         callbacks: Callbacks,
+        mailbox: Mailbox,
         bcast: Broadcast,
         state: Arc<Mutex<Pin<Box<ContextState>>>>,
         child_ref: ChildRef,
+        ticker: Arc<Ticker>,
+        running: RunningGuard,
     ) -> Self {
         debug!("Child({}): Initializing.", bcast.id());
         let pre_start_msgs = Vec::new();
         let started = false;
+        let paused = false;
 
         Child {
             bcast,
+            pid,
             callbacks,
+            mailbox,
             exec,
             state,
             pre_start_msgs,
             child_ref,
+            ticker,
             started,
+            paused,
+            _running: running,
         }
     }
 
@@ -97,44 +223,157 @@ impl Child {
 
         let parent_inner = self.bcast.parent().clone().into_children();
         let child_ref_inner = self.child_ref.clone();
-
-        // FIXME: with_pid
-        ProcStack::default().with_after_panic(move |_state: &mut EmptyProcState| {
-            warn!("Child({}): Panicked.", id);
-
-            if let Some(parent) = &parent_inner {
-                let used_dispatchers = parent.dispatchers();
-                let global_dispatcher = SYSTEM.dispatcher();
-                global_dispatcher.remove(used_dispatchers, &child_ref_inner);
-            }
-
-            let id = id.clone();
-            let msg = BastionMessage::restart_required(id, parent.id().clone());
-            let env = Envelope::new(msg, path.clone(), sender.clone());
-            // TODO: handle errors
-            parent.send(env).ok();
-        })
+        let ticker = self.ticker.clone();
+        let state = self.state.clone();
+        let system = self.bcast.system().clone();
+
+        ProcStack::default().with_pid(self.pid).with_after_panic(
+            move |_state: &mut EmptyProcState| {
+                warn!("Child({}): Panicked.", id);
+
+                if let Some(parent) = &parent_inner {
+                    let used_dispatchers = parent.dispatchers();
+                    let global_dispatcher = system.dispatcher();
+                    global_dispatcher.remove(used_dispatchers, &child_ref_inner);
+                }
+                ticker.stop();
+                if let Some(mut state) = state.try_lock() {
+                    state.dead_letter_stash();
+                }
+                system.topics().unsubscribe_all(&id);
+                system.links().notify_down(&id, TerminationReason::Panicked);
+                logger::with_logger(|logger| logger.log_fault(&id));
+
+                let id = id.clone();
+                let msg = BastionMessage::restart_required(id, parent.id().clone(), None, true);
+                let env = Envelope::new(msg, path.clone(), sender.clone());
+                // TODO: handle errors
+                parent.send(env).ok();
+            },
+        )
     }
 
     pub(crate) fn id(&self) -> &BastionId {
         self.bcast.id()
     }
 
-    fn stopped(&mut self) {
+    // Returns whether this is the dead letters' element, which
+    // isn't counted in the system's stats.
+    fn is_dead_letters(&self) -> bool {
+        self.bcast
+            .parent()
+            .clone()
+            .into_children()
+            .map(|parent| parent.id() == &NIL_ID)
+            .unwrap_or(false)
+    }
+
+    fn stopped(&mut self, reason: TerminationReason) {
         debug!("Child({}): Stopped.", self.id());
-        self.remove_from_dispatchers();
+        self.release(reason);
         self.bcast.stopped();
     }
 
-    fn faulted(&mut self) {
+    // Stops the element once it waited for a message for longer
+    // than its group's idle timeout, which isn't a fault.
+    fn idle_stopped(&mut self) {
+        debug!("Child({}): Stopped after being idle.", self.id());
+        self.callbacks.after_stop();
+        self.release(TerminationReason::Stopped);
+        self.bcast.idle_stopped();
+    }
+
+    // Releases what the element holds once it stopped.
+    fn release(&mut self, reason: TerminationReason) {
+        // A panic caught by the child's future isn't taken by its
+        // supervisor.
+        panic_handler::take_panic(self.id());
+        self.ticker.stop();
+        self.dead_letter_stash();
+        self.remove_from_dispatchers();
+        self.bcast.system().topics().unsubscribe_all(self.id());
+        self.bcast.system().links().notify_down(self.id(), reason);
+        logger::with_logger(|logger| logger.log_stop(self.id()));
+    }
+
+    // Reports that the element took longer than its group's
+    // warning threshold to handle a message, once per message.
+    fn slow_processing(&self, elapsed: Duration, msg_type: &str) {
+        warn!(
+            "Child({}): Handling a message of type {} for {:?}.",
+            self.id(),
+            msg_type,
+            elapsed
+        );
+        lifecycle_event!(
+            "element_slow",
+            element = %self.id(),
+            elapsed_ms = elapsed.as_millis() as u64,
+            message_type = msg_type,
+        );
+        logger::with_logger(|logger| logger.log_slow_processing(self.id(), elapsed, msg_type));
+    }
+
+    fn faulted(&mut self, strategy: Option<SupervisionStrategy>) {
         debug!("Child({}): Faulted.", self.id());
+        self.ticker.stop();
+        self.dead_letter_stash();
+        self.remove_from_dispatchers();
+        self.bcast.system().topics().unsubscribe_all(self.id());
+        self.bcast
+            .system()
+            .links()
+            .notify_down(self.id(), TerminationReason::Faulted);
+        logger::with_logger(|logger| logger.log_fault(self.id()));
+
+        let parent = self.bcast.parent().clone().into_children().unwrap();
+        let path = self.bcast.path().clone();
+        let sender = self.bcast.sender().clone();
+
+        let msg = BastionMessage::restart_required(
+            self.id().clone(),
+            parent.id().clone(),
+            strategy,
+            false,
+        );
+        let env = Envelope::new(msg, path, sender);
+        // TODO: handle errors
+        parent.send(env).ok();
+    }
+
+    // Sends the messages stashed by the child's future to the dead
+    // letters once it isn't polled anymore (and thus can't be
+    // holding the state's lock).
+    fn dead_letter_stash(&self) {
+        if let Some(mut state) = self.state.try_lock() {
+            state.dead_letter_stash();
+        }
+    }
+
+    // Asks the supervisor to launch this child again with the same
+    // state, following its restart strategy (to not relaunch it in
+    // a loop) but without reporting it as faulted.
+    async fn relaunch(&mut self) {
+        debug!("Child({}): Relaunching.", self.id());
+        self.ticker.stop();
         self.remove_from_dispatchers();
+        self.bcast.system().topics().unsubscribe_all(self.id());
 
         let parent = self.bcast.parent().clone().into_children().unwrap();
         let path = self.bcast.path().clone();
         let sender = self.bcast.sender().clone();
 
-        let msg = BastionMessage::restart_required(self.id().clone(), parent.id().clone());
+        let state = self.state.clone();
+        let mut guard = state.lock().await;
+        guard.dead_letter_stash();
+        guard.reset();
+        drop(guard);
+        let msg = BastionMessage::restart_required(
+            self.id().clone(),
+            parent.id().clone(),
+            Some(SupervisionStrategy::OneForOne),
+            false,
+        );
         let env = Envelope::new(msg, path, sender);
         // TODO: handle errors
         parent.send(env).ok();
@@ -150,18 +389,46 @@ impl Child {
                 msg: BastionMessage::Stop,
                 ..
             } => {
-                self.stopped();
+                {
+                    let mut state = self.state.lock().await;
+                    state.stop();
+                    state.refill_budget();
+                }
+                // Giving the future a last chance to notice that the
+                // child was asked to stop (e.g. to let a stream of its
+                // messages end)...
+                let guard = panic_handler::enter_child(self.bcast.id());
+                let _ = poll!(&mut self.exec);
+                drop(guard);
+
+                // The parent is only told that the child stopped
+                // once `after_stop` returned.
                 self.callbacks.after_stop();
+                self.stopped(TerminationReason::Stopped);
                 return Err(());
             }
             Envelope {
                 msg: BastionMessage::Kill,
                 ..
             } => {
-                self.stopped();
+                self.stopped(TerminationReason::Killed);
                 self.callbacks.before_restart();
                 return Err(());
             }
+            Envelope {
+                msg: BastionMessage::Pause,
+                ..
+            } => {
+                debug!("Child({}): Pausing.", self.id());
+                self.paused = true;
+            }
+            Envelope {
+                msg: BastionMessage::Resume,
+                ..
+            } => {
+                debug!("Child({}): Resuming.", self.id());
+                self.paused = false;
+            }
             // FIXME
             Envelope {
                 msg: BastionMessage::Deploy(_),
@@ -186,13 +453,48 @@ impl Child {
                 ..
             } => unreachable!(),
             Envelope {
-                msg: BastionMessage::Message(msg),
-                sign,
+                msg: BastionMessage::MailboxLens { .. },
+                ..
+            } => unreachable!(),
+            Envelope {
+                msg: BastionMessage::HealthCheck { .. },
+                ..
+            } => unreachable!(),
+            Envelope {
+                msg: BastionMessage::Drain { .. },
+                ..
+            } => unreachable!(),
+            Envelope {
+                msg: BastionMessage::Ping { sender },
+                ..
+            } => {
+                trace!("Child({}): Answering a ping.", self.id());
+                // The sender might have stopped waiting for it.
+                sender.send(()).ok();
+            }
+            Envelope {
+                msg: BastionMessage::Emit(_),
+                ..
+            } => unreachable!(),
+            Envelope {
+                msg: BastionMessage::Accumulator { .. },
+                ..
+            } => unreachable!(),
+            Envelope {
+                msg: BastionMessage::AddChild { .. },
+                ..
+            } => unreachable!(),
+            Envelope {
+                msg: BastionMessage::CountedMessage { .. },
+                ..
+            } => unreachable!(),
+            env @ Envelope {
+                msg: BastionMessage::Message(_),
+                ..
             } => {
-                debug!("Child({}): Received a message: {:?}", self.id(), msg);
                 let state = self.state.clone();
                 let mut guard = state.lock().await;
-                guard.push_message(msg, sign);
+                self.mailbox.deliver(&mut guard, env);
             }
             Envelope {
                 msg: BastionMessage::RestartRequired { .. },
@@ -214,6 +516,30 @@ impl Child {
                 msg: BastionMessage::DropChild { .. },
                 ..
             } => unreachable!(),
+            Envelope {
+                msg: BastionMessage::RestartChild { .. },
+                ..
+            } => unreachable!(),
+            Envelope {
+                msg: BastionMessage::ListStopped { .. },
+                ..
+            } => unreachable!(),
+            Envelope {
+                msg: BastionMessage::Inspect { .. },
+                ..
+            } => unreachable!(),
+            Envelope {
+                msg: BastionMessage::DumpState { .. },
+                ..
+            } => unreachable!(),
+            Envelope {
+                msg: BastionMessage::Metrics { .. },
+                ..
+            } => unreachable!(),
+            Envelope {
+                msg: BastionMessage::ResetChild { .. },
+                ..
+            } => unreachable!(),
             Envelope {
                 msg: BastionMessage::SetState { state },
                 ..
@@ -226,11 +552,35 @@ impl Child {
                 msg: BastionMessage::Stopped { .. },
                 ..
             } => unimplemented!(),
+            Envelope {
+                msg: BastionMessage::IdleStop { .. },
+                ..
+            } => unreachable!(),
             // FIXME
             Envelope {
                 msg: BastionMessage::Faulted { .. },
                 ..
             } => unimplemented!(),
+            Envelope {
+                msg: BastionMessage::Adopt { .. },
+                ..
+            } => unreachable!(),
+            Envelope {
+                msg: BastionMessage::SetParent { .. },
+                ..
+            } => unreachable!(),
+            Envelope {
+                msg: BastionMessage::Migrate { .. },
+                ..
+            } => unreachable!(),
+            Envelope {
+                msg: BastionMessage::Relaunch { .. },
+                ..
+            } => unreachable!(),
+            Envelope {
+                msg: BastionMessage::CapacityExceeded { .. },
+                ..
+            } => unreachable!(),
         }
 
         Ok(())
@@ -245,6 +595,8 @@ impl Child {
         debug!("Child({}): Starting.", self.id());
         self.callbacks.before_start();
         self.started = true;
+        self.ticker.start(self.child_ref.clone());
+        logger::with_logger(|logger| logger.log_start(self.id()));
 
         let msgs = self.pre_start_msgs.drain(..).collect::<Vec<_>>();
         self.pre_start_msgs.shrink_to_fit();
@@ -274,11 +626,26 @@ impl Child {
 
     async fn run(mut self) {
         debug!("Child({}): Launched.", self.id());
+        // The dead letters' element isn't counted.
+        let _running = if self.is_dead_letters() {
+            None
+        } else {
+            Some(RunningGuard::new(self.bcast.system().actors().clone()))
+        };
+
         if let Err(e) = self.register_in_dispatchers() {
             error!("couldn't add actor to the registry: {}", e);
             return;
         };
 
+        // The number of messages handled since the element last
+        // yielded, which it does once it handled as many as its
+        // budget allows, the ones left staying in its mailbox.
+        let budget = self.state.lock().await.poll_budget();
+        let mut handled = 0;
+        // When the message the element is handling times out, along
+        // with the delay waking it up then.
+        let mut timeout: Option<(Instant, Delay)> = None;
         loop {
             match poll!(&mut self.bcast.next()) {
                 // TODO: Err if started == true?
@@ -301,7 +668,10 @@ impl Child {
                     );
                     self.pre_start_msgs.push(msg);
 
-                    continue;
+                    handled += 1;
+                    if handled < budget {
+                        continue;
+                    }
                 }
                 Poll::Ready(Some(msg)) => {
                     trace!(
@@ -314,7 +684,10 @@ impl Child {
                         return;
                     }
 
-                    continue;
+                    handled += 1;
+                    if handled < budget {
+                        continue;
+                    }
                 }
                 // NOTE: because `Broadcast` always holds both a `Sender` and
                 //      `Receiver` of the same channel, this would only be
@@ -323,34 +696,122 @@ impl Child {
                 Poll::Pending => (),
             }
 
-            if !self.started {
-                pending!();
+            // The messages keep being pushed to the mailbox while
+            // paused, but the future isn't polled to receive them.
+            if !self.started || self.paused {
+                if handled >= budget {
+                    executor::yield_now().await;
+                } else {
+                    pending!();
+                }
+                handled = 0;
 
                 continue;
             }
 
-            match poll!(&mut self.exec) {
-                Poll::Ready(Ok(())) => {
+            let guard = panic_handler::enter_child(self.bcast.id());
+            let polled = poll!(&mut self.exec);
+            drop(guard);
+
+            match polled {
+                Poll::Ready(FaultPolicy::StopChild) => {
                     debug!(
                         "Child({}): The future finished executing successfully.",
                         self.id()
                     );
-                    return self.stopped();
+                    return self.stopped(TerminationReason::Stopped);
                 }
-                Poll::Ready(Err(())) => {
+                Poll::Ready(FaultPolicy::EscalateToSupervisor) => {
                     warn!("Child({}): The future returned an error.", self.id());
-                    return self.faulted();
+                    return self.faulted(None);
+                }
+                Poll::Ready(FaultPolicy::RestartChild) => {
+                    warn!(
+                        "Child({}): The future returned an error, asking to be restarted.",
+                        self.id()
+                    );
+                    return self.faulted(Some(SupervisionStrategy::OneForOne));
+                }
+                Poll::Ready(FaultPolicy::Ignore) => {
+                    warn!(
+                        "Child({}): The future returned an error, which is ignored.",
+                        self.id()
+                    );
+                    return self.relaunch().await;
                 }
                 Poll::Pending => (),
             }
 
-            pending!();
+            // The future might have stopped receiving messages
+            // because it used its budget too.
+            let (exhausted, deadline, warning, idle, stall) = {
+                let mut state = self.state.lock().await;
+                let stall = state.take_stall();
+                (
+                    state.refill_budget(),
+                    state.handling_deadline(),
+                    state.warning_deadline(),
+                    state.idle_deadline(),
+                    stall,
+                )
+            };
+            if let Some((elapsed, msg_type)) = stall {
+                self.slow_processing(elapsed, msg_type);
+            }
+
+            // The element faults if it is still handling a message
+            // once its group's exec timeout elapsed, getting woken up
+            // when it does (or when it should be warned about for
+            // taking too long) otherwise.
+            if matches!(deadline, Some(deadline) if deadline <= Instant::now()) {
+                warn!("Child({}): Timed out handling a message.", self.id());
+                return self.faulted(None);
+            }
+
+            // It also stops once it waited for a message for longer
+            // than its group's idle timeout.
+            if matches!(idle, Some(idle) if idle <= Instant::now()) {
+                return self.idle_stopped();
+            }
+
+            match deadline.into_iter().chain(warning).chain(idle).min() {
+                Some(deadline) => {
+                    let delay = match &mut timeout {
+                        Some((timeout, delay)) if *timeout == deadline => delay,
+                        _ => {
+                            let delay =
+                                Delay::new(deadline.saturating_duration_since(Instant::now()));
+                            &mut timeout.insert((deadline, delay)).1
+                        }
+                    };
+                    let _ = poll!(delay);
+                }
+                None => timeout = None,
+            }
+
+            if handled >= budget || exhausted {
+                trace!("Child({}): Yielding.", self.id());
+                executor::yield_now().await;
+            } else {
+                pending!();
+            }
+            handled = 0;
         }
     }
 
-    pub(crate) fn launch(self) -> RecoverableHandle<()> {
+    pub(crate) fn launch(self, strategy: SpawnStrategy) -> RecoverableHandle<()> {
         let stack = self.stack();
-        pool::spawn(self.run(), stack)
+        match strategy {
+            SpawnStrategy::DefaultPool => executor::spawn_proc(self.run(), stack),
+            SpawnStrategy::DedicatedThread => {
+                debug!("Child({}): Spawning on a dedicated thread.", self.id());
+                executor::spawn_proc_dedicated(self.run(), stack)
+            }
+            SpawnStrategy::PinnedThread(thread) => {
+                debug!("Child({}): Spawning on thread {}.", self.id(), thread);
+                executor::spawn_proc_pinned(self.run(), stack, thread)
+            }
+        }
     }
 
     /// Adds the actor into each registry declared in the parent node.
@@ -359,7 +820,7 @@ impl Child {
             let child_ref = self.child_ref.clone();
             let used_dispatchers = parent.dispatchers();
 
-            let global_dispatcher = SYSTEM.dispatcher();
+            let global_dispatcher = self.bcast.system().dispatcher();
             // FIXME: Pass the module name explicitly?
             let module_name = module_path!().to_string();
             global_dispatcher.register(used_dispatchers, &child_ref, module_name)?;
@@ -373,14 +834,14 @@ impl Child {
             let child_ref = self.child_ref.clone();
             let used_dispatchers = parent.dispatchers();
 
-            let global_dispatcher = SYSTEM.dispatcher();
+            let global_dispatcher = self.bcast.system().dispatcher();
             global_dispatcher.remove(used_dispatchers, &child_ref);
         }
     }
 }
 
 impl Future for Exec {
-    type Output = Result<(), ()>;
+    type Output = FaultPolicy;
 
     fn poll(self: Pin<&mut Self>, ctx: &mut Context) -> Poll<Self::Output> {
         Pin::new(&mut self.get_mut().0).poll(ctx)
@@ -389,7 +850,7 @@ impl Future for Exec {
 
 impl Default for Init {
     fn default() -> Self {
-        Init::new(|_| async { Ok(()) })
+        Init::new(|_| async { FaultPolicy::StopChild })
     }
 }
 
@@ -5,10 +5,14 @@ use crate::context::BastionId;
 use crate::envelope::{Envelope, RefAddr};
 use crate::message::{Answer, BastionMessage, Message};
 use crate::path::BastionPath;
+use crate::runtime::RuntimeId;
+use crate::scheduler::ScheduledSend;
+use crate::system::GlobalSystem;
 use std::cmp::{Eq, PartialEq};
 use std::fmt::Debug;
 use std::hash::{Hash, Hasher};
 use std::sync::Arc;
+use std::time::Duration;
 use tracing::{debug, trace};
 
 #[derive(Debug, Clone)]
@@ -19,6 +23,7 @@ pub struct ChildRef {
     sender: Sender,
     name: String,
     path: Arc<BastionPath>,
+    system: Arc<GlobalSystem>,
 }
 
 impl ChildRef {
@@ -27,12 +32,14 @@ impl ChildRef {
         sender: Sender,
         name: String,
         path: Arc<BastionPath>,
+        system: Arc<GlobalSystem>,
     ) -> ChildRef {
         ChildRef {
             id,
             sender,
             name,
             path,
+            system,
         }
     }
 
@@ -67,6 +74,36 @@ impl ChildRef {
         &self.id
     }
 
+    /// Returns the identifier of the [`BastionRuntime`] the element
+    /// this `ChildRef` is referencing belongs to.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use bastion::prelude::*;
+    /// #
+    /// let runtime = BastionRuntime::new(Config::new());
+    ///
+    /// runtime.children(|children| {
+    ///     children.with_exec(|ctx: BastionContext| {
+    ///         async move {
+    ///             let runtime_id: RuntimeId = ctx.current().runtime_id();
+    ///             // ...
+    ///             # Ok(())
+    ///         }
+    ///     })
+    /// }).expect("Couldn't create the children group.");
+    /// #
+    /// # runtime.start();
+    /// # runtime.stop();
+    /// # runtime.block_until_stopped();
+    /// ```
+    ///
+    /// [`BastionRuntime`]: ../struct.BastionRuntime.html
+    pub fn runtime_id(&self) -> RuntimeId {
+        self.system.id()
+    }
+
     /// Sends a message to the child this `ChildRef` is referencing.
     /// This message is intended to be used outside of Bastion context when
     /// there is no way for receiver to identify message sender
@@ -121,7 +158,7 @@ impl ChildRef {
     pub fn tell_anonymously<M: Message>(&self, msg: M) -> Result<(), M> {
         debug!("ChildRef({}): Telling message: {:?}", self.id(), msg);
         let msg = BastionMessage::tell(msg);
-        let env = Envelope::from_dead_letters(msg);
+        let env = Envelope::from_dead_letters(msg, &self.system);
         // FIXME: panics?
         self.send(env).map_err(|env| env.into_msg().unwrap())
     }
@@ -207,13 +244,75 @@ impl ChildRef {
     pub fn ask_anonymously<M: Message>(&self, msg: M) -> Result<Answer, M> {
         debug!("ChildRef({}): Asking message: {:?}", self.id(), msg);
         let (msg, answer) = BastionMessage::ask(msg);
-        let env = Envelope::from_dead_letters(msg);
+        let env = Envelope::from_dead_letters(msg, &self.system);
         // FIXME: panics?
         self.send(env).map_err(|env| env.into_msg().unwrap())?;
 
         Ok(answer)
     }
 
+    /// Sends a message to the child this `ChildRef` is referencing
+    /// once the given duration elapsed, as if it was "told" using
+    /// [`tell_anonymously`].
+    ///
+    /// If the child stopped (or was restarted, which makes this
+    /// `ChildRef` outdated) before the message could be sent, it
+    /// is sent to the dead letters instead. The delivery can be
+    /// cancelled using the returned [`ScheduledSend`] and is
+    /// automatically cancelled when the system stops.
+    ///
+    /// # Arguments
+    ///
+    /// * `msg` - The message to send.
+    /// * `delay` - How long to wait before sending the message.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use bastion::prelude::*;
+    /// # use std::time::Duration;
+    /// #
+    /// # Bastion::init();
+    /// #
+    /// # let children_ref = Bastion::children(|children| children).unwrap();
+    /// # let child_ref = &children_ref.elems()[0];
+    /// let scheduled = child_ref.send_after("Timeout.", Duration::from_secs(5));
+    /// // The work finished in time, the message won't be delivered...
+    /// scheduled.cancel();
+    /// #
+    /// # Bastion::start();
+    /// # Bastion::stop();
+    /// # Bastion::block_until_stopped();
+    /// ```
+    ///
+    /// [`tell_anonymously`]: #method.tell_anonymously
+    /// [`ScheduledSend`]: ../scheduler/struct.ScheduledSend.html
+    pub fn send_after<M: Message>(&self, msg: M, delay: Duration) -> ScheduledSend {
+        debug!(
+            "ChildRef({}): Scheduling message in {:?}: {:?}",
+            self.id(),
+            delay,
+            msg
+        );
+        let child = self.clone();
+        self.system.timers().schedule(delay, move || {
+            let msg = BastionMessage::tell(msg);
+            let env = Envelope::from_dead_letters(msg, &child.system);
+            if let Err(env) = child.send(env) {
+                trace!(
+                    "ChildRef({}): Stopped, sending the scheduled message to the dead letters.",
+                    child.id()
+                );
+                child
+                    .system
+                    .dead_letters()
+                    .sender()
+                    .unbounded_send(*env)
+                    .ok();
+            }
+        })
+    }
+
     /// Sends a message to the child this `ChildRef` is referencing
     /// to tell it to stop its execution.
     ///
@@ -238,7 +337,7 @@ impl ChildRef {
     pub fn stop(&self) -> Result<(), ()> {
         debug!("ChildRef({}): Stopping.", self.id);
         let msg = BastionMessage::stop();
-        let env = Envelope::from_dead_letters(msg);
+        let env = Envelope::from_dead_letters(msg, &self.system);
         self.send(env).map_err(|_| ())
     }
 
@@ -266,7 +365,7 @@ impl ChildRef {
     pub fn kill(&self) -> Result<(), ()> {
         debug!("ChildRef({}): Killing.", self.id());
         let msg = BastionMessage::kill();
-        let env = Envelope::from_dead_letters(msg);
+        let env = Envelope::from_dead_letters(msg, &self.system);
         self.send(env).map_err(|_| ())
     }
 
@@ -275,17 +374,19 @@ impl ChildRef {
         RefAddr::new(self.path.clone(), self.sender.clone())
     }
 
-    pub(crate) fn send(&self, env: Envelope) -> Result<(), Envelope> {
+    pub(crate) fn send(&self, env: Envelope) -> Result<(), Box<Envelope>> {
         trace!("ChildRef({}): Sending message: {:?}", self.id(), env);
-        self.sender
-            .unbounded_send(env)
-            .map_err(|err| err.into_inner())
+        self.sender.unbounded_send(env)
     }
 
     pub(crate) fn sender(&self) -> &Sender {
         &self.sender
     }
 
+    pub(crate) fn system(&self) -> &Arc<GlobalSystem> {
+        &self.system
+    }
+
     /// Returns the [`BastionPath`] of the child
     pub fn path(&self) -> &Arc<BastionPath> {
         &self.path
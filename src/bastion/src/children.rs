@@ -1,31 +1,56 @@
 //!
 //! Children are a group of child supervised under a supervisor
-use crate::broadcast::{Broadcast, Parent, Sender};
+use crate::broadcast::{Broadcast, Parent, Sender, TrySendError};
 use crate::callbacks::{CallbackType, Callbacks};
-use crate::child::{Child, Init};
+use crate::child::{Child, Exec, Init, Mailbox};
 use crate::child_ref::ChildRef;
-use crate::children_ref::ChildrenRef;
-use crate::context::{BastionContext, BastionId, ContextState};
+use crate::children_ref::{ChildrenRef, DrainError, HealthStatus};
+use crate::context::{
+    next_pid, BastionContext, BastionId, ContextState, TerminationReason, ThreadContext, NIL_ID,
+};
 use crate::dispatcher::Dispatcher;
-use crate::envelope::Envelope;
-use crate::message::BastionMessage;
+use crate::envelope::{Envelope, RefAddr};
+use crate::events::{EntityKind, Transition};
+use crate::executor;
+use crate::interceptor::{InterceptCtx, InterceptDecision, Interceptors};
+use crate::logger;
+use crate::mailbox_store::MailboxStore;
+use crate::message::{BastionMessage, Message, Msg};
+use crate::metrics::{ChildrenMetrics, MetricsSnapshot};
 use crate::path::BastionPathElement;
-use crate::system::SYSTEM;
+use crate::scheduler::Ticker;
+use crate::supervisor::SupervisionStrategy;
+use crate::system::RunningGuard;
 use anyhow::Result as AnyResult;
 use async_mutex::Mutex;
-use bastion_executor::pool;
-use futures::pending;
-use futures::poll;
+use bastion_executor::{placement, pool};
+use futures::channel::oneshot;
+use futures::future::Either;
 use futures::prelude::*;
 use futures::stream::FuturesOrdered;
-use fxhash::FxHashMap;
+use futures_timer::Delay;
+use fxhash::{FxHashMap, FxHashSet};
 use lightproc::prelude::*;
-use std::fmt::Debug;
+use std::any::{type_name, TypeId};
+use std::collections::HashMap;
+use std::fmt::{self, Debug, Formatter};
 use std::future::Future;
+use std::marker::PhantomData;
+use std::panic::{self, AssertUnwindSafe};
 use std::pin::Pin;
-use std::sync::Arc;
-use std::task::Poll;
-use tracing::{debug, trace, warn};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{mpsc, Arc};
+use std::task::Context;
+use std::thread;
+use std::time::{Duration, Instant};
+use tracing::{debug, error, trace, warn};
+
+// The state an element shares with its context.
+type ElementState = Arc<Mutex<Pin<Box<ContextState>>>>;
+
+// How long each element can take to answer a health check by
+// default (see `Children::with_health_check_timeout`).
+const DEFAULT_HEALTH_CHECK_TIMEOUT: Duration = Duration::from_secs(1);
 
 #[derive(Debug)]
 /// A children group that will contain a defined number of
@@ -73,15 +98,72 @@ use tracing::{debug, trace, warn};
 /// [`SupervisionStrategy`]: supervisor/enum.SupervisionStrategy.html
 pub struct Children {
     bcast: Broadcast,
+    // The pid set on the group's process.
+    pid: usize,
     // The currently launched elements of the group.
-    launched: FxHashMap<BastionId, (Sender, RecoverableHandle<()>)>,
+    launched: FxHashMap<BastionId, (Sender, ElementState, RecoverableHandle<()>)>,
+    // The elements that were stopped to be restarted, whose
+    // "stopped" notification needs to be ignored.
+    resetting: FxHashSet<BastionId>,
+    // The number of currently launched elements, shared with
+    // every `ChildrenRef` referencing the group.
+    len: Arc<AtomicUsize>,
+    // The number of elements whose future wasn't dropped yet,
+    // which for a killed element only happens once it yields.
+    running: Arc<AtomicUsize>,
+    // The number of messages that expired before being received
+    // by the elements, shared with every `ChildrenRef`
+    // referencing the group.
+    expired: Arc<AtomicUsize>,
+    expired_to_dead_letters: bool,
+    // The number of messages each element can stash.
+    stash_capacity: Option<usize>,
+    // The number of messages each element handles before yielding.
+    poll_budget: Option<usize>,
+    // How long each element can take to handle a message.
+    exec_timeout: Option<Duration>,
+    // How long each element can take to handle a message before
+    // a warning is emitted.
+    warning_threshold: Option<Duration>,
+    // How long each element can wait for a message before stopping.
+    idle_timeout: Option<Duration>,
+    // How long each element can take to answer a health check.
+    health_check_timeout: Duration,
+    // Where the messages received by the elements are stored
+    // until they are retrieved, to be replayed if they weren't.
+    store: Option<Arc<dyn MailboxStore>>,
+    // The index of the element the next call will be sent to
+    // (see `ChildrenRef::call_any`), shared with every
+    // `ChildrenRef` referencing the group.
+    next_call: Arc<AtomicUsize>,
     // The closure returning the future that will be used by
     // every element of the group.
     init: Init,
+    // The type of the messages handled by `init`, if it was set
+    // using `with_exec_typed` (see `SupervisorRef::broadcast_typed`).
+    exec_type: Option<TypeId>,
+    // The closures of the elements spawned by their siblings
+    // (see `BastionContext::spawn_sibling`), used instead of
+    // `init` when restarting them.
+    sibling_inits: FxHashMap<BastionId, Init>,
+    // The closure returning the future run by the restarted
+    // elements before the one returned by `init` (see
+    // `with_exec_fallback`).
+    fallback: Option<Init>,
     redundancy: usize,
     // The callbacks called at the group's different lifecycle
     // events.
     callbacks: Callbacks,
+    // The interceptors deciding what to do with the messages
+    // received by the elements of the group.
+    interceptors: Interceptors,
+    // Called once the last element of the group stopped.
+    on_full_stop: Option<OnFullStop>,
+    // Called first when an element of the group faults.
+    supervision_hook: Option<SupervisionHook>,
+    // The number of times each element of the group was
+    // restarted, given to the supervision hook.
+    element_restarts: FxHashMap<BastionId, usize>,
     // Messages that were received before the group was
     // started. Those will be "replayed" once a start message
     // is received.
@@ -91,37 +173,323 @@ pub struct Children {
     dispatchers: Vec<Arc<Box<Dispatcher>>>,
     // The name of children
     name: Option<String>,
+    // The labels attached to the group (see `Children::with_tag`).
+    tags: HashMap<String, String>,
+    // Messages periodically sent to every element of the group
+    // once it has started.
+    scheduled_msgs: Vec<ScheduledMessage>,
+    // The interval at which every element of the group gets
+    // sent a `Tick` message.
+    tick: Option<Duration>,
+    // How the elements of the group are spawned.
+    spawn_strategy: SpawnStrategy,
+    // The cores the elements run on, and the index of the one the
+    // next element is spawned onto.
+    affinity: Affinity,
+    next_core: usize,
+    // Whether the elements of the group were paused (see
+    // `ChildrenRef::pause`), which also applies to the ones
+    // restarted in the meantime.
+    paused: bool,
+    // The state accumulated from the outputs emitted by the
+    // elements of the group.
+    reducer: Option<Box<dyn Reducer>>,
+    // The number of times the elements of the group were
+    // restarted, and the number of messages received and
+    // processed by the ones that aren't launched anymore (see
+    // `ChildrenMetrics`).
+    restarts: u64,
+    retired_received: u64,
+    retired_processed: u64,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+/// The action to take once the future of an element of a
+/// children group finished executing, as returned by the
+/// closures set with [`Children::with_exec_policy`].
+///
+/// The closures set with [`Children::with_exec`] return a
+/// `Result<(), ()>` instead, which is converted to a
+/// `FaultPolicy`: `Ok(())` to `StopChild` and `Err(())` to
+/// `EscalateToSupervisor`.
+///
+/// [`Children::with_exec_policy`]: struct.Children.html#method.with_exec_policy
+/// [`Children::with_exec`]: struct.Children.html#method.with_exec
+pub enum FaultPolicy {
+    /// Restarts the element (and only it, whatever the
+    /// supervisor's [`SupervisionStrategy`] is), following the
+    /// supervisor's restart strategy.
+    ///
+    /// [`SupervisionStrategy`]: ../supervisor/enum.SupervisionStrategy.html
+    RestartChild,
+    /// Stops the element, as if it finished executing
+    /// successfully.
+    StopChild,
+    /// Reports the element as faulted to its supervisor, which
+    /// then recovers from the fault using its
+    /// [`SupervisionStrategy`].
+    ///
+    /// [`SupervisionStrategy`]: ../supervisor/enum.SupervisionStrategy.html
+    EscalateToSupervisor,
+    /// Launches the element again, keeping the messages it
+    /// didn't receive yet, without reporting it as faulted.
+    ///
+    /// Like `RestartChild`, this follows the supervisor's restart
+    /// strategy and counts as one of the element's restarts.
+    Ignore,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+/// How an element of a children group faulted (see
+/// [`FaultEvent`]).
+///
+/// [`FaultEvent`]: struct.FaultEvent.html
+pub enum FaultKind {
+    /// The element's future returned an error (or
+    /// [`FaultPolicy::EscalateToSupervisor`]), or timed out
+    /// handling a message.
+    ///
+    /// [`FaultPolicy::EscalateToSupervisor`]: enum.FaultPolicy.html#variant.EscalateToSupervisor
+    Error,
+    /// The element panicked.
+    Panic,
+}
+
+#[derive(Debug, Clone)]
+/// The fault of an element of a children group, given to the
+/// hook set with [`Children::with_supervision_hook`].
+///
+/// [`Children::with_supervision_hook`]: struct.Children.html#method.with_supervision_hook
+pub struct FaultEvent {
+    /// The identifier of the element that faulted.
+    pub child_id: BastionId,
+    /// The number of times the element was restarted before.
+    pub restart_count: usize,
+    /// How the element faulted.
+    pub fault_kind: FaultKind,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+/// What to do with an element of a children group that faulted,
+/// as decided by the hook set with
+/// [`Children::with_supervision_hook`].
+///
+/// [`Children::with_supervision_hook`]: struct.Children.html#method.with_supervision_hook
+pub enum SupervisionDecision {
+    /// Restarts the element (and only it, whatever the
+    /// supervisor's [`SupervisionStrategy`] is), following the
+    /// supervisor's restart strategy.
+    ///
+    /// [`SupervisionStrategy`]: ../supervisor/enum.SupervisionStrategy.html
+    Restart,
+    /// Stops the element, as if it finished executing
+    /// successfully.
+    Stop,
+    /// Reports the element as faulted to its supervisor, which
+    /// then recovers from the fault using its
+    /// [`SupervisionStrategy`].
+    ///
+    /// [`SupervisionStrategy`]: ../supervisor/enum.SupervisionStrategy.html
+    Escalate,
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+/// How the elements of a children group get spawned, as set
+/// with [`Children::with_spawn_strategy`].
+///
+/// When running on a custom [`BastionExecutor`], elements spawned
+/// using `DedicatedThread` are spawned onto its blocking pool and
+/// the ones using `PinnedThread` like the ones using `DefaultPool`.
+///
+/// [`Children::with_spawn_strategy`]: struct.Children.html#method.with_spawn_strategy
+/// [`BastionExecutor`]: ../executor/trait.BastionExecutor.html
+pub enum SpawnStrategy {
+    /// Spawns the elements onto the executor's pool, where any
+    /// of its threads can run (and steal) them.
+    #[default]
+    DefaultPool,
+    /// Spawns each element onto its own thread, outside of the
+    /// executor's pool, which only runs it.
+    ///
+    /// This is useful for CPU-bound elements, which would
+    /// otherwise keep the pool's threads busy.
+    DedicatedThread,
+    /// Spawns the elements onto the executor's thread with the
+    /// given index (wrapping around the number of threads of the
+    /// pool), which is the only one to run them.
+    PinnedThread(usize),
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+/// The CPU cores the elements of a children group run on, set
+/// with [`Children::with_affinity`].
+///
+/// [`Children::with_affinity`]: struct.Children.html#method.with_affinity
+pub enum Affinity {
+    /// The elements run wherever their spawn strategy makes them
+    /// run (see [`Children::with_spawn_strategy`]).
+    ///
+    /// [`Children::with_spawn_strategy`]: struct.Children.html#method.with_spawn_strategy
+    #[default]
+    Any,
+    /// Each element runs on only one of the cores with the given
+    /// indices (in the list of the cores of the machine), the
+    /// elements being spread over them in turn.
+    Cores(Vec<usize>),
+}
+
+impl From<&[usize]> for Affinity {
+    fn from(cores: &[usize]) -> Self {
+        if cores.is_empty() {
+            Affinity::Any
+        } else {
+            Affinity::Cores(cores.to_vec())
+        }
+    }
+}
+
+impl<const N: usize> From<&[usize; N]> for Affinity {
+    fn from(cores: &[usize; N]) -> Self {
+        Affinity::from(&cores[..])
+    }
+}
+
+impl From<Vec<usize>> for Affinity {
+    fn from(cores: Vec<usize>) -> Self {
+        Affinity::from(&cores[..])
+    }
+}
+
+// The closure called once every element of a children group
+// stopped (see `Children::with_on_full_stop`).
+struct OnFullStop(Box<dyn Fn() + Send + Sync>);
+
+// The closure deciding what to do with the elements of a children
+// group that fault (see `Children::with_supervision_hook`).
+struct SupervisionHook(Box<dyn Fn(FaultEvent) -> SupervisionDecision + Send + Sync>);
+
+// The state accumulated by a children group (see
+// `Children::with_reducer`) from the outputs its elements emit.
+trait Reducer: Debug + Send {
+    // Folds the given output into the state, or returns it if it
+    // isn't of the expected type.
+    fn reduce(&mut self, output: Msg) -> Result<(), Msg>;
+
+    fn current(&self) -> Option<Msg>;
+}
+
+struct Accumulator<S, O, R> {
+    // Only `None` while an output is being folded into it.
+    acc: Option<S>,
+    reduce: R,
+    _output: PhantomData<fn(O)>,
+}
+
+// A message created by a user-defined closure and sent to every
+// element of a children group each time its interval elapses.
+struct ScheduledMessage {
+    interval: Duration,
+    factory: Box<dyn Fn() -> BastionMessage + Send + Sync>,
+    // Only set once the children group has started.
+    delay: Option<Delay>,
 }
 
 impl Children {
     pub(crate) fn new(bcast: Broadcast) -> Self {
         debug!("Children({}): Initializing.", bcast.id());
+        let pid = next_pid();
         let launched = FxHashMap::default();
+        let resetting = FxHashSet::default();
+        let len = Arc::new(AtomicUsize::new(0));
+        let running = Arc::new(AtomicUsize::new(0));
+        let expired = Arc::new(AtomicUsize::new(0));
+        let expired_to_dead_letters = false;
+        let stash_capacity = None;
+        let poll_budget = None;
+        let exec_timeout = None;
+        let warning_threshold = None;
+        let idle_timeout = None;
+        let health_check_timeout = DEFAULT_HEALTH_CHECK_TIMEOUT;
+        let spawn_strategy = SpawnStrategy::default();
+        let affinity = Affinity::default();
+        let next_core = 0;
+        let store = None;
+        let next_call = Arc::new(AtomicUsize::new(0));
         let init = Init::default();
+        let exec_type = None;
+        let sibling_inits = FxHashMap::default();
+        let fallback = None;
         let redundancy = 1;
-        let callbacks = Callbacks::new();
+        let callbacks = bcast.system().config().default_callbacks().clone();
+        let interceptors = Interceptors::default();
+        let on_full_stop = None;
+        let supervision_hook = None;
+        let element_restarts = FxHashMap::default();
         let pre_start_msgs = Vec::new();
         let started = false;
         let dispatchers = Vec::new();
         let name = None;
+        let tags = HashMap::new();
+        let scheduled_msgs = Vec::new();
+        let tick = None;
+        let paused = false;
+        let reducer = None;
+        let restarts = 0;
+        let retired_received = 0;
+        let retired_processed = 0;
 
         Children {
             bcast,
+            pid,
             launched,
+            resetting,
+            len,
+            running,
+            expired,
+            expired_to_dead_letters,
+            stash_capacity,
+            poll_budget,
+            exec_timeout,
+            warning_threshold,
+            idle_timeout,
+            health_check_timeout,
+            spawn_strategy,
+            affinity,
+            next_core,
+            store,
+            next_call,
             init,
+            exec_type,
+            sibling_inits,
+            fallback,
             redundancy,
             callbacks,
+            interceptors,
+            on_full_stop,
+            supervision_hook,
+            element_restarts,
             pre_start_msgs,
             started,
             dispatchers,
             name,
+            tags,
+            scheduled_msgs,
+            tick,
+            paused,
+            reducer,
+            restarts,
+            retired_received,
+            retired_processed,
         }
     }
 
-    fn stack(&self) -> ProcStack {
+    pub(crate) fn stack(&self) -> ProcStack {
         trace!("Children({}): Creating ProcStack.", self.id());
-        // FIXME: with_pid
+        // Like supervisors, children groups get run before their
+        // elements (whose stack is created by `Child::stack`).
         ProcStack::default()
+            .with_pid(self.pid)
+            .with_high_priority(true)
     }
 
     /// Returns this children group's identifier.
@@ -166,6 +534,22 @@ impl Children {
         }
     }
 
+    pub(crate) fn given_name(&self) -> Option<&String> {
+        self.name.as_ref()
+    }
+
+    pub(crate) fn redundancy(&self) -> usize {
+        self.redundancy
+    }
+
+    pub(crate) fn tags(&self) -> &HashMap<String, String> {
+        &self.tags
+    }
+
+    pub(crate) fn exec_type(&self) -> Option<TypeId> {
+        self.exec_type
+    }
+
     pub(crate) fn as_ref(&self) -> ChildrenRef {
         trace!(
             "Children({}): Creating new ChildrenRef({}).",
@@ -176,12 +560,19 @@ impl Children {
         let id = self.bcast.id().clone();
         let sender = self.bcast.sender().clone();
         let path = self.bcast.path().clone();
+        let system = self.bcast.system().clone();
 
         let mut children = Vec::with_capacity(self.launched.len());
-        for (id, (sender, _)) in &self.launched {
+        for (id, (sender, _, _)) in &self.launched {
             trace!("Children({}): Creating new ChildRef({}).", self.id(), id);
             // TODO: clone or ref?
-            let child = ChildRef::new(id.clone(), sender.clone(), self.name(), path.clone());
+            let child = ChildRef::new(
+                id.clone(),
+                sender.clone(),
+                self.name(),
+                path.clone(),
+                system.clone(),
+            );
             children.push(child);
         }
 
@@ -191,15 +582,130 @@ impl Children {
             .map(|dispatcher| dispatcher.dispatcher_type())
             .collect();
 
-        ChildrenRef::new(id, sender, path, children, dispatchers)
+        let name = self.name.clone();
+        let tags = Arc::new(self.tags.clone());
+        let len = self.len.clone();
+        let expired = self.expired.clone();
+        let next_call = self.next_call.clone();
+
+        ChildrenRef::new(
+            id,
+            self.pid,
+            sender,
+            path,
+            children,
+            dispatchers,
+            name,
+            tags,
+            len,
+            expired,
+            next_call,
+            system,
+        )
+    }
+
+    fn update_len(&self) {
+        self.len.store(self.launched.len(), Ordering::SeqCst);
     }
 
-    /// Sets the name of this children group.
+    /// Sets the name of this children group, which is also given
+    /// to its elements (see [`ChildRef::name`]).
+    ///
+    /// The name is registered while the children group is running,
+    /// allowing to find it without knowing where it lives in the
+    /// supervision tree (see [`Bastion::children_named`] and
+    /// [`BastionContext::lookup`]). If another running children
+    /// group already has the same name, the name then refers to
+    /// this one.
+    ///
+    /// This method returns `self` to allow chaining calls.
+    ///
+    /// # Arguments
+    ///
+    /// * `name` - The name of the children group.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use bastion::prelude::*;
+    /// #
+    /// # Bastion::init();
+    /// #
+    /// let children_ref = Bastion::children(|children| {
+    ///     children.with_name("workers")
+    /// }).expect("Couldn't create the children group.");
+    ///
+    /// assert_eq!(children_ref.name(), Some("workers"));
+    /// #
+    /// # Bastion::start();
+    /// # Bastion::stop();
+    /// # Bastion::block_until_stopped();
+    /// ```
+    ///
+    /// [`ChildRef::name`]: ../child_ref/struct.ChildRef.html#method.name
+    /// [`Bastion::children_named`]: ../struct.Bastion.html#method.children_named
+    /// [`BastionContext::lookup`]: ../context/struct.BastionContext.html#method.lookup
     pub fn with_name(mut self, name: impl Into<String>) -> Self {
         self.name = Some(name.into());
+        trace!("Children({}): Setting name: {:?}", self.id(), self.name);
+        self
+    }
+
+    /// Attaches the given label to this children group, replacing
+    /// the value of the label with the same key if it already had
+    /// one.
+    ///
+    /// Labels are never used by bastion itself, but can be read
+    /// using [`ChildrenRef::tags`] and allow supervisors to only
+    /// route messages to some of their children groups (see
+    /// [`Routing::TagBased`]).
+    ///
+    /// This method returns `self` to allow chaining calls.
+    ///
+    /// # Arguments
+    ///
+    /// * `key` - The key of the label.
+    /// * `value` - The value of the label.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use bastion::prelude::*;
+    /// #
+    /// # Bastion::init();
+    /// #
+    /// let children_ref = Bastion::children(|children| {
+    ///     children.with_tag("zone", "eu").with_tag("tier", "db")
+    /// }).expect("Couldn't create the children group.");
+    ///
+    /// assert_eq!(children_ref.tags()["zone"], "eu");
+    /// assert_eq!(children_ref.tags()["tier"], "db");
+    /// #
+    /// # Bastion::start();
+    /// # Bastion::stop();
+    /// # Bastion::block_until_stopped();
+    /// ```
+    ///
+    /// [`ChildrenRef::tags`]: ../children_ref/struct.ChildrenRef.html#method.tags
+    /// [`Routing::TagBased`]: ../supervisor/enum.Routing.html#variant.TagBased
+    pub fn with_tag(mut self, key: &str, value: &str) -> Self {
+        trace!("Children({}): Setting tag: {} = {}", self.id(), key, value);
+        self.tags.insert(key.to_string(), value.to_string());
         self
     }
 
+    fn register_name(&self) {
+        if let Some(name) = &self.name {
+            self.bcast.system().names().register(name, self.as_ref());
+        }
+    }
+
+    fn unregister_name(&self) {
+        if let Some(name) = &self.name {
+            self.bcast.system().names().unregister(name, self.id());
+        }
+    }
+
     /// Sets the closure taking a [`BastionContext`] and returning a
     /// [`Future`] that will be used by every element of this children
     /// group.
@@ -248,19 +754,24 @@ impl Children {
     {
         trace!("Children({}): Setting exec closure.", self.id());
         self.init = Init::new(init);
+        self.exec_type = None;
         self
     }
 
-    /// Sets the number of elements this children group will
-    /// contain. Each element will call the closure passed in
-    /// [`with_exec`] and run the returned future until it stops,
-    /// panics or another element in the group stops or panics.
+    /// Sets the closure taking a [`BastionContext`] and returning a
+    /// [`Future`] that will be used by every element of this
+    /// children group, like [`with_exec`], except that the future
+    /// returns the [`FaultPolicy`] to apply once it finished
+    /// executing, allowing elements to choose how to recover from
+    /// their own faults.
     ///
-    /// The default number of elements a children group contains is `1`.
+    /// This method returns `self` to allow chaining calls.
     ///
     /// # Arguments
     ///
-    /// * `redundancy` - The number of elements this group will contain.
+    /// * `init` - The closure taking a [`BastionContext`] and returning
+    ///     a [`Future`] that will be used by every element of this
+    ///     children group.
     ///
     /// # Example
     ///
@@ -270,8 +781,15 @@ impl Children {
     /// # Bastion::init();
     /// #
     /// Bastion::children(|children| {
-    ///     // Note that "1" is the default number of elements.
-    ///     children.with_redundancy(1)
+    ///     children.with_exec_policy(|ctx| {
+    ///         async move {
+    ///             match ctx.recv().await {
+    ///                 // Only this element needs to be restarted...
+    ///                 Ok(_msg) => FaultPolicy::RestartChild,
+    ///                 Err(()) => FaultPolicy::StopChild,
+    ///             }
+    ///         }
+    ///     })
     /// }).expect("Couldn't create the children group.");
     /// #
     /// # Bastion::start();
@@ -280,29 +798,130 @@ impl Children {
     /// ```
     ///
     /// [`with_exec`]: #method.with_exec
-    pub fn with_redundancy(mut self, redundancy: usize) -> Self {
+    /// [`FaultPolicy`]: enum.FaultPolicy.html
+    pub fn with_exec_policy<I, F>(mut self, init: I) -> Self
+    where
+        I: Fn(BastionContext) -> F + Send + 'static,
+        F: Future<Output = FaultPolicy> + Send + 'static,
+    {
+        trace!("Children({}): Setting exec closure.", self.id());
+        self.init = Init::new(init);
+        self.exec_type = None;
+        self
+    }
+
+    /// Sets the closure taking a [`BastionContext`] and a message
+    /// of type `M` and returning a [`Future`] that will be used by
+    /// every element of this children group to handle each message
+    /// of this type it receives, one at a time.
+    ///
+    /// Only the messages of type `M` are passed to the closure
+    /// (the broadcasted ones being cloned), all the other messages
+    /// being reported as unhandled using [`Msg::send_error_log`].
+    /// The "asked" ones can be answered using
+    /// [`BastionContext::answer`] while they are handled.
+    ///
+    /// If the returned future's output is `Err(())`, the element
+    /// faults as if the future set using [`with_exec`] returned it.
+    ///
+    /// # Arguments
+    ///
+    /// * `exec` - The closure taking a [`BastionContext`] and a
+    ///     message of type `M` and returning a [`Future`] handling
+    ///     it.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use bastion::prelude::*;
+    /// #
+    /// # Bastion::init();
+    /// #
+    /// #[derive(Debug, Clone)]
+    /// struct Invoice {
+    ///     amount: u64,
+    /// }
+    ///
+    /// Bastion::children(|children| {
+    ///     children.with_exec_typed(|ctx: BastionContext, invoice: Invoice| {
+    ///         async move {
+    ///             // Handle the invoice...
+    ///             Ok(())
+    ///         }
+    ///     })
+    /// }).expect("Couldn't create the children group.");
+    /// #
+    /// # Bastion::start();
+    /// # Bastion::stop();
+    /// # Bastion::block_until_stopped();
+    /// ```
+    ///
+    /// [`BastionContext`]: context/struct.BastionContext.html
+    /// [`Future`]: https://doc.rust-lang.org/std/future/trait.Future.html
+    /// [`Msg::send_error_log`]: message/struct.Msg.html#method.send_error_log
+    /// [`BastionContext::answer`]: context/struct.BastionContext.html#method.answer
+    /// [`with_exec`]: #method.with_exec
+    pub fn with_exec_typed<M, I, F>(self, exec: I) -> Self
+    where
+        M: Message + Clone,
+        I: Fn(BastionContext, M) -> F + Send + Sync + 'static,
+        F: Future<Output = Result<(), ()>> + Send + 'static,
+    {
         trace!(
-            "Children({}): Setting redundancy: {}",
+            "Children({}): Setting typed exec closure for {}.",
             self.id(),
-            redundancy
+            type_name::<M>()
         );
-        if redundancy == std::usize::MIN {
-            self.redundancy = redundancy.saturating_add(1);
-        } else {
-            self.redundancy = redundancy;
-        }
+        let exec = Arc::new(exec);
+        let mut children = self.with_exec(move |ctx: BastionContext| {
+            let exec = exec.clone();
+            async move {
+                loop {
+                    let (mut msg, _) = ctx.recv().await?.extract();
+                    if !msg.is::<M>() {
+                        msg.send_error_log();
+                        continue;
+                    }
 
-        self
+                    let sender = msg.take_sender();
+                    let msg = match msg.downcast::<M>() {
+                        Ok(msg) => msg,
+                        // FIXME: panics?
+                        Err(msg) => M::clone(&msg.downcast_ref::<M>().unwrap()),
+                    };
+                    ctx.set_answer(sender);
+                    let handled = exec(ctx.clone(), msg).await;
+                    ctx.set_answer(None);
+                    handled?;
+                }
+            }
+        });
+        children.exec_type = Some(TypeId::of::<M>());
+        children
     }
 
-    /// Appends each supervised element to the declared dispatcher.
+    /// Sets the closure taking a [`ThreadContext`] that will be
+    /// run by every element of this children group on its own
+    /// dedicated OS thread, allowing to supervise synchronous code
+    /// (e.g. blocking loops) without rewriting it as futures.
     ///
-    /// By default supervised elements aren't added to any of dispatcher.
+    /// The messages received by the elements are forwarded to
+    /// their thread, which can wait for them using
+    /// [`ThreadContext::recv`]. Once an element is asked to stop or
+    /// is killed, waiting for a message fails, after which its
+    /// thread should return.
+    ///
+    /// If the closure returns `Err(())`, the element faults as if
+    /// the future set using [`with_exec`] returned it, and if it
+    /// panics, the element faults as if its future panicked. A
+    /// thread that returns `Ok(())` stops its element.
+    ///
+    /// This method returns `self` to allow chaining calls.
     ///
     /// # Arguments
     ///
-    /// * `redundancy` - An instance of struct that implements the
-    /// [`DispatcherHandler`] trait.
+    /// * `exec` - The closure taking a [`ThreadContext`] that will
+    ///     be run by every element of this children group.
     ///
     /// # Example
     ///
@@ -312,32 +931,101 @@ impl Children {
     /// # Bastion::init();
     /// #
     /// Bastion::children(|children| {
-    ///     children
-    ///         .with_dispatcher(
-    ///             Dispatcher::with_type(DispatcherType::Named("CustomGroup".to_string()))
-    ///         )
+    ///     children.with_thread_exec(|ctx: ThreadContext| {
+    ///         while let Ok(msg) = ctx.recv() {
+    ///             // Handle the message, blocking if needed...
+    ///         }
+    ///
+    ///         Ok(())
+    ///     })
     /// }).expect("Couldn't create the children group.");
     /// #
     /// # Bastion::start();
     /// # Bastion::stop();
     /// # Bastion::block_until_stopped();
     /// ```
-    /// [`DispatcherHandler`]: ../dispatcher/trait.DispatcherHandler.html
-    pub fn with_dispatcher(mut self, dispatcher: Dispatcher) -> Self {
-        self.dispatchers.push(Arc::new(Box::new(dispatcher)));
-        self
+    ///
+    /// [`ThreadContext`]: context/struct.ThreadContext.html
+    /// [`ThreadContext::recv`]: context/struct.ThreadContext.html#method.recv
+    /// [`with_exec`]: #method.with_exec
+    pub fn with_thread_exec<I>(self, exec: I) -> Self
+    where
+        I: Fn(ThreadContext) -> Result<(), ()> + Send + Sync + 'static,
+    {
+        trace!("Children({}): Setting thread exec closure.", self.id());
+        let exec = Arc::new(exec);
+        self.with_exec(move |ctx: BastionContext| {
+            let exec = exec.clone();
+            async move {
+                let id = ctx.current().id().clone();
+                let (sender, receiver) = mpsc::channel();
+                let (done_sender, mut done) = oneshot::channel();
+                let thread_ctx =
+                    ThreadContext::new(ctx.current().clone(), ctx.parent().clone(), receiver);
+                let spawned = thread::Builder::new()
+                    .name(format!("bastion-child-{}", id))
+                    .spawn(move || {
+                        let res = panic::catch_unwind(AssertUnwindSafe(|| exec(thread_ctx)));
+                        done_sender.send(res).ok();
+                    });
+                if let Err(err) = spawned {
+                    warn!("Child({}): Couldn't spawn thread: {}", id, err);
+                    return Err(());
+                }
+
+                // Forwarding the messages to the thread until it
+                // returns or the element is asked to stop. The
+                // messages received once it returned are dropped.
+                let stopped = loop {
+                    match future::select(Box::pin(ctx.recv()), &mut done).await {
+                        Either::Left((Ok(msg), _)) => {
+                            sender.send(msg).ok();
+                        }
+                        Either::Left((Err(()), _)) => break None,
+                        Either::Right((res, _)) => break Some(res),
+                    }
+                };
+                let res = match stopped {
+                    Some(res) => res,
+                    None => {
+                        debug!("Child({}): Stopping, waiting for thread.", id);
+                        // Makes the thread fail to receive messages.
+                        drop(sender);
+                        done.await
+                    }
+                };
+
+                match res {
+                    Ok(Ok(res)) => res,
+                    // The panic is propagated to the element's future
+                    // to recover from it like from any panic.
+                    Ok(Err(panic)) => panic::resume_unwind(panic),
+                    // The thread always sends its result.
+                    Err(_) => Err(()),
+                }
+            }
+        })
     }
 
-    /// Sets the callbacks that will get called at this children group's
-    /// different lifecycle events.
+    /// Sets the closure taking a [`BastionContext`] and returning a
+    /// [`Future`] that the elements of this children group run
+    /// when restarted after faulting, before the one set using
+    /// [`with_exec`], to operate in a degraded mode until they can
+    /// be trusted again.
     ///
-    /// See [`Callbacks`]'s documentation for more information about the
-    /// different callbacks available.
+    /// The future returned by `fallback` runs until it returns, then
+    /// the one returned by the closure set using [`with_exec`] is
+    /// run if it returned `Ok(())`. If it returned `Err(())` (or
+    /// panicked), the element faults and is recovered like any
+    /// faulted element, running `fallback` again if restarted.
+    ///
+    /// This method returns `self` to allow chaining calls.
     ///
     /// # Arguments
     ///
-    /// * `callbacks` - The callbacks that will get called for this
-    ///     children group.
+    /// * `fallback` - The closure taking a [`BastionContext`] and
+    ///     returning a [`Future`] run by the restarted elements of
+    ///     this children group.
     ///
     /// # Example
     ///
@@ -347,19 +1035,18 @@ impl Children {
     /// # Bastion::init();
     /// #
     /// Bastion::children(|children| {
-    ///     let callbacks = Callbacks::new()
-    ///         .with_before_start(|| println!("Children group started."))
-    ///         .with_after_stop(|| println!("Children group stopped."));
-    ///
     ///     children
-    ///         .with_callbacks(callbacks)
-    ///         .with_exec(|ctx| {
-    ///             // -- Children group started.
+    ///         .with_exec(|ctx: BastionContext| {
     ///             async move {
-    ///                 // ...
-    ///                 # Ok(())
+    ///                 // Handle the messages, using a flaky database...
+    ///                 Ok(())
+    ///             }
+    ///         })
+    ///         .with_exec_fallback(|ctx: BastionContext| {
+    ///             async move {
+    ///                 // Answer from a cache until the database is back...
+    ///                 Ok(())
     ///             }
-    ///             // -- Children group stopped.
     ///         })
     /// }).expect("Couldn't create the children group.");
     /// #
@@ -368,28 +1055,1082 @@ impl Children {
     /// # Bastion::block_until_stopped();
     /// ```
     ///
-    /// [`Callbacks`]: struct.Callbacks.html
-    pub fn with_callbacks(mut self, callbacks: Callbacks) -> Self {
-        trace!(
-            "Children({}): Setting callbacks: {:?}",
-            self.id(),
-            callbacks
-        );
-        self.callbacks = callbacks;
+    /// [`BastionContext`]: context/struct.BastionContext.html
+    /// [`Future`]: https://doc.rust-lang.org/std/future/trait.Future.html
+    /// [`with_exec`]: #method.with_exec
+    pub fn with_exec_fallback<I, F>(mut self, fallback: I) -> Self
+    where
+        I: Fn(BastionContext) -> F + Send + 'static,
+        F: Future<Output = Result<(), ()>> + Send + 'static,
+    {
+        trace!("Children({}): Setting fallback closure.", self.id());
+        self.fallback = Some(Init::new(fallback));
         self
     }
 
-    async fn kill(&mut self) {
-        debug!("Children({}): Killing.", self.id());
-        self.bcast.kill_children();
-
-        let mut children = FuturesOrdered::new();
-        for (_, (_, launched)) in self.launched.drain() {
-            launched.cancel();
-
-            children.push(launched);
+    /// Sets the number of elements this children group will
+    /// contain. Each element will call the closure passed in
+    /// [`with_exec`] and run the returned future until it stops,
+    /// panics or another element in the group stops or panics.
+    ///
+    /// The default number of elements a children group contains is `1`.
+    ///
+    /// # Arguments
+    ///
+    /// * `redundancy` - The number of elements this group will contain.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use bastion::prelude::*;
+    /// #
+    /// # Bastion::init();
+    /// #
+    /// Bastion::children(|children| {
+    ///     // Note that "1" is the default number of elements.
+    ///     children.with_redundancy(1)
+    /// }).expect("Couldn't create the children group.");
+    /// #
+    /// # Bastion::start();
+    /// # Bastion::stop();
+    /// # Bastion::block_until_stopped();
+    /// ```
+    ///
+    /// [`with_exec`]: #method.with_exec
+    pub fn with_redundancy(mut self, redundancy: usize) -> Self {
+        trace!(
+            "Children({}): Setting redundancy: {}",
+            self.id(),
+            redundancy
+        );
+        if redundancy == std::usize::MIN {
+            self.redundancy = redundancy.saturating_add(1);
+        } else {
+            self.redundancy = redundancy;
+        }
+
+        self
+    }
+
+    /// Appends each supervised element to the declared dispatcher.
+    ///
+    /// By default supervised elements aren't added to any of dispatcher.
+    ///
+    /// # Arguments
+    ///
+    /// * `redundancy` - An instance of struct that implements the
+    /// [`DispatcherHandler`] trait.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use bastion::prelude::*;
+    /// #
+    /// # Bastion::init();
+    /// #
+    /// Bastion::children(|children| {
+    ///     children
+    ///         .with_dispatcher(
+    ///             Dispatcher::with_type(DispatcherType::Named("CustomGroup".to_string()))
+    ///         )
+    /// }).expect("Couldn't create the children group.");
+    /// #
+    /// # Bastion::start();
+    /// # Bastion::stop();
+    /// # Bastion::block_until_stopped();
+    /// ```
+    /// [`DispatcherHandler`]: ../dispatcher/trait.DispatcherHandler.html
+    pub fn with_dispatcher(mut self, dispatcher: Dispatcher) -> Self {
+        self.dispatchers.push(Arc::new(Box::new(dispatcher)));
+        self
+    }
+
+    /// Sets the callbacks that will get called at this children group's
+    /// different lifecycle events.
+    ///
+    /// See [`Callbacks`]'s documentation for more information about the
+    /// different callbacks available.
+    ///
+    /// # Arguments
+    ///
+    /// * `callbacks` - The callbacks that will get called for this
+    ///     children group.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use bastion::prelude::*;
+    /// #
+    /// # Bastion::init();
+    /// #
+    /// Bastion::children(|children| {
+    ///     let callbacks = Callbacks::new()
+    ///         .with_before_start(|| println!("Children group started."))
+    ///         .with_after_stop(|| println!("Children group stopped."));
+    ///
+    ///     children
+    ///         .with_callbacks(callbacks)
+    ///         .with_exec(|ctx| {
+    ///             // -- Children group started.
+    ///             async move {
+    ///                 // ...
+    ///                 # Ok(())
+    ///             }
+    ///             // -- Children group stopped.
+    ///         })
+    /// }).expect("Couldn't create the children group.");
+    /// #
+    /// # Bastion::start();
+    /// # Bastion::stop();
+    /// # Bastion::block_until_stopped();
+    /// ```
+    ///
+    /// [`Callbacks`]: struct.Callbacks.html
+    pub fn with_callbacks(mut self, callbacks: Callbacks) -> Self {
+        trace!(
+            "Children({}): Setting callbacks: {:?}",
+            self.id(),
+            callbacks
+        );
+        self.callbacks = callbacks;
+        self
+    }
+
+    /// Sets a closure that will get called once the last element
+    /// of this children group stopped, either because all of them
+    /// finished executing or because the group was stopped or
+    /// killed.
+    ///
+    /// Unlike the [`Callbacks`] set with [`with_callbacks`], it
+    /// isn't called for every element, which allows to release
+    /// resources shared by all of them.
+    ///
+    /// # Arguments
+    ///
+    /// * `on_full_stop` - The closure that will get called once no
+    ///     element of the group is running anymore.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use bastion::prelude::*;
+    /// #
+    /// # Bastion::init();
+    /// #
+    /// Bastion::children(|children| {
+    ///     children
+    ///         .with_redundancy(3)
+    ///         .with_on_full_stop(|| println!("All the elements stopped."))
+    ///         .with_exec(|ctx| {
+    ///             async move {
+    ///                 // ...
+    ///                 # Ok(())
+    ///             }
+    ///         })
+    /// }).expect("Couldn't create the children group.");
+    /// #
+    /// # Bastion::start();
+    /// # Bastion::stop();
+    /// # Bastion::block_until_stopped();
+    /// ```
+    ///
+    /// [`Callbacks`]: struct.Callbacks.html
+    /// [`with_callbacks`]: #method.with_callbacks
+    pub fn with_on_full_stop<F>(mut self, on_full_stop: F) -> Self
+    where
+        F: Fn() + Send + Sync + 'static,
+    {
+        trace!("Children({}): Setting the full stop callback.", self.id());
+        self.on_full_stop = Some(OnFullStop(Box::new(on_full_stop)));
+        self
+    }
+
+    /// Sets the hook called first when an element of this
+    /// children group faults (because its future returned an
+    /// error, panicked or timed out), deciding whether to restart
+    /// it, to stop it or to escalate the fault to the supervisor,
+    /// whose [`SupervisionStrategy`] is then only applied.
+    ///
+    /// Elements asking to be restarted or ignored (see
+    /// [`FaultPolicy`]) aren't considered as faulted and don't
+    /// get the hook called.
+    ///
+    /// This method returns `self` to allow chaining calls.
+    ///
+    /// # Arguments
+    ///
+    /// * `hook` - The closure taking the [`FaultEvent`] describing
+    ///     the fault and returning a [`SupervisionDecision`].
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use bastion::prelude::*;
+    /// #
+    /// # Bastion::init();
+    /// #
+    /// Bastion::children(|children| {
+    ///     children
+    ///         .with_supervision_hook(|event: FaultEvent| match event.fault_kind {
+    ///             // Errors are transient...
+    ///             FaultKind::Error if event.restart_count < 3 => SupervisionDecision::Restart,
+    ///             FaultKind::Error => SupervisionDecision::Stop,
+    ///             // ...but panics are left to the supervisor.
+    ///             FaultKind::Panic => SupervisionDecision::Escalate,
+    ///         })
+    ///         .with_exec(|ctx| {
+    ///             async move {
+    ///                 // ...
+    ///                 # Ok(())
+    ///             }
+    ///         })
+    /// }).expect("Couldn't create the children group.");
+    /// #
+    /// # Bastion::start();
+    /// # Bastion::stop();
+    /// # Bastion::block_until_stopped();
+    /// ```
+    ///
+    /// [`SupervisionStrategy`]: ../supervisor/enum.SupervisionStrategy.html
+    /// [`FaultPolicy`]: enum.FaultPolicy.html
+    /// [`FaultEvent`]: struct.FaultEvent.html
+    /// [`SupervisionDecision`]: enum.SupervisionDecision.html
+    pub fn with_supervision_hook<F>(mut self, hook: F) -> Self
+    where
+        F: Fn(FaultEvent) -> SupervisionDecision + Send + Sync + 'static,
+    {
+        trace!("Children({}): Setting the supervision hook.", self.id());
+        self.supervision_hook = Some(SupervisionHook(Box::new(hook)));
+        self
+    }
+
+    /// Adds an interceptor called with every message received by
+    /// the elements of this children group before it is handed to
+    /// their futures, deciding whether the message should be
+    /// delivered, dropped or sent to the dead letters.
+    ///
+    /// The interceptors are called in the order they were added,
+    /// until one of them doesn't decide to deliver the message.
+    /// They should be cheap since they are called for every
+    /// message, and an interceptor that panics is considered to
+    /// have decided to deliver it.
+    ///
+    /// This method returns `self` to allow chaining calls.
+    ///
+    /// # Arguments
+    ///
+    /// * `interceptor` - The closure taking the message and an
+    ///     [`InterceptCtx`] and returning an [`InterceptDecision`].
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use bastion::prelude::*;
+    /// #
+    /// # Bastion::init();
+    /// #
+    /// Bastion::children(|children| {
+    ///     children
+    ///         .with_interceptor(|msg: &Msg, ctx: &InterceptCtx| {
+    ///             println!("Child({}) received: {:?}", ctx.id(), msg);
+    ///             InterceptDecision::Deliver
+    ///         })
+    ///         .with_interceptor(|msg: &Msg, _| {
+    ///             if msg.is::<&str>() {
+    ///                 InterceptDecision::Deliver
+    ///             } else {
+    ///                 InterceptDecision::DeadLetter
+    ///             }
+    ///         })
+    /// }).expect("Couldn't create the children group.");
+    /// #
+    /// # Bastion::start();
+    /// # Bastion::stop();
+    /// # Bastion::block_until_stopped();
+    /// ```
+    ///
+    /// [`InterceptCtx`]: ../interceptor/struct.InterceptCtx.html
+    /// [`InterceptDecision`]: ../interceptor/enum.InterceptDecision.html
+    pub fn with_interceptor<I>(mut self, interceptor: I) -> Self
+    where
+        I: Fn(&Msg, &InterceptCtx) -> InterceptDecision + Send + Sync + 'static,
+    {
+        trace!("Children({}): Adding an interceptor.", self.id());
+        self.interceptors.push(Arc::new(interceptor));
+        self
+    }
+
+    /// Makes every element of this children group receive a new
+    /// message created by calling `factory`, every time `interval`
+    /// elapses.
+    ///
+    /// The messages are "told" anonymously to the elements, so
+    /// they can be matched like any other message (without `ref`
+    /// in the [`msg!`] macro). The first messages are sent one
+    /// `interval` after the children group has started and no
+    /// more messages get sent once it has stopped.
+    ///
+    /// This method can be called multiple times to schedule
+    /// different messages.
+    ///
+    /// # Arguments
+    ///
+    /// * `interval` - The duration between two messages sent to
+    ///     each element of the group, of at least a millisecond.
+    /// * `factory` - The closure returning the message to send,
+    ///     called once per element every time `interval` elapses.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use bastion::prelude::*;
+    /// # use std::time::Duration;
+    /// #
+    /// # Bastion::init();
+    /// #
+    /// #[derive(Debug)]
+    /// struct Heartbeat;
+    ///
+    /// Bastion::children(|children| {
+    ///     children
+    ///         .with_scheduled_message(Duration::from_secs(5), || Heartbeat)
+    ///         .with_exec(|ctx| {
+    ///             async move {
+    ///                 loop {
+    ///                     msg! { ctx.recv().await?,
+    ///                         _msg: Heartbeat => {
+    ///                             // Perform the periodic work...
+    ///                         };
+    ///                         _: _ => ();
+    ///                     }
+    ///                 }
+    ///             }
+    ///         })
+    /// }).expect("Couldn't create the children group.");
+    /// #
+    /// # Bastion::start();
+    /// # Bastion::stop();
+    /// # Bastion::block_until_stopped();
+    /// ```
+    ///
+    /// [`msg!`]: ../macro.msg.html
+    pub fn with_scheduled_message<F, M>(mut self, interval: Duration, factory: F) -> Self
+    where
+        F: Fn() -> M + Send + Sync + 'static,
+        M: Message,
+    {
+        trace!(
+            "Children({}): Scheduling a message every {:?}.",
+            self.id(),
+            interval
+        );
+        let factory = Box::new(move || BastionMessage::tell(factory()));
+        self.scheduled_msgs.push(ScheduledMessage {
+            interval: interval.max(Duration::from_millis(1)),
+            factory,
+            delay: None,
+        });
+        self
+    }
+
+    /// Makes the messages sent to this children group that expired
+    /// before being received (see [`ChildrenRef::broadcast_with_ttl`])
+    /// be sent to the dead letters, instead of only being skipped.
+    ///
+    /// This method returns `self` to allow chaining calls.
+    ///
+    /// # Arguments
+    ///
+    /// * `enabled` - Whether expired messages should be sent to
+    ///     the dead letters.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use bastion::prelude::*;
+    /// #
+    /// # Bastion::init();
+    /// #
+    /// Bastion::children(|children| {
+    ///     children.with_expired_to_dead_letters(true)
+    /// }).expect("Couldn't create the children group.");
+    /// #
+    /// # Bastion::start();
+    /// # Bastion::stop();
+    /// # Bastion::block_until_stopped();
+    /// ```
+    ///
+    /// [`ChildrenRef::broadcast_with_ttl`]: ../children_ref/struct.ChildrenRef.html#method.broadcast_with_ttl
+    pub fn with_expired_to_dead_letters(mut self, enabled: bool) -> Self {
+        trace!(
+            "Children({}): Sending expired messages to the dead letters: {}",
+            self.id(),
+            enabled
+        );
+        self.expired_to_dead_letters = enabled;
+        self
+    }
+
+    /// Sets the number of messages each element of this children
+    /// group can stash using [`BastionContext::stash`] before it
+    /// fails (`1024` by default).
+    ///
+    /// This method returns `self` to allow chaining calls.
+    ///
+    /// # Arguments
+    ///
+    /// * `capacity` - The number of messages each element can stash.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use bastion::prelude::*;
+    /// #
+    /// # Bastion::init();
+    /// #
+    /// Bastion::children(|children| {
+    ///     children.with_stash_capacity(16)
+    /// }).expect("Couldn't create the children group.");
+    /// #
+    /// # Bastion::start();
+    /// # Bastion::stop();
+    /// # Bastion::block_until_stopped();
+    /// ```
+    ///
+    /// [`BastionContext::stash`]: ../context/struct.BastionContext.html#method.stash
+    pub fn with_stash_capacity(mut self, capacity: usize) -> Self {
+        trace!(
+            "Children({}): Setting stash capacity: {}",
+            self.id(),
+            capacity
+        );
+        self.stash_capacity = Some(capacity);
+        self
+    }
+
+    /// Sets the number of messages each element of this children
+    /// group handles before yielding (`128` by default), for the
+    /// other elements running on the same thread to get polled
+    /// even when it has many messages waiting in its mailbox.
+    ///
+    /// An element yields once it received as many messages (using
+    /// [`BastionContext::recv`] or [`BastionContext::recv_where`])
+    /// without waiting, or once as many messages were sent to it,
+    /// the messages left staying in its mailbox, in order, until it
+    /// gets polled again. A budget of `0` is treated as `1`.
+    ///
+    /// This method returns `self` to allow chaining calls.
+    ///
+    /// # Arguments
+    ///
+    /// * `budget` - The number of messages each element handles
+    ///     before yielding.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use bastion::prelude::*;
+    /// #
+    /// # Bastion::init();
+    /// #
+    /// Bastion::children(|children| {
+    ///     children.with_poll_budget(32)
+    /// }).expect("Couldn't create the children group.");
+    /// #
+    /// # Bastion::start();
+    /// # Bastion::stop();
+    /// # Bastion::block_until_stopped();
+    /// ```
+    ///
+    /// [`BastionContext::recv`]: ../context/struct.BastionContext.html#method.recv
+    /// [`BastionContext::recv_where`]: ../context/struct.BastionContext.html#method.recv_where
+    pub fn with_poll_budget(mut self, budget: usize) -> Self {
+        trace!("Children({}): Setting poll budget: {}", self.id(), budget);
+        self.poll_budget = Some(budget);
+        self
+    }
+
+    /// Sets how long each element of this children group can take
+    /// to handle a message, from when it received it (using
+    /// [`BastionContext::recv`] or [`BastionContext::recv_where`])
+    /// until it waits for the next one.
+    ///
+    /// An element still handling a message once this duration
+    /// elapsed gets cancelled and faults, as if the future set
+    /// using [`with_exec`] returned `Err(())`, letting its
+    /// supervisor restart it.
+    ///
+    /// This method returns `self` to allow chaining calls.
+    ///
+    /// # Arguments
+    ///
+    /// * `timeout` - How long each element can take to handle a
+    ///     message.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use bastion::prelude::*;
+    /// # use std::time::Duration;
+    /// #
+    /// # Bastion::init();
+    /// #
+    /// Bastion::children(|children| {
+    ///     children
+    ///         .with_exec_timeout(Duration::from_secs(5))
+    ///         .with_exec(|ctx: BastionContext| async move {
+    ///             loop {
+    ///                 let msg = ctx.recv().await?;
+    ///                 // Handling `msg` can't take more than five seconds...
+    ///             }
+    ///         })
+    /// }).expect("Couldn't create the children group.");
+    /// #
+    /// # Bastion::start();
+    /// # Bastion::stop();
+    /// # Bastion::block_until_stopped();
+    /// ```
+    ///
+    /// [`BastionContext::recv`]: ../context/struct.BastionContext.html#method.recv
+    /// [`BastionContext::recv_where`]: ../context/struct.BastionContext.html#method.recv_where
+    /// [`with_exec`]: #method.with_exec
+    pub fn with_exec_timeout(mut self, timeout: Duration) -> Self {
+        trace!(
+            "Children({}): Setting exec timeout: {:?}",
+            self.id(),
+            timeout
+        );
+        self.exec_timeout = Some(timeout);
+        self
+    }
+
+    /// Sets how long each element of this children group can take
+    /// to handle a message it received (using
+    /// [`BastionContext::recv`] or [`BastionContext::recv_where`])
+    /// before a warning is emitted, until it waits for the next one
+    /// or its future returns.
+    ///
+    /// The warning names the element, how long it had been handling
+    /// the message and the message's type. It is logged with a
+    /// `tracing` event at the `WARN` level and reported to the
+    /// logger set with [`Bastion::with_logger`], once per message.
+    /// Unlike [`with_exec_timeout`], the element isn't stopped, so
+    /// that it is safe to enable broadly.
+    ///
+    /// Note that an element blocking its thread can only be warned
+    /// about once it is done handling the message.
+    ///
+    /// This method returns `self` to allow chaining calls.
+    ///
+    /// # Arguments
+    ///
+    /// * `threshold` - How long each element can take to handle a
+    ///     message before a warning is emitted.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use bastion::prelude::*;
+    /// # use std::time::Duration;
+    /// #
+    /// # Bastion::init();
+    /// #
+    /// Bastion::children(|children| {
+    ///     children
+    ///         .with_processing_warning_threshold(Duration::from_secs(1))
+    ///         .with_exec(|ctx: BastionContext| async move {
+    ///             loop {
+    ///                 let msg = ctx.recv().await?;
+    ///                 // Handling `msg` for more than a second gets warned about...
+    ///             }
+    ///         })
+    /// }).expect("Couldn't create the children group.");
+    /// #
+    /// # Bastion::start();
+    /// # Bastion::stop();
+    /// # Bastion::block_until_stopped();
+    /// ```
+    ///
+    /// [`BastionContext::recv`]: ../context/struct.BastionContext.html#method.recv
+    /// [`BastionContext::recv_where`]: ../context/struct.BastionContext.html#method.recv_where
+    /// [`Bastion::with_logger`]: ../struct.Bastion.html#method.with_logger
+    /// [`with_exec_timeout`]: #method.with_exec_timeout
+    pub fn with_processing_warning_threshold(mut self, threshold: Duration) -> Self {
+        trace!(
+            "Children({}): Setting processing warning threshold: {:?}",
+            self.id(),
+            threshold
+        );
+        self.warning_threshold = Some(threshold);
+        self
+    }
+
+    /// Sets how long each element of this children group can wait
+    /// for a message (using [`BastionContext::recv`] or
+    /// [`BastionContext::recv_where`]) before stopping, the timer
+    /// being reset each time it receives one.
+    ///
+    /// An element stopping after being idle isn't faulted, and thus
+    /// isn't restarted by its supervisor. Once all the elements of
+    /// the group stopped, the closure set using
+    /// [`with_on_full_stop`] gets called.
+    ///
+    /// This method returns `self` to allow chaining calls.
+    ///
+    /// # Arguments
+    ///
+    /// * `duration` - How long each element can wait for a message.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use bastion::prelude::*;
+    /// # use std::time::Duration;
+    /// #
+    /// # Bastion::init();
+    /// #
+    /// Bastion::children(|children| {
+    ///     children
+    ///         .with_idle_timeout(Duration::from_secs(60))
+    ///         .with_exec(|ctx: BastionContext| async move {
+    ///             loop {
+    ///                 // Stops if no message is received for a minute...
+    ///                 let msg = ctx.recv().await?;
+    ///             }
+    ///         })
+    /// }).expect("Couldn't create the children group.");
+    /// #
+    /// # Bastion::start();
+    /// # Bastion::stop();
+    /// # Bastion::block_until_stopped();
+    /// ```
+    ///
+    /// [`BastionContext::recv`]: ../context/struct.BastionContext.html#method.recv
+    /// [`BastionContext::recv_where`]: ../context/struct.BastionContext.html#method.recv_where
+    /// [`with_on_full_stop`]: #method.with_on_full_stop
+    pub fn with_idle_timeout(mut self, duration: Duration) -> Self {
+        trace!(
+            "Children({}): Setting idle timeout: {:?}",
+            self.id(),
+            duration
+        );
+        self.idle_timeout = Some(duration);
+        self
+    }
+
+    /// Sets how long each element of this children group can take
+    /// to answer a health check (see [`ChildrenRef::health_check`])
+    /// before being reported as unresponsive. The ones answering
+    /// after half of it are reported as slow.
+    ///
+    /// The default timeout is one second.
+    ///
+    /// This method returns `self` to allow chaining calls.
+    ///
+    /// # Arguments
+    ///
+    /// * `timeout` - How long each element can take to answer a
+    ///     health check.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use bastion::prelude::*;
+    /// # use std::time::Duration;
+    /// #
+    /// # Bastion::init();
+    /// #
+    /// Bastion::children(|children| {
+    ///     children.with_health_check_timeout(Duration::from_millis(200))
+    /// }).expect("Couldn't create the children group.");
+    /// #
+    /// # Bastion::start();
+    /// # Bastion::stop();
+    /// # Bastion::block_until_stopped();
+    /// ```
+    ///
+    /// [`ChildrenRef::health_check`]: ../children_ref/struct.ChildrenRef.html#method.health_check
+    pub fn with_health_check_timeout(mut self, timeout: Duration) -> Self {
+        trace!(
+            "Children({}): Setting health check timeout: {:?}",
+            self.id(),
+            timeout
+        );
+        self.health_check_timeout = timeout;
+        self
+    }
+
+    /// Sets how the elements of this children group get spawned
+    /// ([`SpawnStrategy::DefaultPool`] by default).
+    ///
+    /// This method returns `self` to allow chaining calls.
+    ///
+    /// # Arguments
+    ///
+    /// * `strategy` - The way the elements get spawned.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use bastion::prelude::*;
+    /// #
+    /// # Bastion::init();
+    /// #
+    /// Bastion::children(|children| {
+    ///     children
+    ///         .with_redundancy(4)
+    ///         .with_spawn_strategy(SpawnStrategy::DedicatedThread)
+    ///         .with_exec(|ctx: BastionContext| {
+    ///             async move {
+    ///                 // Each element runs on its own thread...
+    ///                 # Ok(())
+    ///             }
+    ///         })
+    /// }).expect("Couldn't create the children group.");
+    /// #
+    /// # Bastion::start();
+    /// # Bastion::stop();
+    /// # Bastion::block_until_stopped();
+    /// ```
+    ///
+    /// [`SpawnStrategy::DefaultPool`]: enum.SpawnStrategy.html#variant.DefaultPool
+    pub fn with_spawn_strategy(mut self, strategy: SpawnStrategy) -> Self {
+        trace!(
+            "Children({}): Setting spawn strategy: {:?}",
+            self.id(),
+            strategy
+        );
+        self.spawn_strategy = strategy;
+        self.warn_overridden_spawn_strategy();
+        self
+    }
+
+    /// Makes the elements of this children group only run on the
+    /// CPU cores with the given indices (in the list of the cores
+    /// of the machine), or wherever their spawn strategy makes them
+    /// run using [`Affinity::Any`] (the default).
+    ///
+    /// Each element is pinned to one of the executor's threads
+    /// running on those cores, the elements being spread over them
+    /// in turn, and never gets stolen by the other threads. This
+    /// takes precedence over the group's [`SpawnStrategy`].
+    ///
+    /// Creating the group fails if one of the indices isn't the
+    /// one of a core an executor's thread runs on (e.g. because
+    /// the machine has less cores or because of
+    /// [`Config::with_threads`]), in which case
+    /// [`Bastion::children`] returns `Err(())` (as does
+    /// [`Bastion::supervisor`] when the group was created using
+    /// [`Supervisor::children`]).
+    ///
+    /// Note that custom executors (see [`Config::with_executor`])
+    /// run the elements wherever they want.
+    ///
+    /// This method returns `self` to allow chaining calls.
+    ///
+    /// # Arguments
+    ///
+    /// * `affinity` - The cores the elements run on.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use bastion::prelude::*;
+    /// #
+    /// # Bastion::init();
+    /// #
+    /// Bastion::children(|children| {
+    ///     children
+    ///         .with_redundancy(2)
+    ///         .with_affinity(&[0])
+    ///         .with_exec(|ctx: BastionContext| {
+    ///             async move {
+    ///                 // Both elements run on the first core...
+    ///                 # Ok(())
+    ///             }
+    ///         })
+    /// }).expect("Couldn't create the children group.");
+    ///
+    /// // The core indices are checked when creating the group.
+    /// assert!(Bastion::children(|children| children.with_affinity(&[usize::MAX])).is_err());
+    /// #
+    /// # Bastion::start();
+    /// # Bastion::stop();
+    /// # Bastion::block_until_stopped();
+    /// ```
+    ///
+    /// [`Affinity::Any`]: enum.Affinity.html#variant.Any
+    /// [`SpawnStrategy`]: enum.SpawnStrategy.html
+    /// [`Config::with_threads`]: ../struct.Config.html#method.with_threads
+    /// [`Config::with_executor`]: ../struct.Config.html#method.with_executor
+    /// [`Bastion::children`]: ../struct.Bastion.html#method.children
+    /// [`Bastion::supervisor`]: ../struct.Bastion.html#method.supervisor
+    /// [`Supervisor::children`]: ../supervisor/struct.Supervisor.html#method.children
+    pub fn with_affinity(mut self, affinity: impl Into<Affinity>) -> Self {
+        self.affinity = affinity.into();
+        trace!(
+            "Children({}): Setting affinity: {:?}",
+            self.id(),
+            self.affinity
+        );
+        self.warn_overridden_spawn_strategy();
+        self
+    }
+
+    // Warns when the group's affinity overrides the spawn strategy
+    // that was set.
+    fn warn_overridden_spawn_strategy(&self) {
+        if self.affinity != Affinity::Any && self.spawn_strategy != SpawnStrategy::default() {
+            warn!(
+                "Children({}): The affinity overrides the spawn strategy: {:?}",
+                self.id(),
+                self.spawn_strategy
+            );
+        }
+    }
+
+    // Checks that an executor's thread runs on each core of the
+    // group's affinity, ignoring it otherwise.
+    pub(crate) fn check_affinity(&mut self) -> Result<(), ()> {
+        let cores = match &self.affinity {
+            Affinity::Any => return Ok(()),
+            Affinity::Cores(cores) => cores,
+        };
+
+        // The `n`th thread of the pool runs on the `n`th core.
+        let available = placement::get_core_ids()
+            .map(|cores| cores.len())
+            .unwrap_or(1)
+            .min(pool::threads());
+        if !cores.is_empty() && cores.iter().all(|core| *core < available) {
+            return Ok(());
+        }
+
+        error!(
+            "Children({}): Invalid affinity, only {} cores can be used: {:?}",
+            self.id(),
+            available,
+            cores
+        );
+        self.affinity = Affinity::Any;
+        Err(())
+    }
+
+    // Returns how the next element of the group gets spawned.
+    fn next_spawn_strategy(&mut self) -> SpawnStrategy {
+        match &self.affinity {
+            Affinity::Any => self.spawn_strategy,
+            Affinity::Cores(cores) => {
+                let core = cores[self.next_core % cores.len()];
+                self.next_core = self.next_core.wrapping_add(1);
+                SpawnStrategy::PinnedThread(core)
+            }
+        }
+    }
+
+    /// Makes the messages received by the elements of this children
+    /// group be stored in the given [`MailboxStore`] until they are
+    /// retrieved.
+    ///
+    /// The messages that weren't retrieved (e.g. because the group
+    /// was relaunched by its supervisor or because the application
+    /// stopped, if the store persists them) are drained from the
+    /// store when the group launches its elements and placed into
+    /// the mailbox of its first element, before any new message.
+    ///
+    /// This method returns `self` to allow chaining calls.
+    ///
+    /// # Arguments
+    ///
+    /// * `store` - The store to keep the messages in.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use bastion::prelude::*;
+    /// # use std::sync::Arc;
+    /// #
+    /// # #[derive(Debug)]
+    /// # struct MyStore;
+    /// # impl MailboxStore for MyStore {
+    /// #     fn append(&self, _: &Msg) -> Option<u64> { None }
+    /// #     fn mark_processed(&self, _: u64) {}
+    /// #     fn drain(&self) -> Vec<Msg> { vec![] }
+    /// # }
+    /// #
+    /// # Bastion::init();
+    /// #
+    /// Bastion::children(|children| {
+    ///     children.with_persistent_mailbox(Arc::new(MyStore))
+    /// }).expect("Couldn't create the children group.");
+    /// #
+    /// # Bastion::start();
+    /// # Bastion::stop();
+    /// # Bastion::block_until_stopped();
+    /// ```
+    ///
+    /// [`MailboxStore`]: ../mailbox_store/trait.MailboxStore.html
+    pub fn with_persistent_mailbox(mut self, store: Arc<dyn MailboxStore>) -> Self {
+        trace!(
+            "Children({}): Setting persistent mailbox: {:?}",
+            self.id(),
+            store
+        );
+        self.store = Some(store);
+        self
+    }
+
+    /// Makes every element of this children group receive a
+    /// [`Tick`] message each time the given interval elapses,
+    /// until it stops.
+    ///
+    /// Ticks don't pile up if an element is slow to receive them:
+    /// a new tick is only sent once the previous one was received.
+    /// Each element can change its interval or stop receiving
+    /// ticks using [`BastionContext::set_tick`].
+    ///
+    /// This method returns `self` to allow chaining calls.
+    ///
+    /// # Arguments
+    ///
+    /// * `interval` - The interval between two ticks.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use bastion::prelude::*;
+    /// # use std::time::Duration;
+    /// #
+    /// # Bastion::init();
+    /// #
+    /// Bastion::children(|children| {
+    ///     children
+    ///         .with_tick(Duration::from_secs(1))
+    ///         .with_exec(|ctx| {
+    ///             async move {
+    ///                 loop {
+    ///                     msg! { ctx.recv().await?,
+    ///                         _tick: Tick => {
+    ///                             // Flush the buffers...
+    ///                         };
+    ///                         _: _ => ();
+    ///                     }
+    ///                 }
+    ///             }
+    ///         })
+    /// }).expect("Couldn't create the children group.");
+    /// #
+    /// # Bastion::start();
+    /// # Bastion::stop();
+    /// # Bastion::block_until_stopped();
+    /// ```
+    ///
+    /// [`Tick`]: ../scheduler/struct.Tick.html
+    /// [`BastionContext::set_tick`]: ../context/struct.BastionContext.html#method.set_tick
+    pub fn with_tick(mut self, interval: Duration) -> Self {
+        trace!(
+            "Children({}): Setting tick interval: {:?}",
+            self.id(),
+            interval
+        );
+        self.tick = Some(interval);
+        self
+    }
+
+    /// Makes this children group accumulate a state from the
+    /// outputs its elements emit (using [`BastionContext::emit`]),
+    /// folding each of them into it with the given closure, in the
+    /// order the group received them.
+    ///
+    /// The accumulated state can be retrieved using
+    /// [`ChildrenRef::accumulator`]. It is kept when elements get
+    /// restarted, and outputs that aren't of the type expected by
+    /// the closure are dropped.
+    ///
+    /// This method returns `self` to allow chaining calls.
+    ///
+    /// # Arguments
+    ///
+    /// * `init` - The initial state.
+    /// * `reduce` - The closure taking the accumulated state and an
+    ///     emitted output, and returning the new state.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use bastion::prelude::*;
+    /// #
+    /// # Bastion::init();
+    /// # Bastion::start();
+    /// #
+    /// let children_ref = Bastion::children(|children| {
+    ///     children
+    ///         .with_redundancy(4)
+    ///         .with_reducer(0u64, |sum: u64, count: u64| sum + count)
+    ///         .with_exec(|ctx: BastionContext| {
+    ///             async move {
+    ///                 // Count something...
+    ///                 ctx.emit(10u64).ok();
+    ///                 Ok(())
+    ///             }
+    ///         })
+    /// }).expect("Couldn't create the children group.");
+    ///
+    /// # run!(async {
+    /// // Eventually, once every element emitted its count...
+    /// let sum: u64 = children_ref
+    ///     .accumulator()
+    ///     .await
+    ///     .expect("Couldn't get the accumulated state.");
+    /// # });
+    /// #
+    /// # Bastion::stop();
+    /// # Bastion::block_until_stopped();
+    /// ```
+    ///
+    /// [`BastionContext::emit`]: ../context/struct.BastionContext.html#method.emit
+    /// [`ChildrenRef::accumulator`]: ../children_ref/struct.ChildrenRef.html#method.accumulator
+    pub fn with_reducer<S, O, R>(mut self, init: S, reduce: R) -> Self
+    where
+        S: Message + Clone,
+        O: Message,
+        R: Fn(S, O) -> S + Send + 'static,
+    {
+        trace!(
+            "Children({}): Setting reducer: {} -> {}",
+            self.id(),
+            type_name::<O>(),
+            type_name::<S>()
+        );
+        self.reducer = Some(Box::new(Accumulator {
+            acc: Some(init),
+            reduce,
+            _output: PhantomData,
+        }));
+        self
+    }
+
+    async fn kill(&mut self) {
+        debug!("Children({}): Killing.", self.id());
+        self.bcast.kill_children();
+
+        let mut children = FuturesOrdered::new();
+        for (id, (_, state, launched)) in self.launched.drain() {
+            launched.cancel();
+            let (received, processed) = state.lock().await.counters();
+            self.retired_received += received;
+            self.retired_processed += processed;
+            self.bcast.system().topics().unsubscribe_all(&id);
+            self.bcast
+                .system()
+                .links()
+                .notify_down(&id, TerminationReason::Killed);
+
+            children.push(launched);
         }
 
+        self.update_len();
+
         let id = self.id();
         children
             .for_each_concurrent(None, |_| async {
@@ -398,35 +2139,148 @@ impl Children {
             .await;
     }
 
+    // Kills the elements of a children group its supervisor refused
+    // to deploy (see `Supervisor::with_capacity`).
+    pub(crate) async fn discard(mut self) {
+        debug!("Children({}): Discarding.", self.id());
+        self.kill().await;
+        self.unregister_name();
+        if let Err(e) = self.remove_dispatchers() {
+            warn!("couldn't remove all dispatchers from the registry: {}", e);
+        };
+        self.bcast.close();
+    }
+
+    // Prepares a stopped children group to be launched again
+    // under the given broadcast, keeping its identifier (see
+    // `SupervisorRef::migrate`).
+    pub(crate) fn relaunch(&mut self, bcast: Broadcast) {
+        debug!("Children({}): Relaunching.", self.id());
+        self.bcast = bcast;
+        self.started = false;
+        self.pre_start_msgs.clear();
+        self.resetting.clear();
+        if let Err(e) = self.register_dispatchers() {
+            warn!("couldn't register all dispatchers into the registry: {}", e);
+        };
+        self.launch_elems();
+    }
+
     fn stopped(&mut self) {
         debug!("Children({}): Stopped.", self.id());
+        self.unregister_name();
         if let Err(e) = self.remove_dispatchers() {
             warn!("couldn't remove all dispatchers from the registry: {}", e);
         };
         self.bcast.stopped();
+        // Stopped children groups are never relaunched.
+        self.bcast.close();
+    }
+
+    fn publish_event(&self, transition: Transition) {
+        self.bcast.system().events().publish(
+            transition,
+            EntityKind::Children,
+            self.id(),
+            self.bcast.parent().id(),
+            self.name.as_deref(),
+        );
     }
 
     fn faulted(&mut self) {
         debug!("Children({}): Faulted.", self.id());
+        // The name stays registered until the restarted group
+        // replaces it.
         if let Err(e) = self.remove_dispatchers() {
             warn!("couldn't remove all dispatchers from the registry: {}", e);
         };
         self.bcast.faulted();
+        self.bcast.close();
     }
 
     async fn kill_children(&mut self) -> Result<(), ()> {
+        let running = !self.launched.is_empty();
         self.kill().await;
         self.stopped();
+        lifecycle_event!("children_killed", children = %self.id(), name = %self.name());
+        self.publish_event(Transition::Stopped);
+        if running {
+            self.fully_stopped();
+        }
         Err(())
     }
 
     async fn stop_children(&mut self) -> Result<(), ()> {
+        let running = !self.launched.is_empty();
         self.kill().await;
+        // An element that never yields can't be cancelled, so the
+        // group only stops once all of them really did (or gets
+        // killed when stopping with a timeout).
+        while self.running.load(Ordering::SeqCst) > 0 {
+            Delay::new(Duration::from_millis(1)).await;
+        }
         self.stopped();
+        lifecycle_event!("children_stopped", children = %self.id(), name = %self.name());
+        self.publish_event(Transition::Stopped);
+        if running {
+            self.fully_stopped();
+        }
         Err(())
     }
 
+    // Called once the last element of the group stopped, either
+    // on its own or because the group was stopped or killed (but
+    // not when the group faulted).
+    fn fully_stopped(&self) {
+        debug!("Children({}): All elements stopped.", self.id());
+        if let Some(OnFullStop(on_full_stop)) = &self.on_full_stop {
+            on_full_stop();
+        }
+    }
+
+    fn pause_children(&mut self, env: Envelope) {
+        debug!("Children({}): Pausing elements.", self.id());
+        self.paused = true;
+        self.bcast.send_children(env);
+    }
+
+    fn resume_children(&mut self, env: Envelope) {
+        debug!("Children({}): Resuming elements.", self.id());
+        self.paused = false;
+        self.bcast.send_children(env);
+    }
+
+    // Takes the messages queued in the mailboxes of the paused
+    // elements, keeping a single copy of the broadcasted ones.
+    async fn drain_children(&mut self) -> Result<Vec<Msg>, DrainError> {
+        if !self.paused {
+            debug!("Children({}): Can't drain running elements.", self.id());
+            return Err(DrainError::NotPaused);
+        }
+
+        let mut drained: Vec<Msg> = Vec::new();
+        for (_, state, _) in self.launched.values() {
+            for msg in state.lock().await.drain_messages() {
+                if !drained.iter().any(|other| other.same_broadcast(&msg)) {
+                    drained.push(msg);
+                }
+            }
+        }
+
+        debug!(
+            "Children({}): Drained {} messages.",
+            self.id(),
+            drained.len()
+        );
+        Ok(drained)
+    }
+
     async fn handle_stopped_child(&mut self, id: &BastionId) -> Result<(), ()> {
+        if self.resetting.remove(id) {
+            trace!("Children({}): Child({}) stopped to restart.", self.id(), id);
+            return Ok(());
+        }
+
         // FIXME: Err if false?
         if self.launched.contains_key(&id) {
             debug!("Children({}): Child({}) stopped.", self.id(), id);
@@ -435,6 +2289,10 @@ impl Children {
             let msg = BastionMessage::finished_child(id.clone(), self.bcast.id().clone());
             let env = Envelope::new(msg, self.bcast.path().clone(), self.bcast.sender().clone());
             self.bcast.send_parent(env).ok();
+
+            if self.launched.is_empty() {
+                self.fully_stopped();
+            }
         }
 
         Ok(())
@@ -444,6 +2302,13 @@ impl Children {
         // FIXME: Err if false?
         if self.launched.contains_key(id) {
             warn!("Children({}): Child({}) faulted.", self.id(), id);
+            lifecycle_event!(
+                "children_faulted",
+                children = %self.id(),
+                name = %self.name(),
+                element = %id,
+            );
+            self.publish_event(Transition::Faulted);
             self.kill().await;
             self.faulted();
 
@@ -453,43 +2318,121 @@ impl Children {
         Ok(())
     }
 
-    fn request_restarting_child(&mut self, id: &BastionId, parent_id: &BastionId) {
+    async fn request_restarting_child(
+        &mut self,
+        id: &BastionId,
+        parent_id: &BastionId,
+        mut strategy: Option<SupervisionStrategy>,
+        panicked: bool,
+    ) -> Result<(), ()> {
         if parent_id == self.bcast.id() && self.launched.contains_key(id) {
+            if strategy.is_none() {
+                self.bcast.system().events().publish(
+                    Transition::Faulted,
+                    EntityKind::Element,
+                    id,
+                    Some(self.id()),
+                    self.name.as_deref(),
+                );
+            }
+
+            // Elements asking to be restarted already come with
+            // the strategy to use.
+            if let (None, Some(SupervisionHook(hook))) = (&strategy, &self.supervision_hook) {
+                let event = FaultEvent {
+                    child_id: id.clone(),
+                    restart_count: self.element_restarts.get(id).copied().unwrap_or(0),
+                    fault_kind: if panicked {
+                        FaultKind::Panic
+                    } else {
+                        FaultKind::Error
+                    },
+                };
+                let decision = hook(event);
+                debug!(
+                    "Children({}): Child({}) faulted, the supervision hook decided: {:?}",
+                    self.id(),
+                    id,
+                    decision
+                );
+                match decision {
+                    SupervisionDecision::Restart => {
+                        strategy = Some(SupervisionStrategy::OneForOne);
+                    }
+                    SupervisionDecision::Stop => return self.handle_stopped_child(id).await,
+                    SupervisionDecision::Escalate => (),
+                }
+            }
+
             let parent_id = self.bcast.id().clone();
-            let msg = BastionMessage::restart_required(id.clone(), parent_id);
+            let msg = BastionMessage::restart_required(id.clone(), parent_id, strategy, panicked);
             let env = Envelope::new(msg, self.bcast.path().clone(), self.bcast.sender().clone());
             self.bcast.send_parent(env).ok();
         }
+
+        Ok(())
     }
 
     fn restart_child(&mut self, old_id: &BastionId, old_state: Arc<Mutex<Pin<Box<ContextState>>>>) {
+        logger::with_logger(|logger| logger.log_restart(old_id));
+        self.restarts += 1;
+        *self.element_restarts.entry(old_id.clone()).or_insert(0) += 1;
+        lifecycle_event!(
+            "element_restarted",
+            children = %self.id(),
+            name = %self.name(),
+            element = %old_id,
+        );
+        self.bcast.system().events().publish(
+            Transition::Restarted,
+            EntityKind::Element,
+            old_id,
+            Some(self.id()),
+            self.name.as_deref(),
+        );
+        // A sibling restarted along with a faulted element is still
+        // running, and would otherwise never get polled nor dropped.
+        if let Some((_, _, launched)) = self.launched.remove(old_id) {
+            launched.cancel();
+        }
+        self.bcast
+            .system()
+            .restarts()
+            .fetch_add(1, Ordering::SeqCst);
         let parent = Parent::children(self.as_ref());
         let bcast = Broadcast::new(parent, BastionPathElement::Child(old_id.clone()));
 
         let id = bcast.id().clone();
         let sender = bcast.sender().clone();
         let path = bcast.path().clone();
-        let child_ref = ChildRef::new(id.clone(), sender.clone(), self.name(), path);
+        let system = self.bcast.system().clone();
+        let child_ref = ChildRef::new(id.clone(), sender.clone(), self.name(), path, system);
 
         let children = self.as_ref();
         let supervisor = self.bcast.parent().clone().into_supervisor();
 
-        let state = Arc::new(Mutex::new(Box::pin(ContextState::new())));
+        // The restarted element shares its state with the context
+        // given to its future, so that it receives the messages it
+        // didn't receive before restarting and the ones sent to it
+        // afterwards.
+        let state = old_state;
+        let ticker = Arc::new(Ticker::new(self.tick));
 
+        let mailbox = Mailbox::new(&bcast, self.interceptors.clone());
+        let pid = next_pid();
         let ctx = BastionContext::new(
             id.clone(),
+            pid,
             child_ref.clone(),
             children,
             supervisor,
             state.clone(),
+            mailbox.clone(),
+            ticker.clone(),
         );
-        let exec = (self.init.0)(ctx);
+        let exec = self.restarted_exec(&id, ctx);
 
-        self.bcast.register(&bcast);
-
-        let msg = BastionMessage::set_state(old_state);
-        let env = Envelope::new(msg, self.bcast.path().clone(), self.bcast.sender().clone());
-        self.bcast.send_child(&id, env);
+        self.bcast.register(&bcast);
 
         let msg = BastionMessage::apply_callback(CallbackType::AfterRestart);
         let env = Envelope::new(msg, self.bcast.path().clone(), self.bcast.sender().clone());
@@ -499,17 +2442,64 @@ impl Children {
         let env = Envelope::new(msg, self.bcast.path().clone(), self.bcast.sender().clone());
         self.bcast.send_child(&id, env);
 
+        if self.paused {
+            let msg = BastionMessage::pause();
+            let env = Envelope::new(msg, self.bcast.path().clone(), self.bcast.sender().clone());
+            self.bcast.send_child(&id, env);
+        }
+
         debug!("Children({}): Restarting Child({}).", self.id(), bcast.id());
         let callbacks = self.callbacks.clone();
-        let child = Child::new(exec, callbacks, bcast, state, child_ref);
+        let running = RunningGuard::new(self.running.clone());
+        let child = Child::new(
+            exec,
+            pid,
+            callbacks,
+            mailbox,
+            bcast,
+            state.clone(),
+            child_ref,
+            ticker,
+            running,
+        );
         debug!(
             "Children({}): Launching faulted Child({}).",
             self.id(),
             child.id(),
         );
         let id = child.id().clone();
-        let launched = child.launch();
-        self.launched.insert(id, (sender, launched));
+        let launched = child.launch(self.next_spawn_strategy());
+        self.launched.insert(id, (sender, state, launched));
+        self.update_len();
+        // Lookups should return the restarted element.
+        self.register_name();
+    }
+
+    async fn reset_child(&mut self, id: &BastionId, state: Arc<Mutex<Pin<Box<ContextState>>>>) {
+        let (_, _, launched) = match self.launched.remove(id) {
+            Some(launched) => launched,
+            None => {
+                warn!(
+                    "Children({}): Can't restart unknown Child({}).",
+                    self.id(),
+                    id
+                );
+                return;
+            }
+        };
+
+        debug!(
+            "Children({}): Stopping Child({}) to restart it.",
+            self.id(),
+            id
+        );
+        self.resetting.insert(id.clone());
+        self.bcast.stop_child(id);
+        launched.await;
+
+        state.lock().await.reset();
+        self.callbacks.before_restart();
+        self.restart_child(id, state);
     }
 
     fn drop_child(&mut self, id: &BastionId) {
@@ -518,7 +2508,146 @@ impl Children {
             self.id(),
             id,
         );
-        self.launched.remove_entry(id);
+        if let Some((_, state, _)) = self.launched.remove(id) {
+            // The element stopped, releasing its state.
+            if let Some(state) = state.try_lock() {
+                let (received, processed) = state.counters();
+                self.retired_received += received;
+                self.retired_processed += processed;
+            }
+        }
+        self.sibling_inits.remove(id);
+        self.element_restarts.remove(id);
+        self.update_len();
+    }
+
+    // Returns the closure the given element was created with.
+    fn init(&self, id: &BastionId) -> &Init {
+        self.sibling_inits.get(id).unwrap_or(&self.init)
+    }
+
+    // Returns the future run by a restarted element, which first
+    // runs the fallback one until it returns, if there is one.
+    fn restarted_exec(&self, id: &BastionId, ctx: BastionContext) -> Exec {
+        let fallback = match &self.fallback {
+            Some(fallback) => (fallback.0)(ctx.clone()),
+            None => return (self.init(id).0)(ctx),
+        };
+        let exec = (self.init(id).0)(ctx);
+
+        let id = id.clone();
+        Exec(Box::pin(async move {
+            match fallback.0.await {
+                FaultPolicy::StopChild => {
+                    debug!("Child({}): Fallback returned, resuming exec.", id);
+                    exec.0.await
+                }
+                policy => {
+                    debug!("Child({}): Fallback faulted: {:?}", id, policy);
+                    policy
+                }
+            }
+        }))
+    }
+
+    fn add_child(&mut self, init: Init, bcast: Broadcast) {
+        debug!(
+            "Children({}): Adding Child({}) spawned by a sibling.",
+            self.id(),
+            bcast.id()
+        );
+        let id = bcast.id().clone();
+        self.sibling_inits.insert(id.clone(), init);
+        let state = self.element_state();
+        self.launch_elem(bcast, state);
+
+        // The group already started its other elements.
+        let msg = BastionMessage::start();
+        let env = Envelope::new(msg, self.bcast.path().clone(), self.bcast.sender().clone());
+        self.bcast.send_child(&id, env);
+
+        if self.paused {
+            let msg = BastionMessage::pause();
+            let env = Envelope::new(msg, self.bcast.path().clone(), self.bcast.sender().clone());
+            self.bcast.send_child(&id, env);
+        }
+
+        self.update_len();
+        // Lookups should return the new element too.
+        self.register_name();
+    }
+
+    // Returns the group's counters, summed over its launched
+    // elements and the ones that were retired.
+    fn metrics(&self) -> impl Future<Output = ChildrenMetrics> {
+        let mut metrics = ChildrenMetrics {
+            id: self.id().clone(),
+            name: self.name.clone(),
+            received: self.retired_received,
+            processed: self.retired_processed,
+            restarts: self.restarts,
+        };
+        let states = self
+            .launched
+            .values()
+            .map(|(_, state, _)| state.clone())
+            .collect::<Vec<_>>();
+
+        async move {
+            for state in states {
+                let (received, processed) = state.lock().await.counters();
+                metrics.received += received;
+                metrics.processed += processed;
+            }
+
+            metrics
+        }
+    }
+
+    // Pings the launched elements, answering with their statuses
+    // once they all answered or the health check timed out.
+    fn health_check(&self, reply: oneshot::Sender<Vec<(BastionId, HealthStatus)>>) {
+        debug!("Children({}): Checking health.", self.id());
+        let timeout = self.health_check_timeout;
+        let pinged = Instant::now();
+        let mut pongs = Vec::with_capacity(self.launched.len());
+        for (id, (sender, _, _)) in &self.launched {
+            let (pong, receiver) = oneshot::channel();
+            let msg = BastionMessage::ping(pong);
+            let env = Envelope::new(msg, self.bcast.path().clone(), self.bcast.sender().clone());
+            // The element might have stopped meanwhile, dropping the
+            // ping without answering.
+            sender.unbounded_send(env).ok();
+
+            let id = id.clone();
+            pongs.push(async move {
+                let status = match future::select(receiver, Delay::new(timeout)).await {
+                    Either::Left((Ok(()), _)) => {
+                        let elapsed = pinged.elapsed();
+                        if elapsed > timeout / 2 {
+                            HealthStatus::Slow(elapsed)
+                        } else {
+                            HealthStatus::Healthy
+                        }
+                    }
+                    _ => HealthStatus::Unresponsive,
+                };
+
+                (id, status)
+            });
+        }
+
+        // The elements are waited for without blocking the group.
+        let id = self.id().clone();
+        executor::spawn_proc(
+            async move {
+                let statuses = future::join_all(pongs).await;
+                trace!("Children({}): Health: {:?}", id, statuses);
+                // The sender might have stopped waiting for them.
+                reply.send(statuses).ok();
+            },
+            ProcStack::default(),
+        );
     }
 
     async fn handle(&mut self, envelope: Envelope) -> Result<(), ()> {
@@ -535,6 +2664,14 @@ impl Children {
                 msg: BastionMessage::Kill,
                 ..
             } => self.kill_children().await?,
+            Envelope {
+                msg: BastionMessage::Pause,
+                ..
+            } => self.pause_children(envelope),
+            Envelope {
+                msg: BastionMessage::Resume,
+                ..
+            } => self.resume_children(envelope),
             // FIXME
             Envelope {
                 msg: BastionMessage::Deploy(_),
@@ -558,6 +2695,22 @@ impl Children {
                 msg: BastionMessage::InstantiatedChild { .. },
                 ..
             } => unreachable!(),
+            Envelope {
+                msg: BastionMessage::Message(ref message),
+                ref ack,
+                ..
+            } if ack.is_some() || !message.is_broadcast() => {
+                // Messages waiting for an acknowledgment and messages
+                // that can't be cloned (asked or told) are only sent
+                // to one element, and dropped (thus failing to be
+                // acknowledged or answered) if there is none.
+                debug!(
+                    "Children({}): Sending a message to an element: {:?}",
+                    self.id(),
+                    message
+                );
+                self.send_to_one(envelope);
+            }
             Envelope {
                 msg: BastionMessage::Message(ref message),
                 ..
@@ -567,12 +2720,110 @@ impl Children {
                     self.id(),
                     message
                 );
-                self.bcast.send_children(envelope);
+                self.bcast.try_send_children(envelope);
+            }
+            Envelope {
+                msg: BastionMessage::Emit(output),
+                ..
+            } => match &mut self.reducer {
+                Some(reducer) => {
+                    if let Err(output) = reducer.reduce(output) {
+                        warn!(
+                            "Children({}): Dropping emitted output of unexpected type: {:?}",
+                            self.id(),
+                            output
+                        );
+                    }
+                }
+                None => warn!(
+                    "Children({}): Dropping emitted output without reducer: {:?}",
+                    self.id(),
+                    output
+                ),
+            },
+            Envelope {
+                msg: BastionMessage::AddChild { init, bcast },
+                ..
+            } => self.add_child(init, *bcast),
+            Envelope {
+                msg: BastionMessage::Accumulator { sender },
+                ..
+            } => {
+                // Without a reducer, dropping the sender lets the
+                // requester know that there is no state.
+                if let Some(msg) = self.reducer.as_ref().and_then(|reducer| reducer.current()) {
+                    trace!("Children({}): Accumulated state: {:?}", self.id(), msg);
+                    // The sender might have stopped waiting for it.
+                    sender.send(msg).ok();
+                }
+            }
+            Envelope {
+                msg: BastionMessage::MailboxLens { sender },
+                ..
+            } => {
+                let mut lens = Vec::with_capacity(self.launched.len());
+                for (mailbox, state, _) in self.launched.values() {
+                    lens.push(mailbox.len() + state.lock().await.len());
+                }
+
+                trace!("Children({}): Mailbox lengths: {:?}", self.id(), lens);
+                // The sender might have stopped waiting for them.
+                sender.send(lens).ok();
+            }
+            Envelope {
+                msg: BastionMessage::HealthCheck { sender },
+                ..
+            } => self.health_check(sender),
+            Envelope {
+                msg: BastionMessage::Drain { sender },
+                ..
+            } => {
+                let drained = self.drain_children().await;
+                // The sender might have stopped waiting for them.
+                sender.send(drained).ok();
+            }
+            Envelope {
+                msg: BastionMessage::Ping { .. },
+                ..
+            } => unreachable!(),
+            Envelope {
+                msg: BastionMessage::CountedMessage { msg, counter },
+                sign,
+                trace,
+                ack,
+                deadline,
+            } => {
+                debug!(
+                    "Children({}): Broadcasting a counted message: {:?}",
+                    self.id(),
+                    msg
+                );
+                let envelope = Envelope {
+                    msg: BastionMessage::Message(msg),
+                    sign,
+                    trace,
+                    ack,
+                    deadline,
+                };
+                let reached = self.bcast.try_send_children(envelope);
+                // The dead letters' element isn't counted.
+                if self.id() != &NIL_ID {
+                    counter.add(reached);
+                }
             }
             Envelope {
-                msg: BastionMessage::RestartRequired { id, parent_id },
+                msg:
+                    BastionMessage::RestartRequired {
+                        id,
+                        parent_id,
+                        strategy,
+                        panicked,
+                    },
                 ..
-            } => self.request_restarting_child(&id, &parent_id),
+            } => {
+                self.request_restarting_child(&id, &parent_id, strategy, panicked)
+                    .await?
+            }
             Envelope {
                 msg: BastionMessage::FinishedChild { .. },
                 ..
@@ -588,7 +2839,47 @@ impl Children {
             Envelope {
                 msg: BastionMessage::DropChild { id },
                 ..
-            } => self.drop_child(&id),
+            } => {
+                lifecycle_event!(
+                    "element_dropped",
+                    children = %self.id(),
+                    name = %self.name(),
+                    element = %id,
+                );
+                self.drop_child(&id);
+            }
+            Envelope {
+                msg: BastionMessage::RestartChild { .. },
+                ..
+            } => unreachable!(),
+            Envelope {
+                msg: BastionMessage::ListStopped { .. },
+                ..
+            } => unreachable!(),
+            Envelope {
+                msg: BastionMessage::Inspect { .. },
+                ..
+            } => unreachable!(),
+            Envelope {
+                msg: BastionMessage::DumpState { .. },
+                ..
+            } => unreachable!(),
+            Envelope {
+                msg: BastionMessage::Metrics { reply },
+                ..
+            } => {
+                let snapshot = MetricsSnapshot {
+                    supervisors: Vec::new(),
+                    children: vec![self.metrics().await],
+                };
+                trace!("Children({}): Metrics: {:?}", self.id(), snapshot);
+                // The sender might have stopped waiting for them.
+                reply.send(snapshot).ok();
+            }
+            Envelope {
+                msg: BastionMessage::ResetChild { id, state },
+                ..
+            } => self.reset_child(&id, state).await,
             Envelope {
                 msg: BastionMessage::SetState { .. },
                 ..
@@ -597,15 +2888,113 @@ impl Children {
                 msg: BastionMessage::Stopped { id },
                 ..
             } => self.handle_stopped_child(&id).await?,
+            // Elements stopping after being idle aren't recovered.
+            Envelope {
+                msg: BastionMessage::IdleStop { id },
+                ..
+            } => self.handle_stopped_child(&id).await?,
             Envelope {
                 msg: BastionMessage::Faulted { id },
                 ..
             } => self.handle_faulted_child(&id).await?,
+            Envelope {
+                msg: BastionMessage::Adopt { .. },
+                ..
+            } => unreachable!(),
+            Envelope {
+                msg: BastionMessage::SetParent { parent },
+                ..
+            } => {
+                debug!("Children({}): Adopted.", self.id());
+                self.bcast.set_parent(*parent);
+            }
+            Envelope {
+                msg: BastionMessage::Migrate { .. },
+                ..
+            } => unreachable!(),
+            Envelope {
+                msg: BastionMessage::Relaunch { .. },
+                ..
+            } => unreachable!(),
+            Envelope {
+                msg: BastionMessage::CapacityExceeded { .. },
+                ..
+            } => unreachable!(),
         }
 
         Ok(())
     }
 
+    // Sends the envelope to the first element whose mailbox isn't
+    // full, or to the dead letters if there is none.
+    fn send_to_one(&self, mut envelope: Envelope) {
+        // The dead letters can't send messages to themselves.
+        if self.id() == &NIL_ID || self.launched.is_empty() {
+            if let Some(id) = self.launched.keys().next() {
+                self.bcast.send_child(id, envelope);
+            }
+            return;
+        }
+
+        for id in self.launched.keys() {
+            match self.bcast.try_send_child(id, envelope) {
+                Ok(()) => return,
+                Err(TrySendError::Full(env)) | Err(TrySendError::Disconnected(env)) => {
+                    trace!(
+                        "Children({}): Can't send a message to Child({}).",
+                        self.id(),
+                        id
+                    );
+                    envelope = *env;
+                }
+            }
+        }
+
+        warn!(
+            "Children({}): Every element's mailbox is full, sending the message to the dead letters.",
+            self.id()
+        );
+        let dead_letters = self.bcast.system().dead_letters().sender();
+        dead_letters.unbounded_send(envelope).ok();
+    }
+
+    fn send_scheduled_msgs(&mut self, cx: &mut Context) {
+        for scheduled in self.scheduled_msgs.iter_mut() {
+            let interval = scheduled.interval;
+            let delay = scheduled.delay.get_or_insert_with(|| Delay::new(interval));
+
+            if Pin::new(&mut *delay).poll(cx).is_pending() {
+                continue;
+            }
+
+            delay.reset(interval);
+            // The delay needs to be polled again once reset for
+            // this future to get woken up when it elapses (the
+            // messages being sent next time if it already did).
+            let _ = Pin::new(&mut *delay).poll(cx);
+
+            for (id, (sender, _, _)) in &self.launched {
+                let msg = (scheduled.factory)();
+                trace!(
+                    "Children({}): Sending scheduled message to Child({}): {:?}",
+                    self.bcast.id(),
+                    id,
+                    msg
+                );
+                let env = Envelope::from_dead_letters(msg, self.bcast.system());
+                // Scheduled messages are skipped by elements whose
+                // mailbox is full.
+                if let Err(TrySendError::Full(_)) = sender.try_send(env) {
+                    trace!(
+                        "Children({}): Child({})'s mailbox is full, skipping the scheduled message.",
+                        self.bcast.id(),
+                        id
+                    );
+                }
+            }
+        }
+    }
+
     async fn initialize(&mut self) -> Result<(), ()> {
         trace!(
             "Children({}): Received a new message (started=false): {:?}",
@@ -613,6 +3002,8 @@ impl Children {
             BastionMessage::Start
         );
         debug!("Children({}): Starting.", self.id());
+        lifecycle_event!("children_started", children = %self.id(), name = %self.name());
+        self.publish_event(Transition::Started);
         self.started = true;
 
         let msg = BastionMessage::start();
@@ -638,23 +3029,43 @@ impl Children {
 
     async fn run(mut self) -> Self {
         debug!("Children({}): Launched.", self.id());
+        // The dead letters' children group isn't counted.
+        let _running = if self.id() == &NIL_ID {
+            None
+        } else {
+            Some(RunningGuard::new(
+                self.bcast.system().children_groups().clone(),
+            ))
+        };
 
         loop {
-            for (_, launched) in self.launched.values_mut() {
-                let _ = poll!(launched);
-            }
+            // Parks until the next message arrives, while getting
+            // woken up to notice the elements that panicked and to
+            // send the scheduled messages.
+            let msg = future::poll_fn(|cx| {
+                for (_, _, launched) in self.launched.values_mut() {
+                    let _ = Pin::new(launched).poll(cx);
+                }
+
+                if self.started {
+                    self.send_scheduled_msgs(cx);
+                }
 
-            match poll!(&mut self.bcast.next()) {
+                self.bcast.poll_next_unpin(cx)
+            })
+            .await;
+
+            match msg {
                 // TODO: Err if started == true?
-                Poll::Ready(Some(Envelope {
+                Some(Envelope {
                     msg: BastionMessage::Start,
                     ..
-                })) => {
+                }) => {
                     if self.initialize().await.is_err() {
                         return self;
                     }
                 }
-                Poll::Ready(Some(msg)) if !self.started => {
+                Some(msg) if !self.started => {
                     trace!(
                         "Children({}): Received a new message (started=false): {:?}",
                         self.id(),
@@ -662,7 +3073,7 @@ impl Children {
                     );
                     self.pre_start_msgs.push(msg);
                 }
-                Poll::Ready(Some(msg)) => {
+                Some(msg) => {
                     trace!(
                         "Children({}): Received a new message (started=true): {:?}",
                         self.id(),
@@ -675,8 +3086,7 @@ impl Children {
                 // NOTE: because `Broadcast` always holds both a `Sender` and
                 //      `Receiver` of the same channel, this would only be
                 //      possible if the channel was closed, which never happens.
-                Poll::Ready(None) => unreachable!(),
-                Poll::Pending => pending!(),
+                None => unreachable!(),
             }
         }
     }
@@ -684,61 +3094,125 @@ impl Children {
     pub(crate) fn launch_elems(&mut self) {
         debug!("Children({}): Launching elements.", self.id());
 
-        let name = self.name();
+        let mut replayed = match &self.store {
+            Some(store) => store.drain(),
+            None => Vec::new(),
+        };
+        if !replayed.is_empty() {
+            debug!(
+                "Children({}): Replaying {} stored messages.",
+                self.id(),
+                replayed.len()
+            );
+        }
+
         for _ in 0..self.redundancy {
             let parent = Parent::children(self.as_ref());
             let bcast = Broadcast::new(parent, BastionPathElement::Child(BastionId::new()));
 
-            // TODO: clone or ref?
-            let id = bcast.id().clone();
-            let sender = bcast.sender().clone();
-            let path = bcast.path().clone();
-            let child_ref = ChildRef::new(id.clone(), sender.clone(), name.clone(), path);
+            let mut state = self.element_state();
+            for msg in replayed.drain(..) {
+                state.push_message(msg, RefAddr::dead_letters(self.bcast.system()), None, None);
+            }
+            self.launch_elem(bcast, state);
+        }
+
+        self.update_len();
+        self.register_name();
+    }
+
+    // Creates the state of a new element.
+    fn element_state(&self) -> ContextState {
+        let mut state = ContextState::new(self.bcast.system().clone())
+            .with_expiry(self.expired.clone(), self.expired_to_dead_letters)
+            .with_store(self.store.clone());
+        if let Some(stash_capacity) = self.stash_capacity {
+            state = state.with_stash_capacity(stash_capacity);
+        }
+        if let Some(poll_budget) = self.poll_budget {
+            state = state.with_poll_budget(poll_budget);
+        }
+        if let Some(exec_timeout) = self.exec_timeout {
+            state = state.with_exec_timeout(exec_timeout);
+        }
+        if let Some(warning_threshold) = self.warning_threshold {
+            state = state.with_warning_threshold(warning_threshold);
+        }
+        if let Some(idle_timeout) = self.idle_timeout {
+            state = state.with_idle_timeout(idle_timeout);
+        }
 
-            let children = self.as_ref();
-            let supervisor = self.bcast.parent().clone().into_supervisor();
+        state
+    }
 
-            let state = Arc::new(Mutex::new(Box::pin(ContextState::new())));
+    fn launch_elem(&mut self, bcast: Broadcast, state: ContextState) {
+        // TODO: clone or ref?
+        let id = bcast.id().clone();
+        let sender = bcast.sender().clone();
+        let path = bcast.path().clone();
+        let system = self.bcast.system().clone();
+        let child_ref = ChildRef::new(id.clone(), sender.clone(), self.name(), path, system);
 
-            let ctx = BastionContext::new(
-                id.clone(),
-                child_ref.clone(),
-                children,
-                supervisor,
-                state.clone(),
-            );
-            let exec = (self.init.0)(ctx);
+        let children = self.as_ref();
+        let supervisor = self.bcast.parent().clone().into_supervisor();
 
-            let parent_id = self.bcast.id().clone();
-            let msg = BastionMessage::instantiated_child(parent_id, id.clone(), state.clone());
-            let env = Envelope::new(msg, self.bcast.path().clone(), self.bcast.sender().clone());
-            self.bcast.send_parent(env).ok();
+        let state = Arc::new(Mutex::new(Box::pin(state)));
+        let ticker = Arc::new(Ticker::new(self.tick));
 
-            self.bcast.register(&bcast);
+        let mailbox = Mailbox::new(&bcast, self.interceptors.clone());
+        let pid = next_pid();
+        let ctx = BastionContext::new(
+            id.clone(),
+            pid,
+            child_ref.clone(),
+            children,
+            supervisor,
+            state.clone(),
+            mailbox.clone(),
+            ticker.clone(),
+        );
+        let exec = (self.init(&id).0)(ctx);
 
-            debug!(
-                "Children({}): Initializing Child({}).",
-                self.id(),
-                bcast.id()
-            );
-            let callbacks = self.callbacks.clone();
-            let child = Child::new(exec, callbacks, bcast, state, child_ref);
-            debug!("Children({}): Launching Child({}).", self.id(), child.id());
-            let id = child.id().clone();
-            let launched = child.launch();
-            self.launched.insert(id, (sender, launched));
-        }
+        let parent_id = self.bcast.id().clone();
+        let msg = BastionMessage::instantiated_child(parent_id, id.clone(), state.clone());
+        let env = Envelope::new(msg, self.bcast.path().clone(), self.bcast.sender().clone());
+        self.bcast.send_parent(env).ok();
+
+        self.bcast.register(&bcast);
+
+        debug!(
+            "Children({}): Initializing Child({}).",
+            self.id(),
+            bcast.id()
+        );
+        let callbacks = self.callbacks.clone();
+        let running = RunningGuard::new(self.running.clone());
+        let child = Child::new(
+            exec,
+            pid,
+            callbacks,
+            mailbox,
+            bcast,
+            state.clone(),
+            child_ref,
+            ticker,
+            running,
+        );
+        debug!("Children({}): Launching Child({}).", self.id(), child.id());
+        let id = child.id().clone();
+        let launched = child.launch(self.next_spawn_strategy());
+        self.launched.insert(id, (sender, state, launched));
     }
 
     pub(crate) fn launch(self) -> RecoverableHandle<Self> {
         debug!("Children({}): Launching.", self.id());
         let stack = self.stack();
-        pool::spawn(self.run(), stack)
+        executor::spawn_proc(self.run(), stack)
     }
 
     /// Registers all declared local dispatchers in the global dispatcher.
     pub(crate) fn register_dispatchers(&self) -> AnyResult<()> {
-        let global_dispatcher = SYSTEM.dispatcher();
+        let global_dispatcher = self.bcast.system().dispatcher();
 
         for dispatcher in self.dispatchers.iter() {
             global_dispatcher.register_dispatcher(dispatcher)?;
@@ -748,7 +3222,7 @@ impl Children {
 
     /// Removes all declared local dispatchers from the global dispatcher.
     pub(crate) fn remove_dispatchers(&self) -> AnyResult<()> {
-        let global_dispatcher = SYSTEM.dispatcher();
+        let global_dispatcher = self.bcast.system().dispatcher();
 
         for dispatcher in self.dispatchers.iter() {
             global_dispatcher.remove_dispatcher(dispatcher)?;
@@ -756,3 +3230,60 @@ impl Children {
         Ok(())
     }
 }
+
+impl Debug for OnFullStop {
+    fn fmt(&self, fmt: &mut Formatter) -> fmt::Result {
+        fmt.debug_struct("OnFullStop").finish()
+    }
+}
+
+impl Debug for SupervisionHook {
+    fn fmt(&self, fmt: &mut Formatter) -> fmt::Result {
+        fmt.debug_struct("SupervisionHook").finish()
+    }
+}
+
+impl Debug for ScheduledMessage {
+    fn fmt(&self, fmt: &mut Formatter) -> fmt::Result {
+        fmt.debug_struct("ScheduledMessage")
+            .field("interval", &self.interval)
+            .finish()
+    }
+}
+
+impl<S, O, R> Reducer for Accumulator<S, O, R>
+where
+    S: Message + Clone,
+    O: Message,
+    R: Fn(S, O) -> S + Send + 'static,
+{
+    fn reduce(&mut self, output: Msg) -> Result<(), Msg> {
+        let output = output.downcast::<O>()?;
+        if let Some(acc) = self.acc.take() {
+            self.acc = Some((self.reduce)(acc, output));
+        }
+
+        Ok(())
+    }
+
+    fn current(&self) -> Option<Msg> {
+        self.acc.clone().map(Msg::tell)
+    }
+}
+
+impl<S: Debug, O, R> Debug for Accumulator<S, O, R> {
+    fn fmt(&self, fmt: &mut Formatter) -> fmt::Result {
+        fmt.debug_struct("Accumulator")
+            .field("acc", &self.acc)
+            .finish()
+    }
+}
+
+impl From<Result<(), ()>> for FaultPolicy {
+    fn from(res: Result<(), ()>) -> Self {
+        match res {
+            Ok(()) => FaultPolicy::StopChild,
+            Err(()) => FaultPolicy::EscalateToSupervisor,
+        }
+    }
+}
@@ -2,15 +2,26 @@
 //! Allows users to communicate with children through the mailboxes.
 use crate::broadcast::Sender;
 use crate::child_ref::ChildRef;
+#[cfg(feature = "codec")]
+use crate::codec::EncodedEnvelope;
 use crate::context::BastionId;
 use crate::dispatcher::DispatcherType;
-use crate::envelope::Envelope;
-use crate::message::{BastionMessage, Message};
+use crate::envelope::{DeliveryError, Envelope, SignedMessage, TraceId};
+use crate::message::{AskError, BastionMessage, Message, Msg, Recipients};
 use crate::path::BastionPath;
-use crate::system::SYSTEM;
+use crate::runtime::RuntimeId;
+use crate::scheduler::ScheduledSend;
+use crate::system::GlobalSystem;
+use futures::channel::oneshot;
+use futures::future::{self, Either};
+use futures_timer::Delay;
 use std::cmp::{Eq, PartialEq};
+use std::collections::HashMap;
 use std::fmt::Debug;
+use std::future::Future;
+use std::sync::atomic::{AtomicUsize, Ordering};
 use std::sync::Arc;
+use std::time::{Duration, Instant};
 use tracing::{debug, trace};
 
 #[derive(Debug, Clone)]
@@ -18,26 +29,98 @@ use tracing::{debug, trace};
 /// with it.
 pub struct ChildrenRef {
     id: BastionId,
+    // The pid set on the children group's process.
+    pid: usize,
     sender: Sender,
     path: Arc<BastionPath>,
     children: Vec<ChildRef>,
     dispatchers: Vec<DispatcherType>,
+    name: Option<String>,
+    tags: Arc<HashMap<String, String>>,
+    len: Arc<AtomicUsize>,
+    expired: Arc<AtomicUsize>,
+    next_call: Arc<AtomicUsize>,
+    // The system of the runtime the children group belongs to.
+    system: Arc<GlobalSystem>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+/// The reason why a call made using [`ChildrenRef::call`] (or
+/// one of its variants) failed.
+///
+/// [`ChildrenRef::call`]: struct.ChildrenRef.html#method.call
+pub enum CallError {
+    /// The reply wasn't received before the timeout elapsed.
+    Timeout,
+    /// The element the call was sent to stopped without replying.
+    ActorDied,
+    /// The children group had no running element to send the
+    /// call to.
+    NoAvailableChildren,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+/// The status of an element of a children group, as reported by
+/// [`ChildrenRef::health_check`].
+///
+/// [`ChildrenRef::health_check`]: struct.ChildrenRef.html#method.health_check
+pub enum HealthStatus {
+    /// The element answered quickly.
+    Healthy,
+    /// The element answered, but took more than half of its
+    /// group's health check timeout (see
+    /// [`Children::with_health_check_timeout`]) to do so.
+    ///
+    /// [`Children::with_health_check_timeout`]: ../children/struct.Children.html#method.with_health_check_timeout
+    Slow(Duration),
+    /// The element didn't answer before its group's health check
+    /// timeout elapsed, or stopped without answering.
+    Unresponsive,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+/// The reason why [`ChildrenRef::drain`] failed.
+///
+/// [`ChildrenRef::drain`]: struct.ChildrenRef.html#method.drain
+pub enum DrainError {
+    /// The children group wasn't paused (see
+    /// [`ChildrenRef::pause`]).
+    ///
+    /// [`ChildrenRef::pause`]: struct.ChildrenRef.html#method.pause
+    NotPaused,
+    /// The children group stopped without answering.
+    Stopped,
 }
 
 impl ChildrenRef {
+    #[allow(clippy::too_many_arguments)]
     pub(crate) fn new(
         id: BastionId,
+        pid: usize,
         sender: Sender,
         path: Arc<BastionPath>,
         children: Vec<ChildRef>,
         dispatchers: Vec<DispatcherType>,
+        name: Option<String>,
+        tags: Arc<HashMap<String, String>>,
+        len: Arc<AtomicUsize>,
+        expired: Arc<AtomicUsize>,
+        next_call: Arc<AtomicUsize>,
+        system: Arc<GlobalSystem>,
     ) -> Self {
         ChildrenRef {
             id,
+            pid,
             sender,
             path,
             children,
             dispatchers,
+            name,
+            tags,
+            len,
+            expired,
+            next_call,
+            system,
         }
     }
 
@@ -69,6 +152,63 @@ impl ChildrenRef {
         &self.id
     }
 
+    /// Returns the pid set on the process of the children group
+    /// this `ChildrenRef` is referencing.
+    ///
+    /// Note that the pids of the group's elements differ from it
+    /// (see [`BastionContext::pid`]).
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use bastion::prelude::*;
+    /// #
+    /// # Bastion::init();
+    /// #
+    /// let children_ref = Bastion::children(|children| {
+    ///     // ...
+    /// # children
+    /// }).expect("Couldn't create the children group.");
+    ///
+    /// let children_pid: usize = children_ref.pid();
+    /// #
+    /// # Bastion::start();
+    /// # Bastion::stop();
+    /// # Bastion::block_until_stopped();
+    /// ```
+    ///
+    /// [`BastionContext::pid`]: ../context/struct.BastionContext.html#method.pid
+    pub fn pid(&self) -> usize {
+        self.pid
+    }
+
+    /// Returns the identifier of the [`BastionRuntime`] the children group
+    /// this `ChildrenRef` is referencing belongs to.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use bastion::prelude::*;
+    /// #
+    /// let runtime = BastionRuntime::new(Config::new());
+    ///
+    /// let r = runtime.children(|children| {
+    ///     // ...
+    ///     # children
+    /// }).expect("Couldn't create the children group.");
+    ///
+    /// assert_eq!(r.runtime_id(), runtime.id());
+    /// #
+    /// # runtime.start();
+    /// # runtime.stop();
+    /// # runtime.block_until_stopped();
+    /// ```
+    ///
+    /// [`BastionRuntime`]: ../struct.BastionRuntime.html
+    pub fn runtime_id(&self) -> RuntimeId {
+        self.system.id()
+    }
+
     /// Returns a list of dispatcher names that can be used for
     /// comminucation with other actors in the same group(s).
     ///
@@ -115,6 +255,87 @@ impl ChildrenRef {
         &self.children
     }
 
+    /// Returns the number of elements currently running in the
+    /// children group this `ChildrenRef` is referencing.
+    ///
+    /// Unlike [`elems`], which is a snapshot taken when this
+    /// `ChildrenRef` was created, this is kept up to date by the
+    /// children group as its elements are launched and stopped
+    /// and can be called from any thread without waiting.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use bastion::prelude::*;
+    /// #
+    /// # Bastion::init();
+    /// #
+    /// let children_ref = Bastion::children(|children| {
+    ///     children.with_redundancy(4)
+    /// }).expect("Couldn't create the children group.");
+    ///
+    /// assert_eq!(children_ref.len(), 4);
+    /// #
+    /// # Bastion::start();
+    /// # Bastion::stop();
+    /// # Bastion::block_until_stopped();
+    /// ```
+    ///
+    /// [`elems`]: #method.elems
+    pub fn len(&self) -> usize {
+        self.len.load(Ordering::SeqCst)
+    }
+
+    /// Returns whether the children group this `ChildrenRef` is
+    /// referencing currently has no running element (see [`len`]).
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use bastion::prelude::*;
+    /// #
+    /// # Bastion::init();
+    /// #
+    /// # let children_ref = Bastion::children(|children| children).unwrap();
+    /// if children_ref.is_empty() {
+    ///     // All the elements of the group stopped...
+    /// }
+    /// #
+    /// # Bastion::start();
+    /// # Bastion::stop();
+    /// # Bastion::block_until_stopped();
+    /// ```
+    ///
+    /// [`len`]: #method.len
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Returns the number of messages that the elements of the
+    /// children group this `ChildrenRef` is referencing didn't
+    /// receive because they expired first (see
+    /// [`broadcast_with_ttl`]).
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use bastion::prelude::*;
+    /// #
+    /// # Bastion::init();
+    /// #
+    /// # let children_ref = Bastion::children(|children| children).unwrap();
+    /// println!("{} requests expired.", children_ref.expired_count());
+    /// #
+    /// # Bastion::start();
+    /// # Bastion::stop();
+    /// # Bastion::block_until_stopped();
+    /// ```
+    ///
+    /// [`broadcast_with_ttl`]: #method.broadcast_with_ttl
+    pub fn expired_count(&self) -> usize {
+        self.expired.load(Ordering::SeqCst)
+    }
+
     /// Sends a message to the children group this `ChildrenRef`
     /// is referencing which will then send it to all of its
     /// elements.
@@ -174,18 +395,97 @@ impl ChildrenRef {
             msg
         );
         let msg = BastionMessage::broadcast(msg);
-        let env = Envelope::from_dead_letters(msg);
+        let env = Envelope::from_dead_letters(msg, &self.system);
         // FIXME: panics?
         self.send(env).map_err(|err| err.into_msg().unwrap())
     }
 
     /// Sends a message to the children group this `ChildrenRef`
-    /// is referencing to tell it to stop all of its running
-    /// elements.
+    /// is referencing which will then send it to all of its
+    /// elements, like [`broadcast`] does, and counts the elements'
+    /// mailboxes the message was enqueued into.
     ///
-    /// This method returns `()` if it succeeded, or `Err(())`
+    /// This method returns a [`Recipients`] which resolves to the
+    /// number of mailboxes the message reached once the children
+    /// group handled it (a group that isn't started yet handles
+    /// it once started), which is zero if the children group
+    /// already stopped.
+    ///
+    /// # Arguments
+    ///
+    /// * `msg` - The message to send.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use bastion::prelude::*;
+    /// #
+    /// # fn main() {
+    ///     # Bastion::init();
+    ///     # Bastion::start();
+    ///     #
+    /// let children_ref = Bastion::children(|children| {
+    ///     children
+    ///         .with_redundancy(2)
+    ///         .with_exec(|ctx: BastionContext| {
+    ///             async move {
+    ///                 loop {
+    ///                     ctx.recv().await?;
+    ///                 }
+    ///             }
+    ///         })
+    /// }).expect("Couldn't create the children group.");
+    ///
+    /// let msg = "A message containing data.";
+    /// let recipients = children_ref.broadcast_counted(msg);
+    /// assert_eq!(run!(recipients), 2);
+    ///     #
+    ///     # Bastion::stop();
+    ///     # Bastion::block_until_stopped();
+    /// # }
+    /// ```
+    ///
+    /// [`broadcast`]: #method.broadcast
+    /// [`Recipients`]: ../message/struct.Recipients.html
+    pub fn broadcast_counted<M: Message>(&self, msg: M) -> Recipients {
+        debug!(
+            "ChildrenRef({}): Broadcasting counted message: {:?}",
+            self.id(),
+            msg
+        );
+        let (msg, recipients) = BastionMessage::broadcast_counted(msg);
+        let env = Envelope::from_dead_letters(msg, &self.system);
+        // The message isn't sent to the dead letters if the children
+        // group stopped, as it would then be counted as reaching them.
+        if let Err(err) = self.sender.unbounded_send(env) {
+            trace!(
+                "ChildrenRef({}): Children group stopped, message reached nobody: {:?}",
+                self.id(),
+                err
+            );
+        }
+
+        recipients
+    }
+
+    /// Sends a message to the children group this `ChildrenRef`
+    /// is referencing which will then send it to all of its
+    /// elements, attaching the given [`TraceId`] to it.
+    ///
+    /// The elements receiving the message can retrieve the
+    /// `TraceId` using [`BastionContext::current_trace`] and it
+    /// will automatically be attached to the messages they send
+    /// while handling it.
+    ///
+    /// This method returns `()` if it succeeded, or `Err(msg)`
     /// otherwise.
     ///
+    /// # Arguments
+    ///
+    /// * `msg` - The message to send.
+    /// * `trace` - The correlation identifier to attach to the
+    ///     message.
+    ///
     /// # Example
     ///
     /// ```rust
@@ -194,65 +494,1043 @@ impl ChildrenRef {
     /// # Bastion::init();
     /// #
     /// # let children_ref = Bastion::children(|children| children).unwrap();
-    /// children_ref.stop().expect("Couldn't send the message.");
+    /// let msg = "A message containing data.";
+    /// children_ref
+    ///     .broadcast_traced(msg, TraceId::new())
+    ///     .expect("Couldn't send the message.");
     /// #
     /// # Bastion::start();
     /// # Bastion::stop();
     /// # Bastion::block_until_stopped();
     /// ```
-    pub fn stop(&self) -> Result<(), ()> {
-        debug!("ChildrenRef({}): Stopping.", self.id());
-        let msg = BastionMessage::stop();
-        let env = Envelope::from_dead_letters(msg);
-        self.send(env).map_err(|_| ())
+    ///
+    /// [`TraceId`]: ../envelope/struct.TraceId.html
+    /// [`BastionContext::current_trace`]: ../context/struct.BastionContext.html#method.current_trace
+    pub fn broadcast_traced<M: Message>(&self, msg: M, trace: TraceId) -> Result<(), M> {
+        debug!(
+            "ChildrenRef({}): Broadcasting message (trace={}): {:?}",
+            self.id(),
+            trace,
+            msg
+        );
+        let msg = BastionMessage::broadcast(msg);
+        let env = Envelope::from_dead_letters(msg, &self.system).with_trace(Some(trace));
+        // FIXME: panics?
+        self.send(env).map_err(|err| err.into_msg().unwrap())
     }
 
-    /// Sends a message to the children group this `ChildrenRef`
-    /// is referencing to tell it to kill all of its running
-    /// elements.
+    /// Sends an [`EncodedEnvelope`] (e.g. received from another
+    /// system) to the children group this `ChildrenRef` is
+    /// referencing which will then send it to all of its elements,
+    /// where it can be decoded using
+    /// [`BastionContext::recv_deserialized`].
     ///
-    /// This method returns `()` if it succeeded, or `Err(())`
+    /// The envelope's correlation identifier, if any, is used as
+    /// the [`TraceId`] of the message.
+    ///
+    /// This method returns `()` if it succeeded, or `Err(envelope)`
     /// otherwise.
     ///
+    /// This method is only available with the `codec` feature.
+    ///
+    /// # Arguments
+    ///
+    /// * `envelope` - The envelope to broadcast.
+    ///
     /// # Example
     ///
     /// ```rust
     /// # use bastion::prelude::*;
+    /// use bastion::codec::{EncodedEnvelope, JsonCodec, SerializableMessage};
+    /// # use serde::{Deserialize, Serialize};
+    /// #
+    /// # #[derive(Debug, Serialize, Deserialize)]
+    /// # struct Order {
+    /// #     id: u64,
+    /// # }
+    /// #
+    /// # impl SerializableMessage for Order {
+    /// #     const TYPE_TAG: &'static str = "shop.Order";
+    /// # }
     /// #
     /// # Bastion::init();
     /// #
     /// # let children_ref = Bastion::children(|children| children).unwrap();
-    /// children_ref.kill().expect("Couldn't send the message.");
+    /// # let bytes = EncodedEnvelope::encode(&JsonCodec, &Order { id: 42 })
+    /// #     .unwrap()
+    /// #     .to_bytes(&JsonCodec)
+    /// #     .unwrap();
+    /// let envelope = EncodedEnvelope::from_bytes(&JsonCodec, &bytes)
+    ///     .expect("Couldn't decode the envelope.");
+    /// children_ref
+    ///     .broadcast_serialized(envelope)
+    ///     .expect("Couldn't send the envelope.");
     /// #
     /// # Bastion::start();
     /// # Bastion::stop();
     /// # Bastion::block_until_stopped();
     /// ```
-    pub fn kill(&self) -> Result<(), ()> {
-        debug!("ChildrenRef({}): Killing.", self.id());
-        let msg = BastionMessage::kill();
-        let env = Envelope::from_dead_letters(msg);
-        self.send(env).map_err(|_| ())
+    ///
+    /// [`EncodedEnvelope`]: ../codec/struct.EncodedEnvelope.html
+    /// [`BastionContext::recv_deserialized`]: ../context/struct.BastionContext.html#method.recv_deserialized
+    /// [`TraceId`]: ../envelope/struct.TraceId.html
+    #[cfg(feature = "codec")]
+    #[cfg_attr(feature = "docs", doc(cfg(feature = "codec")))]
+    pub fn broadcast_serialized(&self, envelope: EncodedEnvelope) -> Result<(), EncodedEnvelope> {
+        debug!(
+            "ChildrenRef({}): Broadcasting serialized message: {}",
+            self.id(),
+            envelope.type_tag
+        );
+        let trace = envelope.correlation_id;
+        let msg = BastionMessage::broadcast(envelope);
+        let env = Envelope::from_dead_letters(msg, &self.system).with_trace(trace);
+        // FIXME: panics?
+        self.send(env).map_err(|err| err.into_msg().unwrap())
     }
 
-    pub(crate) fn send(&self, env: Envelope) -> Result<(), Envelope> {
-        trace!("ChildrenRef({}): Sending message: {:?}", self.id(), env);
-        self.sender.unbounded_send(env).or_else(|err| {
-            SYSTEM
-                .dead_letters()
-                .sender
-                .unbounded_send(err.into_inner())
-                .map_err(|err| err.into_inner())
-        })
+    /// Sends a message to the children group this `ChildrenRef`
+    /// is referencing which will then send it to all of its
+    /// elements, unless it isn't received before the given
+    /// duration elapsed.
+    ///
+    /// Expired messages are skipped by [`BastionContext::recv`]
+    /// (and the other methods used to receive messages), counted
+    /// (see [`expired_count`]) and, if the children group was
+    /// configured to do so using
+    /// [`Children::with_expired_to_dead_letters`],
+    /// sent to the dead letters.
+    ///
+    /// This method returns `()` if it succeeded, or `Err(msg)`
+    /// otherwise.
+    ///
+    /// # Arguments
+    ///
+    /// * `msg` - The message to send.
+    /// * `ttl` - How long the message can wait to be received.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use bastion::prelude::*;
+    /// # use std::time::Duration;
+    /// #
+    /// # Bastion::init();
+    /// #
+    /// # let children_ref = Bastion::children(|children| children).unwrap();
+    /// let msg = "A request.";
+    /// children_ref
+    ///     .broadcast_with_ttl(msg, Duration::from_secs(10))
+    ///     .expect("Couldn't send the message.");
+    /// #
+    /// # Bastion::start();
+    /// # Bastion::stop();
+    /// # Bastion::block_until_stopped();
+    /// ```
+    ///
+    /// [`BastionContext::recv`]: ../context/struct.BastionContext.html#method.recv
+    /// [`expired_count`]: #method.expired_count
+    /// [`Children::with_expired_to_dead_letters`]: ../children/struct.Children.html#method.with_expired_to_dead_letters
+    pub fn broadcast_with_ttl<M: Message>(&self, msg: M, ttl: Duration) -> Result<(), M> {
+        debug!(
+            "ChildrenRef({}): Broadcasting message (ttl={:?}): {:?}",
+            self.id(),
+            ttl,
+            msg
+        );
+        let deadline = Instant::now() + ttl;
+        let msg = BastionMessage::broadcast(msg);
+        let env = Envelope::from_dead_letters(msg, &self.system).with_deadline(Some(deadline));
+        // FIXME: panics?
+        self.send(env).map_err(|err| err.into_msg().unwrap())
     }
 
-    /// Returns the [`BastionPath`] of this ChildrenRef
-    pub fn path(&self) -> &Arc<BastionPath> {
-        &self.path
+    /// Sends a message directly to the element at the given
+    /// position in [`elems`], without going through the children
+    /// group (like [`ChildRef::tell_anonymously`] does).
+    ///
+    /// This method returns `()` if it succeeded, or `Err(msg)`
+    /// if there is no element at this position or if the message
+    /// couldn't be sent.
+    ///
+    /// # Arguments
+    ///
+    /// * `index` - The position of the element in [`elems`].
+    /// * `msg` - The message to send.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use bastion::prelude::*;
+    /// #
+    /// # Bastion::init();
+    /// #
+    /// let children_ref = Bastion::children(|children| {
+    ///     children.with_redundancy(3)
+    /// }).expect("Couldn't create the children group.");
+    ///
+    /// children_ref.send_to_index(2, "A message containing data.")
+    ///     .expect("Couldn't send the message.");
+    /// assert!(children_ref.send_to_index(3, "Nobody gets this.").is_err());
+    /// #
+    /// # Bastion::start();
+    /// # Bastion::stop();
+    /// # Bastion::block_until_stopped();
+    /// ```
+    ///
+    /// [`elems`]: #method.elems
+    /// [`ChildRef::tell_anonymously`]: ../child_ref/struct.ChildRef.html#method.tell_anonymously
+    pub fn send_to_index<M: Message>(&self, index: usize, msg: M) -> Result<(), M> {
+        match self.children.get(index) {
+            Some(child) => child.tell_anonymously(msg),
+            None => {
+                debug!(
+                    "ChildrenRef({}): No element at index {} to send message to: {:?}",
+                    self.id(),
+                    index,
+                    msg
+                );
+                Err(msg)
+            }
+        }
     }
 
-    pub(crate) fn sender(&self) -> &Sender {
-        &self.sender
+    /// Sends a message to the children group this `ChildrenRef`
+    /// is referencing, which will then send it to all of its
+    /// elements, once the given duration elapsed.
+    ///
+    /// If the children group stopped before the message could be
+    /// sent, it is sent to the dead letters instead. The delivery
+    /// can be cancelled using the returned [`ScheduledSend`] and is
+    /// automatically cancelled when the system stops.
+    ///
+    /// # Arguments
+    ///
+    /// * `delay` - How long to wait before sending the message.
+    /// * `msg` - The message to send.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use bastion::prelude::*;
+    /// # use std::time::Duration;
+    /// #
+    /// # Bastion::init();
+    /// #
+    /// # let children_ref = Bastion::children(|children| children).unwrap();
+    /// let msg = "A message containing data.";
+    /// children_ref.tell_after(Duration::from_secs(5), msg);
+    /// #
+    /// # Bastion::start();
+    /// # Bastion::stop();
+    /// # Bastion::block_until_stopped();
+    /// ```
+    ///
+    /// [`ScheduledSend`]: ../scheduler/struct.ScheduledSend.html
+    pub fn tell_after<M: Message>(&self, delay: Duration, msg: M) -> ScheduledSend {
+        debug!(
+            "ChildrenRef({}): Scheduling message in {:?}: {:?}",
+            self.id(),
+            delay,
+            msg
+        );
+        let children = self.clone();
+        self.system.timers().schedule(delay, move || {
+            let msg = BastionMessage::broadcast(msg);
+            let env = Envelope::from_dead_letters(msg, &children.system);
+            let dead_letters = children.system.dead_letters().sender();
+            if children.is_empty() {
+                trace!(
+                    "ChildrenRef({}): Stopped, sending the scheduled message to the dead letters.",
+                    children.id()
+                );
+                dead_letters.unbounded_send(env).ok();
+            } else if let Err(env) = children.send(env) {
+                dead_letters.unbounded_send(*env).ok();
+            }
+        })
+    }
+
+    /// Sends a message to one of the elements of the children
+    /// group this `ChildrenRef` is referencing, returning a
+    /// [`Future`] that resolves once the message was placed into
+    /// this element's mailbox (but before it gets handled).
+    ///
+    /// The future resolves to `Err(DeliveryError::Stopped)` if the
+    /// children group was stopped (or has no running element) before
+    /// the message could be placed into a mailbox, and to
+    /// `Err(DeliveryError::Timeout)` if it wasn't acknowledged before
+    /// `timeout` elapsed.
+    ///
+    /// # Arguments
+    ///
+    /// * `msg` - The message to send.
+    /// * `timeout` - How long to wait for the acknowledgment.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use bastion::prelude::*;
+    /// # use std::time::Duration;
+    /// #
+    /// # Bastion::init();
+    /// #
+    /// # let children_ref = Bastion::children(|children| children).unwrap();
+    /// # Bastion::start();
+    /// let msg = "A message containing data.";
+    /// let delivery = children_ref.send_acked(msg, Duration::from_secs(1));
+    /// # run!(async {
+    /// match delivery.await {
+    ///     Ok(()) => {
+    ///         // The message is in an element's mailbox...
+    ///     }
+    ///     Err(DeliveryError::Stopped) => {
+    ///         // The children group was stopping...
+    ///     }
+    ///     Err(DeliveryError::Timeout) => {
+    ///         // The children group is too busy...
+    ///     }
+    /// }
+    /// # });
+    /// #
+    /// # Bastion::stop();
+    /// # Bastion::block_until_stopped();
+    /// ```
+    ///
+    /// [`Future`]: https://doc.rust-lang.org/std/future/trait.Future.html
+    pub fn send_acked<M: Message>(
+        &self,
+        msg: M,
+        timeout: Duration,
+    ) -> impl Future<Output = Result<(), DeliveryError>> {
+        debug!(
+            "ChildrenRef({}): Sending message waiting for acknowledgment: {:?}",
+            self.id(),
+            msg
+        );
+        let (sender, receiver) = oneshot::channel();
+        let msg = BastionMessage::tell(msg);
+        let env = Envelope::from_dead_letters(msg, &self.system).with_ack(sender);
+        // If the group has no running element or the envelope
+        // couldn't be sent, it gets dropped along with the
+        // acknowledgment's sender.
+        if !self.is_empty() {
+            self.send(env).ok();
+        }
+
+        async move {
+            match future::select(receiver, Delay::new(timeout)).await {
+                Either::Left((Ok(()), _)) => Ok(()),
+                Either::Left((Err(_), _)) => Err(DeliveryError::Stopped),
+                Either::Right(_) => Err(DeliveryError::Timeout),
+            }
+        }
+    }
+
+    /// "Asks" a message to one of the elements of the children
+    /// group this `ChildrenRef` is referencing, returning a
+    /// [`Future`] that resolves to its answer (see
+    /// [`Answer::timeout`]).
+    ///
+    /// The future resolves to `Err(AskError::SendFailed)` if the
+    /// message couldn't be sent, to `Err(AskError::RecipientDead)`
+    /// if the children group (or the element) stopped without
+    /// answering, and to `Err(AskError::Timeout)` if the answer
+    /// wasn't received before `timeout` elapsed.
+    ///
+    /// # Arguments
+    ///
+    /// * `msg` - The message to ask.
+    /// * `timeout` - How long to wait for the answer.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use bastion::prelude::*;
+    /// # use std::time::Duration;
+    /// #
+    /// # Bastion::init();
+    /// # Bastion::start();
+    /// #
+    /// let children_ref = Bastion::children(|children| {
+    ///     children.with_exec(|ctx: BastionContext| {
+    ///         async move {
+    ///             msg! { ctx.recv().await?,
+    ///                 question: &'static str =!> {
+    ///                     answer!(ctx, "An answer.").ok();
+    ///                 };
+    ///                 _: _ => ();
+    ///             }
+    ///
+    ///             Ok(())
+    ///         }
+    ///     })
+    /// }).expect("Couldn't create the children group.");
+    ///
+    /// # run!(async {
+    /// let answer = children_ref
+    ///     .ask_timeout("A question.", Duration::from_secs(1))
+    ///     .await;
+    /// # });
+    /// #
+    /// # Bastion::stop();
+    /// # Bastion::block_until_stopped();
+    /// ```
+    ///
+    /// [`Future`]: https://doc.rust-lang.org/std/future/trait.Future.html
+    /// [`Answer::timeout`]: ../message/struct.Answer.html#method.timeout
+    pub fn ask_timeout<M: Message>(
+        &self,
+        msg: M,
+        timeout: Duration,
+    ) -> impl Future<Output = Result<SignedMessage, AskError>> {
+        debug!(
+            "ChildrenRef({}): Asking message with a timeout of {:?}: {:?}",
+            self.id(),
+            timeout,
+            msg
+        );
+        let (msg, answer) = BastionMessage::ask(msg);
+        let env = Envelope::from_dead_letters(msg, &self.system);
+        // Not falling back to the dead letters, which would drop
+        // the message anyway.
+        let sent = self.sender.unbounded_send(env).is_ok();
+
+        async move {
+            if !sent {
+                return Err(AskError::SendFailed);
+            }
+
+            answer.timeout(timeout).await
+        }
+    }
+
+    /// Calls one of the elements of the children group this
+    /// `ChildrenRef` is referencing with the given message,
+    /// waiting for its reply. The elements are called in turns
+    /// (see [`call_any`] and [`call_least_busy`]).
+    ///
+    /// This method resolves to the reply if it was received before
+    /// `timeout` elapsed, or to `Err(CallError::Timeout)` otherwise.
+    /// It resolves to `Err(CallError::ActorDied)` if the element
+    /// stopped without replying, and to
+    /// `Err(CallError::NoAvailableChildren)` if none of the elements
+    /// referenced by this `ChildrenRef` (see [`elems`]) is running.
+    ///
+    /// # Arguments
+    ///
+    /// * `msg` - The message to call the element with (it is
+    ///     "asked" to it).
+    /// * `timeout` - How long to wait for the reply.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use bastion::prelude::*;
+    /// # use std::time::Duration;
+    /// #
+    /// # Bastion::init();
+    /// # Bastion::start();
+    /// #
+    /// let children_ref = Bastion::children(|children| {
+    ///     children.with_redundancy(4).with_exec(|ctx: BastionContext| {
+    ///         async move {
+    ///             loop {
+    ///                 msg! { ctx.recv().await?,
+    ///                     n: usize =!> {
+    ///                         answer!(ctx, n * 2).ok();
+    ///                     };
+    ///                     _: _ => ();
+    ///                 }
+    ///             }
+    ///         }
+    ///     })
+    /// }).expect("Couldn't create the children group.");
+    ///
+    /// # run!(async {
+    /// let reply: Msg = children_ref
+    ///     .call(21usize, Duration::from_secs(1))
+    ///     .await
+    ///     .expect("Couldn't call the children group.");
+    /// assert_eq!(reply.downcast::<usize>().ok(), Some(42));
+    /// # });
+    /// #
+    /// # Bastion::stop();
+    /// # Bastion::block_until_stopped();
+    /// ```
+    ///
+    /// [`call_any`]: #method.call_any
+    /// [`call_least_busy`]: #method.call_least_busy
+    /// [`elems`]: #method.elems
+    pub async fn call<M: Message>(&self, msg: M, timeout: Duration) -> Result<Msg, CallError> {
+        self.call_any(msg, timeout).await
+    }
+
+    /// Calls one of the elements of the children group this
+    /// `ChildrenRef` is referencing, taking turns between the
+    /// running ones, with the given message (see [`call`]).
+    ///
+    /// # Arguments
+    ///
+    /// * `msg` - The message to call the element with.
+    /// * `timeout` - How long to wait for the reply.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use bastion::prelude::*;
+    /// # use std::time::Duration;
+    /// #
+    /// # Bastion::init();
+    /// # Bastion::start();
+    /// #
+    /// # let children_ref = Bastion::children(|children| children).unwrap();
+    /// # run!(async {
+    /// match children_ref.call_any("A request.", Duration::from_millis(100)).await {
+    ///     Ok(reply) => {
+    ///         // Handle the reply...
+    ///     }
+    ///     Err(CallError::Timeout) => {
+    ///         // The element is too busy...
+    ///     }
+    ///     Err(_) => {
+    ///         // The children group stopped...
+    ///     }
+    /// }
+    /// # });
+    /// #
+    /// # Bastion::stop();
+    /// # Bastion::block_until_stopped();
+    /// ```
+    ///
+    /// [`call`]: #method.call
+    pub async fn call_any<M: Message>(&self, msg: M, timeout: Duration) -> Result<Msg, CallError> {
+        let elems = self.running_elems();
+        if elems.is_empty() {
+            return Err(CallError::NoAvailableChildren);
+        }
+
+        let index = self.next_call.fetch_add(1, Ordering::SeqCst) % elems.len();
+        self.call_elem(elems[index], msg, timeout).await
+    }
+
+    /// Calls the element of the children group this `ChildrenRef`
+    /// is referencing which has the fewest messages waiting in its
+    /// mailbox with the given message (see [`call`]).
+    ///
+    /// # Arguments
+    ///
+    /// * `msg` - The message to call the element with.
+    /// * `timeout` - How long to wait for the reply.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use bastion::prelude::*;
+    /// # use std::time::Duration;
+    /// #
+    /// # Bastion::init();
+    /// # Bastion::start();
+    /// #
+    /// # let children_ref = Bastion::children(|children| children).unwrap();
+    /// # run!(async {
+    /// let reply = children_ref
+    ///     .call_least_busy("A request.", Duration::from_millis(100))
+    ///     .await;
+    /// # });
+    /// #
+    /// # Bastion::stop();
+    /// # Bastion::block_until_stopped();
+    /// ```
+    ///
+    /// [`call`]: #method.call
+    pub async fn call_least_busy<M: Message>(
+        &self,
+        msg: M,
+        timeout: Duration,
+    ) -> Result<Msg, CallError> {
+        let elem = self
+            .running_elems()
+            .into_iter()
+            .min_by_key(|elem| elem.sender().len())
+            .ok_or(CallError::NoAvailableChildren)?;
+
+        self.call_elem(elem, msg, timeout).await
+    }
+
+    fn running_elems(&self) -> Vec<&ChildRef> {
+        self.children
+            .iter()
+            .filter(|elem| !elem.sender().is_closed())
+            .collect()
+    }
+
+    async fn call_elem<M: Message>(
+        &self,
+        elem: &ChildRef,
+        msg: M,
+        timeout: Duration,
+    ) -> Result<Msg, CallError> {
+        debug!(
+            "ChildrenRef({}): Calling Child({}): {:?}",
+            self.id(),
+            elem.id(),
+            msg
+        );
+        let answer = elem
+            .ask_anonymously(msg)
+            .map_err(|_| CallError::ActorDied)?;
+
+        match answer.timeout(timeout).await {
+            Ok(reply) => Ok(reply.msg),
+            Err(AskError::Timeout) => Err(CallError::Timeout),
+            Err(AskError::RecipientDead) | Err(AskError::SendFailed) => Err(CallError::ActorDied),
+        }
+    }
+
+    /// Asks the children group this `ChildrenRef` is referencing
+    /// for the number of messages waiting in the mailbox of each
+    /// of its running elements (in no particular order).
+    ///
+    /// This method returns a [`Future`] resolving to the mailboxes'
+    /// lengths once the children group answered, or to `Err(())`
+    /// if it stopped without answering. The lengths are approximate
+    /// if messages are being received concurrently.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use bastion::prelude::*;
+    /// #
+    /// # Bastion::init();
+    /// # Bastion::start();
+    /// #
+    /// let children_ref = Bastion::children(|children| {
+    ///     children.with_redundancy(4)
+    /// }).expect("Couldn't create the children group.");
+    ///
+    /// # run!(async {
+    /// let lens: Vec<usize> = children_ref
+    ///     .mailbox_lens()
+    ///     .await
+    ///     .expect("The children group stopped.");
+    /// let pending: usize = lens.iter().sum();
+    /// # });
+    /// #
+    /// # Bastion::stop();
+    /// # Bastion::block_until_stopped();
+    /// ```
+    ///
+    /// [`Future`]: https://doc.rust-lang.org/std/future/trait.Future.html
+    pub fn mailbox_lens(&self) -> impl Future<Output = Result<Vec<usize>, ()>> {
+        debug!("ChildrenRef({}): Asking for mailbox lengths.", self.id());
+        let (sender, receiver) = oneshot::channel();
+        let msg = BastionMessage::mailbox_lens(sender);
+        let env = Envelope::from_dead_letters(msg, &self.system);
+        // If the children group stopped, the envelope gets dropped
+        // along with the sender instead of being sent to the dead
+        // letters (which would answer instead).
+        self.sender.unbounded_send(env).ok();
+
+        async move { receiver.await.map_err(|_| ()) }
+    }
+
+    /// Asks the children group this `ChildrenRef` is referencing
+    /// to check the health of its running elements, each of them
+    /// getting pinged and having to answer before the group's
+    /// health check timeout elapses (see
+    /// [`Children::with_health_check_timeout`]).
+    ///
+    /// The elements answer between two polls of their future,
+    /// which makes the ones blocking their thread or waiting behind
+    /// busy processes slow or unresponsive.
+    ///
+    /// This method returns a [`Future`] resolving to the id and
+    /// [`HealthStatus`] of each of the elements (in no particular
+    /// order), or to an empty list if the children group stopped
+    /// without answering.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use bastion::prelude::*;
+    /// #
+    /// # Bastion::init();
+    /// # Bastion::start();
+    /// #
+    /// let children_ref = Bastion::children(|children| {
+    ///     children.with_redundancy(4)
+    /// }).expect("Couldn't create the children group.");
+    ///
+    /// # run!(async {
+    /// let statuses = children_ref.health_check().await;
+    /// let healthy = statuses
+    ///     .iter()
+    ///     .all(|(_, status)| *status == HealthStatus::Healthy);
+    /// # });
+    /// #
+    /// # Bastion::stop();
+    /// # Bastion::block_until_stopped();
+    /// ```
+    ///
+    /// [`Children::with_health_check_timeout`]: ../children/struct.Children.html#method.with_health_check_timeout
+    /// [`Future`]: https://doc.rust-lang.org/std/future/trait.Future.html
+    /// [`HealthStatus`]: enum.HealthStatus.html
+    pub fn health_check(&self) -> impl Future<Output = Vec<(BastionId, HealthStatus)>> {
+        debug!("ChildrenRef({}): Checking health.", self.id());
+        let (sender, receiver) = oneshot::channel();
+        let msg = BastionMessage::health_check(sender);
+        let env = Envelope::from_dead_letters(msg, &self.system);
+        // Like with `mailbox_lens`, the sender gets dropped if the
+        // children group stopped.
+        self.sender.unbounded_send(env).ok();
+
+        async move { receiver.await.unwrap_or_default() }
+    }
+
+    /// Asks the children group this `ChildrenRef` is referencing
+    /// for the state it accumulated from the outputs its elements
+    /// emitted (see [`Children::with_reducer`]).
+    ///
+    /// This method returns a [`Future`] resolving to the state once
+    /// the children group answered, or to `Err(())` if it stopped
+    /// without answering, has no reducer or if its state isn't of
+    /// type `S`.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use bastion::prelude::*;
+    /// #
+    /// # Bastion::init();
+    /// # Bastion::start();
+    /// #
+    /// let children_ref = Bastion::children(|children| {
+    ///     children.with_reducer(0u64, |sum: u64, count: u64| sum + count)
+    /// }).expect("Couldn't create the children group.");
+    ///
+    /// # run!(async {
+    /// let sum: u64 = children_ref
+    ///     .accumulator()
+    ///     .await
+    ///     .expect("Couldn't get the accumulated state.");
+    /// # assert_eq!(sum, 0);
+    /// # });
+    /// #
+    /// # Bastion::stop();
+    /// # Bastion::block_until_stopped();
+    /// ```
+    ///
+    /// [`Children::with_reducer`]: ../children/struct.Children.html#method.with_reducer
+    /// [`Future`]: https://doc.rust-lang.org/std/future/trait.Future.html
+    pub fn accumulator<S: Message + Clone>(&self) -> impl Future<Output = Result<S, ()>> {
+        debug!("ChildrenRef({}): Asking for accumulated state.", self.id());
+        let (sender, receiver) = oneshot::channel();
+        let msg = BastionMessage::accumulator(sender);
+        let env = Envelope::from_dead_letters(msg, &self.system);
+        // Like with `mailbox_lens`, the sender gets dropped if the
+        // children group stopped.
+        self.sender.unbounded_send(env).ok();
+
+        async move {
+            let msg = receiver.await.map_err(|_| ())?;
+            msg.downcast::<S>().map_err(|_| ())
+        }
+    }
+
+    /// Sends a message to the children group this `ChildrenRef`
+    /// is referencing to tell it to stop all of its running
+    /// elements.
+    ///
+    /// This method returns `()` if it succeeded, or `Err(())`
+    /// otherwise.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use bastion::prelude::*;
+    /// #
+    /// # Bastion::init();
+    /// #
+    /// # let children_ref = Bastion::children(|children| children).unwrap();
+    /// children_ref.stop().expect("Couldn't send the message.");
+    /// #
+    /// # Bastion::start();
+    /// # Bastion::stop();
+    /// # Bastion::block_until_stopped();
+    /// ```
+    pub fn stop(&self) -> Result<(), ()> {
+        debug!("ChildrenRef({}): Stopping.", self.id());
+        let msg = BastionMessage::stop();
+        let env = Envelope::from_dead_letters(msg, &self.system);
+        self.send(env).map_err(|_| ())
+    }
+
+    /// Sends a message to the children group this `ChildrenRef`
+    /// is referencing to tell it to kill all of its running
+    /// elements.
+    ///
+    /// This method returns `()` if it succeeded, or `Err(())`
+    /// otherwise.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use bastion::prelude::*;
+    /// #
+    /// # Bastion::init();
+    /// #
+    /// # let children_ref = Bastion::children(|children| children).unwrap();
+    /// children_ref.kill().expect("Couldn't send the message.");
+    /// #
+    /// # Bastion::start();
+    /// # Bastion::stop();
+    /// # Bastion::block_until_stopped();
+    /// ```
+    pub fn kill(&self) -> Result<(), ()> {
+        debug!("ChildrenRef({}): Killing.", self.id());
+        let msg = BastionMessage::kill();
+        let env = Envelope::from_dead_letters(msg, &self.system);
+        self.send(env).map_err(|_| ())
+    }
+
+    /// Sends a message to the children group this `ChildrenRef`
+    /// is referencing to tell it to pause all of its elements,
+    /// until [`resume`] gets called.
+    ///
+    /// The messages sent to the paused elements keep being
+    /// added to their mailboxes, but their futures don't get
+    /// polled to receive them (which can be used for
+    /// back-pressure or to take a snapshot of their state while
+    /// they are quiescent). The elements restarted while the
+    /// group is paused are paused too.
+    ///
+    /// This method returns `()` if it succeeded, or `Err(())`
+    /// otherwise (e.g. if the children group stopped).
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use bastion::prelude::*;
+    /// #
+    /// # Bastion::init();
+    /// #
+    /// # let children_ref = Bastion::children(|children| children).unwrap();
+    /// children_ref.pause().expect("Couldn't send the message.");
+    /// // The elements' mailboxes keep growing...
+    /// children_ref.resume().expect("Couldn't send the message.");
+    /// #
+    /// # Bastion::start();
+    /// # Bastion::stop();
+    /// # Bastion::block_until_stopped();
+    /// ```
+    ///
+    /// [`resume`]: #method.resume
+    pub fn pause(&self) -> Result<(), ()> {
+        debug!("ChildrenRef({}): Pausing.", self.id());
+        let msg = BastionMessage::pause();
+        let env = Envelope::from_dead_letters(msg, &self.system);
+        // Not falling back to the dead letters, which would get
+        // paused instead.
+        self.sender.unbounded_send(env).map_err(|_| ())
+    }
+
+    /// Sends a message to the children group this `ChildrenRef`
+    /// is referencing to tell it to resume all of its elements
+    /// paused using [`pause`], which then receive the messages
+    /// that were added to their mailboxes in the meantime.
+    ///
+    /// This method returns `()` if it succeeded, or `Err(())`
+    /// otherwise (e.g. if the children group stopped).
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use bastion::prelude::*;
+    /// #
+    /// # Bastion::init();
+    /// #
+    /// # let children_ref = Bastion::children(|children| children).unwrap();
+    /// # children_ref.pause().unwrap();
+    /// children_ref.resume().expect("Couldn't send the message.");
+    /// #
+    /// # Bastion::start();
+    /// # Bastion::stop();
+    /// # Bastion::block_until_stopped();
+    /// ```
+    ///
+    /// [`pause`]: #method.pause
+    pub fn resume(&self) -> Result<(), ()> {
+        debug!("ChildrenRef({}): Resuming.", self.id());
+        let msg = BastionMessage::resume();
+        let env = Envelope::from_dead_letters(msg, &self.system);
+        // Not falling back to the dead letters (see `pause`).
+        self.sender.unbounded_send(env).map_err(|_| ())
+    }
+
+    /// Asks the children group this `ChildrenRef` is referencing,
+    /// which must have been paused using [`pause`], to remove all
+    /// the messages queued in the mailboxes of its elements and
+    /// to send them back, e.g. before stopping the group for a
+    /// maintenance window. The group stays paused, and the
+    /// messages can later be sent back to it (or to another one)
+    /// using [`inject_message`].
+    ///
+    /// Broadcasted messages are only returned once, and the
+    /// messages of each element are returned in the order they
+    /// were received (but in no particular order between the
+    /// elements). Messages that the elements didn't receive yet
+    /// when the group handles the request are left in their
+    /// mailboxes.
+    ///
+    /// This method returns a [`Future`] resolving to the drained
+    /// messages, or to `Err(DrainError::NotPaused)` if the group
+    /// wasn't paused, or to `Err(DrainError::Stopped)` if it
+    /// stopped.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use bastion::prelude::*;
+    /// #
+    /// # Bastion::init();
+    /// # Bastion::start();
+    /// #
+    /// # let children_ref = Bastion::children(|children| children).unwrap();
+    /// children_ref.pause().expect("Couldn't send the message.");
+    /// # run!(async {
+    /// let msgs: Vec<Msg> = children_ref
+    ///     .drain()
+    ///     .await
+    ///     .expect("Couldn't drain the children group.");
+    ///
+    /// // ...
+    ///
+    /// for msg in msgs {
+    ///     children_ref.inject_message(msg).ok();
+    /// }
+    /// children_ref.resume().expect("Couldn't send the message.");
+    /// # });
+    /// #
+    /// # Bastion::stop();
+    /// # Bastion::block_until_stopped();
+    /// ```
+    ///
+    /// [`pause`]: #method.pause
+    /// [`inject_message`]: #method.inject_message
+    /// [`Future`]: https://doc.rust-lang.org/std/future/trait.Future.html
+    pub fn drain(&self) -> impl Future<Output = Result<Vec<Msg>, DrainError>> {
+        debug!("ChildrenRef({}): Draining.", self.id());
+        let (sender, receiver) = oneshot::channel();
+        let msg = BastionMessage::drain(sender);
+        let env = Envelope::from_dead_letters(msg, &self.system);
+        // Not falling back to the dead letters (see `mailbox_lens`).
+        self.sender.unbounded_send(env).ok();
+
+        async move { receiver.await.unwrap_or(Err(DrainError::Stopped)) }
+    }
+
+    /// Sends a message returned by [`drain`] back to the children
+    /// group this `ChildrenRef` is referencing. Broadcasted
+    /// messages are broadcasted again to all of its elements,
+    /// while the other ones are sent to one of them.
+    ///
+    /// This method returns `()` if it succeeded, or `Err(msg)`
+    /// otherwise (e.g. if the children group stopped).
+    ///
+    /// [`drain`]: #method.drain
+    pub fn inject_message(&self, msg: Msg) -> Result<(), Msg> {
+        debug!("ChildrenRef({}): Injecting message: {:?}", self.id(), msg);
+        let env = Envelope::from_dead_letters(BastionMessage::Message(msg), &self.system);
+        // Not falling back to the dead letters, to give the message
+        // back instead.
+        self.sender
+            .unbounded_send(env)
+            .map_err(|err| match err.msg {
+                BastionMessage::Message(msg) => msg,
+                _ => unreachable!(),
+            })
+    }
+
+    pub(crate) fn send(&self, env: Envelope) -> Result<(), Box<Envelope>> {
+        trace!("ChildrenRef({}): Sending message: {:?}", self.id(), env);
+        self.sender
+            .unbounded_send(env)
+            .or_else(|err| self.system.dead_letters().sender().unbounded_send(*err))
+    }
+
+    /// Returns the name of the children group this `ChildrenRef`
+    /// is referencing, if it was given one using
+    /// [`Children::with_name`].
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use bastion::prelude::*;
+    /// #
+    /// # Bastion::init();
+    /// #
+    /// let children_ref = Bastion::children(|children| {
+    ///     children.with_name("workers").with_redundancy(2)
+    /// }).expect("Couldn't create the children group.");
+    /// let anonymous_ref = Bastion::children(|children| children).unwrap();
+    ///
+    /// assert_eq!(children_ref.name(), Some("workers"));
+    /// assert_eq!(anonymous_ref.name(), None);
+    /// // The elements of the group are given its name.
+    /// for elem in children_ref.elems() {
+    ///     assert_eq!(elem.name(), "workers");
+    /// }
+    /// #
+    /// # Bastion::start();
+    /// # Bastion::stop();
+    /// # Bastion::block_until_stopped();
+    /// ```
+    ///
+    /// [`Children::with_name`]: ../children/struct.Children.html#method.with_name
+    pub fn name(&self) -> Option<&str> {
+        self.name.as_deref()
+    }
+
+    /// Returns the labels attached to the children group this
+    /// `ChildrenRef` is referencing using [`Children::with_tag`].
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use bastion::prelude::*;
+    /// #
+    /// # Bastion::init();
+    /// #
+    /// let children_ref = Bastion::children(|children| {
+    ///     children.with_tag("zone", "eu")
+    /// }).expect("Couldn't create the children group.");
+    ///
+    /// let tags = children_ref.tags();
+    /// assert_eq!(tags.len(), 1);
+    /// assert_eq!(tags.get("zone").map(String::as_str), Some("eu"));
+    /// #
+    /// # Bastion::start();
+    /// # Bastion::stop();
+    /// # Bastion::block_until_stopped();
+    /// ```
+    ///
+    /// [`Children::with_tag`]: ../children/struct.Children.html#method.with_tag
+    pub fn tags(&self) -> HashMap<String, String> {
+        (*self.tags).clone()
+    }
+
+    /// Returns the [`BastionPath`] of this ChildrenRef
+    pub fn path(&self) -> &Arc<BastionPath> {
+        &self.path
+    }
+
+    pub(crate) fn sender(&self) -> &Sender {
+        &self.sender
+    }
+
+    pub(crate) fn system(&self) -> &Arc<GlobalSystem> {
+        &self.system
     }
 }
 
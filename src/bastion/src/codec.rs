@@ -0,0 +1,246 @@
+//!
+//! Serialization of messages at the boundaries of a system (e.g.
+//! to send them over the network or to store them), available with
+//! the `codec` feature.
+//!
+//! Messages are still delivered in-process as they are, without
+//! being copied or serialized. A [`Codec`] is only engaged when a
+//! [`SerializableMessage`] is explicitly encoded as an
+//! [`EncodedEnvelope`], which can then be broadcasted to a children
+//! group using [`ChildrenRef::broadcast_serialized`] and decoded by
+//! its elements using [`BastionContext::recv_deserialized`].
+//!
+//! [`Codec`]: trait.Codec.html
+//! [`SerializableMessage`]: trait.SerializableMessage.html
+//! [`EncodedEnvelope`]: struct.EncodedEnvelope.html
+//! [`ChildrenRef::broadcast_serialized`]: ../children_ref/struct.ChildrenRef.html#method.broadcast_serialized
+//! [`BastionContext::recv_deserialized`]: ../context/struct.BastionContext.html#method.recv_deserialized
+use crate::envelope::TraceId;
+use crate::message::Message;
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+use std::fmt::Debug;
+use tracing::trace;
+
+/// A message that can be encoded by a [`Codec`] to cross the
+/// boundaries of a system.
+///
+/// # Example
+///
+/// ```rust
+/// use bastion::codec::SerializableMessage;
+/// use serde::{Deserialize, Serialize};
+///
+/// #[derive(Debug, Serialize, Deserialize)]
+/// struct Order {
+///     id: u64,
+/// }
+///
+/// impl SerializableMessage for Order {
+///     const TYPE_TAG: &'static str = "shop.Order";
+/// }
+/// ```
+///
+/// [`Codec`]: trait.Codec.html
+pub trait SerializableMessage: Message + Serialize + DeserializeOwned {
+    /// The tag identifying the type of the message once encoded,
+    /// which has to be the same for all the systems exchanging it.
+    const TYPE_TAG: &'static str;
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+/// The reason why a [`Codec`] couldn't encode or decode a value.
+///
+/// [`Codec`]: trait.Codec.html
+pub enum CodecError {
+    /// Encoding the value failed.
+    Encode(String),
+    /// Decoding the value failed.
+    Decode(String),
+    /// The envelope didn't contain a message of the expected type.
+    UnexpectedType {
+        /// The type tag of the expected message.
+        expected: &'static str,
+        /// The type tag of the envelope.
+        found: String,
+    },
+}
+
+/// A format in which messages and envelopes are encoded (see
+/// [`EncodedEnvelope`]).
+///
+/// [`EncodedEnvelope`]: struct.EncodedEnvelope.html
+pub trait Codec: Debug + Send + Sync {
+    /// Encodes the given value.
+    ///
+    /// # Arguments
+    ///
+    /// * `value` - The value to encode.
+    fn encode<T: Serialize>(&self, value: &T) -> Result<Vec<u8>, CodecError>;
+
+    /// Decodes a value from the given bytes.
+    ///
+    /// # Arguments
+    ///
+    /// * `bytes` - The bytes previously returned by [`encode`].
+    ///
+    /// [`encode`]: #tymethod.encode
+    fn decode<T: DeserializeOwned>(&self, bytes: &[u8]) -> Result<T, CodecError>;
+}
+
+#[derive(Debug, Default, Clone, Copy)]
+/// A [`Codec`] encoding values as JSON.
+///
+/// [`Codec`]: trait.Codec.html
+pub struct JsonCodec;
+
+impl Codec for JsonCodec {
+    fn encode<T: Serialize>(&self, value: &T) -> Result<Vec<u8>, CodecError> {
+        serde_json::to_vec(value).map_err(|err| CodecError::Encode(err.to_string()))
+    }
+
+    fn decode<T: DeserializeOwned>(&self, bytes: &[u8]) -> Result<T, CodecError> {
+        serde_json::from_slice(bytes).map_err(|err| CodecError::Decode(err.to_string()))
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+/// A [`SerializableMessage`] encoded by a [`Codec`], along with the
+/// tag identifying its type and an optional correlation identifier.
+///
+/// # Example
+///
+/// ```rust
+/// use bastion::codec::{EncodedEnvelope, JsonCodec, SerializableMessage};
+/// # use serde::{Deserialize, Serialize};
+/// #
+/// # #[derive(Debug, PartialEq, Serialize, Deserialize)]
+/// # struct Order {
+/// #     id: u64,
+/// # }
+/// #
+/// # impl SerializableMessage for Order {
+/// #     const TYPE_TAG: &'static str = "shop.Order";
+/// # }
+///
+/// let envelope = EncodedEnvelope::encode(&JsonCodec, &Order { id: 42 })
+///     .expect("Couldn't encode the order.");
+/// // The envelope can itself be encoded to cross a boundary...
+/// let bytes = envelope.to_bytes(&JsonCodec).unwrap();
+///
+/// // ...and decoded on the other side.
+/// let envelope = EncodedEnvelope::from_bytes(&JsonCodec, &bytes).unwrap();
+/// assert!(envelope.is::<Order>());
+/// assert_eq!(envelope.decode::<Order, _>(&JsonCodec), Ok(Order { id: 42 }));
+/// ```
+///
+/// [`SerializableMessage`]: trait.SerializableMessage.html
+/// [`Codec`]: trait.Codec.html
+pub struct EncodedEnvelope {
+    /// The [`SerializableMessage::TYPE_TAG`] of the message.
+    ///
+    /// [`SerializableMessage::TYPE_TAG`]: trait.SerializableMessage.html#associatedconstant.TYPE_TAG
+    pub type_tag: String,
+    /// The encoded message.
+    pub payload: Vec<u8>,
+    /// The identifier correlating the message with the requests it
+    /// is part of, if any. It is used as the [`TraceId`] of the
+    /// message once broadcasted.
+    ///
+    /// [`TraceId`]: ../envelope/struct.TraceId.html
+    pub correlation_id: Option<TraceId>,
+}
+
+impl EncodedEnvelope {
+    /// Encodes the given message using `codec`, without any
+    /// correlation identifier.
+    ///
+    /// # Arguments
+    ///
+    /// * `codec` - The codec used to encode the message.
+    /// * `msg` - The message to encode.
+    pub fn encode<M, C>(codec: &C, msg: &M) -> Result<Self, CodecError>
+    where
+        M: SerializableMessage,
+        C: Codec,
+    {
+        trace!(
+            "EncodedEnvelope: Encoding {} using {:?}.",
+            M::TYPE_TAG,
+            codec
+        );
+        Ok(EncodedEnvelope {
+            type_tag: M::TYPE_TAG.to_string(),
+            payload: codec.encode(msg)?,
+            correlation_id: None,
+        })
+    }
+
+    /// Sets the identifier correlating the message with the
+    /// requests it is part of.
+    ///
+    /// # Arguments
+    ///
+    /// * `correlation_id` - The correlation identifier.
+    pub fn with_correlation_id(mut self, correlation_id: TraceId) -> Self {
+        self.correlation_id = Some(correlation_id);
+        self
+    }
+
+    /// Returns whether this envelope contains a message of type `M`,
+    /// based on its type tag.
+    pub fn is<M: SerializableMessage>(&self) -> bool {
+        self.type_tag == M::TYPE_TAG
+    }
+
+    /// Decodes the message contained in this envelope using `codec`,
+    /// or returns [`CodecError::UnexpectedType`] if it isn't of
+    /// type `M`.
+    ///
+    /// # Arguments
+    ///
+    /// * `codec` - The codec the message was encoded with.
+    ///
+    /// [`CodecError::UnexpectedType`]: enum.CodecError.html#variant.UnexpectedType
+    pub fn decode<M, C>(&self, codec: &C) -> Result<M, CodecError>
+    where
+        M: SerializableMessage,
+        C: Codec,
+    {
+        if !self.is::<M>() {
+            return Err(CodecError::UnexpectedType {
+                expected: M::TYPE_TAG,
+                found: self.type_tag.clone(),
+            });
+        }
+
+        trace!(
+            "EncodedEnvelope: Decoding {} using {:?}.",
+            M::TYPE_TAG,
+            codec
+        );
+        codec.decode(&self.payload)
+    }
+
+    /// Encodes this whole envelope using `codec`.
+    ///
+    /// # Arguments
+    ///
+    /// * `codec` - The codec used to encode the envelope.
+    pub fn to_bytes<C: Codec>(&self, codec: &C) -> Result<Vec<u8>, CodecError> {
+        codec.encode(self)
+    }
+
+    /// Decodes a whole envelope previously encoded using
+    /// [`to_bytes`] with `codec`.
+    ///
+    /// # Arguments
+    ///
+    /// * `codec` - The codec the envelope was encoded with.
+    /// * `bytes` - The encoded envelope.
+    ///
+    /// [`to_bytes`]: #method.to_bytes
+    pub fn from_bytes<C: Codec>(codec: &C, bytes: &[u8]) -> Result<Self, CodecError> {
+        codec.decode(bytes)
+    }
+}
@@ -1,9 +1,38 @@
+use crate::callbacks::Callbacks;
+use crate::executor::{self, BastionExecutor, DeterministicExecutor};
+use crate::panic_handler;
+use crate::supervisor::SupervisionStrategy;
+use bastion_executor::{blocking, metrics, pool};
+use lazy_static::lazy_static;
+use std::sync::{Arc, RwLock};
+use tracing::{debug, warn};
+
+lazy_static! {
+    // The configuration the default runtime gets initialized with.
+    static ref CURRENT: RwLock<Config> = RwLock::new(Config::default());
+}
+
 #[derive(Default, Debug, Clone)]
 /// The configuration that should be used to initialize the
 /// system using [`Bastion::init_with`].
 ///
 /// The default behaviors are the following:
 /// - All backtraces are shown (see [`Config::show_backtraces`]).
+/// - The executor spawns one thread per core (see
+///   [`Config::with_threads`]).
+/// - The executor's blocking pool keeps two threads (see
+///   [`Config::with_blocking_threads`]).
+/// - The system supervisor uses the `OneForOne` strategy (see
+///   [`Config::with_system_strategy`]).
+/// - Children groups and supervisors have no callbacks unless
+///   they set some (see [`Config::with_default_callbacks`]).
+/// - A panic hook is installed (see [`Config::without_panic_hook`]).
+/// - The executor keeps counters about its workers (see
+///   [`Config::without_executor_stats`]).
+/// - Everything runs on bastion's own executor (see
+///   [`Config::with_executor`]).
+/// - The elements' mailboxes are unbounded (see
+///   [`Config::with_broadcast_config`]).
 ///
 /// # Example
 ///
@@ -22,8 +51,49 @@
 /// ```
 ///
 /// [`Bastion::init_with`]: struct.Bastion.html#method.init_with
+/// [`Config::show_backtraces`]: #method.show_backtraces
+/// [`Config::with_threads`]: #method.with_threads
+/// [`Config::with_blocking_threads`]: #method.with_blocking_threads
+/// [`Config::with_system_strategy`]: #method.with_system_strategy
+/// [`Config::with_default_callbacks`]: #method.with_default_callbacks
+/// [`Config::without_panic_hook`]: #method.without_panic_hook
+/// [`Config::without_executor_stats`]: #method.without_executor_stats
+/// [`Config::with_executor`]: #method.with_executor
+/// [`Config::with_broadcast_config`]: #method.with_broadcast_config
 pub struct Config {
     backtraces: Backtraces,
+    threads: Option<usize>,
+    blocking_threads: Option<usize>,
+    system_strategy: SupervisionStrategy,
+    default_callbacks: Callbacks,
+    without_panic_hook: bool,
+    without_executor_stats: bool,
+    executor: Option<Arc<dyn BastionExecutor>>,
+    broadcast: BroadcastConfig,
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+/// Whether the mailboxes of the supervisors, children groups and
+/// elements are bounded, as set with
+/// [`Config::with_broadcast_config`].
+///
+/// [`Config::with_broadcast_config`]: struct.Config.html#method.with_broadcast_config
+pub enum BroadcastConfig {
+    /// The mailboxes accept any number of messages.
+    #[default]
+    Unbounded,
+    /// The messages children groups hand to their elements aren't
+    /// put into the mailboxes already holding the given number of
+    /// envelopes the elements didn't receive yet (e.g. because
+    /// they are blocking the thread they run on).
+    ///
+    /// A message that isn't broadcasted is handed to the next
+    /// element whose mailbox isn't full (or sent to the dead
+    /// letters if there is none), a broadcasted one skips the
+    /// elements whose mailbox is full and a scheduled one is
+    /// dropped for them. The messages used by bastion itself to
+    /// manage the supervision tree are never bounded.
+    Bounded(usize),
 }
 
 #[derive(PartialEq, Eq, Debug, Clone)]
@@ -40,8 +110,31 @@ impl Config {
     /// Creates a new configuration with the following default
     /// behaviors:
     /// - All backtraces are shown (see [`Config::show_backtraces`]).
+    /// - The executor spawns one thread per core (see
+    ///   [`Config::with_threads`]).
+    /// - The executor's blocking pool keeps two threads (see
+    ///   [`Config::with_blocking_threads`]).
+    /// - The system supervisor uses the `OneForOne` strategy (see
+    ///   [`Config::with_system_strategy`]).
+    /// - Children groups and supervisors have no callbacks unless
+    ///   they set some (see [`Config::with_default_callbacks`]).
+    /// - A panic hook is installed (see [`Config::without_panic_hook`]).
+    /// - The executor keeps counters about its workers (see
+    ///   [`Config::without_executor_stats`]).
+    /// - Everything runs on bastion's own executor (see
+    ///   [`Config::with_executor`]).
+    /// - The elements' mailboxes are unbounded (see
+    ///   [`Config::with_broadcast_config`]).
     ///
     /// [`Config::show_backtraces`]: #method.show_backtraces
+    /// [`Config::with_threads`]: #method.with_threads
+    /// [`Config::with_blocking_threads`]: #method.with_blocking_threads
+    /// [`Config::with_system_strategy`]: #method.with_system_strategy
+    /// [`Config::with_default_callbacks`]: #method.with_default_callbacks
+    /// [`Config::without_panic_hook`]: #method.without_panic_hook
+    /// [`Config::without_executor_stats`]: #method.without_executor_stats
+    /// [`Config::with_executor`]: #method.with_executor
+    /// [`Config::with_broadcast_config`]: #method.with_broadcast_config
     pub fn new() -> Self {
         Config::default()
     }
@@ -101,9 +194,384 @@ impl Config {
         self
     }
 
+    /// Sets the number of threads the executor spawns to run
+    /// children groups and supervisors (one per core by default).
+    ///
+    /// Note that this has no effect if the executor was already
+    /// used before the system got initialized.
+    ///
+    /// # Arguments
+    ///
+    /// * `threads` - The number of threads the executor spawns.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use bastion::prelude::*;
+    ///
+    /// let config = Config::new().with_threads(4);
+    ///
+    /// Bastion::init_with(config);
+    /// #
+    /// # Bastion::start();
+    /// # Bastion::stop();
+    /// # Bastion::block_until_stopped();
+    /// ```
+    pub fn with_threads(mut self, threads: usize) -> Self {
+        self.threads = Some(threads.max(1));
+        self
+    }
+
+    /// Sets the number of threads the executor's blocking pool
+    /// spawns to run the blocking closures and futures (see
+    /// [`BastionContext::blocking`] and the `blocking!` macro) and
+    /// keeps once its load decreased (two by default, or the value
+    /// of the `BASTION_BLOCKING_THREADS` environment variable). The
+    /// pool still spawns more threads when they are all busy.
+    ///
+    /// Note that this has no effect if the blocking pool was
+    /// already used before the system got initialized.
+    ///
+    /// # Arguments
+    ///
+    /// * `threads` - The number of threads the blocking pool
+    ///     keeps.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use bastion::prelude::*;
+    ///
+    /// let config = Config::new().with_blocking_threads(8);
+    ///
+    /// Bastion::init_with(config);
+    /// #
+    /// # Bastion::start();
+    /// # Bastion::stop();
+    /// # Bastion::block_until_stopped();
+    /// ```
+    ///
+    /// [`BastionContext::blocking`]: context/struct.BastionContext.html#method.blocking
+    pub fn with_blocking_threads(mut self, threads: usize) -> Self {
+        self.blocking_threads = Some(threads.max(1));
+        self
+    }
+
+    /// Sets the strategy the system supervisor uses to supervise
+    /// the top-level children groups (created using
+    /// [`Bastion::children`]), `OneForOne` by default.
+    ///
+    /// # Arguments
+    ///
+    /// * `strategy` - The strategy the system supervisor uses.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use bastion::prelude::*;
+    ///
+    /// let config = Config::new().with_system_strategy(SupervisionStrategy::OneForAll);
+    ///
+    /// Bastion::init_with(config);
+    /// #
+    /// # Bastion::start();
+    /// # Bastion::stop();
+    /// # Bastion::block_until_stopped();
+    /// ```
+    ///
+    /// [`Bastion::children`]: struct.Bastion.html#method.children
+    pub fn with_system_strategy(mut self, strategy: SupervisionStrategy) -> Self {
+        self.system_strategy = strategy;
+        self
+    }
+
+    /// Sets the callbacks of the children groups and supervisors
+    /// that don't set their own (using `with_callbacks`).
+    ///
+    /// # Arguments
+    ///
+    /// * `callbacks` - The callbacks used by default.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use bastion::prelude::*;
+    ///
+    /// let callbacks = Callbacks::new()
+    ///     .with_after_restart(|| println!("Restarted."));
+    /// let config = Config::new().with_default_callbacks(callbacks);
+    ///
+    /// Bastion::init_with(config);
+    /// #
+    /// # Bastion::start();
+    /// # Bastion::stop();
+    /// # Bastion::block_until_stopped();
+    /// ```
+    pub fn with_default_callbacks(mut self, callbacks: Callbacks) -> Self {
+        self.default_callbacks = callbacks;
+        self
+    }
+
+    /// Makes Bastion not install its process-wide panic hook.
+    ///
+    /// Without it, the handler set using [`Bastion::set_panic_handler`]
+    /// is never called and panics aren't reported with `tracing`
+    /// events, which can be useful if the application installs its
+    /// own hook.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use bastion::prelude::*;
+    ///
+    /// let config = Config::new().without_panic_hook();
+    ///
+    /// Bastion::init_with(config);
+    /// #
+    /// # Bastion::start();
+    /// # Bastion::stop();
+    /// # Bastion::block_until_stopped();
+    /// ```
+    ///
+    /// [`Bastion::set_panic_handler`]: struct.Bastion.html#method.set_panic_handler
+    pub fn without_panic_hook(mut self) -> Self {
+        self.without_panic_hook = true;
+        self
+    }
+
+    /// Makes bastion's executor stop updating the counters
+    /// returned by [`Bastion::executor_stats`], for the few atomic
+    /// operations they cost when scheduling processes to be
+    /// avoided.
+    ///
+    /// The counters are shared by all the runtimes of the process,
+    /// and this has no effect when using another executor (see
+    /// [`Config::with_executor`]).
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use bastion::prelude::*;
+    ///
+    /// let config = Config::new().without_executor_stats();
+    ///
+    /// Bastion::init_with(config);
+    /// #
+    /// # Bastion::start();
+    /// # Bastion::stop();
+    /// # Bastion::block_until_stopped();
+    /// ```
+    ///
+    /// [`Bastion::executor_stats`]: struct.Bastion.html#method.executor_stats
+    /// [`Config::with_executor`]: #method.with_executor
+    pub fn without_executor_stats(mut self) -> Self {
+        self.without_executor_stats = true;
+        self
+    }
+
+    /// Makes Bastion run its supervisors, children groups and
+    /// their elements on the given executor instead of its own
+    /// one, like [`Bastion::with_custom_executor`] does.
+    ///
+    /// The executor is shared by all the runtimes of the process
+    /// and the number of threads set using [`Config::with_threads`]
+    /// has no effect on it.
+    ///
+    /// # Arguments
+    ///
+    /// * `executor` - The executor to run everything on.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use bastion::executor::BastionExecutor;
+    /// use bastion::prelude::*;
+    /// use futures::future::{BoxFuture, LocalBoxFuture};
+    /// use std::thread;
+    ///
+    /// #[derive(Debug)]
+    /// struct ThreadExecutor;
+    ///
+    /// impl BastionExecutor for ThreadExecutor {
+    ///     // ...
+    /// #     fn spawn(&self, future: BoxFuture<'static, ()>) {
+    /// #         thread::spawn(move || futures::executor::block_on(future));
+    /// #     }
+    /// #
+    /// #     fn spawn_blocking(&self, future: BoxFuture<'static, ()>) {
+    /// #         self.spawn(future)
+    /// #     }
+    /// #
+    /// #     fn block_on(&self, future: LocalBoxFuture<'_, ()>) {
+    /// #         futures::executor::block_on(future)
+    /// #     }
+    /// }
+    ///
+    /// let config = Config::new().with_executor(ThreadExecutor);
+    ///
+    /// Bastion::init_with(config);
+    /// #
+    /// # Bastion::start();
+    /// # Bastion::stop();
+    /// # Bastion::block_until_stopped();
+    /// ```
+    ///
+    /// [`Bastion::with_custom_executor`]: struct.Bastion.html#method.with_custom_executor
+    /// [`Config::with_threads`]: #method.with_threads
+    pub fn with_executor(mut self, executor: impl BastionExecutor) -> Self {
+        self.executor = Some(Arc::new(executor));
+        self
+    }
+
+    /// Makes Bastion run everything on a single thread, polling
+    /// the futures that are ready at the same time in an order only
+    /// depending on the given seed (see [`DeterministicExecutor`]).
+    ///
+    /// This is meant to be used by tests, to make the order in which
+    /// the messages get handled reproducible by using the same seed.
+    ///
+    /// # Arguments
+    ///
+    /// * `seed` - The seed of the order in which the futures ready
+    ///     at the same time get polled.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use bastion::prelude::*;
+    ///
+    /// let config = Config::new().with_deterministic_executor(42);
+    ///
+    /// Bastion::init_with(config);
+    /// #
+    /// # Bastion::start();
+    /// # Bastion::stop();
+    /// # Bastion::block_until_stopped();
+    /// ```
+    ///
+    /// [`DeterministicExecutor`]: executor/struct.DeterministicExecutor.html
+    pub fn with_deterministic_executor(self, seed: u64) -> Self {
+        self.with_executor(DeterministicExecutor::new(seed))
+    }
+
+    /// Makes the mailboxes of the elements of the children groups
+    /// bounded or not (see [`BroadcastConfig`]), which lets the
+    /// groups detect elements that don't keep up with the messages
+    /// they are sent instead of piling those up.
+    ///
+    /// Note that the default behavior is for the mailboxes to be
+    /// unbounded.
+    ///
+    /// # Arguments
+    ///
+    /// * `broadcast` - Whether the mailboxes are bounded.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use bastion::prelude::*;
+    ///
+    /// let config = Config::new().with_broadcast_config(BroadcastConfig::Bounded(1024));
+    ///
+    /// Bastion::init_with(config);
+    /// #
+    /// # Bastion::start();
+    /// # Bastion::stop();
+    /// # Bastion::block_until_stopped();
+    /// ```
+    ///
+    /// [`BroadcastConfig`]: enum.BroadcastConfig.html
+    pub fn with_broadcast_config(mut self, broadcast: BroadcastConfig) -> Self {
+        self.broadcast = broadcast;
+        self
+    }
+
     pub(crate) fn backtraces(&self) -> &Backtraces {
         &self.backtraces
     }
+
+    pub(crate) fn threads(&self) -> Option<usize> {
+        self.threads
+    }
+
+    pub(crate) fn blocking_threads(&self) -> Option<usize> {
+        self.blocking_threads
+    }
+
+    pub(crate) fn broadcast_config(&self) -> BroadcastConfig {
+        self.broadcast
+    }
+
+    pub(crate) fn panic_hook(&self) -> bool {
+        !self.without_panic_hook
+    }
+
+    /// Returns the strategy of the system supervisor.
+    pub(crate) fn system_strategy(&self) -> &SupervisionStrategy {
+        &self.system_strategy
+    }
+
+    /// Returns the callbacks of the children groups and supervisors
+    /// that don't set their own.
+    pub(crate) fn default_callbacks(&self) -> &Callbacks {
+        &self.default_callbacks
+    }
+
+    /// Applies the parts of the configuration shared by all the
+    /// runtimes of the process (the panic hook and the executor or
+    /// its threads and counters).
+    pub(crate) fn apply(&self) {
+        if self.backtraces().is_hide() {
+            debug!("Config: Hiding backtraces.");
+        }
+        if self.panic_hook() {
+            panic_handler::install_hook(self.backtraces().is_hide());
+        } else {
+            debug!("Config: Not installing the panic hook.");
+        }
+
+        if let Some(executor) = &self.executor {
+            debug!("Config: Using executor: {:?}", executor);
+            executor::set_executor(executor.clone());
+        } else {
+            if let Some(threads) = self.threads() {
+                debug!("Config: Using {} threads.", threads);
+                if !pool::set_threads(threads) {
+                    warn!(
+                        "Config: The executor is already running with {} threads.",
+                        pool::threads()
+                    );
+                }
+            }
+            if let Some(threads) = self.blocking_threads() {
+                debug!("Config: Using {} blocking threads.", threads);
+                if !blocking::set_low_watermark(threads as u64) {
+                    warn!(
+                        "Config: The blocking pool is already running with {} threads.",
+                        blocking::low_watermark()
+                    );
+                }
+            }
+            if self.without_executor_stats {
+                debug!("Config: Disabling the executor's counters.");
+                metrics::set_enabled(false);
+            }
+        }
+    }
+}
+
+/// Makes the given configuration the one the default runtime
+/// gets initialized with.
+pub(crate) fn set_current(config: Config) {
+    // FIXME: panics
+    *CURRENT.write().unwrap() = config;
+}
+
+/// Returns the configuration the default runtime gets
+/// initialized with.
+pub(crate) fn current() -> Config {
+    // FIXME: panics
+    CURRENT.read().unwrap().clone()
 }
 
 impl Backtraces {
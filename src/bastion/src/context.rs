@@ -2,26 +2,58 @@
 //! A context allows a child's future to access its received
 //! messages, parent and supervisor.
 
+use crate::broadcast::{Broadcast, Parent};
+use crate::child::{Init, Mailbox};
 use crate::child_ref::ChildRef;
 use crate::children_ref::ChildrenRef;
+#[cfg(feature = "codec")]
+use crate::codec::{Codec, EncodedEnvelope, SerializableMessage};
 use crate::dispatcher::{BroadcastTarget, DispatcherType, NotificationType};
-use crate::envelope::{Envelope, RefAddr, SignedMessage};
-use crate::message::{Answer, BastionMessage, Message, Msg};
+use crate::envelope::{Envelope, Expired, RefAddr, SignedMessage, TraceId};
+use crate::executor;
+use crate::mailbox_store::MailboxStore;
+use crate::message::{Answer, AnswerSender, BastionMessage, Message, Msg};
+use crate::panic_handler;
+use crate::path::BastionPathElement;
+use crate::scheduler::{ScheduledSend, Tick, Ticker};
 use crate::supervisor::SupervisorRef;
-use crate::system::SYSTEM;
+use crate::sync::{Barrier, BarrierWaitResult};
+use crate::system::GlobalSystem;
 use async_mutex::Mutex;
-use futures::pending;
+use futures::future::{self, Either};
+use futures::stream::{self, Stream};
+use futures::{pending, pin_mut, poll};
+use futures_timer::Delay;
+use serde::{Deserialize, Serialize};
 use std::collections::VecDeque;
+use std::error::Error;
 use std::fmt::{self, Display, Formatter};
+use std::future::Future;
+use std::panic;
 use std::pin::Pin;
-use std::sync::Arc;
-use tracing::{debug, trace};
+use std::str::FromStr;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{mpsc, Arc, Mutex as StdMutex};
+use std::time::{Duration, Instant};
+use tracing::{debug, trace, warn};
 use uuid::Uuid;
 
 /// Identifier for a root supervisor and dead-letters children.
 pub const NIL_ID: BastionId = BastionId(Uuid::nil());
 
-#[derive(Hash, Eq, PartialEq, Debug, Clone)]
+// The number of messages an element can stash by default.
+const DEFAULT_STASH_CAPACITY: usize = 1024;
+
+// The number of messages an element (or a supervisor replaying the
+// messages it received before starting) handles by default before
+// yielding, for the other processes to get polled too.
+pub(crate) const DEFAULT_POLL_BUDGET: usize = 128;
+
+// The last pid given to the process of a supervisor, children
+// group or element (see `next_pid`).
+static LAST_PID: AtomicUsize = AtomicUsize::new(0);
+
+#[derive(Hash, Eq, PartialEq, Debug, Clone, Serialize, Deserialize)]
 /// An identifier used by supervisors, children groups and
 /// their elements to identify themselves, using a v4 UUID.
 ///
@@ -31,6 +63,12 @@ pub const NIL_ID: BastionId = BastionId(Uuid::nil());
 /// the system at startup) which is a nil UUID
 /// (00000000-0000-0000-0000-000000000000).
 ///
+/// A `BastionId` is displayed using its whole UUID, which can be
+/// parsed back into the same `BastionId` using [`str::parse`],
+/// allowing to pass ids through command lines or admin APIs. The
+/// alternate form (`{:#}`) only displays the first 8 hex characters
+/// of the UUID, which is enough to tell ids apart in logs.
+///
 /// # Example
 ///
 /// ```rust
@@ -54,7 +92,14 @@ pub const NIL_ID: BastionId = BastionId(Uuid::nil());
 /// ```
 pub struct BastionId(pub(crate) Uuid);
 
-#[derive(Debug)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+/// The error returned when parsing a [`BastionId`] from a string
+/// that isn't a full UUID.
+///
+/// [`BastionId`]: struct.BastionId.html
+pub struct ParseIdError;
+
+#[derive(Debug, Clone)]
 /// A child's execution context, allowing its [`exec`] future
 /// to receive messages and access a [`ChildRef`] referencing
 /// it, a [`ChildrenRef`] referencing its children group and
@@ -100,15 +145,172 @@ pub struct BastionId(pub(crate) Uuid);
 /// ```
 pub struct BastionContext {
     id: BastionId,
+    // The pid of the element's process, which changes
+    // when the element is restarted.
+    pid: usize,
     child: ChildRef,
     children: ChildrenRef,
     supervisor: Option<SupervisorRef>,
     state: Arc<Mutex<Pin<Box<ContextState>>>>,
+    // Places the messages received by the element into its
+    // mailbox when they are retrieved without the element's
+    // future yielding (see `try_recv_sync`).
+    mailbox: Mailbox,
+    // The `TraceId` of the last received message, attached
+    // to the messages sent from this context.
+    trace: Arc<StdMutex<Option<TraceId>>>,
+    // The sender answering the message being handled by the
+    // closure given to `Children::with_exec_typed`, if it was
+    // "asked".
+    answer: Arc<StdMutex<Option<AnswerSender>>>,
+    // Periodically sends `Tick` messages to the element.
+    ticker: Arc<Ticker>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+/// The reason why an element stopped, as reported in the
+/// [`LinkDown`] messages sent to the elements watching it.
+///
+/// [`LinkDown`]: struct.LinkDown.html
+pub enum TerminationReason {
+    /// The element's future finished executing successfully or
+    /// the element was asked to stop.
+    Stopped,
+    /// The element was killed.
+    Killed,
+    /// The element's future returned an error.
+    Faulted,
+    /// The element's future panicked.
+    Panicked,
+}
+
+#[derive(Debug, Clone)]
+/// The message "told" to an element when an element it is
+/// linked to or that it monitors (see [`BastionContext::link`]
+/// and [`BastionContext::monitor`]) stops, is killed or faults.
+///
+/// # Example
+///
+/// ```rust
+/// # use bastion::prelude::*;
+/// #
+/// # Bastion::init();
+/// #
+/// Bastion::children(|children| {
+///     children.with_exec(|ctx: BastionContext| {
+///         async move {
+///             msg! { ctx.recv().await?,
+///                 down: LinkDown => {
+///                     if down.reason != TerminationReason::Stopped {
+///                         // Restart, stop or ignore...
+///                     }
+///                 };
+///                 _: _ => ();
+///             }
+///
+///             Ok(())
+///         }
+///     })
+/// }).expect("Couldn't create the children group.");
+/// #
+/// # Bastion::start();
+/// # Bastion::stop();
+/// # Bastion::block_until_stopped();
+/// ```
+///
+/// [`BastionContext::link`]: struct.BastionContext.html#method.link
+/// [`BastionContext::monitor`]: struct.BastionContext.html#method.monitor
+pub struct LinkDown {
+    /// The identifier of the element that went down.
+    pub id: BastionId,
+    /// Why the element went down.
+    pub reason: TerminationReason,
+}
+
+#[derive(Debug)]
+/// The context given to the OS threads of the elements of the
+/// children groups whose closure was set using
+/// [`Children::with_thread_exec`], allowing them to receive the
+/// messages sent to the elements by blocking.
+///
+/// # Example
+///
+/// ```rust
+/// # use bastion::prelude::*;
+/// #
+/// # Bastion::init();
+/// #
+/// Bastion::children(|children| {
+///     children.with_thread_exec(|ctx: ThreadContext| {
+///         // Block until a message has been received...
+///         while let Ok(msg) = ctx.recv() {
+///             // ...and handle it synchronously.
+///         }
+///
+///         Ok(())
+///     })
+/// }).expect("Couldn't create the children group.");
+/// #
+/// # Bastion::start();
+/// # Bastion::stop();
+/// # Bastion::block_until_stopped();
+/// ```
+///
+/// [`Children::with_thread_exec`]: ../children/struct.Children.html#method.with_thread_exec
+pub struct ThreadContext {
+    child: ChildRef,
+    children: ChildrenRef,
+    // The messages forwarded by the element's future, which drops
+    // the sender once the element is asked to stop or killed.
+    receiver: mpsc::Receiver<SignedMessage>,
 }
 
 #[derive(Debug)]
 pub(crate) struct ContextState {
     messages: VecDeque<SignedMessage>,
+    // Whether the element was asked to stop, in which case
+    // waiting for new messages fails once `messages` is empty.
+    stopping: bool,
+    // The number of messages that expired before being received,
+    // shared with the other elements of the children group.
+    expired: Arc<AtomicUsize>,
+    // Whether expired messages are sent to the dead letters.
+    expired_to_dead_letters: bool,
+    // Where the messages are stored until they are retrieved.
+    store: Option<Arc<dyn MailboxStore>>,
+    // The messages retrieved and then stashed by the element,
+    // in the order they were received.
+    stash: VecDeque<SignedMessage>,
+    stash_capacity: usize,
+    // The number of messages the element can receive before
+    // yielding, and how many of them are left until it does.
+    poll_budget: usize,
+    budget_left: usize,
+    // How long the element can take to handle a message, and when
+    // it received the one it is handling, if it isn't waiting for
+    // the next one.
+    exec_timeout: Option<Duration>,
+    handling_since: Option<Instant>,
+    // How long the element can take to handle a message before a
+    // warning is emitted, the type of the message it is handling
+    // and whether the warning was already emitted for it.
+    warning_threshold: Option<Duration>,
+    handling_type: Option<&'static str>,
+    warned: bool,
+    // A message the element took too long to handle without being
+    // warned about while it did (e.g. because it blocked its
+    // thread), along with how long it took.
+    stall: Option<(Duration, &'static str)>,
+    // How long the element can wait for a message before stopping,
+    // and since when it has been waiting for one, if it is.
+    idle_timeout: Option<Duration>,
+    waiting_since: Option<Instant>,
+    // The number of messages pushed to the mailbox and retrieved
+    // from it (see `ChildrenMetrics`).
+    received: u64,
+    retrieved: u64,
+    // The system of the runtime the element belongs to.
+    system: Arc<GlobalSystem>,
 }
 
 impl BastionId {
@@ -119,21 +321,38 @@ impl BastionId {
     }
 }
 
+// Returns a pid that was never given to another process, to be
+// set on the `ProcStack` of a newly spawned process.
+pub(crate) fn next_pid() -> usize {
+    LAST_PID.fetch_add(1, Ordering::Relaxed) + 1
+}
+
 impl BastionContext {
+    #[allow(clippy::too_many_arguments)]
     pub(crate) fn new(
         id: BastionId,
+        pid: usize,
         child: ChildRef,
         children: ChildrenRef,
         supervisor: Option<SupervisorRef>,
         state: Arc<Mutex<Pin<Box<ContextState>>>>,
+        mailbox: Mailbox,
+        ticker: Arc<Ticker>,
     ) -> Self {
         debug!("BastionContext({}): Creating.", id);
+        let trace = Arc::new(StdMutex::new(None));
+        let answer = Arc::new(StdMutex::new(None));
         BastionContext {
             id,
+            pid,
             child,
             children,
             supervisor,
             state,
+            mailbox,
+            trace,
+            answer,
+            ticker,
         }
     }
 
@@ -169,6 +388,40 @@ impl BastionContext {
         &self.child
     }
 
+    /// Returns the pid set on the process running the element
+    /// that is linked to this `BastionContext`.
+    ///
+    /// Every supervisor, children group and element gets its own
+    /// pid, and an element gets a new one each time it is
+    /// restarted, which allows to tell its incarnations apart.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use bastion::prelude::*;
+    /// #
+    /// # Bastion::init();
+    /// #
+    /// Bastion::children(|children| {
+    ///     children.with_exec(|ctx: BastionContext| {
+    ///         async move {
+    ///             let pid: usize = ctx.pid();
+    ///             // Log the pid to tell this incarnation of the
+    ///             // element apart from the restarted ones...
+    ///
+    ///             Ok(())
+    ///         }
+    ///     })
+    /// }).expect("Couldn't create the children group.");
+    /// #
+    /// # Bastion::start();
+    /// # Bastion::stop();
+    /// # Bastion::block_until_stopped();
+    /// ```
+    pub fn pid(&self) -> usize {
+        self.pid
+    }
+
     /// Returns a [`ChildrenRef`] referencing the children group
     /// of the element that is linked to this `BastionContext`.
     ///
@@ -256,6 +509,50 @@ impl BastionContext {
         self.supervisor.as_ref()
     }
 
+    /// Returns an owned [`SupervisorRef`] referencing the supervisor
+    /// that supervises the children group of the element that is
+    /// linked to this `BastionContext`, allowing to interrogate it
+    /// (e.g. to get its id or to spawn sibling children groups).
+    ///
+    /// This method returns `None` if the children group was
+    /// created using [`Bastion::children`] (ie. if it is
+    /// supervised by the system's supervisor).
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use bastion::prelude::*;
+    /// #
+    /// # Bastion::init();
+    /// #
+    /// Bastion::supervisor(|sp| {
+    ///     sp.children(|children| {
+    ///         children.with_exec(|ctx: BastionContext| {
+    ///             async move {
+    ///                 let parent: SupervisorRef = ctx.parent_ref().expect("No supervisor.");
+    ///                 // Spawning a sibling children group...
+    ///                 parent.children(|children| children).ok();
+    ///
+    ///                 Ok(())
+    ///             }
+    ///         })
+    ///     })
+    /// }).expect("Couldn't create the supervisor.");
+    /// #
+    /// # Bastion::start();
+    /// # Bastion::stop();
+    /// # Bastion::block_until_stopped();
+    /// ```
+    ///
+    /// [`SupervisorRef`]: supervisor/struct.SupervisorRef.html
+    /// [`Bastion::children`]: struct.Bastion.html#method.children
+    pub fn parent_ref(&self) -> Option<SupervisorRef> {
+        self.supervisor
+            .as_ref()
+            .filter(|supervisor| supervisor.id() != &NIL_ID)
+            .cloned()
+    }
+
     /// Tries to retrieve asynchronously a message received by
     /// the element this `BastionContext` is linked to.
     ///
@@ -298,6 +595,7 @@ impl BastionContext {
 
         if let Some(msg) = guard.pop_message() {
             trace!("BastionContext({}): Received message: {:?}", self.id, msg);
+            self.received(&msg);
             Some(msg)
         } else {
             trace!("BastionContext({}): Received no message.", self.id);
@@ -305,15 +603,25 @@ impl BastionContext {
         }
     }
 
-    /// Retrieves asynchronously a message received by the element
-    /// this `BastionContext` is linked to and waits (always
-    /// asynchronously) for one if none has been received yet.
+    /// Tries to retrieve synchronously a message received by the
+    /// element this `BastionContext` is linked to, without
+    /// awaiting (and thus without registering any waker).
     ///
-    /// If you don't need to wait until at least one message
-    /// can be retrieved, use [`try_recv`] instead.
+    /// This allows to check for messages between work items of
+    /// a compute-heavy future, including the ones received since
+    /// it last yielded. The messages asking the element to stop
+    /// (or to pause, etc.) are only handled once the future
+    /// yields: [`has_messages`] tells when it should (e.g. by
+    /// calling [`recv`]), since none of the messages received after
+    /// them are retrieved until then.
     ///
-    /// This method returns [`SignedMessage`] if it succeeded, or `Err(())`
-    /// otherwise.
+    /// It can be mixed with [`recv`] and [`try_recv`], each message
+    /// being retrieved only once. If the mailbox is being used
+    /// concurrently (e.g. by a pending [`recv`] polled elsewhere),
+    /// this method returns `None` instead of waiting for it.
+    ///
+    /// This method returns [`SignedMessage`] if a message was available, or
+    /// `None` otherwise.
     ///
     /// # Example
     ///
@@ -325,8 +633,14 @@ impl BastionContext {
     /// Bastion::children(|children| {
     ///     children.with_exec(|ctx: BastionContext| {
     ///         async move {
-    ///             // This will block until a message has been received...
-    ///             let msg: SignedMessage = ctx.recv().await?;
+    ///             for _ in 0..1_000 {
+    ///                 // Do some work...
+    ///
+    ///                 // ...and check for messages between work items.
+    ///                 if let Some(msg) = ctx.try_recv_sync() {
+    ///                     // Handle the message...
+    ///                 }
+    ///             }
     ///
     ///             Ok(())
     ///         }
@@ -338,63 +652,106 @@ impl BastionContext {
     /// # Bastion::block_until_stopped();
     /// ```
     ///
+    /// [`has_messages`]: #method.has_messages
+    /// [`recv`]: #method.recv
     /// [`try_recv`]: #method.try_recv
     /// [`SignedMessage`]: ../prelude/struct.SignedMessage.html
-    pub async fn recv(&self) -> Result<SignedMessage, ()> {
-        debug!("BastionContext({}): Waiting to receive message.", self.id);
-        loop {
-            let state = self.state.clone();
-            let mut guard = state.lock().await;
+    pub fn try_recv_sync(&self) -> Option<SignedMessage> {
+        debug!(
+            "BastionContext({}): Trying to receive message synchronously.",
+            self.id
+        );
+        let mut guard = match self.state.try_lock() {
+            Some(guard) => guard,
+            None => {
+                trace!("BastionContext({}): Mailbox is being used.", self.id);
+                return None;
+            }
+        };
 
+        loop {
             if let Some(msg) = guard.pop_message() {
                 trace!("BastionContext({}): Received message: {:?}", self.id, msg);
-                return Ok(msg);
+                self.received(&msg);
+                return Some(msg);
             }
 
-            drop(guard);
-            pending!();
+            // The messages received since the future last yielded
+            // weren't placed into the mailbox yet.
+            let env = self.mailbox.inbox().try_take(|env| {
+                matches!(
+                    env,
+                    Envelope {
+                        msg: BastionMessage::Message(_),
+                        ..
+                    }
+                )
+            });
+            match env {
+                Some(env) => self.mailbox.deliver(&mut guard, env),
+                None => {
+                    trace!("BastionContext({}): Received no message.", self.id);
+                    return None;
+                }
+            }
         }
     }
 
-    /// Returns [`RefAddr`] of the current `BastionContext`
+    /// Returns whether messages are waiting for the element this
+    /// `BastionContext` is linked to, without awaiting.
+    ///
+    /// This also knows about the messages received since the
+    /// element's future last yielded, including the message asking
+    /// the element to stop, which is only handled once it yields
+    /// (e.g. by calling [`recv`], which then returns an error) and
+    /// which [`try_recv_sync`] thus doesn't retrieve.
     ///
     /// # Example
     ///
     /// ```rust
     /// # use bastion::prelude::*;
     /// #
-    /// # Bastion::init();
+    /// # Bastion::init_with(Config::new().with_threads(2));
     /// #
-    ///
     /// Bastion::children(|children| {
     ///     children.with_exec(|ctx: BastionContext| {
     ///         async move {
-    ///             ctx.tell(&ctx.signature(), "Hello to myself");
+    ///             loop {
+    ///                 while !ctx.has_messages() {
+    ///                     // Do some work until a message is received...
+    ///                 }
     ///
-    ///             # Bastion::stop();
-    ///             Ok(())
+    ///                 // ...and yield to retrieve it.
+    ///                 let msg = ctx.recv().await?;
+    ///             }
     ///         }
     ///     })
     /// }).expect("Couldn't create the children group.");
     /// #
     /// # Bastion::start();
+    /// # Bastion::stop();
     /// # Bastion::block_until_stopped();
     /// ```
     ///
-    /// [`RefAddr`]: /prelude/struct.Answer.html
-    pub fn signature(&self) -> RefAddr {
-        RefAddr::new(
-            self.current().path().clone(),
-            self.current().sender().clone(),
-        )
+    /// [`try_recv_sync`]: #method.try_recv_sync
+    /// [`recv`]: #method.recv
+    pub fn has_messages(&self) -> bool {
+        if self.child.sender().len() > 0 {
+            return true;
+        }
+
+        match self.state.try_lock() {
+            Some(guard) => guard.has_messages(),
+            None => false,
+        }
     }
 
-    /// Sends a message to the specified [`RefAddr`]
-    ///
-    /// # Arguments
+    /// Returns the number of messages waiting in the mailbox of the
+    /// element this `BastionContext` is linked to, including the
+    /// ones that weren't placed into it yet (see [`try_recv_sync`]).
     ///
-    /// * `to` – the [`RefAddr`] to send the message to
-    /// * `msg` – The actual message to send
+    /// The number is approximate if messages are being received
+    /// concurrently.
     ///
     /// # Example
     ///
@@ -406,13 +763,12 @@ impl BastionContext {
     /// Bastion::children(|children| {
     ///     children.with_exec(|ctx: BastionContext| {
     ///         async move {
-    ///             // Wait for a message to be received...
-    ///             let smsg: SignedMessage = ctx.recv().await?;
-    ///             // Obtain address of this message sender...
-    ///             let sender_addr = smsg.signature();
-    ///             // And send something back
-    ///             ctx.tell(&sender_addr, "Ack").expect("Unable to acknowledge");
-    ///             Ok(())
+    ///             loop {
+    ///                 let msg = ctx.recv().await?;
+    ///                 if ctx.mailbox_len() > 1_000 {
+    ///                     // Shed some load...
+    ///                 }
+    ///             }
     ///         }
     ///     })
     /// }).expect("Couldn't create the children group.");
@@ -422,167 +778,1942 @@ impl BastionContext {
     /// # Bastion::block_until_stopped();
     /// ```
     ///
-    /// [`RefAddr`]: ../prelude/struct.RefAddr.html
-    pub fn tell<M: Message>(&self, to: &RefAddr, msg: M) -> Result<(), M> {
-        debug!(
-            "{:?}: Telling message: {:?} to: {:?}",
-            self.current().path(),
-            msg,
-            to.path()
-        );
-        let msg = BastionMessage::tell(msg);
-        let env = Envelope::new_with_sign(msg, self.signature());
-        // FIXME: panics?
-        to.sender()
-            .unbounded_send(env)
-            .map_err(|err| err.into_inner().into_msg().unwrap())
+    /// [`try_recv_sync`]: #method.try_recv_sync
+    pub fn mailbox_len(&self) -> usize {
+        let received = self
+            .state
+            .try_lock()
+            .map(|guard| guard.len())
+            .unwrap_or_default();
+
+        self.child.sender().len() + received
     }
 
-    /// Sends a message from behalf of current context to the addr,
-    /// allowing to addr owner answer.
-    ///
-    /// This method returns [`Answer`] if it succeeded, or `Err(msg)`
-    /// otherwise.
+    /// Retrieves asynchronously a message received by the element
+    /// this `BastionContext` is linked to and waits (always
+    /// asynchronously) for one if none has been received yet.
     ///
-    /// # Argument
+    /// If you don't need to wait until at least one message
+    /// can be retrieved, use [`try_recv`] instead.
     ///
-    /// * `msg` - The message to send.
+    /// This method returns [`SignedMessage`] if it succeeded, or `Err(())`
+    /// if the element was asked to stop and no message is left.
     ///
     /// # Example
     ///
-    /// ```
+    /// ```rust
     /// # use bastion::prelude::*;
     /// #
-    /// # fn main() {
-    ///     # Bastion::init();
-    /// // The message that will be "asked"...
-    /// const ASK_MSG: &'static str = "A message containing data (ask).";
-    /// // The message the will be "answered"...
-    /// const ANSWER_MSG: &'static str = "A message containing data (answer).";
-    ///
-    ///     # let children_ref =
-    /// // Create a new child...
+    /// # Bastion::init();
+    /// #
     /// Bastion::children(|children| {
     ///     children.with_exec(|ctx: BastionContext| {
     ///         async move {
-    ///             // ...which will receive the message asked...
-    ///             msg! { ctx.recv().await?,
-    ///                 msg: &'static str =!> {
-    ///                     assert_eq!(msg, ASK_MSG);
-    ///                     // Handle the message...
-    ///
-    ///                     // ...and eventually answer to it...
-    ///                     answer!(ctx, ANSWER_MSG);
-    ///                 };
-    ///                 // This won't happen because this example
-    ///                 // only "asks" a `&'static str`...
-    ///                 _: _ => ();
-    ///             }
+    ///             // This will block until a message has been received...
+    ///             let msg: SignedMessage = ctx.recv().await?;
     ///
     ///             Ok(())
     ///         }
     ///     })
     /// }).expect("Couldn't create the children group.");
+    /// #
+    /// # Bastion::start();
+    /// # Bastion::stop();
+    /// # Bastion::block_until_stopped();
+    /// ```
     ///
-    ///     # Bastion::children(|children| {
-    ///         # children.with_exec(move |ctx: BastionContext| {
-    ///             # let child_ref = children_ref.elems()[0].clone();
-    ///             # async move {
-    /// // Later, the message is "asked" to the child...
-    /// let answer: Answer = ctx.ask(&child_ref.addr(), ASK_MSG).expect("Couldn't send the message.");
-    ///
-    /// // ...and the child's answer is received...
-    /// msg! { answer.await.expect("Couldn't receive the answer."),
-    ///     msg: &'static str => {
-    ///         assert_eq!(msg, ANSWER_MSG);
-    ///         // Handle the answer...
-    ///     };
-    ///     // This won't happen because this example
-    ///     // only answers a `&'static str`...
-    ///     _: _ => ();
-    /// }
-    ///                 #
-    ///                 # Ok(())
-    ///             # }
-    ///         # })
-    ///     # }).unwrap();
-    ///     #
+    /// [`try_recv`]: #method.try_recv
+    /// [`SignedMessage`]: ../prelude/struct.SignedMessage.html
+    pub async fn recv(&self) -> Result<SignedMessage, ()> {
+        debug!("BastionContext({}): Waiting to receive message.", self.id);
+        loop {
+            let state = self.state.clone();
+            let mut guard = state.lock().await;
+
+            guard.handling(None);
+
+            // The messages are left in the mailbox until the element
+            // yielded once it received too many of them at once.
+            if !guard.has_budget() {
+                drop(guard);
+                pending!();
+                continue;
+            }
+
+            if let Some(msg) = guard.pop_message() {
+                trace!("BastionContext({}): Received message: {:?}", self.id, msg);
+                guard.spend_budget();
+                guard.handling(Some(&msg));
+                self.received(&msg);
+                return Ok(msg);
+            }
+
+            if guard.is_stopping() {
+                debug!("BastionContext({}): Stopping, no message left.", self.id);
+                return Err(());
+            }
+
+            drop(guard);
+            pending!();
+        }
+    }
+
+    /// Retrieves asynchronously the oldest message received by the
+    /// element this `BastionContext` is linked to for which
+    /// `predicate` returns `true`, waiting (always asynchronously)
+    /// for one if none has been received yet.
+    ///
+    /// The messages for which `predicate` returns `false` are kept
+    /// in the mailbox, in the order they were received, to be
+    /// retrieved later (e.g. using [`recv`]). Like any message
+    /// that wasn't retrieved, they are dropped once the element
+    /// stops.
+    ///
+    /// This method returns [`SignedMessage`] if it succeeded, or
+    /// `Err(())` if the element was asked to stop and no matching
+    /// message is left.
+    ///
+    /// # Arguments
+    ///
+    /// * `predicate` - The closure called with the messages, in the
+    ///     order they were received, until it returns `true`.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use bastion::prelude::*;
+    /// #
+    /// # Bastion::init();
+    /// #
+    /// #[derive(Debug)]
+    /// struct Reply {
+    ///     request: usize,
+    /// }
+    ///
+    /// Bastion::children(|children| {
+    ///     children.with_exec(|ctx: BastionContext| {
+    ///         async move {
+    ///             // Waiting for the reply to the request #42, leaving
+    ///             // the other messages in the mailbox...
+    ///             let reply = ctx
+    ///                 .recv_where(|msg| {
+    ///                     msg.peek::<Reply>()
+    ///                         .map(|reply| reply.request == 42)
+    ///                         .unwrap_or(false)
+    ///                 })
+    ///                 .await?;
+    ///
+    ///             Ok(())
+    ///         }
+    ///     })
+    /// }).expect("Couldn't create the children group.");
+    /// #
+    /// # Bastion::start();
+    /// # Bastion::stop();
+    /// # Bastion::block_until_stopped();
+    /// ```
+    ///
+    /// [`recv`]: #method.recv
+    /// [`SignedMessage`]: ../prelude/struct.SignedMessage.html
+    pub async fn recv_where<P>(&self, mut predicate: P) -> Result<SignedMessage, ()>
+    where
+        P: FnMut(&Msg) -> bool,
+    {
+        debug!(
+            "BastionContext({}): Waiting to receive a matching message.",
+            self.id
+        );
+        loop {
+            let state = self.state.clone();
+            let mut guard = state.lock().await;
+
+            guard.handling(None);
+            if !guard.has_budget() {
+                drop(guard);
+                pending!();
+                continue;
+            }
+
+            if let Some(msg) = guard.pop_message_where(&mut predicate) {
+                trace!("BastionContext({}): Received message: {:?}", self.id, msg);
+                guard.spend_budget();
+                guard.handling(Some(&msg));
+                self.received(&msg);
+                return Ok(msg);
+            }
+
+            if guard.is_stopping() {
+                debug!(
+                    "BastionContext({}): Stopping, no matching message left.",
+                    self.id
+                );
+                return Err(());
+            }
+
+            drop(guard);
+            pending!();
+        }
+    }
+
+    /// Retrieves asynchronously the oldest [`EncodedEnvelope`]
+    /// containing a message of type `M` received by the element
+    /// this `BastionContext` is linked to (see
+    /// [`ChildrenRef::broadcast_serialized`]) and decodes it using
+    /// `codec`, waiting (always asynchronously) for one if none has
+    /// been received yet.
+    ///
+    /// The other messages are kept in the mailbox, like with
+    /// [`recv_where`]. Envelopes that `codec` fails to decode are
+    /// reported as unhandled and skipped.
+    ///
+    /// This method returns the decoded message if it succeeded, or
+    /// `Err(())` if the element was asked to stop and no matching
+    /// envelope is left.
+    ///
+    /// This method is only available with the `codec` feature.
+    ///
+    /// # Arguments
+    ///
+    /// * `codec` - The codec the messages were encoded with.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use bastion::prelude::*;
+    /// use bastion::codec::{JsonCodec, SerializableMessage};
+    /// use serde::{Deserialize, Serialize};
+    /// #
+    /// # Bastion::init();
+    ///
+    /// #[derive(Debug, Serialize, Deserialize)]
+    /// struct Order {
+    ///     id: u64,
+    /// }
+    ///
+    /// impl SerializableMessage for Order {
+    ///     const TYPE_TAG: &'static str = "shop.Order";
+    /// }
+    ///
+    /// Bastion::children(|children| {
+    ///     children.with_exec(|ctx: BastionContext| {
+    ///         async move {
+    ///             let order: Order = ctx.recv_deserialized(&JsonCodec).await?;
+    ///
+    ///             Ok(())
+    ///         }
+    ///     })
+    /// }).expect("Couldn't create the children group.");
+    /// #
+    /// # Bastion::start();
+    /// # Bastion::stop();
+    /// # Bastion::block_until_stopped();
+    /// ```
+    ///
+    /// [`EncodedEnvelope`]: ../codec/struct.EncodedEnvelope.html
+    /// [`ChildrenRef::broadcast_serialized`]: ../children_ref/struct.ChildrenRef.html#method.broadcast_serialized
+    /// [`recv_where`]: #method.recv_where
+    #[cfg(feature = "codec")]
+    #[cfg_attr(feature = "docs", doc(cfg(feature = "codec")))]
+    pub async fn recv_deserialized<M, C>(&self, codec: &C) -> Result<M, ()>
+    where
+        M: SerializableMessage,
+        C: Codec,
+    {
+        loop {
+            let signed = self
+                .recv_where(|msg| {
+                    msg.peek::<EncodedEnvelope>()
+                        .map(|envelope| envelope.is::<M>())
+                        .unwrap_or(false)
+                })
+                .await?;
+            // The predicate only matched encoded envelopes.
+            let envelope = signed.msg.peek::<EncodedEnvelope>().unwrap();
+            match envelope.decode(codec) {
+                Ok(msg) => return Ok(msg),
+                Err(err) => {
+                    debug!(
+                        "BastionContext({}): Couldn't decode {}: {:?}",
+                        self.id, envelope.type_tag, err
+                    );
+                    signed.msg.send_error_log();
+                }
+            }
+        }
+    }
+
+    /// Retrieves asynchronously the oldest message received by the
+    /// element this `BastionContext` is linked to for which
+    /// `predicate` returns `true`, like [`recv_where`] but waiting
+    /// for at most `timeout`.
+    ///
+    /// This method returns [`SignedMessage`] if it succeeded, or
+    /// `Err(())` if `timeout` elapsed first or if the element was
+    /// asked to stop and no matching message is left.
+    ///
+    /// # Arguments
+    ///
+    /// * `predicate` - The closure called with the messages, in the
+    ///     order they were received, until it returns `true`.
+    /// * `timeout` - How long to wait for a matching message.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use bastion::prelude::*;
+    /// # use std::time::Duration;
+    /// #
+    /// # Bastion::init();
+    /// #
+    /// Bastion::children(|children| {
+    ///     children.with_exec(|ctx: BastionContext| {
+    ///         async move {
+    ///             let timeout = Duration::from_secs(1);
+    ///             match ctx.recv_where_timeout(|msg| msg.is::<usize>(), timeout).await {
+    ///                 Ok(msg) => {
+    ///                     // Handle the message...
+    ///                 }
+    ///                 Err(()) => {
+    ///                     // No matching message was received in time...
+    ///                 }
+    ///             }
+    ///
+    ///             Ok(())
+    ///         }
+    ///     })
+    /// }).expect("Couldn't create the children group.");
+    /// #
+    /// # Bastion::start();
+    /// # Bastion::stop();
+    /// # Bastion::block_until_stopped();
+    /// ```
+    ///
+    /// [`recv_where`]: #method.recv_where
+    /// [`SignedMessage`]: ../prelude/struct.SignedMessage.html
+    pub async fn recv_where_timeout<P>(
+        &self,
+        predicate: P,
+        timeout: Duration,
+    ) -> Result<SignedMessage, ()>
+    where
+        P: FnMut(&Msg) -> bool,
+    {
+        let recv = self.recv_where(predicate);
+        pin_mut!(recv);
+
+        match future::select(recv, Delay::new(timeout)).await {
+            Either::Left((msg, _)) => msg,
+            Either::Right(_) => {
+                debug!(
+                    "BastionContext({}): Timed out waiting for a matching message.",
+                    self.id
+                );
+                Err(())
+            }
+        }
+    }
+
+    /// Retrieves asynchronously up to `max` of the oldest messages
+    /// received by the element this `BastionContext` is linked to,
+    /// waiting (always asynchronously) for more of them until
+    /// either `max` messages were retrieved or `deadline` elapsed.
+    ///
+    /// This allows elements doing bulk processing (e.g. batching
+    /// database inserts) to handle their messages in batches.
+    ///
+    /// This method returns the retrieved messages, in the order
+    /// they were received, which might be fewer than `max` (or
+    /// none) if `deadline` elapsed first or if the element was
+    /// asked to stop.
+    ///
+    /// # Arguments
+    ///
+    /// * `max` - The maximum number of messages to retrieve.
+    /// * `deadline` - How long to wait for messages at most.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use bastion::prelude::*;
+    /// # use std::time::Duration;
+    /// #
+    /// # Bastion::init();
+    /// #
+    /// Bastion::children(|children| {
+    ///     children.with_exec(|ctx: BastionContext| {
+    ///         async move {
+    ///             loop {
+    ///                 let batch = ctx.recv_batch(100, Duration::from_millis(50)).await;
+    ///                 for msg in batch {
+    ///                     // Handle the message...
+    ///                 }
+    ///             }
+    ///         }
+    ///     })
+    /// }).expect("Couldn't create the children group.");
+    /// #
+    /// # Bastion::start();
+    /// # Bastion::stop();
+    /// # Bastion::block_until_stopped();
+    /// ```
+    pub async fn recv_batch(&self, max: usize, deadline: Duration) -> Vec<SignedMessage> {
+        debug!(
+            "BastionContext({}): Waiting to receive up to {} messages.",
+            self.id, max
+        );
+        let mut batch = Vec::with_capacity(max);
+        let mut delay = Delay::new(deadline);
+        loop {
+            let state = self.state.clone();
+            let mut guard = state.lock().await;
+
+            while batch.len() < max {
+                match guard.pop_message() {
+                    Some(msg) => {
+                        trace!("BastionContext({}): Received message: {:?}", self.id, msg);
+                        self.received(&msg);
+                        batch.push(msg);
+                    }
+                    None => break,
+                }
+            }
+
+            if batch.len() >= max || guard.is_stopping() {
+                break;
+            }
+
+            drop(guard);
+            if poll!(&mut delay).is_ready() {
+                debug!(
+                    "BastionContext({}): Deadline elapsed with {} messages.",
+                    self.id,
+                    batch.len()
+                );
+                break;
+            }
+
+            pending!();
+        }
+
+        batch
+    }
+
+    /// Stashes a message retrieved by the element this
+    /// `BastionContext` is linked to, to have it received again
+    /// once [`unstash_all`] (or [`unstash_one`]) is called (e.g.
+    /// when the element couldn't handle it yet because it is still
+    /// initializing).
+    ///
+    /// If the element stops or restarts before being unstashed,
+    /// its stashed messages are sent to the dead letters.
+    ///
+    /// This method returns `()` if it succeeded, or `Err(msg)` if
+    /// the stash is full (see [`Children::with_stash_capacity`]).
+    ///
+    /// # Arguments
+    ///
+    /// * `msg` - The message to stash.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use bastion::prelude::*;
+    /// #
+    /// # Bastion::init();
+    /// #
+    /// #[derive(Debug)]
+    /// struct Initialized;
+    ///
+    /// Bastion::children(|children| {
+    ///     children.with_exec(|ctx: BastionContext| {
+    ///         async move {
+    ///             // Stashing the messages received while initializing...
+    ///             loop {
+    ///                 let msg = ctx.recv().await?;
+    ///                 if msg.msg().is::<Initialized>() {
+    ///                     break;
+    ///                 }
+    ///
+    ///                 ctx.stash(msg).await.expect("The stash is full.");
+    ///             }
+    ///
+    ///             // ...and receiving them again once initialized.
+    ///             ctx.unstash_all().await;
+    ///             let msg: SignedMessage = ctx.recv().await?;
+    ///
+    ///             Ok(())
+    ///         }
+    ///     })
+    /// }).expect("Couldn't create the children group.");
+    /// #
+    /// # Bastion::start();
+    /// # Bastion::stop();
+    /// # Bastion::block_until_stopped();
+    /// ```
+    ///
+    /// [`unstash_all`]: #method.unstash_all
+    /// [`unstash_one`]: #method.unstash_one
+    /// [`Children::with_stash_capacity`]: ../children/struct.Children.html#method.with_stash_capacity
+    pub async fn stash(&self, msg: SignedMessage) -> Result<(), SignedMessage> {
+        trace!("BastionContext({}): Stashing message: {:?}", self.id, msg);
+        let state = self.state.clone();
+        let mut guard = state.lock().await;
+
+        guard.stash(msg).map_err(|msg| {
+            debug!("BastionContext({}): The stash is full.", self.id);
+            *msg
+        })
+    }
+
+    /// Places the messages stashed using [`stash`] back into the
+    /// mailbox of the element this `BastionContext` is linked to,
+    /// in the order they were received and ahead of the messages
+    /// that weren't retrieved yet.
+    ///
+    /// This method returns the number of messages that were
+    /// unstashed.
+    ///
+    /// # Example
+    ///
+    /// See [`stash`].
+    ///
+    /// [`stash`]: #method.stash
+    pub async fn unstash_all(&self) -> usize {
+        let state = self.state.clone();
+        let mut guard = state.lock().await;
+
+        let unstashed = guard.unstash_all();
+        debug!(
+            "BastionContext({}): Unstashed {} messages.",
+            self.id, unstashed
+        );
+        unstashed
+    }
+
+    /// Places the oldest message stashed using [`stash`] back into
+    /// the mailbox of the element this `BastionContext` is linked
+    /// to, ahead of the messages that weren't retrieved yet, the
+    /// other stashed messages staying stashed.
+    ///
+    /// This method returns whether a message was unstashed, which
+    /// isn't the case if the stash was empty.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use bastion::prelude::*;
+    /// #
+    /// # Bastion::init();
+    /// #
+    /// Bastion::children(|children| {
+    ///     children.with_exec(|ctx: BastionContext| {
+    ///         async move {
+    ///             let msg = ctx.recv().await?;
+    ///             ctx.stash(msg).await.expect("The stash is full.");
+    ///
+    ///             // Receiving the stashed message again...
+    ///             assert!(ctx.unstash_one().await);
+    ///             let msg: SignedMessage = ctx.recv().await?;
+    ///             // ...the stash being empty now.
+    ///             assert!(!ctx.unstash_one().await);
+    ///
+    ///             Ok(())
+    ///         }
+    ///     })
+    /// }).expect("Couldn't create the children group.");
+    /// #
+    /// # Bastion::start();
+    /// # Bastion::stop();
+    /// # Bastion::block_until_stopped();
+    /// ```
+    ///
+    /// [`stash`]: #method.stash
+    pub async fn unstash_one(&self) -> bool {
+        let state = self.state.clone();
+        let mut guard = state.lock().await;
+
+        let unstashed = guard.unstash_one();
+        debug!(
+            "BastionContext({}): Unstashed a message: {}",
+            self.id, unstashed
+        );
+        unstashed
+    }
+
+    /// Returns a [`Stream`] of the messages received by the element
+    /// this `BastionContext` is linked to, allowing to use stream
+    /// combinators instead of calling [`recv`] in a loop.
+    ///
+    /// The stream yields the same [`SignedMessage`]s as [`recv`]
+    /// (and can be used alongside it, each message being retrieved
+    /// only once) and ends once the element was asked to stop (using
+    /// [`ChildRef::stop`]) and no message is left.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use bastion::prelude::*;
+    /// # use futures::prelude::*;
+    /// #
+    /// # Bastion::init();
+    /// #
+    /// Bastion::children(|children| {
+    ///     children.with_exec(|ctx: BastionContext| {
+    ///         async move {
+    ///             ctx.messages()
+    ///                 .for_each_concurrent(8, |msg| async move {
+    ///                     msg! { msg,
+    ///                         msg: &'static str => {
+    ///                             // Handle the message...
+    ///                         };
+    ///                         _: _ => ();
+    ///                     }
+    ///                 })
+    ///                 .await;
+    ///
+    ///             Ok(())
+    ///         }
+    ///     })
+    /// }).expect("Couldn't create the children group.");
+    /// #
+    /// # Bastion::start();
+    /// # Bastion::stop();
+    /// # Bastion::block_until_stopped();
+    /// ```
+    ///
+    /// [`Stream`]: https://docs.rs/futures/0.3/futures/stream/trait.Stream.html
+    /// [`recv`]: #method.recv
+    /// [`SignedMessage`]: ../prelude/struct.SignedMessage.html
+    /// [`ChildRef::stop`]: ../child_ref/struct.ChildRef.html#method.stop
+    pub fn messages(&self) -> impl Stream<Item = SignedMessage> + '_ {
+        debug!("BastionContext({}): Streaming messages.", self.id);
+        stream::unfold(self, |ctx| async move {
+            let msg = ctx.recv().await.ok()?;
+            Some((msg, ctx))
+        })
+    }
+
+    /// Returns the [`TraceId`] attached to the last message
+    /// received by the element this `BastionContext` is linked to,
+    /// if any.
+    ///
+    /// This `TraceId` is automatically attached to the messages
+    /// sent using this `BastionContext` until the next message is
+    /// received, allowing to follow a request through the elements
+    /// it hops through.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use bastion::prelude::*;
+    /// #
+    /// # Bastion::init();
+    /// #
+    /// Bastion::children(|children| {
+    ///     children.with_exec(|ctx: BastionContext| {
+    ///         async move {
+    ///             let msg: SignedMessage = ctx.recv().await?;
+    ///             if let Some(trace) = ctx.current_trace() {
+    ///                 println!("Handling request {}", trace);
+    ///             }
+    ///
+    ///             Ok(())
+    ///         }
+    ///     })
+    /// }).expect("Couldn't create the children group.");
+    /// #
+    /// # Bastion::start();
+    /// # Bastion::stop();
+    /// # Bastion::block_until_stopped();
+    /// ```
+    ///
+    /// [`TraceId`]: ../envelope/struct.TraceId.html
+    pub fn current_trace(&self) -> Option<TraceId> {
+        // FIXME: panics
+        *self.trace.lock().unwrap()
+    }
+
+    /// Answers the message being handled by the closure given to
+    /// [`Children::with_exec_typed`], if it was "asked" and wasn't
+    /// answered yet.
+    ///
+    /// This method returns `()` if it succeeded, or `Err(msg)`
+    /// otherwise.
+    ///
+    /// # Arguments
+    ///
+    /// * `msg` - The answer to send.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use bastion::prelude::*;
+    /// #
+    /// # Bastion::init();
+    /// #
+    /// #[derive(Debug, Clone)]
+    /// struct Square(u64);
+    ///
+    /// Bastion::children(|children| {
+    ///     children.with_exec_typed(|ctx: BastionContext, Square(n)| {
+    ///         async move {
+    ///             ctx.answer(n * n).ok();
+    ///             Ok(())
+    ///         }
+    ///     })
+    /// }).expect("Couldn't create the children group.");
+    /// #
+    /// # Bastion::start();
+    /// # Bastion::stop();
+    /// # Bastion::block_until_stopped();
+    /// ```
+    ///
+    /// [`Children::with_exec_typed`]: ../children/struct.Children.html#method.with_exec_typed
+    pub fn answer<M: Message>(&self, msg: M) -> Result<(), M> {
+        // FIXME: panics
+        let sender = self.answer.lock().unwrap().take();
+        match sender {
+            Some(sender) => sender.send(msg, self.signature()),
+            None => Err(msg),
+        }
+    }
+
+    // Sets the sender answering the message about to be handled
+    // by the closure given to `Children::with_exec_typed`.
+    pub(crate) fn set_answer(&self, sender: Option<AnswerSender>) {
+        // FIXME: panics
+        *self.answer.lock().unwrap() = sender;
+    }
+
+    // Called with every message retrieved from the mailbox.
+    fn received(&self, msg: &SignedMessage) {
+        // FIXME: panics
+        *self.trace.lock().unwrap() = msg.trace();
+
+        if msg.msg.peek::<Tick>().is_some() {
+            self.ticker.received();
+        }
+    }
+
+    /// Returns [`RefAddr`] of the current `BastionContext`
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use bastion::prelude::*;
+    /// #
+    /// # Bastion::init();
+    /// #
+    ///
+    /// Bastion::children(|children| {
+    ///     children.with_exec(|ctx: BastionContext| {
+    ///         async move {
+    ///             ctx.tell(&ctx.signature(), "Hello to myself");
+    ///
+    ///             # Bastion::stop();
+    ///             Ok(())
+    ///         }
+    ///     })
+    /// }).expect("Couldn't create the children group.");
+    /// #
+    /// # Bastion::start();
+    /// # Bastion::block_until_stopped();
+    /// ```
+    ///
+    /// [`RefAddr`]: /prelude/struct.Answer.html
+    pub fn signature(&self) -> RefAddr {
+        RefAddr::new(
+            self.current().path().clone(),
+            self.current().sender().clone(),
+        )
+    }
+
+    /// Sends a message to the specified [`RefAddr`]
+    ///
+    /// # Arguments
+    ///
+    /// * `to` – the [`RefAddr`] to send the message to
+    /// * `msg` – The actual message to send
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use bastion::prelude::*;
+    /// #
+    /// # Bastion::init();
+    /// #
+    /// Bastion::children(|children| {
+    ///     children.with_exec(|ctx: BastionContext| {
+    ///         async move {
+    ///             // Wait for a message to be received...
+    ///             let smsg: SignedMessage = ctx.recv().await?;
+    ///             // Obtain address of this message sender...
+    ///             let sender_addr = smsg.signature();
+    ///             // And send something back
+    ///             ctx.tell(&sender_addr, "Ack").expect("Unable to acknowledge");
+    ///             Ok(())
+    ///         }
+    ///     })
+    /// }).expect("Couldn't create the children group.");
+    /// #
+    /// # Bastion::start();
+    /// # Bastion::stop();
+    /// # Bastion::block_until_stopped();
+    /// ```
+    ///
+    /// [`RefAddr`]: ../prelude/struct.RefAddr.html
+    pub fn tell<M: Message>(&self, to: &RefAddr, msg: M) -> Result<(), M> {
+        debug!(
+            "{:?}: Telling message: {:?} to: {:?}",
+            self.current().path(),
+            msg,
+            to.path()
+        );
+        let msg = BastionMessage::tell(msg);
+        let env = Envelope::new_with_sign(msg, self.signature()).with_trace(self.current_trace());
+        // FIXME: panics?
+        to.sender()
+            .unbounded_send(env)
+            .map_err(|env| env.into_msg().unwrap())
+    }
+
+    /// Sends a message from behalf of current context to the addr,
+    /// allowing to addr owner answer.
+    ///
+    /// This method returns [`Answer`] if it succeeded, or `Err(msg)`
+    /// otherwise.
+    ///
+    /// # Argument
+    ///
+    /// * `msg` - The message to send.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use bastion::prelude::*;
+    /// #
+    /// # fn main() {
+    ///     # Bastion::init();
+    /// // The message that will be "asked"...
+    /// const ASK_MSG: &'static str = "A message containing data (ask).";
+    /// // The message the will be "answered"...
+    /// const ANSWER_MSG: &'static str = "A message containing data (answer).";
+    ///
+    ///     # let children_ref =
+    /// // Create a new child...
+    /// Bastion::children(|children| {
+    ///     children.with_exec(|ctx: BastionContext| {
+    ///         async move {
+    ///             // ...which will receive the message asked...
+    ///             msg! { ctx.recv().await?,
+    ///                 msg: &'static str =!> {
+    ///                     assert_eq!(msg, ASK_MSG);
+    ///                     // Handle the message...
+    ///
+    ///                     // ...and eventually answer to it...
+    ///                     answer!(ctx, ANSWER_MSG);
+    ///                 };
+    ///                 // This won't happen because this example
+    ///                 // only "asks" a `&'static str`...
+    ///                 _: _ => ();
+    ///             }
+    ///
+    ///             Ok(())
+    ///         }
+    ///     })
+    /// }).expect("Couldn't create the children group.");
+    ///
+    ///     # Bastion::children(|children| {
+    ///         # children.with_exec(move |ctx: BastionContext| {
+    ///             # let child_ref = children_ref.elems()[0].clone();
+    ///             # async move {
+    /// // Later, the message is "asked" to the child...
+    /// let answer: Answer = ctx.ask(&child_ref.addr(), ASK_MSG).expect("Couldn't send the message.");
+    ///
+    /// // ...and the child's answer is received...
+    /// msg! { answer.await.expect("Couldn't receive the answer."),
+    ///     msg: &'static str => {
+    ///         assert_eq!(msg, ANSWER_MSG);
+    ///         // Handle the answer...
+    ///     };
+    ///     // This won't happen because this example
+    ///     // only answers a `&'static str`...
+    ///     _: _ => ();
+    /// }
+    ///                 #
+    ///                 # Ok(())
+    ///             # }
+    ///         # })
+    ///     # }).unwrap();
+    ///     #
     ///     # Bastion::start();
     ///     # Bastion::stop();
     ///     # Bastion::block_until_stopped();
     /// # }
     /// ```
     ///
-    /// [`Answer`]: /message/struct.Answer.html
-    pub fn ask<M: Message>(&self, to: &RefAddr, msg: M) -> Result<Answer, M> {
-        debug!(
-            "{:?}: Asking message: {:?} to: {:?}",
-            self.current().path(),
+    /// [`Answer`]: /message/struct.Answer.html
+    pub fn ask<M: Message>(&self, to: &RefAddr, msg: M) -> Result<Answer, M> {
+        debug!(
+            "{:?}: Asking message: {:?} to: {:?}",
+            self.current().path(),
+            msg,
+            to
+        );
+        let (msg, answer) = BastionMessage::ask(msg);
+        let env = Envelope::new_with_sign(msg, self.signature()).with_trace(self.current_trace());
+        // FIXME: panics?
+        to.sender()
+            .unbounded_send(env)
+            .map_err(|env| env.into_msg().unwrap())?;
+
+        Ok(answer)
+    }
+
+    /// Sends a message to the element this `BastionContext` is
+    /// linked to once the given duration elapsed, as if it was
+    /// "told" using [`tell`].
+    ///
+    /// The delivery can be cancelled using the returned
+    /// [`ScheduledSend`] and is automatically cancelled when the
+    /// system stops.
+    ///
+    /// # Arguments
+    ///
+    /// * `delay` - How long to wait before sending the message.
+    /// * `msg` - The message to send.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use bastion::prelude::*;
+    /// # use std::time::Duration;
+    /// #
+    /// # Bastion::init();
+    /// #
+    /// Bastion::children(|children| {
+    ///     children.with_exec(|ctx: BastionContext| {
+    ///         async move {
+    ///             // Retry later...
+    ///             ctx.notify_self_after(Duration::from_secs(5), "Retry.");
+    ///
+    ///             Ok(())
+    ///         }
+    ///     })
+    /// }).expect("Couldn't create the children group.");
+    /// #
+    /// # Bastion::start();
+    /// # Bastion::stop();
+    /// # Bastion::block_until_stopped();
+    /// ```
+    ///
+    /// [`tell`]: #method.tell
+    /// [`ScheduledSend`]: ../scheduler/struct.ScheduledSend.html
+    pub fn notify_self_after<M: Message>(&self, delay: Duration, msg: M) -> ScheduledSend {
+        debug!(
+            "BastionContext({}): Scheduling message to self in {:?}: {:?}",
+            self.id, delay, msg
+        );
+        let addr = self.signature();
+        let trace = self.current_trace();
+        let system = self.children.system().clone();
+        self.children.system().timers().schedule(delay, move || {
+            let msg = BastionMessage::tell(msg);
+            let env = Envelope::new_with_sign(msg, addr.clone()).with_trace(trace);
+            if let Err(err) = addr.sender().unbounded_send(env) {
+                system.dead_letters().sender().unbounded_send(*err).ok();
+            }
+        })
+    }
+
+    /// Runs the given closure on the executor's blocking pool
+    /// (see [`Config::with_blocking_threads`]), returning a future
+    /// resolving to its output, to let the element this
+    /// `BastionContext` is linked to call blocking code (e.g.
+    /// synchronous IO or C libraries) without starving the other
+    /// elements.
+    ///
+    /// A panic of the closure makes the element panic once the
+    /// future is awaited, which its supervisor handles as it would
+    /// handle any other fault of the element. Stopping or killing
+    /// the element while the closure is running doesn't interrupt
+    /// it, but the element doesn't run anything after it.
+    ///
+    /// # Arguments
+    ///
+    /// * `f` - The blocking closure to run.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use bastion::prelude::*;
+    /// # use std::{thread, time::Duration};
+    /// #
+    /// # Bastion::init();
+    /// #
+    /// Bastion::children(|children| {
+    ///     children.with_exec(|ctx: BastionContext| async move {
+    ///         loop {
+    ///             msg! { ctx.recv().await?,
+    ///                 path: &'static str => {
+    ///                     let len = ctx.blocking(move || {
+    ///                         // Some synchronous IO...
+    ///                         thread::sleep(Duration::from_millis(10));
+    ///                         path.len()
+    ///                     }).await;
+    ///                     // ...
+    ///                 };
+    ///                 _: _ => ();
+    ///             }
+    ///         }
+    ///     })
+    /// }).expect("Couldn't create the children group.");
+    /// #
+    /// # Bastion::start();
+    /// # Bastion::stop();
+    /// # Bastion::block_until_stopped();
+    /// ```
+    ///
+    /// [`Config::with_blocking_threads`]: ../struct.Config.html#method.with_blocking_threads
+    pub async fn blocking<F, R>(&self, f: F) -> R
+    where
+        F: FnOnce() -> R + Send + 'static,
+        R: Send + 'static,
+    {
+        debug!("BastionContext({}): Running a blocking closure.", self.id);
+        let id = self.id.clone();
+        let handle = executor::blocking(async move {
+            // The panics of the closure are the ones of the element.
+            let _guard = panic_handler::enter_child(&id);
+            f()
+        });
+
+        match handle.await {
+            Some(output) => output,
+            None => panic::resume_unwind(Box::new(format!(
+                "Child({}): The blocking closure panicked.",
+                self.id
+            ))),
+        }
+    }
+
+    /// Waits until as many elements (or other futures) as the given
+    /// barrier was created for are waiting on it, returning whether
+    /// the element this `BastionContext` is linked to was the last.
+    ///
+    /// The elements waiting on a barrier can belong to different
+    /// children groups, and the barrier can be waited on again once
+    /// they all resumed (see [`Barrier`]).
+    ///
+    /// # Arguments
+    ///
+    /// * `barrier` - The barrier to wait on.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use bastion::prelude::*;
+    /// use bastion::sync::Barrier;
+    /// #
+    /// # Bastion::init();
+    ///
+    /// let barrier = Barrier::new(2);
+    /// for _ in 0..2 {
+    ///     let barrier = barrier.clone();
+    ///     Bastion::children(move |children| {
+    ///         let barrier = barrier.clone();
+    ///         children.with_exec(move |ctx: BastionContext| {
+    ///             let barrier = barrier.clone();
+    ///             async move {
+    ///                 // Loading some data...
+    ///                 if ctx.wait_barrier(&barrier).await.is_leader() {
+    ///                     // ...which both groups now did.
+    ///                 }
+    ///
+    ///                 Ok(())
+    ///             }
+    ///         })
+    ///     }).expect("Couldn't create the children group.");
+    /// }
+    /// #
+    /// # Bastion::start();
+    /// # Bastion::stop();
+    /// # Bastion::block_until_stopped();
+    /// ```
+    ///
+    /// [`Barrier`]: ../sync/struct.Barrier.html
+    pub async fn wait_barrier(&self, barrier: &Barrier) -> BarrierWaitResult {
+        debug!("BastionContext({}): Waiting on a barrier.", self.id);
+        barrier.wait().await
+    }
+
+    /// Changes the interval at which the element this
+    /// `BastionContext` is linked to receives [`Tick`] messages,
+    /// overriding the one set using [`Children::with_tick`].
+    ///
+    /// # Arguments
+    ///
+    /// * `interval` - The new interval between two ticks, or
+    ///     `None` to stop receiving them.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use bastion::prelude::*;
+    /// # use std::time::Duration;
+    /// #
+    /// # Bastion::init();
+    /// #
+    /// Bastion::children(|children| {
+    ///     children
+    ///         .with_tick(Duration::from_secs(1))
+    ///         .with_exec(|ctx: BastionContext| async move {
+    ///             msg! { ctx.recv().await?,
+    ///                 _tick: Tick => {
+    ///                     // The token was refreshed, no need to tick anymore...
+    ///                     ctx.set_tick(None);
+    ///                 };
+    ///                 _: _ => ();
+    ///             }
+    ///
+    ///             Ok(())
+    ///         })
+    /// }).expect("Couldn't create the children group.");
+    /// #
+    /// # Bastion::start();
+    /// # Bastion::stop();
+    /// # Bastion::block_until_stopped();
+    /// ```
+    ///
+    /// [`Tick`]: ../scheduler/struct.Tick.html
+    /// [`Children::with_tick`]: ../children/struct.Children.html#method.with_tick
+    pub fn set_tick(&self, interval: Option<Duration>) {
+        debug!(
+            "BastionContext({}): Setting tick interval: {:?}",
+            self.id, interval
+        );
+        self.ticker.set(interval);
+    }
+
+    /// Links the element this `BastionContext` is linked to with
+    /// the one referenced by the given [`ChildRef`], so that each
+    /// of them is "told" a [`LinkDown`] message when the other
+    /// stops, is killed or faults.
+    ///
+    /// Links are removed once they are triggered, including when
+    /// an element gets restarted after a fault, in which case the
+    /// restarted elements are responsible for linking again.
+    ///
+    /// This method returns `()` if it succeeded, or `Err(())` if
+    /// the referenced element belongs to another [`BastionRuntime`].
+    ///
+    /// # Arguments
+    ///
+    /// * `other` - The element to link to.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use bastion::prelude::*;
+    /// #
+    /// # Bastion::init();
+    /// #
+    /// # let other = Bastion::children(|children| children).unwrap();
+    /// # let other = other.elems()[0].clone();
+    /// Bastion::children(move |children| {
+    ///     let other = other.clone();
+    ///     children.with_exec(move |ctx: BastionContext| {
+    ///         let other = other.clone();
+    ///         async move {
+    ///             ctx.link(&other).expect("Couldn't link the elements.");
+    ///
+    ///             Ok(())
+    ///         }
+    ///     })
+    /// }).expect("Couldn't create the children group.");
+    /// #
+    /// # Bastion::start();
+    /// # Bastion::stop();
+    /// # Bastion::block_until_stopped();
+    /// ```
+    ///
+    /// [`ChildRef`]: ../child_ref/struct.ChildRef.html
+    /// [`LinkDown`]: struct.LinkDown.html
+    /// [`BastionRuntime`]: ../struct.BastionRuntime.html
+    pub fn link(&self, other: &ChildRef) -> Result<(), ()> {
+        self.check_runtime(other)?;
+        self.children
+            .system()
+            .links()
+            .watch(other.id(), self.current());
+        self.children
+            .system()
+            .links()
+            .watch(self.current().id(), other);
+
+        Ok(())
+    }
+
+    /// Makes the element this `BastionContext` is linked to monitor
+    /// the one referenced by the given [`ChildRef`], so that it is
+    /// "told" a [`LinkDown`] message when the monitored element
+    /// stops, is killed or faults (the monitored element doesn't
+    /// get notified of anything).
+    ///
+    /// This method returns `()` if it succeeded, or `Err(())` if
+    /// the referenced element belongs to another [`BastionRuntime`].
+    ///
+    /// # Arguments
+    ///
+    /// * `other` - The element to monitor.
+    ///
+    /// [`ChildRef`]: ../child_ref/struct.ChildRef.html
+    /// [`LinkDown`]: struct.LinkDown.html
+    /// [`BastionRuntime`]: ../struct.BastionRuntime.html
+    pub fn monitor(&self, other: &ChildRef) -> Result<(), ()> {
+        self.check_runtime(other)?;
+        self.children
+            .system()
+            .links()
+            .watch(other.id(), self.current());
+
+        Ok(())
+    }
+
+    fn check_runtime(&self, other: &ChildRef) -> Result<(), ()> {
+        if other.runtime_id() != self.current().runtime_id() {
+            warn!(
+                "BastionContext({}): Child({}) belongs to another runtime.",
+                self.id,
+                other.id()
+            );
+            return Err(());
+        }
+
+        Ok(())
+    }
+
+    /// Sends the notification to each declared dispatcher of the actor.
+    ///
+    /// # Argument
+    ///
+    /// * `dispatchers` - Vector of dispatcher names to which need to
+    /// deliver a notification.
+    /// * `notification_type` - The type of the notification to send.
+    ///
+    pub fn notify(&self, dispatchers: &[DispatcherType], notification_type: NotificationType) {
+        let global_dispatcher = self.children.system().dispatcher();
+        let from_actor = self.current();
+        global_dispatcher.notify(from_actor, dispatchers, notification_type);
+    }
+
+    /// Sends the broadcasted message to the target group(s).
+    ///
+    /// # Argument
+    ///
+    /// * `target` - Defines the message receivers in according with
+    /// the [`BroadcastTarget`] value.
+    /// * `message` - The broadcasted message.
+    ///
+    /// [`BroadcastTarget`]: ../dispatcher/enum.DispatcherType.html
+    pub fn broadcast_message<M: Message>(&self, target: BroadcastTarget, message: M) {
+        let msg = Arc::new(
+            SignedMessage::new(Msg::broadcast(message), self.signature())
+                .with_trace(self.current_trace()),
+        );
+
+        let global_dispatcher = self.children.system().dispatcher();
+        global_dispatcher.broadcast_message(target, &msg);
+    }
+
+    /// Subscribes the element that is linked to this `BastionContext`
+    /// to the given topic, making it receive every message that
+    /// gets published to it using [`Bastion::publish`], wherever
+    /// the publisher lives in the supervision tree.
+    ///
+    /// The subscriptions of an element are automatically removed
+    /// when it stops, faults or gets restarted. Restarted elements
+    /// are thus responsible for subscribing again to the topics
+    /// they are interested in.
+    ///
+    /// # Arguments
+    ///
+    /// * `topic` - The name of the topic to subscribe to.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use bastion::prelude::*;
+    /// #
+    /// # Bastion::init();
+    /// #
+    /// Bastion::children(|children| {
+    ///     children.with_exec(|ctx: BastionContext| {
+    ///         async move {
+    ///             ctx.subscribe("invoices");
+    ///
+    ///             msg! { ctx.recv().await?,
+    ///                 ref invoice: &'static str => {
+    ///                     // Handle the published invoice...
+    ///                 };
+    ///                 _: _ => ();
+    ///             }
+    ///
+    ///             Ok(())
+    ///         }
+    ///     })
+    /// }).expect("Couldn't create the children group.");
+    /// #
+    /// # Bastion::start();
+    /// # Bastion::stop();
+    /// # Bastion::block_until_stopped();
+    /// ```
+    ///
+    /// [`Bastion::publish`]: ../struct.Bastion.html#method.publish
+    pub fn subscribe(&self, topic: impl Into<String>) {
+        self.children
+            .system()
+            .topics()
+            .subscribe(topic.into(), self.current());
+    }
+
+    /// Unsubscribes the element that is linked to this
+    /// `BastionContext` from the given topic.
+    ///
+    /// # Arguments
+    ///
+    /// * `topic` - The name of the topic to unsubscribe from.
+    pub fn unsubscribe(&self, topic: &str) {
+        self.children
+            .system()
+            .topics()
+            .unsubscribe(topic, self.current().id());
+    }
+
+    /// Returns a [`ChildrenRef`] referencing the running children
+    /// group of this element's runtime that was given the specified
+    /// name (using [`Children::with_name`]), if any.
+    ///
+    /// A children group being restarted by its supervisor stays
+    /// registered until its restarted incarnation replaces it, so
+    /// the returned reference might be the one of a faulted group,
+    /// sending to which fails (returning the message).
+    ///
+    /// # Arguments
+    ///
+    /// * `name` - The name of the children group to look up.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use bastion::prelude::*;
+    /// #
+    /// # Bastion::init();
+    /// #
+    /// Bastion::children(|children| {
+    ///     children.with_exec(|ctx: BastionContext| {
+    ///         async move {
+    ///             if let Some(workers) = ctx.lookup("workers") {
+    ///                 workers.broadcast("work").ok();
+    ///             }
+    ///
+    ///             Ok(())
+    ///         }
+    ///     })
+    /// }).expect("Couldn't create the children group.");
+    /// #
+    /// # Bastion::start();
+    /// # Bastion::stop();
+    /// # Bastion::block_until_stopped();
+    /// ```
+    ///
+    /// [`ChildrenRef`]: ../children_ref/struct.ChildrenRef.html
+    /// [`Children::with_name`]: ../children/struct.Children.html#method.with_name
+    pub fn lookup(&self, name: &str) -> Option<ChildrenRef> {
+        self.children.system().names().lookup(name)
+    }
+
+    /// Emits an output to the children group of the element this
+    /// `BastionContext` belongs to, which folds it into the state
+    /// it accumulates (see [`Children::with_reducer`]).
+    ///
+    /// This method returns `()` if it succeeded, or `Err(output)`
+    /// if the children group stopped. Outputs emitted to a group
+    /// without reducer, or of another type than the one its reducer
+    /// expects, are dropped.
+    ///
+    /// # Arguments
+    ///
+    /// * `output` - The output to emit.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use bastion::prelude::*;
+    /// #
+    /// # Bastion::init();
+    /// #
+    /// Bastion::children(|children| {
+    ///     children
+    ///         .with_reducer(0u64, |sum: u64, count: u64| sum + count)
+    ///         .with_exec(|ctx: BastionContext| {
+    ///             async move {
+    ///                 ctx.emit(1u64).expect("Couldn't emit the output.");
+    ///
+    ///                 Ok(())
+    ///             }
+    ///         })
+    /// }).expect("Couldn't create the children group.");
+    /// #
+    /// # Bastion::start();
+    /// # Bastion::stop();
+    /// # Bastion::block_until_stopped();
+    /// ```
+    ///
+    /// [`Children::with_reducer`]: ../children/struct.Children.html#method.with_reducer
+    pub fn emit<M: Message>(&self, output: M) -> Result<(), M> {
+        debug!("BastionContext({}): Emitting output: {:?}", self.id, output);
+        let msg = BastionMessage::emit(output);
+        let env = Envelope::new(
             msg,
-            to
+            self.current().path().clone(),
+            self.current().sender().clone(),
         );
-        let (msg, answer) = BastionMessage::ask(msg);
-        let env = Envelope::new_with_sign(msg, self.signature());
         // FIXME: panics?
-        to.sender()
+        self.children
+            .sender()
             .unbounded_send(env)
-            .map_err(|err| err.into_inner().into_msg().unwrap())?;
-
-        Ok(answer)
+            .map_err(|env| env.into_msg().unwrap())
     }
 
-    /// Sends the notification to each declared dispatcher of the actor.
+    /// Spawns a new element in the children group of the element
+    /// this `BastionContext` belongs to, running the future
+    /// returned by the given closure instead of the one set using
+    /// [`Children::with_exec`].
     ///
-    /// # Argument
+    /// The new element is supervised like its siblings and, when
+    /// restarted on its own, runs the given closure again. It isn't
+    /// spawned again if the whole children group gets restarted.
     ///
-    /// * `dispatchers` - Vector of dispatcher names to which need to
-    /// deliver a notification.
-    /// * `notification_type` - The type of the notification to send.
+    /// This method returns a [`ChildRef`] referencing the new
+    /// element, which can be used right away (messages sent to it
+    /// are received once it started). If the children group stopped,
+    /// the element is never spawned and sending messages to it fails.
     ///
-    pub fn notify(&self, dispatchers: &[DispatcherType], notification_type: NotificationType) {
-        let global_dispatcher = SYSTEM.dispatcher();
-        let from_actor = self.current();
-        global_dispatcher.notify(from_actor, dispatchers, notification_type);
+    /// # Arguments
+    ///
+    /// * `init` - The closure taking a [`BastionContext`] and returning
+    ///     the [`Future`] the new element runs.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use bastion::prelude::*;
+    /// #
+    /// # Bastion::init();
+    /// #
+    /// Bastion::children(|children| {
+    ///     children.with_exec(|ctx: BastionContext| {
+    ///         async move {
+    ///             let helper = ctx.spawn_sibling(|ctx: BastionContext| {
+    ///                 async move {
+    ///                     // Help...
+    ///                     Ok(())
+    ///                 }
+    ///             });
+    ///             helper.tell_anonymously("help").ok();
+    ///
+    ///             Ok(())
+    ///         }
+    ///     })
+    /// }).expect("Couldn't create the children group.");
+    /// #
+    /// # Bastion::start();
+    /// # Bastion::stop();
+    /// # Bastion::block_until_stopped();
+    /// ```
+    ///
+    /// [`Children::with_exec`]: ../children/struct.Children.html#method.with_exec
+    /// [`ChildRef`]: ../child_ref/struct.ChildRef.html
+    /// [`Future`]: https://doc.rust-lang.org/std/future/trait.Future.html
+    pub fn spawn_sibling<I, F>(&self, init: I) -> ChildRef
+    where
+        I: Fn(BastionContext) -> F + Send + 'static,
+        F: Future<Output = Result<(), ()>> + Send + 'static,
+    {
+        let parent = Parent::children(self.children.clone());
+        let bcast = Broadcast::new(parent, BastionPathElement::Child(BastionId::new()));
+        debug!(
+            "BastionContext({}): Spawning sibling Child({}).",
+            self.id,
+            bcast.id()
+        );
+
+        let child_ref = ChildRef::new(
+            bcast.id().clone(),
+            bcast.sender().clone(),
+            self.current().name().to_string(),
+            bcast.path().clone(),
+            self.children.system().clone(),
+        );
+
+        let msg = BastionMessage::add_child(Init::new(init), bcast);
+        let env = Envelope::new(
+            msg,
+            self.current().path().clone(),
+            self.current().sender().clone(),
+        );
+        // If the children group stopped, the element's channel gets
+        // dropped along with the envelope.
+        self.children.sender().unbounded_send(env).ok();
+
+        child_ref
     }
+}
 
-    /// Sends the broadcasted message to the target group(s).
+impl ThreadContext {
+    pub(crate) fn new(
+        child: ChildRef,
+        children: ChildrenRef,
+        receiver: mpsc::Receiver<SignedMessage>,
+    ) -> Self {
+        ThreadContext {
+            child,
+            children,
+            receiver,
+        }
+    }
+
+    /// Returns a [`ChildRef`] referencing the element this
+    /// `ThreadContext` is linked to.
     ///
-    /// # Argument
+    /// [`ChildRef`]: ../child_ref/struct.ChildRef.html
+    pub fn current(&self) -> &ChildRef {
+        &self.child
+    }
+
+    /// Returns a [`ChildrenRef`] referencing the children group of
+    /// the element this `ThreadContext` is linked to.
     ///
-    /// * `target` - Defines the message receivers in according with
-    /// the [`BroadcastTarget`] value.
-    /// * `message` - The broadcasted message.
+    /// [`ChildrenRef`]: ../children_ref/struct.ChildrenRef.html
+    pub fn parent(&self) -> &ChildrenRef {
+        &self.children
+    }
+
+    /// Retrieves a message received by the element this
+    /// `ThreadContext` is linked to, blocking the current thread
+    /// until one has been received.
     ///
-    /// [`BroadcastTarget`]: ../dispatcher/enum.DispatcherType.html
-    pub fn broadcast_message<M: Message>(&self, target: BroadcastTarget, message: M) {
-        let msg = Arc::new(SignedMessage {
-            msg: Msg::broadcast(message),
-            sign: self.signature(),
-        });
+    /// This method returns [`SignedMessage`] if it succeeded, or
+    /// `Err(())` if the element was asked to stop (or was killed),
+    /// after which the thread should return.
+    ///
+    /// [`SignedMessage`]: ../prelude/struct.SignedMessage.html
+    pub fn recv(&self) -> Result<SignedMessage, ()> {
+        trace!(
+            "ThreadContext({}): Waiting to receive message.",
+            self.child.id()
+        );
+        self.receiver.recv().map_err(|_| ())
+    }
 
-        let global_dispatcher = SYSTEM.dispatcher();
-        global_dispatcher.broadcast_message(target, &msg);
+    /// Retrieves a message received by the element this
+    /// `ThreadContext` is linked to, if any, without blocking.
+    ///
+    /// This method returns [`SignedMessage`] if a message was
+    /// available, or `None` otherwise.
+    ///
+    /// [`SignedMessage`]: ../prelude/struct.SignedMessage.html
+    pub fn try_recv(&self) -> Option<SignedMessage> {
+        self.receiver.try_recv().ok()
+    }
+
+    /// Retrieves a message received by the element this
+    /// `ThreadContext` is linked to, like [`recv`] but blocking
+    /// the current thread for at most `timeout`.
+    ///
+    /// This method returns [`SignedMessage`] if it succeeded, or
+    /// `Err(())` if `timeout` elapsed first or if the element was
+    /// asked to stop (or was killed).
+    ///
+    /// # Arguments
+    ///
+    /// * `timeout` - How long to wait for a message.
+    ///
+    /// [`recv`]: #method.recv
+    /// [`SignedMessage`]: ../prelude/struct.SignedMessage.html
+    pub fn recv_timeout(&self, timeout: Duration) -> Result<SignedMessage, ()> {
+        self.receiver.recv_timeout(timeout).map_err(|_| ())
     }
 }
 
 impl ContextState {
-    pub(crate) fn new() -> Self {
+    pub(crate) fn new(system: Arc<GlobalSystem>) -> Self {
         ContextState {
             messages: VecDeque::new(),
+            stopping: false,
+            expired: Arc::new(AtomicUsize::new(0)),
+            expired_to_dead_letters: false,
+            store: None,
+            stash: VecDeque::new(),
+            stash_capacity: DEFAULT_STASH_CAPACITY,
+            poll_budget: DEFAULT_POLL_BUDGET,
+            budget_left: DEFAULT_POLL_BUDGET,
+            exec_timeout: None,
+            handling_since: None,
+            warning_threshold: None,
+            handling_type: None,
+            warned: false,
+            stall: None,
+            idle_timeout: None,
+            waiting_since: None,
+            received: 0,
+            retrieved: 0,
+            system,
+        }
+    }
+
+    pub(crate) fn with_expiry(
+        mut self,
+        expired: Arc<AtomicUsize>,
+        expired_to_dead_letters: bool,
+    ) -> Self {
+        self.expired = expired;
+        self.expired_to_dead_letters = expired_to_dead_letters;
+        self
+    }
+
+    pub(crate) fn with_store(mut self, store: Option<Arc<dyn MailboxStore>>) -> Self {
+        self.store = store;
+        self
+    }
+
+    pub(crate) fn with_stash_capacity(mut self, stash_capacity: usize) -> Self {
+        self.stash_capacity = stash_capacity;
+        self
+    }
+
+    pub(crate) fn with_poll_budget(mut self, poll_budget: usize) -> Self {
+        // An element that can't receive anything would never stop.
+        self.poll_budget = poll_budget.max(1);
+        self.budget_left = self.poll_budget;
+        self
+    }
+
+    pub(crate) fn with_exec_timeout(mut self, exec_timeout: Duration) -> Self {
+        self.exec_timeout = Some(exec_timeout);
+        self
+    }
+
+    pub(crate) fn with_warning_threshold(mut self, warning_threshold: Duration) -> Self {
+        self.warning_threshold = Some(warning_threshold);
+        self
+    }
+
+    pub(crate) fn with_idle_timeout(mut self, idle_timeout: Duration) -> Self {
+        self.idle_timeout = Some(idle_timeout);
+        self
+    }
+
+    /// Returns when the element times out if it is still handling
+    /// the message it received last by then.
+    pub(crate) fn handling_deadline(&self) -> Option<Instant> {
+        Some(self.handling_since? + self.exec_timeout?)
+    }
+
+    /// Returns when the element should be warned about if it is
+    /// still handling the message it received last by then, unless
+    /// it already was.
+    pub(crate) fn warning_deadline(&self) -> Option<Instant> {
+        if self.warned {
+            return None;
+        }
+
+        Some(self.handling_since? + self.warning_threshold?)
+    }
+
+    /// Returns when the element should stop if it is still waiting
+    /// for a message by then, unless messages are already waiting
+    /// for it in its mailbox.
+    pub(crate) fn idle_deadline(&self) -> Option<Instant> {
+        if !self.messages.is_empty() {
+            return None;
+        }
+
+        Some(self.waiting_since? + self.idle_timeout?)
+    }
+
+    /// Returns how long the element has been handling (or took to
+    /// handle) a message and the message's type, if it took longer
+    /// than its group's warning threshold and this wasn't reported
+    /// yet.
+    pub(crate) fn take_stall(&mut self) -> Option<(Duration, &'static str)> {
+        if let Some(stall) = self.stall.take() {
+            return Some(stall);
+        }
+
+        let elapsed = self.handling_since?.elapsed();
+        if self.warned || elapsed < self.warning_threshold? {
+            return None;
         }
+
+        self.warned = true;
+        Some((elapsed, self.handling_type?))
+    }
+
+    // Called when the element receives a message, or waits for one
+    // (`msg` being `None`).
+    fn handling(&mut self, msg: Option<&SignedMessage>) {
+        if let (Some(threshold), Some(since), Some(msg_type)) = (
+            self.warning_threshold,
+            self.handling_since,
+            self.handling_type,
+        ) {
+            let elapsed = since.elapsed();
+            if !self.warned && elapsed >= threshold {
+                self.stall = Some((elapsed, msg_type));
+            }
+        }
+
+        if self.exec_timeout.is_some() || self.warning_threshold.is_some() {
+            self.handling_since = msg.map(|_| Instant::now());
+            self.handling_type = msg.map(|msg| msg.msg.type_name());
+            self.warned = false;
+        }
+
+        if self.idle_timeout.is_some() {
+            self.waiting_since = match msg {
+                Some(_) => None,
+                None => Some(self.waiting_since.unwrap_or_else(Instant::now)),
+            };
+        }
+    }
+
+    pub(crate) fn poll_budget(&self) -> usize {
+        self.poll_budget
+    }
+
+    /// Returns whether the element can still receive messages
+    /// before yielding.
+    pub(crate) fn has_budget(&self) -> bool {
+        self.budget_left > 0
+    }
+
+    pub(crate) fn spend_budget(&mut self) {
+        self.budget_left = self.budget_left.saturating_sub(1);
+    }
+
+    /// Lets the element receive messages again once it yielded,
+    /// returning whether it used all of its budget.
+    pub(crate) fn refill_budget(&mut self) -> bool {
+        let exhausted = !self.has_budget();
+        self.budget_left = self.poll_budget;
+        exhausted
+    }
+
+    pub(crate) fn stop(&mut self) {
+        self.stopping = true;
+    }
+
+    /// Allows an element that was stopped to be restarted,
+    /// keeping the messages it didn't receive yet.
+    pub(crate) fn reset(&mut self) {
+        self.stopping = false;
+    }
+
+    pub(crate) fn is_stopping(&self) -> bool {
+        self.stopping
+    }
+
+    pub(crate) fn push_message(
+        &mut self,
+        msg: Msg,
+        sign: RefAddr,
+        trace: Option<TraceId>,
+        deadline: Option<Instant>,
+    ) {
+        let stored = self.store.as_ref().and_then(|store| store.append(&msg));
+        let mut msg = SignedMessage::new(msg, sign)
+            .with_trace(trace)
+            .with_deadline(deadline);
+        msg.stored = stored;
+        self.received += 1;
+        self.messages.push_back(msg)
+    }
+
+    /// Returns the number of messages pushed to the mailbox and
+    /// retrieved from it.
+    pub(crate) fn counters(&self) -> (u64, u64) {
+        (self.received, self.retrieved)
+    }
+
+    pub(crate) fn len(&self) -> usize {
+        self.messages.len()
     }
 
-    pub(crate) fn push_message(&mut self, msg: Msg, sign: RefAddr) {
-        self.messages.push_back(SignedMessage::new(msg, sign))
+    pub(crate) fn has_messages(&self) -> bool {
+        let now = Instant::now();
+        self.messages.iter().any(|msg| !msg.is_expired(now))
     }
 
+    /// Returns the oldest message that didn't expire yet, skipping
+    /// (and counting) the expired ones.
     pub(crate) fn pop_message(&mut self) -> Option<SignedMessage> {
-        self.messages.pop_front()
+        let mut now = None;
+        while let Some(msg) = self.messages.pop_front() {
+            if msg.deadline.is_none() {
+                self.processed(&msg);
+                self.retrieved += 1;
+                return Some(msg);
+            }
+
+            let now = *now.get_or_insert_with(Instant::now);
+            if !msg.is_expired(now) {
+                self.processed(&msg);
+                self.retrieved += 1;
+                return Some(msg);
+            }
+
+            self.expire(msg);
+        }
+
+        None
+    }
+
+    /// Returns the oldest message that didn't expire yet and for
+    /// which `predicate` returns `true`, removing (and counting)
+    /// the expired messages and keeping the other ones in order.
+    pub(crate) fn pop_message_where<P>(&mut self, predicate: &mut P) -> Option<SignedMessage>
+    where
+        P: FnMut(&Msg) -> bool,
+    {
+        let now = Instant::now();
+        let mut index = 0;
+        while let Some(msg) = self.messages.get(index) {
+            if msg.is_expired(now) {
+                // FIXME: panics?
+                let msg = self.messages.remove(index).unwrap();
+                self.expire(msg);
+            } else if predicate(&msg.msg) {
+                let msg = self.messages.remove(index)?;
+                self.processed(&msg);
+                self.retrieved += 1;
+                return Some(msg);
+            } else {
+                index += 1;
+            }
+        }
+
+        None
+    }
+
+    /// Removes all the messages that didn't expire yet from the
+    /// mailbox and returns them in order, skipping (and counting)
+    /// the expired ones.
+    pub(crate) fn drain_messages(&mut self) -> Vec<Msg> {
+        let mut drained = Vec::with_capacity(self.messages.len());
+        while let Some(msg) = self.pop_message() {
+            drained.push(msg.msg);
+        }
+
+        drained
+    }
+
+    pub(crate) fn stash(&mut self, msg: SignedMessage) -> Result<(), Box<SignedMessage>> {
+        if self.stash.len() >= self.stash_capacity {
+            return Err(Box::new(msg));
+        }
+
+        self.stash.push_back(msg);
+        Ok(())
+    }
+
+    /// Places the oldest stashed message back at the front of the
+    /// mailbox, returning whether there was one.
+    pub(crate) fn unstash_one(&mut self) -> bool {
+        match self.stash.pop_front() {
+            Some(msg) => {
+                self.messages.push_front(msg);
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Places the stashed messages back at the front of the
+    /// mailbox, in the order they were received, returning
+    /// their number.
+    pub(crate) fn unstash_all(&mut self) -> usize {
+        let unstashed = self.stash.len();
+        for msg in self.stash.drain(..).rev() {
+            self.messages.push_front(msg);
+        }
+
+        unstashed
+    }
+
+    /// Sends the stashed messages to the dead letters, once the
+    /// element stopped or before it restarts.
+    pub(crate) fn dead_letter_stash(&mut self) {
+        if self.stash.is_empty() {
+            return;
+        }
+
+        debug!(
+            "ContextState: Sending {} stashed messages to the dead letters.",
+            self.stash.len()
+        );
+        for msg in self.stash.drain(..) {
+            let env = Envelope::new_with_sign(BastionMessage::Message(msg.msg), msg.sign);
+            let dead_letters = self.system.dead_letters().sender();
+            dead_letters.unbounded_send(env).ok();
+        }
+    }
+
+    // Removes a retrieved (or expired) message from the store.
+    fn processed(&self, msg: &SignedMessage) {
+        if let (Some(store), Some(key)) = (&self.store, msg.stored) {
+            store.mark_processed(key);
+        }
+    }
+
+    fn expire(&mut self, msg: SignedMessage) {
+        debug!("ContextState: Message expired: {:?}", msg);
+        self.processed(&msg);
+        self.expired.fetch_add(1, Ordering::SeqCst);
+        if self.expired_to_dead_letters {
+            let msg = BastionMessage::tell(Expired(msg));
+            let env = Envelope::from_dead_letters(msg, &self.system);
+            let dead_letters = self.system.dead_letters().sender();
+            dead_letters.unbounded_send(env).ok();
+        }
     }
 }
 
 impl Display for BastionId {
     fn fmt(&self, fmt: &mut Formatter) -> fmt::Result {
-        self.0.fmt(fmt)
+        if !fmt.alternate() {
+            return self.0.fmt(fmt);
+        }
+
+        let mut buf = Uuid::encode_buffer();
+        let simple = self.0.to_simple_ref().encode_lower(&mut buf);
+        fmt.write_str(&simple[..8])
+    }
+}
+
+impl FromStr for BastionId {
+    type Err = ParseIdError;
+
+    fn from_str(id: &str) -> Result<Self, Self::Err> {
+        Uuid::parse_str(id).map(BastionId).map_err(|_| ParseIdError)
+    }
+}
+
+impl Display for ParseIdError {
+    fn fmt(&self, fmt: &mut Formatter) -> fmt::Result {
+        fmt.write_str("invalid bastion id, expected a full UUID")
     }
 }
+
+impl Error for ParseIdError {}
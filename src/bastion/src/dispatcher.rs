@@ -390,7 +390,7 @@ mod tests {
     use crate::envelope::{RefAddr, SignedMessage};
     use crate::message::Msg;
     use crate::path::BastionPath;
-    use futures::channel::mpsc;
+    use crate::system::SYSTEM;
     use std::sync::{Arc, Mutex};
 
     #[derive(Clone)]
@@ -453,10 +453,10 @@ mod tests {
     fn test_local_dispatcher_append_child_ref() {
         let instance = Dispatcher::default();
         let bastion_id = BastionId::new();
-        let (sender, _) = mpsc::unbounded();
+        let (sender, _) = crate::broadcast::channel();
         let path = Arc::new(BastionPath::root());
         let name = "test_name".to_string();
-        let child_ref = ChildRef::new(bastion_id, sender, name, path);
+        let child_ref = ChildRef::new(bastion_id, sender, name, path, SYSTEM.clone());
 
         assert_eq!(instance.actors.contains_key(&child_ref), false);
 
@@ -470,10 +470,10 @@ mod tests {
     fn test_dispatcher_remove_child_ref() {
         let instance = Dispatcher::default();
         let bastion_id = BastionId::new();
-        let (sender, _) = mpsc::unbounded();
+        let (sender, _) = crate::broadcast::channel();
         let path = Arc::new(BastionPath::root());
         let name = "test_name".to_string();
-        let child_ref = ChildRef::new(bastion_id, sender, name, path);
+        let child_ref = ChildRef::new(bastion_id, sender, name, path, SYSTEM.clone());
 
         instance
             .register(&child_ref, "my::test::module".to_string())
@@ -489,10 +489,10 @@ mod tests {
         let handler = Box::new(CustomHandler::new(false));
         let instance = Dispatcher::default().with_handler(handler.clone());
         let bastion_id = BastionId::new();
-        let (sender, _) = mpsc::unbounded();
+        let (sender, _) = crate::broadcast::channel();
         let path = Arc::new(BastionPath::root());
         let name = "test_name".to_string();
-        let child_ref = ChildRef::new(bastion_id, sender, name, path);
+        let child_ref = ChildRef::new(bastion_id, sender, name, path, SYSTEM.clone());
 
         instance.notify(&child_ref, NotificationType::Register);
         let handler_was_called = handler.was_called();
@@ -503,7 +503,7 @@ mod tests {
     fn test_local_dispatcher_broadcast_message() {
         let handler = Box::new(CustomHandler::new(false));
         let instance = Dispatcher::default().with_handler(handler.clone());
-        let (sender, _) = mpsc::unbounded();
+        let (sender, _) = crate::broadcast::channel();
         let path = Arc::new(BastionPath::root());
 
         const DATA: &str = "A message containing data (ask).";
@@ -563,10 +563,10 @@ mod tests {
     #[test]
     fn test_global_dispatcher_register_actor() {
         let bastion_id = BastionId::new();
-        let (sender, _) = mpsc::unbounded();
+        let (sender, _) = crate::broadcast::channel();
         let path = Arc::new(BastionPath::root());
         let name = "test_name".to_string();
-        let child_ref = ChildRef::new(bastion_id, sender, name, path);
+        let child_ref = ChildRef::new(bastion_id, sender, name, path, SYSTEM.clone());
 
         let dispatcher_type = DispatcherType::Named("test".to_string());
         let local_dispatcher = Arc::new(Box::new(Dispatcher::with_type(dispatcher_type.clone())));
@@ -589,10 +589,10 @@ mod tests {
     #[test]
     fn test_global_dispatcher_remove_actor() {
         let bastion_id = BastionId::new();
-        let (sender, _) = mpsc::unbounded();
+        let (sender, _) = crate::broadcast::channel();
         let path = Arc::new(BastionPath::root());
         let name = "test_name".to_string();
-        let child_ref = ChildRef::new(bastion_id, sender, name, path);
+        let child_ref = ChildRef::new(bastion_id, sender, name, path, SYSTEM.clone());
 
         let dispatcher_type = DispatcherType::Named("test".to_string());
         let local_dispatcher = Arc::new(Box::new(Dispatcher::with_type(dispatcher_type.clone())));
@@ -616,10 +616,10 @@ mod tests {
     #[test]
     fn test_global_dispatcher_notify() {
         let bastion_id = BastionId::new();
-        let (sender, _) = mpsc::unbounded();
+        let (sender, _) = crate::broadcast::channel();
         let path = Arc::new(BastionPath::root());
         let name = "test_name".to_string();
-        let child_ref = ChildRef::new(bastion_id, sender, name, path);
+        let child_ref = ChildRef::new(bastion_id, sender, name, path, SYSTEM.clone());
 
         let dispatcher_type = DispatcherType::Named("test".to_string());
         let handler = Box::new(CustomHandler::new(false));
@@ -645,10 +645,10 @@ mod tests {
     #[test]
     fn test_global_dispatcher_broadcast_message() {
         let bastion_id = BastionId::new();
-        let (sender, _) = mpsc::unbounded();
+        let (sender, _) = crate::broadcast::channel();
         let path = Arc::new(BastionPath::root());
         let name = "test_name".to_string();
-        let child_ref = ChildRef::new(bastion_id, sender, name, path);
+        let child_ref = ChildRef::new(bastion_id, sender, name, path, SYSTEM.clone());
 
         let dispatcher_type = DispatcherType::Named("test".to_string());
         let handler = Box::new(CustomHandler::new(false));
@@ -666,7 +666,7 @@ mod tests {
             .register(&actor_groups, &child_ref, module_name)
             .unwrap();
 
-        let (sender, _) = mpsc::unbounded();
+        let (sender, _) = crate::broadcast::channel();
         let path = Arc::new(BastionPath::root());
         const DATA: &str = "A message containing data (ask).";
         let message = Arc::new(SignedMessage::new(
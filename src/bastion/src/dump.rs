@@ -0,0 +1,173 @@
+//!
+//! Snapshots of the internal state of the supervisors and children
+//! groups of a supervision tree, rendered as a textual report by
+//! [`Bastion::dump_state`] to diagnose wedged systems.
+//!
+//! Each supervisor answers with its own state (even before being
+//! started) once its supervised elements answered with theirs,
+//! waiting for them until a deadline earlier than its own and
+//! reporting the ones that didn't answer in time as unresponsive.
+//! The supervisors it queries are given this earlier deadline, so
+//! that a wedged supervisor is reported by its parent instead of
+//! making it miss its own deadline too.
+//!
+//! [`Bastion::dump_state`]: ../struct.Bastion.html#method.dump_state
+use crate::context::BastionId;
+use crate::supervisor::{SupervisedKind, SupervisionStrategy};
+use futures::channel::oneshot;
+use futures::future::{self, Either};
+use futures_timer::Delay;
+use std::fmt::{self, Display, Formatter};
+use std::time::Instant;
+
+#[derive(Debug)]
+// The state of a supervisor, as answered to `BastionMessage::DumpState`.
+pub(crate) struct SupervisorDump {
+    pub(crate) id: BastionId,
+    pub(crate) name: Option<String>,
+    pub(crate) strategy: SupervisionStrategy,
+    pub(crate) started: bool,
+    pub(crate) pre_start_msgs: usize,
+    // The supervised elements in the order they were added,
+    // and the ones that are running, stopped or were killed.
+    pub(crate) order: Vec<BastionId>,
+    pub(crate) launched: Vec<BastionId>,
+    pub(crate) stopped: Vec<BastionId>,
+    pub(crate) killed: Vec<BastionId>,
+    // The running supervised elements.
+    pub(crate) elems: Vec<DumpedElement>,
+}
+
+#[derive(Debug)]
+pub(crate) enum DumpedElement {
+    Supervisor(SupervisorDump),
+    // The mailbox lengths of the elements of a children group,
+    // or `None` if its supervisor wasn't started yet.
+    Children {
+        id: BastionId,
+        name: Option<String>,
+        mailbox_lens: Option<Vec<usize>>,
+    },
+    Unresponsive {
+        id: BastionId,
+        kind: SupervisedKind,
+    },
+    // The state of the system, with the system supervisor and the
+    // top-level supervisors it launched.
+    System {
+        started: bool,
+        pre_start_msgs: usize,
+        elems: Vec<DumpedElement>,
+    },
+}
+
+/// Returns the deadline until which a supervisor that has to
+/// answer before `deadline` waits for the supervised elements it
+/// queries (and which is given to them), leaving it a quarter of
+/// the remaining time to answer.
+pub(crate) fn sub_deadline(deadline: Instant) -> Instant {
+    let now = Instant::now();
+    now + deadline.saturating_duration_since(now) * 3 / 4
+}
+
+/// Waits for an answer until `deadline`, returning `None` if it
+/// elapsed or if the answer will never be sent.
+pub(crate) async fn answer_before<T>(
+    receiver: oneshot::Receiver<T>,
+    deadline: Instant,
+) -> Option<T> {
+    let delay = Delay::new(deadline.saturating_duration_since(Instant::now()));
+    match future::select(receiver, delay).await {
+        Either::Left((Ok(answer), _)) => Some(answer),
+        _ => None,
+    }
+}
+
+// Writes a list of identifiers on one line.
+fn write_ids(f: &mut Formatter, indent: &str, label: &str, ids: &[BastionId]) -> fmt::Result {
+    write!(f, "{}  {}: [", indent, label)?;
+    for (i, id) in ids.iter().enumerate() {
+        if i > 0 {
+            write!(f, ", ")?;
+        }
+        write!(f, "{}", id)?;
+    }
+    writeln!(f, "]")
+}
+
+impl DumpedElement {
+    fn write(&self, f: &mut Formatter, depth: usize) -> fmt::Result {
+        let indent = "  ".repeat(depth);
+        match self {
+            DumpedElement::Supervisor(dump) => {
+                write!(f, "{}Supervisor({})", indent, dump.id)?;
+                if let Some(name) = &dump.name {
+                    write!(f, " {:?}", name)?;
+                }
+                writeln!(
+                    f,
+                    ": {:?}, {}, {} pre-start messages",
+                    dump.strategy,
+                    if dump.started {
+                        "started"
+                    } else {
+                        "not started"
+                    },
+                    dump.pre_start_msgs
+                )?;
+                write_ids(f, &indent, "order", &dump.order)?;
+                write_ids(f, &indent, "launched", &dump.launched)?;
+                write_ids(f, &indent, "stopped", &dump.stopped)?;
+                write_ids(f, &indent, "killed", &dump.killed)?;
+                for elem in &dump.elems {
+                    elem.write(f, depth + 1)?;
+                }
+
+                Ok(())
+            }
+            DumpedElement::Children {
+                id,
+                name,
+                mailbox_lens,
+            } => {
+                write!(f, "{}Children({})", indent, id)?;
+                if let Some(name) = name {
+                    write!(f, " {:?}", name)?;
+                }
+                match mailbox_lens {
+                    Some(lens) => {
+                        writeln!(f, ": {} elements, mailbox lengths: {:?}", lens.len(), lens)
+                    }
+                    None => writeln!(f, ": not started"),
+                }
+            }
+            DumpedElement::Unresponsive { id, kind } => {
+                writeln!(f, "{}{:?}({}): unresponsive", indent, kind, id)
+            }
+            DumpedElement::System {
+                started,
+                pre_start_msgs,
+                elems,
+            } => {
+                writeln!(
+                    f,
+                    "{}System: {}, {} pre-start messages",
+                    indent,
+                    if *started { "started" } else { "not started" },
+                    pre_start_msgs
+                )?;
+                for elem in elems {
+                    elem.write(f, depth + 1)?;
+                }
+
+                Ok(())
+            }
+        }
+    }
+}
+
+impl Display for DumpedElement {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        self.write(f, 0)
+    }
+}
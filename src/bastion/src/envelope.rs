@@ -5,15 +5,74 @@
 use crate::broadcast::Sender;
 use crate::message::{BastionMessage, Message, Msg};
 use crate::path::BastionPath;
-use crate::system::SYSTEM;
+use crate::system::GlobalSystem;
+use futures::channel::oneshot;
+use std::fmt::{self, Display, Formatter};
 use std::sync::Arc;
+use std::time::Instant;
+use uuid::Uuid;
 
 #[derive(Debug)]
 pub(crate) struct Envelope {
     pub(crate) msg: BastionMessage,
     pub(crate) sign: RefAddr,
+    pub(crate) trace: Option<TraceId>,
+    // Set when the sender waits for the message to be placed
+    // into an element's mailbox (see `ChildrenRef::send_acked`).
+    pub(crate) ack: Option<AckSender>,
+    // After which the message isn't delivered anymore (see
+    // `ChildrenRef::broadcast_with_ttl`).
+    pub(crate) deadline: Option<Instant>,
 }
 
+pub(crate) type AckSender = oneshot::Sender<()>;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+/// The reason why a message sent using [`ChildrenRef::send_acked`]
+/// couldn't be acknowledged.
+///
+/// [`ChildrenRef::send_acked`]: ../children_ref/struct.ChildrenRef.html#method.send_acked
+pub enum DeliveryError {
+    /// The children group was stopped, or stopping, before the
+    /// message could be placed into one of its elements' mailbox.
+    Stopped,
+    /// The message wasn't acknowledged before the timeout elapsed.
+    Timeout,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "codec", derive(serde::Serialize, serde::Deserialize))]
+/// A correlation identifier attached to messages, allowing to
+/// follow a request while it hops through different elements.
+///
+/// Messages sent by an element (using [`BastionContext::tell`]
+/// or [`BastionContext::ask`]) are automatically attached the
+/// `TraceId` of the last message it received, if any.
+///
+/// # Example
+///
+/// ```rust
+/// # use bastion::prelude::*;
+/// #
+/// # Bastion::init();
+/// #
+/// # let children_ref = Bastion::children(|children| children).unwrap();
+/// let trace = TraceId::new();
+/// println!("Sending request {}", trace);
+/// children_ref
+///     .broadcast_traced("A request.", trace)
+///     .expect("Couldn't send the message.");
+/// #
+/// # Bastion::start();
+/// # Bastion::stop();
+/// # Bastion::block_until_stopped();
+/// ```
+///
+/// [`BastionContext::tell`]: ../context/struct.BastionContext.html#method.tell
+/// [`BastionContext::ask`]: ../context/struct.BastionContext.html#method.ask
+// Stored as bytes rather than as a `u128` to keep the envelopes small.
+pub struct TraceId([u8; 16]);
+
 #[derive(Debug)]
 /// A struct containing a message and its sender signature
 ///
@@ -41,11 +100,73 @@ pub(crate) struct Envelope {
 pub struct SignedMessage {
     pub(crate) msg: Msg,
     pub(crate) sign: RefAddr,
+    pub(crate) trace: Option<TraceId>,
+    pub(crate) deadline: Option<Instant>,
+    // The key identifying the message in the element's
+    // `MailboxStore`, if it was stored.
+    pub(crate) stored: Option<u64>,
+}
+
+#[derive(Debug)]
+#[allow(dead_code)]
+// The message told to the dead letters in place of a message that
+// expired before being received.
+pub(crate) struct Expired(pub(crate) SignedMessage);
+
+impl TraceId {
+    /// Creates a new random `TraceId`.
+    // Being random, it isn't a sensible default value.
+    #[allow(clippy::new_without_default)]
+    pub fn new() -> Self {
+        TraceId(*Uuid::new_v4().as_bytes())
+    }
 }
 
 impl SignedMessage {
     pub(crate) fn new(msg: Msg, sign: RefAddr) -> Self {
-        SignedMessage { msg, sign }
+        let trace = None;
+        let deadline = None;
+        let stored = None;
+        SignedMessage {
+            msg,
+            sign,
+            trace,
+            deadline,
+            stored,
+        }
+    }
+
+    pub(crate) fn with_trace(mut self, trace: Option<TraceId>) -> Self {
+        self.trace = trace;
+        self
+    }
+
+    pub(crate) fn with_deadline(mut self, deadline: Option<Instant>) -> Self {
+        self.deadline = deadline;
+        self
+    }
+
+    pub(crate) fn is_expired(&self, now: Instant) -> bool {
+        match self.deadline {
+            Some(deadline) => deadline <= now,
+            None => false,
+        }
+    }
+
+    /// Returns the [`TraceId`] attached to the message, if any.
+    ///
+    /// [`TraceId`]: struct.TraceId.html
+    pub fn trace(&self) -> Option<TraceId> {
+        self.trace
+    }
+
+    /// Returns a reference to the message, allowing to inspect it
+    /// without consuming the `SignedMessage` (e.g. to decide
+    /// whether to stash it using [`BastionContext::stash`]).
+    ///
+    /// [`BastionContext::stash`]: ../context/struct.BastionContext.html#method.stash
+    pub fn msg(&self) -> &Msg {
+        &self.msg
     }
 
     #[doc(hidden)]
@@ -117,11 +238,8 @@ impl RefAddr {
         RefAddr { path, sender }
     }
 
-    pub(crate) fn dead_letters() -> Self {
-        Self::new(
-            SYSTEM.dead_letters().path().clone(),
-            SYSTEM.dead_letters().sender().clone(),
-        )
+    pub(crate) fn dead_letters(system: &GlobalSystem) -> Self {
+        system.dead_letters().clone()
     }
 
     /// Checks whether the sender is identified.
@@ -218,24 +336,56 @@ impl Envelope {
         Envelope {
             msg,
             sign: RefAddr::new(path, sender),
+            trace: None,
+            ack: None,
+            deadline: None,
         }
     }
 
     pub(crate) fn new_with_sign(msg: BastionMessage, sign: RefAddr) -> Self {
-        Envelope { msg, sign }
+        Envelope {
+            msg,
+            sign,
+            trace: None,
+            ack: None,
+            deadline: None,
+        }
     }
 
-    pub(crate) fn from_dead_letters(msg: BastionMessage) -> Self {
+    pub(crate) fn from_dead_letters(msg: BastionMessage, system: &GlobalSystem) -> Self {
         Envelope {
             msg,
-            sign: RefAddr::dead_letters(),
+            sign: RefAddr::dead_letters(system),
+            trace: None,
+            ack: None,
+            deadline: None,
         }
     }
 
+    pub(crate) fn with_trace(mut self, trace: Option<TraceId>) -> Self {
+        self.trace = trace;
+        self
+    }
+
+    pub(crate) fn with_ack(mut self, ack: AckSender) -> Self {
+        self.ack = Some(ack);
+        self
+    }
+
+    pub(crate) fn with_deadline(mut self, deadline: Option<Instant>) -> Self {
+        self.deadline = deadline;
+        self
+    }
+
     pub(crate) fn try_clone(&self) -> Option<Self> {
+        // The acknowledgment is only sent by the element
+        // receiving the original envelope.
         self.msg.try_clone().map(|msg| Envelope {
             msg,
             sign: self.sign.clone(),
+            trace: self.trace,
+            ack: None,
+            deadline: self.deadline,
         })
     }
 
@@ -243,3 +393,9 @@ impl Envelope {
         self.msg.into_msg()
     }
 }
+
+impl Display for TraceId {
+    fn fmt(&self, fmt: &mut Formatter) -> fmt::Result {
+        write!(fmt, "{:032x}", u128::from_be_bytes(self.0))
+    }
+}
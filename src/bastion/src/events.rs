@@ -0,0 +1,183 @@
+//!
+//! The lifecycle events of a whole supervision tree, which can be
+//! streamed from anywhere using [`Bastion::events`].
+//!
+//! [`Bastion::events`]: ../struct.Bastion.html#method.events
+use crate::context::{BastionId, NIL_ID};
+use futures::channel::mpsc;
+use futures::prelude::*;
+use std::sync::Mutex;
+use std::time::SystemTime;
+use tracing::{debug, trace};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+/// An item of the stream returned by [`Bastion::events`].
+///
+/// [`Bastion::events`]: ../struct.Bastion.html#method.events
+pub enum SystemEvent {
+    /// A lifecycle transition of a supervisor, children group or
+    /// element of the supervision tree.
+    Lifecycle(LifecycleEvent),
+    /// The given number of events were dropped because the
+    /// subscriber didn't keep up with them and its buffer was
+    /// full.
+    Lagged(usize),
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+/// A lifecycle transition of an entity of the supervision tree
+/// (see [`SystemEvent`]).
+///
+/// [`SystemEvent`]: enum.SystemEvent.html
+pub struct LifecycleEvent {
+    /// What happened to the entity.
+    pub transition: Transition,
+    /// Whether the entity is a supervisor, a children group or an
+    /// element of a children group.
+    pub entity: EntityKind,
+    /// The identifier of the entity.
+    pub id: BastionId,
+    /// The identifier of the entity's supervisor (or children
+    /// group, for an element), or `None` for the top-level
+    /// supervisors.
+    pub parent: Option<BastionId>,
+    /// The name of the entity, if it is a children group (or an
+    /// element of one) that was given one.
+    pub name: Option<String>,
+    /// When the transition happened.
+    pub timestamp: SystemTime,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+/// What happened to an entity of the supervision tree (see
+/// [`LifecycleEvent`]).
+///
+/// [`LifecycleEvent`]: struct.LifecycleEvent.html
+pub enum Transition {
+    /// The entity was added to its supervisor.
+    Deployed,
+    /// The entity started handling messages.
+    Started,
+    /// The entity stopped, either because it was asked to (or
+    /// killed) or because it finished.
+    Stopped,
+    /// The entity was restarted after it (or a sibling) faulted.
+    Restarted,
+    /// The entity faulted.
+    Faulted,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+/// The kind of entity a [`LifecycleEvent`] is about.
+///
+/// [`LifecycleEvent`]: struct.LifecycleEvent.html
+pub enum EntityKind {
+    /// A supervisor.
+    Supervisor,
+    /// A children group.
+    Children,
+    /// An element of a children group.
+    Element,
+}
+
+// A subscriber to the events, and the number of events it missed
+// since the last ones it was sent.
+#[derive(Debug)]
+struct Subscriber {
+    sender: mpsc::Sender<SystemEvent>,
+    lagged: usize,
+}
+
+#[derive(Debug)]
+pub(crate) struct EventBus {
+    // `None` once the system stopped, which ends the subscribers'
+    // streams.
+    subscribers: Mutex<Option<Vec<Subscriber>>>,
+}
+
+impl EventBus {
+    pub(crate) fn new() -> Self {
+        EventBus {
+            subscribers: Mutex::new(Some(Vec::new())),
+        }
+    }
+
+    pub(crate) fn subscribe(&self, capacity: usize) -> impl Stream<Item = SystemEvent> {
+        debug!("EventBus: Subscribing with capacity: {}", capacity);
+        let (sender, recver) = mpsc::channel(capacity);
+        // FIXME: panics
+        if let Some(subscribers) = &mut *self.subscribers.lock().unwrap() {
+            subscribers.push(Subscriber { sender, lagged: 0 });
+        }
+
+        recver
+    }
+
+    /// Sends the event to every subscriber without waiting for
+    /// the ones whose buffer is full, which are told how many
+    /// events they missed once they catch up.
+    pub(crate) fn publish(
+        &self,
+        transition: Transition,
+        entity: EntityKind,
+        id: &BastionId,
+        parent: Option<&BastionId>,
+        name: Option<&str>,
+    ) {
+        // The system supervisor and the dead letters aren't part of
+        // the users' supervision tree.
+        if id == &NIL_ID {
+            return;
+        }
+
+        // FIXME: panics
+        let mut subscribers = self.subscribers.lock().unwrap();
+        let subscribers = match &mut *subscribers {
+            Some(subscribers) if !subscribers.is_empty() => subscribers,
+            _ => return,
+        };
+
+        let event = SystemEvent::Lifecycle(LifecycleEvent {
+            transition,
+            entity,
+            id: id.clone(),
+            parent: parent.filter(|parent| *parent != &NIL_ID).cloned(),
+            name: name.map(Into::into),
+            timestamp: SystemTime::now(),
+        });
+        trace!("EventBus: Publishing: {:?}", event);
+        subscribers.retain_mut(|subscriber| {
+            if subscriber.lagged > 0 {
+                match subscriber
+                    .sender
+                    .try_send(SystemEvent::Lagged(subscriber.lagged))
+                {
+                    Ok(()) => subscriber.lagged = 0,
+                    Err(err) if err.is_full() => {
+                        subscriber.lagged += 1;
+                        return true;
+                    }
+                    Err(_) => return false,
+                }
+            }
+
+            match subscriber.sender.try_send(event.clone()) {
+                Ok(()) => true,
+                Err(err) if err.is_full() => {
+                    subscriber.lagged += 1;
+                    true
+                }
+                // The stream was dropped.
+                Err(_) => false,
+            }
+        });
+    }
+
+    /// Ends the subscribers' streams, once they received the
+    /// events that were already sent to them.
+    pub(crate) fn close(&self) {
+        debug!("EventBus: Closing.");
+        // FIXME: panics
+        self.subscribers.lock().unwrap().take();
+    }
+}
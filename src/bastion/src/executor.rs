@@ -1,8 +1,443 @@
 //! A module that exposes the functions used under the hoods from `bastion`s macros: `spawn!`, `run!`
-//! and `blocking!`.
+//! and `blocking!`, and the [`BastionExecutor`] trait allowing to run bastion on another
+//! executor than its own.
+//!
+//! [`BastionExecutor`]: trait.BastionExecutor.html
+use bastion_executor::blocking;
+pub use bastion_executor::metrics::{ExecutorStats, WorkerStats};
+use bastion_executor::pool::{Scope, ShutDown};
+use futures::future::{BoxFuture, LocalBoxFuture};
+use futures::task::{self, ArcWake};
+use lazy_static::lazy_static;
+use lightproc::lightproc::LightProc;
 pub use lightproc::proc_stack::ProcStack;
 use lightproc::recoverable_handle::RecoverableHandle;
+use std::fmt::{self, Debug};
 use std::future::Future;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Condvar, Mutex, RwLock};
+use std::task::{Context, Poll};
+use std::thread::{self, ThreadId};
+use std::time::Duration;
+use tracing::warn;
+
+lazy_static! {
+    // The executor set using `Bastion::with_custom_executor` or
+    // `Config::with_executor`, if any.
+    static ref EXECUTOR: RwLock<Option<Arc<dyn BastionExecutor>>> = RwLock::new(None);
+    // The processes bastion spawned onto its own executor, shut
+    // down without affecting the other processes of its pool.
+    static ref SCOPE: Scope = Scope::new();
+    // The number of `Retained` alive, bastion's own executor being
+    // shut down once the last one got dropped.
+    static ref RUNNING: Mutex<usize> = Mutex::new(0);
+}
+
+// How long `block_until_quiesced` waits for a process which never
+// yields before giving up.
+const QUIESCE_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// An executor that bastion can run its supervisors, children
+/// groups and their elements (and the futures given to [`spawn`],
+/// [`blocking`] and [`run`]) on, instead of its own one.
+///
+/// The futures an executor receives wrap the lightweight processes
+/// bastion uses, which already catch the panics of the futures
+/// they run. Every future spawned by bastion only polls its process
+/// once and completes (the process getting spawned again once woken
+/// up), so spawning should be cheap.
+///
+/// # Contract
+///
+/// Supervision relies on every executor (including bastion's own
+/// one) upholding the following, which the `conformance` tests of
+/// bastion check against each of the executors it provides:
+///
+/// - every spawned future gets polled until it completes, without
+///   being dropped before: a future dropped early makes the handle
+///   of its process resolve to `None`, as if it got cancelled.
+/// - the futures never panic (their process catches the panics of
+///   the future it runs, resolving its handle to `None` and notifying
+///   the supervisor of the element that panicked), so an executor
+///   doesn't need to catch them and must keep running the other
+///   futures afterwards.
+/// - once a process got cancelled (e.g. because its element got
+///   killed), it gets spawned one last time, for its future to get
+///   dropped without being polled again. Dropping the handle of a
+///   process doesn't cancel it, but detaches it instead.
+///
+/// An executor can be set using either [`Bastion::with_custom_executor`]
+/// or [`Config::with_executor`], and is then shared by all the
+/// runtimes of the process.
+///
+/// # Example
+///
+/// ```rust
+/// use bastion::executor::BastionExecutor;
+/// use bastion::prelude::*;
+/// use futures::future::{BoxFuture, LocalBoxFuture};
+/// use std::thread;
+///
+/// // An executor running each future on a new thread.
+/// #[derive(Debug)]
+/// struct ThreadExecutor;
+///
+/// impl BastionExecutor for ThreadExecutor {
+///     fn spawn(&self, future: BoxFuture<'static, ()>) {
+///         thread::spawn(move || futures::executor::block_on(future));
+///     }
+///
+///     fn spawn_blocking(&self, future: BoxFuture<'static, ()>) {
+///         self.spawn(future)
+///     }
+///
+///     fn block_on(&self, future: LocalBoxFuture<'_, ()>) {
+///         futures::executor::block_on(future)
+///     }
+/// }
+///
+/// Bastion::init_with(Config::new().with_executor(ThreadExecutor));
+/// #
+/// # Bastion::start();
+/// # Bastion::stop();
+/// # Bastion::block_until_stopped();
+/// ```
+///
+/// [`spawn`]: fn.spawn.html
+/// [`blocking`]: fn.blocking.html
+/// [`run`]: fn.run.html
+/// [`Bastion::with_custom_executor`]: ../struct.Bastion.html#method.with_custom_executor
+/// [`Config::with_executor`]: ../struct.Config.html#method.with_executor
+pub trait BastionExecutor: Debug + Send + Sync + 'static {
+    /// Spawns the given future, polling it until it completes.
+    ///
+    /// # Arguments
+    ///
+    /// * `future` - The future to spawn.
+    fn spawn(&self, future: BoxFuture<'static, ()>);
+
+    /// Spawns the given future on a thread where it is allowed to
+    /// block, polling it until it completes.
+    ///
+    /// This is used for [`blocking`] and the children groups
+    /// elements spawned using [`SpawnStrategy::DedicatedThread`].
+    ///
+    /// # Arguments
+    ///
+    /// * `future` - The future to spawn.
+    ///
+    /// [`blocking`]: fn.blocking.html
+    /// [`SpawnStrategy::DedicatedThread`]: crate::children::SpawnStrategy::DedicatedThread
+    fn spawn_blocking(&self, future: BoxFuture<'static, ()>);
+
+    /// Blocks the current thread until the given future completes.
+    ///
+    /// This is used for [`run`] (and thus the `run!` macro).
+    ///
+    /// # Arguments
+    ///
+    /// * `future` - The future to block on.
+    ///
+    /// [`run`]: fn.run.html
+    fn block_on(&self, future: LocalBoxFuture<'_, ()>);
+}
+
+#[cfg(feature = "tokio-executor")]
+#[cfg_attr(feature = "docs", doc(cfg(feature = "tokio-executor")))]
+#[derive(Debug, Clone)]
+/// A [`BastionExecutor`] running everything on a tokio runtime.
+///
+/// This is only available with the `tokio-executor` feature.
+///
+/// This allows applications already running on tokio to run their
+/// supervision tree on it too, instead of on the threads spawned by
+/// bastion's own executor (which then never get spawned), while
+/// supervisors still get notified of the panics of their children.
+///
+/// Note that [`run`] (and thus the `run!` macro) can only be called
+/// from inside a multi-threaded tokio runtime when using this
+/// executor, since it then blocks using `block_in_place`.
+///
+/// # Example
+///
+/// ```rust
+/// use bastion::executor::TokioExecutor;
+/// use bastion::prelude::*;
+///
+/// let tokio = tokio::runtime::Builder::new_multi_thread()
+///     .build()
+///     .unwrap();
+///
+/// Bastion::with_custom_executor(TokioExecutor::new(tokio.handle().clone()));
+/// Bastion::init();
+/// #
+/// # Bastion::start();
+/// # Bastion::stop();
+/// # Bastion::block_until_stopped();
+/// ```
+///
+/// [`BastionExecutor`]: trait.BastionExecutor.html
+/// [`run`]: fn.run.html
+pub struct TokioExecutor {
+    handle: tokio::runtime::Handle,
+}
+
+#[cfg(feature = "tokio-executor")]
+impl TokioExecutor {
+    /// Creates a new executor spawning onto the tokio runtime
+    /// referenced by the given handle.
+    ///
+    /// # Arguments
+    ///
+    /// * `handle` - The handle of the tokio runtime to use.
+    pub fn new(handle: tokio::runtime::Handle) -> Self {
+        TokioExecutor { handle }
+    }
+
+    /// Creates a new executor spawning onto the tokio runtime the
+    /// current thread is running in.
+    ///
+    /// # Panics
+    ///
+    /// This method panics if it isn't called from inside a tokio
+    /// runtime.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use bastion::executor::TokioExecutor;
+    /// use bastion::prelude::*;
+    ///
+    /// #[tokio::main]
+    /// async fn main() {
+    ///     Bastion::with_custom_executor(TokioExecutor::current());
+    ///     Bastion::init();
+    ///     Bastion::start();
+    ///
+    ///     // ...
+    ///     #
+    ///     # Bastion::stop();
+    ///     # Bastion::block_until_stopped();
+    /// }
+    /// ```
+    pub fn current() -> Self {
+        TokioExecutor::new(tokio::runtime::Handle::current())
+    }
+}
+
+#[cfg(feature = "tokio-executor")]
+impl BastionExecutor for TokioExecutor {
+    fn spawn(&self, future: BoxFuture<'static, ()>) {
+        self.handle.spawn(future);
+    }
+
+    fn spawn_blocking(&self, future: BoxFuture<'static, ()>) {
+        self.handle
+            .spawn_blocking(move || futures::executor::block_on(future));
+    }
+
+    fn block_on(&self, future: LocalBoxFuture<'_, ()>) {
+        if tokio::runtime::Handle::try_current().is_ok() {
+            // Blocking inside of the runtime is only possible once
+            // the current worker handed its tasks over.
+            tokio::task::block_in_place(|| self.handle.block_on(future))
+        } else {
+            self.handle.block_on(future)
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+/// A [`BastionExecutor`] running everything on a single thread,
+/// picking the next ready future to poll using a seeded random
+/// number generator.
+///
+/// This is meant to be used by tests: the multi-threaded pool of
+/// bastion's own executor makes the order in which messages get
+/// handled change from a run to another, while this executor always
+/// interleaves the futures ready at the same time in the same order
+/// for a given seed, allowing to reproduce a failing run by using
+/// its seed again.
+///
+/// Note that futures woken up from other threads (e.g. once a timer
+/// fired or once a message was sent from outside of the tree) still
+/// become ready whenever those threads wake them up, and that the
+/// futures given to [`run`] from outside of the tree still get polled
+/// by the thread calling it.
+///
+/// # Example
+///
+/// ```rust
+/// use bastion::executor::DeterministicExecutor;
+/// use bastion::prelude::*;
+///
+/// Bastion::with_custom_executor(DeterministicExecutor::new(42));
+/// Bastion::init();
+/// #
+/// # Bastion::start();
+/// # Bastion::stop();
+/// # Bastion::block_until_stopped();
+/// ```
+///
+/// [`BastionExecutor`]: trait.BastionExecutor.html
+/// [`run`]: fn.run.html
+pub struct DeterministicExecutor {
+    scheduler: Arc<Scheduler>,
+    thread: ThreadId,
+}
+
+// The futures spawned onto a `DeterministicExecutor`, shared with
+// the thread polling them.
+struct Scheduler {
+    state: Mutex<SchedulerState>,
+    ready: Condvar,
+}
+
+struct SchedulerState {
+    seed: u64,
+    rng: u64,
+    futures: Vec<BoxFuture<'static, ()>>,
+}
+
+// Wakes up the thread polling a future given to
+// `Scheduler::block_on`.
+struct Woken {
+    scheduler: Arc<Scheduler>,
+    woken: AtomicBool,
+}
+
+impl DeterministicExecutor {
+    /// Creates a new executor, spawning the thread it runs
+    /// everything on.
+    ///
+    /// # Arguments
+    ///
+    /// * `seed` - The seed of the order in which the futures ready
+    ///     at the same time get polled.
+    pub fn new(seed: u64) -> Self {
+        let scheduler = Arc::new(Scheduler {
+            state: Mutex::new(SchedulerState {
+                seed,
+                // Xorshift's state can't be zero.
+                rng: seed.max(1),
+                futures: Vec::new(),
+            }),
+            ready: Condvar::new(),
+        });
+
+        let thread = {
+            let scheduler = scheduler.clone();
+            thread::Builder::new()
+                .name(format!("bastion-deterministic-{}", seed))
+                .spawn(move || {
+                    let idle = AtomicBool::new(false);
+                    while let Some(future) = scheduler.next(&idle) {
+                        scheduler.clone().block_on(future);
+                    }
+                })
+                .expect("Couldn't spawn the thread of the deterministic executor.")
+                .thread()
+                .id()
+        };
+
+        DeterministicExecutor { scheduler, thread }
+    }
+}
+
+impl BastionExecutor for DeterministicExecutor {
+    fn spawn(&self, future: BoxFuture<'static, ()>) {
+        // FIXME: panics
+        let mut state = self.scheduler.state.lock().unwrap();
+        state.futures.push(future);
+        self.scheduler.ready.notify_all();
+    }
+
+    fn spawn_blocking(&self, future: BoxFuture<'static, ()>) {
+        // Everything runs on the same thread.
+        self.spawn(future)
+    }
+
+    fn block_on(&self, future: LocalBoxFuture<'_, ()>) {
+        if thread::current().id() == self.thread {
+            // Blocking the thread would prevent the future from
+            // ever getting ready, so it keeps polling the others.
+            self.scheduler.clone().block_on(future)
+        } else {
+            futures::executor::block_on(future)
+        }
+    }
+}
+
+impl Scheduler {
+    // Polls the given future until it completes, polling the other
+    // futures while it isn't ready.
+    fn block_on(self: Arc<Self>, mut future: LocalBoxFuture<'_, ()>) {
+        let woken = Arc::new(Woken {
+            scheduler: self.clone(),
+            woken: AtomicBool::new(true),
+        });
+        let waker = task::waker(woken.clone());
+        let mut cx = Context::from_waker(&waker);
+
+        loop {
+            if woken.woken.swap(false, Ordering::SeqCst) && future.as_mut().poll(&mut cx).is_ready()
+            {
+                return;
+            }
+
+            if let Some(other) = self.next(&woken.woken) {
+                self.clone().block_on(other);
+            }
+        }
+    }
+
+    // Removes a random future from the ready ones, waiting for one
+    // to get spawned unless `woken` is set.
+    fn next(&self, woken: &AtomicBool) -> Option<BoxFuture<'static, ()>> {
+        // FIXME: panics
+        let mut state = self.state.lock().unwrap();
+        loop {
+            if !state.futures.is_empty() {
+                // Xorshift64, see https://www.jstatsoft.org/v08/i14/paper
+                state.rng ^= state.rng << 13;
+                state.rng ^= state.rng >> 7;
+                state.rng ^= state.rng << 17;
+
+                let idx = (state.rng % state.futures.len() as u64) as usize;
+                return Some(state.futures.swap_remove(idx));
+            }
+
+            if woken.load(Ordering::SeqCst) {
+                return None;
+            }
+
+            // FIXME: panics
+            state = self.ready.wait(state).unwrap();
+        }
+    }
+}
+
+impl ArcWake for Woken {
+    fn wake_by_ref(arc_self: &Arc<Self>) {
+        arc_self.woken.store(true, Ordering::SeqCst);
+        // Taking the lock makes sure that the thread either didn't
+        // check `woken` yet or is already waiting.
+        // FIXME: panics
+        let _state = arc_self.scheduler.state.lock().unwrap();
+        arc_self.scheduler.ready.notify_all();
+    }
+}
+
+impl Debug for Scheduler {
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
+        // FIXME: panics
+        let state = self.state.lock().unwrap();
+        fmt.debug_struct("Scheduler")
+            .field("seed", &state.seed)
+            .field("ready", &state.futures.len())
+            .finish()
+    }
+}
 
 /// Spawns a blocking task, which will run on the blocking thread pool,
 /// and returns the handle.
@@ -20,7 +455,7 @@ where
     F: Future<Output = R> + Send + 'static,
     R: Send + 'static,
 {
-    bastion_executor::blocking::spawn_blocking(future, lightproc::proc_stack::ProcStack::default())
+    spawn_proc_blocking(future, ProcStack::default())
 }
 
 /// Block the current thread until passed
@@ -50,7 +485,16 @@ pub fn run<F, T>(future: F) -> T
 where
     F: Future<Output = T>,
 {
-    bastion_executor::run::run(future, lightproc::proc_stack::ProcStack::default())
+    match custom() {
+        Some(executor) => {
+            let mut output = None;
+            executor.block_on(Box::pin(async {
+                output = Some(future.await);
+            }));
+            output.expect("BastionExecutor::block_on returned before the future completed.")
+        }
+        None => bastion_executor::run::run(future, ProcStack::default()),
+    }
 }
 
 /// Spawn a given future onto the executor from the global level.
@@ -69,5 +513,176 @@ where
     F: Future<Output = T> + Send + 'static,
     T: Send + 'static,
 {
-    bastion_executor::pool::spawn(future, lightproc::proc_stack::ProcStack::default())
+    spawn_proc(future, ProcStack::default())
+}
+
+pub(crate) fn set_executor(executor: Arc<dyn BastionExecutor>) {
+    // FIXME: panics
+    *EXECUTOR.write().unwrap() = Some(executor);
+}
+
+fn custom() -> Option<Arc<dyn BastionExecutor>> {
+    // FIXME: panics
+    EXECUTOR.read().unwrap().clone()
+}
+
+// Spawns the process onto the custom executor, which gets asked to
+// spawn it again every time it gets scheduled.
+fn spawn_custom<F, T>(
+    executor: Arc<dyn BastionExecutor>,
+    future: F,
+    stack: ProcStack,
+    blocking: bool,
+) -> RecoverableHandle<T>
+where
+    F: Future<Output = T> + Send + 'static,
+    T: Send + 'static,
+{
+    let schedule = move |proc: LightProc| {
+        let future = Box::pin(async move { proc.run() });
+        if blocking {
+            executor.spawn_blocking(future);
+        } else {
+            executor.spawn(future);
+        }
+    };
+    let (proc, handle) = LightProc::recoverable(future, schedule, stack);
+    proc.schedule();
+    handle
+}
+
+// Returns the handle of the process spawned onto bastion's own
+// executor or, if it was shut down, a handle resolving to `None`.
+fn or_cancelled<T>(spawned: Result<RecoverableHandle<T>, ShutDown>) -> RecoverableHandle<T>
+where
+    T: Send + 'static,
+{
+    spawned.unwrap_or_else(|err| {
+        warn!("Executor: Couldn't spawn a process: {}.", err);
+        let future = futures::future::pending::<T>();
+        // Dropping the process cancels it.
+        let (_, handle) = LightProc::recoverable(future, |_| {}, ProcStack::default());
+        handle
+    })
+}
+
+/// Spawns the process onto the executor's pool.
+pub(crate) fn spawn_proc<F, T>(future: F, stack: ProcStack) -> RecoverableHandle<T>
+where
+    F: Future<Output = T> + Send + 'static,
+    T: Send + 'static,
+{
+    match custom() {
+        Some(executor) => spawn_custom(executor, future, stack, false),
+        None => or_cancelled(SCOPE.try_spawn(future, stack)),
+    }
+}
+
+/// Spawns the process onto the executor's blocking pool.
+pub(crate) fn spawn_proc_blocking<F, T>(future: F, stack: ProcStack) -> RecoverableHandle<T>
+where
+    F: Future<Output = T> + Send + 'static,
+    T: Send + 'static,
+{
+    match custom() {
+        Some(executor) => spawn_custom(executor, future, stack, true),
+        None => blocking::spawn_blocking(future, stack),
+    }
+}
+
+/// Spawns the process onto its own thread (or onto the blocking
+/// pool of a custom executor).
+pub(crate) fn spawn_proc_dedicated<F, T>(future: F, stack: ProcStack) -> RecoverableHandle<T>
+where
+    F: Future<Output = T> + Send + 'static,
+    T: Send + 'static,
+{
+    match custom() {
+        Some(executor) => spawn_custom(executor, future, stack, true),
+        None => or_cancelled(SCOPE.try_spawn_dedicated(future, stack)),
+    }
+}
+
+/// Spawns the process onto the given thread of the executor's pool
+/// (or anywhere for a custom executor).
+pub(crate) fn spawn_proc_pinned<F, T>(
+    future: F,
+    stack: ProcStack,
+    thread: usize,
+) -> RecoverableHandle<T>
+where
+    F: Future<Output = T> + Send + 'static,
+    T: Send + 'static,
+{
+    match custom() {
+        Some(executor) => spawn_custom(executor, future, stack, false),
+        None => or_cancelled(SCOPE.try_spawn_pinned(future, stack, thread)),
+    }
+}
+
+/// Wakes the current process up and yields, for the executor to
+/// poll the other ones before polling it again.
+pub(crate) async fn yield_now() {
+    let mut yielded = false;
+    futures::future::poll_fn(|cx| {
+        if yielded {
+            return Poll::Ready(());
+        }
+
+        yielded = true;
+        cx.waker().wake_by_ref();
+        Poll::Pending
+    })
+    .await
+}
+
+/// Keeps bastion's own executor running (reopening it if it was
+/// shut down) until dropped, e.g. by a system once it stopped.
+#[derive(Debug)]
+pub(crate) struct Retained(());
+
+/// Keeps bastion's own executor running (reopening it if it was
+/// shut down) until the returned [`Retained`] gets dropped.
+///
+/// [`Retained`]: struct.Retained.html
+pub(crate) fn retain() -> Retained {
+    // FIXME: panics
+    let mut running = RUNNING.lock().unwrap();
+    *running += 1;
+    SCOPE.reopen();
+    Retained(())
+}
+
+impl Drop for Retained {
+    fn drop(&mut self) {
+        release();
+    }
+}
+
+// Lets bastion's own executor shut down once nothing else retains
+// it: the processes which get scheduled afterwards are cancelled
+// and new ones are refused, until it gets retained again.
+fn release() {
+    // FIXME: panics
+    let mut running = RUNNING.lock().unwrap();
+    *running = running.saturating_sub(1);
+    if *running == 0 {
+        SCOPE.shutdown();
+    }
+}
+
+/// Blocks the current thread until none of the processes bastion
+/// spawned onto its own executor is running anymore, if it was
+/// shut down.
+pub(crate) fn block_until_quiesced() {
+    if !SCOPE.is_shut_down() {
+        return;
+    }
+
+    if !SCOPE.block_until_quiesced(QUIESCE_TIMEOUT) {
+        warn!(
+            "Executor: Still running after {:?}, a process probably never yields.",
+            QUIESCE_TIMEOUT
+        );
+    }
 }
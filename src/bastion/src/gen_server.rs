@@ -0,0 +1,247 @@
+//!
+//! A behaviour mirroring Erlang's `gen_server`, allowing to
+//! implement the common "call/cast/info" actor pattern with a
+//! [`GenServer`] instead of writing the receive loop by hand.
+//!
+//! A server is started using [`start`], which runs it in a new
+//! children group, and then communicated with using [`call`]
+//! (which "asks" the server and waits for its reply) and [`cast`]
+//! (which "tells" the server without waiting for anything).
+//!
+//! # Example
+//!
+//! ```rust
+//! # use bastion::prelude::*;
+//! #
+//! # fn main() {
+//!     # Bastion::init();
+//!     # Bastion::start();
+//! #[derive(Clone, Default)]
+//! struct Counter(usize);
+//!
+//! #[derive(Debug)]
+//! struct Get;
+//! #[derive(Debug)]
+//! struct Add(usize);
+//!
+//! impl GenServer for Counter {
+//!     type Call = Get;
+//!     type Reply = usize;
+//!     type Cast = Add;
+//!
+//!     fn handle_call(&mut self, _request: Get, _ctx: &BastionContext) -> usize {
+//!         self.0
+//!     }
+//!
+//!     fn handle_cast(&mut self, Add(n): Add, _ctx: &BastionContext) {
+//!         self.0 += n;
+//!     }
+//! }
+//!
+//! let server = gen_server::start(Counter::default()).expect("Couldn't start the server.");
+//!
+//! gen_server::cast::<Counter>(&server, Add(2)).expect("Couldn't send the message.");
+//! let count = run!(gen_server::call::<Counter>(&server, Get));
+//! assert_eq!(count, Ok(2));
+//!     #
+//!     # Bastion::stop();
+//!     # Bastion::block_until_stopped();
+//! # }
+//! ```
+//!
+//! [`GenServer`]: trait.GenServer.html
+//! [`start`]: fn.start.html
+//! [`call`]: fn.call.html
+//! [`cast`]: fn.cast.html
+use crate::bastion::Bastion;
+use crate::child_ref::ChildRef;
+use crate::context::BastionContext;
+use crate::envelope::SignedMessage;
+use crate::message::Message;
+use std::sync::Mutex;
+use tracing::{debug, trace};
+
+/// A server handling the requests it is "called" with, the
+/// messages it is "cast" and any other message it receives
+/// (see the [module documentation]).
+///
+/// The handlers are called one at a time, in the order the
+/// messages were received, with the state of the server.
+///
+/// [module documentation]: index.html
+pub trait GenServer: Send + Sized + 'static {
+    /// The type of the requests the server can be called with
+    /// (using [`call`]).
+    ///
+    /// [`call`]: fn.call.html
+    type Call: Message;
+    /// The type of the replies to the requests the server is
+    /// called with.
+    type Reply: Message;
+    /// The type of the messages that can be cast to the server
+    /// (using [`cast`]).
+    ///
+    /// [`cast`]: fn.cast.html
+    type Cast: Message;
+
+    /// Called once the server started (or restarted), before it
+    /// handles any message.
+    ///
+    /// # Arguments
+    ///
+    /// * `ctx` - The context of the element running the server.
+    fn init(&mut self, ctx: &BastionContext) {
+        let _ = ctx;
+    }
+
+    /// Handles a request the server was called with and returns
+    /// the reply to send back to the caller.
+    ///
+    /// # Arguments
+    ///
+    /// * `request` - The request the server was called with.
+    /// * `ctx` - The context of the element running the server.
+    fn handle_call(&mut self, request: Self::Call, ctx: &BastionContext) -> Self::Reply;
+
+    /// Handles a message that was cast to the server.
+    ///
+    /// # Arguments
+    ///
+    /// * `msg` - The message that was cast to the server.
+    /// * `ctx` - The context of the element running the server.
+    fn handle_cast(&mut self, msg: Self::Cast, ctx: &BastionContext);
+
+    /// Handles any other message the server received (e.g. a
+    /// broadcasted message or a message of an unexpected type),
+    /// which is ignored by default.
+    ///
+    /// # Arguments
+    ///
+    /// * `msg` - The message the server received.
+    /// * `ctx` - The context of the element running the server.
+    fn handle_info(&mut self, msg: SignedMessage, ctx: &BastionContext) {
+        let _ = (msg, ctx);
+    }
+
+    /// Called once the server was asked to stop and handled
+    /// all the messages it received.
+    ///
+    /// # Arguments
+    ///
+    /// * `ctx` - The context of the element running the server.
+    fn terminate(&mut self, ctx: &BastionContext) {
+        let _ = ctx;
+    }
+}
+
+/// Starts a server in a new children group, with the given initial
+/// state. If the server faults, it is restarted with a copy of its
+/// initial state.
+///
+/// This method returns a [`ChildRef`] referencing the element
+/// running the server if it succeeded, or `Err(())` otherwise.
+/// Note that, like for any element, this `ChildRef` can't be
+/// used anymore once the server restarted.
+///
+/// # Arguments
+///
+/// * `initial_state` - The state the server starts with.
+///
+/// # Example
+///
+/// See the [module documentation].
+///
+/// [`ChildRef`]: ../child_ref/struct.ChildRef.html
+/// [module documentation]: index.html
+pub fn start<S>(initial_state: S) -> Result<ChildRef, ()>
+where
+    S: GenServer + Clone,
+{
+    debug!("GenServer: Starting.");
+    // The `Mutex` allows the server not to be `Sync`.
+    let initial_state = Mutex::new(initial_state);
+    let children_ref = Bastion::children(move |children| {
+        children.with_exec(move |ctx: BastionContext| {
+            // FIXME: panics
+            let server = initial_state.lock().unwrap().clone();
+            serve(server, ctx)
+        })
+    })?;
+
+    // FIXME: panics?
+    Ok(children_ref.elems()[0].clone())
+}
+
+/// Calls the server the given [`ChildRef`] is referencing with
+/// the given request, waiting for its reply.
+///
+/// This method returns a future resolving to the server's reply
+/// if it succeeded, or `Err(())` otherwise.
+///
+/// # Arguments
+///
+/// * `server` - The `ChildRef` returned by [`start`].
+/// * `request` - The request to call the server with.
+///
+/// # Example
+///
+/// See the [module documentation].
+///
+/// [`ChildRef`]: ../child_ref/struct.ChildRef.html
+/// [`start`]: fn.start.html
+/// [module documentation]: index.html
+pub async fn call<S: GenServer>(server: &ChildRef, request: S::Call) -> Result<S::Reply, ()> {
+    debug!("GenServer({}): Calling: {:?}", server.id(), request);
+    let answer = server.ask_anonymously(request).map_err(|_| ())?;
+    let reply = answer.await?;
+    trace!("GenServer({}): Replied: {:?}", server.id(), reply);
+
+    reply.msg.downcast().map_err(|_| ())
+}
+
+/// Casts the given message to the server the given [`ChildRef`]
+/// is referencing, without waiting for it to be handled.
+///
+/// This method returns `()` if it succeeded, or `Err(msg)`
+/// otherwise.
+///
+/// # Arguments
+///
+/// * `server` - The `ChildRef` returned by [`start`].
+/// * `msg` - The message to cast to the server.
+///
+/// # Example
+///
+/// See the [module documentation].
+///
+/// [`ChildRef`]: ../child_ref/struct.ChildRef.html
+/// [`start`]: fn.start.html
+/// [module documentation]: index.html
+pub fn cast<S: GenServer>(server: &ChildRef, msg: S::Cast) -> Result<(), S::Cast> {
+    debug!("GenServer({}): Casting: {:?}", server.id(), msg);
+    server.tell_anonymously(msg)
+}
+
+async fn serve<S: GenServer>(mut server: S, ctx: BastionContext) -> Result<(), ()> {
+    server.init(&ctx);
+
+    while let Ok(mut signed) = ctx.recv().await {
+        if signed.msg.is_ask() && signed.msg.is::<S::Call>() {
+            // FIXME: panics?
+            let sender = signed.msg.take_sender().unwrap();
+            let request = signed.msg.downcast().unwrap();
+            let reply = server.handle_call(request, &ctx);
+            // The caller might have stopped waiting for the reply.
+            sender.send(reply, ctx.signature()).ok();
+        } else if signed.msg.is_tell() && signed.msg.is::<S::Cast>() {
+            // FIXME: panics?
+            let msg = signed.msg.downcast().unwrap();
+            server.handle_cast(msg, &ctx);
+        } else {
+            server.handle_info(signed, &ctx);
+        }
+    }
+
+    server.terminate(&ctx);
+    Ok(())
+}
@@ -0,0 +1,77 @@
+//!
+//! An HTTP endpoint answering the liveness and readiness probes of
+//! container orchestrators (see `Bastion::register_health_endpoint`).
+use crate::system::GlobalSystem;
+use serde_json::json;
+use std::net::SocketAddr;
+use std::sync::atomic::Ordering;
+use std::sync::Arc;
+use std::thread;
+use std::time::Duration;
+use tiny_http::{Header, Method, Request, Response, Server};
+use tracing::{debug, trace, warn};
+
+// How often the serving thread checks whether the system stopped
+// while no request is received.
+const STOPPED_POLL_INTERVAL: Duration = Duration::from_millis(50);
+
+// Starts serving the health endpoint of the given system on a
+// dedicated thread, until the system stops.
+pub(crate) fn serve(system: Arc<GlobalSystem>, port: u16) -> Result<SocketAddr, ()> {
+    let server = match Server::http(("0.0.0.0", port)) {
+        Ok(server) => server,
+        Err(err) => {
+            warn!("Health endpoint: Couldn't listen on port {}: {}", port, err);
+            return Err(());
+        }
+    };
+    // The server only listens on IP addresses.
+    let addr = server.server_addr().to_ip().ok_or(())?;
+    debug!("Health endpoint: Listening on {}.", addr);
+
+    thread::spawn(move || {
+        while !system.has_stopped() {
+            match server.recv_timeout(STOPPED_POLL_INTERVAL) {
+                Ok(Some(request)) => respond(&system, request),
+                Ok(None) => (),
+                Err(err) => warn!("Health endpoint: Couldn't receive a request: {}", err),
+            }
+        }
+
+        // Dropping the server stops listening.
+        debug!(
+            "Health endpoint: System stopped, stopping listening on {}.",
+            addr
+        );
+    });
+
+    Ok(addr)
+}
+
+fn respond(system: &GlobalSystem, request: Request) {
+    trace!(
+        "Health endpoint: Received: {} {}",
+        request.method(),
+        request.url()
+    );
+    let response = match (request.method(), request.url()) {
+        (Method::Get, "/health") => {
+            let body = json!({
+                "status": "ok",
+                "actors": system.actors().load(Ordering::SeqCst),
+            });
+            // FIXME: panics?
+            let content_type = Header::from_bytes("Content-Type", "application/json").unwrap();
+            Response::from_string(body.to_string()).with_header(content_type)
+        }
+        (Method::Get, "/ready") if system.has_started() => Response::from_string("ready"),
+        (Method::Get, "/ready") => Response::from_string("not ready").with_status_code(503),
+        (_, "/health") | (_, "/ready") => Response::from_string("").with_status_code(405),
+        _ => Response::from_string("").with_status_code(404),
+    };
+
+    // The client might have disconnected meanwhile.
+    if let Err(err) = request.respond(response) {
+        trace!("Health endpoint: Couldn't respond: {}", err);
+    }
+}
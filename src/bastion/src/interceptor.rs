@@ -0,0 +1,112 @@
+//!
+//! Interceptors allow to inspect the messages received by the
+//! elements of a children group before they are handed to their
+//! futures, to implement cross-cutting concerns (e.g. logging,
+//! metering or validation) without changing them (see
+//! [`Children::with_interceptor`]).
+//!
+//! [`Children::with_interceptor`]: ../children/struct.Children.html#method.with_interceptor
+use crate::context::BastionId;
+use crate::envelope::{RefAddr, TraceId};
+use crate::message::Msg;
+use std::fmt::{self, Debug, Formatter};
+use std::panic::{self, AssertUnwindSafe};
+use std::sync::Arc;
+use tracing::{trace, warn};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+/// What to do with a message received by an element, as
+/// returned by the closures set with
+/// [`Children::with_interceptor`].
+///
+/// [`Children::with_interceptor`]: ../children/struct.Children.html#method.with_interceptor
+pub enum InterceptDecision {
+    /// Hands the message to the element (or to the next
+    /// interceptor).
+    Deliver,
+    /// Silently drops the message. If it was "asked", the
+    /// sender won't receive any answer.
+    Drop,
+    /// Sends the message to the dead letters instead.
+    DeadLetter,
+}
+
+#[derive(Debug)]
+/// Information about a message intercepted by the closures
+/// set with [`Children::with_interceptor`] and about the
+/// element receiving it.
+///
+/// [`Children::with_interceptor`]: ../children/struct.Children.html#method.with_interceptor
+pub struct InterceptCtx<'a> {
+    id: &'a BastionId,
+    sign: &'a RefAddr,
+    trace: Option<TraceId>,
+}
+
+pub(crate) type Interceptor = Arc<dyn Fn(&Msg, &InterceptCtx) -> InterceptDecision + Send + Sync>;
+
+#[derive(Default, Clone)]
+// The interceptors of a children group, called in the order
+// they were added.
+pub(crate) struct Interceptors(Vec<Interceptor>);
+
+impl<'a> InterceptCtx<'a> {
+    pub(crate) fn new(id: &'a BastionId, sign: &'a RefAddr, trace: Option<TraceId>) -> Self {
+        InterceptCtx { id, sign, trace }
+    }
+
+    /// Returns the identifier of the element receiving the
+    /// message.
+    pub fn id(&self) -> &BastionId {
+        self.id
+    }
+
+    /// Returns the address of the sender of the message.
+    pub fn sender(&self) -> &RefAddr {
+        self.sign
+    }
+
+    /// Returns the [`TraceId`] attached to the message, if any.
+    ///
+    /// [`TraceId`]: ../envelope/struct.TraceId.html
+    pub fn trace(&self) -> Option<TraceId> {
+        self.trace
+    }
+}
+
+impl Interceptors {
+    pub(crate) fn push(&mut self, interceptor: Interceptor) {
+        self.0.push(interceptor);
+    }
+
+    // Calls the interceptors in order until one of them doesn't
+    // decide to deliver the message. An interceptor that panics
+    // is considered to have decided to deliver it.
+    pub(crate) fn intercept(&self, msg: &Msg, ctx: &InterceptCtx) -> InterceptDecision {
+        for interceptor in &self.0 {
+            let decision = panic::catch_unwind(AssertUnwindSafe(|| interceptor(msg, ctx)))
+                .unwrap_or_else(|_| {
+                    warn!(
+                        "Child({}): Interceptor panicked, delivering the message.",
+                        ctx.id()
+                    );
+                    InterceptDecision::Deliver
+                });
+
+            if decision != InterceptDecision::Deliver {
+                trace!("Child({}): Intercepted message: {:?}", ctx.id(), decision);
+                return decision;
+            }
+        }
+
+        InterceptDecision::Deliver
+    }
+}
+
+impl Debug for Interceptors {
+    fn fmt(&self, fmt: &mut Formatter) -> fmt::Result {
+        fmt.debug_struct("Interceptors")
+            .field("len", &self.0.len())
+            .finish()
+    }
+}
@@ -56,6 +56,7 @@
 pub use self::bastion::Bastion;
 pub use self::callbacks::Callbacks;
 pub use self::config::Config;
+pub use self::runtime::{BastionRuntime, RuntimeId};
 
 #[macro_use]
 mod macros;
@@ -65,18 +66,42 @@ mod broadcast;
 mod callbacks;
 mod child;
 mod config;
+mod dump;
+#[cfg(feature = "http-health")]
+mod health;
+mod link;
+mod names;
+mod panic_handler;
+mod runtime;
 mod system;
+mod topic;
 
 pub mod child_ref;
 pub mod children;
 pub mod children_ref;
+#[cfg(feature = "codec")]
+#[cfg_attr(feature = "docs", doc(cfg(feature = "codec")))]
+pub mod codec;
 pub mod context;
 pub mod dispatcher;
 pub mod envelope;
+pub mod events;
 pub mod executor;
+pub mod gen_server;
+pub mod interceptor;
+pub mod logger;
+pub mod mailbox_store;
 pub mod message;
+pub mod metrics;
 pub mod path;
+#[cfg(feature = "remote")]
+#[cfg_attr(feature = "docs", doc(cfg(feature = "remote")))]
+pub mod remote;
+pub mod scheduler;
+pub mod state_backend;
+pub mod storm;
 pub mod supervisor;
+pub mod sync;
 
 distributed_api! {
     // pub mod dist_messages;
@@ -86,23 +111,40 @@ distributed_api! {
 ///
 /// Prelude of Bastion
 pub mod prelude {
-    pub use crate::bastion::Bastion;
+    pub use crate::bastion::{Bastion, SystemExit, SystemStats};
     pub use crate::callbacks::Callbacks;
     pub use crate::child_ref::ChildRef;
-    pub use crate::children::Children;
-    pub use crate::children_ref::ChildrenRef;
-    pub use crate::config::Config;
-    pub use crate::context::{BastionContext, BastionId, NIL_ID};
+    pub use crate::children::{
+        Affinity, Children, FaultEvent, FaultKind, FaultPolicy, SpawnStrategy, SupervisionDecision,
+    };
+    pub use crate::children_ref::{CallError, ChildrenRef, DrainError, HealthStatus};
+    pub use crate::config::{BroadcastConfig, Config};
+    pub use crate::context::{
+        BastionContext, BastionId, LinkDown, ParseIdError, TerminationReason, ThreadContext, NIL_ID,
+    };
     pub use crate::dispatcher::{
         BroadcastTarget, DefaultDispatcherHandler, Dispatcher, DispatcherHandler, DispatcherMap,
         DispatcherType, NotificationType,
     };
-    pub use crate::envelope::{RefAddr, SignedMessage};
-    pub use crate::message::{Answer, AnswerSender, Message, Msg};
+    pub use crate::envelope::{DeliveryError, RefAddr, SignedMessage, TraceId};
+    pub use crate::events::{EntityKind, LifecycleEvent, SystemEvent, Transition};
+    pub use crate::executor::ExecutorStats;
+    pub use crate::gen_server::{self, GenServer};
+    pub use crate::interceptor::{InterceptCtx, InterceptDecision};
+    pub use crate::logger::{BastionLogger, StderrLogger};
+    pub use crate::mailbox_store::MailboxStore;
+    pub use crate::message::{Answer, AnswerSender, AskError, Message, Msg, Recipients};
+    pub use crate::metrics::{ChildrenMetrics, MetricsSnapshot, SupervisorMetrics};
     pub use crate::msg;
     pub use crate::path::{BastionPath, BastionPathElement};
+    pub use crate::runtime::{BastionRuntime, RuntimeId};
+    pub use crate::scheduler::{ScheduledSend, Tick};
+    pub use crate::state_backend::{BackendError, StateBackend};
+    pub use crate::storm::{StormOffender, StormReport};
     pub use crate::supervisor::{
-        ActorRestartStrategy, RestartPolicy, RestartStrategy, SupervisionStrategy, Supervisor,
+        ActorRestartStrategy, InspectReport, InspectedElement, OrderGuarantee, OrphanPolicy,
+        RestartPolicy, RestartStrategy, RestartWindow, Routing, ShutdownReport, StopReason,
+        SupervisedInfo, SupervisedKind, SupervisionStrategy, SupervisionTree, Supervisor,
         SupervisorRef,
     };
     pub use crate::{answer, blocking, children, run, spawn, supervisor};
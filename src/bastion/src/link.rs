@@ -0,0 +1,71 @@
+//!
+//! Registry of the elements watching other elements (because
+//! they were linked to or monitored them), which get notified
+//! when those stop or fault.
+use crate::child_ref::ChildRef;
+use crate::context::{BastionId, LinkDown, TerminationReason};
+use crate::envelope::Envelope;
+use crate::message::BastionMessage;
+use fxhash::FxHashMap;
+use std::sync::Mutex;
+use tracing::{debug, trace};
+
+#[derive(Debug, Default)]
+pub(crate) struct LinkRegistry {
+    // Each watched element's identifier associated with the
+    // elements watching it.
+    watchers: Mutex<FxHashMap<BastionId, FxHashMap<BastionId, ChildRef>>>,
+}
+
+impl LinkRegistry {
+    pub(crate) fn new() -> Self {
+        LinkRegistry::default()
+    }
+
+    pub(crate) fn watch(&self, watched: &BastionId, watcher: &ChildRef) {
+        debug!("Child({}): Watching Child({}).", watcher.id(), watched);
+        // FIXME: panics
+        let mut watchers = self.watchers.lock().unwrap();
+        watchers
+            .entry(watched.clone())
+            .or_default()
+            .insert(watcher.id().clone(), watcher.clone());
+    }
+
+    /// Sends a [`LinkDown`] message to every element watching
+    /// the one with the given identifier, which then stops
+    /// watching other elements.
+    pub(crate) fn notify_down(&self, id: &BastionId, reason: TerminationReason) {
+        let watchers = {
+            // FIXME: panics
+            let mut watchers = self.watchers.lock().unwrap();
+            let notified = watchers.remove(id);
+            watchers.retain(|_, watchers| {
+                watchers.remove(id);
+                !watchers.is_empty()
+            });
+
+            match notified {
+                Some(notified) => notified,
+                None => return,
+            }
+        };
+
+        for (_, watcher) in watchers {
+            trace!(
+                "Child({}): Notifying Child({}) that it is down: {:?}",
+                id,
+                watcher.id(),
+                reason
+            );
+            let msg = LinkDown {
+                id: id.clone(),
+                reason,
+            };
+            let msg = BastionMessage::tell(msg);
+            let env = Envelope::from_dead_letters(msg, watcher.system());
+            // Best-effort: the watcher might be stopping as well.
+            watcher.send(env).ok();
+        }
+    }
+}
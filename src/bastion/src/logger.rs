@@ -0,0 +1,186 @@
+//!
+//! Pluggable logging backend notified of the lifecycle events of
+//! the children's elements (see [`Bastion::with_logger`]).
+//!
+//! With the `lifecycle-events` feature, the supervision tree also
+//! emits `tracing` events with the `bastion::lifecycle` target for
+//! its own transitions, including the ones no callback sees (e.g.
+//! the restarts decided by the supervisors). Each event has an
+//! `event` field naming the transition (`supervisor_started`,
+//! `supervisor_stopped`, `supervisor_killed`, `supervisor_faulted`,
+//! `supervisor_escalated`, `supervisor_deployed`,
+//! `supervisor_restarted`, `supervised_faulted`,
+//! `supervised_stopped`, `supervised_killed`, `strategy_changed`,
+//! `children_deployed`, `children_started`, `children_stopped`,
+//! `children_killed`, `children_faulted`, `element_restarted`,
+//! `element_dropped`, `element_slow` or `message_dead_lettered`)
+//! along with the ids (and children groups' names) involved.
+//! Without the feature, no event is emitted nor any field
+//! computed.
+//!
+//! [`Bastion::with_logger`]: ../struct.Bastion.html#method.with_logger
+use crate::context::BastionId;
+use crate::message::Msg;
+use lazy_static::lazy_static;
+use std::fmt::Debug;
+use std::sync::{Arc, RwLock};
+use std::time::Duration;
+
+lazy_static! {
+    static ref LOGGER: RwLock<Option<Arc<dyn BastionLogger>>> = RwLock::new(None);
+}
+
+/// A logging backend that gets notified of the lifecycle events
+/// of the children's elements, allowing to forward them to any
+/// logging library.
+///
+/// Every method does nothing by default, so that implementors
+/// only need to override the events they are interested in.
+///
+/// Note that those events are also always reported with `tracing`
+/// events, independently of the logger that is being used.
+///
+/// # Example
+///
+/// ```rust
+/// # use bastion::prelude::*;
+/// #
+/// #[derive(Debug)]
+/// struct FaultLogger;
+///
+/// impl BastionLogger for FaultLogger {
+///     fn log_fault(&self, id: &BastionId) {
+///         eprintln!("Child({}) faulted.", id);
+///     }
+/// }
+///
+/// Bastion::init();
+/// Bastion::with_logger(Box::new(FaultLogger));
+/// #
+/// # Bastion::start();
+/// # Bastion::stop();
+/// # Bastion::block_until_stopped();
+/// ```
+pub trait BastionLogger: Debug + Send + Sync + 'static {
+    /// Called when an element has started.
+    ///
+    /// # Arguments
+    ///
+    /// * `id` - The identifier of the element.
+    fn log_start(&self, _id: &BastionId) {}
+
+    /// Called when an element has stopped, either because its
+    /// future finished executing or because it was stopped or
+    /// killed.
+    ///
+    /// # Arguments
+    ///
+    /// * `id` - The identifier of the element.
+    fn log_stop(&self, _id: &BastionId) {}
+
+    /// Called when an element's future returned an error or
+    /// panicked.
+    ///
+    /// # Arguments
+    ///
+    /// * `id` - The identifier of the element.
+    fn log_fault(&self, _id: &BastionId) {}
+
+    /// Called when an element is restarted by its children group
+    /// after it faulted.
+    ///
+    /// # Arguments
+    ///
+    /// * `id` - The identifier of the element.
+    fn log_restart(&self, _id: &BastionId) {}
+
+    /// Called when a message received by an element is dropped
+    /// without being handled (see [`Msg::send_error_log`]).
+    ///
+    /// # Arguments
+    ///
+    /// * `id` - The identifier of the element.
+    /// * `msg` - The message that was dropped.
+    ///
+    /// [`Msg::send_error_log`]: ../message/struct.Msg.html#method.send_error_log
+    fn log_message_drop(&self, _id: &BastionId, _msg: &Msg) {}
+
+    /// Called when an element took longer than its children
+    /// group's warning threshold to handle a message (see
+    /// [`Children::with_processing_warning_threshold`]), once per
+    /// message.
+    ///
+    /// # Arguments
+    ///
+    /// * `id` - The identifier of the element.
+    /// * `elapsed` - How long the element had been handling the
+    ///     message when this was detected.
+    /// * `msg_type` - The name of the type of the message.
+    ///
+    /// [`Children::with_processing_warning_threshold`]: ../children/struct.Children.html#method.with_processing_warning_threshold
+    fn log_slow_processing(&self, _id: &BastionId, _elapsed: Duration, _msg_type: &str) {}
+}
+
+#[derive(Debug, Default, Clone, Copy)]
+/// A [`BastionLogger`] printing every event to the standard error.
+///
+/// # Example
+///
+/// ```rust
+/// # use bastion::prelude::*;
+/// #
+/// Bastion::init();
+/// Bastion::with_logger(Box::new(StderrLogger));
+/// #
+/// # Bastion::start();
+/// # Bastion::stop();
+/// # Bastion::block_until_stopped();
+/// ```
+///
+/// [`BastionLogger`]: trait.BastionLogger.html
+pub struct StderrLogger;
+
+impl BastionLogger for StderrLogger {
+    fn log_start(&self, id: &BastionId) {
+        eprintln!("[bastion] Child({}): Started.", id);
+    }
+
+    fn log_stop(&self, id: &BastionId) {
+        eprintln!("[bastion] Child({}): Stopped.", id);
+    }
+
+    fn log_fault(&self, id: &BastionId) {
+        eprintln!("[bastion] Child({}): Faulted.", id);
+    }
+
+    fn log_restart(&self, id: &BastionId) {
+        eprintln!("[bastion] Child({}): Restarted.", id);
+    }
+
+    fn log_message_drop(&self, id: &BastionId, msg: &Msg) {
+        eprintln!("[bastion] Child({}): Dropped message: {:?}", id, msg);
+    }
+
+    fn log_slow_processing(&self, id: &BastionId, elapsed: Duration, msg_type: &str) {
+        eprintln!(
+            "[bastion] Child({}): Handling a message of type {} for {:?}.",
+            id, msg_type, elapsed
+        );
+    }
+}
+
+pub(crate) fn set_logger(logger: Arc<dyn BastionLogger>) {
+    // FIXME: panics
+    *LOGGER.write().unwrap() = Some(logger);
+}
+
+/// Calls the given closure with the active logger, if any.
+pub(crate) fn with_logger<F>(f: F)
+where
+    F: FnOnce(&dyn BastionLogger),
+{
+    let logger = LOGGER.read().ok().and_then(|logger| logger.clone());
+    if let Some(logger) = logger {
+        f(&*logger);
+    }
+}
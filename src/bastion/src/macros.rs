@@ -275,3 +275,22 @@ macro_rules! distributed_api {
         )*
     }
 }
+
+///
+/// Emits a `tracing` event about a lifecycle transition of the
+/// supervision tree (with the `bastion::lifecycle` target and the
+/// transition's name as its `event` field), expanding to nothing
+/// unless the `lifecycle-events` feature is enabled.
+#[doc(hidden)]
+#[cfg(feature = "lifecycle-events")]
+macro_rules! lifecycle_event {
+    ($event:literal $(, $($fields:tt)+)?) => {
+        tracing::info!(target: "bastion::lifecycle", event = $event $(, $($fields)+)?)
+    };
+}
+
+#[doc(hidden)]
+#[cfg(not(feature = "lifecycle-events"))]
+macro_rules! lifecycle_event {
+    ($($tokens:tt)*) => {};
+}
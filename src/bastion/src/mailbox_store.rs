@@ -0,0 +1,189 @@
+//!
+//! Mailbox stores keep the messages received by the elements of a
+//! children group until they are retrieved, allowing to replay the
+//! ones that weren't when the group is launched again (see
+//! [`Children::with_persistent_mailbox`]).
+//!
+//! [`Children::with_persistent_mailbox`]: ../children/struct.Children.html#method.with_persistent_mailbox
+use crate::message::Msg;
+use std::fmt::Debug;
+
+#[cfg(feature = "sled-mailbox")]
+use crate::message::Message;
+#[cfg(feature = "sled-mailbox")]
+use serde::{de::DeserializeOwned, Serialize};
+#[cfg(feature = "sled-mailbox")]
+use std::marker::PhantomData;
+#[cfg(feature = "sled-mailbox")]
+use std::sync::atomic::{AtomicU64, Ordering};
+#[cfg(feature = "sled-mailbox")]
+use tracing::{error, trace};
+
+/// A storage for the messages received by the elements of a
+/// children group that weren't retrieved yet (see
+/// [`Children::with_persistent_mailbox`]).
+///
+/// Messages are appended once placed into an element's mailbox,
+/// marked as processed once retrieved by the element (or once
+/// they expired), and the remaining ones are drained when the
+/// children group launches its elements, to be received again.
+///
+/// [`Children::with_persistent_mailbox`]: ../children/struct.Children.html#method.with_persistent_mailbox
+pub trait MailboxStore: Debug + Send + Sync {
+    /// Stores a message placed into an element's mailbox.
+    ///
+    /// This method returns the key identifying the stored message,
+    /// or `None` if it can't be stored (e.g. if it was "asked",
+    /// since it couldn't be answered once replayed, or if it is of
+    /// a type the store doesn't handle).
+    ///
+    /// # Arguments
+    ///
+    /// * `msg` - The message to store.
+    fn append(&self, msg: &Msg) -> Option<u64>;
+
+    /// Removes the stored message identified by the given key,
+    /// once it was processed.
+    ///
+    /// # Arguments
+    ///
+    /// * `key` - The key returned by [`append`].
+    ///
+    /// [`append`]: #tymethod.append
+    fn mark_processed(&self, key: u64);
+
+    /// Removes and returns all the stored messages, in the order
+    /// they were appended (each one being created using
+    /// [`Msg::tell`]).
+    ///
+    /// [`Msg::tell`]: ../message/struct.Msg.html#method.tell
+    fn drain(&self) -> Vec<Msg>;
+}
+
+#[cfg(feature = "sled-mailbox")]
+#[cfg_attr(feature = "docs", doc(cfg(feature = "sled-mailbox")))]
+#[derive(Debug)]
+/// A [`MailboxStore`] storing messages of type `M` (serialized as
+/// JSON) in a [`sled::Tree`], which persists them across restarts
+/// of the application.
+///
+/// Messages of other types aren't stored.
+///
+/// # Example
+///
+/// ```rust
+/// # use bastion::prelude::*;
+/// # use bastion::mailbox_store::SledMailboxStore;
+/// # use std::sync::Arc;
+/// #
+/// # Bastion::init();
+/// #
+/// let db = sled::Config::new().temporary(true).open().unwrap();
+/// let tree = db.open_tree("workers").unwrap();
+/// let store = SledMailboxStore::<String>::new(tree);
+///
+/// Bastion::children(|children| {
+///     children.with_persistent_mailbox(Arc::new(store))
+/// }).expect("Couldn't create the children group.");
+/// #
+/// # Bastion::start();
+/// # Bastion::stop();
+/// # Bastion::block_until_stopped();
+/// ```
+///
+/// [`MailboxStore`]: trait.MailboxStore.html
+/// [`sled::Tree`]: https://docs.rs/sled/0.34/sled/struct.Tree.html
+pub struct SledMailboxStore<M> {
+    tree: sled::Tree,
+    // The key of the next appended message, keys being stored
+    // big-endian to keep the tree in the order messages were
+    // appended.
+    next_key: AtomicU64,
+    _msg: PhantomData<fn() -> M>,
+}
+
+#[cfg(feature = "sled-mailbox")]
+impl<M> SledMailboxStore<M>
+where
+    M: Message + Serialize + DeserializeOwned,
+{
+    /// Creates a new `SledMailboxStore` storing messages in the
+    /// given tree, which might already contain messages stored by
+    /// a previous run of the application.
+    ///
+    /// # Arguments
+    ///
+    /// * `tree` - The tree to store messages in.
+    pub fn new(tree: sled::Tree) -> Self {
+        let next_key = match tree.last() {
+            Ok(Some((key, _))) => Self::decode_key(&key) + 1,
+            _ => 0,
+        };
+
+        SledMailboxStore {
+            tree,
+            next_key: AtomicU64::new(next_key),
+            _msg: PhantomData,
+        }
+    }
+
+    fn decode_key(key: &[u8]) -> u64 {
+        let mut bytes = [0; 8];
+        bytes.copy_from_slice(&key[..8]);
+        u64::from_be_bytes(bytes)
+    }
+}
+
+#[cfg(feature = "sled-mailbox")]
+impl<M> MailboxStore for SledMailboxStore<M>
+where
+    M: Message + Serialize + DeserializeOwned,
+{
+    fn append(&self, msg: &Msg) -> Option<u64> {
+        if msg.is_ask() {
+            return None;
+        }
+
+        let value = serde_json::to_vec(msg.peek::<M>()?)
+            .map_err(|err| error!("SledMailboxStore: Couldn't serialize message: {}", err))
+            .ok()?;
+
+        let key = self.next_key.fetch_add(1, Ordering::SeqCst);
+        trace!("SledMailboxStore: Appending message {}.", key);
+        self.tree
+            .insert(key.to_be_bytes(), value)
+            .map_err(|err| error!("SledMailboxStore: Couldn't store message: {}", err))
+            .ok()?;
+
+        Some(key)
+    }
+
+    fn mark_processed(&self, key: u64) {
+        trace!("SledMailboxStore: Message {} processed.", key);
+        if let Err(err) = self.tree.remove(key.to_be_bytes()) {
+            error!("SledMailboxStore: Couldn't remove message: {}", err);
+        }
+    }
+
+    fn drain(&self) -> Vec<Msg> {
+        let mut msgs = Vec::new();
+        for entry in self.tree.iter() {
+            let (key, value) = match entry {
+                Ok(entry) => entry,
+                Err(err) => {
+                    error!("SledMailboxStore: Couldn't read message: {}", err);
+                    break;
+                }
+            };
+
+            self.tree.remove(&key).ok();
+            match serde_json::from_slice::<M>(&value) {
+                Ok(msg) => msgs.push(Msg::tell(msg)),
+                Err(err) => error!("SledMailboxStore: Couldn't deserialize message: {}", err),
+            }
+        }
+
+        trace!("SledMailboxStore: Drained {} messages.", msgs.len());
+        msgs
+    }
+}
@@ -6,19 +6,34 @@
 //! * All message communication relies on at-most-once delivery guarantee.
 //! * Messages are not guaranteed to be ordered, all message's order is causal.
 //!
+use crate::broadcast::{Broadcast, Parent};
 use crate::callbacks::CallbackType;
+use crate::child::Init;
 use crate::children::Children;
-use crate::context::{BastionId, ContextState};
+use crate::children_ref::{DrainError, HealthStatus};
+use crate::context::{BastionId, ContextState, NIL_ID};
+use crate::dump::DumpedElement;
 use crate::envelope::{RefAddr, SignedMessage};
-use crate::supervisor::{SupervisionStrategy, Supervisor};
+use crate::logger;
+use crate::metrics::MetricsSnapshot;
+use crate::panic_handler;
+use crate::supervisor::{
+    InspectReport, Orphan, Supervised, SupervisedInfo, SupervisionStrategy, Supervisor,
+    SupervisorRef,
+};
 use async_mutex::Mutex;
 use futures::channel::oneshot::{self, Receiver};
+use futures::future::{self, Either};
+use futures_timer::Delay;
+use lazy_static::lazy_static;
 use std::any::{type_name, Any};
 use std::fmt::Debug;
 use std::future::Future;
 use std::pin::Pin;
-use std::sync::Arc;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Arc, RwLock};
 use std::task::{Context, Poll};
+use std::time::{Duration, Instant};
 use tracing::{debug, trace};
 
 /// A trait that any message sent needs to implement (it is
@@ -33,6 +48,17 @@ use tracing::{debug, trace};
 pub trait Message: Any + Send + Sync + Debug {}
 impl<T> Message for T where T: Any + Send + Sync + Debug {}
 
+pub(crate) type UnhandledMessageHook = Arc<dyn Fn(&Msg, &BastionId) + Send + Sync>;
+
+lazy_static! {
+    static ref UNHANDLED_MESSAGE_HOOK: RwLock<Option<UnhandledMessageHook>> = RwLock::new(None);
+}
+
+pub(crate) fn set_unhandled_message_hook(hook: UnhandledMessageHook) {
+    // FIXME: panics
+    *UNHANDLED_MESSAGE_HOOK.write().unwrap() = Some(hook);
+}
+
 #[derive(Debug)]
 #[doc(hidden)]
 pub struct AnswerSender(oneshot::Sender<SignedMessage>);
@@ -115,6 +141,74 @@ pub struct AnswerSender(oneshot::Sender<SignedMessage>);
 /// [`msg!`]: macro.msg.html
 pub struct Answer(Receiver<SignedMessage>);
 
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+/// The reason why an answer to a message "asked" with a
+/// timeout (see [`Answer::timeout`] and
+/// [`ChildrenRef::ask_timeout`]) couldn't be received.
+///
+/// [`Answer::timeout`]: struct.Answer.html#method.timeout
+/// [`ChildrenRef::ask_timeout`]: ../children_ref/struct.ChildrenRef.html#method.ask_timeout
+pub enum AskError {
+    /// The answer wasn't received before the timeout elapsed.
+    Timeout,
+    /// The recipient stopped (or dropped the message) without
+    /// answering.
+    RecipientDead,
+    /// The message couldn't be sent.
+    SendFailed,
+}
+
+#[derive(Debug)]
+/// A [`Future`] returned when successfully broadcasting a
+/// message using [`ChildrenRef::broadcast_counted`] or
+/// [`SupervisorRef::broadcast_counted`] and which resolves to
+/// the number of children group elements' mailboxes the
+/// message was enqueued into.
+///
+/// # Example
+///
+/// ```rust
+/// # use bastion::prelude::*;
+/// #
+/// # fn main() {
+///     # Bastion::init();
+///     # Bastion::start();
+/// let children_ref = Bastion::children(|children| {
+///     children
+///         .with_redundancy(4)
+///         .with_exec(|ctx: BastionContext| {
+///             async move {
+///                 loop {
+///                     ctx.recv().await?;
+///                 }
+///             }
+///         })
+/// }).expect("Couldn't create the children group.");
+///
+/// let recipients: Recipients = children_ref.broadcast_counted("A message containing data.");
+/// assert_eq!(run!(recipients), 4);
+///     #
+///     # Bastion::stop();
+///     # Bastion::block_until_stopped();
+/// # }
+/// ```
+///
+/// [`Future`]: https://doc.rust-lang.org/std/future/trait.Future.html
+/// [`ChildrenRef::broadcast_counted`]: ../children_ref/struct.ChildrenRef.html#method.broadcast_counted
+/// [`SupervisorRef::broadcast_counted`]: ../supervisor/struct.SupervisorRef.html#method.broadcast_counted
+pub struct Recipients(Receiver<usize>);
+
+// Counts the mailboxes a message was enqueued into while it is
+// forwarded through the supervision tree, each forwarded copy
+// of the message holding a reference to it. The count is sent
+// to the `Recipients` once every copy has been forwarded or
+// dropped.
+#[derive(Debug)]
+pub(crate) struct RecipientCounter {
+    reached: AtomicUsize,
+    sender: Option<oneshot::Sender<usize>>,
+}
+
 #[derive(Debug)]
 /// A message returned by [`BastionContext::recv`] or
 /// [`BastionContext::try_recv`] that should be passed to the
@@ -174,7 +268,9 @@ pub struct Answer(Receiver<SignedMessage>);
 /// [`BastionContext::recv`]: context/struct.BastionContext.html#method.recv
 /// [`BastionContext::try_recv`]: context/struct.BastionContext.html#method.try_recv
 /// [`msg!`]: macro.msg.html
-pub struct Msg(MsgInner);
+// The type name of the message is kept for diagnostics (see
+// `Children::with_processing_warning_threshold`).
+pub struct Msg(MsgInner, &'static str);
 
 #[derive(Debug)]
 enum MsgInner {
@@ -191,7 +287,14 @@ pub(crate) enum BastionMessage {
     Start,
     Stop,
     Kill,
+    Pause,
+    Resume,
     Deploy(Box<Deployment>),
+    // Sent back by a supervisor refusing a deployment because it
+    // reached its capacity (see `Supervisor::with_capacity`).
+    CapacityExceeded {
+        id: BastionId,
+    },
     Prune {
         id: BastionId,
     },
@@ -203,9 +306,47 @@ pub(crate) enum BastionMessage {
         state: Arc<Mutex<Pin<Box<ContextState>>>>,
     },
     Message(Msg),
+    CountedMessage {
+        msg: Msg,
+        counter: Arc<RecipientCounter>,
+    },
+    MailboxLens {
+        sender: oneshot::Sender<Vec<usize>>,
+    },
+    // Asks a children group to ping its elements (see
+    // `ChildrenRef::health_check`).
+    HealthCheck {
+        sender: oneshot::Sender<Vec<(BastionId, HealthStatus)>>,
+    },
+    // Asks a paused children group for the messages queued in the
+    // mailboxes of its elements (see `ChildrenRef::drain`).
+    Drain {
+        sender: oneshot::Sender<Result<Vec<Msg>, DrainError>>,
+    },
+    // Answered by an element as soon as it receives it.
+    Ping {
+        sender: oneshot::Sender<()>,
+    },
+    // An output emitted by an element, folded into its children
+    // group's accumulated state.
+    Emit(Msg),
+    Accumulator {
+        sender: oneshot::Sender<Msg>,
+    },
+    // An element spawned by one of its siblings, which the
+    // children group should launch.
+    AddChild {
+        init: Init,
+        bcast: Box<Broadcast>,
+    },
     RestartRequired {
         id: BastionId,
         parent_id: BastionId,
+        // Overrides the supervisor's strategy if set.
+        strategy: Option<SupervisionStrategy>,
+        // Whether the element panicked, rather than returned an
+        // error (see `RestartPolicy::OnError`).
+        panicked: bool,
     },
     FinishedChild {
         id: BastionId,
@@ -219,15 +360,68 @@ pub(crate) enum BastionMessage {
     DropChild {
         id: BastionId,
     },
+    RestartChild {
+        id: BastionId,
+    },
+    ListStopped {
+        sender: oneshot::Sender<Vec<SupervisedInfo>>,
+    },
+    Inspect {
+        reply: oneshot::Sender<InspectReport>,
+    },
+    // Asks a supervisor (or the system) for its state and the one
+    // of its subtree, to answer before the deadline (see
+    // `Bastion::dump_state`).
+    DumpState {
+        reply: oneshot::Sender<DumpedElement>,
+        deadline: Instant,
+    },
+    // Asks a supervisor or children group for the counters of its
+    // subtree (see `SupervisorRef::metrics`).
+    Metrics {
+        reply: oneshot::Sender<MetricsSnapshot>,
+    },
+    ResetChild {
+        id: BastionId,
+        state: Arc<Mutex<Pin<Box<ContextState>>>>,
+    },
     SetState {
         state: Arc<Mutex<Pin<Box<ContextState>>>>,
     },
     Stopped {
         id: BastionId,
     },
+    // An element that stopped because it waited for a message for
+    // longer than its group's idle timeout.
+    IdleStop {
+        id: BastionId,
+    },
     Faulted {
         id: BastionId,
     },
+    // The running children groups and supervisors of a faulted
+    // supervisor, which the supervisor receiving it should
+    // supervise from then on.
+    Adopt {
+        children: Vec<Orphan>,
+    },
+    // Tells an adopted children group or supervisor who its new
+    // parent is.
+    SetParent {
+        parent: Box<Parent>,
+    },
+    // Asks a supervisor to give one of its supervised elements to
+    // another supervisor (see `SupervisorRef::migrate`).
+    Migrate {
+        id: BastionId,
+        target: Box<SupervisorRef>,
+    },
+    // A children group or supervisor stopped by another supervisor
+    // to migrate it, which the supervisor receiving it should
+    // relaunch under the same identifier.
+    Relaunch {
+        supervised: Box<Supervised>,
+    },
 }
 
 #[derive(Debug)]
@@ -253,12 +447,20 @@ impl AnswerSender {
 impl Msg {
     pub(crate) fn broadcast<M: Message>(msg: M) -> Self {
         let inner = MsgInner::Broadcast(Arc::new(msg));
-        Msg(inner)
+        Msg(inner, type_name::<M>())
     }
 
-    pub(crate) fn tell<M: Message>(msg: M) -> Self {
+    /// Creates a new `Msg` as if `msg` was "told" (e.g. to
+    /// replay it from a [`MailboxStore`]).
+    ///
+    /// # Arguments
+    ///
+    /// * `msg` - The message to wrap.
+    ///
+    /// [`MailboxStore`]: ../mailbox_store/trait.MailboxStore.html
+    pub fn tell<M: Message>(msg: M) -> Self {
         let inner = MsgInner::Tell(Box::new(msg));
-        Msg(inner)
+        Msg(inner, type_name::<M>())
     }
 
     pub(crate) fn ask<M: Message>(msg: M) -> (Self, Answer) {
@@ -270,7 +472,7 @@ impl Msg {
         let sender = Some(sender);
         let inner = MsgInner::Ask { msg, sender };
 
-        (Msg(inner), answer)
+        (Msg(inner, type_name::<M>()), answer)
     }
 
     #[doc(hidden)]
@@ -322,6 +524,7 @@ impl Msg {
     #[doc(hidden)]
     pub fn downcast<M: Message>(self) -> Result<M, Self> {
         trace!("{:?}: Downcasting to {}.", self, type_name::<M>());
+        let type_name = self.1;
         match self.0 {
             MsgInner::Tell(msg) => {
                 if msg.is::<M>() {
@@ -329,7 +532,7 @@ impl Msg {
                     Ok(*msg.downcast().unwrap())
                 } else {
                     let inner = MsgInner::Tell(msg);
-                    Err(Msg(inner))
+                    Err(Msg(inner, type_name))
                 }
             }
             MsgInner::Ask { msg, sender } => {
@@ -338,7 +541,7 @@ impl Msg {
                     Ok(*msg.downcast().unwrap())
                 } else {
                     let inner = MsgInner::Ask { msg, sender };
-                    Err(Msg(inner))
+                    Err(Msg(inner, type_name))
                 }
             }
             _ => Err(self),
@@ -357,11 +560,61 @@ impl Msg {
         None
     }
 
+    #[doc(hidden)]
+    pub fn peek<M: Message>(&self) -> Option<&M> {
+        trace!("{:?}: Peeking as {}.", self, type_name::<M>());
+        match &self.0 {
+            MsgInner::Tell(msg) => msg.downcast_ref(),
+            MsgInner::Ask { msg, .. } => msg.downcast_ref(),
+            MsgInner::Broadcast(msg) => msg.downcast_ref(),
+        }
+    }
+
+    /// Reports this message as unhandled, logging it with a
+    /// `tracing` event at the `DEBUG` level, calling the hook set
+    /// with [`Bastion::set_unhandled_message_hook`], if any, and
+    /// notifying the logger set with [`Bastion::with_logger`].
+    ///
+    /// This is automatically called by the [`msg!`] macro when the
+    /// message doesn't match any of its cases but the default one.
+    ///
+    /// [`Bastion::set_unhandled_message_hook`]: ../struct.Bastion.html#method.set_unhandled_message_hook
+    /// [`Bastion::with_logger`]: ../struct.Bastion.html#method.with_logger
+    /// [`msg!`]: ../macro.msg.html
+    pub fn send_error_log(&self) {
+        let id = panic_handler::current_child().unwrap_or(NIL_ID);
+        debug!("Child({}): Received an unhandled message: {:?}", id, self);
+
+        let hook = UNHANDLED_MESSAGE_HOOK
+            .read()
+            .ok()
+            .and_then(|hook| hook.clone());
+        if let Some(hook) = hook {
+            hook(self, &id);
+        }
+
+        logger::with_logger(|logger| logger.log_message_drop(&id, self));
+    }
+
+    /// Returns the name of the type of the wrapped message.
+    pub(crate) fn type_name(&self) -> &'static str {
+        self.1
+    }
+
+    /// Returns whether both messages are copies of the same
+    /// broadcasted message.
+    pub(crate) fn same_broadcast(&self, other: &Msg) -> bool {
+        match (&self.0, &other.0) {
+            (MsgInner::Broadcast(msg), MsgInner::Broadcast(other)) => Arc::ptr_eq(msg, other),
+            _ => false,
+        }
+    }
+
     pub(crate) fn try_clone(&self) -> Option<Self> {
         trace!("{:?}: Trying to clone.", self);
         if let MsgInner::Broadcast(msg) = &self.0 {
             let inner = MsgInner::Broadcast(msg.clone());
-            Some(Msg(inner))
+            Some(Msg(inner, self.1))
         } else {
             None
         }
@@ -369,18 +622,19 @@ impl Msg {
 
     pub(crate) fn try_unwrap<M: Message>(self) -> Result<M, Self> {
         debug!("{:?}: Trying to unwrap.", self);
+        let type_name = self.1;
         if let MsgInner::Broadcast(msg) = self.0 {
             match msg.downcast() {
                 Ok(msg) => match Arc::try_unwrap(msg) {
                     Ok(msg) => Ok(msg),
                     Err(msg) => {
                         let inner = MsgInner::Broadcast(msg);
-                        Err(Msg(inner))
+                        Err(Msg(inner, type_name))
                     }
                 },
                 Err(msg) => {
                     let inner = MsgInner::Broadcast(msg);
-                    Err(Msg(inner))
+                    Err(Msg(inner, type_name))
                 }
             }
         } else {
@@ -402,6 +656,14 @@ impl BastionMessage {
         BastionMessage::Kill
     }
 
+    pub(crate) fn pause() -> Self {
+        BastionMessage::Pause
+    }
+
+    pub(crate) fn resume() -> Self {
+        BastionMessage::Resume
+    }
+
     pub(crate) fn deploy_supervisor(supervisor: Supervisor) -> Self {
         let deployment = Deployment::Supervisor(supervisor);
 
@@ -414,6 +676,10 @@ impl BastionMessage {
         BastionMessage::Deploy(deployment.into())
     }
 
+    pub(crate) fn capacity_exceeded(id: BastionId) -> Self {
+        BastionMessage::CapacityExceeded { id }
+    }
+
     pub(crate) fn prune(id: BastionId) -> Self {
         BastionMessage::Prune { id }
     }
@@ -443,6 +709,43 @@ impl BastionMessage {
         BastionMessage::Message(msg)
     }
 
+    pub(crate) fn broadcast_counted<M: Message>(msg: M) -> (Self, Recipients) {
+        let msg = Msg::broadcast(msg);
+        let (counter, recipients) = RecipientCounter::new();
+        let counter = Arc::new(counter);
+        (BastionMessage::CountedMessage { msg, counter }, recipients)
+    }
+
+    pub(crate) fn mailbox_lens(sender: oneshot::Sender<Vec<usize>>) -> Self {
+        BastionMessage::MailboxLens { sender }
+    }
+
+    pub(crate) fn emit<M: Message>(output: M) -> Self {
+        let output = Msg::tell(output);
+        BastionMessage::Emit(output)
+    }
+
+    pub(crate) fn health_check(sender: oneshot::Sender<Vec<(BastionId, HealthStatus)>>) -> Self {
+        BastionMessage::HealthCheck { sender }
+    }
+
+    pub(crate) fn drain(sender: oneshot::Sender<Result<Vec<Msg>, DrainError>>) -> Self {
+        BastionMessage::Drain { sender }
+    }
+
+    pub(crate) fn ping(sender: oneshot::Sender<()>) -> Self {
+        BastionMessage::Ping { sender }
+    }
+
+    pub(crate) fn accumulator(sender: oneshot::Sender<Msg>) -> Self {
+        BastionMessage::Accumulator { sender }
+    }
+
+    pub(crate) fn add_child(init: Init, bcast: Broadcast) -> Self {
+        let bcast = Box::new(bcast);
+        BastionMessage::AddChild { init, bcast }
+    }
+
     pub(crate) fn tell<M: Message>(msg: M) -> Self {
         let msg = Msg::tell(msg);
         BastionMessage::Message(msg)
@@ -453,8 +756,18 @@ impl BastionMessage {
         (BastionMessage::Message(msg), answer)
     }
 
-    pub(crate) fn restart_required(id: BastionId, parent_id: BastionId) -> Self {
-        BastionMessage::RestartRequired { id, parent_id }
+    pub(crate) fn restart_required(
+        id: BastionId,
+        parent_id: BastionId,
+        strategy: Option<SupervisionStrategy>,
+        panicked: bool,
+    ) -> Self {
+        BastionMessage::RestartRequired {
+            id,
+            parent_id,
+            strategy,
+            panicked,
+        }
     }
 
     pub(crate) fn finished_child(id: BastionId, parent_id: BastionId) -> Self {
@@ -473,6 +786,30 @@ impl BastionMessage {
         BastionMessage::DropChild { id }
     }
 
+    pub(crate) fn restart_child(id: BastionId) -> Self {
+        BastionMessage::RestartChild { id }
+    }
+
+    pub(crate) fn list_stopped(sender: oneshot::Sender<Vec<SupervisedInfo>>) -> Self {
+        BastionMessage::ListStopped { sender }
+    }
+
+    pub(crate) fn inspect(reply: oneshot::Sender<InspectReport>) -> Self {
+        BastionMessage::Inspect { reply }
+    }
+
+    pub(crate) fn dump_state(reply: oneshot::Sender<DumpedElement>, deadline: Instant) -> Self {
+        BastionMessage::DumpState { reply, deadline }
+    }
+
+    pub(crate) fn metrics(reply: oneshot::Sender<MetricsSnapshot>) -> Self {
+        BastionMessage::Metrics { reply }
+    }
+
+    pub(crate) fn reset_child(id: BastionId, state: Arc<Mutex<Pin<Box<ContextState>>>>) -> Self {
+        BastionMessage::ResetChild { id, state }
+    }
+
     pub(crate) fn set_state(state: Arc<Mutex<Pin<Box<ContextState>>>>) -> Self {
         BastionMessage::SetState { state }
     }
@@ -481,18 +818,50 @@ impl BastionMessage {
         BastionMessage::Stopped { id }
     }
 
+    pub(crate) fn idle_stop(id: BastionId) -> Self {
+        BastionMessage::IdleStop { id }
+    }
+
     pub(crate) fn faulted(id: BastionId) -> Self {
         BastionMessage::Faulted { id }
     }
 
+    pub(crate) fn adopt(children: Vec<Orphan>) -> Self {
+        BastionMessage::Adopt { children }
+    }
+
+    pub(crate) fn set_parent(parent: Parent) -> Self {
+        BastionMessage::SetParent {
+            parent: Box::new(parent),
+        }
+    }
+
+    pub(crate) fn migrate(id: BastionId, target: SupervisorRef) -> Self {
+        BastionMessage::Migrate {
+            id,
+            target: Box::new(target),
+        }
+    }
+
+    pub(crate) fn relaunch(supervised: Supervised) -> Self {
+        BastionMessage::Relaunch {
+            supervised: Box::new(supervised),
+        }
+    }
+
     pub(crate) fn try_clone(&self) -> Option<Self> {
         trace!("{:?}: Trying to clone.", self);
         let clone = match self {
             BastionMessage::Start => BastionMessage::start(),
             BastionMessage::Stop => BastionMessage::stop(),
             BastionMessage::Kill => BastionMessage::kill(),
+            BastionMessage::Pause => BastionMessage::pause(),
+            BastionMessage::Resume => BastionMessage::resume(),
             // FIXME
             BastionMessage::Deploy(_) => unimplemented!(),
+            BastionMessage::CapacityExceeded { id } => {
+                BastionMessage::capacity_exceeded(id.clone())
+            }
             BastionMessage::Prune { id } => BastionMessage::prune(id.clone()),
             BastionMessage::SuperviseWith(strategy) => {
                 BastionMessage::supervise_with(strategy.clone())
@@ -510,9 +879,28 @@ impl BastionMessage {
                 state.clone(),
             ),
             BastionMessage::Message(msg) => BastionMessage::Message(msg.try_clone()?),
-            BastionMessage::RestartRequired { id, parent_id } => {
-                BastionMessage::restart_required(id.clone(), parent_id.clone())
-            }
+            BastionMessage::CountedMessage { msg, counter } => BastionMessage::CountedMessage {
+                msg: msg.try_clone()?,
+                counter: counter.clone(),
+            },
+            BastionMessage::MailboxLens { .. } => return None,
+            BastionMessage::HealthCheck { .. } => return None,
+            BastionMessage::Drain { .. } => return None,
+            BastionMessage::Ping { .. } => return None,
+            BastionMessage::Emit(output) => BastionMessage::Emit(output.try_clone()?),
+            BastionMessage::Accumulator { .. } => return None,
+            BastionMessage::AddChild { .. } => return None,
+            BastionMessage::RestartRequired {
+                id,
+                parent_id,
+                strategy,
+                panicked,
+            } => BastionMessage::restart_required(
+                id.clone(),
+                parent_id.clone(),
+                strategy.clone(),
+                *panicked,
+            ),
             BastionMessage::FinishedChild { id, parent_id } => {
                 BastionMessage::finished_child(id.clone(), parent_id.clone())
             }
@@ -521,19 +909,128 @@ impl BastionMessage {
                 BastionMessage::restore_child(id.clone(), state.clone())
             }
             BastionMessage::DropChild { id } => BastionMessage::drop_child(id.clone()),
+            BastionMessage::RestartChild { id } => BastionMessage::restart_child(id.clone()),
+            BastionMessage::ListStopped { .. } => return None,
+            BastionMessage::Inspect { .. } => return None,
+            BastionMessage::DumpState { .. } => return None,
+            BastionMessage::Metrics { .. } => return None,
+            BastionMessage::ResetChild { id, state } => {
+                BastionMessage::reset_child(id.clone(), state.clone())
+            }
             BastionMessage::SetState { state } => BastionMessage::set_state(state.clone()),
             BastionMessage::Stopped { id } => BastionMessage::stopped(id.clone()),
+            BastionMessage::IdleStop { id } => BastionMessage::idle_stop(id.clone()),
             BastionMessage::Faulted { id } => BastionMessage::faulted(id.clone()),
+            BastionMessage::Adopt { .. } => return None,
+            BastionMessage::SetParent { parent } => BastionMessage::set_parent(*parent.clone()),
+            BastionMessage::Migrate { id, target } => {
+                BastionMessage::migrate(id.clone(), *target.clone())
+            }
+            BastionMessage::Relaunch { .. } => return None,
         };
 
         Some(clone)
     }
 
     pub(crate) fn into_msg<M: Message>(self) -> Option<M> {
-        if let BastionMessage::Message(msg) = self {
-            msg.try_unwrap().ok()
-        } else {
-            None
+        match self {
+            BastionMessage::Message(msg)
+            | BastionMessage::CountedMessage { msg, .. }
+            | BastionMessage::Emit(msg) => msg.try_unwrap().ok(),
+            _ => None,
+        }
+    }
+}
+
+impl RecipientCounter {
+    fn new() -> (Self, Recipients) {
+        let (sender, recipients) = oneshot::channel();
+        let counter = RecipientCounter {
+            reached: AtomicUsize::new(0),
+            sender: Some(sender),
+        };
+
+        (counter, Recipients(recipients))
+    }
+
+    pub(crate) fn add(&self, reached: usize) {
+        self.reached.fetch_add(reached, Ordering::SeqCst);
+    }
+}
+
+impl Drop for RecipientCounter {
+    fn drop(&mut self) {
+        if let Some(sender) = self.sender.take() {
+            // The `Recipients` might have been dropped.
+            sender.send(*self.reached.get_mut()).ok();
+        }
+    }
+}
+
+impl Future for Recipients {
+    type Output = usize;
+
+    fn poll(self: Pin<&mut Self>, ctx: &mut Context) -> Poll<Self::Output> {
+        debug!("{:?}: Polling.", self);
+        // NOTE: the counter always sends the count before being
+        //      dropped.
+        Pin::new(&mut self.get_mut().0)
+            .poll(ctx)
+            .map(|reached| reached.unwrap_or(0))
+    }
+}
+
+impl Answer {
+    /// Makes this `Answer` resolve to `Err(AskError::Timeout)` if
+    /// the answer isn't received before `timeout` elapsed, and to
+    /// `Err(AskError::RecipientDead)` if the recipient stopped
+    /// without answering.
+    ///
+    /// An answer sent once the timeout elapsed is dropped.
+    ///
+    /// # Arguments
+    ///
+    /// * `timeout` - How long to wait for the answer.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use bastion::prelude::*;
+    /// # use std::time::Duration;
+    /// #
+    /// # Bastion::init();
+    /// # Bastion::start();
+    /// #
+    /// # let children_ref = Bastion::children(|children| children).unwrap();
+    /// # let child_ref = children_ref.elems()[0].clone();
+    /// let answer = child_ref
+    ///     .ask_anonymously("A message containing data (ask).")
+    ///     .expect("Couldn't send the message.");
+    ///
+    /// # run!(async {
+    /// match answer.timeout(Duration::from_millis(100)).await {
+    ///     Ok(answer) => {
+    ///         // Handle the answer...
+    ///     }
+    ///     Err(AskError::Timeout) => {
+    ///         // The child is too busy...
+    ///     }
+    ///     Err(_) => {
+    ///         // The child stopped...
+    ///     }
+    /// }
+    /// # });
+    /// #
+    /// # Bastion::stop();
+    /// # Bastion::block_until_stopped();
+    /// ```
+    pub async fn timeout(self, timeout: Duration) -> Result<SignedMessage, AskError> {
+        match future::select(self, Delay::new(timeout)).await {
+            Either::Left((Ok(answer), _)) => Ok(answer),
+            Either::Left((Err(()), _)) => Err(AskError::RecipientDead),
+            // Dropping the receiver makes late answers fail to be
+            // sent instead of being kept around.
+            Either::Right(_) => Err(AskError::Timeout),
         }
     }
 }
@@ -558,7 +1055,16 @@ impl Future for Answer {
 /// - a colon
 /// - a type that the message must be of to match this case
 ///   (note that if the message was broadcasted, the actual
-///   type of the variable will be a reference to this type)
+///   type of the variable will be a reference to this type),
+///   or several types separated by `|` (in which case the
+///   code of the case is repeated for each type and thus must
+///   be valid for all of them, eg. by only using methods of a
+///   trait they all implement)
+/// - an optional guard (`if` followed by a condition) which
+///   will make the case only match if the condition is true
+///   (note that in the condition, the variable is always a
+///   reference to the message) and otherwise fall through to
+///   the next cases
 /// - an arrow (`=>`) with an optional bang (`!`) between
 ///   the equal and greater-than signs which will make the
 ///   case only match if the message can be answered
@@ -571,7 +1077,9 @@ impl Future for Answer {
 ///
 /// A default case is required, which is defined in the same
 /// way as any other case but with its type set as `_` (note
-/// that it doesn't has the optional `ref` or `=!>`).
+/// that it doesn't has the optional `ref` or `=!>`). Messages
+/// reaching the default case are reported as unhandled using
+/// [`Msg::send_error_log`] before its code gets executed.
 ///
 /// # Example
 ///
@@ -614,6 +1122,12 @@ impl Future for Answer {
 ///                         // ...and eventually answer to it...
 ///                         answer!(ctx, "An answer to the message.");
 ///                     };
+///                     // We match `u8`s and `u16`s "told" to this child
+///                     // which are even...
+///                     msg: u8 | u16 if *msg % 2 == 0 => {
+///                         let _half = msg / 2;
+///                         // Handle the message...
+///                     };
 ///                     // We are only broadcasting, "telling" and "asking" a
 ///                     // `&'static str` in this example, so we know that this won't
 ///                     // happen...
@@ -632,6 +1146,7 @@ impl Future for Answer {
 /// ```
 ///
 /// [`Msg`]: children/struct.Msg.html
+/// [`Msg::send_error_log`]: message/struct.Msg.html#method.send_error_log
 /// [`BastionContext::recv`]: context/struct.BastionContext.html#method.recv
 /// [`BastionContext::try_recv`]: context/struct.BastionContext.html#method.try_recv
 macro_rules! msg {
@@ -641,72 +1156,19 @@ macro_rules! msg {
 
     (@internal
         $msg:expr,
-        ($($bvar:ident, $bty:ty, $bhandle:expr,)*),
-        ($($tvar:ident, $tty:ty, $thandle:expr,)*),
-        ($($avar:ident, $aty:ty, $ahandle:expr,)*),
-        ref $var:ident: $ty:ty => $handle:expr;
-        $($rest:tt)+
-    ) => {
-        msg!(@internal $msg,
-            ($($bvar, $bty, $bhandle,)* $var, $ty, $handle,),
-            ($($tvar, $tty, $thandle,)*),
-            ($($avar, $aty, $ahandle,)*),
-            $($rest)+
-        )
-    };
-
-    (@internal
-        $msg:expr,
-        ($($bvar:ident, $bty:ty, $bhandle:expr,)*),
-        ($($tvar:ident, $tty:ty, $thandle:expr,)*),
-        ($($avar:ident, $aty:ty, $ahandle:expr,)*),
-        $var:ident: $ty:ty => $handle:expr;
-        $($rest:tt)+
-    ) => {
-        msg!(@internal $msg,
-            ($($bvar, $bty, $bhandle,)*),
-            ($($tvar, $tty, $thandle,)* $var, $ty, $handle,),
-            ($($avar, $aty, $ahandle,)*),
-            $($rest)+
-        )
-    };
-
-    (@internal
-        $msg:expr,
-        ($($bvar:ident, $bty:ty, $bhandle:expr,)*),
-        ($($tvar:ident, $tty:ty, $thandle:expr,)*),
-        ($($avar:ident, $aty:ty, $ahandle:expr,)*),
-        $var:ident: $ty:ty =!> $handle:expr;
-        $($rest:tt)+
-    ) => {
-        msg!(@internal $msg,
-            ($($bvar, $bty, $bhandle,)*),
-            ($($tvar, $tty, $thandle,)*),
-            ($($avar, $aty, $ahandle,)* $var, $ty, $handle,),
-            $($rest)+
-        )
-    };
-
-    (@internal
-        $msg:expr,
-        ($($bvar:ident, $bty:ty, $bhandle:expr,)*),
-        ($($tvar:ident, $tty:ty, $thandle:expr,)*),
-        ($($avar:ident, $aty:ty, $ahandle:expr,)*),
+        $bcast:tt,
+        $tell:tt,
+        $ask:tt,
         _: _ => $handle:expr;
     ) => {
-        msg!(@internal $msg,
-            ($($bvar, $bty, $bhandle,)*),
-            ($($tvar, $tty, $thandle,)*),
-            ($($avar, $aty, $ahandle,)*),
-            msg: _ => $handle;
-        )
+        msg!(@internal $msg, $bcast, $tell, $ask, msg: _ => $handle;)
     };
 
     (@internal
         $msg:expr,
-        ($($bvar:ident, $bty:ty, $bhandle:expr,)*),
-        ($($tvar:ident, $tty:ty, $thandle:expr,)*),
-        ($($avar:ident, $aty:ty, $ahandle:expr,)*),
+        ($($bvar:ident, $bty:ty, $bguard:expr, $bhandle:expr,)*),
+        ($($tvar:ident, $tty:ty, $tguard:expr, $thandle:expr,)*),
+        ($($avar:ident, $aty:ty, $aguard:expr, $ahandle:expr,)*),
         $var:ident: _ => $handle:expr;
     ) => { {
         let mut signed = $msg;
@@ -725,12 +1187,17 @@ macro_rules! msg {
                 unreachable!();
             }
             $(
-                else if $var.is::<$bty>() {
+                else if $var.is::<$bty>() && {
+                    #[allow(unused_variables)]
+                    let $bvar: &$bty = $var.peek::<$bty>().unwrap();
+                    $bguard
+                } {
                     let $bvar = &*$var.downcast_ref::<$bty>().unwrap();
                     { $bhandle }
                 }
             )*
             else {
+                $var.send_error_log();
                 { $handle }
             }
         } else if sender.is_some() {
@@ -749,12 +1216,17 @@ macro_rules! msg {
                 unreachable!();
             }
             $(
-                else if $var.is::<$aty>() {
+                else if $var.is::<$aty>() && {
+                    #[allow(unused_variables)]
+                    let $avar: &$aty = $var.peek::<$aty>().unwrap();
+                    $aguard
+                } {
                     let $avar = $var.downcast::<$aty>().unwrap();
                     { $ahandle }
                 }
             )*
             else {
+                $var.send_error_log();
                 { $handle }
             }
         } else {
@@ -762,16 +1234,205 @@ macro_rules! msg {
                 unreachable!();
             }
             $(
-                else if $var.is::<$tty>() {
+                else if $var.is::<$tty>() && {
+                    #[allow(unused_variables)]
+                    let $tvar: &$tty = $var.peek::<$tty>().unwrap();
+                    $tguard
+                } {
                     let $tvar = $var.downcast::<$tty>().unwrap();
                     { $thandle }
                 }
             )*
             else {
+                $var.send_error_log();
                 { $handle }
             }
         }
     } };
+
+    // Cases without a guard can directly be parsed...
+    (@internal
+        $msg:expr,
+        $bcast:tt,
+        $tell:tt,
+        $ask:tt,
+        ref $var:ident: $($ty:ty)|+ => $handle:expr;
+        $($rest:tt)+
+    ) => {
+        msg!(@case $msg, $bcast, $tell, $ask, (ref $var), ($($ty)|+), (true), =>, $handle, $($rest)+)
+    };
+
+    (@internal
+        $msg:expr,
+        $bcast:tt,
+        $tell:tt,
+        $ask:tt,
+        $var:ident: $($ty:ty)|+ => $handle:expr;
+        $($rest:tt)+
+    ) => {
+        msg!(@case $msg, $bcast, $tell, $ask, ($var), ($($ty)|+), (true), =>, $handle, $($rest)+)
+    };
+
+    (@internal
+        $msg:expr,
+        $bcast:tt,
+        $tell:tt,
+        $ask:tt,
+        $var:ident: $($ty:ty)|+ =!> $handle:expr;
+        $($rest:tt)+
+    ) => {
+        msg!(@case $msg, $bcast, $tell, $ask, ($var), ($($ty)|+), (true), =!>, $handle, $($rest)+)
+    };
+
+    // ...while cases with a guard need their types and guard to be
+    // collected token by token (because a type can't be followed by
+    // an `if`).
+    (@internal
+        $msg:expr,
+        $bcast:tt,
+        $tell:tt,
+        $ask:tt,
+        ref $var:ident: $($rest:tt)+
+    ) => {
+        msg!(@types $msg, $bcast, $tell, $ask, (ref $var), (), $($rest)+)
+    };
+
+    (@internal
+        $msg:expr,
+        $bcast:tt,
+        $tell:tt,
+        $ask:tt,
+        $var:ident: $($rest:tt)+
+    ) => {
+        msg!(@types $msg, $bcast, $tell, $ask, ($var), (), $($rest)+)
+    };
+
+    (@types
+        $msg:expr,
+        $bcast:tt,
+        $tell:tt,
+        $ask:tt,
+        $var:tt,
+        ($($ty:tt)+),
+        if $($rest:tt)+
+    ) => {
+        msg!(@guard $msg, $bcast, $tell, $ask, $var, ($($ty)+), (), $($rest)+)
+    };
+
+    (@types
+        $msg:expr,
+        $bcast:tt,
+        $tell:tt,
+        $ask:tt,
+        $var:tt,
+        ($($ty:tt)*),
+        $next:tt $($rest:tt)+
+    ) => {
+        msg!(@types $msg, $bcast, $tell, $ask, $var, ($($ty)* $next), $($rest)+)
+    };
+
+    (@guard
+        $msg:expr,
+        $bcast:tt,
+        $tell:tt,
+        $ask:tt,
+        $var:tt,
+        $ty:tt,
+        ($($guard:tt)+),
+        => $handle:expr;
+        $($rest:tt)+
+    ) => {
+        msg!(@case $msg, $bcast, $tell, $ask, $var, $ty, ($($guard)+), =>, $handle, $($rest)+)
+    };
+
+    (@guard
+        $msg:expr,
+        $bcast:tt,
+        $tell:tt,
+        $ask:tt,
+        $var:tt,
+        $ty:tt,
+        ($($guard:tt)+),
+        =!> $handle:expr;
+        $($rest:tt)+
+    ) => {
+        msg!(@case $msg, $bcast, $tell, $ask, $var, $ty, ($($guard)+), =!>, $handle, $($rest)+)
+    };
+
+    (@guard
+        $msg:expr,
+        $bcast:tt,
+        $tell:tt,
+        $ask:tt,
+        $var:tt,
+        $ty:tt,
+        ($($guard:tt)*),
+        $next:tt $($rest:tt)+
+    ) => {
+        msg!(@guard $msg, $bcast, $tell, $ask, $var, $ty, ($($guard)* $next), $($rest)+)
+    };
+
+    // Each type of a case is then registered as its own case, sharing
+    // the same variable name, guard and code.
+    (@case
+        $msg:expr,
+        ($($bcast:tt)*),
+        $tell:tt,
+        $ask:tt,
+        (ref $var:ident),
+        ($($ty:ty)|+),
+        ($guard:expr),
+        =>,
+        $handle:expr,
+        $($rest:tt)+
+    ) => {
+        msg!(@internal $msg,
+            ($($bcast)* $($var, $ty, $guard, $handle,)+),
+            $tell,
+            $ask,
+            $($rest)+
+        )
+    };
+
+    (@case
+        $msg:expr,
+        $bcast:tt,
+        ($($tell:tt)*),
+        $ask:tt,
+        ($var:ident),
+        ($($ty:ty)|+),
+        ($guard:expr),
+        =>,
+        $handle:expr,
+        $($rest:tt)+
+    ) => {
+        msg!(@internal $msg,
+            $bcast,
+            ($($tell)* $($var, $ty, $guard, $handle,)+),
+            $ask,
+            $($rest)+
+        )
+    };
+
+    (@case
+        $msg:expr,
+        $bcast:tt,
+        $tell:tt,
+        ($($ask:tt)*),
+        ($var:ident),
+        ($($ty:ty)|+),
+        ($guard:expr),
+        =!>,
+        $handle:expr,
+        $($rest:tt)+
+    ) => {
+        msg!(@internal $msg,
+            $bcast,
+            $tell,
+            ($($ask)* $($var, $ty, $guard, $handle,)+),
+            $($rest)+
+        )
+    };
 }
 
 #[macro_export]
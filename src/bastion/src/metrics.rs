@@ -0,0 +1,117 @@
+//!
+//! Counters kept by each supervisor and children group, gathered
+//! in a [`MetricsSnapshot`] (see [`Bastion::metrics`]).
+//!
+//! [`MetricsSnapshot`]: struct.MetricsSnapshot.html
+//! [`Bastion::metrics`]: ../struct.Bastion.html#method.metrics
+use crate::context::BastionId;
+
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+/// The counters of the supervisors and children groups of a
+/// supervision tree, as returned by [`Bastion::metrics`] or
+/// [`SupervisorRef::metrics`].
+///
+/// This is plain data, meant to be converted by users to the
+/// format their metrics system expects (e.g. Prometheus or
+/// statsd).
+///
+/// [`Bastion::metrics`]: ../struct.Bastion.html#method.metrics
+/// [`SupervisorRef::metrics`]: ../supervisor/struct.SupervisorRef.html#method.metrics
+pub struct MetricsSnapshot {
+    /// The counters of each running supervisor.
+    pub supervisors: Vec<SupervisorMetrics>,
+    /// The counters of each running children group.
+    pub children: Vec<ChildrenMetrics>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+/// The counters of a supervisor (see [`MetricsSnapshot`]), kept
+/// across its restarts.
+///
+/// [`MetricsSnapshot`]: struct.MetricsSnapshot.html
+pub struct SupervisorMetrics {
+    /// The identifier of the supervisor.
+    pub id: BastionId,
+    /// The number of children groups and supervisors deployed
+    /// under the supervisor.
+    pub deploys: u64,
+    /// The number of times one of its supervised elements faulted.
+    pub faults: u64,
+    /// The number of times it recovered from a fault using
+    /// [`SupervisionStrategy::OneForOne`].
+    ///
+    /// [`SupervisionStrategy::OneForOne`]: ../supervisor/enum.SupervisionStrategy.html#variant.OneForOne
+    pub one_for_one_restarts: u64,
+    /// The number of times it recovered from a fault using
+    /// [`SupervisionStrategy::OneForAll`].
+    ///
+    /// [`SupervisionStrategy::OneForAll`]: ../supervisor/enum.SupervisionStrategy.html#variant.OneForAll
+    pub one_for_all_restarts: u64,
+    /// The number of times it recovered from a fault using
+    /// [`SupervisionStrategy::RestForOne`].
+    ///
+    /// [`SupervisionStrategy::RestForOne`]: ../supervisor/enum.SupervisionStrategy.html#variant.RestForOne
+    pub rest_for_one_restarts: u64,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+/// The counters of a children group (see [`MetricsSnapshot`]),
+/// summed over all of its elements.
+///
+/// [`MetricsSnapshot`]: struct.MetricsSnapshot.html
+pub struct ChildrenMetrics {
+    /// The identifier of the children group.
+    pub id: BastionId,
+    /// The name of the children group, if it has one (see
+    /// [`Children::with_name`]).
+    ///
+    /// [`Children::with_name`]: ../children/struct.Children.html#method.with_name
+    pub name: Option<String>,
+    /// The number of messages delivered to the mailboxes of its
+    /// elements.
+    pub received: u64,
+    /// The number of messages its elements retrieved from their
+    /// mailboxes (the unstashed ones being counted again).
+    pub processed: u64,
+    /// The number of times its elements were restarted.
+    pub restarts: u64,
+}
+
+impl MetricsSnapshot {
+    /// Returns the counters of the supervisor with the given
+    /// identifier, if it is part of the snapshot.
+    ///
+    /// # Arguments
+    ///
+    /// * `id` - The identifier of the supervisor.
+    pub fn supervisor(&self, id: &BastionId) -> Option<&SupervisorMetrics> {
+        self.supervisors.iter().find(|metrics| &metrics.id == id)
+    }
+
+    /// Returns the counters of the children group with the given
+    /// identifier, if it is part of the snapshot.
+    ///
+    /// # Arguments
+    ///
+    /// * `id` - The identifier of the children group.
+    pub fn children(&self, id: &BastionId) -> Option<&ChildrenMetrics> {
+        self.children.iter().find(|metrics| &metrics.id == id)
+    }
+
+    /// Returns the counters of the children group with the given
+    /// name, if it is part of the snapshot.
+    ///
+    /// # Arguments
+    ///
+    /// * `name` - The name of the children group.
+    pub fn children_named(&self, name: &str) -> Option<&ChildrenMetrics> {
+        self.children
+            .iter()
+            .find(|metrics| metrics.name.as_deref() == Some(name))
+    }
+
+    pub(crate) fn merge(&mut self, other: MetricsSnapshot) {
+        self.supervisors.extend(other.supervisors);
+        self.children.extend(other.children);
+    }
+}
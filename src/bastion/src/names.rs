@@ -0,0 +1,70 @@
+//!
+//! Registry of the named children groups (see
+//! [`Children::with_name`]), which allows finding them without
+//! knowing where they live in the supervision tree.
+//!
+//! A group stays registered while it is being restarted by its
+//! supervisor (until its restarted incarnation replaces it), so
+//! lookups racing a restart return the faulted group's reference,
+//! sending to which fails (returning the message) instead of
+//! panicking.
+//!
+//! [`Children::with_name`]: ../children/struct.Children.html#method.with_name
+use crate::children_ref::ChildrenRef;
+use crate::context::BastionId;
+use fxhash::FxHashMap;
+use std::sync::Mutex;
+use tracing::{debug, warn};
+
+#[derive(Debug, Default)]
+pub(crate) struct NameRegistry {
+    // Each name associated with the running children group
+    // that was given it.
+    groups: Mutex<FxHashMap<String, ChildrenRef>>,
+}
+
+impl NameRegistry {
+    pub(crate) fn new() -> Self {
+        NameRegistry::default()
+    }
+
+    pub(crate) fn register(&self, name: &str, children_ref: ChildrenRef) {
+        debug!(
+            "Children({}): Registering with name: {}",
+            children_ref.id(),
+            name
+        );
+        // FIXME: panics
+        let mut groups = self.groups.lock().unwrap();
+        let id = children_ref.id().clone();
+        if let Some(previous) = groups.insert(name.to_string(), children_ref) {
+            if previous.id() == &id {
+                return;
+            }
+
+            warn!(
+                "Children({}): Name \"{}\" is now used by another children group.",
+                previous.id(),
+                name
+            );
+        }
+    }
+
+    /// Returns the children group currently registered with the
+    /// given name, if any.
+    pub(crate) fn lookup(&self, name: &str) -> Option<ChildrenRef> {
+        // FIXME: panics
+        self.groups.lock().unwrap().get(name).cloned()
+    }
+
+    /// Removes the name of the children group with the given
+    /// identifier, unless it was given to another group since.
+    pub(crate) fn unregister(&self, name: &str, id: &BastionId) {
+        debug!("Children({}): Unregistering name: {}", id, name);
+        // FIXME: panics
+        let mut groups = self.groups.lock().unwrap();
+        if groups.get(name).map(|group| group.id() == id) == Some(true) {
+            groups.remove(name);
+        }
+    }
+}
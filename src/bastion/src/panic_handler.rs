@@ -0,0 +1,147 @@
+//!
+//! Process-wide panic hook that reports panics happening inside
+//! children to a user-defined handler before they get caught and
+//! the faulted child gets recovered by its supervisor.
+use crate::context::BastionId;
+use fxhash::FxHashMap;
+use lazy_static::lazy_static;
+use std::cell::RefCell;
+use std::panic;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex, Once, RwLock};
+use tracing::error;
+
+// `PanicInfo` is only deprecated (as an alias of `PanicHookInfo`)
+// in versions of Rust more recent than the ones supported.
+#[allow(deprecated)]
+pub(crate) type PanicInfo<'a> = panic::PanicInfo<'a>;
+
+pub(crate) type PanicHandler = Arc<dyn Fn(BastionId, &PanicInfo) + Send + Sync>;
+pub(crate) type PanicHook = Arc<dyn Fn(&PanicInfo, Option<&BastionId>) + Send + Sync>;
+
+lazy_static! {
+    static ref PANIC_HANDLER: RwLock<Option<PanicHandler>> = RwLock::new(None);
+    // Called instead of the previous hook for the panics of
+    // children, and before it for the other ones.
+    static ref PANIC_HOOK: RwLock<Option<PanicHook>> = RwLock::new(None);
+    // The message of the last panic of each child that panicked
+    // (or supervisor that forwarded one), until its supervisor
+    // takes it when recovering it or it stops.
+    static ref PANIC_MESSAGES: Mutex<FxHashMap<BastionId, String>> =
+        Mutex::new(FxHashMap::default());
+}
+
+static INSTALL_HOOK: Once = Once::new();
+static HIDE_BACKTRACES: AtomicBool = AtomicBool::new(false);
+
+thread_local! {
+    // The identifier of the child whose future is currently
+    // being polled on this thread, if any.
+    static CURRENT_CHILD: RefCell<Option<BastionId>> = const { RefCell::new(None) };
+}
+
+pub(crate) struct ChildGuard {
+    previous: Option<BastionId>,
+}
+
+/// Marks the child with the given identifier as the one being
+/// polled on the current thread until the returned guard is
+/// dropped.
+pub(crate) fn enter_child(id: &BastionId) -> ChildGuard {
+    let previous = CURRENT_CHILD.with(|current| current.borrow_mut().replace(id.clone()));
+
+    ChildGuard { previous }
+}
+
+/// Returns the identifier of the child whose future is currently
+/// being polled on this thread, if any.
+pub(crate) fn current_child() -> Option<BastionId> {
+    CURRENT_CHILD
+        .try_with(|current| current.borrow().clone())
+        .ok()
+        .flatten()
+}
+
+/// Returns (and forgets) the message of the last panic of the
+/// child with the given identifier, if it panicked.
+pub(crate) fn take_panic(id: &BastionId) -> Option<String> {
+    PANIC_MESSAGES.lock().ok()?.remove(id)
+}
+
+/// Records the message of the panic forwarded by the supervisor
+/// with the given identifier, for its parent to take it like the
+/// ones of the children that panicked.
+pub(crate) fn forward_panic(id: &BastionId, message: String) {
+    if let Ok(mut messages) = PANIC_MESSAGES.lock() {
+        messages.insert(id.clone(), message);
+    }
+}
+
+pub(crate) fn set_handler(handler: PanicHandler) {
+    // FIXME: panics
+    *PANIC_HANDLER.write().unwrap() = Some(handler);
+}
+
+pub(crate) fn set_hook(hook: PanicHook) {
+    // FIXME: panics
+    *PANIC_HOOK.write().unwrap() = Some(hook);
+}
+
+/// Installs the process-wide panic hook (only once) and updates
+/// whether the default hook should still be called after it.
+pub(crate) fn install_hook(hide_backtraces: bool) {
+    HIDE_BACKTRACES.store(hide_backtraces, Ordering::SeqCst);
+
+    INSTALL_HOOK.call_once(|| {
+        let default_hook = panic::take_hook();
+        panic::set_hook(Box::new(move |info| {
+            let current = CURRENT_CHILD.with(|current| current.borrow().clone());
+            if let Some(id) = &current {
+                error!("Child({}): Panicked: {}", id, info);
+
+                let payload = info.payload();
+                let message = match payload.downcast_ref::<&str>() {
+                    Some(message) => message.to_string(),
+                    None => match payload.downcast_ref::<String>() {
+                        Some(message) => message.clone(),
+                        None => "Box<dyn Any>".to_string(),
+                    },
+                };
+                if let Ok(mut messages) = PANIC_MESSAGES.lock() {
+                    messages.insert(id.clone(), message);
+                }
+
+                let handler = PANIC_HANDLER
+                    .read()
+                    .ok()
+                    .and_then(|handler| handler.clone());
+                if let Some(handler) = handler {
+                    handler(id.clone(), info);
+                }
+            }
+
+            let hook = PANIC_HOOK.read().ok().and_then(|hook| hook.clone());
+            if let Some(hook) = &hook {
+                hook(info, current.as_ref());
+            }
+
+            // The panics of children are only redirected to the
+            // hook, if one was set.
+            let redirected = hook.is_some() && current.is_some();
+            if !redirected && !HIDE_BACKTRACES.load(Ordering::SeqCst) {
+                default_hook(info);
+            }
+        }));
+    });
+}
+
+impl Drop for ChildGuard {
+    fn drop(&mut self) {
+        let previous = self.previous.take();
+        // `try_with` because the guard might get dropped while the
+        // thread-local storage is being destroyed.
+        CURRENT_CHILD
+            .try_with(|current| *current.borrow_mut() = previous)
+            .ok();
+    }
+}
@@ -195,7 +195,13 @@ impl fmt::Display for BastionPath {
             f,
             "/{}",
             self.iter()
-                .map(|id| format!("{}", id))
+                .map(|id| {
+                    if f.alternate() {
+                        format!("{:#}", id)
+                    } else {
+                        format!("{}", id)
+                    }
+                })
                 .collect::<Vec<String>>()
                 .join("/")
         )
@@ -0,0 +1,432 @@
+//!
+//! Sending messages to the named children groups of a system
+//! running on another host, over TCP, available with the `remote`
+//! feature.
+//!
+//! A system exposes some of its named children groups (see
+//! [`Children::with_name`]) using [`Bastion::serve_remote`], and
+//! another one sends messages to them through a
+//! [`RemoteChildrenRef`] returned by [`Bastion::remote_children`].
+//! The messages are encoded as [`EncodedEnvelope`]s (using
+//! [`JsonCodec`]), which the elements of the groups decode using
+//! [`BastionContext::recv_deserialized`].
+//!
+//! Each message is sent as a frame made of its length (as a
+//! big-endian `u32`) followed by the encoded message. Messages
+//! are only sent one way: nothing acknowledges their delivery.
+//!
+//! [`Children::with_name`]: ../children/struct.Children.html#method.with_name
+//! [`Bastion::serve_remote`]: ../struct.Bastion.html#method.serve_remote
+//! [`RemoteChildrenRef`]: struct.RemoteChildrenRef.html
+//! [`Bastion::remote_children`]: ../struct.Bastion.html#method.remote_children
+//! [`EncodedEnvelope`]: ../codec/struct.EncodedEnvelope.html
+//! [`JsonCodec`]: ../codec/struct.JsonCodec.html
+//! [`BastionContext::recv_deserialized`]: ../context/struct.BastionContext.html#method.recv_deserialized
+use crate::codec::{Codec, CodecError, EncodedEnvelope, JsonCodec, SerializableMessage};
+use crate::envelope::Envelope;
+use crate::executor;
+use crate::message::{BastionMessage, Message};
+use crate::system::GlobalSystem;
+use serde::{Deserialize, Serialize};
+use std::fmt::{self, Debug, Formatter};
+use std::io::{self, ErrorKind, Read, Write};
+use std::net::{SocketAddr, TcpListener, TcpStream};
+use std::sync::{Arc, Mutex};
+use std::thread;
+use std::time::Duration;
+use tracing::{debug, trace, warn};
+
+// How often the serving threads check whether the system stopped
+// while no connection or message is received.
+const STOPPED_POLL_INTERVAL: Duration = Duration::from_millis(50);
+// How many times a `RemoteChildrenRef` tries to (re)connect to its
+// peer before giving up on a message.
+const CONNECT_ATTEMPTS: u32 = 3;
+// How long a `RemoteChildrenRef` waits for its peer to accept a
+// connection, and between two attempts.
+const CONNECT_TIMEOUT: Duration = Duration::from_secs(1);
+const RECONNECT_DELAY: Duration = Duration::from_millis(50);
+// The size of the largest frame sent or accepted, protecting the
+// serving system from peers sending garbage.
+const MAX_FRAME_LEN: usize = 16 * 1024 * 1024;
+
+#[derive(Debug)]
+/// The reason why a [`RemoteChildrenRef`] couldn't send a message.
+///
+/// [`RemoteChildrenRef`]: struct.RemoteChildrenRef.html
+pub enum RemoteError {
+    /// Encoding the message failed.
+    Encode(CodecError),
+    /// The encoded message (of the given size, in bytes) is too
+    /// large to be sent.
+    TooLarge(usize),
+    /// The peer couldn't be reached, even after trying to
+    /// reconnect to it. The message was sent to the dead letters.
+    Unreachable(io::Error),
+}
+
+// A message sent to a remote children group.
+#[derive(Debug, Serialize, Deserialize)]
+struct Frame {
+    // The name of the children group.
+    group: String,
+    // Whether the message is sent to all the elements of the
+    // group or to only one of them.
+    broadcast: bool,
+    envelope: EncodedEnvelope,
+}
+
+#[derive(Clone)]
+/// A "reference" to a named children group of a system running
+/// on another host (see [`Bastion::remote_children`]), allowing to
+/// send it messages.
+///
+/// The clones of a `RemoteChildrenRef` share the same connection,
+/// which is only established when the first message is sent, and
+/// re-established when lost. Sending a message returns a future
+/// which completes once it was written to the connection (but not
+/// once it was received), the connection being handled on the
+/// blocking thread pool.
+///
+/// [`Bastion::remote_children`]: ../struct.Bastion.html#method.remote_children
+pub struct RemoteChildrenRef {
+    addr: SocketAddr,
+    name: String,
+    // The connection to the peer, if established and not lost.
+    stream: Arc<Mutex<Option<TcpStream>>>,
+    system: Arc<GlobalSystem>,
+}
+
+impl RemoteChildrenRef {
+    pub(crate) fn new(system: Arc<GlobalSystem>, addr: SocketAddr, name: &str) -> Self {
+        RemoteChildrenRef {
+            addr,
+            name: name.to_string(),
+            stream: Arc::new(Mutex::new(None)),
+            system,
+        }
+    }
+
+    /// Returns the address of the system the referenced children
+    /// group is running in.
+    pub fn addr(&self) -> SocketAddr {
+        self.addr
+    }
+
+    /// Returns the name of the referenced children group.
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Sends a message to the referenced children group, which will
+    /// then send it to all of its elements.
+    ///
+    /// This method returns a future which completes with `()`
+    /// once the message was sent, or with `Err(RemoteError)` if it
+    /// couldn't be. If the peer couldn't be reached, the message is
+    /// sent to the dead letters.
+    ///
+    /// # Arguments
+    ///
+    /// * `msg` - The message to send.
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// # use bastion::prelude::*;
+    /// use bastion::codec::SerializableMessage;
+    /// use serde::{Deserialize, Serialize};
+    /// #
+    /// # Bastion::init();
+    ///
+    /// #[derive(Debug, Serialize, Deserialize)]
+    /// struct Order {
+    ///     id: u64,
+    /// }
+    ///
+    /// impl SerializableMessage for Order {
+    ///     const TYPE_TAG: &'static str = "shop.Order";
+    /// }
+    ///
+    /// let addr = "10.0.0.2:4200".parse().unwrap();
+    /// let orders = Bastion::remote_children(addr, "orders");
+    /// run!(orders.broadcast(Order { id: 42 })).expect("Couldn't send the order.");
+    /// ```
+    pub async fn broadcast<M: SerializableMessage>(&self, msg: M) -> Result<(), RemoteError> {
+        debug!(
+            "RemoteChildrenRef({}@{}): Broadcasting message: {:?}",
+            self.name, self.addr, msg
+        );
+        self.send(msg, true).await
+    }
+
+    /// Sends a message to the referenced children group, which will
+    /// then send it to one of its elements.
+    ///
+    /// This method returns a future which completes with `()`
+    /// once the message was sent, or with `Err(RemoteError)` if it
+    /// couldn't be. If the peer couldn't be reached, the message is
+    /// sent to the dead letters.
+    ///
+    /// # Arguments
+    ///
+    /// * `msg` - The message to send.
+    pub async fn tell<M: SerializableMessage>(&self, msg: M) -> Result<(), RemoteError> {
+        debug!(
+            "RemoteChildrenRef({}@{}): Telling message: {:?}",
+            self.name, self.addr, msg
+        );
+        self.send(msg, false).await
+    }
+
+    async fn send<M: SerializableMessage>(
+        &self,
+        msg: M,
+        broadcast: bool,
+    ) -> Result<(), RemoteError> {
+        let frame = Frame {
+            group: self.name.clone(),
+            broadcast,
+            envelope: EncodedEnvelope::encode(&JsonCodec, &msg).map_err(RemoteError::Encode)?,
+        };
+        let bytes = JsonCodec.encode(&frame).map_err(RemoteError::Encode)?;
+        if bytes.len() > MAX_FRAME_LEN {
+            return Err(RemoteError::TooLarge(bytes.len()));
+        }
+
+        // Connecting (and waiting before reconnecting) blocks, so
+        // the frame is written on the blocking thread pool.
+        let remote = self.clone();
+        let written = executor::blocking(async move { remote.write_frame(&bytes) })
+            .await
+            .unwrap_or_else(|| Err(io::Error::new(ErrorKind::Other, "writing cancelled")));
+        match written {
+            Ok(()) => Ok(()),
+            Err(err) => {
+                warn!(
+                    "RemoteChildrenRef({}@{}): Peer unreachable, sending the message to the dead letters: {}",
+                    self.name, self.addr, err
+                );
+                self.to_dead_letters(msg);
+                Err(RemoteError::Unreachable(err))
+            }
+        }
+    }
+
+    // Writes a frame to the connection, (re)connecting to the peer
+    // if needed.
+    fn write_frame(&self, bytes: &[u8]) -> io::Result<()> {
+        let len = (bytes.len() as u32).to_be_bytes();
+        // FIXME: panics
+        let mut stream = self.stream.lock().unwrap();
+        let mut last_err = None;
+        for attempt in 0..CONNECT_ATTEMPTS {
+            if attempt > 0 {
+                thread::sleep(RECONNECT_DELAY);
+            }
+
+            if stream.is_none() {
+                trace!(
+                    "RemoteChildrenRef({}@{}): Connecting (attempt #{}).",
+                    self.name,
+                    self.addr,
+                    attempt + 1
+                );
+                match TcpStream::connect_timeout(&self.addr, CONNECT_TIMEOUT) {
+                    Ok(connected) => {
+                        connected.set_nodelay(true).ok();
+                        *stream = Some(connected);
+                    }
+                    Err(err) => {
+                        debug!(
+                            "RemoteChildrenRef({}@{}): Couldn't connect: {}",
+                            self.name, self.addr, err
+                        );
+                        last_err = Some(err);
+                        continue;
+                    }
+                }
+            }
+
+            // FIXME: panics?
+            let connected = stream.as_mut().unwrap();
+            match connected
+                .write_all(&len)
+                .and_then(|_| connected.write_all(bytes))
+            {
+                Ok(()) => return Ok(()),
+                Err(err) => {
+                    debug!(
+                        "RemoteChildrenRef({}@{}): Connection lost: {}",
+                        self.name, self.addr, err
+                    );
+                    *stream = None;
+                    last_err = Some(err);
+                }
+            }
+        }
+
+        Err(last_err.unwrap_or_else(|| ErrorKind::NotConnected.into()))
+    }
+
+    fn to_dead_letters<M: Message>(&self, msg: M) {
+        let msg = BastionMessage::tell(msg);
+        let env = Envelope::from_dead_letters(msg, &self.system);
+        self.system.dead_letters().sender().unbounded_send(env).ok();
+    }
+}
+
+impl Debug for RemoteChildrenRef {
+    fn fmt(&self, fmt: &mut Formatter) -> fmt::Result {
+        fmt.debug_struct("RemoteChildrenRef")
+            .field("addr", &self.addr)
+            .field("name", &self.name)
+            .finish()
+    }
+}
+
+// Starts accepting connections on the given address on a dedicated
+// thread, until the system stops, delivering the messages sent to
+// the children groups with the given names.
+pub(crate) fn serve(
+    system: Arc<GlobalSystem>,
+    addr: SocketAddr,
+    names: Vec<String>,
+) -> Result<SocketAddr, ()> {
+    let listener = match TcpListener::bind(addr) {
+        Ok(listener) => listener,
+        Err(err) => {
+            warn!("Remote: Couldn't listen on {}: {}", addr, err);
+            return Err(());
+        }
+    };
+    let addr = listener.local_addr().map_err(|_| ())?;
+    // Accepting without blocking allows noticing that the system
+    // stopped.
+    listener.set_nonblocking(true).map_err(|_| ())?;
+    debug!("Remote: Listening on {}, exposing: {:?}", addr, names);
+
+    let names = Arc::new(names);
+    thread::spawn(move || {
+        while !system.has_stopped() {
+            match listener.accept() {
+                Ok((stream, peer)) => {
+                    debug!("Remote: Accepted connection from {}.", peer);
+                    let (system, names) = (system.clone(), names.clone());
+                    thread::spawn(move || {
+                        if let Err(err) = receive(&system, &names, stream) {
+                            debug!("Remote: Connection from {} lost: {}", peer, err);
+                        }
+                    });
+                }
+                Err(err) if err.kind() == ErrorKind::WouldBlock => {
+                    thread::sleep(STOPPED_POLL_INTERVAL);
+                }
+                Err(err) => warn!("Remote: Couldn't accept a connection: {}", err),
+            }
+        }
+
+        // Dropping the listener stops listening.
+        debug!("Remote: System stopped, stopping listening on {}.", addr);
+    });
+
+    Ok(addr)
+}
+
+// Delivers the messages received from a connection, until it is
+// closed or the system stops.
+fn receive(system: &GlobalSystem, names: &[String], mut stream: TcpStream) -> io::Result<()> {
+    stream.set_nonblocking(false)?;
+    stream.set_read_timeout(Some(STOPPED_POLL_INTERVAL))?;
+
+    // The number of messages received, used to spread the ones
+    // told to a group over its elements.
+    let mut next_elem = 0;
+    loop {
+        let mut len = [0; 4];
+        if !read_full(system, &mut stream, &mut len)? {
+            trace!("Remote: Connection closed.");
+            return Ok(());
+        }
+
+        let len = u32::from_be_bytes(len) as usize;
+        if len > MAX_FRAME_LEN {
+            return Err(io::Error::new(
+                ErrorKind::InvalidData,
+                format!("frame of {} bytes", len),
+            ));
+        }
+
+        let mut bytes = vec![0; len];
+        if !read_full(system, &mut stream, &mut bytes)? {
+            return Err(ErrorKind::UnexpectedEof.into());
+        }
+
+        let frame: Frame = match JsonCodec.decode(&bytes) {
+            Ok(frame) => frame,
+            Err(err) => {
+                warn!("Remote: Couldn't decode a frame: {:?}", err);
+                continue;
+            }
+        };
+        next_elem += 1;
+        deliver(system, names, frame, next_elem);
+    }
+}
+
+// Fills `buf`, returning `false` if the connection was closed
+// before anything was read.
+fn read_full(system: &GlobalSystem, stream: &mut TcpStream, buf: &mut [u8]) -> io::Result<bool> {
+    let mut read = 0;
+    while read < buf.len() {
+        match stream.read(&mut buf[read..]) {
+            Ok(0) if read == 0 => return Ok(false),
+            Ok(0) => return Err(ErrorKind::UnexpectedEof.into()),
+            Ok(n) => read += n,
+            Err(err)
+                if err.kind() == ErrorKind::WouldBlock || err.kind() == ErrorKind::TimedOut =>
+            {
+                if system.has_stopped() {
+                    return Err(io::Error::new(ErrorKind::Other, "system stopped"));
+                }
+            }
+            Err(err) if err.kind() == ErrorKind::Interrupted => (),
+            Err(err) => return Err(err),
+        }
+    }
+
+    Ok(true)
+}
+
+fn deliver(system: &GlobalSystem, names: &[String], frame: Frame, next_elem: usize) {
+    trace!(
+        "Remote: Received {} for {:?}.",
+        frame.envelope.type_tag,
+        frame.group
+    );
+    let children_ref = if names.contains(&frame.group) {
+        system.names().lookup(&frame.group)
+    } else {
+        None
+    };
+
+    let envelope = frame.envelope;
+    let res = match children_ref {
+        Some(children_ref) if frame.broadcast => children_ref.broadcast_serialized(envelope),
+        Some(children_ref) if !children_ref.elems().is_empty() => {
+            let index = next_elem % children_ref.elems().len();
+            children_ref.send_to_index(index, envelope)
+        }
+        _ => Err(envelope),
+    };
+
+    if let Err(envelope) = res {
+        debug!(
+            "Remote: Couldn't deliver {} to {:?}, sending it to the dead letters.",
+            envelope.type_tag, frame.group
+        );
+        let msg = BastionMessage::tell(envelope);
+        let env = Envelope::from_dead_letters(msg, system);
+        system.dead_letters().sender().unbounded_send(env).ok();
+    }
+}
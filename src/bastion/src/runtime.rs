@@ -0,0 +1,837 @@
+//!
+//! Runtimes each running their own supervision tree, isolated from
+//! the ones of the other runtimes of the process.
+use crate::bastion::{SystemExit, SystemStats};
+use crate::broadcast::{Broadcast, Parent};
+use crate::children::Children;
+use crate::children_ref::ChildrenRef;
+use crate::config::{self, Config};
+use crate::context::{BastionContext, BastionId};
+use crate::dump;
+use crate::envelope::Envelope;
+use crate::events::SystemEvent;
+use crate::executor;
+#[cfg(feature = "http-health")]
+use crate::health;
+use crate::message::{BastionMessage, Message, Recipients};
+use crate::metrics::MetricsSnapshot;
+use crate::path::BastionPathElement;
+#[cfg(feature = "remote")]
+use crate::remote::{self, RemoteChildrenRef};
+use crate::storm::{self, StormReport};
+use crate::supervisor::{InspectReport, ShutdownReport, Supervisor, SupervisorRef};
+use crate::system::{GlobalSystem, SYSTEM};
+use core::future::Future;
+use futures::channel::oneshot;
+use futures::{FutureExt, Stream};
+use lazy_static::lazy_static;
+use lightproc::prelude::*;
+use std::fmt::{self, Debug, Formatter};
+#[cfg(any(feature = "http-health", feature = "remote"))]
+use std::net::SocketAddr;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Arc, RwLock};
+use std::time::{Duration, Instant};
+use tracing::{debug, trace, warn};
+
+// The number of events buffered for a subscriber by default (see
+// `Bastion::events`).
+const DEFAULT_EVENTS_CAPACITY: usize = 1024;
+// How long `dump_state` waits for the supervision tree to answer.
+const DEFAULT_DUMP_TIMEOUT: Duration = Duration::from_secs(1);
+
+lazy_static! {
+    // The runtime used by `Bastion`, created the first time it
+    // is used and replaced by a new one when it gets initialized,
+    // started or deployed into again once it stopped.
+    static ref DEFAULT: RwLock<BastionRuntime> = RwLock::new(BastionRuntime {
+        system: SYSTEM.clone(),
+    });
+}
+
+static NEXT_RUNTIME_ID: AtomicUsize = AtomicUsize::new(0);
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+/// Identifies a [`BastionRuntime`], as returned by
+/// [`BastionRuntime::id`] and by the `runtime_id` method of the
+/// references to the supervisors, children groups and elements
+/// it runs.
+///
+/// [`BastionRuntime`]: struct.BastionRuntime.html
+/// [`BastionRuntime::id`]: struct.BastionRuntime.html#method.id
+pub struct RuntimeId(usize);
+
+#[derive(Clone)]
+/// A runtime, running its own system supervisor, supervisors and
+/// children groups, isolated from the ones of the other runtimes
+/// of the process (e.g. broadcasting a message, stopping or
+/// counting the actors only affects one runtime).
+///
+/// [`Bastion`] is a facade over a default runtime, which gets
+/// created the first time it is used. The references to the
+/// supervisors and children groups created in a runtime can't be
+/// used to deploy new ones into another.
+///
+/// Cloning a `BastionRuntime` returns another handle to the same
+/// runtime.
+///
+/// Note that the executor's threads, the panic hook and handler,
+/// the unhandled message hook and the logger are shared by all
+/// the runtimes of the process.
+///
+/// # Example
+///
+/// ```rust
+/// use bastion::prelude::*;
+///
+/// let first = BastionRuntime::new(Config::new());
+/// let second = BastionRuntime::new(Config::new());
+///
+/// first.children(|children| {
+///     children.with_exec(|ctx: BastionContext| async move {
+///         ctx.recv().await?;
+///         Ok(())
+///     })
+/// }).expect("Couldn't create the children group.");
+///
+/// first.start();
+/// second.start();
+///
+/// // Stopping the first runtime doesn't stop the second one...
+/// first.stop();
+/// assert_eq!(first.block_until_stopped(), SystemExit::Stopped);
+/// #
+/// # second.stop();
+/// # second.block_until_stopped();
+/// ```
+///
+/// [`Bastion`]: struct.Bastion.html
+pub struct BastionRuntime {
+    system: Arc<GlobalSystem>,
+}
+
+impl RuntimeId {
+    pub(crate) fn new() -> Self {
+        RuntimeId(NEXT_RUNTIME_ID.fetch_add(1, Ordering::SeqCst))
+    }
+}
+
+impl BastionRuntime {
+    /// Creates and launches a new runtime using the specified
+    /// [`Config`].
+    ///
+    /// The executor's threads and the panic hook set in the
+    /// configuration are shared by all the runtimes of the process,
+    /// which thus all use the last ones that were set.
+    ///
+    /// # Arguments
+    ///
+    /// * `config` - The configuration used to initialize the
+    ///     runtime.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use bastion::prelude::*;
+    ///
+    /// let runtime = BastionRuntime::new(Config::new());
+    ///
+    /// // You can now use the runtime...
+    /// #
+    /// # runtime.start();
+    /// # runtime.stop();
+    /// # runtime.block_until_stopped();
+    /// ```
+    ///
+    /// [`Config`]: struct.Config.html
+    pub fn new(config: Config) -> Self {
+        debug!("BastionRuntime: Initializing with config: {:?}", config);
+        config.apply();
+        let system = GlobalSystem::launch(config);
+        debug!("BastionRuntime({:?}): Initialized.", system.id());
+
+        BastionRuntime { system }
+    }
+
+    pub(crate) fn default_runtime() -> Self {
+        // FIXME: panics
+        DEFAULT.read().unwrap().clone()
+    }
+
+    // Returns the default runtime, replacing it by a new one using
+    // the current configuration first if it stopped.
+    pub(crate) fn relaunched_default_runtime() -> Self {
+        // FIXME: panics
+        let mut default = DEFAULT.write().unwrap();
+        if default.system.has_stopped() {
+            warn!(
+                "BastionRuntime({:?}): Relaunching the stopped default runtime, whose supervisors and children groups are gone.",
+                default.id()
+            );
+            *default = BastionRuntime {
+                system: GlobalSystem::launch(config::current()),
+            };
+        }
+
+        default.clone()
+    }
+
+    /// Returns the identifier of this runtime.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use bastion::prelude::*;
+    /// #
+    /// let runtime = BastionRuntime::new(Config::new());
+    /// let supervisor_ref = runtime.supervisor(|sp| sp).unwrap();
+    ///
+    /// assert_eq!(supervisor_ref.runtime_id(), runtime.id());
+    /// #
+    /// # runtime.start();
+    /// # runtime.stop();
+    /// # runtime.block_until_stopped();
+    /// ```
+    pub fn id(&self) -> RuntimeId {
+        self.system.id()
+    }
+
+    /// Creates a new [`Supervisor`], passes it through the
+    /// specified `init` closure and then sends it to this
+    /// runtime's system for it to start supervising it, like
+    /// [`Bastion::supervisor`].
+    ///
+    /// This method returns a [`SupervisorRef`] referencing the
+    /// newly created supervisor if it succeeded, or `Err(())`
+    /// otherwise (e.g. if `init` returned a supervisor created in
+    /// another runtime).
+    ///
+    /// # Arguments
+    ///
+    /// * `init` - The closure taking the new [`Supervisor`] as an
+    ///     argument and returning it once configured.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use bastion::prelude::*;
+    /// #
+    /// let runtime = BastionRuntime::new(Config::new());
+    ///
+    /// let supervisor_ref = runtime.supervisor(|sp| {
+    ///     // Configure the supervisor...
+    ///     sp.with_strategy(SupervisionStrategy::OneForOne)
+    ///     // ...and return it.
+    /// }).expect("Couldn't create the supervisor.");
+    /// #
+    /// # runtime.start();
+    /// # runtime.stop();
+    /// # runtime.block_until_stopped();
+    /// ```
+    ///
+    /// [`Supervisor`]: supervisor/struct.Supervisor.html
+    /// [`SupervisorRef`]: supervisor/struct.SupervisorRef.html
+    /// [`Bastion::supervisor`]: struct.Bastion.html#method.supervisor
+    pub fn supervisor<S>(&self, init: S) -> Result<SupervisorRef, ()>
+    where
+        S: FnOnce(Supervisor) -> Supervisor,
+    {
+        debug!("BastionRuntime({:?}): Creating supervisor.", self.id());
+        let parent = Parent::system(self.system.clone());
+        let bcast = Broadcast::new(parent, BastionPathElement::Supervisor(BastionId::new()));
+
+        debug!(
+            "BastionRuntime({:?}): Initializing Supervisor({}).",
+            self.id(),
+            bcast.id()
+        );
+        let supervisor = Supervisor::new(bcast);
+        let supervisor = init(supervisor);
+        debug!("Supervisor({}): Initialized.", supervisor.id());
+        if supervisor.is_invalid() {
+            return Err(());
+        }
+        let supervisor_ref = supervisor.as_ref();
+
+        debug!(
+            "BastionRuntime({:?}): Deploying Supervisor({}).",
+            self.id(),
+            supervisor.id()
+        );
+        let msg = BastionMessage::deploy_supervisor(supervisor);
+        let envelope = Envelope::new(
+            msg,
+            self.system.path().clone(),
+            self.system.sender().clone(),
+        );
+        trace!(
+            "BastionRuntime({:?}): Sending envelope: {:?}",
+            self.id(),
+            envelope
+        );
+        self.system
+            .sender()
+            .unbounded_send(envelope)
+            .map_err(|_| ())?;
+
+        Ok(supervisor_ref)
+    }
+
+    /// Creates a new [`Children`], passes it through the specified
+    /// `init` closure and then sends it to this runtime's system
+    /// supervisor for it to start supervising it, like
+    /// [`Bastion::children`].
+    ///
+    /// This method returns a [`ChildrenRef`] referencing the newly
+    /// created children group if it succeeded, or `Err(())`
+    /// otherwise.
+    ///
+    /// # Arguments
+    ///
+    /// * `init` - The closure taking the new [`Children`] as an
+    ///     argument and returning it once configured.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use bastion::prelude::*;
+    /// #
+    /// let runtime = BastionRuntime::new(Config::new());
+    ///
+    /// let children_ref = runtime.children(|children| {
+    ///     children.with_exec(|ctx: BastionContext| async move {
+    ///         ctx.recv().await?;
+    ///         Ok(())
+    ///     })
+    /// }).expect("Couldn't create the children group.");
+    ///
+    /// assert_eq!(children_ref.runtime_id(), runtime.id());
+    /// #
+    /// # runtime.start();
+    /// # runtime.stop();
+    /// # runtime.block_until_stopped();
+    /// ```
+    ///
+    /// [`Children`]: children/struct.Children.html
+    /// [`ChildrenRef`]: children_ref/struct.ChildrenRef.html
+    /// [`Bastion::children`]: struct.Bastion.html#method.children
+    pub fn children<C>(&self, init: C) -> Result<ChildrenRef, ()>
+    where
+        C: FnOnce(Children) -> Children,
+    {
+        debug!("BastionRuntime({:?}): Creating children group.", self.id());
+        self.system.supervisor().children(init)
+    }
+
+    /// Creates a new [`Children`] which will have the given closure
+    /// as action and then sends it to this runtime's system
+    /// supervisor, like [`Bastion::spawn`].
+    ///
+    /// This method returns a [`ChildrenRef`] referencing the newly
+    /// created children group if it succeeded, or `Err(())`
+    /// otherwise.
+    ///
+    /// # Arguments
+    ///
+    /// * `action` - The closure which gets executed by the child.
+    ///
+    /// [`Children`]: children/struct.Children.html
+    /// [`ChildrenRef`]: children_ref/struct.ChildrenRef.html
+    /// [`Bastion::spawn`]: struct.Bastion.html#method.spawn
+    pub fn spawn<I, F>(&self, action: I) -> Result<ChildrenRef, ()>
+    where
+        I: Fn(BastionContext) -> F + Send + 'static,
+        F: Future<Output = Result<(), ()>> + Send + 'static,
+    {
+        self.children(|ch| ch.with_redundancy(1).with_exec(action))
+    }
+
+    /// Spawns the given future, cancelling it if it didn't complete
+    /// once `timeout` elapsed or if this runtime's system stops
+    /// before, like [`Bastion::spawn_with_timeout`].
+    ///
+    /// # Arguments
+    ///
+    /// * `future` - The future to spawn.
+    /// * `timeout` - How long to wait for the future to complete.
+    ///
+    /// [`Bastion::spawn_with_timeout`]: struct.Bastion.html#method.spawn_with_timeout
+    pub fn spawn_with_timeout<F, T>(
+        &self,
+        future: F,
+        timeout: Duration,
+    ) -> impl Future<Output = Option<T>>
+    where
+        F: Future<Output = T> + Send + 'static,
+        T: Send + 'static,
+    {
+        debug!(
+            "BastionRuntime({:?}): Spawning future with timeout: {:?}",
+            self.id(),
+            timeout
+        );
+        self.system
+            .timers()
+            .spawn_with_timeout(future, timeout)
+            .map(Option::flatten)
+    }
+
+    /// Returns a [`ChildrenRef`] referencing the running children
+    /// group of this runtime that was given the specified name, if
+    /// any, like [`Bastion::children_named`].
+    ///
+    /// # Arguments
+    ///
+    /// * `name` - The name of the children group to look up.
+    ///
+    /// [`ChildrenRef`]: children_ref/struct.ChildrenRef.html
+    /// [`Bastion::children_named`]: struct.Bastion.html#method.children_named
+    pub fn children_named(&self, name: &str) -> Option<ChildrenRef> {
+        self.system.names().lookup(name)
+    }
+
+    /// Sends a message to this runtime's system which will then
+    /// send it to all its supervisors, children groups and their
+    /// elements, like [`Bastion::broadcast`].
+    ///
+    /// This method returns `()` if it succeeded, or `Err(msg)`
+    /// otherwise.
+    ///
+    /// # Arguments
+    ///
+    /// * `msg` - The message to send.
+    ///
+    /// [`Bastion::broadcast`]: struct.Bastion.html#method.broadcast
+    pub fn broadcast<M: Message>(&self, msg: M) -> Result<(), M> {
+        debug!(
+            "BastionRuntime({:?}): Broadcasting message: {:?}",
+            self.id(),
+            msg
+        );
+        let msg = BastionMessage::broadcast(msg);
+        let envelope = Envelope::from_dead_letters(msg, &self.system);
+        trace!(
+            "BastionRuntime({:?}): Sending envelope: {:?}",
+            self.id(),
+            envelope
+        );
+        // FIXME: panics?
+        self.system
+            .sender()
+            .unbounded_send(envelope)
+            .map_err(|env| env.into_msg().unwrap())
+    }
+
+    /// Sends a message to this runtime's system which will then
+    /// send it to all its supervisors, children groups and their
+    /// elements, counting the mailboxes it reached, like
+    /// [`Bastion::broadcast_counted`].
+    ///
+    /// This method returns a [`Recipients`] which resolves to the
+    /// number of mailboxes the message reached, or `Err(msg)` if it
+    /// failed.
+    ///
+    /// # Arguments
+    ///
+    /// * `msg` - The message to send.
+    ///
+    /// [`Bastion::broadcast_counted`]: struct.Bastion.html#method.broadcast_counted
+    /// [`Recipients`]: message/struct.Recipients.html
+    pub fn broadcast_counted<M: Message>(&self, msg: M) -> Result<Recipients, M> {
+        debug!(
+            "BastionRuntime({:?}): Broadcasting counted message: {:?}",
+            self.id(),
+            msg
+        );
+        let (msg, recipients) = BastionMessage::broadcast_counted(msg);
+        let envelope = Envelope::from_dead_letters(msg, &self.system);
+        // FIXME: panics?
+        self.system
+            .sender()
+            .unbounded_send(envelope)
+            .map(|_| recipients)
+            .map_err(|env| env.into_msg().unwrap())
+    }
+
+    /// Sends a message to every element of this runtime subscribed
+    /// to the given topic, like [`Bastion::publish`].
+    ///
+    /// This method returns `()` if it succeeded, or `Err(msg)`
+    /// otherwise.
+    ///
+    /// # Arguments
+    ///
+    /// * `topic` - The topic to publish the message to.
+    /// * `msg` - The message to send.
+    ///
+    /// [`Bastion::publish`]: struct.Bastion.html#method.publish
+    pub fn publish<M: Message>(&self, topic: &str, msg: M) -> Result<(), M> {
+        debug!(
+            "BastionRuntime({:?}): Publishing message to topic {}: {:?}",
+            self.id(),
+            topic,
+            msg
+        );
+        let msg = BastionMessage::broadcast(msg);
+        let envelope = Envelope::from_dead_letters(msg, &self.system);
+        trace!(
+            "BastionRuntime({:?}): Publishing envelope: {:?}",
+            self.id(),
+            envelope
+        );
+        // FIXME: panics?
+        self.system
+            .topics()
+            .publish(topic, envelope)
+            .map_err(|env| env.into_msg().unwrap())
+    }
+
+    /// Returns the number of children groups elements currently
+    /// running in this runtime, like [`Bastion::num_actors`].
+    ///
+    /// [`Bastion::num_actors`]: struct.Bastion.html#method.num_actors
+    pub fn num_actors(&self) -> usize {
+        self.system.actors().load(Ordering::SeqCst)
+    }
+
+    /// Returns the number of supervisors currently running in this
+    /// runtime (without counting its system supervisor), like
+    /// [`Bastion::num_supervisors`].
+    ///
+    /// [`Bastion::num_supervisors`]: struct.Bastion.html#method.num_supervisors
+    pub fn num_supervisors(&self) -> usize {
+        self.system.supervisors().load(Ordering::SeqCst)
+    }
+
+    /// Returns process-wide counters about this runtime's system,
+    /// like [`Bastion::stats`].
+    ///
+    /// [`Bastion::stats`]: struct.Bastion.html#method.stats
+    pub fn stats(&self) -> SystemStats {
+        SystemStats {
+            supervisors: self.num_supervisors(),
+            children_groups: self.system.children_groups().load(Ordering::SeqCst),
+            elements: self.num_actors(),
+            restarts: self.system.restarts().load(Ordering::SeqCst),
+            messages_delivered: self.system.delivered().load(Ordering::SeqCst),
+            uptime: self.system.uptime(),
+        }
+    }
+
+    /// Subscribes to the lifecycle events of this runtime's whole
+    /// supervision tree, like [`Bastion::events`].
+    ///
+    /// [`Bastion::events`]: struct.Bastion.html#method.events
+    pub fn events(&self) -> impl Stream<Item = SystemEvent> {
+        self.events_with_capacity(DEFAULT_EVENTS_CAPACITY)
+    }
+
+    /// Subscribes to the lifecycle events of this runtime's whole
+    /// supervision tree, buffering at most the given number of
+    /// them, like [`Bastion::events_with_capacity`].
+    ///
+    /// [`Bastion::events_with_capacity`]: struct.Bastion.html#method.events_with_capacity
+    pub fn events_with_capacity(&self, capacity: usize) -> impl Stream<Item = SystemEvent> {
+        debug!("BastionRuntime({:?}): Subscribing to events.", self.id());
+        self.system.events().subscribe(capacity)
+    }
+
+    /// Calls the given hook whenever this runtime's whole
+    /// supervision tree restarts more than `max_restarts` times
+    /// within `window`, like [`Bastion::on_restart_storm`].
+    ///
+    /// [`Bastion::on_restart_storm`]: struct.Bastion.html#method.on_restart_storm
+    pub fn on_restart_storm<H>(&self, max_restarts: usize, window: Duration, hook: H)
+    where
+        H: Fn(StormReport) + Send + Sync + 'static,
+    {
+        debug!(
+            "BastionRuntime({:?}): Watching for more than {} restarts within {:?}.",
+            self.id(),
+            max_restarts,
+            window
+        );
+        let events = self.system.events().subscribe(DEFAULT_EVENTS_CAPACITY);
+        // The hook runs on the blocking pool, where it can take its
+        // time without holding back the supervision tree. The
+        // watcher stops along with the system, which ends the stream.
+        executor::spawn_proc_blocking(
+            storm::watch(events, max_restarts, window, hook),
+            ProcStack::default(),
+        );
+    }
+
+    /// Asks this runtime's system for a report of its whole
+    /// supervision tree, like [`Bastion::inspect`].
+    ///
+    /// [`Bastion::inspect`]: struct.Bastion.html#method.inspect
+    pub fn inspect(&self) -> impl Future<Output = Result<InspectReport, ()>> {
+        debug!("BastionRuntime({:?}): Inspecting.", self.id());
+        let (sender, receiver) = oneshot::channel();
+        let msg = BastionMessage::inspect(sender);
+        let env = Envelope::new(
+            msg,
+            self.system.path().clone(),
+            self.system.sender().clone(),
+        );
+        // If the system stopped, the envelope gets dropped along
+        // with the sender.
+        self.system.sender().unbounded_send(env).ok();
+
+        async move { receiver.await.map_err(|_| ()) }
+    }
+
+    /// Renders this runtime's whole supervision tree as a
+    /// Graphviz DOT graph, like [`Bastion::tree_dot`].
+    ///
+    /// [`Bastion::tree_dot`]: struct.Bastion.html#method.tree_dot
+    pub fn tree_dot(&self) -> impl Future<Output = Result<String, ()>> {
+        let report = self.inspect();
+        async move { report.await.map(|report| report.to_dot()) }
+    }
+
+    /// Renders this runtime's whole supervision tree as a
+    /// Graphviz DOT graph colored by health, like
+    /// [`Bastion::export_dot`].
+    ///
+    /// [`Bastion::export_dot`]: struct.Bastion.html#method.export_dot
+    pub fn export_dot(&self) -> impl Future<Output = String> {
+        let report = self.inspect();
+        async move {
+            report
+                .await
+                .map(|report| report.to_health_dot())
+                .unwrap_or_else(|_| String::from("digraph bastion {\n}\n"))
+        }
+    }
+
+    /// Asks this runtime's system for a textual report of the
+    /// internal state of its whole supervision tree, like
+    /// [`Bastion::dump_state`].
+    ///
+    /// [`Bastion::dump_state`]: struct.Bastion.html#method.dump_state
+    pub fn dump_state(&self) -> impl Future<Output = Result<String, ()>> {
+        self.dump_state_with_timeout(DEFAULT_DUMP_TIMEOUT)
+    }
+
+    /// Asks this runtime's system for a textual report of the
+    /// internal state of its whole supervision tree, waiting for
+    /// it at most `timeout`, like [`Bastion::dump_state_with_timeout`].
+    ///
+    /// [`Bastion::dump_state_with_timeout`]: struct.Bastion.html#method.dump_state_with_timeout
+    pub fn dump_state_with_timeout(
+        &self,
+        timeout: Duration,
+    ) -> impl Future<Output = Result<String, ()>> {
+        debug!("BastionRuntime({:?}): Dumping state.", self.id());
+        let deadline = Instant::now() + timeout;
+        let (sender, receiver) = oneshot::channel();
+        let msg = BastionMessage::dump_state(sender, deadline);
+        let env = Envelope::new(
+            msg,
+            self.system.path().clone(),
+            self.system.sender().clone(),
+        );
+        // If the system stopped, the envelope gets dropped along
+        // with the sender.
+        self.system.sender().unbounded_send(env).ok();
+
+        async move {
+            dump::answer_before(receiver, deadline)
+                .await
+                .map(|dump| dump.to_string())
+                .ok_or(())
+        }
+    }
+
+    /// Starts serving an HTTP health-check endpoint for this
+    /// runtime on the given port, like
+    /// [`Bastion::register_health_endpoint`].
+    ///
+    /// This method is only available with the `http-health`
+    /// feature.
+    ///
+    /// [`Bastion::register_health_endpoint`]: struct.Bastion.html#method.register_health_endpoint
+    #[cfg(feature = "http-health")]
+    #[cfg_attr(feature = "docs", doc(cfg(feature = "http-health")))]
+    pub fn register_health_endpoint(&self, port: u16) -> Result<SocketAddr, ()> {
+        debug!(
+            "BastionRuntime({:?}): Registering health endpoint on port {}.",
+            self.id(),
+            port
+        );
+        health::serve(self.system.clone(), port)
+    }
+
+    /// Starts accepting connections on the given address, letting
+    /// other systems send messages to this runtime's children
+    /// groups with the given names, like [`Bastion::serve_remote`].
+    ///
+    /// This method is only available with the `remote` feature.
+    ///
+    /// [`Bastion::serve_remote`]: struct.Bastion.html#method.serve_remote
+    #[cfg(feature = "remote")]
+    #[cfg_attr(feature = "docs", doc(cfg(feature = "remote")))]
+    pub fn serve_remote(&self, addr: SocketAddr, names: &[&str]) -> Result<SocketAddr, ()> {
+        debug!(
+            "BastionRuntime({:?}): Serving {:?} on {}.",
+            self.id(),
+            names,
+            addr
+        );
+        let names = names.iter().map(|name| name.to_string()).collect();
+        remote::serve(self.system.clone(), addr, names)
+    }
+
+    /// Returns a [`RemoteChildrenRef`] referencing the children
+    /// group with the given name of the system listening on the
+    /// given address, like [`Bastion::remote_children`].
+    ///
+    /// This method is only available with the `remote` feature.
+    ///
+    /// [`RemoteChildrenRef`]: remote/struct.RemoteChildrenRef.html
+    /// [`Bastion::remote_children`]: struct.Bastion.html#method.remote_children
+    #[cfg(feature = "remote")]
+    #[cfg_attr(feature = "docs", doc(cfg(feature = "remote")))]
+    pub fn remote_children(&self, addr: SocketAddr, name: &str) -> RemoteChildrenRef {
+        RemoteChildrenRef::new(self.system.clone(), addr, name)
+    }
+
+    /// Asks this runtime's system for the counters of its whole
+    /// supervision tree, like [`Bastion::metrics`].
+    ///
+    /// [`Bastion::metrics`]: struct.Bastion.html#method.metrics
+    pub fn metrics(&self) -> impl Future<Output = Result<MetricsSnapshot, ()>> {
+        debug!("BastionRuntime({:?}): Asking for metrics.", self.id());
+        let (sender, receiver) = oneshot::channel();
+        let msg = BastionMessage::metrics(sender);
+        let env = Envelope::new(
+            msg,
+            self.system.path().clone(),
+            self.system.sender().clone(),
+        );
+        // If the system stopped, the envelope gets dropped along
+        // with the sender.
+        self.system.sender().unbounded_send(env).ok();
+
+        async move { receiver.await.map_err(|_| ()) }
+    }
+
+    /// Sends a message to this runtime's system to tell it to start
+    /// handling messages and running children, like
+    /// [`Bastion::start`].
+    ///
+    /// [`Bastion::start`]: struct.Bastion.html#method.start
+    pub fn start(&self) {
+        debug!("BastionRuntime({:?}): Starting.", self.id());
+        let msg = BastionMessage::start();
+        let envelope = Envelope::from_dead_letters(msg, &self.system);
+        trace!(
+            "BastionRuntime({:?}): Sending envelope: {:?}",
+            self.id(),
+            envelope
+        );
+        // FIXME: Err(Error)
+        self.system.sender().unbounded_send(envelope).ok();
+    }
+
+    /// Sends a message to this runtime's system to tell it to stop
+    /// every running children groups and supervisors, like
+    /// [`Bastion::stop`].
+    ///
+    /// [`Bastion::stop`]: struct.Bastion.html#method.stop
+    pub fn stop(&self) {
+        debug!("BastionRuntime({:?}): Stopping.", self.id());
+        let msg = BastionMessage::stop();
+        let envelope = Envelope::from_dead_letters(msg, &self.system);
+        trace!(
+            "BastionRuntime({:?}): Sending envelope: {:?}",
+            self.id(),
+            envelope
+        );
+        // FIXME: Err(Error)
+        self.system.sender().unbounded_send(envelope).ok();
+    }
+
+    /// Stops this runtime like [`stop`], waiting up to `timeout`
+    /// for it to stop before killing it, and then returns which
+    /// children groups and supervisors stopped in time, like
+    /// [`Bastion::stop_with_timeout`].
+    ///
+    /// # Arguments
+    ///
+    /// * `timeout` - How long to wait for the children groups and
+    ///     supervisors to stop before killing them.
+    ///
+    /// [`stop`]: #method.stop
+    /// [`Bastion::stop_with_timeout`]: struct.Bastion.html#method.stop_with_timeout
+    pub fn stop_with_timeout(&self, timeout: Duration) -> ShutdownReport {
+        debug!(
+            "BastionRuntime({:?}): Stopping with a timeout of {:?}.",
+            self.id(),
+            timeout
+        );
+        self.system.shutdown().begin();
+        self.stop();
+
+        if !self.system.wait_until_stopped_timeout(timeout) {
+            warn!(
+                "BastionRuntime({:?}): Couldn't stop in time, killing.",
+                self.id()
+            );
+            self.kill();
+        }
+
+        self.system.shutdown().finish()
+    }
+
+    /// Sends a message to this runtime's system to tell it to kill
+    /// every running children groups and supervisors, like
+    /// [`Bastion::kill`].
+    ///
+    /// [`Bastion::kill`]: struct.Bastion.html#method.kill
+    pub fn kill(&self) {
+        debug!("BastionRuntime({:?}): Killing.", self.id());
+        let msg = BastionMessage::kill();
+        let envelope = Envelope::from_dead_letters(msg, &self.system);
+        trace!(
+            "BastionRuntime({:?}): Sending envelope: {:?}",
+            self.id(),
+            envelope
+        );
+        // FIXME: Err(Error)
+        self.system.sender().unbounded_send(envelope).ok();
+
+        let handle = self.system.handle();
+        let system = crate::executor::run(async { handle.lock().await.take() });
+        if let Some(system) = system {
+            debug!("BastionRuntime({:?}): Cancelling system handle.", self.id());
+            system.cancel();
+        }
+
+        self.system.notify_stopped(SystemExit::Killed);
+    }
+
+    /// Blocks the current thread until this runtime is stopped
+    /// (either by calling [`stop`] or [`kill`]) and returns how it
+    /// stopped, like [`Bastion::block_until_stopped`].
+    ///
+    /// [`stop`]: #method.stop
+    /// [`kill`]: #method.kill
+    /// [`Bastion::block_until_stopped`]: struct.Bastion.html#method.block_until_stopped
+    pub fn block_until_stopped(&self) -> SystemExit {
+        debug!(
+            "BastionRuntime({:?}): Blocking until system is stopped.",
+            self.id()
+        );
+        self.system.wait_until_stopped()
+    }
+}
+
+impl Debug for BastionRuntime {
+    fn fmt(&self, fmt: &mut Formatter) -> fmt::Result {
+        fmt.debug_struct("BastionRuntime")
+            .field("id", &self.id())
+            .finish()
+    }
+}
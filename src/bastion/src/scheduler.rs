@@ -0,0 +1,309 @@
+//!
+//! Delayed messages delivery, allowing to send a message once a
+//! duration elapsed (see [`ChildrenRef::tell_after`],
+//! [`ChildRef::send_after`] and
+//! [`BastionContext::notify_self_after`]) or periodically (see
+//! [`Children::with_tick`]).
+//!
+//! [`ChildrenRef::tell_after`]: ../children_ref/struct.ChildrenRef.html#method.tell_after
+//! [`ChildRef::send_after`]: ../child_ref/struct.ChildRef.html#method.send_after
+//! [`BastionContext::notify_self_after`]: ../context/struct.BastionContext.html#method.notify_self_after
+//! [`Children::with_tick`]: ../children/struct.Children.html#method.with_tick
+use crate::child_ref::ChildRef;
+use crate::executor;
+use futures::future::{self, AbortHandle, AbortRegistration, Abortable, Either};
+use futures_timer::Delay;
+use fxhash::FxHashMap;
+use lightproc::prelude::*;
+use std::future::Future;
+use std::mem;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use tracing::{debug, trace};
+
+#[derive(Debug, Clone)]
+/// A handle to a message whose delivery was scheduled, allowing
+/// to cancel it.
+///
+/// Dropping this handle doesn't cancel the delivery.
+///
+/// # Example
+///
+/// ```rust
+/// # use bastion::prelude::*;
+/// # use std::time::Duration;
+/// #
+/// # Bastion::init();
+/// #
+/// # let children_ref = Bastion::children(|children| children).unwrap();
+/// let scheduled: ScheduledSend = children_ref.tell_after(Duration::from_secs(5), "Retry.");
+/// // The message won't be delivered...
+/// scheduled.cancel();
+/// #
+/// # Bastion::start();
+/// # Bastion::stop();
+/// # Bastion::block_until_stopped();
+/// ```
+pub struct ScheduledSend {
+    handle: AbortHandle,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+/// The message periodically sent to the elements of a children
+/// group that was configured using [`Children::with_tick`] (or
+/// to an element that called [`BastionContext::set_tick`]).
+///
+/// At most one `Tick` is waiting to be received by an element at
+/// any time: ticks aren't sent while the previous one wasn't
+/// received yet.
+///
+/// # Example
+///
+/// ```rust
+/// # use bastion::prelude::*;
+/// # use std::time::Duration;
+/// #
+/// # Bastion::init();
+/// #
+/// Bastion::children(|children| {
+///     children
+///         .with_tick(Duration::from_secs(1))
+///         .with_exec(|ctx: BastionContext| async move {
+///             loop {
+///                 msg! { ctx.recv().await?,
+///                     _tick: Tick => {
+///                         // Flush the buffers...
+///                     };
+///                     _: _ => ();
+///                 }
+///             }
+///         })
+/// }).expect("Couldn't create the children group.");
+/// #
+/// # Bastion::start();
+/// # Bastion::stop();
+/// # Bastion::block_until_stopped();
+/// ```
+///
+/// [`Children::with_tick`]: ../children/struct.Children.html#method.with_tick
+/// [`BastionContext::set_tick`]: ../context/struct.BastionContext.html#method.set_tick
+pub struct Tick;
+
+#[derive(Debug, Default)]
+pub(crate) struct Timers {
+    next_id: AtomicU64,
+    // The scheduled deliveries that didn't happen yet, which
+    // get cancelled when the system stops.
+    pending: Arc<Mutex<FxHashMap<u64, AbortHandle>>>,
+}
+
+#[derive(Debug, Default)]
+pub(crate) struct Ticker {
+    inner: Mutex<TickerState>,
+}
+
+#[derive(Debug, Default)]
+struct TickerState {
+    interval: Option<Duration>,
+    // The element receiving the ticks, only set while it is
+    // running.
+    child: Option<ChildRef>,
+    // Incremented each time the interval changes or the element
+    // stops, so that ticks scheduled before are ignored.
+    generation: u64,
+    scheduled: Option<ScheduledSend>,
+    // Whether a tick was sent but not received yet.
+    pending: bool,
+}
+
+impl ScheduledSend {
+    /// Cancels the delivery of the scheduled message, if it
+    /// didn't happen yet.
+    pub fn cancel(&self) {
+        debug!("ScheduledSend: Cancelling.");
+        self.handle.abort();
+    }
+}
+
+impl Ticker {
+    pub(crate) fn new(interval: Option<Duration>) -> Self {
+        let inner = Mutex::new(TickerState {
+            interval,
+            ..TickerState::default()
+        });
+
+        Ticker { inner }
+    }
+
+    /// Starts sending ticks to the given element, if an interval
+    /// was set.
+    pub(crate) fn start(self: &Arc<Self>, child: ChildRef) {
+        // FIXME: panics
+        let mut inner = self.inner.lock().unwrap();
+        inner.child = Some(child);
+        self.schedule(&mut inner);
+    }
+
+    /// Changes the interval between two ticks, stopping sending
+    /// them if `None`.
+    pub(crate) fn set(self: &Arc<Self>, interval: Option<Duration>) {
+        // FIXME: panics
+        let mut inner = self.inner.lock().unwrap();
+        inner.interval = interval;
+        inner.cancel();
+        self.schedule(&mut inner);
+    }
+
+    /// Stops sending ticks, because the element stopped.
+    pub(crate) fn stop(&self) {
+        // FIXME: panics
+        let mut inner = self.inner.lock().unwrap();
+        inner.child = None;
+        inner.cancel();
+    }
+
+    /// Allows the next tick to be sent, because the previous
+    /// one was received.
+    pub(crate) fn received(&self) {
+        // FIXME: panics
+        self.inner.lock().unwrap().pending = false;
+    }
+
+    fn schedule(self: &Arc<Self>, inner: &mut TickerState) {
+        let (interval, child) = match (inner.interval, &inner.child) {
+            (Some(interval), Some(child)) => (interval, child),
+            _ => return,
+        };
+
+        let ticker = self.clone();
+        let generation = inner.generation;
+        let scheduled = child
+            .system()
+            .timers()
+            .schedule(interval, move || ticker.tick(generation));
+        inner.scheduled = Some(scheduled);
+    }
+
+    fn tick(self: &Arc<Self>, generation: u64) {
+        // FIXME: panics
+        let mut inner = self.inner.lock().unwrap();
+        if inner.generation != generation {
+            return;
+        }
+
+        let child = match &inner.child {
+            Some(child) => child.clone(),
+            None => return,
+        };
+
+        if inner.pending {
+            trace!("Ticker: Child({}): Skipping tick.", child.id());
+        } else {
+            trace!("Ticker: Child({}): Sending tick.", child.id());
+            inner.pending = child.tell_anonymously(Tick).is_ok();
+        }
+
+        self.schedule(&mut inner);
+    }
+}
+
+impl TickerState {
+    fn cancel(&mut self) {
+        self.generation += 1;
+        if let Some(scheduled) = self.scheduled.take() {
+            scheduled.cancel();
+        }
+    }
+}
+
+impl Timers {
+    pub(crate) fn new() -> Self {
+        Timers::default()
+    }
+
+    /// Calls `send` once `delay` elapsed, unless the returned
+    /// handle gets cancelled or the system stops before.
+    pub(crate) fn schedule<F>(&self, delay: Duration, send: F) -> ScheduledSend
+    where
+        F: FnOnce() + Send + 'static,
+    {
+        let (id, handle, registration) = self.register();
+        trace!("Timers: Scheduling delivery {} in {:?}.", id, delay);
+
+        let delivery = Abortable::new(
+            async move {
+                Delay::new(delay).await;
+                trace!("Timers: Delivering {}.", id);
+                send();
+            },
+            registration,
+        );
+        let pending = self.pending.clone();
+        executor::spawn_proc(
+            async move {
+                delivery.await.ok();
+                // FIXME: panics
+                pending.lock().unwrap().remove(&id);
+            },
+            ProcStack::default(),
+        );
+
+        ScheduledSend { handle }
+    }
+
+    /// Spawns `future`, cancelling it once `timeout` elapsed or
+    /// when the system stops, whichever happens first, in which
+    /// case the returned handle's output is `None`.
+    pub(crate) fn spawn_with_timeout<F, T>(
+        &self,
+        future: F,
+        timeout: Duration,
+    ) -> RecoverableHandle<Option<T>>
+    where
+        F: Future<Output = T> + Send + 'static,
+        T: Send + 'static,
+    {
+        let (id, _, registration) = self.register();
+        trace!("Timers: Spawning future {} with timeout {:?}.", id, timeout);
+
+        let future = Box::pin(Abortable::new(future, registration));
+        let pending = self.pending.clone();
+        executor::spawn_proc(
+            async move {
+                let output = match future::select(future, Delay::new(timeout)).await {
+                    Either::Left((output, _)) => output.ok(),
+                    Either::Right(_) => {
+                        debug!("Timers: Future {} timed out.", id);
+                        None
+                    }
+                };
+                // FIXME: panics
+                pending.lock().unwrap().remove(&id);
+                output
+            },
+            ProcStack::default(),
+        )
+    }
+
+    // Returns a new abort handle (with its id and registration),
+    // which gets aborted if the system stops before it gets removed
+    // from `pending`.
+    fn register(&self) -> (u64, AbortHandle, AbortRegistration) {
+        let id = self.next_id.fetch_add(1, Ordering::SeqCst);
+        let (handle, registration) = AbortHandle::new_pair();
+        // FIXME: panics
+        self.pending.lock().unwrap().insert(id, handle.clone());
+        (id, handle, registration)
+    }
+
+    /// Cancels all the scheduled deliveries that didn't happen yet.
+    pub(crate) fn cancel_all(&self) {
+        // FIXME: panics
+        let pending = mem::take(&mut *self.pending.lock().unwrap());
+        debug!("Timers: Cancelling {} scheduled deliveries.", pending.len());
+        for (_, handle) in pending {
+            handle.abort();
+        }
+    }
+}
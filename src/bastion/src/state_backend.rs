@@ -0,0 +1,129 @@
+//!
+//! State backends keep the state of supervisors checkpointed
+//! before they stop, allowing to restore it when they are
+//! restarted (see [`Supervisor::with_state_persistence`]).
+//!
+//! [`Supervisor::with_state_persistence`]: ../supervisor/struct.Supervisor.html#method.with_state_persistence
+use crate::context::BastionId;
+use std::fmt::Debug;
+use std::fs::{self, File};
+use std::io::{self, ErrorKind, Write};
+use std::path::PathBuf;
+use tracing::trace;
+
+#[derive(Debug)]
+/// The reason why a [`StateBackend`] couldn't save or load a
+/// state.
+///
+/// [`StateBackend`]: trait.StateBackend.html
+pub enum BackendError {
+    /// Accessing the underlying storage failed.
+    Io(io::Error),
+    /// The backend failed for another reason.
+    Other(String),
+}
+
+/// A storage for the states checkpointed by supervisors (see
+/// [`Supervisor::with_state_persistence`]).
+///
+/// States are opaque bytes, saved under the identifier of the
+/// supervisor they belong to, replacing the previous one.
+///
+/// [`Supervisor::with_state_persistence`]: ../supervisor/struct.Supervisor.html#method.with_state_persistence
+pub trait StateBackend: Debug + Send + Sync {
+    /// Saves the given state of the supervisor identified by `id`.
+    ///
+    /// # Arguments
+    ///
+    /// * `id` - The identifier of the supervisor.
+    /// * `state` - The state to save.
+    fn save(&self, id: &BastionId, state: &[u8]) -> Result<(), BackendError>;
+
+    /// Returns the last state saved for the supervisor identified
+    /// by `id`, or `None` if there is none.
+    ///
+    /// # Arguments
+    ///
+    /// * `id` - The identifier of the supervisor.
+    fn load(&self, id: &BastionId) -> Result<Option<Vec<u8>>, BackendError>;
+}
+
+#[derive(Debug, Clone)]
+/// A [`StateBackend`] saving each state in its own file, named
+/// after the identifier of its supervisor, in a given directory.
+///
+/// # Example
+///
+/// ```rust
+/// # use bastion::prelude::*;
+/// # use bastion::state_backend::FileStateBackend;
+/// # use std::sync::Arc;
+/// #
+/// # Bastion::init();
+/// #
+/// let backend = FileStateBackend::new(std::env::temp_dir().join("bastion-states"));
+///
+/// Bastion::supervisor(|sp| {
+///     sp.with_state_persistence(Arc::new(backend))
+/// }).expect("Couldn't create the supervisor.");
+/// #
+/// # Bastion::start();
+/// # Bastion::stop();
+/// # Bastion::block_until_stopped();
+/// ```
+///
+/// [`StateBackend`]: trait.StateBackend.html
+pub struct FileStateBackend {
+    dir: PathBuf,
+}
+
+impl FileStateBackend {
+    /// Creates a new `FileStateBackend` saving states in the given
+    /// directory, which is created when saving the first one if it
+    /// doesn't exist.
+    ///
+    /// # Arguments
+    ///
+    /// * `dir` - The directory to save states in.
+    pub fn new(dir: impl Into<PathBuf>) -> Self {
+        FileStateBackend { dir: dir.into() }
+    }
+
+    fn path(&self, id: &BastionId) -> PathBuf {
+        self.dir.join(format!("{}.state", id))
+    }
+}
+
+impl StateBackend for FileStateBackend {
+    fn save(&self, id: &BastionId, state: &[u8]) -> Result<(), BackendError> {
+        let path = self.path(id);
+        trace!("FileStateBackend: Saving state to {:?}.", path);
+        fs::create_dir_all(&self.dir)?;
+        // The state is written next to the previous one before
+        // replacing it, so that a crash while writing it can't
+        // leave a truncated state behind.
+        let tmp_path = path.with_extension("state.tmp");
+        let mut file = File::create(&tmp_path)?;
+        file.write_all(state)?;
+        file.sync_all()?;
+        fs::rename(tmp_path, path)?;
+
+        Ok(())
+    }
+
+    fn load(&self, id: &BastionId) -> Result<Option<Vec<u8>>, BackendError> {
+        let path = self.path(id);
+        trace!("FileStateBackend: Loading state from {:?}.", path);
+        match fs::read(path) {
+            Ok(state) => Ok(Some(state)),
+            Err(err) if err.kind() == ErrorKind::NotFound => Ok(None),
+            Err(err) => Err(err.into()),
+        }
+    }
+}
+
+impl From<io::Error> for BackendError {
+    fn from(err: io::Error) -> Self {
+        BackendError::Io(err)
+    }
+}
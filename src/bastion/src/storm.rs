@@ -0,0 +1,265 @@
+//!
+//! Soft detection of restart storms, where the whole supervision
+//! tree restarts more than a given number of times within a sliding
+//! window (see [`Bastion::on_restart_storm`]).
+//!
+//! The restarts are counted from the lifecycle events of the tree
+//! (see [`Bastion::events`]), which are published without waiting
+//! for the subscribers, so that a slow hook can't delay restarts.
+//!
+//! [`Bastion::on_restart_storm`]: ../struct.Bastion.html#method.on_restart_storm
+//! [`Bastion::events`]: ../struct.Bastion.html#method.events
+use crate::context::BastionId;
+use crate::events::{EntityKind, SystemEvent, Transition};
+use futures::future::Either;
+use futures::prelude::*;
+use futures_timer::Delay;
+use std::cmp::Reverse;
+use std::collections::VecDeque;
+use std::time::{Duration, Instant};
+use tracing::{debug, warn};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+/// A restart storm detected by [`Bastion::on_restart_storm`].
+///
+/// [`Bastion::on_restart_storm`]: ../struct.Bastion.html#method.on_restart_storm
+pub struct StormReport {
+    /// The number of restarts performed within the window.
+    pub restarts: usize,
+    /// The duration of the sliding window.
+    pub window: Duration,
+    /// The supervisors and children groups that restarted (or
+    /// whose elements restarted) within the window, the ones that
+    /// restarted the most first.
+    pub offenders: Vec<StormOffender>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+/// A supervisor or children group that took part in a restart
+/// storm (see [`StormReport`]).
+///
+/// [`StormReport`]: struct.StormReport.html
+pub struct StormOffender {
+    /// The identifier of the supervisor or children group.
+    pub id: BastionId,
+    /// Whether it is a supervisor or a children group.
+    pub kind: EntityKind,
+    /// Its name, if it was given one.
+    pub name: Option<String>,
+    /// The number of times it (or one of its elements) restarted
+    /// within the window.
+    pub restarts: usize,
+}
+
+// A restart counted in the window, attributed to a supervisor or
+// children group.
+#[derive(Debug)]
+struct Restart {
+    at: Instant,
+    id: BastionId,
+    kind: EntityKind,
+    name: Option<String>,
+}
+
+impl StormReport {
+    fn new(restarts: &VecDeque<Restart>, window: Duration) -> Self {
+        let mut offenders: Vec<StormOffender> = Vec::new();
+        for restart in restarts {
+            match offenders
+                .iter_mut()
+                .find(|offender| offender.id == restart.id)
+            {
+                Some(offender) => offender.restarts += 1,
+                None => offenders.push(StormOffender {
+                    id: restart.id.clone(),
+                    kind: restart.kind,
+                    name: restart.name.clone(),
+                    restarts: 1,
+                }),
+            }
+        }
+        // The sort is stable, keeping the offenders that restarted
+        // as many times in the order they first restarted.
+        offenders.sort_by_key(|offender| Reverse(offender.restarts));
+
+        StormReport {
+            restarts: restarts.len(),
+            window,
+            offenders,
+        }
+    }
+}
+
+// Counts the restarts within the window, telling when they start
+// and stop being a storm.
+#[derive(Debug)]
+struct Watcher {
+    max_restarts: usize,
+    window: Duration,
+    restarts: VecDeque<Restart>,
+    storming: bool,
+}
+
+impl Watcher {
+    fn new(max_restarts: usize, window: Duration) -> Self {
+        Watcher {
+            max_restarts,
+            window,
+            restarts: VecDeque::new(),
+            storming: false,
+        }
+    }
+
+    // Counts a restart, returning the report of the storm it
+    // started, if it did.
+    fn restarted(&mut self, restart: Restart) -> Option<StormReport> {
+        let now = restart.at;
+        self.restarts.push_back(restart);
+        self.expire(now);
+
+        if self.storming || self.restarts.len() <= self.max_restarts {
+            return None;
+        }
+
+        self.storming = true;
+        Some(StormReport::new(&self.restarts, self.window))
+    }
+
+    // Forgets the restarts that left the window, returning whether
+    // the storm calmed down because of it.
+    fn expire(&mut self, now: Instant) -> bool {
+        while let Some(restart) = self.restarts.front() {
+            if now.duration_since(restart.at) <= self.window {
+                break;
+            }
+
+            self.restarts.pop_front();
+        }
+
+        if self.storming && self.restarts.len() <= self.max_restarts {
+            self.storming = false;
+            return true;
+        }
+
+        false
+    }
+
+    // When the oldest restart leaves the window, which might calm
+    // the storm down, or `None` if there is no storm.
+    fn calms_at(&self) -> Option<Instant> {
+        if !self.storming {
+            return None;
+        }
+
+        self.restarts
+            .front()
+            .map(|restart| restart.at + self.window)
+    }
+}
+
+/// Counts the restarts published as `events`, calling `hook` once
+/// more than `max_restarts` of them happened within `window`, and
+/// again only once there were at most `max_restarts` of them.
+pub(crate) async fn watch<S, H>(mut events: S, max_restarts: usize, window: Duration, hook: H)
+where
+    S: Stream<Item = SystemEvent> + Unpin,
+    H: Fn(StormReport),
+{
+    let mut watcher = Watcher::new(max_restarts, window);
+    loop {
+        // While storming, the watcher also wakes up once the oldest
+        // restart leaves the window, to notice that the storm calmed
+        // down even if no other restart follows.
+        let event = match watcher.calms_at() {
+            Some(at) => {
+                let delay = Delay::new(at.saturating_duration_since(Instant::now()));
+                match future::select(events.next(), delay).await {
+                    Either::Left((event, _)) => event,
+                    Either::Right(((), _)) => {
+                        if watcher.expire(Instant::now()) {
+                            debug!("RestartStorm: Calmed down.");
+                        }
+
+                        continue;
+                    }
+                }
+            }
+            None => events.next().await,
+        };
+
+        let event = match event {
+            Some(SystemEvent::Lifecycle(event)) if event.transition == Transition::Restarted => {
+                event
+            }
+            // The missed events can't be counted.
+            Some(_) => continue,
+            None => return,
+        };
+
+        // The restarts of elements are attributed to their group.
+        let (id, kind) = match (event.entity, event.parent) {
+            (EntityKind::Element, Some(parent)) => (parent, EntityKind::Children),
+            (kind, _) => (event.id, kind),
+        };
+        let restart = Restart {
+            at: Instant::now(),
+            id,
+            kind,
+            name: event.name,
+        };
+        if let Some(report) = watcher.restarted(restart) {
+            warn!(
+                "RestartStorm: {} restarts within {:?}: {:?}",
+                report.restarts, report.window, report.offenders
+            );
+            hook(report);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn restart(id: &BastionId, at: Instant) -> Restart {
+        Restart {
+            at,
+            id: id.clone(),
+            kind: EntityKind::Children,
+            name: None,
+        }
+    }
+
+    #[test]
+    fn calms_down() {
+        let window = Duration::from_secs(10);
+        let mut watcher = Watcher::new(1, window);
+        let id = BastionId::new();
+        let start = Instant::now();
+
+        assert_eq!(watcher.restarted(restart(&id, start)), None);
+        assert_eq!(watcher.calms_at(), None);
+        let report = watcher.restarted(restart(&id, start + Duration::from_secs(1)));
+        assert_eq!(report.map(|report| report.restarts), Some(2));
+
+        // The storm goes on while both restarts are in the window...
+        assert_eq!(watcher.calms_at(), Some(start + window));
+        assert!(!watcher.expire(start + window));
+        assert_eq!(
+            watcher.restarted(restart(&id, start + Duration::from_secs(2))),
+            None
+        );
+
+        // ...and calms down once the restarts left it, without
+        // another restart being needed.
+        assert!(!watcher.expire(start + window + Duration::from_secs(1)));
+        assert!(watcher.expire(start + window + Duration::from_secs(2)));
+        assert_eq!(watcher.calms_at(), None);
+        assert!(!watcher.expire(start + window + Duration::from_secs(3)));
+
+        // A new storm is then reported again.
+        let at = start + window + Duration::from_secs(4);
+        assert_eq!(watcher.restarted(restart(&id, at)), None);
+        assert!(watcher.restarted(restart(&id, at)).is_some());
+    }
+}
@@ -5,25 +5,37 @@ use crate::broadcast::{Broadcast, Parent, Sender};
 use crate::callbacks::Callbacks;
 use crate::children::Children;
 use crate::children_ref::ChildrenRef;
-use crate::context::{BastionId, ContextState};
-use crate::envelope::Envelope;
-use crate::message::{BastionMessage, Deployment, Message};
+use crate::context::{next_pid, BastionId, ContextState, DEFAULT_POLL_BUDGET, NIL_ID};
+use crate::dump::{self, DumpedElement, SupervisorDump};
+use crate::envelope::{Envelope, RefAddr};
+use crate::events::{EntityKind, Transition};
+use crate::executor;
+use crate::message::{BastionMessage, Deployment, Message, Msg, Recipients};
+use crate::metrics::{MetricsSnapshot, SupervisorMetrics};
+use crate::panic_handler;
 use crate::path::{BastionPath, BastionPathElement};
+use crate::runtime::RuntimeId;
+use crate::state_backend::StateBackend;
+use crate::system::{GlobalSystem, RunningGuard};
 use async_mutex::Mutex;
-use bastion_executor::pool;
+use futures::channel::oneshot;
 use futures::prelude::*;
-use futures::stream::FuturesOrdered;
-use futures::{pending, poll};
+use futures::stream::{FuturesOrdered, FuturesUnordered};
 use futures_timer::Delay;
 use fxhash::FxHashMap;
 use lightproc::prelude::*;
+use serde::{Deserialize, Serialize};
+use std::any::TypeId;
 use std::cmp::{Eq, PartialEq};
+use std::collections::{HashMap, VecDeque};
+use std::fmt::{self, Debug, Formatter};
 use std::ops::Range;
+use std::panic;
 use std::pin::Pin;
-use std::sync::Arc;
-use std::task::Poll;
-use std::time::Duration;
-use tracing::{debug, trace, warn};
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+use std::sync::{Arc, RwLock};
+use std::time::{Duration, Instant};
+use tracing::{debug, error, trace, warn};
 
 #[derive(Debug)]
 /// A supervisor that can supervise both [`Children`] and other
@@ -63,6 +75,19 @@ use tracing::{debug, trace, warn};
 /// [`Bastion::children`]: struct.Bastion.html#method.children
 pub struct Supervisor {
     bcast: Broadcast,
+    // The name given to the supervisor, which is kept when it
+    // is restarted.
+    name: Option<String>,
+    // The number of supervised elements that were launched or are
+    // being deployed and the supervisor's capacity, shared with its
+    // refs for them to refuse deploying more than it.
+    slots: Arc<Slots>,
+    // The children groups handling typed messages, shared with
+    // its refs (see `SupervisorRef::broadcast_typed`).
+    typed: Arc<TypedChildren>,
+    // The pid set on the supervisor's process, which changes
+    // when it is restarted.
+    pid: usize,
     // The order in which children and supervisors were added.
     // It is only updated when at least one of those is resat.
     order: Vec<BastionId>,
@@ -76,10 +101,11 @@ pub struct Supervisor {
     // The currently launched supervised children and supervisors.
     // The last value is the amount of times a given actor has restarted.
     launched: FxHashMap<BastionId, (usize, RecoverableHandle<Supervised>)>,
-    // Supervised children and supervisors that are stopped.
+    // Supervised children and supervisors that are stopped,
+    // along with the reason why they stopped.
     // This is used when resetting or recovering when the
     // supervision strategy is not "one-for-one".
-    stopped: FxHashMap<BastionId, Supervised>,
+    stopped: FxHashMap<BastionId, (StopReason, Supervised)>,
     // Supervised children and supervisors that were killed.
     // This is used when resetting only.
     killed: FxHashMap<BastionId, Supervised>,
@@ -97,10 +123,52 @@ pub struct Supervisor {
     // is received.
     pre_start_msgs: Vec<Envelope>,
     started: bool,
+    // Whether a children group (or supervisor) created using the
+    // builder methods was invalid (see `Children::with_affinity`),
+    // in which case deploying the supervisor fails.
+    invalid: AtomicBool,
     // Stores amount of subtree restarts.
     subtree_restarts: usize,
     // Store the maximum acceptable restarts for the supervisor.
     subtree_restarts_limit: usize,
+    // How user messages are delivered to the supervised elements.
+    routing: Routing,
+    // The index of the supervised element the next user message
+    // will be sent to when using `Routing::RoundRobin`.
+    next_route: usize,
+    // The labels of the supervised children groups, used when
+    // using `Routing::TagBased`.
+    tags: FxHashMap<BastionId, HashMap<String, String>>,
+    // The names (if any) and redundancies of the supervised
+    // children groups, reported when inspecting the supervisor.
+    groups: FxHashMap<BastionId, (Option<String>, usize)>,
+    // The restarts recently done by the supervisor, which makes
+    // it escalate when there are too many of them.
+    restart_window: Option<RestartWindow>,
+    // Where the supervisor checkpoints its state before stopping,
+    // to restore it when restarted.
+    state_backend: Option<Arc<dyn StateBackend>>,
+    // What happens to the running supervised elements when the
+    // supervisor faults.
+    orphan_policy: OrphanPolicy,
+    // In which order the supervised elements are sent the messages
+    // restarting them.
+    order_guarantee: OrderGuarantee,
+    // The number of deployed supervised elements, of faults and of
+    // recoveries using each strategy (see `SupervisorMetrics`),
+    // kept across restarts.
+    deploys: u64,
+    faults: u64,
+    one_for_one_restarts: u64,
+    one_for_all_restarts: u64,
+    rest_for_one_restarts: u64,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+// The state checkpointed by a supervisor with a state backend.
+struct Checkpoint {
+    order: Vec<BastionId>,
+    strategy: SupervisionStrategy,
 }
 
 #[derive(Debug, Clone)]
@@ -110,12 +178,49 @@ struct TrackedChildState {
     restarts_counts: usize,
 }
 
+#[derive(Debug)]
+// The number of supervised elements a supervisor launched or is
+// deploying, and how many it can launch at most (see
+// `Supervisor::with_capacity`).
+pub(crate) struct Slots {
+    used: AtomicUsize,
+    // `usize::MAX` if the supervisor has no capacity.
+    capacity: AtomicUsize,
+}
+
+#[derive(Debug, Default)]
+// The children groups handling typed messages (see
+// `Children::with_exec_typed`) supervised by a supervisor, by
+// the type they handle, and the ones of the supervisors it
+// supervises.
+pub(crate) struct TypedChildren {
+    children: RwLock<FxHashMap<TypeId, FxHashMap<BastionId, Sender>>>,
+    supervisors: RwLock<FxHashMap<BastionId, Arc<TypedChildren>>>,
+}
+
 #[derive(Debug)]
 enum RestartedElement {
     Supervisor(BastionId),
     Child { id: BastionId, parent_id: BastionId },
 }
 
+#[derive(Debug)]
+// A running children group or supervisor given away by its
+// faulted supervisor (see `OrphanPolicy::Adopt`).
+pub(crate) struct Orphan {
+    id: BastionId,
+    sender: Sender,
+    launched: RecoverableHandle<Supervised>,
+    // The states of the group's elements, if it is a children
+    // group.
+    tracked: Option<Vec<TrackedChildState>>,
+    // The labels of the group, if it is a children group.
+    tags: Option<HashMap<String, String>>,
+    // The name and redundancy of the group, if it is a children
+    // group.
+    group: Option<(Option<String>, usize)>,
+}
+
 #[derive(Debug)]
 enum ActorSearchMethod {
     OneActor { id: BastionId, parent_id: BastionId },
@@ -130,11 +235,18 @@ enum ActorSearchMethod {
 /// [`Supervisor`]: supervisor/struct.Supervisor.html
 pub struct SupervisorRef {
     id: BastionId,
+    // The pid set on the supervisor's process.
+    pid: usize,
     sender: Sender,
     path: Arc<BastionPath>,
+    name: Option<String>,
+    slots: Arc<Slots>,
+    typed: Arc<TypedChildren>,
+    // The system of the runtime the supervisor belongs to.
+    system: Arc<GlobalSystem>,
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 /// The strategy a supervisor should use when one of its
 /// supervised children groups or supervisors dies (in
 /// the case of a children group, it could be because one
@@ -159,10 +271,120 @@ pub enum SupervisionStrategy {
     /// were stopped) in the same order they were added to
     /// the supervisor.
     RestForOne,
+    /// When a children group dies (either because it got
+    /// killed, it panicked or returned an error), nothing is
+    /// restarted: the supervisor kills all its supervised
+    /// elements and then panics itself in its own task, with
+    /// the original panic message if one of the group's elements
+    /// panicked, terminating its whole tree. If the supervisor
+    /// is a top-level one, the whole system then terminates (see
+    /// [`SystemExit::Faulted`]).
+    ///
+    /// This is useful for environments wanting the panics of
+    /// children to propagate (e.g. tests or services monitoring
+    /// panics).
+    ///
+    /// [`SystemExit::Faulted`]: ../enum.SystemExit.html#variant.Faulted
+    PanicForward,
+}
+
+#[derive(Clone, Default)]
+/// The way a supervisor should deliver the user messages it
+/// receives (eg. using [`SupervisorRef::broadcast`]) to its
+/// supervised children groups and supervisors.
+///
+/// This only affects user messages, the supervisor's lifecycle
+/// messages are always sent to all the supervised elements.
+///
+/// The default routing is `Broadcast`.
+///
+/// [`SupervisorRef::broadcast`]: struct.SupervisorRef.html#method.broadcast
+pub enum Routing {
+    /// Every message is sent to all the supervised children
+    /// groups and supervisors.
+    #[default]
+    Broadcast,
+    /// Each message is sent to only one of the supervised
+    /// children groups or supervisors, taking turns in the order
+    /// they were added to the supervisor.
+    RoundRobin,
+    /// Each message is sent to only one of the supervised
+    /// children groups or supervisors, whose index in the order
+    /// they were added to the supervisor is the value returned by
+    /// the given function (modulo the number of running supervised
+    /// elements).
+    ByKey(fn(&Msg) -> usize),
+    /// Every message is sent to all the supervised children
+    /// groups whose labels (see [`Children::with_tag`]) match the
+    /// given predicate. Supervised supervisors have no labels,
+    /// the predicate being called with an empty map for them.
+    ///
+    /// [`Children::with_tag`]: ../children/struct.Children.html#method.with_tag
+    TagBased(TagPredicate),
+}
+
+/// The predicate used by [`Routing::TagBased`], deciding whether
+/// a children group should receive a message given its labels.
+///
+/// [`Routing::TagBased`]: enum.Routing.html#variant.TagBased
+pub type TagPredicate = Arc<dyn Fn(&HashMap<String, String>) -> bool + Send + Sync>;
+
+#[derive(Debug, Clone, Default)]
+/// What a supervisor does with its running supervised children
+/// groups and supervisors when it faults because it can't recover
+/// from one of them faulting (see [`Supervisor::with_restart_window`]
+/// and [`SupervisionStrategy::PanicForward`]). The one that faulted
+/// is always killed.
+///
+/// The default policy is `Kill`.
+///
+/// [`Supervisor::with_restart_window`]: struct.Supervisor.html#method.with_restart_window
+/// [`SupervisionStrategy::PanicForward`]: enum.SupervisionStrategy.html#variant.PanicForward
+pub enum OrphanPolicy {
+    /// They are killed along with the supervisor.
+    #[default]
+    Kill,
+    /// They keep running and are supervised by the given
+    /// supervisor from then on, which should not be the faulted
+    /// supervisor nor one of the supervisors it supervises (they
+    /// are killed instead if it already stopped).
+    Adopt(SupervisorRef),
+    /// They keep running without being supervised anymore:
+    /// nothing restarts them when they fault and stopping the
+    /// system doesn't stop them.
+    Detach,
+}
+
+#[derive(Debug, Clone, Copy, Default, Eq, PartialEq)]
+/// In which order a supervisor sends the messages restarting its
+/// supervised elements, when resetting or recovering from a fault
+/// restarts several of them (see [`Supervisor::with_order_guarantee`]).
+///
+/// The messages restarting children groups' elements are only sent
+/// once the elements' [`ActorRestartStrategy`] allows it, which can
+/// take longer for the elements that restarted more often.
+///
+/// The default guarantee is `None`.
+///
+/// [`Supervisor::with_order_guarantee`]: struct.Supervisor.html#method.with_order_guarantee
+/// [`ActorRestartStrategy`]: enum.ActorRestartStrategy.html
+pub enum OrderGuarantee {
+    /// Each message is sent as soon as possible, without waiting
+    /// for the ones that should have been sent before it.
+    #[default]
+    None,
+    /// The messages sent to a same children group or supervisor
+    /// are sent in the order its elements were added, while the
+    /// ones sent to others don't wait for them.
+    PerActor,
+    /// All the messages are sent in the order the supervised
+    /// elements were added, each one waiting for the previous ones
+    /// to be sent.
+    Global,
 }
 
 #[derive(Debug)]
-enum Supervised {
+pub(crate) enum Supervised {
     Supervisor(Supervisor),
     Children(Children),
 }
@@ -175,6 +397,12 @@ enum Supervised {
 pub enum RestartPolicy {
     /// Restart the failed actor with unlimited amount of attempts.
     Always,
+    /// Restart the failed actor with unlimited amount of attempts
+    /// if its future returned an error, but escalate to the
+    /// supervisor's own supervisor if it panicked (the panic being
+    /// considered a permanent failure the supervisor can't recover
+    /// from).
+    OnError,
     /// Never restart the failed actor when it happens.
     Never,
     /// Restart the failed actor with the limited amount of attempts.
@@ -195,6 +423,400 @@ pub struct RestartStrategy {
     strategy: ActorRestartStrategy,
 }
 
+#[derive(Debug, Clone, Eq, PartialEq)]
+/// The maximum number of restarts a supervisor accepts to do
+/// within a sliding window of time (see
+/// [`Supervisor::with_restart_window`]).
+///
+/// Restarts that happened longer than `window` ago aren't
+/// counted anymore.
+///
+/// # Example
+///
+/// ```rust
+/// # use bastion::prelude::*;
+/// # use std::time::Duration;
+/// #
+/// // At most 3 restarts within the last 5 seconds...
+/// let mut window = RestartWindow::new(3, Duration::from_secs(5));
+///
+/// assert!(window.record_and_check());
+/// assert!(window.record_and_check());
+/// assert!(window.record_and_check());
+/// // ...which the fourth one would exceed.
+/// assert!(!window.record_and_check());
+/// ```
+///
+/// [`Supervisor::with_restart_window`]: struct.Supervisor.html#method.with_restart_window
+pub struct RestartWindow {
+    max: usize,
+    window: Duration,
+    // When the restarts within the window happened, from the
+    // oldest to the most recent.
+    restarts: VecDeque<Instant>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+/// Information about a children group or supervisor that a
+/// supervisor supervised and that is stopped, as returned by
+/// [`SupervisorRef::list_stopped`].
+///
+/// [`SupervisorRef::list_stopped`]: struct.SupervisorRef.html#method.list_stopped
+pub struct SupervisedInfo {
+    /// The identifier of the stopped children group or supervisor.
+    pub id: BastionId,
+    /// Whether it is a children group or a supervisor.
+    pub kind: SupervisedKind,
+    /// Why it stopped.
+    pub stop_reason: StopReason,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+/// What happened to the supervisors and children groups that were
+/// running when the system was stopped using
+/// [`Bastion::stop_with_timeout`]: either they stopped cleanly
+/// before the deadline, or they had to be killed.
+///
+/// # Example
+///
+/// ```rust
+/// # use bastion::prelude::*;
+/// # use std::time::Duration;
+/// #
+/// # Bastion::init();
+/// # Bastion::start();
+/// #
+/// let report = Bastion::stop_with_timeout(Duration::from_secs(5));
+/// for info in report.killed() {
+///     println!("{} had to be killed.", info.id);
+/// }
+/// ```
+///
+/// [`Bastion::stop_with_timeout`]: ../struct.Bastion.html#method.stop_with_timeout
+pub struct ShutdownReport {
+    elems: Vec<SupervisedInfo>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+/// The kind of a supervised element (see [`SupervisedInfo`]).
+///
+/// [`SupervisedInfo`]: struct.SupervisedInfo.html
+pub enum SupervisedKind {
+    /// The supervised element is a children group.
+    Children,
+    /// The supervised element is a supervisor.
+    Supervisor,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+/// The reason why a supervised element stopped (see
+/// [`SupervisedInfo`]).
+///
+/// [`SupervisedInfo`]: struct.SupervisedInfo.html
+pub enum StopReason {
+    /// The supervised element stopped, either because it was
+    /// asked to or because all of its elements finished.
+    Stopped,
+    /// The supervised element faulted and wasn't restarted.
+    Faulted,
+    /// The supervised element was killed.
+    Killed,
+}
+
+#[derive(Debug, Clone)]
+/// A snapshot of a supervisor and of the children groups and
+/// supervisors it supervises, as returned by
+/// [`SupervisorRef::inspect`].
+///
+/// [`SupervisorRef::inspect`]: struct.SupervisorRef.html#method.inspect
+pub struct InspectReport {
+    /// The identifier of the supervisor.
+    pub id: BastionId,
+    /// The strategy the supervisor uses.
+    pub strategy: SupervisionStrategy,
+    /// The number of supervised elements that are running.
+    pub launched: usize,
+    /// The number of supervised elements that stopped or faulted.
+    pub stopped: usize,
+    /// The number of supervised elements that were killed.
+    pub killed: usize,
+    /// The supervised elements, in the order they were added to
+    /// the supervisor.
+    pub children: Vec<InspectedElement>,
+}
+
+#[derive(Debug, Clone)]
+/// A children group or supervisor supervised by an inspected
+/// supervisor (see [`InspectReport`]).
+///
+/// [`InspectReport`]: struct.InspectReport.html
+pub struct InspectedElement {
+    /// The identifier of the children group or supervisor.
+    pub id: BastionId,
+    /// Whether it is a children group or a supervisor.
+    pub kind: SupervisedKind,
+    /// The name of the children group, if it was given one (see
+    /// [`Children::with_name`]).
+    ///
+    /// [`Children::with_name`]: ../children/struct.Children.html#method.with_name
+    pub name: Option<String>,
+    /// The number of elements the children group runs, or `None`
+    /// if it is a supervisor.
+    pub redundancy: Option<usize>,
+    /// Why it stopped, or `None` if it is running.
+    pub stop_reason: Option<StopReason>,
+    /// The report of the supervisor, if it is a running one that
+    /// answered before stopping.
+    pub report: Option<InspectReport>,
+}
+
+#[derive(Debug, Clone)]
+/// A breadth-first iterator over the running children groups and
+/// supervisors of an [`InspectReport`] and, recursively, of the
+/// running supervisors among them, along with their depth (`0`
+/// for the ones supervised by the inspected supervisor itself).
+///
+/// See [`InspectReport::tree`].
+///
+/// It walks a snapshot of the tree rather than a live `&Supervisor`
+/// yielding `&Supervised` elements: a supervisor doesn't hold its
+/// running supervised elements, each of them being owned by its
+/// own process, so only an [`InspectReport`] (which they answer
+/// to) can describe them without stopping them.
+///
+/// [`InspectReport`]: struct.InspectReport.html
+/// [`InspectReport::tree`]: struct.InspectReport.html#method.tree
+pub struct SupervisionTree<'a> {
+    queue: VecDeque<(usize, &'a InspectedElement)>,
+}
+
+impl InspectReport {
+    /// Returns an iterator over the running children groups and
+    /// supervisors of the report and of the reports of the running
+    /// supervisors among them, breadth-first, along with their
+    /// depth.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use bastion::prelude::*;
+    /// #
+    /// # Bastion::init();
+    /// #
+    /// Bastion::supervisor(|sp| sp.children(|children| children.with_name("workers")))
+    ///     .expect("Couldn't create the supervisor.");
+    ///
+    /// Bastion::start();
+    ///
+    /// # run!(async {
+    /// let report = Bastion::inspect().await.expect("The system stopped.");
+    /// for (depth, elem) in report.tree() {
+    ///     println!("{}{:?}({})", "  ".repeat(depth), elem.kind, elem.id);
+    /// }
+    /// # });
+    /// #
+    /// # Bastion::stop();
+    /// # Bastion::block_until_stopped();
+    /// ```
+    pub fn tree(&self) -> SupervisionTree<'_> {
+        SupervisionTree {
+            queue: SupervisionTree::running(self, 0).collect(),
+        }
+    }
+
+    /// Renders the report as a [Graphviz DOT] graph, where
+    /// supervisors are boxes labelled with their identifier and
+    /// strategy, children groups are ellipses labelled with their
+    /// name and redundancy, and edges go from supervisors to the
+    /// elements they supervise.
+    ///
+    /// The elements are rendered in the order they were added to
+    /// their supervisor, so that diffing two renderings of the
+    /// same tree only shows what changed. Stopped elements are
+    /// dashed, faulted ones are filled in red and killed ones are
+    /// filled in grey.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use bastion::prelude::*;
+    /// #
+    /// # Bastion::init();
+    /// #
+    /// Bastion::children(|children| children.with_name("workers"))
+    ///     .expect("Couldn't create the children group.");
+    ///
+    /// Bastion::start();
+    ///
+    /// # run!(async {
+    /// let report = Bastion::inspect().await.expect("The system stopped.");
+    /// let dot = report.to_dot();
+    /// assert!(dot.contains("workers"));
+    /// # });
+    /// #
+    /// # Bastion::stop();
+    /// # Bastion::block_until_stopped();
+    /// ```
+    ///
+    /// [Graphviz DOT]: https://graphviz.org/doc/info/lang.html
+    pub fn to_dot(&self) -> String {
+        self.render_dot(DotStyle::Plain)
+    }
+
+    /// Renders the report as a [Graphviz DOT] graph like
+    /// [`to_dot`] does, but with the nodes filled depending on the
+    /// health of the elements: green when running, yellow when
+    /// stopped, red when faulted and grey when killed (see
+    /// [`Bastion::export_dot`]).
+    ///
+    /// [Graphviz DOT]: https://graphviz.org/doc/info/lang.html
+    /// [`to_dot`]: #method.to_dot
+    /// [`Bastion::export_dot`]: ../struct.Bastion.html#method.export_dot
+    pub fn to_health_dot(&self) -> String {
+        self.render_dot(DotStyle::Health)
+    }
+
+    fn render_dot(&self, style: DotStyle) -> String {
+        let mut dot = String::from("digraph bastion {\n");
+        dot.push_str(&dot_node(
+            &self.id,
+            SupervisedKind::Supervisor,
+            &[
+                format!("Supervisor({})", self.id),
+                format!("{:?}", self.strategy),
+            ],
+            None,
+            style,
+        ));
+        self.write_dot(&mut dot, style);
+        dot.push_str("}\n");
+
+        dot
+    }
+
+    fn write_dot(&self, dot: &mut String, style: DotStyle) {
+        for elem in &self.children {
+            let mut label = Vec::new();
+            match elem.kind {
+                SupervisedKind::Supervisor => {
+                    label.push(format!("Supervisor({})", elem.id));
+                    if let Some(report) = &elem.report {
+                        label.push(format!("{:?}", report.strategy));
+                    }
+                }
+                SupervisedKind::Children => {
+                    match &elem.name {
+                        Some(name) => label.push(name.clone()),
+                        None => label.push(format!("Children({})", elem.id)),
+                    }
+                    if let Some(redundancy) = elem.redundancy {
+                        label.push(format!("redundancy: {}", redundancy));
+                    }
+                }
+            }
+
+            dot.push_str(&dot_node(
+                &elem.id,
+                elem.kind,
+                &label,
+                elem.stop_reason,
+                style,
+            ));
+            dot.push_str(&format!("    \"{}\" -> \"{}\";\n", self.id, elem.id));
+            if let Some(report) = &elem.report {
+                report.write_dot(dot, style);
+            }
+        }
+    }
+}
+
+impl<'a> SupervisionTree<'a> {
+    fn running(
+        report: &'a InspectReport,
+        depth: usize,
+    ) -> impl Iterator<Item = (usize, &'a InspectedElement)> {
+        report
+            .children
+            .iter()
+            .filter(|elem| elem.stop_reason.is_none())
+            .map(move |elem| (depth, elem))
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+// How the nodes of a DOT graph are styled (see
+// `InspectReport::to_dot` and `InspectReport::to_health_dot`).
+enum DotStyle {
+    Plain,
+    Health,
+}
+
+// Renders a supervisor or children group as a DOT node, styled
+// depending on why it stopped.
+fn dot_node(
+    id: &BastionId,
+    kind: SupervisedKind,
+    label: &[String],
+    stop_reason: Option<StopReason>,
+    style: DotStyle,
+) -> String {
+    let shape = match kind {
+        SupervisedKind::Supervisor => "box",
+        SupervisedKind::Children => "ellipse",
+    };
+    let mut label = label
+        .iter()
+        .map(|line| line.replace('\\', "\\\\").replace('"', "\\\""))
+        .collect::<Vec<_>>();
+    let style = match (style, stop_reason) {
+        (DotStyle::Plain, None) => "",
+        (DotStyle::Plain, Some(StopReason::Stopped)) => ", style=dashed, color=gray",
+        (DotStyle::Health, None) => ", style=filled, fillcolor=green",
+        (DotStyle::Health, Some(StopReason::Stopped)) => ", style=filled, fillcolor=yellow",
+        (_, Some(StopReason::Faulted)) => ", style=filled, fillcolor=red",
+        (_, Some(StopReason::Killed)) => ", style=filled, fillcolor=gray",
+    };
+    if let Some(reason) = stop_reason {
+        label.push(format!("{:?}", reason).to_lowercase());
+    }
+
+    format!(
+        "    \"{}\" [shape={}, label=\"{}\"{}];\n",
+        id,
+        shape,
+        label.join("\\n"),
+        style
+    )
+}
+
+impl ShutdownReport {
+    pub(crate) fn new(elems: Vec<SupervisedInfo>) -> Self {
+        ShutdownReport { elems }
+    }
+
+    /// Returns the supervisors and children groups that stopped
+    /// cleanly before the deadline.
+    pub fn stopped(&self) -> impl Iterator<Item = &SupervisedInfo> {
+        self.elems
+            .iter()
+            .filter(|info| info.stop_reason == StopReason::Stopped)
+    }
+
+    /// Returns the supervisors and children groups that didn't
+    /// stop before the deadline and had to be killed.
+    pub fn killed(&self) -> impl Iterator<Item = &SupervisedInfo> {
+        self.elems
+            .iter()
+            .filter(|info| info.stop_reason == StopReason::Killed)
+    }
+
+    /// Returns whether all the supervisors and children groups
+    /// stopped cleanly before the deadline.
+    pub fn is_clean(&self) -> bool {
+        self.killed().next().is_none()
+    }
+}
+
 #[derive(Debug, Clone, Eq, PartialEq)]
 /// The strategy for restating an actor as far as it
 /// returned an failure.
@@ -226,6 +848,10 @@ pub enum ActorRestartStrategy {
 impl Supervisor {
     pub(crate) fn new(bcast: Broadcast) -> Self {
         debug!("Supervisor({}): Initializing.", bcast.id());
+        let name = None;
+        let slots = Arc::new(Slots::new());
+        let typed = Arc::new(TypedChildren::default());
+        let pid = next_pid();
         let order = Vec::new();
         let tracked_groups = FxHashMap::default();
         let tracked_groups_order = FxHashMap::default();
@@ -234,15 +860,33 @@ impl Supervisor {
         let killed = FxHashMap::default();
         let strategy = SupervisionStrategy::default();
         let restart_strategy = RestartStrategy::default();
-        let callbacks = Callbacks::new();
+        let callbacks = bcast.system().config().default_callbacks().clone();
         let is_system_supervisor = false;
         let pre_start_msgs = Vec::new();
         let started = false;
+        let invalid = AtomicBool::new(false);
         let subtree_restarts = 0;
         let subtree_restarts_limit = 3;
+        let routing = Routing::default();
+        let next_route = 0;
+        let tags = FxHashMap::default();
+        let groups = FxHashMap::default();
+        let restart_window = None;
+        let state_backend = None;
+        let orphan_policy = OrphanPolicy::default();
+        let order_guarantee = OrderGuarantee::default();
+        let deploys = 0;
+        let faults = 0;
+        let one_for_one_restarts = 0;
+        let one_for_all_restarts = 0;
+        let rest_for_one_restarts = 0;
 
         Supervisor {
             bcast,
+            name,
+            slots,
+            typed,
+            pid,
             order,
             tracked_groups,
             tracked_groups_order,
@@ -255,22 +899,41 @@ impl Supervisor {
             is_system_supervisor,
             pre_start_msgs,
             started,
+            invalid,
             subtree_restarts,
             subtree_restarts_limit,
+            routing,
+            next_route,
+            tags,
+            groups,
+            restart_window,
+            state_backend,
+            orphan_policy,
+            order_guarantee,
+            deploys,
+            faults,
+            one_for_one_restarts,
+            one_for_all_restarts,
+            rest_for_one_restarts,
         }
     }
 
     pub(crate) fn system(bcast: Broadcast) -> Self {
         let mut supervisor = Supervisor::new(bcast);
         supervisor.is_system_supervisor = true;
+        supervisor.strategy = supervisor.bcast.system().config().system_strategy().clone();
+        supervisor.callbacks = Callbacks::new();
 
         supervisor
     }
 
     fn stack(&self) -> ProcStack {
         trace!("Supervisor({}): Creating ProcStack.", self.id());
-        // FIXME: with_pid
+        // Supervisors get run before the children they supervise,
+        // for faults to get handled even when the pool is busy.
         ProcStack::default()
+            .with_pid(self.pid)
+            .with_high_priority(true)
     }
 
     pub(crate) async fn reset(&mut self, bcast: Option<Broadcast>) {
@@ -288,14 +951,24 @@ impl Supervisor {
             );
         }
 
+        let id = self.id().clone();
+        self.checkpoint();
         // TODO: stop or kill?
         self.kill(0..self.order.len()).await;
+        self.restore(&id);
 
         if let Some(bcast) = bcast {
             self.bcast = bcast;
         } else {
             self.bcast.clear_children();
         }
+        self.pid = next_pid();
+
+        // The restarted supervisor gets to restart its supervised
+        // elements again.
+        if let Some(restart_window) = &mut self.restart_window {
+            restart_window.clear();
+        }
 
         debug!(
             "Supervisor({}): Removing {} pre-start messages.",
@@ -362,6 +1035,18 @@ impl Supervisor {
         &self.callbacks
     }
 
+    pub(crate) fn pid(&self) -> usize {
+        self.pid
+    }
+
+    pub(crate) fn given_name(&self) -> Option<&String> {
+        self.name.as_ref()
+    }
+
+    pub(crate) fn is_invalid(&self) -> bool {
+        self.invalid.load(Ordering::SeqCst)
+    }
+
     pub(crate) fn as_ref(&self) -> SupervisorRef {
         trace!(
             "Supervisor({}): Creating new SupervisorRef({}).",
@@ -372,8 +1057,13 @@ impl Supervisor {
         let id = self.bcast.id().clone();
         let sender = self.bcast.sender().clone();
         let path = self.bcast.path().clone();
+        let system = self.bcast.system().clone();
 
-        SupervisorRef::new(id, sender, path)
+        let name = self.name.clone();
+        let slots = self.slots.clone();
+        let typed = self.typed.clone();
+
+        SupervisorRef::new(id, self.pid, sender, path, name, slots, typed, system)
     }
 
     /// Creates a new supervisor, passes it through the specified
@@ -426,12 +1116,18 @@ impl Supervisor {
         let supervisor = Supervisor::new(bcast);
         let supervisor = init(supervisor);
         debug!("Supervisor({}): Initialized.", supervisor.id());
+        if supervisor.is_invalid() {
+            self.invalid.store(true, Ordering::SeqCst);
+            return self;
+        }
 
         debug!(
             "Supervisor({}): Deploying Supervisor({}).",
             self.id(),
             supervisor.id()
         );
+        self.slots.add();
+        self.typed.register_supervisor(&supervisor);
         let msg = BastionMessage::deploy_supervisor(supervisor);
         let env = Envelope::new(msg, self.bcast.path().clone(), self.bcast.sender().clone());
         self.bcast.send_self(env);
@@ -491,12 +1187,18 @@ impl Supervisor {
         let supervisor = init(supervisor);
         debug!("Supervisor({}): Initialized.", supervisor.id());
         let supervisor_ref = supervisor.as_ref();
+        if supervisor.is_invalid() {
+            self.invalid.store(true, Ordering::SeqCst);
+            return supervisor_ref;
+        }
 
         debug!(
             "Supervisor({}): Deploying Supervisor({}).",
             self.id(),
             supervisor.id()
         );
+        self.slots.add();
+        self.typed.register_supervisor(&supervisor);
         let msg = BastionMessage::deploy_supervisor(supervisor);
         let env = Envelope::new(msg, self.bcast.path().clone(), self.bcast.sender().clone());
         self.bcast.send_self(env);
@@ -563,6 +1265,10 @@ impl Supervisor {
         let children = Children::new(bcast);
         let mut children = init(children);
         debug!("Children({}): Initialized.", children.id());
+        if children.check_affinity().is_err() {
+            self.invalid.store(true, Ordering::SeqCst);
+            return self;
+        }
         // FIXME: children group elems launched without the group itself being launched
         if let Err(e) = children.register_dispatchers() {
             warn!("couldn't register all dispatchers into the registry: {}", e);
@@ -574,6 +1280,8 @@ impl Supervisor {
             self.id(),
             children.id()
         );
+        self.slots.add();
+        self.typed.register_children(&children);
         let msg = BastionMessage::deploy_children(children);
         let env = Envelope::new(msg, self.bcast.path().clone(), self.bcast.sender().clone());
         self.bcast.send_self(env);
@@ -641,6 +1349,10 @@ impl Supervisor {
         let children = Children::new(bcast);
         let mut children = init(children);
         debug!("Children({}): Initialized.", children.id());
+        if children.check_affinity().is_err() {
+            self.invalid.store(true, Ordering::SeqCst);
+            return children.as_ref();
+        }
         // FIXME: children group elems launched without the group itself being launched
         children.launch_elems();
 
@@ -650,6 +1362,8 @@ impl Supervisor {
             self.id(),
             children.id()
         );
+        self.slots.add();
+        self.typed.register_children(&children);
         let msg = BastionMessage::deploy_children(children);
         let env = Envelope::new(msg, self.bcast.path().clone(), self.bcast.sender().clone());
         self.bcast.send_self(env);
@@ -657,6 +1371,88 @@ impl Supervisor {
         children_ref
     }
 
+    /// Sets the name of this supervisor, which is kept when it is
+    /// restarted and shows up in the debug output of the supervisor
+    /// and of the [`SupervisorRef`]s referencing it, in its
+    /// lifecycle events and in its logs.
+    ///
+    /// This method returns `self` to allow chaining calls.
+    ///
+    /// # Arguments
+    ///
+    /// * `name` - The name of the supervisor.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use bastion::prelude::*;
+    /// #
+    /// # Bastion::init();
+    /// #
+    /// let sp_ref = Bastion::supervisor(|sp| {
+    ///     sp.with_name("workers-supervisor")
+    /// }).expect("Couldn't create the supervisor.");
+    ///
+    /// assert_eq!(sp_ref.name(), Some("workers-supervisor"));
+    /// #
+    /// # Bastion::start();
+    /// # Bastion::stop();
+    /// # Bastion::block_until_stopped();
+    /// ```
+    ///
+    /// [`SupervisorRef`]: struct.SupervisorRef.html
+    pub fn with_name(mut self, name: impl Into<String>) -> Self {
+        self.name = Some(name.into());
+        trace!("Supervisor({}): Setting name: {:?}", self.id(), self.name);
+        self
+    }
+
+    /// Sets the maximum number of supervised children groups and
+    /// supervisors this supervisor launches, preventing it from
+    /// growing without bound when they are added dynamically (e.g.
+    /// one children group per request).
+    ///
+    /// Once the supervisor reached its capacity,
+    /// [`SupervisorRef::children`] and [`SupervisorRef::supervisor`]
+    /// return `Err(())` until some of the supervised elements stop.
+    ///
+    /// This method returns `self` to allow chaining calls.
+    ///
+    /// # Arguments
+    ///
+    /// * `max_launched` - The maximum number of supervised children
+    ///     groups and supervisors.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use bastion::prelude::*;
+    /// #
+    /// # Bastion::init();
+    /// #
+    /// let sp_ref = Bastion::supervisor(|sp| sp.with_capacity(1))
+    ///     .expect("Couldn't create the supervisor.");
+    ///
+    /// assert!(sp_ref.children(|children| children).is_ok());
+    /// assert!(sp_ref.children(|children| children).is_err());
+    /// #
+    /// # Bastion::start();
+    /// # Bastion::stop();
+    /// # Bastion::block_until_stopped();
+    /// ```
+    ///
+    /// [`SupervisorRef::children`]: struct.SupervisorRef.html#method.children
+    /// [`SupervisorRef::supervisor`]: struct.SupervisorRef.html#method.supervisor
+    pub fn with_capacity(self, max_launched: usize) -> Self {
+        trace!(
+            "Supervisor({}): Setting capacity: {}",
+            self.id(),
+            max_launched
+        );
+        self.slots.set_capacity(max_launched);
+        self
+    }
+
     /// Sets the strategy the supervisor should use when one
     /// of its supervised children groups or supervisors dies
     /// (in the case of a children group, it could be because one
@@ -712,6 +1508,56 @@ impl Supervisor {
         self
     }
 
+    /// Sets the way the supervisor should deliver the user
+    /// messages it receives to its supervised children groups and
+    /// supervisors, allowing it to act as a router.
+    ///
+    /// The default routing is [`Routing::Broadcast`].
+    ///
+    /// # Arguments
+    ///
+    /// * `routing` - The routing to use:
+    ///     - [`Routing::Broadcast`] would send every message to all
+    ///         the supervised children groups and supervisors.
+    ///     - [`Routing::RoundRobin`] would send each message to only
+    ///         one of them, taking turns.
+    ///     - [`Routing::ByKey`] would send each message to only the
+    ///         one chosen by the given function.
+    ///     - [`Routing::TagBased`] would send every message to all
+    ///         the children groups whose labels match the given
+    ///         predicate.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use bastion::prelude::*;
+    /// #
+    /// # Bastion::init();
+    /// #
+    /// let sp_ref = Bastion::supervisor(|sp| {
+    ///     sp.with_routing(Routing::RoundRobin)
+    ///         .children(|children| children)
+    ///         .children(|children| children)
+    /// }).expect("Couldn't create the supervisor");
+    ///
+    /// // Only one of the children groups will receive the message.
+    /// sp_ref.broadcast("A message.").expect("Couldn't send the message.");
+    /// #
+    /// # Bastion::start();
+    /// # Bastion::stop();
+    /// # Bastion::block_until_stopped();
+    /// ```
+    ///
+    /// [`Routing::Broadcast`]: supervisor/enum.Routing.html#variant.Broadcast
+    /// [`Routing::RoundRobin`]: supervisor/enum.Routing.html#variant.RoundRobin
+    /// [`Routing::ByKey`]: supervisor/enum.Routing.html#variant.ByKey
+    /// [`Routing::TagBased`]: supervisor/enum.Routing.html#variant.TagBased
+    pub fn with_routing(mut self, routing: Routing) -> Self {
+        trace!("Supervisor({}): Setting routing: {:?}", self.id(), routing);
+        self.routing = routing;
+        self
+    }
+
     /// Sets the actor restart strategy the supervisor should use
     /// of its supervised children groups or supervisors dies to
     /// restore in the correct state.
@@ -753,16 +1599,20 @@ impl Supervisor {
         self
     }
 
-    /// Sets the callbacks that will get called at this supervisor's
-    /// different lifecycle events.
+    /// Sets the restart policy the supervisor should use when one
+    /// of the elements of its supervised children groups faults,
+    /// keeping the delay it waits before restarting them (see
+    /// [`Supervisor::with_restart_strategy`]).
     ///
-    /// See [`Callbacks`]'s documentation for more information about the
-    /// different callbacks available.
+    /// The default policy is [`RestartPolicy::Always`], while
+    /// [`RestartPolicy::OnError`] only restarts the elements whose
+    /// future returned an error, escalating the panics to the
+    /// supervisor's own supervisor, and [`RestartPolicy::Never`]
+    /// removes the faulted elements from their group.
     ///
     /// # Arguments
     ///
-    /// * `callbacks` - The callbacks that will get called for this
-    ///     supervisor.
+    /// * `restart_policy` - The restart policy to use.
     ///
     /// # Example
     ///
@@ -772,11 +1622,15 @@ impl Supervisor {
     /// # Bastion::init();
     /// #
     /// Bastion::supervisor(|sp| {
-    ///     let callbacks = Callbacks::new()
-    ///         .with_before_start(|| println!("Supervisor started."))
-    ///         .with_after_stop(|| println!("Supervisor stopped."));
-    ///
-    ///     sp.with_callbacks(callbacks)
+    ///     sp.with_restart_policy(RestartPolicy::OnError)
+    ///         .children(|children| {
+    ///             children.with_exec(|ctx| async move {
+    ///                 // Returning an error gets this element
+    ///                 // restarted, while panicking gets its
+    ///                 // supervisor restarted.
+    ///                 # Ok(())
+    ///             })
+    ///         })
     /// }).expect("Couldn't create the supervisor.");
     /// #
     /// # Bastion::start();
@@ -784,8 +1638,313 @@ impl Supervisor {
     /// # Bastion::block_until_stopped();
     /// ```
     ///
-    /// [`Callbacks`]: struct.Callbacks.html
-    pub fn with_callbacks(mut self, callbacks: Callbacks) -> Self {
+    /// [`Supervisor::with_restart_strategy`]: #method.with_restart_strategy
+    /// [`RestartPolicy::Always`]: enum.RestartPolicy.html#variant.Always
+    /// [`RestartPolicy::OnError`]: enum.RestartPolicy.html#variant.OnError
+    /// [`RestartPolicy::Never`]: enum.RestartPolicy.html#variant.Never
+    pub fn with_restart_policy(mut self, restart_policy: RestartPolicy) -> Self {
+        trace!(
+            "Supervisor({}): Setting restart policy: {:?}",
+            self.id(),
+            restart_policy
+        );
+        self.restart_strategy.restart_policy = restart_policy;
+        self
+    }
+
+    /// Sets the maximum number of restarts the supervisor accepts
+    /// to do within a sliding window of time. If one of its
+    /// supervised children groups or supervisors faults once this
+    /// number was reached, the supervisor stops all of them and
+    /// escalates the fault to its own supervisor instead of
+    /// recovering from it.
+    ///
+    /// By default, the supervisor never escalates.
+    ///
+    /// This method returns `self` to allow chaining calls.
+    ///
+    /// # Arguments
+    ///
+    /// * `restart_window` - The maximum number of restarts and the
+    ///     duration of the window they are counted within.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use bastion::prelude::*;
+    /// # use std::time::Duration;
+    /// #
+    /// # Bastion::init();
+    /// #
+    /// Bastion::supervisor(|sp| {
+    ///     // Escalating after more than 3 restarts within 5 seconds.
+    ///     sp.with_restart_window(RestartWindow::new(3, Duration::from_secs(5)))
+    /// }).expect("Couldn't create the supervisor.");
+    /// #
+    /// # Bastion::start();
+    /// # Bastion::stop();
+    /// # Bastion::block_until_stopped();
+    /// ```
+    pub fn with_restart_window(mut self, restart_window: RestartWindow) -> Self {
+        trace!(
+            "Supervisor({}): Setting restart window: {:?}",
+            self.id(),
+            restart_window
+        );
+        self.restart_window = Some(restart_window);
+        self
+    }
+
+    /// Sets what the supervisor does with its running supervised
+    /// children groups and supervisors if it faults because it
+    /// can't recover from one of them faulting.
+    ///
+    /// By default, they are killed along with the supervisor.
+    ///
+    /// This method returns `self` to allow chaining calls.
+    ///
+    /// # Arguments
+    ///
+    /// * `orphan_policy` - What happens to the supervised elements
+    ///     that are still running when the supervisor faults.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use bastion::prelude::*;
+    /// # use std::time::Duration;
+    /// #
+    /// # Bastion::init();
+    /// #
+    /// let backup = Bastion::supervisor(|sp| sp).expect("Couldn't create the supervisor.");
+    ///
+    /// Bastion::supervisor(|sp| {
+    ///     // Giving the supervised elements to `backup` when
+    ///     // escalating after more than 3 restarts within 5 seconds.
+    ///     sp.with_restart_window(RestartWindow::new(3, Duration::from_secs(5)))
+    ///         .with_orphan_policy(OrphanPolicy::Adopt(backup))
+    /// }).expect("Couldn't create the supervisor.");
+    /// #
+    /// # Bastion::start();
+    /// # Bastion::stop();
+    /// # Bastion::block_until_stopped();
+    /// ```
+    pub fn with_orphan_policy(mut self, orphan_policy: OrphanPolicy) -> Self {
+        trace!(
+            "Supervisor({}): Setting orphan policy: {:?}",
+            self.id(),
+            orphan_policy
+        );
+        self.orphan_policy = orphan_policy;
+        self
+    }
+
+    /// Sets in which order the supervisor sends the messages
+    /// restarting its supervised elements when it restarts several
+    /// of them at once (e.g. when using
+    /// [`SupervisionStrategy::OneForAll`] or once it got reset).
+    ///
+    /// By default, each message is sent as soon as possible
+    /// ([`OrderGuarantee::None`]).
+    ///
+    /// This method returns `self` to allow chaining calls.
+    ///
+    /// # Arguments
+    ///
+    /// * `guarantee` - In which order the messages restarting the
+    ///     supervised elements are sent.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use bastion::prelude::*;
+    /// #
+    /// # Bastion::init();
+    /// #
+    /// Bastion::supervisor(|sp| {
+    ///     // Restarting the elements in the order they were added.
+    ///     sp.with_strategy(SupervisionStrategy::OneForAll)
+    ///         .with_order_guarantee(OrderGuarantee::Global)
+    /// }).expect("Couldn't create the supervisor.");
+    /// #
+    /// # Bastion::start();
+    /// # Bastion::stop();
+    /// # Bastion::block_until_stopped();
+    /// ```
+    ///
+    /// [`SupervisionStrategy::OneForAll`]: enum.SupervisionStrategy.html#variant.OneForAll
+    /// [`OrderGuarantee::None`]: enum.OrderGuarantee.html#variant.None
+    pub fn with_order_guarantee(mut self, guarantee: OrderGuarantee) -> Self {
+        trace!(
+            "Supervisor({}): Setting order guarantee: {:?}",
+            self.id(),
+            guarantee
+        );
+        self.order_guarantee = guarantee;
+        self
+    }
+
+    /// Makes the supervisor checkpoint its state (the order its
+    /// supervised children groups and supervisors were added in and
+    /// its [`SupervisionStrategy`], which might have been changed
+    /// using [`SupervisorRef::strategy`]) to the given backend each
+    /// time before it stops, and restore it when it is restarted.
+    ///
+    /// The state is saved under the identifier the supervisor had
+    /// when stopping. Failing to save or restore it is logged and
+    /// doesn't prevent the supervisor from stopping or restarting.
+    ///
+    /// This method returns `self` to allow chaining calls.
+    ///
+    /// # Arguments
+    ///
+    /// * `backend` - The backend to checkpoint the state to.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use bastion::prelude::*;
+    /// # use bastion::state_backend::FileStateBackend;
+    /// # use std::sync::Arc;
+    /// #
+    /// # Bastion::init();
+    /// #
+    /// let backend = FileStateBackend::new(std::env::temp_dir().join("bastion-states"));
+    ///
+    /// Bastion::supervisor(|sp| {
+    ///     sp.with_state_persistence(Arc::new(backend))
+    /// }).expect("Couldn't create the supervisor.");
+    /// #
+    /// # Bastion::start();
+    /// # Bastion::stop();
+    /// # Bastion::block_until_stopped();
+    /// ```
+    ///
+    /// [`SupervisionStrategy`]: enum.SupervisionStrategy.html
+    /// [`SupervisorRef::strategy`]: struct.SupervisorRef.html#method.strategy
+    pub fn with_state_persistence(mut self, backend: Arc<dyn StateBackend>) -> Self {
+        trace!(
+            "Supervisor({}): Setting state backend: {:?}",
+            self.id(),
+            backend
+        );
+        self.state_backend = Some(backend);
+        self
+    }
+
+    // Saves the supervisor's state to its backend, if it has one.
+    fn checkpoint(&self) {
+        let backend = match &self.state_backend {
+            Some(backend) => backend,
+            None => return,
+        };
+
+        debug!("Supervisor({}): Checkpointing state.", self.id());
+        let checkpoint = Checkpoint {
+            order: self.order.clone(),
+            strategy: self.strategy.clone(),
+        };
+        let state = match serde_json::to_vec(&checkpoint) {
+            Ok(state) => state,
+            Err(err) => {
+                error!(
+                    "Supervisor({}): Couldn't serialize state: {}",
+                    self.id(),
+                    err
+                );
+                return;
+            }
+        };
+        if let Err(err) = backend.save(self.id(), &state) {
+            error!("Supervisor({}): Couldn't save state: {:?}", self.id(), err);
+        }
+    }
+
+    // Restores the state saved to the supervisor's backend (if it
+    // has one) under the given identifier. This must only be called
+    // once every supervised element was stopped or killed.
+    fn restore(&mut self, id: &BastionId) {
+        let backend = match &self.state_backend {
+            Some(backend) => backend,
+            None => return,
+        };
+
+        let checkpoint = match backend.load(id) {
+            Ok(Some(state)) => serde_json::from_slice::<Checkpoint>(&state)
+                .map_err(|err| {
+                    error!(
+                        "Supervisor({}): Couldn't deserialize state: {}",
+                        self.id(),
+                        err
+                    )
+                })
+                .ok(),
+            Ok(None) => None,
+            Err(err) => {
+                error!("Supervisor({}): Couldn't load state: {:?}", self.id(), err);
+                None
+            }
+        };
+        let checkpoint = match checkpoint {
+            Some(checkpoint) => checkpoint,
+            None => return,
+        };
+
+        debug!(
+            "Supervisor({}): Restoring state: {:?}",
+            self.id(),
+            checkpoint
+        );
+        self.strategy = checkpoint.strategy;
+        // The order can only be restored if it is the one of the
+        // elements that are still supervised.
+        let mut restored = checkpoint.order.clone();
+        let mut current = self.order.clone();
+        restored.sort_by_key(|id| id.0);
+        current.sort_by_key(|id| id.0);
+        if restored == current {
+            self.order = checkpoint.order;
+        } else {
+            warn!(
+                "Supervisor({}): Not restoring the order of elements that aren't supervised anymore.",
+                self.id()
+            );
+        }
+    }
+
+    /// Sets the callbacks that will get called at this supervisor's
+    /// different lifecycle events.
+    ///
+    /// See [`Callbacks`]'s documentation for more information about the
+    /// different callbacks available.
+    ///
+    /// # Arguments
+    ///
+    /// * `callbacks` - The callbacks that will get called for this
+    ///     supervisor.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use bastion::prelude::*;
+    /// #
+    /// # Bastion::init();
+    /// #
+    /// Bastion::supervisor(|sp| {
+    ///     let callbacks = Callbacks::new()
+    ///         .with_before_start(|| println!("Supervisor started."))
+    ///         .with_after_stop(|| println!("Supervisor stopped."));
+    ///
+    ///     sp.with_callbacks(callbacks)
+    /// }).expect("Couldn't create the supervisor.");
+    /// #
+    /// # Bastion::start();
+    /// # Bastion::stop();
+    /// # Bastion::block_until_stopped();
+    /// ```
+    ///
+    /// [`Callbacks`]: struct.Callbacks.html
+    pub fn with_callbacks(mut self, callbacks: Callbacks) -> Self {
         trace!(
             "Supervisor({}): Setting callbacks: {:?}",
             self.id(),
@@ -801,15 +1960,43 @@ impl Supervisor {
             self.id(),
             objects.len()
         );
-        let mut restart_futures = FuturesOrdered::new();
+        let mut restart_futures = Vec::with_capacity(objects.len());
+        let restart_strategy = self.restart_strategy.clone();
+        // Each message is sent once the restart strategy allows it
+        // (if it restarts an element).
+        let restart_after = |backoff: Option<usize>, receiver: BastionId, msg| {
+            let restart_strategy = restart_strategy.clone();
+            let restart_future = {
+                let receiver = receiver.clone();
+                async move {
+                    if let Some(restarts_count) = backoff {
+                        restart_strategy.apply_strategy(restarts_count).await;
+                    }
+
+                    (receiver, msg)
+                }
+            };
+
+            (receiver, restart_future)
+        };
 
         for object in objects {
             match object {
                 RestartedElement::Supervisor(supervisor_id) => {
+                    lifecycle_event!(
+                        "supervisor_restarted",
+                        supervisor = %self.id(),
+                        restarted = %supervisor_id,
+                    );
+                    self.bcast.system().events().publish(
+                        Transition::Restarted,
+                        EntityKind::Supervisor,
+                        &supervisor_id,
+                        Some(self.id()),
+                        None,
+                    );
                     let msg = BastionMessage::restart_subtree();
-                    let env =
-                        Envelope::new(msg, self.bcast.path().clone(), self.bcast.sender().clone());
-                    self.bcast.send_child(&supervisor_id, env);
+                    restart_futures.push(restart_after(None, supervisor_id, msg));
                 }
                 RestartedElement::Child { id, parent_id } => {
                     let index = match self.tracked_groups_order.get(&id) {
@@ -827,7 +2014,7 @@ impl Supervisor {
                     let restarts_count = tracked_state.restarts_count();
 
                     let restart_required = match self.restart_strategy.restart_policy() {
-                        RestartPolicy::Always => true,
+                        RestartPolicy::Always | RestartPolicy::OnError => true,
                         RestartPolicy::Never => false,
                         RestartPolicy::Tries(max_retries) => restarts_count < max_retries,
                     };
@@ -843,23 +2030,338 @@ impl Supervisor {
                             BastionMessage::drop_child(id)
                         }
                     };
-                    let restart_strategy = self.restart_strategy.clone();
+                    let backoff = if restart_required {
+                        Some(restarts_count)
+                    } else {
+                        None
+                    };
+                    restart_futures.push(restart_after(backoff, parent_id, msg));
+                }
+            }
+        }
 
-                    restart_futures.push(async move {
-                        if restart_required {
-                            restart_strategy.apply_strategy(restarts_count).await;
-                        }
+        let mut restart_msgs = match self.order_guarantee {
+            OrderGuarantee::None => restart_futures
+                .into_iter()
+                .map(|(_, restart_future)| restart_future)
+                .collect::<FuturesUnordered<_>>()
+                .boxed(),
+            OrderGuarantee::PerActor => {
+                let mut per_actor: FxHashMap<_, FuturesOrdered<_>> = FxHashMap::default();
+                for (receiver, restart_future) in restart_futures {
+                    per_actor
+                        .entry(receiver)
+                        .or_default()
+                        .push_back(restart_future);
+                }
+
+                stream::select_all(per_actor.into_values()).boxed()
+            }
+            OrderGuarantee::Global => restart_futures
+                .into_iter()
+                .map(|(_, restart_future)| restart_future)
+                .collect::<FuturesOrdered<_>>()
+                .boxed(),
+        };
+
+        while let Some((receiver, msg)) = restart_msgs.next().await {
+            let env = Envelope::new(msg, self.bcast.path().clone(), self.bcast.sender().clone());
+            self.bcast.send_child(&receiver, env);
+        }
+    }
+
+    // Returns the stopped and killed supervised elements, in the
+    // order they were added to the supervisor.
+    fn list_stopped(&self) -> Vec<SupervisedInfo> {
+        self.order
+            .iter()
+            .filter_map(|id| {
+                let (stop_reason, supervised) = match self.stopped.get(id) {
+                    Some((reason, supervised)) => (*reason, supervised),
+                    None => (StopReason::Killed, self.killed.get(id)?),
+                };
+
+                Some(SupervisedInfo {
+                    id: id.clone(),
+                    kind: supervised.kind(),
+                    stop_reason,
+                })
+            })
+            .collect()
+    }
+
+    // Answers with a report of the supervised elements, once the
+    // running supervisors among them answered with their own.
+    fn inspect(&self, reply: oneshot::Sender<InspectReport>) {
+        let mut children = Vec::with_capacity(self.order.len());
+        let mut reports = FuturesOrdered::new();
+        for id in &self.order {
+            // The dead letters' children group isn't reported.
+            if id == &NIL_ID {
+                continue;
+            }
+
+            let (kind, stop_reason) = if self.launched.contains_key(id) {
+                if self.tracked_groups.contains_key(id) {
+                    (SupervisedKind::Children, None)
+                } else {
+                    (SupervisedKind::Supervisor, None)
+                }
+            } else if let Some((reason, supervised)) = self.stopped.get(id) {
+                (supervised.kind(), Some(*reason))
+            } else if let Some(supervised) = self.killed.get(id) {
+                (supervised.kind(), Some(StopReason::Killed))
+            } else {
+                continue;
+            };
+
+            if kind == SupervisedKind::Supervisor && stop_reason.is_none() {
+                let (sender, receiver) = oneshot::channel();
+                let msg = BastionMessage::inspect(sender);
+                let env =
+                    Envelope::new(msg, self.bcast.path().clone(), self.bcast.sender().clone());
+                self.bcast.send_child(id, env);
+
+                let index = children.len();
+                reports.push_back(receiver.map(move |report| (index, report.ok())));
+            }
+
+            let (name, redundancy) = match self.groups.get(id) {
+                Some((name, redundancy)) => (name.clone(), Some(*redundancy)),
+                None => (None, None),
+            };
+            children.push(InspectedElement {
+                id: id.clone(),
+                kind,
+                name,
+                redundancy,
+                stop_reason,
+                report: None,
+            });
+        }
+
+        let count = |reason| {
+            children
+                .iter()
+                .filter(|elem| elem.stop_reason == reason)
+                .count()
+        };
+        let killed = count(Some(StopReason::Killed));
+        let launched = count(None);
+        let mut report = InspectReport {
+            id: self.id().clone(),
+            strategy: self.strategy.clone(),
+            launched,
+            stopped: children.len() - launched - killed,
+            killed,
+            children,
+        };
+
+        // The supervised supervisors are waited for without
+        // blocking the supervisor, which might be one of their
+        // parents' supervised elements too.
+        executor::spawn_proc(
+            async move {
+                while let Some((index, sub_report)) = reports.next().await {
+                    report.children[index].report = sub_report;
+                }
+
+                trace!("Supervisor({}): Inspected: {:?}", report.id, report);
+                // The sender might have stopped waiting for it.
+                reply.send(report).ok();
+            },
+            ProcStack::default(),
+        );
+    }
+
+    // Answers with the supervisor's state and the ones of its
+    // running supervised elements that answered before the
+    // deadline (see `Bastion::dump_state`).
+    fn dump_state(&self, reply: oneshot::Sender<DumpedElement>, deadline: Instant) {
+        let mut dump = SupervisorDump {
+            id: self.id().clone(),
+            name: self.name.clone(),
+            strategy: self.strategy.clone(),
+            started: self.started,
+            pre_start_msgs: self.pre_start_msgs.len(),
+            order: Vec::with_capacity(self.order.len()),
+            launched: Vec::new(),
+            stopped: Vec::new(),
+            killed: Vec::new(),
+            elems: Vec::new(),
+        };
+
+        let sub_deadline = dump::sub_deadline(deadline);
+        let mut elems = FuturesOrdered::new();
+        for id in &self.order {
+            // The dead letters' children group isn't reported.
+            if id == &NIL_ID {
+                continue;
+            }
+
+            dump.order.push(id.clone());
+            if self.stopped.contains_key(id) {
+                dump.stopped.push(id.clone());
+                continue;
+            } else if self.killed.contains_key(id) {
+                dump.killed.push(id.clone());
+                continue;
+            } else if !self.launched.contains_key(id) {
+                continue;
+            }
 
-                        (parent_id, msg)
-                    });
+            dump.launched.push(id.clone());
+            let id = id.clone();
+            if self.tracked_groups.contains_key(&id) {
+                let name = self.groups.get(&id).and_then(|(name, _)| name.clone());
+                // Children groups only answer once started.
+                if !self.started {
+                    let elem = DumpedElement::Children {
+                        id,
+                        name,
+                        mailbox_lens: None,
+                    };
+                    elems.push_back(future::ready(elem).boxed());
+                    continue;
                 }
+
+                let (sender, receiver) = oneshot::channel();
+                let msg = BastionMessage::mailbox_lens(sender);
+                let env =
+                    Envelope::new(msg, self.bcast.path().clone(), self.bcast.sender().clone());
+                self.bcast.send_child(&id, env);
+                elems.push_back(
+                    async move {
+                        match dump::answer_before(receiver, sub_deadline).await {
+                            Some(lens) => DumpedElement::Children {
+                                id,
+                                name,
+                                mailbox_lens: Some(lens),
+                            },
+                            None => DumpedElement::Unresponsive {
+                                id,
+                                kind: SupervisedKind::Children,
+                            },
+                        }
+                    }
+                    .boxed(),
+                );
+            } else {
+                let (sender, receiver) = oneshot::channel();
+                let msg = BastionMessage::dump_state(sender, sub_deadline);
+                let env =
+                    Envelope::new(msg, self.bcast.path().clone(), self.bcast.sender().clone());
+                self.bcast.send_child(&id, env);
+                elems.push_back(
+                    async move {
+                        dump::answer_before(receiver, sub_deadline).await.unwrap_or(
+                            DumpedElement::Unresponsive {
+                                id,
+                                kind: SupervisedKind::Supervisor,
+                            },
+                        )
+                    }
+                    .boxed(),
+                );
             }
         }
 
-        while let Some((receiver, msg)) = restart_futures.next().await {
+        // Like when inspecting, the supervised elements are waited
+        // for without blocking the supervisor.
+        executor::spawn_proc(
+            async move {
+                dump.elems = elems.collect().await;
+                trace!("Supervisor({}): Dumped state: {:?}", dump.id, dump);
+                // The sender might have stopped waiting for it.
+                reply.send(DumpedElement::Supervisor(dump)).ok();
+            },
+            ProcStack::default(),
+        );
+    }
+
+    // Answers with the supervisor's counters and the ones of its
+    // subtree, once its running supervised elements answered.
+    fn metrics(&self, reply: oneshot::Sender<MetricsSnapshot>) {
+        let mut snapshot = MetricsSnapshot::default();
+        snapshot.supervisors.push(SupervisorMetrics {
+            id: self.id().clone(),
+            deploys: self.deploys,
+            faults: self.faults,
+            one_for_one_restarts: self.one_for_one_restarts,
+            one_for_all_restarts: self.one_for_all_restarts,
+            rest_for_one_restarts: self.rest_for_one_restarts,
+        });
+
+        let mut snapshots = FuturesOrdered::new();
+        for id in &self.order {
+            // The dead letters' children group isn't reported.
+            if id == &NIL_ID || !self.launched.contains_key(id) {
+                continue;
+            }
+
+            let (sender, receiver) = oneshot::channel();
+            let msg = BastionMessage::metrics(sender);
             let env = Envelope::new(msg, self.bcast.path().clone(), self.bcast.sender().clone());
-            self.bcast.send_child(&receiver, env);
+            self.bcast.send_child(id, env);
+            snapshots.push_back(receiver);
         }
+
+        // Like when inspecting, the supervised elements are waited
+        // for without blocking the supervisor.
+        executor::spawn_proc(
+            async move {
+                while let Some(sub_snapshot) = snapshots.next().await {
+                    // The supervised element might have stopped
+                    // without answering.
+                    if let Ok(sub_snapshot) = sub_snapshot {
+                        snapshot.merge(sub_snapshot);
+                    }
+                }
+
+                // The sender might have stopped waiting for it.
+                reply.send(snapshot).ok();
+            },
+            ProcStack::default(),
+        );
+    }
+
+    fn restart_child(&mut self, id: BastionId) {
+        let index = match self.tracked_groups_order.get(&id) {
+            Some(index) => *index,
+            None => {
+                warn!(
+                    "Supervisor({}): Can't restart unknown Child({}).",
+                    self.id(),
+                    id
+                );
+                return;
+            }
+        };
+
+        let tracked = self
+            .tracked_groups
+            .iter_mut()
+            .find_map(|(parent_id, childs)| match childs.get_mut(index) {
+                Some(tracked_state) if tracked_state.id == id => {
+                    Some((parent_id.clone(), tracked_state))
+                }
+                _ => None,
+            });
+        let (parent_id, tracked_state) = match tracked {
+            Some(tracked) => tracked,
+            None => return,
+        };
+
+        debug!(
+            "Supervisor({}): Restarting Child({}) of Children({}).",
+            self.bcast.id(),
+            id,
+            parent_id
+        );
+        tracked_state.increase_restarts_counter();
+        let msg = BastionMessage::reset_child(id, tracked_state.state());
+        let env = Envelope::new(msg, self.bcast.path().clone(), self.bcast.sender().clone());
+        self.bcast.send_child(&parent_id, env);
     }
 
     fn remove_child(&mut self, id: &BastionId, parent_id: &BastionId) {
@@ -896,6 +2398,16 @@ impl Supervisor {
         for id in self.order.get(range.clone()).unwrap() {
             // TODO: Err if None?
             if let Some((_, launched)) = self.launched.remove(&id) {
+                self.slots.release();
+                if id != &NIL_ID {
+                    let kind = if self.tracked_groups.contains_key(id) {
+                        SupervisedKind::Children
+                    } else {
+                        SupervisedKind::Supervisor
+                    };
+                    self.bcast.system().shutdown().expect(id, kind);
+                }
+
                 // TODO: add a "stopped" list and poll from it instead of awaiting
                 supervised.push(launched);
             }
@@ -909,10 +2421,17 @@ impl Supervisor {
                         self.id(),
                         supervised.id()
                     );
+                    lifecycle_event!(
+                        "supervised_stopped",
+                        supervisor = %self.id(),
+                        stopped = %supervised.id(),
+                    );
                     supervised.callbacks().after_stop();
+                    self.bcast.system().shutdown().acknowledge(supervised.id());
 
                     let id = supervised.id().clone();
-                    self.stopped.insert(id, supervised);
+                    self.typed.unregister(&id);
+                    self.stopped.insert(id, (StopReason::Stopped, supervised));
                 }
                 // FIXME
                 None => unimplemented!(),
@@ -937,6 +2456,7 @@ impl Supervisor {
         for id in self.order.get(range.clone()).unwrap() {
             // TODO: Err if None?
             if let Some((_, launched)) = self.launched.remove(&id) {
+                self.slots.release();
                 // TODO: add a "stopped" list and poll from it instead of awaiting
                 supervised.push(launched);
             }
@@ -950,7 +2470,13 @@ impl Supervisor {
                         self.id(),
                         supervised.id()
                     );
+                    lifecycle_event!(
+                        "supervised_killed",
+                        supervisor = %self.id(),
+                        killed = %supervised.id(),
+                    );
                     let id = supervised.id().clone();
+                    self.typed.unregister(&id);
                     self.killed.insert(id, supervised);
                 }
                 // FIXME
@@ -964,25 +2490,339 @@ impl Supervisor {
         self.bcast.stopped();
     }
 
-    fn faulted(&mut self) {
-        debug!("Supervisor({}): Faulted.", self.id());
-        self.bcast.faulted();
+    fn publish_event(&self, transition: Transition) {
+        self.bcast.system().events().publish(
+            transition,
+            EntityKind::Supervisor,
+            self.id(),
+            self.bcast.parent().id(),
+            self.name.as_deref(),
+        );
+    }
+
+    fn faulted(&mut self) {
+        debug!("Supervisor({}): Faulted.", self.id());
+        lifecycle_event!("supervisor_faulted", supervisor = %self.id());
+        self.publish_event(Transition::Faulted);
+        self.bcast.faulted();
+    }
+
+    // Returns the identifier of the supervised element that
+    // faulted, given the one that asked to be restarted.
+    fn culprit(&self, id: &BastionId, parent_id: &BastionId) -> BastionId {
+        if self.tracked_groups.contains_key(parent_id) {
+            parent_id.clone()
+        } else {
+            id.clone()
+        }
+    }
+
+    // Gives away or lets go of the running supervised elements
+    // depending on the orphan policy, before faulting, and kills
+    // the others (including the one that faulted).
+    async fn orphan(&mut self, culprit: &BastionId) {
+        match self.orphan_policy.clone() {
+            OrphanPolicy::Kill => (),
+            OrphanPolicy::Adopt(supervisor) => {
+                let children = self.release(culprit);
+                debug!(
+                    "Supervisor({}): Giving {} orphans to Supervisor({}).",
+                    self.id(),
+                    children.len(),
+                    supervisor.id()
+                );
+
+                let msg = BastionMessage::adopt(children);
+                let env =
+                    Envelope::new(msg, self.bcast.path().clone(), self.bcast.sender().clone());
+                if let Err(BastionMessage::Adopt { children }) =
+                    supervisor.send(env).map_err(|env| env.msg)
+                {
+                    warn!(
+                        "Supervisor({}): Supervisor({}) stopped, killing the orphans.",
+                        self.id(),
+                        supervisor.id()
+                    );
+                    self.adopt(children);
+                }
+            }
+            OrphanPolicy::Detach => {
+                let children = self.release(culprit);
+                debug!(
+                    "Supervisor({}): Detaching {} orphans.",
+                    self.id(),
+                    children.len()
+                );
+                // The orphans keep bastion's executor running after
+                // the system stopped, until they stop too.
+                for Orphan { launched, .. } in children {
+                    let retained = executor::retain();
+                    executor::spawn_proc(
+                        async move {
+                            launched.await;
+                            drop(retained);
+                        },
+                        ProcStack::default(),
+                    );
+                }
+            }
+        }
+
+        self.kill(0..self.order.len()).await;
+    }
+
+    // Stops supervising the running supervised elements (except
+    // the one that faulted) and returns them.
+    fn release(&mut self, culprit: &BastionId) -> Vec<Orphan> {
+        let ids = self
+            .order
+            .iter()
+            .filter(|id| *id != culprit)
+            .cloned()
+            .collect::<Vec<_>>();
+
+        ids.iter().filter_map(|id| self.release_one(id)).collect()
+    }
+
+    // Stops supervising the supervised element with the given
+    // identifier and returns it, if it is running.
+    fn release_one(&mut self, id: &BastionId) -> Option<Orphan> {
+        let (_, launched) = self.launched.remove(id)?;
+        self.slots.release();
+        // FIXME: panics
+        let sender = self.bcast.take_child(id).unwrap();
+        let tracked = self.tracked_groups.remove(id);
+        if let Some(tracked) = &tracked {
+            for state in tracked {
+                self.tracked_groups_order.remove(&state.id);
+            }
+        }
+        let tags = self.tags.remove(id);
+        let group = self.groups.remove(id);
+        self.remove_from_order(id);
+
+        Some(Orphan {
+            id: id.clone(),
+            sender,
+            launched,
+            tracked,
+            tags,
+            group,
+        })
+    }
+
+    // Removes the supervised element with the given identifier
+    // from the deployment order, shifting the indexes of the
+    // launched elements that followed it.
+    fn remove_from_order(&mut self, id: &BastionId) {
+        self.typed.unregister(id);
+        let index = match self.order.iter().position(|other| other == id) {
+            Some(index) => index,
+            None => return,
+        };
+        self.order.remove(index);
+
+        for (order_index, _) in self.launched.values_mut() {
+            if *order_index > index {
+                *order_index -= 1;
+            }
+        }
+    }
+
+    // Stops the running supervised element with the given
+    // identifier and gives it to the given supervisor, which
+    // relaunches it under the same identifier.
+    async fn migrate(&mut self, id: BastionId, target: SupervisorRef) {
+        if target.id() == self.id() {
+            return;
+        }
+
+        let launched = match self.launched.remove(&id) {
+            Some((_, launched)) => launched,
+            None => {
+                warn!(
+                    "Supervisor({}): Can't migrate Supervised({}) which isn't running.",
+                    self.id(),
+                    id
+                );
+                return;
+            }
+        };
+        debug!(
+            "Supervisor({}): Migrating Supervised({}) to Supervisor({}).",
+            self.id(),
+            id,
+            target.id()
+        );
+        self.slots.release();
+        self.bcast.stop_child(&id);
+        if let Some(tracked) = self.tracked_groups.remove(&id) {
+            for state in tracked {
+                self.tracked_groups_order.remove(&state.id);
+            }
+        }
+        self.tags.remove(&id);
+        self.groups.remove(&id);
+        self.remove_from_order(&id);
+        self.checkpoint();
+
+        // A stopped supervisor checkpoints its state to its state
+        // backend (if it has one), which it restores once
+        // relaunched.
+        // TODO: add a "waiting" list an poll from it instead of awaiting
+        let supervised = match launched.await {
+            Some(supervised) => supervised,
+            None => {
+                warn!(
+                    "Supervisor({}): Supervised({}) panicked while migrating.",
+                    self.id(),
+                    id
+                );
+                return;
+            }
+        };
+
+        let msg = BastionMessage::relaunch(supervised);
+        let env = Envelope::new(msg, self.bcast.path().clone(), self.bcast.sender().clone());
+        if let Err(BastionMessage::Relaunch { supervised }) =
+            target.send(env).map_err(|env| env.msg)
+        {
+            warn!(
+                "Supervisor({}): Supervisor({}) stopped, relaunching Supervised({}).",
+                self.id(),
+                target.id(),
+                id
+            );
+            self.relaunch(*supervised).await;
+        }
+    }
+
+    // Relaunches a supervised element that another supervisor
+    // stopped to migrate it, under the same identifier.
+    async fn relaunch(&mut self, supervised: Supervised) {
+        debug!(
+            "Supervisor({}): Relaunching Supervised({}).",
+            self.id(),
+            supervised.id()
+        );
+        let parent = Parent::supervisor(self.as_ref());
+        let supervised = match supervised {
+            Supervised::Supervisor(mut supervisor) => {
+                let elem = BastionPathElement::Supervisor(supervisor.id().clone());
+                supervisor.reset(Some(Broadcast::new(parent, elem))).await;
+                supervisor.callbacks().before_start();
+                Supervised::supervisor(supervisor)
+            }
+            Supervised::Children(mut children) => {
+                let elem = BastionPathElement::Children(children.id().clone());
+                children.relaunch(Broadcast::new(parent, elem));
+                children.callbacks().before_start();
+                self.tags
+                    .insert(children.id().clone(), children.tags().clone());
+                self.groups.insert(
+                    children.id().clone(),
+                    (children.given_name().cloned(), children.redundancy()),
+                );
+                Supervised::children(children)
+            }
+        };
+
+        self.slots.add();
+        self.launch_supervised(supervised);
+        self.checkpoint();
+    }
+
+    // Starts supervising the running children groups and
+    // supervisors given away by a faulted supervisor.
+    fn adopt(&mut self, orphans: Vec<Orphan>) {
+        for orphan in orphans {
+            debug!(
+                "Supervisor({}): Adopting Supervised({}).",
+                self.id(),
+                orphan.id
+            );
+            let msg = BastionMessage::set_parent(Parent::supervisor(self.as_ref()));
+            let env = Envelope::new(msg, self.bcast.path().clone(), self.bcast.sender().clone());
+            // FIXME: handle errors
+            orphan.sender.unbounded_send(env).ok();
+            self.bcast.register_sender(orphan.id.clone(), orphan.sender);
+
+            if let Some(tracked) = orphan.tracked {
+                for (index, state) in tracked.iter().enumerate() {
+                    self.tracked_groups_order.insert(state.id(), index);
+                }
+                self.tracked_groups.insert(orphan.id.clone(), tracked);
+            }
+            if let Some(tags) = orphan.tags {
+                self.tags.insert(orphan.id.clone(), tags);
+            }
+            if let Some(group) = orphan.group {
+                self.groups.insert(orphan.id.clone(), group);
+            }
+
+            self.slots.add();
+            self.launched
+                .insert(orphan.id.clone(), (self.order.len(), orphan.launched));
+            self.order.push(orphan.id);
+        }
     }
 
-    async fn recover(&mut self, id: BastionId, parent_id: BastionId) -> Result<(), ()> {
+    async fn recover(
+        &mut self,
+        id: BastionId,
+        parent_id: BastionId,
+        strategy: Option<SupervisionStrategy>,
+        panicked: bool,
+    ) -> Result<(), ()> {
+        let strategy = strategy.unwrap_or_else(|| self.strategy.clone());
         debug!(
             "Supervisor({}): Recovering using strategy: {:?}",
             self.id(),
-            self.strategy
+            strategy
+        );
+        lifecycle_event!(
+            "supervised_faulted",
+            supervisor = %self.id(),
+            faulted = %id,
+            parent = %parent_id,
+            strategy = ?strategy,
+            panicked,
         );
 
-        match self.strategy {
+        // Taking it even if it isn't forwarded to forget it.
+        let panic = panic_handler::take_panic(&id);
+
+        if panicked && self.restart_strategy.restart_policy == RestartPolicy::OnError {
+            warn!(
+                "Supervisor({}): Supervised({}) panicked, escalating.",
+                self.id(),
+                id
+            );
+            lifecycle_event!("supervisor_escalated", supervisor = %self.id(), reason = "panic");
+            return Err(());
+        }
+
+        if let Some(restart_window) = &mut self.restart_window {
+            if !restart_window.record_and_check() {
+                warn!("Supervisor({}): Too many restarts, escalating.", self.id());
+                lifecycle_event!(
+                    "supervisor_escalated",
+                    supervisor = %self.id(),
+                    reason = "restart_window",
+                );
+                return Err(());
+            }
+        }
+
+        match strategy {
             SupervisionStrategy::OneForOne => {
+                self.one_for_one_restarts += 1;
                 let search_method = ActorSearchMethod::OneActor { id, parent_id };
                 let objects = self.search_restarted_objects(search_method);
                 self.restart(objects).await;
             }
             SupervisionStrategy::OneForAll => {
+                self.one_for_all_restarts += 1;
                 let search_method = ActorSearchMethod::All;
                 let objects = self.search_restarted_objects(search_method);
                 self.restart(objects).await;
@@ -992,10 +2832,27 @@ impl Supervisor {
                 self.killed.shrink_to_fit();
             }
             SupervisionStrategy::RestForOne => {
+                self.rest_for_one_restarts += 1;
                 let search_method = ActorSearchMethod::FromActor { id, parent_id };
                 let objects = self.search_restarted_objects(search_method);
                 self.restart(objects).await;
             }
+            SupervisionStrategy::PanicForward => {
+                error!(
+                    "Supervisor({}): Supervised({}) faulted, forwarding the panic.",
+                    self.id(),
+                    id
+                );
+                let culprit = self.culprit(&id, &parent_id);
+                self.orphan(&culprit).await;
+                // Lets the parent wait for this supervisor's task,
+                // which then finds out that it panicked.
+                self.faulted();
+
+                let message = panic.unwrap_or_else(|| format!("Supervised({}) faulted.", id));
+                panic_handler::forward_panic(self.id(), message.clone());
+                panic::resume_unwind(Box::new(message));
+            }
         }
 
         Ok(())
@@ -1081,16 +2938,61 @@ impl Supervisor {
     }
 
     async fn deinit_with_stop(&mut self) {
+        self.checkpoint();
         self.stop(0..self.order.len()).await;
         self.stopped();
+        lifecycle_event!("supervisor_stopped", supervisor = %self.id());
+        self.publish_event(Transition::Stopped);
     }
 
     async fn deinit_with_kill(&mut self) {
+        self.checkpoint();
         self.kill(0..self.order.len()).await;
         self.stopped();
+        lifecycle_event!("supervisor_killed", supervisor = %self.id());
+        self.publish_event(Transition::Stopped);
     }
 
-    async fn deploy_supervised_object(&mut self, deployment: Box<Deployment>) {
+    async fn deploy_supervised_object(&mut self, deployment: Box<Deployment>, sign: RefAddr) {
+        let bcast = match &*deployment {
+            Deployment::Supervisor(supervisor) => supervisor.bcast(),
+            Deployment::Children(children) => children.bcast(),
+        };
+        if bcast.system().id() != self.bcast.system().id() {
+            warn!(
+                "Supervisor({}): Refusing to deploy Supervised({}) from another runtime.",
+                self.id(),
+                bcast.id()
+            );
+            self.slots.release();
+            return;
+        }
+
+        if let Some(capacity) = self.slots.capacity() {
+            if self.launched.len() >= capacity {
+                let id = bcast.id().clone();
+                warn!(
+                    "Supervisor({}): Refusing to deploy Supervised({}), capacity of {} exceeded.",
+                    self.id(),
+                    id,
+                    capacity
+                );
+                self.slots.release();
+                // FIXME: the elements of a refused supervisor's
+                // children groups keep running.
+                if let Deployment::Children(children) = *deployment {
+                    children.discard().await;
+                }
+
+                let msg = BastionMessage::capacity_exceeded(id);
+                let env =
+                    Envelope::new(msg, self.bcast.path().clone(), self.bcast.sender().clone());
+                // The sender might have stopped.
+                sign.sender().unbounded_send(env).ok();
+                return;
+            }
+        }
+
         let supervised = match *deployment {
             Deployment::Supervisor(supervisor) => {
                 debug!(
@@ -1098,6 +3000,18 @@ impl Supervisor {
                     self.id(),
                     supervisor.id()
                 );
+                lifecycle_event!(
+                    "supervisor_deployed",
+                    supervisor = %self.id(),
+                    deployed = %supervisor.id(),
+                );
+                self.bcast.system().events().publish(
+                    Transition::Deployed,
+                    EntityKind::Supervisor,
+                    supervisor.id(),
+                    Some(self.id()),
+                    supervisor.given_name().map(String::as_str),
+                );
                 supervisor.callbacks().before_start();
                 Supervised::supervisor(supervisor)
             }
@@ -1107,12 +3021,39 @@ impl Supervisor {
                     self.id(),
                     children.id()
                 );
+                lifecycle_event!(
+                    "children_deployed",
+                    supervisor = %self.id(),
+                    children = %children.id(),
+                    name = %children.name(),
+                );
+                self.bcast.system().events().publish(
+                    Transition::Deployed,
+                    EntityKind::Children,
+                    children.id(),
+                    Some(self.id()),
+                    children.given_name().map(String::as_str),
+                );
                 children.callbacks().before_start();
+                self.tags
+                    .insert(children.id().clone(), children.tags().clone());
+                self.groups.insert(
+                    children.id().clone(),
+                    (children.given_name().cloned(), children.redundancy()),
+                );
                 Supervised::children(children)
             }
         };
 
+        self.deploys += 1;
+        self.launch_supervised(supervised);
+    }
+
+    // Launches the given supervised element, sending it a start
+    // message if the supervisor already started.
+    fn launch_supervised(&mut self, supervised: Supervised) {
         self.bcast.register(supervised.bcast());
+        self.typed.register(&supervised);
         if self.started {
             let msg = BastionMessage::start();
             let env = Envelope::new(msg, self.bcast.path().clone(), self.bcast.sender().clone());
@@ -1128,20 +3069,29 @@ impl Supervisor {
         let launched = supervised.launch();
         self.launched
             .insert(id.clone(), (self.order.len(), launched));
+        self.callbacks.on_child_start(&id);
         self.order.push(id);
     }
 
-    async fn cleanup_supervised_object(&mut self, id: BastionId) {
+    async fn cleanup_supervised_object(&mut self, id: BastionId, reason: StopReason) {
+        self.typed.unregister(&id);
         // FIXME: Err if None?
         if let Some((_, launched)) = self.launched.remove(&id) {
+            self.slots.release();
             debug!("Supervisor({}): Supervised({}) stopped.", self.id(), id);
             // TODO: add a "waiting" list an poll from it instead of awaiting
-            // FIXME: panics?
-            let supervised = launched.await.unwrap();
+            let supervised = match launched.await {
+                Some(supervised) => supervised,
+                None => {
+                    warn!("Supervisor({}): Supervised({}) panicked.", self.id(), id);
+                    self.bcast.unregister(&id);
+                    return;
+                }
+            };
             supervised.callbacks().after_stop();
 
             self.bcast.unregister(&id);
-            self.stopped.insert(id.clone(), supervised);
+            self.stopped.insert(id.clone(), (reason, supervised));
         }
     }
 
@@ -1149,14 +3099,22 @@ impl Supervisor {
         &mut self,
         id: BastionId,
         parent_id: BastionId,
+        strategy: Option<SupervisionStrategy>,
+        panicked: bool,
     ) -> Result<(), ()> {
         if self.launched.contains_key(&id) {
             warn!("Supervisor({}): Supervised({}) faulted.", self.id(), id);
         }
-
-        if self.recover(id, parent_id).await.is_err() {
+        self.faults += 1;
+
+        let culprit = self.culprit(&id, &parent_id);
+        if self
+            .recover(id, parent_id, strategy, panicked)
+            .await
+            .is_err()
+        {
             // TODO: stop or kill?
-            self.kill(0..self.order.len()).await;
+            self.orphan(&culprit).await;
             self.faulted();
 
             return Err(());
@@ -1165,6 +3123,70 @@ impl Supervisor {
         Ok(())
     }
 
+    // Sends the user message to the supervised elements chosen
+    // by the supervisor's routing.
+    fn route(&mut self, env: Envelope) {
+        if let Routing::Broadcast = self.routing {
+            self.bcast.send_children(env);
+            return;
+        }
+
+        if let Routing::TagBased(matches) = &self.routing {
+            let untagged = HashMap::new();
+            for id in &self.order {
+                if !self.launched.contains_key(id) {
+                    continue;
+                }
+
+                let tags = self.tags.get(id).unwrap_or(&untagged);
+                if matches(tags) {
+                    trace!("Supervisor({}): Routing the message to: {}", self.id(), id);
+                    if let Some(env) = env.try_clone() {
+                        self.bcast.send_child(id, env);
+                    }
+                }
+            }
+
+            return;
+        }
+
+        let running = self
+            .order
+            .iter()
+            .filter(|id| self.launched.contains_key(id))
+            .cloned()
+            .collect::<Vec<_>>();
+        if running.is_empty() {
+            debug!(
+                "Supervisor({}): No supervised element to route the message to.",
+                self.id()
+            );
+            return;
+        }
+
+        let index = match &self.routing {
+            Routing::Broadcast | Routing::TagBased(_) => unreachable!(),
+            Routing::RoundRobin => {
+                let index = self.next_route % running.len();
+                self.next_route = self.next_route.wrapping_add(1);
+                index
+            }
+            Routing::ByKey(key) => match &env.msg {
+                BastionMessage::Message(msg) | BastionMessage::CountedMessage { msg, .. } => {
+                    key(msg) % running.len()
+                }
+                _ => unreachable!(),
+            },
+        };
+
+        trace!(
+            "Supervisor({}): Routing the message to: {}",
+            self.id(),
+            running[index]
+        );
+        self.bcast.send_child(&running[index], env);
+    }
+
     async fn handle(&mut self, env: Envelope) -> Result<(), ()> {
         match env {
             Envelope {
@@ -1185,10 +3207,29 @@ impl Supervisor {
                 self.deinit_with_kill().await;
                 return Err(());
             }
+            Envelope {
+                msg: BastionMessage::Pause,
+                ..
+            } => unreachable!(),
+            Envelope {
+                msg: BastionMessage::Resume,
+                ..
+            } => unreachable!(),
             Envelope {
                 msg: BastionMessage::Deploy(deployment),
+                sign,
+                ..
+            } => self.deploy_supervised_object(deployment, sign).await,
+            Envelope {
+                msg: BastionMessage::CapacityExceeded { id },
                 ..
-            } => self.deploy_supervised_object(deployment).await,
+            } => {
+                warn!(
+                    "Supervisor({}): Supervised({}) wasn't deployed, capacity exceeded.",
+                    self.id(),
+                    id
+                );
+            }
             // FIXME
             Envelope {
                 msg: BastionMessage::Prune { .. },
@@ -1203,6 +3244,11 @@ impl Supervisor {
                     self.id(),
                     strategy
                 );
+                lifecycle_event!(
+                    "strategy_changed",
+                    supervisor = %self.id(),
+                    strategy = ?strategy,
+                );
                 self.strategy = strategy;
             }
             Envelope {
@@ -1239,13 +3285,63 @@ impl Supervisor {
                     self.id(),
                     message
                 );
-                self.bcast.send_children(env);
+                self.route(env);
+            }
+            Envelope {
+                msg: BastionMessage::MailboxLens { .. },
+                ..
+            } => unreachable!(),
+            Envelope {
+                msg: BastionMessage::HealthCheck { .. },
+                ..
+            } => unreachable!(),
+            Envelope {
+                msg: BastionMessage::Drain { .. },
+                ..
+            } => unreachable!(),
+            Envelope {
+                msg: BastionMessage::Ping { .. },
+                ..
+            } => unreachable!(),
+            Envelope {
+                msg: BastionMessage::Emit(_),
+                ..
+            } => unreachable!(),
+            Envelope {
+                msg: BastionMessage::Accumulator { .. },
+                ..
+            } => unreachable!(),
+            Envelope {
+                msg: BastionMessage::AddChild { .. },
+                ..
+            } => unreachable!(),
+            Envelope {
+                msg: BastionMessage::CountedMessage { ref msg, .. },
+                ..
+            } => {
+                debug!(
+                    "Supervisor({}): Broadcasting a counted message: {:?}",
+                    self.id(),
+                    msg
+                );
+                self.route(env);
             }
             Envelope {
-                msg: BastionMessage::RestartRequired { id, parent_id },
+                msg:
+                    BastionMessage::RestartRequired {
+                        id,
+                        parent_id,
+                        strategy,
+                        panicked,
+                    },
                 ..
             } => {
-                if self.recover_supervised_object(id, parent_id).await.is_err() {
+                self.callbacks.on_child_fault(&id);
+                if self
+                    .recover_supervised_object(id, parent_id, strategy, panicked)
+                    .await
+                    .is_err()
+                {
                     return Err(());
                 }
             }
@@ -1265,18 +3361,82 @@ impl Supervisor {
                 msg: BastionMessage::DropChild { .. },
                 ..
             } => unreachable!(),
+            Envelope {
+                msg: BastionMessage::RestartChild { id },
+                ..
+            } => self.restart_child(id),
+            Envelope {
+                msg: BastionMessage::ListStopped { sender },
+                ..
+            } => {
+                let stopped = self.list_stopped();
+                trace!("Supervisor({}): Stopped elements: {:?}", self.id(), stopped);
+                // The sender might have stopped waiting for them.
+                sender.send(stopped).ok();
+            }
+            Envelope {
+                msg: BastionMessage::Inspect { reply },
+                ..
+            } => self.inspect(reply),
+            Envelope {
+                msg: BastionMessage::DumpState { reply, deadline },
+                ..
+            } => self.dump_state(reply, deadline),
+            Envelope {
+                msg: BastionMessage::Metrics { reply },
+                ..
+            } => self.metrics(reply),
+            Envelope {
+                msg: BastionMessage::ResetChild { .. },
+                ..
+            } => unreachable!(),
             Envelope {
                 msg: BastionMessage::SetState { .. },
                 ..
             } => unreachable!(),
+            Envelope {
+                msg: BastionMessage::Adopt { children },
+                ..
+            } => {
+                self.adopt(children);
+                self.checkpoint();
+            }
+            Envelope {
+                msg: BastionMessage::Migrate { id, target },
+                ..
+            } => self.migrate(id, *target).await,
+            Envelope {
+                msg: BastionMessage::Relaunch { supervised },
+                ..
+            } => self.relaunch(*supervised).await,
+            Envelope {
+                msg: BastionMessage::SetParent { parent },
+                ..
+            } => {
+                debug!("Supervisor({}): Adopted.", self.id());
+                self.bcast.set_parent(*parent);
+            }
             Envelope {
                 msg: BastionMessage::Stopped { id },
                 ..
-            } => self.cleanup_supervised_object(id).await,
+            } => {
+                self.callbacks.on_child_stop(&id);
+                self.cleanup_supervised_object(id, StopReason::Stopped)
+                    .await
+            }
+            Envelope {
+                msg: BastionMessage::IdleStop { .. },
+                ..
+            } => unreachable!(),
             Envelope {
                 msg: BastionMessage::Faulted { id },
                 ..
-            } => self.cleanup_supervised_object(id).await,
+            } => {
+                self.callbacks.on_child_fault(&id);
+                self.faults += 1;
+                self.cleanup_supervised_object(id, StopReason::Faulted)
+                    .await
+            }
         }
 
         Ok(())
@@ -1289,6 +3449,8 @@ impl Supervisor {
             BastionMessage::Start
         );
         debug!("Supervisor({}): Starting.", self.id());
+        lifecycle_event!("supervisor_started", supervisor = %self.id());
+        self.publish_event(Transition::Started);
         self.started = true;
 
         let msg = BastionMessage::start();
@@ -1302,7 +3464,15 @@ impl Supervisor {
             "Supervisor({}): Replaying messages received before starting.",
             self.id()
         );
-        for msg in msgs {
+        for (replayed, msg) in msgs.into_iter().enumerate() {
+            // Yields between two messages once it replayed as many as
+            // an element would handle before yielding (the messages
+            // received meanwhile only being handled once all of them
+            // were replayed).
+            if replayed > 0 && replayed % DEFAULT_POLL_BUDGET == 0 {
+                executor::yield_now().await;
+            }
+
             trace!("Supervisor({}): Replaying message: {:?}", self.id(), msg);
             if self.handle(msg).await.is_err() {
                 return Err(());
@@ -1314,18 +3484,32 @@ impl Supervisor {
 
     async fn run(mut self) -> Self {
         debug!("Supervisor({}): Launched.", self.id());
+        // The system's supervisor isn't counted.
+        let _running = if self.id() == &NIL_ID {
+            None
+        } else {
+            Some(RunningGuard::new(self.bcast.system().supervisors().clone()))
+        };
+
         loop {
-            match poll!(&mut self.bcast.next()) {
+            // Parks until the next message arrives.
+            match self.bcast.next().await {
                 // TODO: Err if started == true?
-                Poll::Ready(Some(Envelope {
+                Some(Envelope {
                     msg: BastionMessage::Start,
                     ..
-                })) => {
+                }) => {
                     if self.initialize().await.is_err() {
                         return self;
                     }
                 }
-                Poll::Ready(Some(msg)) if !self.started => {
+                // Answered even before being started, to diagnose
+                // supervisors that never were.
+                Some(Envelope {
+                    msg: BastionMessage::DumpState { reply, deadline },
+                    ..
+                }) => self.dump_state(reply, deadline),
+                Some(msg) if !self.started => {
                     trace!(
                         "Supervisor({}): Received a new message (started=false): {:?}",
                         self.id(),
@@ -1333,7 +3517,7 @@ impl Supervisor {
                     );
                     self.pre_start_msgs.push(msg);
                 }
-                Poll::Ready(Some(msg)) => {
+                Some(msg) => {
                     trace!(
                         "Supervisor({}): Received a new message (started=true): {:?}",
                         self.id(),
@@ -1346,8 +3530,7 @@ impl Supervisor {
                 // NOTE: because `Broadcast` always holds both a `Sender` and
                 //      `Receiver` of the same channel, this would only be
                 //      possible if the channel was closed, which never happens.
-                Poll::Ready(None) => unreachable!(),
-                Poll::Pending => pending!(),
+                None => unreachable!(),
             }
         }
     }
@@ -1355,13 +3538,127 @@ impl Supervisor {
     pub(crate) fn launch(self) -> RecoverableHandle<Self> {
         debug!("Supervisor({}): Launching.", self.id());
         let stack = self.stack();
-        pool::spawn(self.run(), stack)
+        executor::spawn_proc(self.run(), stack)
+    }
+}
+
+impl TypedChildren {
+    fn register(&self, supervised: &Supervised) {
+        match supervised {
+            Supervised::Supervisor(supervisor) => self.register_supervisor(supervisor),
+            Supervised::Children(children) => self.register_children(children),
+        }
+    }
+
+    // Called when deploying an element too (as `register_children`
+    // is), for typed messages to reach it before it was launched.
+    fn register_supervisor(&self, supervisor: &Supervisor) {
+        let typed = supervisor.typed.clone();
+        // FIXME: panics
+        let mut supervisors = self.supervisors.write().unwrap();
+        supervisors.insert(supervisor.id().clone(), typed);
+    }
+
+    fn register_children(&self, children: &Children) {
+        if let Some(type_id) = children.exec_type() {
+            let sender = children.bcast().sender().clone();
+            // FIXME: panics
+            let mut typed = self.children.write().unwrap();
+            let children_groups = typed.entry(type_id).or_default();
+            children_groups.insert(children.id().clone(), sender);
+        }
+    }
+
+    fn unregister(&self, id: &BastionId) {
+        // FIXME: panics
+        for children_groups in self.children.write().unwrap().values_mut() {
+            children_groups.remove(id);
+        }
+        self.supervisors.write().unwrap().remove(id);
+    }
+
+    // Collects the senders of the children groups handling messages
+    // of the given type, including the ones of the supervised
+    // supervisors.
+    fn senders(&self, type_id: TypeId, senders: &mut Vec<Sender>) {
+        // FIXME: panics
+        if let Some(children_groups) = self.children.read().unwrap().get(&type_id) {
+            senders.extend(children_groups.values().cloned());
+        }
+        for typed in self.supervisors.read().unwrap().values() {
+            typed.senders(type_id, senders);
+        }
+    }
+}
+
+impl Slots {
+    pub(crate) fn new() -> Self {
+        Slots {
+            used: AtomicUsize::new(0),
+            capacity: AtomicUsize::new(usize::MAX),
+        }
+    }
+
+    fn capacity(&self) -> Option<usize> {
+        match self.capacity.load(Ordering::SeqCst) {
+            usize::MAX => None,
+            capacity => Some(capacity),
+        }
+    }
+
+    fn set_capacity(&self, capacity: usize) {
+        self.capacity.store(capacity, Ordering::SeqCst);
+    }
+
+    // Takes a slot for a supervised element about to be deployed,
+    // unless the capacity (which is returned) was reached.
+    fn reserve(&self) -> Result<(), usize> {
+        let capacity = self.capacity.load(Ordering::SeqCst);
+        self.used
+            .fetch_update(Ordering::SeqCst, Ordering::SeqCst, |used| {
+                if used < capacity {
+                    Some(used + 1)
+                } else {
+                    None
+                }
+            })
+            .map(|_| ())
+            .map_err(|_| capacity)
+    }
+
+    // Takes a slot even if the capacity was reached, for the
+    // supervisor to refuse the deployment once it handles it.
+    fn add(&self) {
+        self.used.fetch_add(1, Ordering::SeqCst);
+    }
+
+    fn release(&self) {
+        self.used.fetch_sub(1, Ordering::SeqCst);
     }
 }
 
 impl SupervisorRef {
-    pub(crate) fn new(id: BastionId, sender: Sender, path: Arc<BastionPath>) -> Self {
-        SupervisorRef { id, sender, path }
+    #[allow(clippy::too_many_arguments)]
+    pub(crate) fn new(
+        id: BastionId,
+        pid: usize,
+        sender: Sender,
+        path: Arc<BastionPath>,
+        name: Option<String>,
+        slots: Arc<Slots>,
+        typed: Arc<TypedChildren>,
+        system: Arc<GlobalSystem>,
+    ) -> Self {
+        SupervisorRef {
+            id,
+            pid,
+            sender,
+            path,
+            name,
+            slots,
+            typed,
+            system,
+        }
     }
 
     /// Returns the identifier of the supervisor this `SupervisorRef`
@@ -1375,21 +3672,102 @@ impl SupervisorRef {
     /// ```rust
     /// # use bastion::prelude::*;
     /// #
-    /// # Bastion::init();
-    /// #
-    /// let supervisor_ref = Bastion::supervisor(|sp| {
+    /// # Bastion::init();
+    /// #
+    /// let supervisor_ref = Bastion::supervisor(|sp| {
+    ///     // ...
+    ///     # sp
+    /// }).expect("Couldn't create the supervisor.");
+    ///
+    /// let supervisor_id: &BastionId = supervisor_ref.id();
+    /// #
+    /// # Bastion::start();
+    /// # Bastion::stop();
+    /// # Bastion::block_until_stopped();
+    /// ```
+    pub fn id(&self) -> &BastionId {
+        &self.id
+    }
+
+    /// Returns the name given to the supervisor this `SupervisorRef`
+    /// is referencing using [`Supervisor::with_name`], if any.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use bastion::prelude::*;
+    /// #
+    /// # Bastion::init();
+    /// #
+    /// let supervisor_ref = Bastion::supervisor(|sp| {
+    ///     sp.with_name("workers-supervisor")
+    /// }).expect("Couldn't create the supervisor.");
+    ///
+    /// assert_eq!(supervisor_ref.name(), Some("workers-supervisor"));
+    /// #
+    /// # Bastion::start();
+    /// # Bastion::stop();
+    /// # Bastion::block_until_stopped();
+    /// ```
+    ///
+    /// [`Supervisor::with_name`]: struct.Supervisor.html#method.with_name
+    pub fn name(&self) -> Option<&str> {
+        self.name.as_deref()
+    }
+
+    /// Returns the pid set on the process of the supervisor this
+    /// `SupervisorRef` is referencing.
+    ///
+    /// Note that, like its identifier, the supervisor's pid
+    /// changes when it is restarted.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use bastion::prelude::*;
+    /// #
+    /// # Bastion::init();
+    /// #
+    /// let supervisor_ref = Bastion::supervisor(|sp| {
+    ///     // ...
+    ///     # sp
+    /// }).expect("Couldn't create the supervisor.");
+    ///
+    /// let supervisor_pid: usize = supervisor_ref.pid();
+    /// #
+    /// # Bastion::start();
+    /// # Bastion::stop();
+    /// # Bastion::block_until_stopped();
+    /// ```
+    pub fn pid(&self) -> usize {
+        self.pid
+    }
+
+    /// Returns the identifier of the [`BastionRuntime`] the supervisor
+    /// this `SupervisorRef` is referencing belongs to.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use bastion::prelude::*;
+    /// #
+    /// let runtime = BastionRuntime::new(Config::new());
+    ///
+    /// let r = runtime.supervisor(|sp| {
     ///     // ...
     ///     # sp
     /// }).expect("Couldn't create the supervisor.");
     ///
-    /// let supervisor_id: &BastionId = supervisor_ref.id();
+    /// assert_eq!(r.runtime_id(), runtime.id());
     /// #
-    /// # Bastion::start();
-    /// # Bastion::stop();
-    /// # Bastion::block_until_stopped();
+    /// # runtime.start();
+    /// # runtime.stop();
+    /// # runtime.block_until_stopped();
     /// ```
-    pub fn id(&self) -> &BastionId {
-        &self.id
+    ///
+    /// [`BastionRuntime`]: ../struct.BastionRuntime.html
+    pub fn runtime_id(&self) -> RuntimeId {
+        self.system.id()
     }
 
     /// Creates a new [`Supervisor`], passes it through the specified
@@ -1430,6 +3808,7 @@ impl SupervisorRef {
         S: FnOnce(Supervisor) -> Supervisor,
     {
         debug!("SupervisorRef({}): Creating supervisor.", self.id());
+        self.reserve_slot()?;
         let parent = Parent::supervisor(self.clone());
         let bcast = Broadcast::new(parent, BastionPathElement::Supervisor(BastionId::new()));
 
@@ -1442,15 +3821,20 @@ impl SupervisorRef {
         let supervisor = init(supervisor);
         let supervisor_ref = supervisor.as_ref();
         debug!("Supervisor({}): Initialized.", supervisor.id());
+        if supervisor.is_invalid() {
+            self.release_slot();
+            return Err(());
+        }
 
         debug!(
             "SupervisorRef({}): Deploying Supervisor({}).",
             self.id(),
             supervisor.id()
         );
+        self.typed.register_supervisor(&supervisor);
         let msg = BastionMessage::deploy_supervisor(supervisor);
         let env = Envelope::new(msg, self.path.clone(), self.sender.clone());
-        self.send(env).map_err(|_| ())?;
+        self.send(env).map_err(|_| self.release_slot())?;
 
         Ok(supervisor_ref)
     }
@@ -1509,6 +3893,7 @@ impl SupervisorRef {
         C: FnOnce(Children) -> Children,
     {
         debug!("SupervisorRef({}): Creating children group.", self.id());
+        self.reserve_slot()?;
         let parent = Parent::supervisor(self.clone());
         let bcast = Broadcast::new(parent, BastionPathElement::Children(id));
 
@@ -1520,6 +3905,7 @@ impl SupervisorRef {
         let children = Children::new(bcast);
         let mut children = init(children);
         debug!("Children({}): Initialized.", children.id());
+        children.check_affinity().map_err(|_| self.release_slot())?;
         // FIXME: children group elems launched without the group itself being launched
         children.launch_elems();
 
@@ -1529,9 +3915,10 @@ impl SupervisorRef {
             self.id(),
             children.id()
         );
+        self.typed.register_children(&children);
         let msg = BastionMessage::deploy_children(children);
         let env = Envelope::new(msg, self.path.clone(), self.sender.clone());
-        self.send(env).map_err(|_| ())?;
+        self.send(env).map_err(|_| self.release_slot())?;
 
         Ok(children_ref)
     }
@@ -1592,7 +3979,7 @@ impl SupervisorRef {
             strategy
         );
         let msg = BastionMessage::supervise_with(strategy);
-        let env = Envelope::from_dead_letters(msg);
+        let env = Envelope::from_dead_letters(msg, &self.system);
         self.send(env).map_err(|_| ())
     }
 
@@ -1651,11 +4038,153 @@ impl SupervisorRef {
             msg
         );
         let msg = BastionMessage::broadcast(msg);
-        let env = Envelope::from_dead_letters(msg);
+        let env = Envelope::from_dead_letters(msg, &self.system);
         // FIXME: panics?
         self.send(env).map_err(|env| env.into_msg().unwrap())
     }
 
+    /// Sends a message to the supervisor this `SupervisorRef`
+    /// is referencing which will then send it to all of its
+    /// supervised children groups and supervisors, like
+    /// [`broadcast`] does, and counts the children groups
+    /// elements' mailboxes the message was enqueued into.
+    ///
+    /// This method returns a [`Recipients`] which resolves to the
+    /// number of mailboxes the message reached once every children
+    /// group it was sent to handled it, or `Err(msg)` if it failed.
+    /// Note that the message is only sent to the supervised
+    /// elements chosen by the supervisors' [`Routing`].
+    ///
+    /// # Arguments
+    ///
+    /// * `msg` - The message to send.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use bastion::prelude::*;
+    /// #
+    /// # fn main() {
+    ///     # Bastion::init();
+    ///     # Bastion::start();
+    ///     #
+    /// let sp_ref = Bastion::supervisor(|sp| sp).expect("Couldn't create the supervisor.");
+    ///
+    /// // Nothing is supervised yet...
+    /// let recipients = sp_ref
+    ///     .broadcast_counted("A message containing data.")
+    ///     .expect("Couldn't send the message.");
+    /// assert_eq!(run!(recipients), 0);
+    ///     #
+    ///     # Bastion::stop();
+    ///     # Bastion::block_until_stopped();
+    /// # }
+    /// ```
+    ///
+    /// [`broadcast`]: #method.broadcast
+    /// [`Recipients`]: ../message/struct.Recipients.html
+    /// [`Routing`]: enum.Routing.html
+    pub fn broadcast_counted<M: Message>(&self, msg: M) -> Result<Recipients, M> {
+        debug!(
+            "SupervisorRef({}): Broadcasting counted message: {:?}",
+            self.id(),
+            msg
+        );
+        let (msg, recipients) = BastionMessage::broadcast_counted(msg);
+        let env = Envelope::from_dead_letters(msg, &self.system);
+        // FIXME: panics?
+        self.send(env)
+            .map(|_| recipients)
+            .map_err(|env| env.into_msg().unwrap())
+    }
+
+    /// Sends a message to the children groups supervised by the
+    /// supervisor this `SupervisorRef` is referencing (or by the
+    /// supervisors it supervises) whose elements handle messages of
+    /// type `M` (set using [`Children::with_exec_typed`]), which will
+    /// then send it to all of their elements, the other ones not
+    /// receiving it.
+    ///
+    /// Unlike with [`broadcast`], the message is thus passed to the
+    /// closures given to [`Children::with_exec_typed`], instead of
+    /// being reported as unhandled by the elements not expecting it.
+    ///
+    /// This method returns the number of children groups the message
+    /// was sent to, or `Err(())` if it wasn't sent to any.
+    ///
+    /// # Arguments
+    ///
+    /// * `msg` - The message to send.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use bastion::prelude::*;
+    /// #
+    /// # fn main() {
+    ///     # Bastion::init();
+    ///     # Bastion::start();
+    ///     #
+    /// #[derive(Debug, Clone)]
+    /// struct Invoice {
+    ///     amount: u64,
+    /// }
+    ///
+    /// let sp_ref = Bastion::supervisor(|sp| {
+    ///     sp.children(|children| {
+    ///         children
+    ///             .with_redundancy(2)
+    ///             .with_exec_typed(|ctx: BastionContext, invoice: Invoice| {
+    ///                 async move {
+    ///                     // Handle the invoice...
+    ///                     Ok(())
+    ///                 }
+    ///             })
+    ///     })
+    ///     // This children group doesn't handle invoices...
+    ///     .children(|children| children)
+    /// }).expect("Couldn't create the supervisor.");
+    ///
+    /// let sent = sp_ref
+    ///     .broadcast_typed(Invoice { amount: 42 })
+    ///     .expect("Couldn't send the message.");
+    /// assert_eq!(sent, 1);
+    ///     #
+    ///     # Bastion::stop();
+    ///     # Bastion::block_until_stopped();
+    /// # }
+    /// ```
+    ///
+    /// [`Children::with_exec_typed`]: ../children/struct.Children.html#method.with_exec_typed
+    /// [`broadcast`]: #method.broadcast
+    pub fn broadcast_typed<M: Message>(&self, msg: M) -> Result<usize, ()> {
+        debug!(
+            "SupervisorRef({}): Broadcasting typed message: {:?}",
+            self.id(),
+            msg
+        );
+        let mut senders = Vec::new();
+        self.typed.senders(TypeId::of::<M>(), &mut senders);
+
+        // The message is shared by the elements of every group.
+        let msg = Msg::broadcast(msg);
+        let mut sent = 0;
+        for sender in senders {
+            // FIXME: panics?
+            let msg = BastionMessage::Message(msg.try_clone().unwrap());
+            let env = Envelope::from_dead_letters(msg, &self.system);
+            if sender.unbounded_send(env).is_ok() {
+                sent += 1;
+            }
+        }
+
+        if sent == 0 {
+            return Err(());
+        }
+
+        Ok(sent)
+    }
+
     /// Sends a message to the supervisor this `SupervisorRef`
     /// is referencing to tell it to stop every running children
     /// groups and supervisors that it is supervising.
@@ -1680,7 +4209,7 @@ impl SupervisorRef {
     pub fn stop(&self) -> Result<(), ()> {
         debug!("SupervisorRef({}): Stopping.", self.id());
         let msg = BastionMessage::stop();
-        let env = Envelope::from_dead_letters(msg);
+        let env = Envelope::from_dead_letters(msg, &self.system);
         self.send(env).map_err(|_| ())
     }
 
@@ -1708,20 +4237,302 @@ impl SupervisorRef {
     pub fn kill(&self) -> Result<(), ()> {
         debug!("SupervisorRef({}): Killing.", self.id());
         let msg = BastionMessage::kill();
-        let env = Envelope::from_dead_letters(msg);
+        let env = Envelope::from_dead_letters(msg, &self.system);
+        self.send(env).map_err(|_| ())
+    }
+
+    /// Sends a message to the supervisor this `SupervisorRef`
+    /// is referencing to tell it to restart one of the elements
+    /// of the children groups it is supervising, even though it
+    /// didn't fault.
+    ///
+    /// The element is stopped and, once it finished stopping,
+    /// relaunched (calling its [`Callbacks`]' `before_restart`
+    /// and `after_restart` callbacks), keeping the messages it
+    /// didn't receive yet. This counts as one of its restarts.
+    ///
+    /// This method returns `()` if it succeeded, or `Err(())`
+    /// otherwise.
+    ///
+    /// # Arguments
+    ///
+    /// * `id` - The identifier of the element to restart.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use bastion::prelude::*;
+    /// #
+    /// # Bastion::init();
+    /// #
+    /// let sp_ref = Bastion::supervisor(|sp| sp).unwrap();
+    /// let children_ref = sp_ref.children(|children| children).unwrap();
+    ///
+    /// let id = children_ref.elems()[0].id().clone();
+    /// sp_ref.restart_child(id).expect("Couldn't send the message.");
+    /// #
+    /// # Bastion::start();
+    /// # Bastion::stop();
+    /// # Bastion::block_until_stopped();
+    /// ```
+    ///
+    /// [`Callbacks`]: ../struct.Callbacks.html
+    pub fn restart_child(&self, id: BastionId) -> Result<(), ()> {
+        debug!("SupervisorRef({}): Restarting Child({}).", self.id(), id);
+        let msg = BastionMessage::restart_child(id);
+        let env = Envelope::from_dead_letters(msg, &self.system);
+        self.send(env).map_err(|_| ())
+    }
+
+    /// Sends a message to the supervisor this `SupervisorRef` is
+    /// referencing to tell it to give the children group or
+    /// supervisor with the given identifier that it supervises to
+    /// the supervisor referenced by `target`, which supervises it
+    /// from then on.
+    ///
+    /// The element is stopped, then relaunched by `target` under
+    /// the same identifier (the elements of a children group being
+    /// relaunched from their `init` closure). A migrated supervisor
+    /// checkpoints its state to its state backend (see
+    /// [`Supervisor::with_state_persistence`]) when stopped and
+    /// restores it when relaunched. References to the element
+    /// taken before the migration can't be used anymore. The
+    /// element is relaunched where it was if `target` already
+    /// stopped.
+    ///
+    /// This method returns `()` if it succeeded, or `Err(())`
+    /// otherwise (including when `target` belongs to another
+    /// [`BastionRuntime`], or is the migrated supervisor or one of
+    /// the supervisors it supervises).
+    ///
+    /// # Arguments
+    ///
+    /// * `id` - The identifier of the children group or
+    ///     supervisor to migrate.
+    /// * `target` - The supervisor to migrate it to.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use bastion::prelude::*;
+    /// #
+    /// # Bastion::init();
+    /// #
+    /// let source = Bastion::supervisor(|sp| sp).unwrap();
+    /// let target = Bastion::supervisor(|sp| sp).unwrap();
+    /// let children_ref = source.children(|children| children).unwrap();
+    ///
+    /// source
+    ///     .migrate(children_ref.id().clone(), &target)
+    ///     .expect("Couldn't send the message.");
+    /// #
+    /// # Bastion::start();
+    /// # Bastion::stop();
+    /// # Bastion::block_until_stopped();
+    /// ```
+    ///
+    /// [`Supervisor::with_state_persistence`]: struct.Supervisor.html#method.with_state_persistence
+    /// [`BastionRuntime`]: ../struct.BastionRuntime.html
+    pub fn migrate(&self, id: BastionId, target: &SupervisorRef) -> Result<(), ()> {
+        debug!(
+            "SupervisorRef({}): Migrating Supervised({}) to Supervisor({}).",
+            self.id(),
+            id,
+            target.id()
+        );
+        if target.runtime_id() != self.runtime_id() {
+            warn!(
+                "SupervisorRef({}): Supervisor({}) belongs to another runtime.",
+                self.id(),
+                target.id()
+            );
+            return Err(());
+        }
+        // The target's path holds the identifiers of its parents.
+        if target.path().iter().any(|other| other == &id) {
+            warn!(
+                "SupervisorRef({}): Can't migrate Supervised({}) to itself or one of its descendants.",
+                self.id(),
+                id
+            );
+            return Err(());
+        }
+
+        let msg = BastionMessage::migrate(id, target.clone());
+        let env = Envelope::from_dead_letters(msg, &self.system);
         self.send(env).map_err(|_| ())
     }
 
-    pub(crate) fn send(&self, env: Envelope) -> Result<(), Envelope> {
+    /// Asks the supervisor this `SupervisorRef` is referencing
+    /// for the children groups and supervisors it supervised
+    /// that are stopped (or were killed), in the order they were
+    /// added to it.
+    ///
+    /// This method returns a [`Future`] resolving to the stopped
+    /// elements once the supervisor answered, or to `Err(())` if
+    /// it stopped without answering.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use bastion::prelude::*;
+    /// #
+    /// # Bastion::init();
+    /// # Bastion::start();
+    /// #
+    /// let sp_ref = Bastion::supervisor(|sp| sp).unwrap();
+    ///
+    /// # run!(async {
+    /// let stopped: Vec<SupervisedInfo> = sp_ref
+    ///     .list_stopped()
+    ///     .await
+    ///     .expect("The supervisor stopped.");
+    /// for info in stopped {
+    ///     println!("{} stopped: {:?}", info.id, info.stop_reason);
+    /// }
+    /// # });
+    /// #
+    /// # Bastion::stop();
+    /// # Bastion::block_until_stopped();
+    /// ```
+    ///
+    /// [`Future`]: https://doc.rust-lang.org/std/future/trait.Future.html
+    pub fn list_stopped(&self) -> impl Future<Output = Result<Vec<SupervisedInfo>, ()>> {
+        debug!("SupervisorRef({}): Listing stopped elements.", self.id());
+        let (sender, receiver) = oneshot::channel();
+        let msg = BastionMessage::list_stopped(sender);
+        let env = Envelope::from_dead_letters(msg, &self.system);
+        // If the supervisor stopped, the envelope gets dropped
+        // along with the sender.
+        self.send(env).ok();
+
+        async move { receiver.await.map_err(|_| ()) }
+    }
+
+    /// Asks the supervisor this `SupervisorRef` is referencing
+    /// for a report of the children groups and supervisors it
+    /// supervises, including the reports of the running
+    /// supervisors among them.
+    ///
+    /// This method returns a [`Future`] resolving to the report
+    /// once the supervisor answered, or to `Err(())` if it stopped
+    /// without answering.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use bastion::prelude::*;
+    /// #
+    /// # Bastion::init();
+    /// # Bastion::start();
+    /// #
+    /// let sp_ref = Bastion::supervisor(|sp| sp).unwrap();
+    ///
+    /// # run!(async {
+    /// let report: InspectReport = sp_ref
+    ///     .inspect()
+    ///     .await
+    ///     .expect("The supervisor stopped.");
+    /// for elem in report.children {
+    ///     println!("{:?}({})", elem.kind, elem.id);
+    /// }
+    /// # });
+    /// #
+    /// # Bastion::stop();
+    /// # Bastion::block_until_stopped();
+    /// ```
+    ///
+    /// [`Future`]: https://doc.rust-lang.org/std/future/trait.Future.html
+    pub fn inspect(&self) -> impl Future<Output = Result<InspectReport, ()>> {
+        debug!("SupervisorRef({}): Inspecting.", self.id());
+        let (sender, receiver) = oneshot::channel();
+        let msg = BastionMessage::inspect(sender);
+        let env = Envelope::from_dead_letters(msg, &self.system);
+        // If the supervisor stopped, the envelope gets dropped
+        // along with the sender.
+        self.send(env).ok();
+
+        async move { receiver.await.map_err(|_| ()) }
+    }
+
+    /// Asks the supervisor this `SupervisorRef` is referencing
+    /// for its counters and the ones of the children groups and
+    /// supervisors it supervises, recursively.
+    ///
+    /// This method returns a [`Future`] resolving to the snapshot
+    /// of the counters once every running supervised element
+    /// answered (the ones stopping meanwhile being left out), or
+    /// to `Err(())` if the supervisor stopped.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use bastion::prelude::*;
+    /// #
+    /// # Bastion::init();
+    /// #
+    /// let sp_ref = Bastion::supervisor(|sp| {
+    ///     sp.children(|children| children.with_name("workers"))
+    /// }).expect("Couldn't create the supervisor.");
+    ///
+    /// Bastion::start();
+    ///
+    /// # run!(async {
+    /// let snapshot = sp_ref.metrics().await.expect("The supervisor stopped.");
+    /// let deploys = snapshot.supervisor(sp_ref.id()).unwrap().deploys;
+    /// if let Some(workers) = snapshot.children_named("workers") {
+    ///     println!("workers: {} messages processed", workers.processed);
+    /// }
+    /// # assert_eq!(deploys, 1);
+    /// # });
+    /// #
+    /// # Bastion::stop();
+    /// # Bastion::block_until_stopped();
+    /// ```
+    ///
+    /// [`Future`]: https://doc.rust-lang.org/std/future/trait.Future.html
+    pub fn metrics(&self) -> impl Future<Output = Result<MetricsSnapshot, ()>> {
+        debug!("SupervisorRef({}): Asking for metrics.", self.id());
+        let (sender, receiver) = oneshot::channel();
+        let msg = BastionMessage::metrics(sender);
+        let env = Envelope::from_dead_letters(msg, &self.system);
+        // If the supervisor stopped, the envelope gets dropped
+        // along with the sender.
+        self.send(env).ok();
+
+        async move { receiver.await.map_err(|_| ()) }
+    }
+
+    fn reserve_slot(&self) -> Result<(), ()> {
+        self.slots.reserve().map_err(|capacity| {
+            warn!(
+                "SupervisorRef({}): Capacity of {} supervised elements exceeded.",
+                self.id(),
+                capacity
+            );
+        })
+    }
+
+    fn release_slot(&self) {
+        self.slots.release();
+    }
+
+    pub(crate) fn send(&self, env: Envelope) -> Result<(), Box<Envelope>> {
         trace!("SupervisorRef({}): Sending message: {:?}", self.id(), env);
-        self.sender
-            .unbounded_send(env)
-            .map_err(|err| err.into_inner())
+        self.sender.unbounded_send(env)
     }
 
     pub(crate) fn path(&self) -> &Arc<BastionPath> {
         &self.path
     }
+
+    pub(crate) fn sender(&self) -> &Sender {
+        &self.sender
+    }
+
+    pub(crate) fn system(&self) -> &Arc<GlobalSystem> {
+        &self.system
+    }
 }
 
 impl TrackedChildState {
@@ -1761,8 +4572,10 @@ impl Supervised {
 
     fn stack(&self) -> ProcStack {
         trace!("Supervised({}): Creating ProcStack.", self.id());
-        // FIXME: with_id
-        ProcStack::default()
+        match self {
+            Supervised::Supervisor(supervisor) => supervisor.stack(),
+            Supervised::Children(children) => children.stack(),
+        }
     }
 
     fn id(&self) -> &BastionId {
@@ -1772,6 +4585,13 @@ impl Supervised {
         }
     }
 
+    fn kind(&self) -> SupervisedKind {
+        match self {
+            Supervised::Supervisor(_) => SupervisedKind::Supervisor,
+            Supervised::Children(_) => SupervisedKind::Children,
+        }
+    }
+
     fn bcast(&self) -> &Broadcast {
         match self {
             Supervised::Supervisor(supervisor) => supervisor.bcast(),
@@ -1791,7 +4611,7 @@ impl Supervised {
         let stack = self.stack();
         match self {
             Supervised::Supervisor(supervisor) => {
-                pool::spawn(
+                executor::spawn_proc(
                     async {
                         // FIXME: panics?
                         let supervisor = supervisor.launch().await.unwrap();
@@ -1801,7 +4621,7 @@ impl Supervised {
                 )
             }
             Supervised::Children(children) => {
-                pool::spawn(
+                executor::spawn_proc(
                     async {
                         // FIXME: panics?
                         let children = children.launch().await.unwrap();
@@ -1822,6 +4642,9 @@ impl RestartStrategy {
     /// * `restart_policy` - Defines a restart policy to use for failed actor:
     ///     - [`RestartStrategy::Always`] would restart the
     ///         failed actor each time as it fails.
+    ///     - [`RestartStrategy::OnError`] would restart the
+    ///         failed actor each time its future returns an error,
+    ///         but escalate if it panics.
     ///     - [`RestartStrategy::Never`] would not restart the
     ///         failed actor and remove it from tracking.
     ///     - [`RestartStrategy::Tries`] would restart the
@@ -1850,6 +4673,7 @@ impl RestartStrategy {
     /// ```
     ///
     /// [`RestartStrategy::Always`]: enum.RestartPolicy.html#variant.Always
+    /// [`RestartStrategy::OnError`]: enum.RestartPolicy.html#variant.OnError
     /// [`RestartStrategy::Never`]: enum.RestartPolicy.html#variant.Never
     /// [`RestartStrategy::Tries`]: enum.RestartPolicy.html#variant.Tries
     /// [`ActorRestartStrategy::Immediate`]: enum.ActorRestartStrategy.html#variant.Immediate
@@ -1906,6 +4730,80 @@ impl RestartStrategy {
     }
 }
 
+impl RestartWindow {
+    /// Creates a new `RestartWindow` accepting at most `max`
+    /// restarts within `window`.
+    ///
+    /// # Arguments
+    ///
+    /// * `max` - The maximum number of restarts.
+    /// * `window` - How long a restart is counted for.
+    pub fn new(max: usize, window: Duration) -> Self {
+        let restarts = VecDeque::with_capacity(max + 1);
+
+        RestartWindow {
+            max,
+            window,
+            restarts,
+        }
+    }
+
+    /// Returns the maximum number of restarts within the window.
+    pub fn max(&self) -> usize {
+        self.max
+    }
+
+    /// Returns how long a restart is counted for.
+    pub fn window(&self) -> Duration {
+        self.window
+    }
+
+    /// Records a restart happening now and returns whether the
+    /// restarts within the window don't exceed the maximum.
+    pub fn record_and_check(&mut self) -> bool {
+        let now = Instant::now();
+        while let Some(restart) = self.restarts.front() {
+            if now.duration_since(*restart) < self.window {
+                break;
+            }
+
+            self.restarts.pop_front();
+        }
+
+        self.restarts.push_back(now);
+        self.restarts.len() <= self.max
+    }
+
+    pub(crate) fn clear(&mut self) {
+        self.restarts.clear();
+    }
+}
+
+impl Debug for Routing {
+    fn fmt(&self, fmt: &mut Formatter) -> fmt::Result {
+        match self {
+            Routing::Broadcast => write!(fmt, "Broadcast"),
+            Routing::RoundRobin => write!(fmt, "RoundRobin"),
+            Routing::ByKey(key) => fmt.debug_tuple("ByKey").field(key).finish(),
+            Routing::TagBased(_) => write!(fmt, "TagBased"),
+        }
+    }
+}
+
+impl<'a> Iterator for SupervisionTree<'a> {
+    type Item = (usize, &'a InspectedElement);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let (depth, elem) = self.queue.pop_front()?;
+        if let Some(report) = &elem.report {
+            self.queue
+                .extend(SupervisionTree::running(report, depth + 1));
+        }
+
+        Some((depth, elem))
+    }
+}
+
 impl Default for SupervisionStrategy {
     fn default() -> Self {
         SupervisionStrategy::OneForOne
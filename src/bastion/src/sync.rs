@@ -0,0 +1,434 @@
+//!
+//! Synchronization primitives allowing elements (of the same or of
+//! different children groups) to coordinate without exchanging
+//! messages, and external code to signal them (see [`signal`]).
+//!
+//! [`signal`]: fn.signal.html
+use futures::channel::{mpsc, oneshot};
+use futures::stream::FusedStream;
+use futures::task::AtomicWaker;
+use futures::Stream;
+use std::any::Any;
+use std::pin::Pin;
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex};
+use std::task::{Context, Poll};
+use tracing::trace;
+
+// The number of values a signal created using `signal` can hold
+// before sending more fails.
+const DEFAULT_SIGNAL_CAPACITY: usize = 32;
+
+#[derive(Debug, Clone)]
+/// A barrier letting a number of elements (or any other futures)
+/// wait for each other, like [`std::sync::Barrier`] but without
+/// blocking the threads they run on.
+///
+/// Once as many futures as the barrier was created for are waiting
+/// on it, they all resume at the same time. The barrier can then be
+/// waited on again, by the same number of futures.
+///
+/// A barrier can be cloned (its clones referring to the same
+/// barrier) to be shared by the elements of different children
+/// groups.
+///
+/// Note that an element that gets stopped or killed while waiting
+/// on a barrier still counts as waiting on it.
+///
+/// # Example
+///
+/// ```rust
+/// # use bastion::prelude::*;
+/// use bastion::sync::Barrier;
+/// #
+/// # Bastion::init();
+///
+/// let barrier = Barrier::new(3);
+///
+/// Bastion::children(|children| {
+///     children
+///         .with_redundancy(3)
+///         .with_exec(move |ctx: BastionContext| {
+///             let barrier = barrier.clone();
+///             async move {
+///                 // Some setup...
+///                 ctx.wait_barrier(&barrier).await;
+///                 // ...all three elements are now set up.
+///
+///                 Ok(())
+///             }
+///         })
+/// }).expect("Couldn't create the children group.");
+/// #
+/// # Bastion::start();
+/// # Bastion::stop();
+/// # Bastion::block_until_stopped();
+/// ```
+///
+/// [`std::sync::Barrier`]: https://doc.rust-lang.org/std/sync/struct.Barrier.html
+pub struct Barrier {
+    n: usize,
+    // The senders waking up the futures currently waiting on the
+    // barrier.
+    waiting: Arc<Mutex<Vec<oneshot::Sender<()>>>>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+/// What waiting on a [`Barrier`] returns once all the futures it
+/// was created for waited on it.
+///
+/// [`Barrier`]: struct.Barrier.html
+pub struct BarrierWaitResult(bool);
+
+impl Barrier {
+    /// Creates a new barrier resuming the futures waiting on it
+    /// once `n` of them are.
+    ///
+    /// Like with [`std::sync::Barrier`], waiting on a barrier
+    /// created for zero futures never blocks.
+    ///
+    /// # Arguments
+    ///
+    /// * `n` - The number of futures that need to wait on the
+    ///     barrier for them to resume.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use bastion::sync::Barrier;
+    ///
+    /// let barrier = Barrier::new(2);
+    /// ```
+    ///
+    /// [`std::sync::Barrier`]: https://doc.rust-lang.org/std/sync/struct.Barrier.html
+    pub fn new(n: usize) -> Self {
+        let waiting = Arc::new(Mutex::new(Vec::with_capacity(n)));
+        Barrier { n, waiting }
+    }
+
+    /// Waits until as many futures as this barrier was created for
+    /// are waiting on it, returning whether this one was the last.
+    ///
+    /// Elements can use [`BastionContext::wait_barrier`] instead.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use bastion::prelude::*;
+    /// use bastion::sync::Barrier;
+    /// #
+    /// # Bastion::init();
+    ///
+    /// let barrier = Barrier::new(2);
+    /// let other = barrier.clone();
+    /// spawn!(async move {
+    ///     other.wait().await;
+    /// });
+    ///
+    /// # run!(async {
+    /// if barrier.wait().await.is_leader() {
+    ///     // ...
+    /// }
+    /// # });
+    /// ```
+    ///
+    /// [`BastionContext::wait_barrier`]: ../context/struct.BastionContext.html#method.wait_barrier
+    pub async fn wait(&self) -> BarrierWaitResult {
+        let receiver = {
+            // FIXME: panics?
+            let mut waiting = self.waiting.lock().unwrap();
+            if waiting.len() + 1 >= self.n {
+                trace!("Barrier: Releasing {} waiting futures.", waiting.len());
+                for sender in waiting.drain(..) {
+                    // The future might have been dropped.
+                    sender.send(()).ok();
+                }
+
+                return BarrierWaitResult(true);
+            }
+
+            let (sender, receiver) = oneshot::channel();
+            waiting.push(sender);
+            receiver
+        };
+
+        // The barrier holds the sender until sending to it.
+        receiver.await.ok();
+        BarrierWaitResult(false)
+    }
+}
+
+impl BarrierWaitResult {
+    /// Returns whether the future that got this result was the
+    /// last one to wait on the barrier (only one of the futures
+    /// released at the same time is).
+    pub fn is_leader(&self) -> bool {
+        self.0
+    }
+}
+
+/// Creates a signal allowing external code (e.g. a Tokio runtime
+/// or a signal handler) to poke an element, returning its sending
+/// and receiving halves.
+///
+/// The [`SignalReceiver`] is a [`Stream`] of the values sent using
+/// the [`SignalSender`] (or one of its clones), which an element
+/// can poll alongside [`BastionContext::recv`] using
+/// `futures::select!`.
+///
+/// Sending never blocks nor locks: the signal holds at most 32
+/// values (see [`signal_with_capacity`]) before sending more fails,
+/// and unit signals raised using [`SignalSender::raise`] are
+/// coalesced until received.
+///
+/// # Example
+///
+/// ```rust
+/// # use bastion::prelude::*;
+/// use bastion::sync::signal;
+/// use futures::{select, FutureExt, StreamExt};
+/// use std::sync::{Arc, Mutex};
+/// #
+/// # Bastion::init();
+///
+/// let (reload, receiver) = signal::<()>();
+/// // The receiver is given to the first element only.
+/// let receiver = Arc::new(Mutex::new(Some(receiver)));
+///
+/// Bastion::children(|children| {
+///     children.with_exec(move |ctx: BastionContext| {
+///         let receiver = receiver.lock().unwrap().take();
+///         async move {
+///             let mut reload = receiver.ok_or(())?;
+///             loop {
+///                 select! {
+///                     msg = ctx.recv().fuse() => {
+///                         // Handle the message...
+///                         # msg?;
+///                     }
+///                     _ = reload.next() => {
+///                         // Reload the configuration...
+///                     }
+///                 }
+///             }
+///         }
+///     })
+/// }).expect("Couldn't create the children group.");
+///
+/// // From outside the actor system...
+/// reload.raise().expect("The element stopped.");
+/// #
+/// # Bastion::start();
+/// # Bastion::stop();
+/// # Bastion::block_until_stopped();
+/// ```
+///
+/// [`SignalReceiver`]: struct.SignalReceiver.html
+/// [`SignalSender`]: struct.SignalSender.html
+/// [`SignalSender::raise`]: struct.SignalSender.html#method.raise
+/// [`Stream`]: https://docs.rs/futures/0.3/futures/stream/trait.Stream.html
+/// [`BastionContext::recv`]: ../context/struct.BastionContext.html#method.recv
+/// [`signal_with_capacity`]: fn.signal_with_capacity.html
+pub fn signal<T: Send + 'static>() -> (SignalSender<T>, SignalReceiver<T>) {
+    signal_with_capacity(DEFAULT_SIGNAL_CAPACITY)
+}
+
+/// Creates a signal like [`signal`] does, holding at most the
+/// given number of values before sending more fails.
+///
+/// # Arguments
+///
+/// * `capacity` - The number of values sent but not received yet
+///     that the signal can hold.
+///
+/// [`signal`]: fn.signal.html
+pub fn signal_with_capacity<T: Send + 'static>(
+    capacity: usize,
+) -> (SignalSender<T>, SignalReceiver<T>) {
+    let (sender, receiver) = mpsc::unbounded();
+    let shared = Arc::new(SignalShared {
+        capacity,
+        pending: AtomicUsize::new(0),
+        raised: AtomicBool::new(false),
+        waker: AtomicWaker::new(),
+        closed: AtomicBool::new(false),
+    });
+
+    let sender = SignalSender {
+        values: sender,
+        shared: shared.clone(),
+    };
+    let receiver = SignalReceiver {
+        values: receiver,
+        shared,
+        terminated: false,
+    };
+    (sender, receiver)
+}
+
+#[derive(Debug)]
+// The state shared by the halves of a signal.
+struct SignalShared {
+    // The number of values the signal can hold, and the number of
+    // values sent but not received yet.
+    capacity: usize,
+    pending: AtomicUsize,
+    // Whether a unit signal was raised but not received yet, and
+    // the waker of the receiver waiting for one.
+    raised: AtomicBool,
+    waker: AtomicWaker,
+    // Whether the receiver was dropped.
+    closed: AtomicBool,
+}
+
+#[derive(Debug)]
+/// The sending half of a signal (see [`signal`]), which can be
+/// cloned to be shared by different threads.
+///
+/// [`signal`]: fn.signal.html
+pub struct SignalSender<T> {
+    values: mpsc::UnboundedSender<T>,
+    shared: Arc<SignalShared>,
+}
+
+#[derive(Debug)]
+/// The receiving half of a signal (see [`signal`]), which is a
+/// [`Stream`] of the values sent to it, ending once all of its
+/// senders were dropped.
+///
+/// Dropping it makes sending to the signal fail.
+///
+/// [`signal`]: fn.signal.html
+/// [`Stream`]: https://docs.rs/futures/0.3/futures/stream/trait.Stream.html
+pub struct SignalReceiver<T> {
+    values: mpsc::UnboundedReceiver<T>,
+    shared: Arc<SignalShared>,
+    terminated: bool,
+}
+
+impl<T> SignalSender<T> {
+    /// Sends a value to the signal's receiver, without blocking.
+    ///
+    /// This method returns `()` if it succeeded, or `Err(value)`
+    /// if the signal already holds as many values as it can or if
+    /// its receiver was dropped.
+    pub fn send(&self, value: T) -> Result<(), T> {
+        if self.is_closed() {
+            return Err(value);
+        }
+
+        let capacity = self.shared.capacity;
+        let reserved =
+            self.shared
+                .pending
+                .fetch_update(Ordering::AcqRel, Ordering::Acquire, |pending| {
+                    if pending < capacity {
+                        Some(pending + 1)
+                    } else {
+                        None
+                    }
+                });
+        if reserved.is_err() {
+            trace!("SignalSender: Full.");
+            return Err(value);
+        }
+
+        self.values.unbounded_send(value).map_err(|err| {
+            self.shared.pending.fetch_sub(1, Ordering::AcqRel);
+            err.into_inner()
+        })
+    }
+
+    /// Returns whether the signal's receiver was dropped, in which
+    /// case sending to it fails.
+    pub fn is_closed(&self) -> bool {
+        self.shared.closed.load(Ordering::Acquire)
+    }
+}
+
+impl SignalSender<()> {
+    /// Raises a unit signal, without blocking. Raising it again
+    /// before the receiver received it has no effect, the receiver
+    /// then only receiving it once.
+    ///
+    /// This method returns `()` if it succeeded, or `Err(())` if
+    /// the signal's receiver was dropped.
+    pub fn raise(&self) -> Result<(), ()> {
+        if self.is_closed() {
+            return Err(());
+        }
+
+        self.shared.raised.store(true, Ordering::Release);
+        self.shared.waker.wake();
+        Ok(())
+    }
+}
+
+impl<T> Clone for SignalSender<T> {
+    fn clone(&self) -> Self {
+        SignalSender {
+            values: self.values.clone(),
+            shared: self.shared.clone(),
+        }
+    }
+}
+
+impl<T: 'static> SignalReceiver<T> {
+    // Returns a raised unit signal, which can only have been raised
+    // if `T` is `()`.
+    fn take_raised(&self) -> Option<T> {
+        if !self.shared.raised.swap(false, Ordering::AcqRel) {
+            return None;
+        }
+
+        let unit: Box<dyn Any> = Box::new(());
+        unit.downcast().ok().map(|unit| *unit)
+    }
+}
+
+impl<T: 'static> Stream for SignalReceiver<T> {
+    type Item = T;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context) -> Poll<Option<T>> {
+        if self.terminated {
+            return Poll::Ready(None);
+        }
+
+        if let Some(unit) = self.take_raised() {
+            return Poll::Ready(Some(unit));
+        }
+
+        match Pin::new(&mut self.values).poll_next(cx) {
+            Poll::Ready(Some(value)) => {
+                self.shared.pending.fetch_sub(1, Ordering::AcqRel);
+                Poll::Ready(Some(value))
+            }
+            Poll::Ready(None) => {
+                self.terminated = true;
+                Poll::Ready(None)
+            }
+            Poll::Pending => {
+                self.shared.waker.register(cx.waker());
+                // The signal might have been raised before the
+                // waker got registered.
+                match self.take_raised() {
+                    Some(unit) => Poll::Ready(Some(unit)),
+                    None => Poll::Pending,
+                }
+            }
+        }
+    }
+}
+
+impl<T: 'static> FusedStream for SignalReceiver<T> {
+    fn is_terminated(&self) -> bool {
+        self.terminated
+    }
+}
+
+impl<T> Drop for SignalReceiver<T> {
+    fn drop(&mut self) {
+        trace!("SignalReceiver: Dropped.");
+        self.shared.closed.store(true, Ordering::Release);
+    }
+}
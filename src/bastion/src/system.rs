@@ -1,42 +1,105 @@
+use crate::bastion::SystemExit;
 use crate::broadcast::{Broadcast, Parent, Sender};
+use crate::callbacks::Callbacks;
 use crate::children_ref::ChildrenRef;
-use crate::context::{BastionContext, BastionId, NIL_ID};
+use crate::config::{self, Config};
+use crate::context::{next_pid, BastionContext, BastionId, NIL_ID};
 use crate::dispatcher::GlobalDispatcher;
-use crate::envelope::Envelope;
+use crate::dump::{self, DumpedElement};
+use crate::envelope::{Envelope, RefAddr};
+use crate::events::{EntityKind, EventBus, Transition};
+use crate::executor;
+use crate::link::LinkRegistry;
 use crate::message::{BastionMessage, Deployment};
+use crate::metrics::MetricsSnapshot;
+use crate::names::NameRegistry;
+use crate::panic_handler;
 use crate::path::{BastionPath, BastionPathElement};
-use crate::supervisor::{Supervisor, SupervisorRef};
+use crate::runtime::RuntimeId;
+use crate::scheduler::Timers;
+use crate::supervisor::{
+    InspectReport, InspectedElement, ShutdownReport, Slots, StopReason, SupervisedInfo,
+    SupervisedKind, Supervisor, SupervisorRef, TypedChildren,
+};
+use crate::topic::TopicRegistry;
 use async_mutex::Mutex as AsyncMutex;
-use bastion_executor::pool;
+use futures::channel::oneshot;
 use futures::prelude::*;
-use futures::stream::FuturesUnordered;
-use futures::{pending, poll};
+use futures::stream::{FuturesOrdered, FuturesUnordered};
 use fxhash::{FxHashMap, FxHashSet};
 use lazy_static::lazy_static;
 use lightproc::prelude::*;
-use std::sync::{Arc, Condvar, Mutex};
+use std::fmt::{self, Debug, Formatter};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Arc, Condvar, Mutex, OnceLock};
 use std::task::Poll;
+use std::time::{Duration, Instant};
 use tracing::{debug, error, info, trace, warn};
 
 lazy_static! {
-    pub(crate) static ref SYSTEM: GlobalSystem = System::init();
+    // The system of the default runtime, used by `Bastion`.
+    pub(crate) static ref SYSTEM: Arc<GlobalSystem> = System::init(config::current());
 }
 
+// The state shared by everything running in a runtime (see
+// `BastionRuntime`), which `Bastion` uses a default one of.
 pub(crate) struct GlobalSystem {
-    sender: Sender,
-    supervisor: SupervisorRef,
-    dead_letters: ChildrenRef,
+    id: RuntimeId,
+    config: Config,
+    // Those are only set once the system was launched, since the
+    // system needs to be shared with them before.
+    sender: OnceLock<Sender>,
+    supervisor: OnceLock<RefAddr>,
+    // The pid set on the system supervisor's process, which
+    // changes when it is recovered.
+    supervisor_pid: AtomicUsize,
+    dead_letters: OnceLock<RefAddr>,
     path: Arc<BastionPath>,
     handle: Arc<AsyncMutex<Option<RecoverableHandle<()>>>>,
-    running: Mutex<bool>,
+    // How the system stopped, or `None` while it is running.
+    exit: Mutex<Option<SystemExit>>,
     stopping_cvar: Condvar,
     dispatcher: GlobalDispatcher,
+    topics: TopicRegistry,
+    timers: Timers,
+    links: LinkRegistry,
+    names: NameRegistry,
+    events: EventBus,
+    shutdown: ShutdownTracker,
+    // The number of running children groups elements and
+    // supervisors, without counting the system's own.
+    actors: Arc<AtomicUsize>,
+    supervisors: Arc<AtomicUsize>,
+    // The number of running children groups, without counting the
+    // dead letters' one.
+    children_groups: Arc<AtomicUsize>,
+    // The number of restarts and of delivered messages since the
+    // system started.
+    restarts: AtomicUsize,
+    delivered: AtomicUsize,
+    started_at: OnceLock<Instant>,
+    // Keeps bastion's own executor running until the system
+    // stopped, or until it gets dropped if it never did.
+    retained: Mutex<Option<executor::Retained>>,
 }
 
+// Keeps track of the supervisors and children groups asked to
+// stop by `Bastion::stop_with_timeout`, and of which of them
+// acknowledged that they stopped, while it is waiting.
+#[derive(Debug, Default)]
+pub(crate) struct ShutdownTracker(Mutex<Option<Vec<SupervisedInfo>>>);
+
+// Counts a running children group element or supervisor until
+// dropped (which also happens if it panicked or was killed).
+#[derive(Debug)]
+pub(crate) struct RunningGuard(Arc<AtomicUsize>);
+
 #[derive(Debug)]
 struct System {
     bcast: Broadcast,
     launched: FxHashMap<BastionId, RecoverableHandle<Supervisor>>,
+    // The order in which the top-level supervisors were deployed.
+    order: Vec<BastionId>,
     // TODO: set limit
     restart: FxHashSet<BastionId>,
     waiting: FuturesUnordered<RecoverableHandle<Supervisor>>,
@@ -44,43 +107,109 @@ struct System {
     started: bool,
 }
 
+// What the system got woken up by in its run loop (only living
+// until it handled it, hence not boxing the supervisor).
+#[allow(clippy::large_enum_variant)]
+enum Wakeup {
+    // A supervisor it was waiting for stopped (or panicked, in
+    // which case its state can't be recovered).
+    Stopped(Option<Supervisor>),
+    Received(Option<Envelope>),
+}
+
 #[allow(clippy::mutex_atomic)]
 impl GlobalSystem {
-    fn new(
-        sender: Sender,
-        supervisor: SupervisorRef,
-        dead_letters: ChildrenRef,
-        handle: RecoverableHandle<()>,
-    ) -> Self {
-        let handle = Some(handle);
-        let handle = Arc::new(AsyncMutex::new(handle));
+    fn new(config: Config) -> Self {
+        let id = RuntimeId::new();
+        let sender = OnceLock::new();
+        let supervisor = OnceLock::new();
+        let supervisor_pid = AtomicUsize::new(0);
+        let dead_letters = OnceLock::new();
+        let handle = Arc::new(AsyncMutex::new(None));
         let path = Arc::new(BastionPath::root());
-        let running = Mutex::new(true);
+        let exit = Mutex::new(None);
         let stopping_cvar = Condvar::new();
         let dispatcher = GlobalDispatcher::new();
+        let topics = TopicRegistry::new();
+        let timers = Timers::new();
+        let links = LinkRegistry::new();
+        let names = NameRegistry::new();
+        let events = EventBus::new();
+        let shutdown = ShutdownTracker::default();
+        let actors = Arc::new(AtomicUsize::new(0));
+        let supervisors = Arc::new(AtomicUsize::new(0));
+        let children_groups = Arc::new(AtomicUsize::new(0));
+        let restarts = AtomicUsize::new(0);
+        let delivered = AtomicUsize::new(0);
+        let started_at = OnceLock::new();
+        let retained = Mutex::new(Some(executor::retain()));
 
         GlobalSystem {
+            id,
+            config,
             sender,
             supervisor,
+            supervisor_pid,
             dead_letters,
             path,
             handle,
-            running,
+            exit,
             stopping_cvar,
             dispatcher,
+            topics,
+            timers,
+            links,
+            names,
+            events,
+            shutdown,
+            actors,
+            supervisors,
+            children_groups,
+            restarts,
+            delivered,
+            started_at,
+            retained,
         }
     }
 
+    // Launches a new system, for a new runtime.
+    pub(crate) fn launch(config: Config) -> Arc<Self> {
+        System::init(config)
+    }
+
+    pub(crate) fn id(&self) -> RuntimeId {
+        self.id
+    }
+
+    pub(crate) fn config(&self) -> &Config {
+        &self.config
+    }
+
     pub(crate) fn sender(&self) -> &Sender {
-        &self.sender
+        // FIXME: panics
+        self.sender.get().expect("System not launched.")
     }
 
-    pub(crate) fn supervisor(&self) -> &SupervisorRef {
-        &self.supervisor
+    // Returns a reference to the system supervisor (the refs
+    // aren't kept since they hold the system).
+    pub(crate) fn supervisor(self: &Arc<Self>) -> SupervisorRef {
+        // FIXME: panics
+        let supervisor = self.supervisor.get().expect("System not launched.");
+        SupervisorRef::new(
+            supervisor.path().id().clone(),
+            self.supervisor_pid.load(Ordering::SeqCst),
+            supervisor.sender().clone(),
+            supervisor.path().clone(),
+            None,
+            Arc::new(Slots::new()),
+            Arc::new(TypedChildren::default()),
+            self.clone(),
+        )
     }
 
-    pub(crate) fn dead_letters(&self) -> &ChildrenRef {
-        &self.dead_letters
+    pub(crate) fn dead_letters(&self) -> &RefAddr {
+        // FIXME: panics
+        self.dead_letters.get().expect("System not launched.")
     }
 
     pub(crate) fn handle(&self) -> Arc<AsyncMutex<Option<RecoverableHandle<()>>>> {
@@ -95,37 +224,198 @@ impl GlobalSystem {
         &self.dispatcher
     }
 
-    pub(crate) fn notify_stopped(&self) {
+    pub(crate) fn events(&self) -> &EventBus {
+        &self.events
+    }
+
+    pub(crate) fn topics(&self) -> &TopicRegistry {
+        &self.topics
+    }
+
+    pub(crate) fn timers(&self) -> &Timers {
+        &self.timers
+    }
+
+    pub(crate) fn links(&self) -> &LinkRegistry {
+        &self.links
+    }
+
+    pub(crate) fn names(&self) -> &NameRegistry {
+        &self.names
+    }
+
+    pub(crate) fn shutdown(&self) -> &ShutdownTracker {
+        &self.shutdown
+    }
+
+    pub(crate) fn actors(&self) -> &Arc<AtomicUsize> {
+        &self.actors
+    }
+
+    pub(crate) fn supervisors(&self) -> &Arc<AtomicUsize> {
+        &self.supervisors
+    }
+
+    pub(crate) fn children_groups(&self) -> &Arc<AtomicUsize> {
+        &self.children_groups
+    }
+
+    pub(crate) fn restarts(&self) -> &AtomicUsize {
+        &self.restarts
+    }
+
+    pub(crate) fn delivered(&self) -> &AtomicUsize {
+        &self.delivered
+    }
+
+    // Returns how long ago the system started, or zero if it
+    // didn't yet.
+    pub(crate) fn uptime(&self) -> Duration {
+        self.started_at
+            .get()
+            .map(Instant::elapsed)
+            .unwrap_or_default()
+    }
+
+    // Only the first way the system stopped is kept (e.g. the
+    // system reports it got killed after `Bastion::kill` did).
+    pub(crate) fn notify_stopped(&self, exit: SystemExit) {
+        self.timers.cancel_all();
+        self.events.close();
         // FIXME: panics
-        *self.running.lock().unwrap() = false;
+        let mut stopped = self.exit.lock().unwrap();
+        if stopped.is_none() {
+            *stopped = Some(exit);
+            // FIXME: panics
+            self.retained.lock().unwrap().take();
+        }
         self.stopping_cvar.notify_all();
     }
 
-    pub(crate) fn wait_until_stopped(&self) {
+    #[cfg(feature = "http-health")]
+    pub(crate) fn has_started(&self) -> bool {
+        self.started_at.get().is_some()
+    }
+
+    pub(crate) fn has_stopped(&self) -> bool {
+        // FIXME: panics
+        self.exit.lock().unwrap().is_some()
+    }
+
+    pub(crate) fn wait_until_stopped(&self) -> SystemExit {
+        // FIXME: panics
+        let mut stopped = self.exit.lock().unwrap();
+        let exit = loop {
+            match &*stopped {
+                Some(exit) => break exit.clone(),
+                None => stopped = self.stopping_cvar.wait(stopped).unwrap(),
+            }
+        };
+        drop(stopped);
+
+        executor::block_until_quiesced();
+        exit
+    }
+
+    // Returns whether the system stopped before the timeout.
+    pub(crate) fn wait_until_stopped_timeout(&self, timeout: Duration) -> bool {
+        let deadline = Instant::now() + timeout;
+        // FIXME: panics
+        let mut exit = self.exit.lock().unwrap();
+        while exit.is_none() {
+            let now = Instant::now();
+            if now >= deadline {
+                return false;
+            }
+
+            exit = self
+                .stopping_cvar
+                .wait_timeout(exit, deadline - now)
+                .unwrap()
+                .0;
+        }
+
+        true
+    }
+}
+
+impl Debug for GlobalSystem {
+    fn fmt(&self, fmt: &mut Formatter) -> fmt::Result {
+        fmt.debug_struct("GlobalSystem")
+            .field("id", &self.id)
+            .finish()
+    }
+}
+
+impl ShutdownTracker {
+    pub(crate) fn begin(&self) {
+        // FIXME: panics
+        *self.0.lock().unwrap() = Some(Vec::new());
+    }
+
+    // Records that the supervisor or children group was asked to
+    // stop, counting it as killed until it acknowledges it stopped.
+    pub(crate) fn expect(&self, id: &BastionId, kind: SupervisedKind) {
+        // FIXME: panics
+        if let Some(elems) = &mut *self.0.lock().unwrap() {
+            trace!("System: Waiting for Supervised({}) to stop.", id);
+            elems.push(SupervisedInfo {
+                id: id.clone(),
+                kind,
+                stop_reason: StopReason::Killed,
+            });
+        }
+    }
+
+    pub(crate) fn acknowledge(&self, id: &BastionId) {
         // FIXME: panics
-        let mut running = self.running.lock().unwrap();
-        while *running {
-            running = self.stopping_cvar.wait(running).unwrap();
+        if let Some(elems) = &mut *self.0.lock().unwrap() {
+            if let Some(info) = elems.iter_mut().find(|info| &info.id == id) {
+                trace!("System: Supervised({}) stopped in time.", id);
+                info.stop_reason = StopReason::Stopped;
+            }
         }
     }
+
+    pub(crate) fn finish(&self) -> ShutdownReport {
+        // FIXME: panics
+        let elems = self.0.lock().unwrap().take().unwrap_or_default();
+        ShutdownReport::new(elems)
+    }
+}
+
+impl RunningGuard {
+    pub(crate) fn new(running: Arc<AtomicUsize>) -> Self {
+        running.fetch_add(1, Ordering::SeqCst);
+        RunningGuard(running)
+    }
+}
+
+impl Drop for RunningGuard {
+    fn drop(&mut self) {
+        self.0.fetch_sub(1, Ordering::SeqCst);
+    }
 }
 
 impl System {
-    fn init() -> GlobalSystem {
+    fn init(config: Config) -> Arc<GlobalSystem> {
         info!("System: Initializing.");
-        let parent = Parent::none();
-        let bcast = Broadcast::new_root(parent);
+        let global = Arc::new(GlobalSystem::new(config));
+        let bcast = Broadcast::new_root(global.clone());
         let launched = FxHashMap::default();
+        let order = Vec::new();
         let restart = FxHashSet::default();
         let waiting = FuturesUnordered::new();
         let pre_start_msgs = Vec::new();
         let started = false;
 
-        let sender = bcast.sender().clone();
+        // FIXME: Err(Error)
+        global.sender.set(bcast.sender().clone()).ok();
 
         let system = System {
             bcast,
             launched,
+            order,
             restart,
             waiting,
             pre_start_msgs,
@@ -133,11 +423,19 @@ impl System {
         };
 
         debug!("System: Creating the system supervisor.");
-        let parent = Parent::system();
+        let parent = Parent::system(global.clone());
         let bcast = Broadcast::new(parent, BastionPathElement::Supervisor(NIL_ID));
 
         let supervisor = Supervisor::system(bcast);
         let supervisor_ref = supervisor.as_ref();
+        let supervisor_addr = RefAddr::new(
+            supervisor_ref.path().clone(),
+            supervisor_ref.sender().clone(),
+        );
+        global.supervisor.set(supervisor_addr).ok();
+        global
+            .supervisor_pid
+            .store(supervisor_ref.pid(), Ordering::SeqCst);
 
         let msg = BastionMessage::deploy_supervisor(supervisor);
         let env = Envelope::new(
@@ -149,36 +447,60 @@ impl System {
 
         debug!("System: Launching.");
         let stack = system.stack();
-        let handle = pool::spawn(system.run(), stack);
+        let handle = executor::spawn_proc(system.run(), stack);
+        // Nothing else can hold the lock yet.
+        *global.handle.try_lock().unwrap() = Some(handle);
 
         let dead_letters_ref =
             Self::spawn_dead_letters(&supervisor_ref).expect("Can't spawn dead letters");
+        let dead_letters_addr = RefAddr::new(
+            dead_letters_ref.path().clone(),
+            dead_letters_ref.sender().clone(),
+        );
+        global.dead_letters.set(dead_letters_addr).ok();
 
-        GlobalSystem::new(sender, supervisor_ref, dead_letters_ref, handle)
+        global
     }
 
     fn stack(&self) -> ProcStack {
-        // FIXME: with_id
         ProcStack::default()
+            .with_pid(next_pid())
+            .with_high_priority(true)
     }
 
     fn spawn_dead_letters(root_sv: &SupervisorRef) -> Result<ChildrenRef, ()> {
         root_sv.children_with_id(NIL_ID, |children| {
-            children.with_exec(|ctx: BastionContext| async move {
-                loop {
-                    let smsg = ctx.recv().await?;
-                    debug!("Received dead letter: {:?}", smsg);
-                }
-            })
+            // The default callbacks are only meant for users' ones.
+            children
+                .with_callbacks(Callbacks::new())
+                .with_exec(|ctx: BastionContext| async move {
+                    loop {
+                        let smsg = ctx.recv().await?;
+                        debug!("Received dead letter: {:?}", smsg);
+                        lifecycle_event!(
+                            "message_dead_lettered",
+                            sender = %smsg.signature().path(),
+                        );
+                    }
+                })
         })
     }
 
     // TODO: set a limit?
     async fn recover(&mut self, mut supervisor: Supervisor) {
         warn!("System: Recovering Supervisor({}).", supervisor.id());
+        lifecycle_event!("supervisor_restarted", restarted = %supervisor.id());
+        self.bcast.system().events().publish(
+            Transition::Restarted,
+            EntityKind::Supervisor,
+            supervisor.id(),
+            None,
+            supervisor.given_name().map(String::as_str),
+        );
         supervisor.callbacks().before_restart();
 
-        let parent = Parent::system();
+        let old_id = supervisor.id().clone();
+        let parent = Parent::system(self.bcast.system().clone());
         let bcast = if supervisor.id() == &NIL_ID {
             None
         } else {
@@ -189,12 +511,27 @@ impl System {
         };
 
         supervisor.reset(bcast).await;
+        if supervisor.id() == &NIL_ID {
+            self.bcast
+                .system()
+                .supervisor_pid
+                .store(supervisor.pid(), Ordering::SeqCst);
+        }
         supervisor.callbacks().after_restart();
+        self.bcast
+            .system()
+            .restarts()
+            .fetch_add(1, Ordering::SeqCst);
 
         self.bcast.register(supervisor.bcast());
 
         info!("System: Launching Supervisor({}).", supervisor.id());
         let id = supervisor.id().clone();
+        // The restarted supervisor keeps its place in the
+        // deployment order, under its new identifier.
+        if let Some(pos) = self.order.iter().position(|other| other == &old_id) {
+            self.order[pos] = id.clone();
+        }
         let launched = supervisor.launch();
         self.launched.insert(id, launched);
     }
@@ -202,24 +539,31 @@ impl System {
     async fn stop(&mut self) -> Vec<Supervisor> {
         self.bcast.stop_children();
 
-        for (_, launched) in self.launched.drain() {
+        for (id, launched) in self.launched.drain() {
+            if id != NIL_ID {
+                self.bcast
+                    .system()
+                    .shutdown()
+                    .expect(&id, SupervisedKind::Supervisor);
+            }
+
             self.waiting.push(launched);
         }
 
         let mut supervisors = Vec::new();
-        loop {
-            match poll!(&mut self.waiting.next()) {
-                Poll::Ready(Some(Some(supervisor))) => {
+        while let Some(stopped) = self.waiting.next().await {
+            match stopped {
+                Some(supervisor) => {
                     debug!("System: Supervisor({}) stopped.", supervisor.id());
                     supervisors.push(supervisor);
                 }
-                Poll::Ready(Some(None)) => {
+                None => {
                     error!("System: Unknown supervisor cancelled instead of stopped.");
                 }
-                Poll::Ready(None) => return supervisors,
-                Poll::Pending => pending!(),
             }
         }
+
+        supervisors
     }
 
     async fn kill(&mut self) {
@@ -235,16 +579,14 @@ impl System {
             self.waiting.push(launched);
         }
 
-        loop {
-            match poll!(&mut self.waiting.next()) {
-                Poll::Ready(Some(Some(supervisor))) => {
+        while let Some(killed) = self.waiting.next().await {
+            match killed {
+                Some(supervisor) => {
                     debug!("System: Supervisor({}) killed.", supervisor.id());
                 }
-                Poll::Ready(Some(None)) => {
+                None => {
                     debug!("System: Unknown Supervisor killed.");
                 }
-                Poll::Ready(None) => return,
-                Poll::Pending => pending!(),
             }
         }
     }
@@ -252,7 +594,23 @@ impl System {
     async fn deploy(&mut self, deployment: Box<Deployment>) {
         match *deployment {
             Deployment::Supervisor(supervisor) => {
+                if supervisor.bcast().system().id() != self.bcast.system().id() {
+                    warn!(
+                        "System: Refusing to deploy Supervisor({}) from another runtime.",
+                        supervisor.id()
+                    );
+                    return;
+                }
+
                 debug!("System: Deploying Supervisor({}).", supervisor.id());
+                lifecycle_event!("supervisor_deployed", deployed = %supervisor.id());
+                self.bcast.system().events().publish(
+                    Transition::Deployed,
+                    EntityKind::Supervisor,
+                    supervisor.id(),
+                    None,
+                    supervisor.given_name().map(String::as_str),
+                );
                 supervisor.callbacks().before_start();
 
                 self.bcast.register(supervisor.bcast());
@@ -266,6 +624,7 @@ impl System {
                 info!("System: Launching Supervisor({}).", supervisor.id());
                 let id = supervisor.id().clone();
                 let launched = supervisor.launch();
+                self.order.push(id.clone());
                 self.launched.insert(id, launched);
             }
             // FIXME
@@ -275,6 +634,7 @@ impl System {
 
     async fn prune_supervised_object(&mut self, id: BastionId) {
         // TODO: Err if None?
+        self.order.retain(|other| other != &id);
         if let Some(launched) = self.launched.remove(&id) {
             // TODO: stop or kill?
             self.bcast.kill_child(&id);
@@ -282,6 +642,136 @@ impl System {
         }
     }
 
+    // Answers with the report of the system supervisor, to which
+    // the ones of the top-level supervisors are added.
+    fn inspect(&self, reply: oneshot::Sender<InspectReport>) {
+        let mut reports = FuturesOrdered::new();
+        // The system supervisor isn't part of the deployment order
+        // but is always launched.
+        let ids = std::iter::once(&NIL_ID).chain(self.order.iter());
+        for id in ids.filter(|id| self.launched.contains_key(id)) {
+            let (sender, receiver) = oneshot::channel();
+            let msg = BastionMessage::inspect(sender);
+            let env = Envelope::new(msg, self.bcast.path().clone(), self.bcast.sender().clone());
+            self.bcast.send_child(id, env);
+
+            let id = id.clone();
+            reports.push_back(receiver.map(move |report| (id, report.ok())));
+        }
+
+        executor::spawn_proc(
+            async move {
+                let mut root = None;
+                let mut supervisors = Vec::new();
+                while let Some((id, report)) = reports.next().await {
+                    if id == NIL_ID {
+                        root = report;
+                    } else {
+                        supervisors.push(InspectedElement {
+                            id,
+                            kind: SupervisedKind::Supervisor,
+                            name: None,
+                            redundancy: None,
+                            stop_reason: None,
+                            report,
+                        });
+                    }
+                }
+
+                // The system supervisor only stops along with the
+                // system, in which case the sender gets dropped.
+                if let Some(mut root) = root {
+                    root.launched += supervisors.len();
+                    root.children.extend(supervisors);
+                    trace!("System: Inspected: {:?}", root);
+                    // The sender might have stopped waiting for it.
+                    reply.send(root).ok();
+                }
+            },
+            ProcStack::default(),
+        );
+    }
+
+    // Answers with the state of the system, along with the ones of
+    // the system supervisor and of the top-level supervisors that
+    // answered before the deadline (see `Bastion::dump_state`).
+    fn dump_state(&self, reply: oneshot::Sender<DumpedElement>, deadline: Instant) {
+        let started = self.started;
+        let pre_start_msgs = self.pre_start_msgs.len();
+        let sub_deadline = dump::sub_deadline(deadline);
+        let mut dumps = FuturesOrdered::new();
+        let ids = std::iter::once(&NIL_ID).chain(self.order.iter());
+        for id in ids.filter(|id| self.launched.contains_key(id)) {
+            let (sender, receiver) = oneshot::channel();
+            let msg = BastionMessage::dump_state(sender, sub_deadline);
+            let env = Envelope::new(msg, self.bcast.path().clone(), self.bcast.sender().clone());
+            self.bcast.send_child(id, env);
+
+            let id = id.clone();
+            dumps.push_back(async move {
+                dump::answer_before(receiver, sub_deadline).await.unwrap_or(
+                    DumpedElement::Unresponsive {
+                        id,
+                        kind: SupervisedKind::Supervisor,
+                    },
+                )
+            });
+        }
+
+        executor::spawn_proc(
+            async move {
+                let dump = DumpedElement::System {
+                    started,
+                    pre_start_msgs,
+                    elems: dumps.collect().await,
+                };
+                trace!("System: Dumped state: {:?}", dump);
+                // The sender might have stopped waiting for it.
+                reply.send(dump).ok();
+            },
+            ProcStack::default(),
+        );
+    }
+
+    // Answers with the counters of the system supervisor's subtree
+    // and of the top-level supervisors' ones.
+    fn metrics(&self, reply: oneshot::Sender<MetricsSnapshot>) {
+        let mut snapshots = FuturesUnordered::new();
+        for id in self.launched.keys() {
+            let (sender, receiver) = oneshot::channel();
+            let msg = BastionMessage::metrics(sender);
+            let env = Envelope::new(msg, self.bcast.path().clone(), self.bcast.sender().clone());
+            self.bcast.send_child(id, env);
+
+            let id = id.clone();
+            snapshots.push(receiver.map(move |snapshot| (id, snapshot.ok())));
+        }
+
+        executor::spawn_proc(
+            async move {
+                let mut root = None;
+                let mut snapshot = MetricsSnapshot::default();
+                while let Some((id, sub_snapshot)) = snapshots.next().await {
+                    if id == NIL_ID {
+                        root = sub_snapshot;
+                    } else if let Some(sub_snapshot) = sub_snapshot {
+                        snapshot.merge(sub_snapshot);
+                    }
+                }
+
+                // The system supervisor only stops along with the
+                // system, in which case the sender gets dropped.
+                if let Some(mut root) = root {
+                    root.merge(snapshot);
+                    trace!("System: Metrics: {:?}", root);
+                    // The sender might have stopped waiting for it.
+                    reply.send(root).ok();
+                }
+            },
+            ProcStack::default(),
+        );
+    }
+
     fn restart_supervised_object(&mut self, id: BastionId) {
         // TODO: Err if None?
         if let Some(launched) = self.launched.remove(&id) {
@@ -291,7 +781,36 @@ impl System {
         }
     }
 
-    async fn handle(&mut self, env: Envelope) -> Result<(), ()> {
+    // Kills every supervisor and returns which one panicked, if
+    // it is known.
+    async fn fault(&mut self) -> Option<BastionId> {
+        // The supervisors being restarted are the ones which
+        // faulted, so the culprit is only known for sure if
+        // there is only one.
+        let culprit = if self.restart.len() == 1 {
+            self.restart.drain().next()
+        } else {
+            None
+        };
+        match &culprit {
+            Some(id) => error!("System: Supervisor({}) panicked.", id),
+            None => error!("System: Unknown supervisor panicked."),
+        }
+
+        self.kill().await;
+        culprit
+    }
+
+    async fn terminate(&mut self, exit: SystemExit) {
+        let global = self.bcast.system().clone();
+        let handle = global.handle();
+        let mut system = handle.lock().await;
+        *system = None;
+
+        global.notify_stopped(exit);
+    }
+
+    async fn handle(&mut self, env: Envelope) -> Result<(), SystemExit> {
         match env {
             Envelope {
                 msg: BastionMessage::Start,
@@ -304,9 +823,10 @@ impl System {
                 info!("System: Stopping.");
                 for supervisor in self.stop().await {
                     supervisor.callbacks().after_stop();
+                    self.bcast.system().shutdown().acknowledge(supervisor.id());
                 }
 
-                return Err(());
+                return Err(SystemExit::Stopped);
             }
             Envelope {
                 msg: BastionMessage::Kill,
@@ -315,8 +835,16 @@ impl System {
                 info!("System: Killing.");
                 self.kill().await;
 
-                return Err(());
+                return Err(SystemExit::Killed);
             }
+            Envelope {
+                msg: BastionMessage::Pause,
+                ..
+            } => unreachable!(),
+            Envelope {
+                msg: BastionMessage::Resume,
+                ..
+            } => unreachable!(),
             Envelope {
                 msg: BastionMessage::Deploy(deployment),
                 ..
@@ -345,6 +873,41 @@ impl System {
                 debug!("System: Broadcasting a message: {:?}", message);
                 self.bcast.send_children(env);
             }
+            Envelope {
+                msg: BastionMessage::MailboxLens { .. },
+                ..
+            } => unreachable!(),
+            Envelope {
+                msg: BastionMessage::HealthCheck { .. },
+                ..
+            } => unreachable!(),
+            Envelope {
+                msg: BastionMessage::Drain { .. },
+                ..
+            } => unreachable!(),
+            Envelope {
+                msg: BastionMessage::Ping { .. },
+                ..
+            } => unreachable!(),
+            Envelope {
+                msg: BastionMessage::Emit(_),
+                ..
+            } => unreachable!(),
+            Envelope {
+                msg: BastionMessage::Accumulator { .. },
+                ..
+            } => unreachable!(),
+            Envelope {
+                msg: BastionMessage::AddChild { .. },
+                ..
+            } => unreachable!(),
+            Envelope {
+                msg: BastionMessage::CountedMessage { ref msg, .. },
+                ..
+            } => {
+                debug!("System: Broadcasting a counted message: {:?}", msg);
+                self.bcast.send_children(env);
+            }
             Envelope {
                 msg: BastionMessage::RestartRequired { .. },
                 ..
@@ -365,6 +928,30 @@ impl System {
                 msg: BastionMessage::DropChild { .. },
                 ..
             } => unreachable!(),
+            Envelope {
+                msg: BastionMessage::RestartChild { .. },
+                ..
+            } => unreachable!(),
+            Envelope {
+                msg: BastionMessage::ListStopped { .. },
+                ..
+            } => unreachable!(),
+            Envelope {
+                msg: BastionMessage::Inspect { reply },
+                ..
+            } => self.inspect(reply),
+            Envelope {
+                msg: BastionMessage::DumpState { reply, deadline },
+                ..
+            } => self.dump_state(reply, deadline),
+            Envelope {
+                msg: BastionMessage::Metrics { reply },
+                ..
+            } => self.metrics(reply),
+            Envelope {
+                msg: BastionMessage::ResetChild { .. },
+                ..
+            } => unreachable!(),
             Envelope {
                 msg: BastionMessage::SetState { .. },
                 ..
@@ -373,10 +960,34 @@ impl System {
                 msg: BastionMessage::Stopped { id, .. },
                 ..
             } => self.restart_supervised_object(id),
+            Envelope {
+                msg: BastionMessage::IdleStop { .. },
+                ..
+            } => unreachable!(),
             Envelope {
                 msg: BastionMessage::Faulted { id, .. },
                 ..
             } => self.restart_supervised_object(id),
+            Envelope {
+                msg: BastionMessage::Adopt { .. },
+                ..
+            } => unreachable!(),
+            Envelope {
+                msg: BastionMessage::SetParent { .. },
+                ..
+            } => unreachable!(),
+            Envelope {
+                msg: BastionMessage::Migrate { .. },
+                ..
+            } => unreachable!(),
+            Envelope {
+                msg: BastionMessage::Relaunch { .. },
+                ..
+            } => unreachable!(),
+            Envelope {
+                msg: BastionMessage::CapacityExceeded { .. },
+                ..
+            } => unreachable!(),
         }
 
         Ok(())
@@ -385,8 +996,20 @@ impl System {
     async fn run(mut self) {
         info!("System: Launched.");
         loop {
-            match poll!(&mut self.waiting.next()) {
-                Poll::Ready(Some(Some(supervisor))) => {
+            // Parks until a supervisor it was waiting for stopped or
+            // the next message arrives, the former being handled
+            // first.
+            let wakeup = future::poll_fn(|cx| {
+                if let Poll::Ready(Some(stopped)) = self.waiting.poll_next_unpin(cx) {
+                    return Poll::Ready(Wakeup::Stopped(stopped));
+                }
+
+                self.bcast.poll_next_unpin(cx).map(Wakeup::Received)
+            })
+            .await;
+
+            match wakeup {
+                Wakeup::Stopped(Some(supervisor)) => {
                     let id = supervisor.id();
                     self.bcast.unregister(&id);
 
@@ -395,17 +1018,16 @@ impl System {
                     } else {
                         supervisor.callbacks().after_stop();
                     }
-
-                    continue;
                 }
-                // FIXME
-                Poll::Ready(Some(None)) => unimplemented!(),
-                Poll::Ready(None) | Poll::Pending => (),
-            }
+                Wakeup::Stopped(None) => {
+                    let culprit = self.fault().await;
+                    let panic = culprit.as_ref().and_then(panic_handler::take_panic);
+                    self.terminate(SystemExit::Faulted { culprit, panic }).await;
 
-            match poll!(&mut self.bcast.next()) {
+                    return;
+                }
                 // TODO: Err if started == true?
-                Poll::Ready(Some(Envelope {
+                Wakeup::Received(Some(Envelope {
                     msg: BastionMessage::Start,
                     ..
                 })) => {
@@ -415,6 +1037,7 @@ impl System {
                     );
                     info!("System: Starting.");
                     self.started = true;
+                    self.bcast.system().started_at.set(Instant::now()).ok();
 
                     let msg = BastionMessage::start();
                     let env =
@@ -428,29 +1051,27 @@ impl System {
                     for msg in msgs {
                         trace!("System: Replaying message: {:?}", msg);
                         // FIXME: Err(Error)?
-                        if self.handle(msg).await.is_err() {
-                            let handle = SYSTEM.handle();
-                            let mut system = handle.lock().await;
-                            *system = None;
-
-                            SYSTEM.notify_stopped();
+                        if let Err(exit) = self.handle(msg).await {
+                            self.terminate(exit).await;
 
                             return;
                         }
                     }
                 }
-                Poll::Ready(Some(msg)) if !self.started => {
+                // Answered even before being started (see
+                // `Supervisor::run`).
+                Wakeup::Received(Some(Envelope {
+                    msg: BastionMessage::DumpState { reply, deadline },
+                    ..
+                })) => self.dump_state(reply, deadline),
+                Wakeup::Received(Some(msg)) if !self.started => {
                     trace!("System: Received a new message (started=false): {:?}", msg);
                     self.pre_start_msgs.push(msg);
                 }
-                Poll::Ready(Some(msg)) => {
+                Wakeup::Received(Some(msg)) => {
                     trace!("System: Received a new message (started=true): {:?}", msg);
-                    if self.handle(msg).await.is_err() {
-                        let handle = SYSTEM.handle();
-                        let mut system = handle.lock().await;
-                        *system = None;
-
-                        SYSTEM.notify_stopped();
+                    if let Err(exit) = self.handle(msg).await {
+                        self.terminate(exit).await;
 
                         return;
                     }
@@ -458,8 +1079,7 @@ impl System {
                 // NOTE: because `Broadcast` always holds both a `Sender` and
                 //      `Receiver` of the same channel, this would only be
                 //      possible if the channel was closed, which never happens.
-                Poll::Ready(None) => unreachable!(),
-                Poll::Pending => pending!(),
+                Wakeup::Received(None) => unreachable!(),
             }
         }
     }
@@ -0,0 +1,80 @@
+//!
+//! Registry of the named topics children subscribed to, which
+//! allows publishing messages to them without knowing where
+//! they live in the supervision tree.
+use crate::child_ref::ChildRef;
+use crate::context::BastionId;
+use crate::envelope::Envelope;
+use fxhash::FxHashMap;
+use std::sync::Mutex;
+use tracing::{debug, trace};
+
+#[derive(Debug, Default)]
+pub(crate) struct TopicRegistry {
+    // Each topic's name associated with the elements that
+    // subscribed to it.
+    topics: Mutex<FxHashMap<String, FxHashMap<BastionId, ChildRef>>>,
+}
+
+impl TopicRegistry {
+    pub(crate) fn new() -> Self {
+        TopicRegistry::default()
+    }
+
+    pub(crate) fn subscribe(&self, topic: String, child_ref: &ChildRef) {
+        debug!("Child({}): Subscribing to topic: {}", child_ref.id(), topic);
+        // FIXME: panics
+        let mut topics = self.topics.lock().unwrap();
+        topics
+            .entry(topic)
+            .or_default()
+            .insert(child_ref.id().clone(), child_ref.clone());
+    }
+
+    pub(crate) fn unsubscribe(&self, topic: &str, id: &BastionId) {
+        debug!("Child({}): Unsubscribing from topic: {}", id, topic);
+        // FIXME: panics
+        let mut topics = self.topics.lock().unwrap();
+        if let Some(subscribers) = topics.get_mut(topic) {
+            subscribers.remove(id);
+            if subscribers.is_empty() {
+                topics.remove(topic);
+            }
+        }
+    }
+
+    /// Removes the element with the given identifier from all the
+    /// topics it subscribed to.
+    pub(crate) fn unsubscribe_all(&self, id: &BastionId) {
+        trace!("Child({}): Unsubscribing from all topics.", id);
+        // FIXME: panics
+        let mut topics = self.topics.lock().unwrap();
+        topics.retain(|_, subscribers| {
+            subscribers.remove(id);
+            !subscribers.is_empty()
+        });
+    }
+
+    /// Sends a copy of the envelope to each subscriber of the
+    /// topic, returning the envelope back if there is none.
+    pub(crate) fn publish(&self, topic: &str, env: Envelope) -> Result<(), Box<Envelope>> {
+        let subscribers = {
+            // FIXME: panics
+            let topics = self.topics.lock().unwrap();
+            match topics.get(topic) {
+                Some(subscribers) => subscribers.values().cloned().collect::<Vec<_>>(),
+                None => return Err(Box::new(env)),
+            }
+        };
+
+        for subscriber in subscribers {
+            // FIXME: panics?
+            let env = env.try_clone().unwrap();
+            // Delivery is best-effort: a subscriber that is being
+            // stopped will soon be removed from the registry.
+            subscriber.send(env).ok();
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,66 @@
+use bastion::prelude::*;
+use futures_timer::Delay;
+use std::time::Duration;
+
+#[test]
+fn ask_timeout() {
+    Bastion::init();
+
+    let children_ref = Bastion::children(|children| {
+        children.with_exec(|ctx: BastionContext| async move {
+            loop {
+                msg! { ctx.recv().await?,
+                    msg: &'static str =!> {
+                        if msg == "slow" {
+                            Delay::new(Duration::from_millis(200)).await;
+                            // The answer is dropped since it's too late.
+                            assert!(answer!(ctx, "pong").is_err());
+                        } else if msg == "ping" {
+                            answer!(ctx, "pong").unwrap();
+                        }
+                        // Otherwise, not answering...
+                    };
+                    _: _ => ();
+                }
+            }
+        })
+    })
+    .expect("Couldn't create the children group.");
+
+    Bastion::start();
+
+    let answer = run!(children_ref.ask_timeout("ping", Duration::from_secs(5)))
+        .expect("Couldn't receive the answer.");
+    msg! { answer,
+        msg: &'static str => assert_eq!(msg, "pong");
+        _: _ => panic!("Unexpected answer.");
+    }
+
+    let answer = run!(children_ref.ask_timeout("slow", Duration::from_millis(50)));
+    assert_eq!(answer.err(), Some(AskError::Timeout));
+
+    let answer = run!(children_ref.ask_timeout("silence", Duration::from_secs(5)));
+    assert_eq!(answer.err(), Some(AskError::RecipientDead));
+
+    // The element is still running after dropping the late answer.
+    let answer = run!(children_ref.ask_timeout("ping", Duration::from_secs(5)));
+    assert!(answer.is_ok());
+
+    children_ref.stop().unwrap();
+    let answer = run!(async {
+        // Waiting for the children group to stop...
+        while !children_ref.is_empty() {
+            Delay::new(Duration::from_millis(10)).await;
+        }
+        children_ref
+            .ask_timeout("ping", Duration::from_secs(5))
+            .await
+    });
+    assert!(matches!(
+        answer.err(),
+        Some(AskError::SendFailed) | Some(AskError::RecipientDead)
+    ));
+
+    Bastion::stop();
+    Bastion::block_until_stopped();
+}
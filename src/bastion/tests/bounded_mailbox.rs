@@ -0,0 +1,58 @@
+mod common;
+
+use bastion::prelude::*;
+use common::wait_until;
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+use std::sync::Arc;
+use std::thread;
+use std::time::Duration;
+
+#[test]
+fn bounded_mailbox() {
+    let config = Config::new().with_broadcast_config(BroadcastConfig::Bounded(2));
+    let runtime = BastionRuntime::new(config);
+
+    let blocking = Arc::new(AtomicBool::new(false));
+    let received = Arc::new(AtomicUsize::new(0));
+    let (blocking_inner, received_inner) = (blocking.clone(), received.clone());
+    let children_ref = runtime
+        .children(move |children| {
+            let (blocking, received) = (blocking_inner.clone(), received_inner.clone());
+            children
+                // The element blocks the thread it runs on.
+                .with_spawn_strategy(SpawnStrategy::DedicatedThread)
+                .with_exec(move |ctx: BastionContext| {
+                    let (blocking, received) = (blocking.clone(), received.clone());
+                    async move {
+                        loop {
+                            ctx.recv().await?;
+                            if !blocking.swap(true, Ordering::SeqCst) {
+                                thread::sleep(Duration::from_millis(300));
+                            }
+                            received.fetch_add(1, Ordering::SeqCst);
+                        }
+                    }
+                })
+        })
+        .expect("Couldn't create the children group.");
+    runtime.start();
+    wait_until(|| runtime.num_actors() == 1);
+
+    children_ref.broadcast("block").unwrap();
+    wait_until(|| blocking.load(Ordering::SeqCst));
+
+    // The element doesn't receive the messages while it blocks, so
+    // only the first ones fit in its mailbox...
+    let reached = (0..4)
+        .map(|_| run!(children_ref.broadcast_counted("message")))
+        .collect::<Vec<_>>();
+    assert_eq!(reached, vec![1, 1, 0, 0]);
+
+    // ...and are the only ones it handles once it stops blocking.
+    wait_until(|| received.load(Ordering::SeqCst) == 3);
+    thread::sleep(Duration::from_millis(100));
+    assert_eq!(received.load(Ordering::SeqCst), 3);
+
+    runtime.stop();
+    assert_eq!(runtime.block_until_stopped(), SystemExit::Stopped);
+}
@@ -0,0 +1,62 @@
+mod common;
+
+use bastion::prelude::*;
+use common::wait_until;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+use std::thread;
+use std::time::Duration;
+
+#[test]
+fn broadcast_counted() {
+    Bastion::init();
+
+    let received = Arc::new(AtomicUsize::new(0));
+    let received_inner = received.clone();
+    Bastion::children(move |children| {
+        let received = received_inner.clone();
+        children
+            .with_redundancy(2)
+            .with_exec(move |ctx: BastionContext| {
+                let received = received.clone();
+                async move {
+                    loop {
+                        msg! { ctx.recv().await?,
+                            ref _msg: &'static str => {
+                                received.fetch_add(1, Ordering::SeqCst);
+                            };
+                            _: _ => ();
+                        }
+                    }
+                }
+            })
+    })
+    .expect("Couldn't create the children group.");
+    Bastion::supervisor(|sp| {
+        sp.children(|children| {
+            children.with_exec(|ctx: BastionContext| async move {
+                loop {
+                    ctx.recv().await?;
+                }
+            })
+        })
+    })
+    .expect("Couldn't create the supervisor.");
+
+    // Messages broadcasted before starting are held until then...
+    let recipients = Bastion::broadcast_counted("reload").expect("Couldn't broadcast the message.");
+    thread::sleep(Duration::from_millis(50));
+    assert_eq!(received.load(Ordering::SeqCst), 0);
+
+    // ...and reach every element of the tree exactly once.
+    Bastion::start();
+    assert_eq!(run!(recipients), 3);
+    wait_until(|| received.load(Ordering::SeqCst) == 2);
+    assert_eq!(received.load(Ordering::SeqCst), 2);
+    // No element receives it a second time.
+    thread::sleep(Duration::from_millis(100));
+    assert_eq!(received.load(Ordering::SeqCst), 2);
+
+    Bastion::stop();
+    Bastion::block_until_stopped();
+}
@@ -0,0 +1,84 @@
+mod common;
+
+use bastion::prelude::*;
+use common::wait_until;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+
+// Returns a children group whose elements wait for messages,
+// counting how many of them started.
+fn looping_children(children: Children, redundancy: usize, starts: Arc<AtomicUsize>) -> Children {
+    children
+        .with_redundancy(redundancy)
+        .with_exec(move |ctx: BastionContext| {
+            let starts = starts.clone();
+            async move {
+                starts.fetch_add(1, Ordering::SeqCst);
+                loop {
+                    ctx.recv().await?;
+                }
+            }
+        })
+}
+
+#[test]
+fn broadcast_recipients() {
+    Bastion::init();
+    Bastion::start();
+
+    run_children_recipients();
+    run_supervisor_recipients();
+    run_stopped_recipients();
+
+    Bastion::stop();
+    Bastion::block_until_stopped();
+}
+
+fn run_children_recipients() {
+    let starts = Arc::new(AtomicUsize::new(0));
+
+    let starts_inner = starts.clone();
+    let children_ref =
+        Bastion::children(move |children| looping_children(children, 3, starts_inner.clone()))
+            .expect("Couldn't create the children group.");
+    wait_until(|| starts.load(Ordering::SeqCst) == 3);
+
+    let recipients = children_ref.broadcast_counted("ping");
+    assert_eq!(run!(recipients), 3);
+}
+
+fn run_supervisor_recipients() {
+    let starts = Arc::new(AtomicUsize::new(0));
+
+    let starts_inner = starts.clone();
+    let sp_ref = Bastion::supervisor(move |sp| {
+        let (first, second) = (starts_inner.clone(), starts_inner.clone());
+        sp.children(move |children| looping_children(children, 2, first))
+            .supervisor(move |sp| {
+                sp.children(move |children| looping_children(children, 3, second))
+            })
+    })
+    .expect("Couldn't create the supervisor.");
+    wait_until(|| starts.load(Ordering::SeqCst) == 5);
+
+    let recipients = sp_ref.broadcast_counted("ping").unwrap();
+    assert_eq!(run!(recipients), 5);
+}
+
+fn run_stopped_recipients() {
+    let starts = Arc::new(AtomicUsize::new(0));
+
+    let starts_inner = starts.clone();
+    let children_ref =
+        Bastion::children(move |children| looping_children(children, 2, starts_inner.clone()))
+            .expect("Couldn't create the children group.");
+    wait_until(|| starts.load(Ordering::SeqCst) == 2);
+
+    children_ref.stop().unwrap();
+    wait_until(|| children_ref.is_empty());
+
+    // The message can still be sent to the group, but doesn't
+    // reach any element.
+    let recipients = children_ref.broadcast_counted("ping");
+    assert_eq!(run!(recipients), 0);
+}
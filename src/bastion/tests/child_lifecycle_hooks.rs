@@ -0,0 +1,135 @@
+mod common;
+
+use bastion::prelude::*;
+use common::wait_until;
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex};
+
+#[test]
+fn child_lifecycle_hooks() {
+    Bastion::init();
+
+    let events = Arc::new(Mutex::new(vec![]));
+    let faulting_child = Arc::new(Mutex::new(None));
+
+    let (start, stop, fault) = (events.clone(), events.clone(), events.clone());
+    let callbacks = Callbacks::new()
+        .with_on_child_start(move |id| start.lock().unwrap().push(("start", id)))
+        .with_on_child_stop(move |id| stop.lock().unwrap().push(("stop", id)))
+        .with_on_child_fault(move |id| fault.lock().unwrap().push(("fault", id)));
+    let sp_ref = Bastion::supervisor(|sp| sp.with_callbacks(callbacks))
+        .expect("Couldn't create the supervisor.");
+
+    let stopping = sp_ref
+        .children(|children| {
+            children.with_exec(|ctx: BastionContext| async move {
+                loop {
+                    ctx.recv().await?;
+                }
+            })
+        })
+        .expect("Couldn't create the children group.");
+
+    let faulted = Arc::new(AtomicBool::new(false));
+    let faulting_child_inner = faulting_child.clone();
+    let faulting = sp_ref
+        .children(move |children| {
+            children.with_exec(move |ctx: BastionContext| {
+                let faulted = faulted.clone();
+                let faulting_child = faulting_child_inner.clone();
+                async move {
+                    // Faulting once, and then waiting to be stopped.
+                    if !faulted.swap(true, Ordering::SeqCst) {
+                        *faulting_child.lock().unwrap() = Some(ctx.current().id().clone());
+                        return Err(());
+                    }
+
+                    loop {
+                        ctx.recv().await?;
+                    }
+                }
+            })
+        })
+        .expect("Couldn't create the children group.");
+
+    Bastion::start();
+
+    wait_until(|| events.lock().unwrap().len() == 3);
+    stopping.stop().unwrap();
+    wait_until(|| events.lock().unwrap().len() == 4);
+
+    let faulting_child = faulting_child.lock().unwrap().clone().unwrap();
+    let events = events.lock().unwrap();
+    assert_eq!(
+        *events,
+        vec![
+            ("start", stopping.id().clone()),
+            ("start", faulting.id().clone()),
+            ("fault", faulting_child),
+            ("stop", stopping.id().clone()),
+        ]
+    );
+
+    Bastion::stop();
+    Bastion::block_until_stopped();
+}
+
+#[test]
+fn on_full_stop() {
+    let runtime = BastionRuntime::new(Config::new());
+
+    let finished = Arc::new(AtomicUsize::new(0));
+    let finished_inner = finished.clone();
+    let finishing = runtime
+        .children(move |children| {
+            let finished = finished_inner.clone();
+            children
+                .with_redundancy(3)
+                .with_on_full_stop(move || {
+                    finished.fetch_add(1, Ordering::SeqCst);
+                })
+                .with_exec(|ctx: BastionContext| async move {
+                    // Finishing once asked to.
+                    ctx.recv().await?;
+                    Ok(())
+                })
+        })
+        .expect("Couldn't create the children group.");
+
+    let stopped = Arc::new(AtomicUsize::new(0));
+    let stopped_inner = stopped.clone();
+    let stopping = runtime
+        .children(move |children| {
+            let stopped = stopped_inner.clone();
+            children
+                .with_redundancy(2)
+                .with_on_full_stop(move || {
+                    stopped.fetch_add(1, Ordering::SeqCst);
+                })
+                .with_exec(|ctx: BastionContext| async move {
+                    loop {
+                        ctx.recv().await?;
+                    }
+                })
+        })
+        .expect("Couldn't create the children group.");
+
+    runtime.start();
+
+    // The closure is only called once the last element finished...
+    finishing.elems()[0].tell_anonymously("stop").unwrap();
+    wait_until(|| finishing.len() == 2);
+    assert_eq!(finished.load(Ordering::SeqCst), 0);
+    finishing.broadcast("stop").unwrap();
+    wait_until(|| finished.load(Ordering::SeqCst) == 1);
+    assert_eq!(finished.load(Ordering::SeqCst), 1);
+
+    // ...or the group stopped.
+    stopping.stop().unwrap();
+    wait_until(|| stopped.load(Ordering::SeqCst) == 1);
+    assert_eq!(stopped.load(Ordering::SeqCst), 1);
+
+    runtime.stop();
+    assert_eq!(runtime.block_until_stopped(), SystemExit::Stopped);
+    assert_eq!(finished.load(Ordering::SeqCst), 1);
+}
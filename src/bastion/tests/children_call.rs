@@ -0,0 +1,71 @@
+use bastion::prelude::*;
+use futures_timer::Delay;
+use std::collections::HashSet;
+use std::time::Duration;
+
+#[test]
+fn children_call() {
+    Bastion::init();
+
+    let children_ref = Bastion::children(|children| {
+        children
+            .with_redundancy(2)
+            .with_exec(|ctx: BastionContext| async move {
+                loop {
+                    msg! { ctx.recv().await?,
+                        msg: &'static str =!> {
+                            if msg == "slow" {
+                                Delay::new(Duration::from_millis(200)).await;
+                            }
+                            answer!(ctx, ctx.current().id().clone()).ok();
+                        };
+                        _: _ => ();
+                    }
+                }
+            })
+    })
+    .expect("Couldn't create the children group.");
+
+    Bastion::start();
+
+    let call = |children_ref: &ChildrenRef, least_busy: bool| {
+        let reply = if least_busy {
+            run!(children_ref.call_least_busy("id", Duration::from_secs(5)))
+        } else {
+            run!(children_ref.call_any("id", Duration::from_secs(5)))
+        };
+        reply
+            .expect("Couldn't call the children group.")
+            .downcast::<BastionId>()
+            .unwrap()
+    };
+
+    // The elements are called in turns.
+    let ids: HashSet<_> = (0..2).map(|_| call(&children_ref, false)).collect();
+    assert_eq!(ids.len(), 2);
+    assert!(children_ref
+        .elems()
+        .iter()
+        .all(|elem| ids.contains(elem.id())));
+
+    assert!(ids.contains(&call(&children_ref, true)));
+
+    let reply = run!(children_ref.call("slow", Duration::from_millis(50)));
+    assert_eq!(reply.err(), Some(CallError::Timeout));
+
+    children_ref.stop().unwrap();
+    let reply = run!(async {
+        // Waiting for the children group to stop...
+        while !children_ref.is_empty() {
+            Delay::new(Duration::from_millis(10)).await;
+        }
+        children_ref.call("id", Duration::from_secs(5)).await
+    });
+    assert!(matches!(
+        reply.err(),
+        Some(CallError::NoAvailableChildren) | Some(CallError::ActorDied)
+    ));
+
+    Bastion::stop();
+    Bastion::block_until_stopped();
+}
@@ -0,0 +1,30 @@
+use bastion::prelude::*;
+use std::thread;
+use std::time::Duration;
+
+#[test]
+fn len_follows_running_elements() {
+    Bastion::init();
+
+    let children = Bastion::children(|children| {
+        children
+            .with_redundancy(3)
+            .with_exec(|_ctx: BastionContext| async move { Ok(()) })
+    })
+    .expect("Couldn't create the children group.");
+
+    assert_eq!(children.len(), 3);
+    assert!(!children.is_empty());
+
+    Bastion::start();
+
+    let mut tries = 0;
+    while !children.is_empty() && tries < 100 {
+        thread::sleep(Duration::from_millis(10));
+        tries += 1;
+    }
+    assert!(children.is_empty());
+
+    Bastion::stop();
+    Bastion::block_until_stopped();
+}
@@ -0,0 +1,95 @@
+mod common;
+
+use bastion::prelude::*;
+use common::wait_until;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+
+#[test]
+fn children_named() {
+    Bastion::init();
+    Bastion::start();
+
+    let starts = Arc::new(AtomicUsize::new(0));
+    let received = Arc::new(AtomicUsize::new(0));
+
+    let (starts_inner, received_inner) = (starts.clone(), received.clone());
+    let workers_ref = Bastion::children(move |children| {
+        let (starts, received) = (starts_inner.clone(), received_inner.clone());
+        children
+            .with_name("workers")
+            .with_exec(move |ctx: BastionContext| {
+                let (starts, received) = (starts.clone(), received.clone());
+                async move {
+                    starts.fetch_add(1, Ordering::SeqCst);
+                    loop {
+                        msg! { ctx.recv().await?,
+                            ref msg: &'static str => {
+                                if *msg == "crash" {
+                                    panic!("crash");
+                                }
+                                received.fetch_add(1, Ordering::SeqCst);
+                            };
+                            _msg: &'static str => {
+                                received.fetch_add(1, Ordering::SeqCst);
+                            };
+                            _: _ => ();
+                        }
+                    }
+                }
+            })
+    })
+    .expect("Couldn't create the children group.");
+    wait_until(|| starts.load(Ordering::SeqCst) == 1);
+
+    // Named groups can be found from anywhere...
+    let found = Bastion::children_named("workers").expect("Couldn't find the workers.");
+    assert_eq!(found.id(), workers_ref.id());
+    assert!(Bastion::children_named("nobody").is_none());
+
+    // ...including from inside other groups.
+    let starts_inner = starts.clone();
+    let forwarder_ref = Bastion::children(move |children| {
+        let starts = starts_inner.clone();
+        children.with_exec(move |ctx: BastionContext| {
+            let starts = starts.clone();
+            async move {
+                starts.fetch_add(1, Ordering::SeqCst);
+                loop {
+                    msg! { ctx.recv().await?,
+                        ref msg: &'static str => {
+                            if *msg == "forward" {
+                                ctx.lookup("workers").unwrap().broadcast("work").unwrap();
+                            }
+                        };
+                        _: _ => ();
+                    }
+                }
+            }
+        })
+    })
+    .expect("Couldn't create the children group.");
+    wait_until(|| starts.load(Ordering::SeqCst) == 2);
+    forwarder_ref.broadcast("forward").unwrap();
+    wait_until(|| received.load(Ordering::SeqCst) == 1);
+    assert_eq!(received.load(Ordering::SeqCst), 1);
+
+    // Lookups return the restarted elements.
+    found.broadcast("crash").unwrap();
+    wait_until(|| starts.load(Ordering::SeqCst) == 3);
+    let restarted = Bastion::children_named("workers").unwrap();
+    assert_eq!(restarted.id(), workers_ref.id());
+    restarted.elems()[0]
+        .tell_anonymously("work")
+        .expect("Couldn't send the message to the restarted element.");
+    wait_until(|| received.load(Ordering::SeqCst) == 2);
+    assert_eq!(received.load(Ordering::SeqCst), 2);
+
+    // Stopped groups can't be found anymore.
+    workers_ref.stop().unwrap();
+    wait_until(|| Bastion::children_named("workers").is_none());
+    assert!(Bastion::children_named("workers").is_none());
+
+    Bastion::stop();
+    Bastion::block_until_stopped();
+}
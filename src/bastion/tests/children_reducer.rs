@@ -0,0 +1,46 @@
+mod common;
+
+use bastion::prelude::*;
+use common::wait_until;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+
+#[test]
+fn children_reducer() {
+    Bastion::init();
+    Bastion::start();
+
+    let emitted = Arc::new(AtomicUsize::new(0));
+    let emitted_inner = emitted.clone();
+    let children_ref = Bastion::children(move |children| {
+        let emitted = emitted_inner.clone();
+        children
+            .with_redundancy(4)
+            .with_reducer(0usize, |sum: usize, count: usize| sum + count)
+            .with_exec(move |ctx: BastionContext| {
+                let emitted = emitted.clone();
+                async move {
+                    ctx.emit(10usize).unwrap();
+                    // Outputs of another type are dropped.
+                    ctx.emit("ignored").unwrap();
+                    emitted.fetch_add(1, Ordering::SeqCst);
+                    loop {
+                        ctx.recv().await?;
+                    }
+                }
+            })
+    })
+    .expect("Couldn't create the children group.");
+    wait_until(|| emitted.load(Ordering::SeqCst) == 4);
+
+    let sum: usize = run!(children_ref.accumulator()).expect("Couldn't get the state.");
+    assert_eq!(sum, 40);
+    assert!(run!(children_ref.accumulator::<u64>()).is_err());
+
+    // Groups without reducer have no state.
+    let other_ref = Bastion::children(|children| children).unwrap();
+    assert!(run!(other_ref.accumulator::<usize>()).is_err());
+
+    Bastion::stop();
+    Bastion::block_until_stopped();
+}
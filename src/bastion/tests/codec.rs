@@ -0,0 +1,127 @@
+#![cfg(feature = "codec")]
+mod common;
+
+use bastion::codec::{CodecError, EncodedEnvelope, JsonCodec, SerializableMessage};
+use bastion::prelude::*;
+use common::wait_until;
+use serde::{Deserialize, Serialize};
+use std::sync::{Arc, Mutex};
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+struct Order {
+    id: u64,
+    items: Vec<String>,
+}
+
+impl SerializableMessage for Order {
+    const TYPE_TAG: &'static str = "shop.Order";
+}
+
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+struct Refund {
+    order: u64,
+}
+
+impl SerializableMessage for Refund {
+    const TYPE_TAG: &'static str = "shop.Refund";
+}
+
+#[test]
+fn encoded_envelope() {
+    let order = Order {
+        id: 42,
+        items: vec!["book".to_string()],
+    };
+    let trace = TraceId::new();
+    let envelope = EncodedEnvelope::encode(&JsonCodec, &order)
+        .unwrap()
+        .with_correlation_id(trace);
+    assert_eq!(envelope.type_tag, "shop.Order");
+    assert_eq!(envelope.correlation_id, Some(trace));
+
+    // Envelopes round-trip through their own encoding...
+    let bytes = envelope.to_bytes(&JsonCodec).unwrap();
+    let decoded = EncodedEnvelope::from_bytes(&JsonCodec, &bytes).unwrap();
+    assert_eq!(decoded, envelope);
+    assert_eq!(decoded.decode::<Order, _>(&JsonCodec), Ok(order));
+
+    // ...and are only decoded as the type they were tagged with.
+    assert!(!decoded.is::<Refund>());
+    assert_eq!(
+        decoded.decode::<Refund, _>(&JsonCodec),
+        Err(CodecError::UnexpectedType {
+            expected: "shop.Refund",
+            found: "shop.Order".to_string(),
+        })
+    );
+    assert!(matches!(
+        EncodedEnvelope::from_bytes(&JsonCodec, b"garbage"),
+        Err(CodecError::Decode(_))
+    ));
+}
+
+#[test]
+fn recv_deserialized() {
+    let runtime = BastionRuntime::new(Config::new());
+    let received = Arc::new(Mutex::new(Vec::new()));
+    let received_inner = received.clone();
+    let children_ref = runtime
+        .children(move |children| {
+            let received = received_inner.clone();
+            children.with_exec(move |ctx: BastionContext| {
+                let received = received.clone();
+                async move {
+                    let order: Order = ctx.recv_deserialized(&JsonCodec).await?;
+                    received
+                        .lock()
+                        .unwrap()
+                        .push((format!("{:?}", order), ctx.current_trace()));
+
+                    // The messages that weren't decoded are left in
+                    // the mailbox, and delivered as they were sent.
+                    loop {
+                        msg! { ctx.recv().await?,
+                            ref msg: &'static str => {
+                                received.lock().unwrap().push((msg.to_string(), None));
+                            };
+                            ref msg: EncodedEnvelope => {
+                                received.lock().unwrap().push((msg.type_tag.clone(), None));
+                            };
+                            _: _ => ();
+                        }
+                    }
+                }
+            })
+        })
+        .expect("Couldn't create the children group.");
+    runtime.start();
+
+    children_ref.broadcast("in-process").unwrap();
+    let refund = EncodedEnvelope::encode(&JsonCodec, &Refund { order: 1 }).unwrap();
+    children_ref.broadcast_serialized(refund).unwrap();
+    let trace = TraceId::new();
+    let order = Order {
+        id: 42,
+        items: vec!["book".to_string()],
+    };
+    let bytes = EncodedEnvelope::encode(&JsonCodec, &order)
+        .unwrap()
+        .with_correlation_id(trace)
+        .to_bytes(&JsonCodec)
+        .unwrap();
+    let envelope = EncodedEnvelope::from_bytes(&JsonCodec, &bytes).unwrap();
+    children_ref.broadcast_serialized(envelope).unwrap();
+
+    wait_until(|| received.lock().unwrap().len() == 3);
+    assert_eq!(
+        *received.lock().unwrap(),
+        vec![
+            (format!("{:?}", order), Some(trace)),
+            ("in-process".to_string(), None),
+            ("shop.Refund".to_string(), None),
+        ]
+    );
+
+    runtime.stop();
+    runtime.block_until_stopped();
+}
@@ -0,0 +1,12 @@
+use std::thread;
+use std::time::Duration;
+
+// Waits until the condition is met, or for at most five seconds,
+// letting the assertions that follow report what went wrong.
+pub fn wait_until(condition: impl Fn() -> bool) {
+    let mut tries = 0;
+    while !condition() && tries < 500 {
+        thread::sleep(Duration::from_millis(10));
+        tries += 1;
+    }
+}
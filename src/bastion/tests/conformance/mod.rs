@@ -0,0 +1,108 @@
+// Checks that the executor bastion got started with upholds the
+// contract of `BastionExecutor`, supervision relying on it.
+use crate::common::wait_until;
+use bastion::prelude::*;
+use futures::channel::oneshot;
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+use std::sync::Arc;
+
+// Sets the flag once dropped, along with the future holding it.
+struct Dropped(Arc<AtomicBool>);
+
+impl Drop for Dropped {
+    fn drop(&mut self) {
+        self.0.store(true, Ordering::SeqCst);
+    }
+}
+
+// Must be called once bastion got started.
+pub fn check() {
+    outputs();
+    panics();
+    cancellation();
+    detached();
+    supervision();
+}
+
+// The futures get polled until they complete.
+fn outputs() {
+    assert_eq!(run!(spawn!(async { 42 })), Some(42));
+    assert_eq!(run!(blocking!(42)), Some(42));
+}
+
+// Panics get caught, and the executor keeps running the other
+// futures afterwards.
+fn panics() {
+    let handle = spawn!(async {
+        panic!("conformance");
+    });
+    assert_eq!(run!(handle), None::<()>);
+    assert_eq!(run!(spawn!(async { 42 })), Some(42));
+}
+
+// A cancelled process gets its future dropped, its handle
+// resolving to `None`.
+fn cancellation() {
+    let dropped = Arc::new(AtomicBool::new(false));
+    let guard = Dropped(dropped.clone());
+    let handle = spawn!(async move {
+        let _guard = guard;
+        futures::future::pending::<()>().await;
+    });
+
+    handle.cancel();
+    wait_until(|| dropped.load(Ordering::SeqCst));
+    assert!(dropped.load(Ordering::SeqCst));
+    assert_eq!(run!(handle), None);
+}
+
+// Dropping a handle detaches its process, which still completes.
+fn detached() {
+    let completed = Arc::new(AtomicBool::new(false));
+    let (sender, receiver) = oneshot::channel::<()>();
+    let completed_inner = completed.clone();
+    drop(spawn!(async move {
+        receiver.await.ok();
+        completed_inner.store(true, Ordering::SeqCst);
+    }));
+
+    sender.send(()).unwrap();
+    wait_until(|| completed.load(Ordering::SeqCst));
+    assert!(completed.load(Ordering::SeqCst));
+}
+
+// Supervisors get notified of the panics of their children, and
+// killing the elements cancels them.
+fn supervision() {
+    let started = Arc::new(AtomicUsize::new(0));
+    let dropped = Arc::new(AtomicBool::new(false));
+    let (started_inner, dropped_inner) = (started.clone(), dropped.clone());
+    let children_ref = Bastion::children(move |children| {
+        let (started, dropped) = (started_inner.clone(), dropped_inner.clone());
+        children.with_exec(move |ctx: BastionContext| {
+            let (started, dropped) = (started.clone(), dropped.clone());
+            async move {
+                // The first element panics once to get restarted...
+                if started.fetch_add(1, Ordering::SeqCst) == 0 {
+                    panic!("conformance");
+                }
+
+                // ...and the restarted one waits until killed.
+                let _guard = Dropped(dropped);
+                loop {
+                    ctx.recv().await?;
+                }
+            }
+        })
+    })
+    .expect("Couldn't create the children group.");
+
+    wait_until(|| started.load(Ordering::SeqCst) == 2);
+    assert_eq!(started.load(Ordering::SeqCst), 2);
+
+    children_ref
+        .kill()
+        .expect("Couldn't kill the children group.");
+    wait_until(|| dropped.load(Ordering::SeqCst));
+    assert!(dropped.load(Ordering::SeqCst));
+}
@@ -0,0 +1,86 @@
+mod common;
+mod conformance;
+
+use bastion::executor::BastionExecutor;
+use bastion::prelude::*;
+use common::wait_until;
+use futures::future::{BoxFuture, LocalBoxFuture};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+use std::thread;
+use std::time::Duration;
+
+// Runs each future on a new thread, counting them.
+#[derive(Debug, Clone, Default)]
+struct ThreadExecutor {
+    spawned: Arc<AtomicUsize>,
+    blocked_on: Arc<AtomicUsize>,
+}
+
+impl BastionExecutor for ThreadExecutor {
+    fn spawn(&self, future: BoxFuture<'static, ()>) {
+        self.spawned.fetch_add(1, Ordering::SeqCst);
+        thread::spawn(move || futures::executor::block_on(future));
+    }
+
+    fn spawn_blocking(&self, future: BoxFuture<'static, ()>) {
+        self.spawn(future)
+    }
+
+    fn block_on(&self, future: LocalBoxFuture<'_, ()>) {
+        self.blocked_on.fetch_add(1, Ordering::SeqCst);
+        futures::executor::block_on(future)
+    }
+}
+
+#[test]
+fn custom_executor() {
+    let executor = ThreadExecutor::default();
+    Bastion::init_with(Config::new().with_executor(executor.clone()));
+    Bastion::start();
+
+    let started = Arc::new(AtomicUsize::new(0));
+    let started_inner = started.clone();
+    let children_ref = Bastion::children(move |children| {
+        let started = started_inner.clone();
+        children.with_exec(move |ctx: BastionContext| {
+            let started = started.clone();
+            async move {
+                // The first element panics once to get restarted...
+                if started.fetch_add(1, Ordering::SeqCst) == 0 {
+                    panic!("first start");
+                }
+
+                msg! { ctx.recv().await?,
+                    msg: &'static str =!> {
+                        answer!(ctx, msg).unwrap();
+                    };
+                    _: _ => ();
+                }
+
+                Ok(())
+            }
+        })
+    })
+    .expect("Couldn't create the children group.");
+
+    // ...and still answers once restarted.
+    wait_until(|| started.load(Ordering::SeqCst) == 2);
+    let answer = run!(children_ref.ask_timeout("ping", Duration::from_secs(5)))
+        .expect("Couldn't receive the answer.");
+    msg! { answer,
+        msg: &'static str => assert_eq!(msg, "ping");
+        _: _ => panic!("Unexpected answer.");
+    }
+    assert_eq!(started.load(Ordering::SeqCst), 2);
+
+    // Everything ran on the custom executor.
+    assert!(executor.spawned.load(Ordering::SeqCst) > 0);
+    assert!(executor.blocked_on.load(Ordering::SeqCst) > 0);
+    assert_eq!(run!(spawn!(async { 42 })), Some(42));
+
+    conformance::check();
+
+    Bastion::stop();
+    assert_eq!(Bastion::block_until_stopped(), SystemExit::Stopped);
+}
@@ -0,0 +1,15 @@
+mod common;
+mod conformance;
+
+use bastion::prelude::*;
+
+#[test]
+fn default_executor() {
+    Bastion::init();
+    Bastion::start();
+
+    conformance::check();
+
+    Bastion::stop();
+    assert_eq!(Bastion::block_until_stopped(), SystemExit::Stopped);
+}
@@ -0,0 +1,93 @@
+mod common;
+mod conformance;
+
+use bastion::executor::{BastionExecutor, DeterministicExecutor};
+use bastion::prelude::*;
+use common::wait_until;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{mpsc, Arc, Mutex};
+use std::thread;
+
+// Returns the order in which twenty futures spawned at the same time
+// got polled.
+fn order(seed: u64) -> Vec<usize> {
+    let executor = Arc::new(DeterministicExecutor::new(seed));
+    let (sender, receiver) = mpsc::channel();
+
+    let inner = executor.clone();
+    executor.spawn(Box::pin(async move {
+        for i in 0..20 {
+            let sender = sender.clone();
+            inner.spawn(Box::pin(async move {
+                sender.send(i).unwrap();
+            }));
+        }
+    }));
+
+    receiver.iter().take(20).collect()
+}
+
+#[test]
+fn deterministic_order() {
+    let first = order(7);
+    assert_eq!(first, order(7));
+    assert_ne!(first, order(8));
+
+    let mut sorted = first;
+    sorted.sort_unstable();
+    assert_eq!(sorted, (0..20).collect::<Vec<_>>());
+}
+
+#[test]
+fn deterministic_executor() {
+    Bastion::init_with(Config::new().with_deterministic_executor(42));
+    Bastion::start();
+
+    let started = Arc::new(AtomicUsize::new(0));
+    let threads = Arc::new(Mutex::new(Vec::new()));
+    let (started_inner, threads_inner) = (started.clone(), threads.clone());
+    let children_ref = Bastion::children(move |children| {
+        let (started, threads) = (started_inner.clone(), threads_inner.clone());
+        children
+            .with_redundancy(3)
+            .with_exec(move |ctx: BastionContext| {
+                let (started, threads) = (started.clone(), threads.clone());
+                async move {
+                    let name = thread::current().name().map(ToString::to_string);
+                    threads.lock().unwrap().push(name);
+                    started.fetch_add(1, Ordering::SeqCst);
+
+                    loop {
+                        msg! { ctx.recv().await?,
+                            msg: &'static str =!> {
+                                answer!(ctx, msg).unwrap();
+                            };
+                            _: _ => ();
+                        }
+                    }
+                }
+            })
+    })
+    .expect("Couldn't create the children group.");
+
+    // All the elements answer, running on the same thread.
+    wait_until(|| started.load(Ordering::SeqCst) == 3);
+    for child in children_ref.elems() {
+        let answer = child
+            .ask_anonymously("ping")
+            .expect("Couldn't send the message.");
+        let answer = run!(answer).expect("Couldn't receive the answer.");
+        msg! { answer,
+            msg: &'static str => assert_eq!(msg, "ping");
+            _: _ => panic!("Unexpected answer.");
+        }
+    }
+    for name in threads.lock().unwrap().iter() {
+        assert_eq!(name.as_deref(), Some("bastion-deterministic-42"));
+    }
+
+    conformance::check();
+
+    Bastion::stop();
+    assert_eq!(Bastion::block_until_stopped(), SystemExit::Stopped);
+}
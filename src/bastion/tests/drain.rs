@@ -0,0 +1,80 @@
+mod common;
+
+use bastion::prelude::*;
+use common::wait_until;
+use std::sync::{Arc, Mutex};
+
+#[test]
+fn drain() {
+    let runtime = BastionRuntime::new(Config::new());
+    runtime.start();
+
+    let received = Arc::new(Mutex::new(Vec::new()));
+    let received_inner = received.clone();
+    let children_ref = runtime
+        .children(move |children| {
+            children
+                .with_redundancy(2)
+                .with_exec(move |ctx: BastionContext| {
+                    let received = received_inner.clone();
+                    async move {
+                        loop {
+                            msg! { ctx.recv().await?,
+                                msg: &'static str => {
+                                    received.lock().unwrap().push(msg);
+                                };
+                                ref msg: &'static str => {
+                                    received.lock().unwrap().push(*msg);
+                                };
+                                _: _ => ();
+                            }
+                        }
+                    }
+                })
+        })
+        .expect("Couldn't create the children group.");
+
+    // Running groups can't be drained.
+    assert_eq!(
+        run!(children_ref.drain()).err(),
+        Some(DrainError::NotPaused)
+    );
+
+    children_ref.pause().unwrap();
+    children_ref.broadcast("broadcasted").unwrap();
+    // Only the messages delivered to the elements are drained.
+    let delivered = || {
+        let snapshot = run!(runtime.metrics()).expect("The system stopped.");
+        snapshot
+            .children(children_ref.id())
+            .map(|metrics| metrics.received)
+    };
+    // The elements are paused once they got the broadcasted message,
+    // which their group sent them after telling them to pause.
+    wait_until(|| delivered() == Some(2));
+    children_ref.elems()[0].tell_anonymously("told").unwrap();
+    wait_until(|| delivered() == Some(3));
+    assert_eq!(delivered(), Some(3));
+
+    // Broadcasted messages are only returned once...
+    let msgs = run!(children_ref.drain()).expect("Couldn't drain the children group.");
+    assert_eq!(msgs.len(), 2);
+    assert!(msgs.iter().any(|msg| msg.peek() == Some(&"told")));
+    assert!(msgs.iter().any(|msg| msg.peek() == Some(&"broadcasted")));
+    let lens = run!(children_ref.mailbox_lens()).unwrap();
+    assert_eq!(lens.iter().sum::<usize>(), 0);
+
+    // ...and the group stays paused until resumed.
+    assert!(run!(children_ref.drain()).unwrap().is_empty());
+    for msg in msgs {
+        children_ref.inject_message(msg).unwrap();
+    }
+    children_ref.resume().unwrap();
+    wait_until(|| received.lock().unwrap().len() == 3);
+    let mut received = received.lock().unwrap().clone();
+    received.sort_unstable();
+    assert_eq!(received, vec!["broadcasted", "broadcasted", "told"]);
+
+    runtime.stop();
+    runtime.block_until_stopped();
+}
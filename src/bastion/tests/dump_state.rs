@@ -0,0 +1,80 @@
+mod common;
+
+use bastion::prelude::*;
+use common::wait_until;
+use std::thread;
+use std::time::Duration;
+
+async fn idle(ctx: BastionContext) -> Result<(), ()> {
+    loop {
+        ctx.recv().await?;
+    }
+}
+
+#[test]
+fn dump_state() {
+    let runtime = BastionRuntime::new(Config::new().with_threads(4));
+    let sp_ref = runtime
+        .supervisor(|sp| sp.with_name("named"))
+        .expect("Couldn't create the supervisor.");
+    let children_ref = sp_ref
+        .children(|children| {
+            children
+                .with_name("workers")
+                .with_redundancy(2)
+                .with_exec(idle)
+        })
+        .expect("Couldn't create the children group.");
+
+    // The system answers before being started, the deployments of
+    // its supervisors being queued...
+    let dump = run!(runtime.dump_state()).expect("Couldn't dump the state.");
+    assert!(dump.starts_with("System: not started, "));
+    assert_eq!(dump.lines().count(), 1);
+
+    // ...and reports its supervisors and their running children
+    // groups once started.
+    runtime.start();
+    wait_until(|| {
+        matches!(run!(runtime.dump_state()),
+            Ok(dump) if dump.contains("\"workers\": 2 elements, mailbox lengths: [0, 0]"))
+    });
+    let dump = run!(runtime.dump_state()).unwrap();
+    assert!(dump.starts_with("System: started, 0 pre-start messages\n"));
+    assert!(dump.contains(&format!(
+        "  Supervisor({}) \"named\": OneForOne, started, 0 pre-start messages\n",
+        sp_ref.id()
+    )));
+    assert!(dump.contains(&format!("    launched: [{}]\n", children_ref.id())));
+    assert!(dump.contains("\"workers\": 2 elements, mailbox lengths: [0, 0]"));
+
+    runtime.stop();
+    runtime.block_until_stopped();
+    assert!(run!(runtime.dump_state()).is_err());
+}
+
+#[test]
+fn unresponsive_supervisors() {
+    let runtime = BastionRuntime::new(Config::new().with_threads(4));
+    let sp_ref = runtime
+        .supervisor(|sp| sp)
+        .expect("Couldn't create the supervisor.");
+    runtime.start();
+
+    // A supervisor blocked while deploying a children group is
+    // reported as such, along with the responsive ones.
+    sp_ref
+        .children(|children| {
+            children.with_callbacks(Callbacks::new().with_before_start(|| {
+                thread::sleep(Duration::from_millis(500));
+            }))
+        })
+        .expect("Couldn't create the children group.");
+    let dump = run!(runtime.dump_state_with_timeout(Duration::from_millis(200)))
+        .expect("Couldn't dump the state.");
+    assert!(dump.contains(&format!("Supervisor({}): unresponsive", sp_ref.id())));
+    assert!(dump.contains(&format!("Supervisor({}): OneForOne", NIL_ID)));
+
+    runtime.stop();
+    runtime.block_until_stopped();
+}
@@ -0,0 +1,69 @@
+mod common;
+
+use bastion::prelude::*;
+use common::wait_until;
+use std::sync::{Arc, Mutex};
+
+#[test]
+fn exec_fallback() {
+    let runtime = BastionRuntime::new(Config::new());
+    let runs = Arc::new(Mutex::new(Vec::new()));
+    let runs_inner = runs.clone();
+    let children_ref = runtime
+        .children(move |children| {
+            let (exec_runs, fallback_runs) = (runs_inner.clone(), runs_inner.clone());
+            children
+                .with_exec(move |ctx: BastionContext| {
+                    let runs = exec_runs.clone();
+                    async move {
+                        runs.lock().unwrap().push("exec");
+                        loop {
+                            msg! { ctx.recv().await?,
+                                ref _msg: &'static str => return Err(());
+                                _: _ => ();
+                            }
+                        }
+                    }
+                })
+                .with_exec_fallback(move |ctx: BastionContext| {
+                    let runs = fallback_runs.clone();
+                    async move {
+                        runs.lock().unwrap().push("fallback");
+                        msg! { ctx.recv().await?,
+                            ref msg: &'static str => {
+                                if *msg == "fault" {
+                                    return Err(());
+                                }
+                            };
+                            _: _ => ();
+                        }
+
+                        Ok(())
+                    }
+                })
+        })
+        .expect("Couldn't create the children group.");
+    runtime.start();
+    wait_until(|| runs.lock().unwrap().len() == 1);
+
+    // The fallback only runs after a fault, until it returns...
+    children_ref.broadcast("fault").unwrap();
+    wait_until(|| runs.lock().unwrap().len() == 2);
+    children_ref.broadcast("recovered").unwrap();
+    wait_until(|| runs.lock().unwrap().len() == 3);
+
+    // ...and is run again if it faults itself.
+    children_ref.broadcast("fault").unwrap();
+    wait_until(|| runs.lock().unwrap().len() == 4);
+    children_ref.broadcast("fault").unwrap();
+    wait_until(|| runs.lock().unwrap().len() == 5);
+    children_ref.broadcast("recovered").unwrap();
+    wait_until(|| runs.lock().unwrap().len() == 6);
+    assert_eq!(
+        *runs.lock().unwrap(),
+        vec!["exec", "fallback", "exec", "fallback", "fallback", "exec"]
+    );
+
+    runtime.stop();
+    runtime.block_until_stopped();
+}
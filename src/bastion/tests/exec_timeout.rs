@@ -0,0 +1,99 @@
+mod common;
+
+use bastion::prelude::*;
+use common::wait_until;
+use futures_timer::Delay;
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+use std::sync::Arc;
+use std::thread;
+use std::time::{Duration, Instant};
+
+// Sets the flag once dropped, along with the future holding it.
+struct Dropped(Arc<AtomicBool>);
+
+impl Drop for Dropped {
+    fn drop(&mut self) {
+        self.0.store(true, Ordering::SeqCst);
+    }
+}
+
+#[test]
+fn spawn_with_timeout_cancelled_on_stop() {
+    let runtime = BastionRuntime::new(Config::new());
+    runtime.start();
+
+    let dropped = Arc::new(AtomicBool::new(false));
+    let guard = Dropped(dropped.clone());
+    let spawned = runtime.spawn_with_timeout(
+        async move {
+            let _guard = guard;
+            futures::future::pending::<()>().await;
+        },
+        Duration::from_secs(60),
+    );
+
+    // Stopping the system cancels the future long before it
+    // would have timed out.
+    let stopping = Instant::now();
+    runtime.stop();
+    assert_eq!(run!(spawned), None);
+    assert!(stopping.elapsed() < Duration::from_secs(5));
+    wait_until(|| dropped.load(Ordering::SeqCst));
+    assert!(dropped.load(Ordering::SeqCst));
+    runtime.block_until_stopped();
+}
+
+#[test]
+fn exec_timeout() {
+    let runtime = BastionRuntime::new(Config::new());
+
+    let started = Arc::new(AtomicUsize::new(0));
+    let handled = Arc::new(AtomicUsize::new(0));
+    let (started_inner, handled_inner) = (started.clone(), handled.clone());
+    let children_ref = runtime
+        .children(move |children| {
+            let (started, handled) = (started_inner.clone(), handled_inner.clone());
+            children
+                .with_exec_timeout(Duration::from_millis(100))
+                .with_exec(move |ctx: BastionContext| {
+                    let (started, handled) = (started.clone(), handled.clone());
+                    async move {
+                        started.fetch_add(1, Ordering::SeqCst);
+                        loop {
+                            msg! { ctx.recv().await?,
+                                ref msg: &'static str => {
+                                    if *msg == "slow" {
+                                        Delay::new(Duration::from_secs(10)).await;
+                                    }
+                                    handled.fetch_add(1, Ordering::SeqCst);
+                                };
+                                _: _ => ();
+                            }
+                        }
+                    }
+                })
+        })
+        .expect("Couldn't create the children group.");
+    runtime.start();
+    wait_until(|| started.load(Ordering::SeqCst) == 1);
+
+    // Waiting for messages doesn't time out...
+    thread::sleep(Duration::from_millis(300));
+    assert_eq!(started.load(Ordering::SeqCst), 1);
+
+    // ...but taking too long to handle one makes the element
+    // fault and get restarted...
+    children_ref.broadcast("slow").unwrap();
+    wait_until(|| started.load(Ordering::SeqCst) == 2);
+    assert_eq!(started.load(Ordering::SeqCst), 2);
+    assert_eq!(handled.load(Ordering::SeqCst), 0);
+
+    // ...which then handles the other messages.
+    children_ref.broadcast("fast").unwrap();
+    wait_until(|| handled.load(Ordering::SeqCst) == 1);
+    assert_eq!(handled.load(Ordering::SeqCst), 1);
+    assert_eq!(started.load(Ordering::SeqCst), 2);
+
+    runtime.stop();
+    runtime.block_until_stopped();
+}
@@ -0,0 +1,155 @@
+mod common;
+
+use bastion::prelude::*;
+use common::wait_until;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+use std::thread;
+use std::time::Duration;
+
+#[derive(Debug, Clone)]
+struct Add(usize);
+
+#[derive(Debug, Clone)]
+struct Sub(usize);
+
+// Adds the additions it handles to `total`.
+fn adding(children: Children, total: Arc<AtomicUsize>) -> Children {
+    children.with_exec_typed(move |_ctx: BastionContext, add: Add| {
+        let total = total.clone();
+        async move {
+            total.fetch_add(add.0, Ordering::SeqCst);
+            Ok(())
+        }
+    })
+}
+
+#[test]
+fn typed_exec_only_receives_its_message_type() {
+    Bastion::init();
+
+    let unhandled = Arc::new(AtomicUsize::new(0));
+    let unhandled_inner = unhandled.clone();
+    Bastion::set_unhandled_message_hook(move |msg, _| {
+        assert!(msg.is::<&'static str>());
+        unhandled_inner.fetch_add(1, Ordering::SeqCst);
+    });
+
+    Bastion::start();
+
+    let total = Arc::new(AtomicUsize::new(0));
+    let total_inner = total.clone();
+    let children = Bastion::children(|children| {
+        children.with_exec_typed(move |_ctx: BastionContext, add: Add| {
+            let total = total_inner.clone();
+            async move {
+                if total.fetch_add(add.0, Ordering::SeqCst) + add.0 == 6 {
+                    Bastion::stop();
+                }
+
+                Ok(())
+            }
+        })
+    })
+    .expect("Couldn't create the children group.");
+
+    let child = &children.elems()[0];
+    child.tell_anonymously(Add(1)).unwrap();
+    child.tell_anonymously("not an addition").unwrap();
+    child.tell_anonymously(Add(2)).unwrap();
+    child.tell_anonymously(Add(3)).unwrap();
+
+    Bastion::block_until_stopped();
+
+    assert_eq!(total.load(Ordering::SeqCst), 6);
+    assert_eq!(unhandled.load(Ordering::SeqCst), 1);
+}
+
+#[test]
+fn typed_exec_receives_broadcasts_and_asks() {
+    let runtime = BastionRuntime::new(Config::new());
+
+    let total = Arc::new(AtomicUsize::new(0));
+    let total_inner = total.clone();
+    let children_ref = runtime
+        .children(move |children| {
+            let total = total_inner.clone();
+            children
+                .with_redundancy(2)
+                .with_exec_typed(move |ctx: BastionContext, add: Add| {
+                    let total = total.clone();
+                    async move {
+                        let sum = total.fetch_add(add.0, Ordering::SeqCst) + add.0;
+                        // Only the asked additions can be answered.
+                        ctx.answer(sum).ok();
+                        Ok(())
+                    }
+                })
+        })
+        .expect("Couldn't create the children group.");
+    runtime.start();
+
+    // Broadcasted messages are cloned for each element...
+    children_ref.broadcast(Add(1)).unwrap();
+    wait_until(|| total.load(Ordering::SeqCst) == 2);
+    assert_eq!(total.load(Ordering::SeqCst), 2);
+
+    // ...while asked ones get answered.
+    let answer = children_ref.elems()[0].ask_anonymously(Add(3)).unwrap();
+    let sum = msg! { run!(answer).unwrap(),
+        sum: usize => sum;
+        _: _ => panic!("Unexpected answer.");
+    };
+    assert_eq!(sum, 5);
+
+    runtime.stop();
+    runtime.block_until_stopped();
+}
+
+#[test]
+fn broadcast_typed() {
+    let runtime = BastionRuntime::new(Config::new());
+
+    let total = Arc::new(AtomicUsize::new(0));
+    let received = Arc::new(AtomicUsize::new(0));
+    let (total_inner, received_inner) = (total.clone(), received.clone());
+    let sp_ref = runtime
+        .supervisor(move |sp| {
+            let total = total_inner.clone();
+            sp.children(|children| adding(children.with_redundancy(2), total.clone()))
+                // An untyped children group...
+                .children(move |children| {
+                    children.with_exec(move |ctx: BastionContext| {
+                        let received = received_inner.clone();
+                        async move {
+                            loop {
+                                ctx.recv().await?;
+                                received.fetch_add(1, Ordering::SeqCst);
+                            }
+                        }
+                    })
+                })
+                .supervisor(move |sp| {
+                    sp.children(|children| adding(children, total.clone()))
+                        // ...and one handling another type.
+                        .children(|children| {
+                            children.with_exec_typed(|_ctx: BastionContext, sub: Sub| async move {
+                                panic!("Unexpected subtraction of {}.", sub.0);
+                            })
+                        })
+                })
+        })
+        .expect("Couldn't create the supervisor.");
+    runtime.start();
+    wait_until(|| runtime.num_actors() == 5);
+
+    // Only the children groups handling additions receive it.
+    assert_eq!(sp_ref.broadcast_typed(Add(5)), Ok(2));
+    wait_until(|| total.load(Ordering::SeqCst) == 15);
+    thread::sleep(Duration::from_millis(100));
+    assert_eq!(total.load(Ordering::SeqCst), 15);
+    assert_eq!(received.load(Ordering::SeqCst), 0);
+
+    runtime.stop();
+    runtime.block_until_stopped();
+}
@@ -0,0 +1,173 @@
+mod common;
+
+use bastion::prelude::*;
+use common::wait_until;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+use std::thread;
+use std::time::Duration;
+
+// Returns a children group whose element returns the given policy
+// the first time it runs, and then waits for messages.
+fn counting_children(
+    children: Children,
+    starts: Arc<AtomicUsize>,
+    policy: FaultPolicy,
+) -> Children {
+    children.with_exec_policy(move |ctx: BastionContext| {
+        let starts = starts.clone();
+        async move {
+            if starts.fetch_add(1, Ordering::SeqCst) == 0 {
+                return policy;
+            }
+
+            loop {
+                if ctx.recv().await.is_err() {
+                    return FaultPolicy::StopChild;
+                }
+            }
+        }
+    })
+}
+
+// Returns a children group whose element waits for messages,
+// counting how many times it started.
+fn looping_children(children: Children, starts: Arc<AtomicUsize>) -> Children {
+    children.with_exec(move |ctx: BastionContext| {
+        let starts = starts.clone();
+        async move {
+            starts.fetch_add(1, Ordering::SeqCst);
+            loop {
+                ctx.recv().await?;
+            }
+        }
+    })
+}
+
+#[test]
+fn fault_policies() {
+    Bastion::init();
+    Bastion::start();
+
+    run_restart_child();
+    run_escalate_to_supervisor();
+    run_stop_child();
+    run_ignore();
+    run_ignore_restart_limit();
+
+    Bastion::stop();
+    Bastion::block_until_stopped();
+}
+
+fn run_restart_child() {
+    let faulted = Arc::new(AtomicUsize::new(0));
+    let sibling = Arc::new(AtomicUsize::new(0));
+
+    let (faulted_inner, sibling_inner) = (faulted.clone(), sibling.clone());
+    Bastion::supervisor(move |sp| {
+        let (faulted, sibling) = (faulted_inner.clone(), sibling_inner.clone());
+        sp.with_strategy(SupervisionStrategy::OneForAll)
+            .children(move |children| looping_children(children, sibling))
+            .children(move |children| {
+                counting_children(children, faulted, FaultPolicy::RestartChild)
+            })
+    })
+    .expect("Couldn't create the supervisor.");
+
+    wait_until(|| faulted.load(Ordering::SeqCst) == 2);
+    thread::sleep(Duration::from_millis(100));
+
+    // Only the element asking to be restarted was restarted,
+    // even though the supervisor uses the "one for all" strategy.
+    assert_eq!(faulted.load(Ordering::SeqCst), 2);
+    assert_eq!(sibling.load(Ordering::SeqCst), 1);
+}
+
+fn run_escalate_to_supervisor() {
+    let faulted = Arc::new(AtomicUsize::new(0));
+    let sibling = Arc::new(AtomicUsize::new(0));
+
+    let (faulted_inner, sibling_inner) = (faulted.clone(), sibling.clone());
+    Bastion::supervisor(move |sp| {
+        let (faulted, sibling) = (faulted_inner.clone(), sibling_inner.clone());
+        sp.with_strategy(SupervisionStrategy::OneForAll)
+            .children(move |children| looping_children(children, sibling))
+            .children(move |children| {
+                counting_children(children, faulted, FaultPolicy::EscalateToSupervisor)
+            })
+    })
+    .expect("Couldn't create the supervisor.");
+
+    // The supervisor restarted every element.
+    wait_until(|| faulted.load(Ordering::SeqCst) == 2 && sibling.load(Ordering::SeqCst) == 2);
+    assert_eq!(faulted.load(Ordering::SeqCst), 2);
+    assert_eq!(sibling.load(Ordering::SeqCst), 2);
+}
+
+fn run_stop_child() {
+    let starts = Arc::new(AtomicUsize::new(0));
+
+    let starts_inner = starts.clone();
+    let children_ref = Bastion::children(move |children| {
+        counting_children(children, starts_inner.clone(), FaultPolicy::StopChild)
+    })
+    .expect("Couldn't create the children group.");
+
+    wait_until(|| children_ref.is_empty());
+    thread::sleep(Duration::from_millis(100));
+    assert!(children_ref.is_empty());
+    assert_eq!(starts.load(Ordering::SeqCst), 1);
+}
+
+fn run_ignore() {
+    let starts = Arc::new(AtomicUsize::new(0));
+    let restarts = Arc::new(AtomicUsize::new(0));
+
+    let (starts_inner, restarts_inner) = (starts.clone(), restarts.clone());
+    let children_ref = Bastion::children(move |children| {
+        let restarts = restarts_inner.clone();
+        let callbacks = Callbacks::new().with_after_restart(move || {
+            restarts.fetch_add(1, Ordering::SeqCst);
+        });
+
+        counting_children(children, starts_inner.clone(), FaultPolicy::Ignore)
+            .with_callbacks(callbacks)
+    })
+    .expect("Couldn't create the children group.");
+
+    wait_until(|| starts.load(Ordering::SeqCst) == 2);
+    assert_eq!(starts.load(Ordering::SeqCst), 2);
+    assert_eq!(restarts.load(Ordering::SeqCst), 1);
+    assert_eq!(children_ref.len(), 1);
+}
+
+fn run_ignore_restart_limit() {
+    let starts = Arc::new(AtomicUsize::new(0));
+
+    let starts_inner = starts.clone();
+    let sp_ref = Bastion::supervisor(move |sp| {
+        let starts = starts_inner.clone();
+        let restart_strategy =
+            RestartStrategy::default().with_restart_policy(RestartPolicy::Tries(2));
+
+        sp.with_restart_strategy(restart_strategy)
+            .children(move |children| {
+                children.with_exec_policy(move |_ctx: BastionContext| {
+                    let starts = starts.clone();
+                    async move {
+                        starts.fetch_add(1, Ordering::SeqCst);
+                        FaultPolicy::Ignore
+                    }
+                })
+            })
+    })
+    .expect("Couldn't create the supervisor.");
+
+    // Ignoring a fault counts as a restart, so the element isn't
+    // relaunched forever.
+    wait_until(|| starts.load(Ordering::SeqCst) == 3);
+    thread::sleep(Duration::from_millis(100));
+    assert_eq!(starts.load(Ordering::SeqCst), 3);
+
+    sp_ref.stop().unwrap();
+}
@@ -0,0 +1,85 @@
+mod common;
+
+use bastion::prelude::*;
+use common::wait_until;
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+use std::sync::Arc;
+
+#[derive(Clone)]
+struct Counter {
+    count: usize,
+    inits: Arc<AtomicUsize>,
+    infos: Arc<AtomicUsize>,
+    terminated: Arc<AtomicBool>,
+}
+
+#[derive(Debug)]
+struct Get;
+
+#[derive(Debug)]
+struct Add(usize);
+
+impl GenServer for Counter {
+    type Call = Get;
+    type Reply = usize;
+    type Cast = Add;
+
+    fn init(&mut self, _ctx: &BastionContext) {
+        self.inits.fetch_add(1, Ordering::SeqCst);
+    }
+
+    fn handle_call(&mut self, _request: Get, _ctx: &BastionContext) -> usize {
+        self.count
+    }
+
+    fn handle_cast(&mut self, Add(n): Add, _ctx: &BastionContext) {
+        self.count += n;
+    }
+
+    fn handle_info(&mut self, _msg: SignedMessage, _ctx: &BastionContext) {
+        self.infos.fetch_add(1, Ordering::SeqCst);
+    }
+
+    fn terminate(&mut self, _ctx: &BastionContext) {
+        self.terminated.store(true, Ordering::SeqCst);
+    }
+}
+
+#[test]
+fn gen_server() {
+    Bastion::init();
+    Bastion::start();
+
+    let inits = Arc::new(AtomicUsize::new(0));
+    let infos = Arc::new(AtomicUsize::new(0));
+    let terminated = Arc::new(AtomicBool::new(false));
+    let counter = Counter {
+        count: 0,
+        inits: inits.clone(),
+        infos: infos.clone(),
+        terminated: terminated.clone(),
+    };
+
+    let server = gen_server::start(counter).expect("Couldn't start the server.");
+    wait_until(|| inits.load(Ordering::SeqCst) == 1);
+    assert_eq!(inits.load(Ordering::SeqCst), 1);
+
+    gen_server::cast::<Counter>(&server, Add(2)).unwrap();
+    gen_server::cast::<Counter>(&server, Add(3)).unwrap();
+    assert_eq!(run!(gen_server::call::<Counter>(&server, Get)), Ok(5));
+
+    // Messages that aren't calls or casts are handled as infos,
+    // including a request that is told instead of asked.
+    server.tell_anonymously("hello").unwrap();
+    server.tell_anonymously(Get).unwrap();
+    wait_until(|| infos.load(Ordering::SeqCst) == 2);
+    assert_eq!(infos.load(Ordering::SeqCst), 2);
+    assert_eq!(run!(gen_server::call::<Counter>(&server, Get)), Ok(5));
+
+    server.stop().unwrap();
+    wait_until(|| terminated.load(Ordering::SeqCst));
+    assert!(terminated.load(Ordering::SeqCst));
+
+    Bastion::stop();
+    Bastion::block_until_stopped();
+}
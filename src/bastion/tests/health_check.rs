@@ -0,0 +1,81 @@
+mod common;
+
+use bastion::prelude::*;
+use common::wait_until;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::thread;
+use std::time::Duration;
+
+#[test]
+fn health_check() {
+    let runtime = BastionRuntime::new(Config::new());
+
+    let blocking = Arc::new(AtomicBool::new(false));
+    let blocking_inner = blocking.clone();
+    let children_ref = runtime
+        .children(move |children| {
+            let blocking = blocking_inner.clone();
+            children
+                .with_redundancy(2)
+                .with_health_check_timeout(Duration::from_millis(400))
+                // Blocking the element's thread doesn't block the pool.
+                .with_spawn_strategy(SpawnStrategy::DedicatedThread)
+                .with_exec(move |ctx: BastionContext| {
+                    let blocking = blocking.clone();
+                    async move {
+                        loop {
+                            msg! { ctx.recv().await?,
+                                millis: u64 => {
+                                    blocking.store(true, Ordering::SeqCst);
+                                    thread::sleep(Duration::from_millis(millis));
+                                    blocking.store(false, Ordering::SeqCst);
+                                };
+                                _: _ => ();
+                            }
+                        }
+                    }
+                })
+        })
+        .expect("Couldn't create the children group.");
+    runtime.start();
+    wait_until(|| children_ref.len() == 2);
+
+    let check = || {
+        let mut statuses = run!(children_ref.health_check())
+            .into_iter()
+            .map(|(_, status)| status)
+            .collect::<Vec<_>>();
+        statuses.sort_by_key(|status| *status == HealthStatus::Healthy);
+        statuses
+    };
+
+    // Idle elements answer right away...
+    assert_eq!(check(), [HealthStatus::Healthy, HealthStatus::Healthy]);
+
+    // ...while the ones busy for longer than the timeout don't...
+    children_ref.send_to_index(0, 1000u64).unwrap();
+    wait_until(|| blocking.load(Ordering::SeqCst));
+    assert_eq!(check(), [HealthStatus::Unresponsive, HealthStatus::Healthy]);
+    wait_until(|| !blocking.load(Ordering::SeqCst));
+
+    // ...and the ones busy for more than half of it are slow.
+    children_ref.send_to_index(0, 300u64).unwrap();
+    wait_until(|| blocking.load(Ordering::SeqCst));
+    match check().as_slice() {
+        [HealthStatus::Slow(elapsed), HealthStatus::Healthy] => {
+            assert!(*elapsed > Duration::from_millis(200));
+            assert!(*elapsed < Duration::from_millis(400));
+        }
+        statuses => panic!("Unexpected statuses: {:?}", statuses),
+    }
+    wait_until(|| !blocking.load(Ordering::SeqCst));
+
+    // A stopped children group doesn't answer.
+    children_ref.stop().unwrap();
+    wait_until(|| children_ref.is_empty());
+    assert!(run!(children_ref.health_check()).is_empty());
+
+    runtime.stop();
+    runtime.block_until_stopped();
+}
@@ -0,0 +1,72 @@
+#![cfg(feature = "http-health")]
+mod common;
+
+use bastion::prelude::*;
+use common::wait_until;
+use std::io::{Read, Write};
+use std::net::{SocketAddr, TcpStream};
+
+// Sends a `GET` request to the endpoint, returning the status line
+// and body of the response.
+fn get(addr: SocketAddr, path: &str) -> std::io::Result<(String, String)> {
+    let mut stream = TcpStream::connect(addr)?;
+    write!(
+        stream,
+        "GET {} HTTP/1.1\r\nHost: localhost\r\nConnection: close\r\n\r\n",
+        path
+    )?;
+    let mut response = String::new();
+    stream.read_to_string(&mut response)?;
+
+    let status = response.lines().next().unwrap_or_default().to_string();
+    let body = response
+        .split("\r\n\r\n")
+        .nth(1)
+        .unwrap_or_default()
+        .to_string();
+    Ok((status, body))
+}
+
+#[test]
+fn health_endpoint() {
+    let runtime = BastionRuntime::new(Config::new());
+    runtime
+        .children(|children| {
+            children
+                .with_redundancy(2)
+                .with_exec(|ctx: BastionContext| async move {
+                    loop {
+                        ctx.recv().await?;
+                    }
+                })
+        })
+        .expect("Couldn't create the children group.");
+    let addr = runtime.register_health_endpoint(0).unwrap();
+    let addr = SocketAddr::from(([127, 0, 0, 1], addr.port()));
+
+    // The system isn't ready until it got started...
+    let (status, _) = get(addr, "/ready").unwrap();
+    assert!(status.ends_with("503 Service Unavailable"), "{}", status);
+
+    runtime.start();
+    let ready = || get(addr, "/ready").unwrap().0;
+    wait_until(|| ready().ends_with("200 OK"));
+    let status = ready();
+    assert!(status.ends_with("200 OK"), "{}", status);
+
+    // ...while it reports how many elements are running...
+    let health = || get(addr, "/health").unwrap();
+    wait_until(|| health().1.contains("\"actors\":2"));
+    let (status, body) = health();
+    assert!(status.ends_with("200 OK"), "{}", status);
+    let body: serde_json::Value = serde_json::from_str(&body).unwrap();
+    assert_eq!(body, serde_json::json!({ "status": "ok", "actors": 2 }));
+
+    let (status, _) = get(addr, "/metrics").unwrap();
+    assert!(status.ends_with("404 Not Found"), "{}", status);
+
+    // ...and stops listening once the system stopped.
+    runtime.stop();
+    runtime.block_until_stopped();
+    wait_until(|| get(addr, "/health").is_err());
+}
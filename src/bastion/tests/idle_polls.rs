@@ -0,0 +1,95 @@
+mod common;
+
+use bastion::executor::BastionExecutor;
+use bastion::prelude::*;
+use common::wait_until;
+use futures::future::{BoxFuture, LocalBoxFuture};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+use std::thread;
+use std::time::Duration;
+
+// Runs each future on a new thread, counting them (every future
+// polling a process once, they count how many times the system,
+// supervisors, children groups and elements got polled).
+#[derive(Debug, Clone, Default)]
+struct CountingExecutor {
+    polls: Arc<AtomicUsize>,
+}
+
+impl CountingExecutor {
+    // Returns the number of polls once the tree settled.
+    fn settled_polls(&self) -> usize {
+        thread::sleep(Duration::from_millis(100));
+        self.polls.load(Ordering::SeqCst)
+    }
+}
+
+impl BastionExecutor for CountingExecutor {
+    fn spawn(&self, future: BoxFuture<'static, ()>) {
+        self.polls.fetch_add(1, Ordering::SeqCst);
+        thread::spawn(move || futures::executor::block_on(future));
+    }
+
+    fn spawn_blocking(&self, future: BoxFuture<'static, ()>) {
+        self.spawn(future)
+    }
+
+    fn block_on(&self, future: LocalBoxFuture<'_, ()>) {
+        futures::executor::block_on(future)
+    }
+}
+
+#[test]
+fn idle_polls() {
+    let executor = CountingExecutor::default();
+    Bastion::init_with(Config::new().with_executor(executor.clone()));
+    Bastion::start();
+
+    let received = Arc::new(AtomicUsize::new(0));
+    let mut children_refs = Vec::new();
+    for _ in 0..5 {
+        let supervisor_ref = Bastion::supervisor(|sp| sp).expect("Couldn't create the supervisor.");
+        let received = received.clone();
+        let children_ref = supervisor_ref
+            .children(move |children| {
+                let received = received.clone();
+                children.with_exec(move |ctx: BastionContext| {
+                    let received = received.clone();
+                    async move {
+                        loop {
+                            ctx.recv().await?;
+                            received.fetch_add(1, Ordering::SeqCst);
+                        }
+                    }
+                })
+            })
+            .expect("Couldn't create the children group.");
+        children_refs.push(children_ref);
+    }
+    wait_until(|| Bastion::num_actors() == 5);
+
+    // Nothing gets polled while idle...
+    let idle = executor.settled_polls();
+    thread::sleep(Duration::from_millis(200));
+    assert_eq!(executor.polls.load(Ordering::SeqCst), idle);
+
+    // ...and everything only once per message: the element...
+    let elem = &children_refs[0].elems()[0];
+    elem.tell_anonymously("ping").unwrap();
+    wait_until(|| received.load(Ordering::SeqCst) == 1);
+    assert_eq!(executor.settled_polls(), idle + 1);
+
+    // ...or its group and the element.
+    children_refs[0].broadcast("ping").unwrap();
+    wait_until(|| received.load(Ordering::SeqCst) == 2);
+    assert_eq!(executor.settled_polls(), idle + 3);
+
+    // The system also only gets polled once to deploy a supervisor,
+    // which then starts right away.
+    Bastion::supervisor(|sp| sp).expect("Couldn't create the supervisor.");
+    assert_eq!(executor.settled_polls(), idle + 5);
+
+    Bastion::stop();
+    assert_eq!(Bastion::block_until_stopped(), SystemExit::Stopped);
+}
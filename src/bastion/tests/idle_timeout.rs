@@ -0,0 +1,63 @@
+mod common;
+
+use bastion::prelude::*;
+use common::wait_until;
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+use std::sync::Arc;
+use std::thread;
+use std::time::Duration;
+
+#[test]
+fn idle_timeout() {
+    let runtime = BastionRuntime::new(Config::new());
+
+    let started = Arc::new(AtomicUsize::new(0));
+    let handled = Arc::new(AtomicUsize::new(0));
+    let stopped = Arc::new(AtomicBool::new(false));
+    let (started_inner, handled_inner, stopped_inner) =
+        (started.clone(), handled.clone(), stopped.clone());
+    let children_ref = runtime
+        .children(move |children| {
+            let (started, handled) = (started_inner.clone(), handled_inner.clone());
+            let stopped = stopped_inner.clone();
+            children
+                .with_redundancy(2)
+                .with_idle_timeout(Duration::from_millis(300))
+                .with_on_full_stop(move || stopped.store(true, Ordering::SeqCst))
+                .with_exec(move |ctx: BastionContext| {
+                    let (started, handled) = (started.clone(), handled.clone());
+                    async move {
+                        started.fetch_add(1, Ordering::SeqCst);
+                        loop {
+                            msg! { ctx.recv().await?,
+                                _: _ => {
+                                    handled.fetch_add(1, Ordering::SeqCst);
+                                };
+                            }
+                        }
+                    }
+                })
+        })
+        .expect("Couldn't create the children group.");
+    runtime.start();
+    wait_until(|| started.load(Ordering::SeqCst) == 2);
+
+    // Receiving messages resets the timer of the elements...
+    for _ in 0..6 {
+        thread::sleep(Duration::from_millis(100));
+        children_ref.broadcast("ping").unwrap();
+    }
+    wait_until(|| handled.load(Ordering::SeqCst) == 12);
+    assert_eq!(handled.load(Ordering::SeqCst), 12);
+    assert!(!stopped.load(Ordering::SeqCst));
+
+    // ...which stop once they waited for longer, without being
+    // restarted.
+    wait_until(|| stopped.load(Ordering::SeqCst));
+    assert!(stopped.load(Ordering::SeqCst));
+    thread::sleep(Duration::from_millis(100));
+    assert_eq!(started.load(Ordering::SeqCst), 2);
+
+    runtime.stop();
+    runtime.block_until_stopped();
+}
@@ -0,0 +1,105 @@
+mod common;
+
+use bastion::prelude::*;
+use common::wait_until;
+use futures::StreamExt;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+
+#[test]
+fn display_and_parse() {
+    let runtime = BastionRuntime::new(Config::new());
+    let children_ref = runtime
+        .children(|children| children.with_exec(|_| async { Ok(()) }))
+        .unwrap();
+    let id = children_ref.id();
+
+    // Ids are displayed in full, which can be parsed back...
+    let full = id.to_string();
+    assert_eq!(&full.parse::<BastionId>().unwrap(), id);
+    assert_eq!(NIL_ID.to_string().parse::<BastionId>(), Ok(NIL_ID));
+    assert_eq!("not an id".parse::<BastionId>(), Err(ParseIdError));
+
+    // ...unless using the alternate form, which shortens them.
+    let short = format!("{:#}", id);
+    assert_eq!(short.len(), 8);
+    assert!(short.chars().all(|c| c.is_ascii_hexdigit()));
+    assert_eq!(format!("{:#}", NIL_ID), "00000000");
+    assert!(full.replace('-', "").starts_with(&short));
+    assert_eq!(short.parse::<BastionId>(), Err(ParseIdError));
+
+    let path = children_ref.path();
+    assert!(path.to_string().ends_with(&format!("/{}", full)));
+    assert!(format!("{:#}", path).ends_with(&format!("/{}", short)));
+}
+
+#[test]
+fn supervisor_names() {
+    let runtime = BastionRuntime::new(Config::new());
+    let events = runtime.events();
+
+    let faults = Arc::new(AtomicUsize::new(0));
+    let restarts = Arc::new(AtomicUsize::new(0));
+    let (faults_inner, restarts_inner) = (faults.clone(), restarts.clone());
+    let mut debug = String::new();
+    let sp_ref = runtime
+        .supervisor(|sp| {
+            let callbacks = Callbacks::new().with_before_restart(move || {
+                restarts_inner.fetch_add(1, Ordering::SeqCst);
+            });
+            let sp = sp
+                .with_name("named")
+                .with_callbacks(callbacks)
+                .with_restart_window(RestartWindow::new(0, Duration::from_secs(60)))
+                .children(move |children| {
+                    let faults = faults_inner.clone();
+                    children
+                        .with_name("workers")
+                        .with_exec(move |ctx: BastionContext| {
+                            let faults = faults.clone();
+                            async move {
+                                // Escalating on the first fault gets the
+                                // supervisor restarted by the system.
+                                if faults.fetch_add(1, Ordering::SeqCst) == 0 {
+                                    return Err(());
+                                }
+                                loop {
+                                    ctx.recv().await?;
+                                }
+                            }
+                        })
+                });
+            debug = format!("{:?}", sp);
+            sp
+        })
+        .expect("Couldn't create the supervisor.");
+
+    assert_eq!(sp_ref.name(), Some("named"));
+    assert!(debug.contains("name: Some(\"named\")"));
+    assert!(format!("{:?}", sp_ref).contains("name: Some(\"named\")"));
+
+    runtime.start();
+    wait_until(|| restarts.load(Ordering::SeqCst) == 1);
+    assert_eq!(restarts.load(Ordering::SeqCst), 1);
+    runtime.stop();
+    runtime.block_until_stopped();
+
+    // The restarted supervisor got a new identifier but kept its name.
+    let events = run!(events.collect::<Vec<_>>());
+    let stopped = events
+        .iter()
+        .filter_map(|event| match event {
+            SystemEvent::Lifecycle(event)
+                if event.entity == EntityKind::Supervisor
+                    && event.transition == Transition::Stopped =>
+            {
+                Some(event)
+            }
+            _ => None,
+        })
+        .collect::<Vec<_>>();
+    assert_eq!(stopped.len(), 1);
+    assert_ne!(&stopped[0].id, sp_ref.id());
+    assert_eq!(stopped[0].name.as_deref(), Some("named"));
+}
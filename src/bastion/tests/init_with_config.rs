@@ -0,0 +1,55 @@
+mod common;
+
+use bastion::prelude::*;
+use common::wait_until;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+use std::thread;
+use std::time::Duration;
+
+#[test]
+fn init_with_config() {
+    let before_starts = Arc::new(AtomicUsize::new(0));
+    let before_starts_inner = before_starts.clone();
+    let callbacks = Callbacks::new().with_before_start(move || {
+        before_starts_inner.fetch_add(1, Ordering::SeqCst);
+    });
+
+    let config = Config::new()
+        .with_threads(2)
+        .with_system_strategy(SupervisionStrategy::OneForAll)
+        .with_default_callbacks(callbacks);
+    Bastion::init_with(config);
+
+    assert_eq!(bastion_executor::pool::threads(), 2);
+
+    // Using the default callbacks...
+    Bastion::children(|children| {
+        children.with_exec(|ctx: BastionContext| async move {
+            ctx.recv().await?;
+            Ok(())
+        })
+    })
+    .expect("Couldn't create the children group.");
+    // ...or its own ones.
+    Bastion::children(|children| {
+        children
+            .with_callbacks(Callbacks::new())
+            .with_exec(|ctx: BastionContext| async move {
+                ctx.recv().await?;
+                Ok(())
+            })
+    })
+    .expect("Couldn't create the children group.");
+
+    Bastion::start();
+
+    // Called when the first group got deployed and when its
+    // element started.
+    wait_until(|| before_starts.load(Ordering::SeqCst) == 2);
+    thread::sleep(Duration::from_millis(50));
+    assert_eq!(before_starts.load(Ordering::SeqCst), 2);
+
+    Bastion::stop();
+    Bastion::block_until_stopped();
+}
@@ -0,0 +1,313 @@
+mod common;
+
+use bastion::prelude::*;
+use common::wait_until;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+
+async fn idle(ctx: BastionContext) -> Result<(), ()> {
+    loop {
+        ctx.recv().await?;
+    }
+}
+
+#[test]
+fn inspect() {
+    Bastion::init();
+    Bastion::start();
+
+    let top_ref = Bastion::children(|children| children.with_exec(idle))
+        .expect("Couldn't create the children group.");
+    let mut nested = None;
+    let sp_ref = Bastion::supervisor(|mut sp| {
+        nested = Some(sp.supervisor_ref(|sp| sp.children(|children| children.with_exec(idle))));
+        sp.with_strategy(SupervisionStrategy::OneForAll)
+    })
+    .expect("Couldn't create the supervisor.");
+    let nested_ref = nested.unwrap();
+    let stopped_ref = sp_ref
+        .children(|children| children.with_exec(idle))
+        .expect("Couldn't create the children group.");
+    wait_until(|| Bastion::num_actors() == 3);
+    stopped_ref.stop().unwrap();
+    wait_until(|| matches!(run!(sp_ref.inspect()), Ok(report) if report.stopped == 1));
+
+    // Supervisors report the elements they supervise...
+    let report = run!(sp_ref.inspect()).expect("Couldn't inspect the supervisor.");
+    assert_eq!(&report.id, sp_ref.id());
+    assert!(matches!(report.strategy, SupervisionStrategy::OneForAll));
+    assert_eq!((report.launched, report.stopped, report.killed), (1, 1, 0));
+    assert_eq!(report.children.len(), 2);
+
+    let nested = &report.children[0];
+    assert_eq!(&nested.id, nested_ref.id());
+    assert_eq!(nested.kind, SupervisedKind::Supervisor);
+    assert_eq!(nested.stop_reason, None);
+    // ...including the reports of the running supervisors.
+    let nested_report = nested.report.as_ref().unwrap();
+    assert!(matches!(
+        nested_report.strategy,
+        SupervisionStrategy::OneForOne
+    ));
+    assert_eq!(nested_report.launched, 1);
+    assert_eq!(nested_report.children[0].kind, SupervisedKind::Children);
+
+    let stopped = &report.children[1];
+    assert_eq!(&stopped.id, stopped_ref.id());
+    assert_eq!(stopped.kind, SupervisedKind::Children);
+    assert_eq!(stopped.stop_reason, Some(StopReason::Stopped));
+    assert!(stopped.report.is_none());
+
+    // The whole tree is rooted at the system supervisor, without
+    // the dead letters.
+    let root = run!(Bastion::inspect()).expect("Couldn't inspect the system.");
+    assert_eq!(root.launched, 2);
+    assert_eq!(root.children.len(), 2);
+    let top = root.children.iter().find(|elem| &elem.id == top_ref.id());
+    assert_eq!(top.unwrap().kind, SupervisedKind::Children);
+    let sp = root.children.iter().find(|elem| &elem.id == sp_ref.id());
+    assert_eq!(sp.unwrap().report.as_ref().unwrap().children.len(), 2);
+
+    Bastion::stop();
+    Bastion::block_until_stopped();
+
+    // Stopped supervisors don't answer.
+    assert!(run!(sp_ref.inspect()).is_err());
+}
+
+fn counting_children(sp_ref: &SupervisorRef, name: &str, started: Arc<AtomicUsize>) -> ChildrenRef {
+    sp_ref
+        .children(move |children| {
+            children
+                .with_name(name)
+                .with_exec(move |ctx: BastionContext| {
+                    let started = started.clone();
+                    async move {
+                        started.fetch_add(1, Ordering::SeqCst);
+                        loop {
+                            msg! { ctx.recv().await?,
+                                ref _msg: &'static str => panic!("faulting");
+                                _: _ => ();
+                            }
+                        }
+                    }
+                })
+        })
+        .expect("Couldn't create the children group.")
+}
+
+#[test]
+fn migrate() {
+    let runtime = BastionRuntime::new(Config::new());
+    let source = runtime
+        .supervisor(|sp| sp.with_strategy(SupervisionStrategy::RestForOne))
+        .unwrap();
+    let target = runtime.supervisor(|sp| sp).unwrap();
+
+    let counters = (0..3)
+        .map(|_| Arc::new(AtomicUsize::new(0)))
+        .collect::<Vec<_>>();
+    let migrated_ref = counting_children(&source, "migrated", counters[0].clone());
+    let first_ref = counting_children(&source, "first", counters[1].clone());
+    let second_ref = counting_children(&source, "second", counters[2].clone());
+    let count = |index: usize| counters[index].load(Ordering::SeqCst);
+    runtime.start();
+    wait_until(|| count(0) == 1 && count(1) == 1 && count(2) == 1);
+
+    // The group is stopped and relaunched by the target...
+    source.migrate(migrated_ref.id().clone(), &target).unwrap();
+    wait_until(|| matches!(run!(target.inspect()), Ok(report) if report.launched == 1));
+    wait_until(|| count(0) == 2);
+    assert_eq!(count(0), 2);
+
+    // ...keeping its identifier.
+    let report = run!(source.inspect()).expect("Couldn't inspect the source.");
+    assert_eq!(report.launched, 2);
+    let ids = report
+        .children
+        .iter()
+        .map(|elem| &elem.id)
+        .collect::<Vec<_>>();
+    assert_eq!(ids, vec![first_ref.id(), second_ref.id()]);
+    let report = run!(target.inspect()).expect("Couldn't inspect the target.");
+    assert_eq!(report.launched, 1);
+    assert_eq!(&report.children[0].id, migrated_ref.id());
+
+    // Its faults are now handled by the target...
+    let relaunched_ref = runtime.children_named("migrated").unwrap();
+    assert_eq!(relaunched_ref.id(), migrated_ref.id());
+    relaunched_ref.broadcast("fault").unwrap();
+    wait_until(|| count(0) == 3);
+    assert_eq!(count(0), 3);
+
+    // ...while the source restarts the groups that followed the
+    // faulted one, which moved up.
+    first_ref.broadcast("fault").unwrap();
+    wait_until(|| count(1) == 2 && count(2) == 2);
+    assert_eq!((count(1), count(2)), (2, 2));
+
+    // Supervisors can be migrated too, but not to themselves or
+    // to the supervisors they supervise.
+    let mut nested = None;
+    let supervisor_ref = source
+        .supervisor(|mut sp| {
+            nested = Some(sp.supervisor_ref(|sp| sp));
+            sp
+        })
+        .unwrap();
+    let nested = nested.unwrap();
+    assert!(source
+        .migrate(supervisor_ref.id().clone(), &supervisor_ref)
+        .is_err());
+    assert!(source
+        .migrate(supervisor_ref.id().clone(), &nested)
+        .is_err());
+    source
+        .migrate(supervisor_ref.id().clone(), &target)
+        .unwrap();
+    wait_until(|| matches!(run!(target.inspect()), Ok(report) if report.launched == 2));
+    let report = run!(target.inspect()).expect("Couldn't inspect the target.");
+    assert_eq!(&report.children[1].id, supervisor_ref.id());
+    assert_eq!(report.children[1].kind, SupervisedKind::Supervisor);
+
+    target.stop().unwrap();
+    wait_until(|| relaunched_ref.is_empty());
+    assert!(relaunched_ref.is_empty());
+
+    runtime.stop();
+    assert_eq!(runtime.block_until_stopped(), SystemExit::Stopped);
+}
+
+#[test]
+fn tree() {
+    let runtime = BastionRuntime::new(Config::new());
+    let mut nested = None;
+    let sp_ref = runtime
+        .supervisor(|mut sp| {
+            nested = Some(sp.supervisor_ref(|sp| sp.children(|children| children.with_exec(idle))));
+            sp
+        })
+        .unwrap();
+    let nested_ref = nested.unwrap();
+    let first_ref = sp_ref
+        .children(|children| children.with_exec(idle))
+        .unwrap();
+    let stopped_ref = sp_ref
+        .children(|children| children.with_exec(idle))
+        .unwrap();
+    runtime.start();
+    wait_until(|| runtime.num_actors() == 3);
+    stopped_ref.stop().unwrap();
+    wait_until(|| matches!(run!(sp_ref.inspect()), Ok(report) if report.stopped == 1));
+
+    // The running elements are yielded breadth-first, along with
+    // their depth.
+    let report = run!(sp_ref.inspect()).expect("Couldn't inspect the supervisor.");
+    let tree = report
+        .tree()
+        .map(|(depth, elem)| (depth, elem.id.clone(), elem.kind))
+        .collect::<Vec<_>>();
+    assert_eq!(tree.len(), 3);
+    assert_eq!(
+        tree[..2],
+        [
+            (0, nested_ref.id().clone(), SupervisedKind::Supervisor),
+            (0, first_ref.id().clone(), SupervisedKind::Children),
+        ]
+    );
+    assert_eq!((tree[2].0, tree[2].2), (1, SupervisedKind::Children));
+
+    runtime.stop();
+    runtime.block_until_stopped();
+}
+
+#[test]
+fn tree_dot() {
+    let runtime = BastionRuntime::new(Config::new());
+    let sp_ref = runtime
+        .supervisor(|sp| sp.with_strategy(SupervisionStrategy::OneForAll))
+        .unwrap();
+    let workers_ref = sp_ref
+        .children(|children| {
+            children
+                .with_name("workers")
+                .with_redundancy(2)
+                .with_exec(idle)
+        })
+        .expect("Couldn't create the children group.");
+    let stopped_ref = sp_ref
+        .children(|children| children.with_name("stopped").with_exec(idle))
+        .expect("Couldn't create the children group.");
+    runtime.start();
+    wait_until(|| runtime.num_actors() == 3);
+    stopped_ref.stop().unwrap();
+    wait_until(|| matches!(run!(sp_ref.inspect()), Ok(report) if report.stopped == 1));
+
+    let report = run!(sp_ref.inspect()).expect("Couldn't inspect the supervisor.");
+    assert_eq!(report.children[0].name.as_deref(), Some("workers"));
+    assert_eq!(report.children[0].redundancy, Some(2));
+
+    let dot = run!(runtime.tree_dot()).expect("Couldn't render the tree.");
+    assert!(dot.starts_with("digraph bastion {\n"));
+    assert!(dot.contains(&format!(
+        "\"{}\" [shape=box, label=\"Supervisor({})\\nOneForAll\"];",
+        sp_ref.id(),
+        sp_ref.id()
+    )));
+    assert!(dot.contains(&format!(
+        "\"{}\" [shape=ellipse, label=\"workers\\nredundancy: 2\"];",
+        workers_ref.id()
+    )));
+    assert!(dot.contains(&format!(
+        "\"{}\" [shape=ellipse, label=\"stopped\\nredundancy: 1\\nstopped\", style=dashed, color=gray];",
+        stopped_ref.id()
+    )));
+    assert!(dot.contains(&format!("\"{}\" -> \"{}\";", sp_ref.id(), workers_ref.id())));
+    // The elements are rendered in the order they were added.
+    assert!(dot.find("workers").unwrap() < dot.find("stopped").unwrap());
+    // Rendering the same tree twice gives the same graph.
+    assert_eq!(run!(runtime.tree_dot()), Ok(dot));
+
+    runtime.stop();
+    assert_eq!(runtime.block_until_stopped(), SystemExit::Stopped);
+}
+
+#[test]
+fn export_dot() {
+    let runtime = BastionRuntime::new(Config::new());
+    let sp_ref = runtime.supervisor(|sp| sp).unwrap();
+    let workers_ref = sp_ref
+        .children(|children| children.with_name("workers").with_exec(idle))
+        .expect("Couldn't create the children group.");
+    let stopped_ref = sp_ref
+        .children(|children| children.with_name("stopped").with_exec(idle))
+        .expect("Couldn't create the children group.");
+    runtime.start();
+    wait_until(|| runtime.num_actors() == 2);
+    stopped_ref.stop().unwrap();
+    wait_until(|| matches!(run!(sp_ref.inspect()), Ok(report) if report.stopped == 1));
+
+    // The nodes are colored depending on the elements' health.
+    let dot = run!(runtime.export_dot());
+    assert!(dot.starts_with("digraph bastion {\n"));
+    assert!(dot.contains(&format!(
+        "\"{}\" [shape=box, label=\"Supervisor({})\\nOneForOne\", style=filled, fillcolor=green];",
+        sp_ref.id(),
+        sp_ref.id()
+    )));
+    assert!(dot.contains(&format!(
+        "\"{}\" [shape=ellipse, label=\"workers\\nredundancy: 1\", style=filled, fillcolor=green];",
+        workers_ref.id()
+    )));
+    assert!(dot.contains(&format!(
+        "\"{}\" [shape=ellipse, label=\"stopped\\nredundancy: 1\\nstopped\", style=filled, fillcolor=yellow];",
+        stopped_ref.id()
+    )));
+    assert!(dot.contains(&format!("\"{}\" -> \"{}\";", sp_ref.id(), stopped_ref.id())));
+
+    runtime.stop();
+    runtime.block_until_stopped();
+
+    // Stopped systems give an empty graph.
+    assert_eq!(run!(runtime.export_dot()), "digraph bastion {\n}\n");
+}
@@ -0,0 +1,58 @@
+mod common;
+
+use bastion::prelude::*;
+use common::wait_until;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex};
+
+#[test]
+fn interceptors() {
+    Bastion::init();
+
+    let intercepted = Arc::new(AtomicUsize::new(0));
+    let received = Arc::new(Mutex::new(vec![]));
+
+    let intercepted_inner = intercepted.clone();
+    let received_inner = received.clone();
+    let children_ref = Bastion::children(move |children| {
+        let intercepted = intercepted_inner.clone();
+        let received = received_inner.clone();
+        children
+            // A buggy interceptor doesn't prevent delivery.
+            .with_interceptor(|_, _| panic!("buggy interceptor"))
+            .with_interceptor(move |_, _| {
+                intercepted.fetch_add(1, Ordering::SeqCst);
+                InterceptDecision::Deliver
+            })
+            .with_interceptor(|msg: &Msg, _| match msg.peek::<&str>() {
+                Some(&"drop") => InterceptDecision::Drop,
+                Some(&"dead letter") => InterceptDecision::DeadLetter,
+                _ => InterceptDecision::Deliver,
+            })
+            .with_exec(move |ctx: BastionContext| {
+                let received = received.clone();
+                async move {
+                    loop {
+                        msg! { ctx.recv().await?,
+                            ref msg: &'static str => received.lock().unwrap().push(*msg);
+                            _: _ => ();
+                        }
+                    }
+                }
+            })
+    })
+    .expect("Couldn't create the children group.");
+
+    Bastion::start();
+
+    children_ref.broadcast("drop").unwrap();
+    children_ref.broadcast("dead letter").unwrap();
+    children_ref.broadcast("deliver").unwrap();
+
+    wait_until(|| !received.lock().unwrap().is_empty());
+    assert_eq!(*received.lock().unwrap(), vec!["deliver"]);
+    assert_eq!(intercepted.load(Ordering::SeqCst), 3);
+
+    Bastion::stop();
+    Bastion::block_until_stopped();
+}
@@ -0,0 +1,112 @@
+#![cfg(feature = "lifecycle-events")]
+mod common;
+
+use bastion::prelude::*;
+use common::wait_until;
+use std::fmt::Debug;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex};
+use tracing::field::{Field, Visit};
+use tracing::{Event, Subscriber};
+use tracing_subscriber::layer::{Context, Layer, SubscriberExt};
+
+// Records the names of the lifecycle events.
+#[derive(Debug, Default, Clone)]
+struct Recorder {
+    events: Arc<Mutex<Vec<String>>>,
+}
+
+struct EventName(Option<String>);
+
+impl Visit for EventName {
+    fn record_str(&mut self, field: &Field, value: &str) {
+        if field.name() == "event" {
+            self.0 = Some(value.to_string());
+        }
+    }
+
+    fn record_debug(&mut self, _field: &Field, _value: &dyn Debug) {}
+}
+
+impl<S: Subscriber> Layer<S> for Recorder {
+    fn on_event(&self, event: &Event<'_>, _ctx: Context<'_, S>) {
+        if event.metadata().target() != "bastion::lifecycle" {
+            return;
+        }
+
+        let mut name = EventName(None);
+        event.record(&mut name);
+        if let Some(name) = name.0 {
+            self.events.lock().unwrap().push(name);
+        }
+    }
+}
+
+#[test]
+fn lifecycle_events() {
+    let recorder = Recorder::default();
+    let events = recorder.events.clone();
+    tracing::subscriber::set_global_default(tracing_subscriber::registry().with(recorder)).unwrap();
+    let recorded = |event: &str| events.lock().unwrap().iter().any(|name| name == event);
+
+    let runtime = BastionRuntime::new(Config::new());
+    let started = Arc::new(AtomicUsize::new(0));
+    let started_inner = started.clone();
+    let children_ref = Arc::new(Mutex::new(None));
+    let children_ref_inner = children_ref.clone();
+    let sp_ref = runtime
+        .supervisor(move |sp| {
+            let started = started_inner.clone();
+            let group = sp.children_ref(move |children| {
+                children
+                    .with_name("faulty")
+                    .with_exec(move |ctx: BastionContext| {
+                        let started = started.clone();
+                        async move {
+                            // Faults once.
+                            if started.fetch_add(1, Ordering::SeqCst) == 0 {
+                                return Err(());
+                            }
+
+                            loop {
+                                ctx.recv().await?;
+                            }
+                        }
+                    })
+            });
+            *children_ref_inner.lock().unwrap() = Some(group);
+            sp
+        })
+        .expect("Couldn't create the supervisor.");
+    // Changed before the group gets started (and faults).
+    sp_ref.strategy(SupervisionStrategy::OneForAll).unwrap();
+    runtime.start();
+    wait_until(|| started.load(Ordering::SeqCst) == 2);
+
+    // The events are emitted without any callback being set...
+    for event in &[
+        "supervisor_deployed",
+        "children_deployed",
+        "supervisor_started",
+        "children_started",
+        "supervised_faulted",
+        "strategy_changed",
+        "element_restarted",
+    ] {
+        wait_until(|| recorded(event));
+        assert!(recorded(event), "Missing event: {} in {:?}", event, events);
+    }
+
+    // ...including the ones about the messages nobody received...
+    let children_ref = children_ref.lock().unwrap().clone().unwrap();
+    children_ref.stop().unwrap();
+    wait_until(|| recorded("children_stopped"));
+    children_ref.broadcast("lost").ok();
+    wait_until(|| recorded("message_dead_lettered"));
+    assert!(recorded("message_dead_lettered"));
+
+    // ...and the system's own stop.
+    runtime.stop();
+    runtime.block_until_stopped();
+    assert!(recorded("supervisor_stopped"));
+}
@@ -0,0 +1,84 @@
+mod common;
+
+use bastion::prelude::*;
+use common::wait_until;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex};
+
+#[test]
+fn watchers_receive_link_down() {
+    Bastion::init();
+    Bastion::start();
+
+    // An element that waits until it gets told to fault.
+    let linked = Bastion::children(|children| {
+        children.with_exec(|ctx: BastionContext| async move {
+            msg! { ctx.recv().await?,
+                _msg: &'static str => return Err(());
+                _: _ => ();
+            }
+
+            Ok(())
+        })
+    })
+    .expect("Couldn't create the children group.");
+    let monitored = Bastion::children(|children| {
+        children.with_exec(|ctx: BastionContext| async move {
+            loop {
+                ctx.recv().await?;
+            }
+        })
+    })
+    .expect("Couldn't create the children group.");
+
+    let linked_ref = linked.elems()[0].clone();
+    let monitored_ref = monitored.elems()[0].clone();
+
+    let ready = Arc::new(AtomicBool::new(false));
+    let downs = Arc::new(Mutex::new(Vec::new()));
+    let (ready_inner, downs_inner) = (ready.clone(), downs.clone());
+    Bastion::children(move |children| {
+        let (linked_ref, monitored_ref) = (linked_ref.clone(), monitored_ref.clone());
+        let (ready, downs) = (ready_inner.clone(), downs_inner.clone());
+        children.with_exec(move |ctx: BastionContext| {
+            let (linked_ref, monitored_ref) = (linked_ref.clone(), monitored_ref.clone());
+            let (ready, downs) = (ready.clone(), downs.clone());
+            async move {
+                ctx.link(&linked_ref).unwrap();
+                ctx.monitor(&monitored_ref).unwrap();
+                ready.store(true, Ordering::SeqCst);
+
+                loop {
+                    msg! { ctx.recv().await?,
+                        down: LinkDown => {
+                            downs.lock().unwrap().push((down.id, down.reason));
+                        };
+                        _: _ => ();
+                    }
+                }
+            }
+        })
+    })
+    .expect("Couldn't create the children group.");
+
+    wait_until(|| ready.load(Ordering::SeqCst));
+
+    let monitored_id = monitored.elems()[0].id().clone();
+    monitored.elems()[0].stop().unwrap();
+    wait_until(|| downs.lock().unwrap().len() == 1);
+
+    let linked_id = linked.elems()[0].id().clone();
+    linked.elems()[0].tell_anonymously("fault").unwrap();
+    wait_until(|| downs.lock().unwrap().len() == 2);
+
+    assert_eq!(
+        *downs.lock().unwrap(),
+        vec![
+            (monitored_id, TerminationReason::Stopped),
+            (linked_id, TerminationReason::Faulted),
+        ]
+    );
+
+    Bastion::stop();
+    Bastion::block_until_stopped();
+}
@@ -0,0 +1,59 @@
+use bastion::prelude::*;
+use std::thread;
+use std::time::Duration;
+
+fn list_stopped(sp_ref: &SupervisorRef) -> Vec<SupervisedInfo> {
+    run!(sp_ref.list_stopped()).expect("The supervisor stopped.")
+}
+
+#[test]
+fn list_stopped_subtrees() {
+    Bastion::init();
+
+    let sp_ref = Bastion::supervisor(|sp| sp).expect("Couldn't create the supervisor.");
+    let running = sp_ref
+        .children(|children| {
+            children.with_exec(|ctx: BastionContext| async move {
+                loop {
+                    ctx.recv().await?;
+                }
+            })
+        })
+        .expect("Couldn't create the children group.");
+    let stopped = sp_ref
+        .children(|children| {
+            children.with_exec(|ctx: BastionContext| async move {
+                loop {
+                    ctx.recv().await?;
+                }
+            })
+        })
+        .expect("Couldn't create the children group.");
+
+    Bastion::start();
+
+    assert!(list_stopped(&sp_ref).is_empty());
+
+    stopped.stop().unwrap();
+
+    let mut tries = 0;
+    while list_stopped(&sp_ref).is_empty() && tries < 500 {
+        thread::sleep(Duration::from_millis(10));
+        tries += 1;
+    }
+
+    assert_eq!(
+        list_stopped(&sp_ref),
+        vec![SupervisedInfo {
+            id: stopped.id().clone(),
+            kind: SupervisedKind::Children,
+            stop_reason: StopReason::Stopped,
+        }]
+    );
+    assert!(list_stopped(&sp_ref)
+        .iter()
+        .all(|info| info.id != *running.id()));
+
+    Bastion::stop();
+    Bastion::block_until_stopped();
+}
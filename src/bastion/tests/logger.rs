@@ -0,0 +1,63 @@
+use bastion::prelude::*;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex};
+
+static FAULTED: AtomicBool = AtomicBool::new(false);
+
+#[derive(Debug, Default)]
+struct RecordingLogger {
+    events: Arc<Mutex<Vec<(&'static str, BastionId)>>>,
+}
+
+impl BastionLogger for RecordingLogger {
+    fn log_start(&self, id: &BastionId) {
+        self.events.lock().unwrap().push(("start", id.clone()));
+    }
+
+    fn log_stop(&self, id: &BastionId) {
+        self.events.lock().unwrap().push(("stop", id.clone()));
+    }
+
+    fn log_fault(&self, id: &BastionId) {
+        self.events.lock().unwrap().push(("fault", id.clone()));
+    }
+
+    fn log_restart(&self, id: &BastionId) {
+        self.events.lock().unwrap().push(("restart", id.clone()));
+    }
+}
+
+#[test]
+fn logger_receives_lifecycle_events() {
+    Bastion::init();
+
+    let logger = RecordingLogger::default();
+    let events = logger.events.clone();
+    Bastion::with_logger(Box::new(logger));
+
+    Bastion::start();
+
+    let children = Bastion::children(|children| {
+        children.with_exec(|_ctx: BastionContext| async move {
+            if !FAULTED.swap(true, Ordering::SeqCst) {
+                return Err(());
+            }
+
+            Bastion::stop();
+            Ok(())
+        })
+    })
+    .expect("Couldn't create the children group.");
+    let id = children.elems()[0].id().clone();
+
+    Bastion::block_until_stopped();
+
+    // The events of the system's own children are ignored.
+    let events = events.lock().unwrap();
+    let kinds = events
+        .iter()
+        .filter(|(_, event_id)| *event_id == id)
+        .map(|(kind, _)| *kind)
+        .collect::<Vec<_>>();
+    assert_eq!(kinds, vec!["start", "fault", "restart", "start", "stop"]);
+}
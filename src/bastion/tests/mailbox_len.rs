@@ -0,0 +1,72 @@
+mod common;
+
+use bastion::prelude::*;
+use common::wait_until;
+use futures_timer::Delay;
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+#[test]
+fn mailbox_len() {
+    Bastion::init();
+    Bastion::start();
+
+    let started = Arc::new(AtomicUsize::new(0));
+    let release = Arc::new(AtomicBool::new(false));
+    let seen = Arc::new(Mutex::new(vec![]));
+
+    let (started_inner, release_inner, seen_inner) =
+        (started.clone(), release.clone(), seen.clone());
+    let children_ref = Bastion::children(move |children| {
+        let (started, release, seen) = (
+            started_inner.clone(),
+            release_inner.clone(),
+            seen_inner.clone(),
+        );
+        children
+            .with_redundancy(2)
+            .with_exec(move |ctx: BastionContext| {
+                let (started, release, seen) = (started.clone(), release.clone(), seen.clone());
+                async move {
+                    started.fetch_add(1, Ordering::SeqCst);
+                    // Being busy, letting the messages pile up...
+                    while !release.load(Ordering::SeqCst) {
+                        Delay::new(Duration::from_millis(10)).await;
+                    }
+
+                    let before = ctx.mailbox_len();
+                    for _ in 0..3 {
+                        ctx.recv().await?;
+                    }
+                    seen.lock().unwrap().push((before, ctx.mailbox_len()));
+
+                    loop {
+                        ctx.recv().await?;
+                    }
+                }
+            })
+    })
+    .expect("Couldn't create the children group.");
+
+    wait_until(|| started.load(Ordering::SeqCst) == 2);
+    for _ in 0..3 {
+        children_ref.broadcast("work").unwrap();
+    }
+
+    wait_until(|| run!(children_ref.mailbox_lens()) == Ok(vec![3, 3]));
+    assert_eq!(run!(children_ref.mailbox_lens()), Ok(vec![3, 3]));
+
+    release.store(true, Ordering::SeqCst);
+    wait_until(|| seen.lock().unwrap().len() == 2);
+    assert_eq!(*seen.lock().unwrap(), vec![(3, 0), (3, 0)]);
+    assert_eq!(run!(children_ref.mailbox_lens()), Ok(vec![0, 0]));
+
+    // A stopped children group doesn't answer.
+    children_ref.stop().unwrap();
+    wait_until(|| children_ref.is_empty());
+    assert_eq!(run!(children_ref.mailbox_lens()), Err(()));
+
+    Bastion::stop();
+    Bastion::block_until_stopped();
+}
@@ -0,0 +1,69 @@
+use bastion::prelude::*;
+use futures::prelude::*;
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+use std::sync::Arc;
+use std::thread;
+use std::time::Duration;
+
+#[test]
+fn message_stream_ends_when_child_stops() {
+    Bastion::init();
+    Bastion::start();
+
+    let received = Arc::new(AtomicUsize::new(0));
+    let ended = Arc::new(AtomicBool::new(false));
+    let received_inner = received.clone();
+    let ended_inner = ended.clone();
+
+    let children = Bastion::children(|children| {
+        children.with_exec(move |ctx: BastionContext| {
+            let received = received_inner.clone();
+            let ended = ended_inner.clone();
+            async move {
+                // The first message is retrieved directly...
+                msg! { ctx.recv().await?,
+                    msg: u32 => assert_eq!(msg, 0);
+                    _: _ => panic!();
+                }
+
+                // ...and the next ones through the stream.
+                ctx.messages()
+                    .for_each(|msg| {
+                        let received = received.clone();
+                        async move {
+                            msg! { msg,
+                                _msg: u32 => {
+                                    received.fetch_add(1, Ordering::SeqCst);
+                                };
+                                _: _ => panic!();
+                            }
+                        }
+                    })
+                    .await;
+
+                ended.store(true, Ordering::SeqCst);
+                Ok(())
+            }
+        })
+    })
+    .expect("Couldn't create the children group.");
+    let child = &children.elems()[0];
+
+    for i in 0..4u32 {
+        child.tell_anonymously(i).unwrap();
+    }
+
+    while received.load(Ordering::SeqCst) < 3 {
+        thread::sleep(Duration::from_millis(10));
+    }
+
+    child.stop().unwrap();
+    while !ended.load(Ordering::SeqCst) {
+        thread::sleep(Duration::from_millis(10));
+    }
+
+    Bastion::stop();
+    Bastion::block_until_stopped();
+
+    assert_eq!(received.load(Ordering::SeqCst), 3);
+}
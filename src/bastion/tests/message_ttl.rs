@@ -0,0 +1,54 @@
+mod common;
+
+use bastion::prelude::*;
+use common::wait_until;
+use std::sync::{Arc, Mutex};
+use std::thread;
+use std::time::Duration;
+
+#[test]
+fn expired_messages_are_skipped() {
+    Bastion::init();
+    Bastion::start();
+
+    let received = Arc::new(Mutex::new(Vec::new()));
+
+    let received_inner = received.clone();
+    let children_ref = Bastion::children(move |children| {
+        let received = received_inner.clone();
+        children
+            .with_expired_to_dead_letters(true)
+            .with_exec(move |ctx: BastionContext| {
+                let received = received.clone();
+                async move {
+                    // Being slow to receive the messages...
+                    thread::sleep(Duration::from_millis(200));
+
+                    loop {
+                        msg! { ctx.recv().await?,
+                            ref msg: &'static str => {
+                                received.lock().unwrap().push(*msg);
+                            };
+                            _: _ => ();
+                        }
+                    }
+                }
+            })
+    })
+    .expect("Couldn't create the children group.");
+
+    children_ref
+        .broadcast_with_ttl("expired", Duration::from_millis(50))
+        .unwrap();
+    children_ref
+        .broadcast_with_ttl("alive", Duration::from_secs(60))
+        .unwrap();
+    children_ref.broadcast("forever").unwrap();
+
+    wait_until(|| received.lock().unwrap().len() == 2);
+    assert_eq!(*received.lock().unwrap(), vec!["alive", "forever"]);
+    assert_eq!(children_ref.expired_count(), 1);
+
+    Bastion::stop();
+    Bastion::block_until_stopped();
+}
@@ -0,0 +1,87 @@
+mod common;
+
+use bastion::prelude::*;
+use common::wait_until;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+
+#[test]
+fn metrics() {
+    let runtime = BastionRuntime::new(Config::new());
+    let started = Arc::new(AtomicUsize::new(0));
+    let started_inner = started.clone();
+    let handled = Arc::new(AtomicUsize::new(0));
+    let handled_inner = handled.clone();
+    let sp_ref = runtime
+        .supervisor(move |sp| {
+            let started = started_inner.clone();
+            let handled = handled_inner.clone();
+            sp.children(move |children| {
+                children
+                    .with_name("workers")
+                    .with_exec(move |ctx: BastionContext| {
+                        let started = started.clone();
+                        let handled = handled.clone();
+                        async move {
+                            started.fetch_add(1, Ordering::SeqCst);
+                            loop {
+                                msg! { ctx.recv().await?,
+                                    ref msg: &'static str => {
+                                        handled.fetch_add(1, Ordering::SeqCst);
+                                        // Faults when told to.
+                                        if *msg == "fault" {
+                                            return Err(());
+                                        }
+                                    };
+                                    _: _ => ();
+                                }
+                            }
+                        }
+                    })
+            })
+        })
+        .expect("Couldn't create the supervisor.");
+    runtime.start();
+    wait_until(|| started.load(Ordering::SeqCst) == 1);
+
+    let workers = || {
+        let snapshot = run!(runtime.metrics()).expect("The system stopped.");
+        snapshot.children_named("workers").unwrap().clone()
+    };
+    let supervisor = || {
+        let snapshot = run!(sp_ref.metrics()).expect("The supervisor stopped.");
+        snapshot.supervisor(sp_ref.id()).unwrap().clone()
+    };
+
+    // The messages received and processed are counted...
+    for _ in 0..3 {
+        runtime.broadcast("work").unwrap();
+    }
+    wait_until(|| handled.load(Ordering::SeqCst) == 3);
+    let before = workers();
+    assert_eq!(before.received, 3);
+    assert_eq!(before.processed, 3);
+    assert_eq!(before.restarts, 0);
+    assert_eq!(supervisor().deploys, 1);
+    assert_eq!(supervisor().faults, 0);
+
+    // ...along with the faults and restarts, which are kept once
+    // the supervisor recovered.
+    runtime.broadcast("fault").unwrap();
+    wait_until(|| started.load(Ordering::SeqCst) == 2);
+    let after = workers();
+    assert_eq!(after.received, 4);
+    assert_eq!(after.processed, 4);
+    assert_eq!(after.restarts, 1);
+    let supervisor = supervisor();
+    assert_eq!(supervisor.faults, 1);
+    assert_eq!(supervisor.one_for_one_restarts, 1);
+    assert_eq!(supervisor.one_for_all_restarts, 0);
+
+    // A stopped supervisor doesn't answer.
+    sp_ref.stop().unwrap();
+    wait_until(|| run!(sp_ref.metrics()).is_err());
+
+    runtime.stop();
+    runtime.block_until_stopped();
+}
@@ -0,0 +1,102 @@
+use bastion::prelude::*;
+use std::sync::{Arc, Mutex};
+
+#[derive(Debug, PartialEq)]
+enum Kind {
+    Urgent,
+    Normal,
+}
+
+#[derive(Debug)]
+struct Job {
+    kind: Kind,
+}
+
+#[derive(Debug)]
+struct Ping;
+
+#[derive(Debug)]
+struct Pong;
+
+trait Named {
+    fn name(&self) -> &'static str;
+}
+
+impl Named for Ping {
+    fn name(&self) -> &'static str {
+        "ping"
+    }
+}
+
+impl Named for Pong {
+    fn name(&self) -> &'static str {
+        "pong"
+    }
+}
+
+#[test]
+fn guards_and_multiple_types() {
+    Bastion::init();
+    Bastion::start();
+
+    let matched = Arc::new(Mutex::new(Vec::new()));
+    let matched_inner = matched.clone();
+
+    Bastion::spawn(move |ctx: BastionContext| {
+        let matched = matched_inner.clone();
+        async move {
+            let addr = ctx.current().addr();
+            ctx.tell(&addr, Job { kind: Kind::Urgent }).unwrap();
+            ctx.tell(&addr, Job { kind: Kind::Normal }).unwrap();
+            ctx.tell(&addr, Ping).unwrap();
+            ctx.tell(&addr, Pong).unwrap();
+            ctx.tell(&addr, 42usize).unwrap();
+            ctx.tell(&addr, 7usize).unwrap();
+
+            for _ in 0..6 {
+                msg! { ctx.recv().await?,
+                    msg: Job if msg.kind == Kind::Urgent => {
+                        matched.lock().unwrap().push(format!("urgent {:?}", msg));
+                    };
+                    msg: Job => {
+                        matched.lock().unwrap().push(format!("job {:?}", msg.kind));
+                    };
+                    msg: Ping | Pong => {
+                        matched.lock().unwrap().push(msg.name().to_string());
+                    };
+                    ref msg: &'static str if msg.is_empty() => {
+                        matched.lock().unwrap().push("empty".to_string());
+                    };
+                    msg: &'static str if msg.is_empty() =!> {
+                        answer!(ctx, "empty").unwrap();
+                    };
+                    msg: usize if *msg > 10 => {
+                        matched.lock().unwrap().push(format!("big {}", msg));
+                    };
+                    msg: _ => {
+                        matched.lock().unwrap().push(format!("other {:?}", msg.is::<usize>()));
+                    };
+                }
+            }
+
+            Bastion::stop();
+            Ok(())
+        }
+    })
+    .unwrap();
+
+    Bastion::block_until_stopped();
+
+    let matched = matched.lock().unwrap();
+    assert_eq!(
+        *matched,
+        vec![
+            "urgent Job { kind: Urgent }",
+            "job Normal",
+            "ping",
+            "pong",
+            "big 42",
+            "other true",
+        ]
+    );
+}
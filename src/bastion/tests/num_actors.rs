@@ -0,0 +1,49 @@
+mod common;
+
+use bastion::prelude::*;
+use common::wait_until;
+
+fn looping_children(children: Children, redundancy: usize) -> Children {
+    children
+        .with_redundancy(redundancy)
+        .with_exec(|ctx: BastionContext| async move {
+            loop {
+                ctx.recv().await?;
+            }
+        })
+}
+
+#[test]
+fn num_actors() {
+    Bastion::init();
+    Bastion::start();
+
+    wait_until(|| Bastion::num_actors() == 0 && Bastion::num_supervisors() == 0);
+    assert_eq!(Bastion::num_actors(), 0);
+    assert_eq!(Bastion::num_supervisors(), 0);
+
+    let children_ref = Bastion::children(|children| looping_children(children, 3))
+        .expect("Couldn't create the children group.");
+    Bastion::supervisor(|sp| {
+        sp.children(|children| looping_children(children, 2))
+            .supervisor(|sp| sp.children(|children| looping_children(children, 1)))
+    })
+    .expect("Couldn't create the supervisor.");
+
+    wait_until(|| Bastion::num_actors() == 6 && Bastion::num_supervisors() == 2);
+    assert_eq!(Bastion::num_actors(), 6);
+    assert_eq!(Bastion::num_supervisors(), 2);
+
+    // Stopped elements aren't counted anymore.
+    children_ref.stop().unwrap();
+    wait_until(|| Bastion::num_actors() == 3);
+    assert_eq!(Bastion::num_actors(), 3);
+    assert_eq!(Bastion::num_supervisors(), 2);
+
+    Bastion::stop();
+    Bastion::block_until_stopped();
+
+    wait_until(|| Bastion::num_actors() == 0 && Bastion::num_supervisors() == 0);
+    assert_eq!(Bastion::num_actors(), 0);
+    assert_eq!(Bastion::num_supervisors(), 0);
+}
@@ -0,0 +1,64 @@
+mod common;
+
+use bastion::prelude::*;
+use common::wait_until;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+
+#[test]
+fn panic_forward() {
+    Bastion::init();
+
+    let starts = Arc::new(AtomicUsize::new(0));
+
+    let sp_ref = Bastion::supervisor(|sp| sp.with_strategy(SupervisionStrategy::PanicForward))
+        .expect("Couldn't create the supervisor.");
+    let starts_inner = starts.clone();
+    let panicking = sp_ref
+        .children(move |children| {
+            children.with_exec(move |ctx: BastionContext| {
+                let starts = starts_inner.clone();
+                async move {
+                    starts.fetch_add(1, Ordering::SeqCst);
+                    msg! { ctx.recv().await?,
+                        ref msg: &'static str => panic!("{}", msg);
+                        _: _ => ();
+                    }
+
+                    Ok(())
+                }
+            })
+        })
+        .expect("Couldn't create the children group.");
+    let sibling = sp_ref
+        .children(|children| {
+            children.with_exec(|ctx: BastionContext| async move {
+                loop {
+                    ctx.recv().await?;
+                }
+            })
+        })
+        .expect("Couldn't create the children group.");
+
+    Bastion::start();
+
+    wait_until(|| starts.load(Ordering::SeqCst) == 1);
+    panicking.broadcast("forwarded").unwrap();
+
+    // The supervisor's whole tree is terminated...
+    wait_until(|| sibling.is_empty() && run!(sp_ref.list_stopped()).is_err());
+    assert!(sibling.is_empty());
+    // ...including the supervisor itself...
+    assert!(run!(sp_ref.list_stopped()).is_err());
+    // ...and the faulted element wasn't restarted.
+    assert_eq!(starts.load(Ordering::SeqCst), 1);
+
+    // The system terminates with the element's panic.
+    match Bastion::block_until_stopped() {
+        SystemExit::Faulted { culprit, panic } => {
+            assert_eq!(culprit.as_ref(), Some(sp_ref.id()));
+            assert_eq!(panic.as_deref(), Some("forwarded"));
+        }
+        exit => panic!("The system didn't fault: {:?}", exit),
+    }
+}
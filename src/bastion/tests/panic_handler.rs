@@ -0,0 +1,58 @@
+use bastion::prelude::*;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex};
+use std::thread;
+
+static PANICKED: AtomicBool = AtomicBool::new(false);
+
+#[test]
+fn panic_handler_receives_child_id() {
+    let config = Config::new().hide_backtraces();
+    Bastion::init_with(config);
+
+    let reported = Arc::new(Mutex::new(None));
+    let reported_inner = reported.clone();
+    Bastion::set_panic_handler(move |id, info| {
+        let message = info
+            .payload()
+            .downcast_ref::<&str>()
+            .map(|msg| msg.to_string());
+        *reported_inner.lock().unwrap() = Some((id, message));
+    });
+
+    let hooked = Arc::new(Mutex::new(Vec::new()));
+    let hooked_inner = hooked.clone();
+    Bastion::with_panic_hook(move |_info, id| {
+        hooked_inner.lock().unwrap().push(id.cloned());
+    });
+
+    Bastion::start();
+
+    let children = Bastion::children(|children| {
+        children.with_exec(|_ctx: BastionContext| async move {
+            if !PANICKED.swap(true, Ordering::SeqCst) {
+                panic!("child panicked");
+            }
+
+            Bastion::stop();
+            Ok(())
+        })
+    })
+    .expect("Couldn't create the children group.");
+    let child_id = children.elems()[0].id().clone();
+
+    Bastion::block_until_stopped();
+
+    let reported = reported.lock().unwrap().take();
+    let (id, message) = reported.expect("The panic handler wasn't called.");
+    assert_eq!(id, child_id);
+    assert_eq!(message.as_deref(), Some("child panicked"));
+
+    // The hook also receives the panics happening outside of
+    // children.
+    thread::spawn(|| panic!("thread panicked"))
+        .join()
+        .unwrap_err();
+    let hooked = hooked.lock().unwrap().clone();
+    assert_eq!(hooked, vec![Some(child_id), None]);
+}
@@ -0,0 +1,74 @@
+mod common;
+
+use bastion::prelude::*;
+use common::wait_until;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex};
+
+#[test]
+fn parent_ref() {
+    Bastion::init();
+
+    let parent_ids = Arc::new(Mutex::new(vec![]));
+    let siblings = Arc::new(AtomicUsize::new(0));
+
+    let parent_ids_inner = parent_ids.clone();
+    let siblings_inner = siblings.clone();
+    let supervisor_ref = Bastion::supervisor(move |sp| {
+        let parent_ids = parent_ids_inner.clone();
+        let siblings = siblings_inner.clone();
+        sp.children(move |children| {
+            children.with_exec(move |ctx: BastionContext| {
+                let parent_ids = parent_ids.clone();
+                let siblings = siblings.clone();
+                async move {
+                    let parent = ctx.parent_ref().expect("No supervisor.");
+                    parent_ids.lock().unwrap().push(parent.id().clone());
+
+                    // The parent can be used to spawn a sibling group.
+                    parent
+                        .children(move |children| {
+                            children.with_exec(move |_| {
+                                let siblings = siblings.clone();
+                                async move {
+                                    siblings.fetch_add(1, Ordering::SeqCst);
+                                    Ok(())
+                                }
+                            })
+                        })
+                        .expect("Couldn't create the sibling group.");
+
+                    Ok(())
+                }
+            })
+        })
+    })
+    .expect("Couldn't create the supervisor.");
+
+    let system_level = Arc::new(Mutex::new(None));
+    let system_level_inner = system_level.clone();
+    Bastion::children(move |children| {
+        let system_level = system_level_inner.clone();
+        children.with_exec(move |ctx: BastionContext| {
+            let system_level = system_level.clone();
+            async move {
+                *system_level.lock().unwrap() = Some(ctx.parent_ref().is_none());
+                Ok(())
+            }
+        })
+    })
+    .expect("Couldn't create the children group.");
+
+    Bastion::start();
+
+    wait_until(|| siblings.load(Ordering::SeqCst) == 1 && system_level.lock().unwrap().is_some());
+    assert_eq!(siblings.load(Ordering::SeqCst), 1);
+    assert_eq!(
+        *parent_ids.lock().unwrap(),
+        vec![supervisor_ref.id().clone()]
+    );
+    assert_eq!(*system_level.lock().unwrap(), Some(true));
+
+    Bastion::stop();
+    Bastion::block_until_stopped();
+}
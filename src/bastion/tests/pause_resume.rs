@@ -0,0 +1,67 @@
+mod common;
+
+use bastion::prelude::*;
+use common::wait_until;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+use std::thread;
+use std::time::Duration;
+
+#[test]
+fn pause_resume() {
+    Bastion::init();
+    Bastion::start();
+
+    let started = Arc::new(AtomicUsize::new(0));
+    let received = Arc::new(AtomicUsize::new(0));
+
+    let (started_inner, received_inner) = (started.clone(), received.clone());
+    let children_ref = Bastion::children(move |children| {
+        let (started, received) = (started_inner.clone(), received_inner.clone());
+        children
+            .with_redundancy(2)
+            .with_exec(move |ctx: BastionContext| {
+                let (started, received) = (started.clone(), received.clone());
+                async move {
+                    started.fetch_add(1, Ordering::SeqCst);
+                    loop {
+                        ctx.recv().await?;
+                        received.fetch_add(1, Ordering::SeqCst);
+                    }
+                }
+            })
+    })
+    .expect("Couldn't create the children group.");
+
+    wait_until(|| started.load(Ordering::SeqCst) == 2);
+    children_ref
+        .pause()
+        .expect("Couldn't pause the children group.");
+    for _ in 0..3 {
+        children_ref.broadcast("work").unwrap();
+    }
+
+    // The messages pile up without being received...
+    wait_until(|| run!(children_ref.mailbox_lens()) == Ok(vec![3, 3]));
+    assert_eq!(run!(children_ref.mailbox_lens()), Ok(vec![3, 3]));
+    thread::sleep(Duration::from_millis(100));
+    assert_eq!(received.load(Ordering::SeqCst), 0);
+
+    // ...until the elements get resumed.
+    children_ref
+        .resume()
+        .expect("Couldn't resume the children group.");
+    wait_until(|| received.load(Ordering::SeqCst) == 6);
+    assert_eq!(received.load(Ordering::SeqCst), 6);
+    assert_eq!(run!(children_ref.mailbox_lens()), Ok(vec![0, 0]));
+
+    // A stopped children group can't be paused.
+    children_ref.stop().unwrap();
+    wait_until(|| children_ref.is_empty());
+    thread::sleep(Duration::from_millis(100));
+    assert!(children_ref.pause().is_err());
+    assert!(children_ref.resume().is_err());
+
+    Bastion::stop();
+    Bastion::block_until_stopped();
+}
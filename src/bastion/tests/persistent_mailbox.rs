@@ -0,0 +1,109 @@
+mod common;
+
+use bastion::prelude::*;
+use common::wait_until;
+use std::collections::BTreeMap;
+use std::sync::{Arc, Mutex};
+
+#[derive(Debug, Default)]
+struct MemoryStore {
+    msgs: Mutex<BTreeMap<u64, String>>,
+    next_key: Mutex<u64>,
+}
+
+impl MemoryStore {
+    fn insert(&self, msg: String) -> u64 {
+        let mut next_key = self.next_key.lock().unwrap();
+        let key = *next_key;
+        *next_key += 1;
+        self.msgs.lock().unwrap().insert(key, msg);
+        key
+    }
+}
+
+impl MailboxStore for MemoryStore {
+    fn append(&self, msg: &Msg) -> Option<u64> {
+        let msg = msg.peek::<String>()?.clone();
+        Some(self.insert(msg))
+    }
+
+    fn mark_processed(&self, key: u64) {
+        self.msgs.lock().unwrap().remove(&key);
+    }
+
+    fn drain(&self) -> Vec<Msg> {
+        let msgs = std::mem::take(&mut *self.msgs.lock().unwrap());
+        msgs.into_values().map(Msg::tell).collect()
+    }
+}
+
+#[test]
+fn persistent_mailbox() {
+    Bastion::init();
+
+    // Messages left unprocessed by a previous run...
+    let store = Arc::new(MemoryStore::default());
+    store.insert("first".to_string());
+    store.insert("second".to_string());
+
+    let received = Arc::new(Mutex::new(vec![]));
+
+    let store_inner = store.clone();
+    let received_inner = received.clone();
+    let children_ref = Bastion::children(move |children| {
+        let received = received_inner.clone();
+        children
+            .with_persistent_mailbox(store_inner.clone())
+            .with_exec(move |ctx: BastionContext| {
+                let received = received.clone();
+                async move {
+                    loop {
+                        msg! { ctx.recv().await?,
+                            msg: String => received.lock().unwrap().push(msg);
+                            ref msg: String => received.lock().unwrap().push(msg.clone());
+                            _: _ => ();
+                        }
+                    }
+                }
+            })
+    })
+    .expect("Couldn't create the children group.");
+
+    Bastion::start();
+
+    children_ref.broadcast("third".to_string()).unwrap();
+
+    wait_until(|| received.lock().unwrap().len() == 3);
+    assert_eq!(*received.lock().unwrap(), vec!["first", "second", "third"]);
+    // The processed messages were removed from the store.
+    wait_until(|| store.msgs.lock().unwrap().is_empty());
+    assert!(store.msgs.lock().unwrap().is_empty());
+
+    Bastion::stop();
+    Bastion::block_until_stopped();
+}
+
+#[cfg(feature = "sled-mailbox")]
+#[test]
+fn sled_mailbox_store() {
+    use bastion::mailbox_store::SledMailboxStore;
+
+    let db = sled::Config::new().temporary(true).open().unwrap();
+    let store = SledMailboxStore::<String>::new(db.open_tree("mailbox").unwrap());
+
+    let first = store.append(&Msg::tell("first".to_string())).unwrap();
+    store.append(&Msg::tell("second".to_string())).unwrap();
+    // Messages of other types aren't stored.
+    assert!(store.append(&Msg::tell(42usize)).is_none());
+    store.mark_processed(first);
+
+    // Reopening the store keeps the unprocessed messages.
+    let store = SledMailboxStore::<String>::new(db.open_tree("mailbox").unwrap());
+    let msgs: Vec<_> = store
+        .drain()
+        .into_iter()
+        .map(|msg| msg.downcast::<String>().unwrap())
+        .collect();
+    assert_eq!(msgs, vec!["second"]);
+    assert!(store.drain().is_empty());
+}
@@ -0,0 +1,87 @@
+mod common;
+
+use bastion::executor::BastionExecutor;
+use bastion::prelude::*;
+use common::wait_until;
+use futures::future::{BoxFuture, LocalBoxFuture};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex};
+use std::thread;
+use std::time::Duration;
+
+// Runs each future on a new thread, counting them (every future
+// polling a process once).
+#[derive(Debug, Clone, Default)]
+struct CountingExecutor {
+    polls: Arc<AtomicUsize>,
+}
+
+impl CountingExecutor {
+    // Returns the number of polls once the tree settled.
+    fn settled_polls(&self) -> usize {
+        thread::sleep(Duration::from_millis(100));
+        self.polls.load(Ordering::SeqCst)
+    }
+}
+
+impl BastionExecutor for CountingExecutor {
+    fn spawn(&self, future: BoxFuture<'static, ()>) {
+        self.polls.fetch_add(1, Ordering::SeqCst);
+        thread::spawn(move || futures::executor::block_on(future));
+    }
+
+    fn spawn_blocking(&self, future: BoxFuture<'static, ()>) {
+        self.spawn(future)
+    }
+
+    fn block_on(&self, future: LocalBoxFuture<'_, ()>) {
+        futures::executor::block_on(future)
+    }
+}
+
+#[test]
+fn poll_budget() {
+    let executor = CountingExecutor::default();
+    Bastion::init_with(Config::new().with_executor(executor.clone()));
+    Bastion::start();
+
+    let received = Arc::new(Mutex::new(Vec::new()));
+    let received_inner = received.clone();
+    let children_ref = Bastion::children(move |children| {
+        let received = received_inner.clone();
+        children
+            .with_poll_budget(10)
+            .with_exec(move |ctx: BastionContext| {
+                let received = received.clone();
+                async move {
+                    loop {
+                        msg! { ctx.recv().await?,
+                            ref msg: usize => received.lock().unwrap().push(*msg);
+                            _: _ => ();
+                        }
+                    }
+                }
+            })
+    })
+    .expect("Couldn't create the children group.");
+    wait_until(|| Bastion::num_actors() == 1);
+
+    // The messages pile up in the element's mailbox while paused...
+    children_ref.pause().unwrap();
+    for i in 0..100usize {
+        children_ref.broadcast(i).unwrap();
+    }
+    wait_until(|| run!(children_ref.mailbox_lens()) == Ok(vec![100]));
+    let paused = executor.settled_polls();
+    assert!(received.lock().unwrap().is_empty());
+
+    // ...and once resumed, the element yields every ten of them,
+    // still receiving all of them in order.
+    children_ref.resume().unwrap();
+    wait_until(|| received.lock().unwrap().len() == 100);
+    assert_eq!(*received.lock().unwrap(), (0..100).collect::<Vec<_>>());
+    assert!(executor.settled_polls() >= paused + 10);
+
+    Bastion::stop();
+    assert_eq!(Bastion::block_until_stopped(), SystemExit::Stopped);
+}
@@ -0,0 +1,107 @@
+mod common;
+
+use bastion::prelude::*;
+use common::wait_until;
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex};
+use std::task::Poll;
+use std::time::{Duration, Instant};
+
+// Records how many steps the busy elements took when the first
+// element got restarted.
+#[derive(Debug, Default)]
+struct RestartLogger {
+    steps: Arc<AtomicUsize>,
+    restarted: Arc<Mutex<Option<usize>>>,
+}
+
+impl BastionLogger for RestartLogger {
+    fn log_restart(&self, _id: &BastionId) {
+        let steps = self.steps.load(Ordering::SeqCst);
+        self.restarted.lock().unwrap().get_or_insert(steps);
+    }
+}
+
+// Spins for the given duration, then yields for the other
+// processes to run.
+async fn busy(duration: Duration) {
+    let start = Instant::now();
+    while start.elapsed() < duration {}
+
+    let mut yielded = false;
+    futures::future::poll_fn(|cx| {
+        if yielded {
+            return Poll::Ready(());
+        }
+
+        yielded = true;
+        cx.waker().wake_by_ref();
+        Poll::Pending
+    })
+    .await
+}
+
+#[test]
+fn restart_under_load() {
+    let logger = RestartLogger::default();
+    let (steps, restarted) = (logger.steps.clone(), logger.restarted.clone());
+    Bastion::with_logger(Box::new(logger));
+
+    let runtime = BastionRuntime::new(Config::new());
+
+    let started = Arc::new(AtomicUsize::new(0));
+    let done = Arc::new(AtomicBool::new(false));
+    let panicked = Arc::new(Mutex::new(None));
+
+    let (started_inner, done_inner) = (started.clone(), done.clone());
+    let (steps_inner, panicked_inner) = (steps.clone(), panicked.clone());
+    runtime
+        .children(move |children| {
+            let (started, done) = (started_inner.clone(), done_inner.clone());
+            let (steps, panicked) = (steps_inner.clone(), panicked_inner.clone());
+            children
+                .with_redundancy(20)
+                .with_exec(move |_ctx: BastionContext| {
+                    let (started, done) = (started.clone(), done.clone());
+                    let (steps, panicked) = (steps.clone(), panicked.clone());
+                    async move {
+                        let faulty = started.fetch_add(1, Ordering::SeqCst) == 0;
+                        // Bounded, in case the restart never happens.
+                        for i in 0..200 {
+                            if done.load(Ordering::SeqCst) {
+                                break;
+                            }
+
+                            // The first element panics once all the
+                            // elements keep the pool busy.
+                            if faulty && i == 5 {
+                                *panicked.lock().unwrap() = Some(steps.load(Ordering::SeqCst));
+                                panic!("Faulty element.");
+                            }
+
+                            busy(Duration::from_millis(5)).await;
+                            steps.fetch_add(1, Ordering::SeqCst);
+                        }
+
+                        Ok(())
+                    }
+                })
+        })
+        .expect("Couldn't create the children group.");
+    runtime.start();
+
+    wait_until(|| restarted.lock().unwrap().is_some());
+    done.store(true, Ordering::SeqCst);
+
+    // The supervisors and children groups handling the fault don't
+    // wait for the 19 other busy elements to each take a step.
+    let panicked = panicked.lock().unwrap().expect("The element didn't panic.");
+    let restarted = restarted
+        .lock()
+        .unwrap()
+        .expect("The element wasn't restarted.");
+    assert!(restarted - panicked < 19);
+
+    runtime.stop();
+    runtime.block_until_stopped();
+}
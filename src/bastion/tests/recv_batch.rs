@@ -0,0 +1,53 @@
+mod common;
+
+use bastion::prelude::*;
+use common::wait_until;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+#[test]
+fn recv_batch() {
+    Bastion::init();
+
+    let batches = Arc::new(Mutex::new(vec![]));
+
+    let batches_inner = batches.clone();
+    let children_ref = Bastion::children(move |children| {
+        let batches = batches_inner.clone();
+        children.with_exec(move |ctx: BastionContext| {
+            let batches = batches.clone();
+            async move {
+                for _ in 0..3 {
+                    let started = Instant::now();
+                    let batch: Vec<usize> = ctx
+                        .recv_batch(2, Duration::from_millis(50))
+                        .await
+                        .into_iter()
+                        .filter_map(|msg| msg.msg().peek::<usize>().copied())
+                        .collect();
+                    // The deadline is never exceeded by much.
+                    assert!(started.elapsed() < Duration::from_secs(1));
+                    batches.lock().unwrap().push(batch);
+                }
+
+                Ok(())
+            }
+        })
+    })
+    .expect("Couldn't create the children group.");
+
+    // The messages are received before the element's future is
+    // first polled.
+    for i in 0..3usize {
+        children_ref.broadcast(i).unwrap();
+    }
+    Bastion::start();
+
+    wait_until(|| batches.lock().unwrap().len() == 3);
+    // Full batch, then partial batch once the deadline elapsed,
+    // then an empty one.
+    assert_eq!(*batches.lock().unwrap(), vec![vec![0, 1], vec![2], vec![]]);
+
+    Bastion::stop();
+    Bastion::block_until_stopped();
+}
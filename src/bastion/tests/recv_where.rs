@@ -0,0 +1,76 @@
+mod common;
+
+use bastion::prelude::*;
+use common::wait_until;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+#[derive(Debug)]
+struct Reply(usize);
+
+#[test]
+fn recv_where() {
+    Bastion::init();
+
+    let seen = Arc::new(Mutex::new(vec![]));
+
+    let seen_inner = seen.clone();
+    let children_ref = Bastion::children(move |children| {
+        let seen = seen_inner.clone();
+        children.with_exec(move |ctx: BastionContext| {
+            let seen = seen.clone();
+            async move {
+                let timed_out = ctx
+                    .recv_where_timeout(|msg| msg.is::<bool>(), Duration::from_millis(10))
+                    .await
+                    .is_err();
+                seen.lock()
+                    .unwrap()
+                    .push(format!("timed out: {}", timed_out));
+
+                let reply = ctx
+                    .recv_where(|msg| msg.peek::<Reply>().map(|r| r.0 == 42).unwrap_or(false))
+                    .await?;
+                msg! { reply,
+                    ref reply: Reply => seen.lock().unwrap().push(format!("{:?}", reply));
+                    _: _ => ();
+                }
+
+                // The skipped messages are received in order.
+                while let Some(msg) = ctx.try_recv().await {
+                    msg! { msg,
+                        ref msg: &'static str => seen.lock().unwrap().push(msg.to_string());
+                        ref reply: Reply => seen.lock().unwrap().push(format!("{:?}", reply));
+                        _: _ => ();
+                    }
+                }
+
+                Ok(())
+            }
+        })
+    })
+    .expect("Couldn't create the children group.");
+
+    // The messages are received before the element's future is
+    // first polled.
+    children_ref.broadcast("first").unwrap();
+    children_ref.broadcast(Reply(41)).unwrap();
+    children_ref.broadcast(Reply(42)).unwrap();
+    children_ref.broadcast("second").unwrap();
+    Bastion::start();
+
+    wait_until(|| seen.lock().unwrap().len() == 5);
+    assert_eq!(
+        *seen.lock().unwrap(),
+        vec![
+            "timed out: true",
+            "Reply(42)",
+            "first",
+            "Reply(41)",
+            "second"
+        ]
+    );
+
+    Bastion::stop();
+    Bastion::block_until_stopped();
+}
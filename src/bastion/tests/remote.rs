@@ -0,0 +1,105 @@
+#![cfg(feature = "remote")]
+mod common;
+
+use bastion::codec::{JsonCodec, SerializableMessage};
+use bastion::prelude::*;
+use bastion::remote::RemoteError;
+use common::wait_until;
+use serde::{Deserialize, Serialize};
+use std::net::{SocketAddr, TcpListener};
+use std::sync::{Arc, Mutex};
+
+#[derive(Debug, Serialize, Deserialize)]
+struct Order {
+    id: u64,
+}
+
+impl SerializableMessage for Order {
+    const TYPE_TAG: &'static str = "shop.Order";
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+struct Catalog {
+    data: String,
+}
+
+impl SerializableMessage for Catalog {
+    const TYPE_TAG: &'static str = "shop.Catalog";
+}
+
+// Returns an address nothing is listening on (for now).
+fn unused_addr() -> SocketAddr {
+    TcpListener::bind("127.0.0.1:0")
+        .unwrap()
+        .local_addr()
+        .unwrap()
+}
+
+#[test]
+fn remote_children() {
+    let server = BastionRuntime::new(Config::new());
+    let received = Arc::new(Mutex::new(Vec::new()));
+    let received_inner = received.clone();
+    server
+        .children(move |children| {
+            let received = received_inner.clone();
+            children
+                .with_name("orders")
+                .with_redundancy(2)
+                .with_exec(move |ctx: BastionContext| {
+                    let received = received.clone();
+                    async move {
+                        loop {
+                            let order: Order = ctx.recv_deserialized(&JsonCodec).await?;
+                            received.lock().unwrap().push(order.id);
+                        }
+                    }
+                })
+        })
+        .expect("Couldn't create the children group.");
+    server.start();
+
+    let client = BastionRuntime::new(Config::new());
+    client.start();
+
+    // Messages sent while the peer is unreachable fail...
+    let addr = unused_addr();
+    let orders = client.remote_children(addr, "orders");
+    assert!(matches!(
+        run!(orders.broadcast(Order { id: 0 })),
+        Err(RemoteError::Unreachable(_))
+    ));
+
+    // ...until it can be connected to.
+    let addr = server
+        .serve_remote(addr, &["orders"])
+        .expect("Couldn't listen for remote messages.");
+    run!(orders.broadcast(Order { id: 1 })).unwrap();
+    wait_until(|| received.lock().unwrap().len() == 2);
+    run!(orders.tell(Order { id: 2 })).unwrap();
+    wait_until(|| received.lock().unwrap().len() == 3);
+    assert_eq!(*received.lock().unwrap(), vec![1, 1, 2]);
+
+    // Messages for groups that aren't exposed aren't delivered.
+    run!(client
+        .remote_children(addr, "unexposed")
+        .broadcast(Order { id: 3 }))
+    .unwrap();
+    run!(orders.tell(Order { id: 4 })).unwrap();
+    wait_until(|| received.lock().unwrap().len() == 4);
+    assert_eq!(*received.lock().unwrap(), vec![1, 1, 2, 4]);
+
+    // Messages that are too large aren't sent.
+    let catalog = Catalog {
+        data: "x".repeat(16 * 1024 * 1024),
+    };
+    assert!(matches!(
+        run!(orders.broadcast(catalog)),
+        Err(RemoteError::TooLarge(_))
+    ));
+
+    client.stop();
+    client.block_until_stopped();
+    server.stop();
+    server.block_until_stopped();
+}
@@ -0,0 +1,86 @@
+mod common;
+
+use bastion::prelude::*;
+use common::wait_until;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex};
+
+#[test]
+fn restart_child_relaunches_the_element() {
+    Bastion::init();
+    Bastion::start();
+
+    let starts = Arc::new(AtomicUsize::new(0));
+    let received = Arc::new(AtomicUsize::new(0));
+    let before_restart = Arc::new(AtomicUsize::new(0));
+    let after_restart = Arc::new(AtomicUsize::new(0));
+    let pids = Arc::new(Mutex::new(vec![]));
+
+    let (starts_inner, received_inner) = (starts.clone(), received.clone());
+    let pids_inner = pids.clone();
+    let (before_inner, after_inner) = (before_restart.clone(), after_restart.clone());
+    let sp_ref = Bastion::supervisor(|sp| sp).expect("Couldn't create the supervisor.");
+    let children_ref = sp_ref
+        .children(move |children| {
+            let (before, after) = (before_inner.clone(), after_inner.clone());
+            let callbacks = Callbacks::new()
+                .with_before_restart(move || {
+                    before.fetch_add(1, Ordering::SeqCst);
+                })
+                .with_after_restart(move || {
+                    after.fetch_add(1, Ordering::SeqCst);
+                });
+
+            let (starts, received) = (starts_inner.clone(), received_inner.clone());
+            let pids = pids_inner.clone();
+            children
+                .with_callbacks(callbacks)
+                .with_exec(move |ctx: BastionContext| {
+                    let (starts, received) = (starts.clone(), received.clone());
+                    let pids = pids.clone();
+                    async move {
+                        pids.lock().unwrap().push(ctx.pid());
+                        starts.fetch_add(1, Ordering::SeqCst);
+                        loop {
+                            msg! { ctx.recv().await?,
+                                ref _msg: &'static str => {
+                                    received.fetch_add(1, Ordering::SeqCst);
+                                };
+                                _: _ => ();
+                            }
+                        }
+                    }
+                })
+        })
+        .expect("Couldn't create the children group.");
+
+    wait_until(|| starts.load(Ordering::SeqCst) == 1);
+
+    let id = children_ref.elems()[0].id().clone();
+    sp_ref
+        .restart_child(id)
+        .expect("Couldn't send the message.");
+    wait_until(|| starts.load(Ordering::SeqCst) == 2);
+
+    assert_eq!(starts.load(Ordering::SeqCst), 2);
+    assert_eq!(before_restart.load(Ordering::SeqCst), 1);
+    assert_eq!(after_restart.load(Ordering::SeqCst), 1);
+    assert_eq!(children_ref.len(), 1);
+
+    // The restarted element got a new pid, which differs from the
+    // ones of its group and supervisor.
+    let pids = pids.lock().unwrap().clone();
+    assert_ne!(pids[0], pids[1]);
+    assert!(!pids.contains(&children_ref.pid()));
+    assert!(!pids.contains(&sp_ref.pid()));
+    assert_ne!(children_ref.pid(), sp_ref.pid());
+    assert!(format!("{:?}", children_ref).contains(&format!("pid: {}", children_ref.pid())));
+
+    // The restarted element still receives the group's messages.
+    children_ref.broadcast("ping").unwrap();
+    wait_until(|| received.load(Ordering::SeqCst) == 1);
+    assert_eq!(received.load(Ordering::SeqCst), 1);
+
+    Bastion::stop();
+    Bastion::block_until_stopped();
+}
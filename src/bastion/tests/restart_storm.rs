@@ -0,0 +1,73 @@
+mod common;
+
+use bastion::prelude::*;
+use common::wait_until;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex};
+use std::thread;
+use std::time::Duration;
+
+#[test]
+fn restart_storm() {
+    let runtime = BastionRuntime::new(Config::new());
+    let reports = Arc::new(Mutex::new(Vec::new()));
+    let reports_inner = reports.clone();
+    runtime.on_restart_storm(3, Duration::from_secs(60), move |report| {
+        reports_inner.lock().unwrap().push(report);
+    });
+
+    let starts = Arc::new(AtomicUsize::new(0));
+    let starts_inner = starts.clone();
+    let children_ref = runtime
+        .children(move |children| {
+            let starts = starts_inner.clone();
+            children
+                .with_name("flaky")
+                .with_exec(move |ctx: BastionContext| {
+                    let starts = starts.clone();
+                    async move {
+                        starts.fetch_add(1, Ordering::SeqCst);
+                        msg! { ctx.recv().await?,
+                            ref _msg: &'static str => Err(());
+                            _: _ => Ok(());
+                        }
+                    }
+                })
+        })
+        .expect("Couldn't create the children group.");
+    runtime.start();
+    wait_until(|| starts.load(Ordering::SeqCst) == 1);
+
+    // Restarting up to the limit isn't a storm...
+    for restarts in 1..=3 {
+        children_ref.broadcast("fault").unwrap();
+        wait_until(|| starts.load(Ordering::SeqCst) == restarts + 1);
+    }
+    thread::sleep(Duration::from_millis(100));
+    assert!(reports.lock().unwrap().is_empty());
+
+    // ...while going beyond it is, and only gets reported once.
+    for restarts in 4..=6 {
+        children_ref.broadcast("fault").unwrap();
+        wait_until(|| starts.load(Ordering::SeqCst) == restarts + 1);
+    }
+    wait_until(|| !reports.lock().unwrap().is_empty());
+    thread::sleep(Duration::from_millis(100));
+    let reports = reports.lock().unwrap();
+    assert_eq!(reports.len(), 1);
+    let report = &reports[0];
+    assert_eq!(report.restarts, 4);
+    assert_eq!(report.window, Duration::from_secs(60));
+    assert_eq!(
+        report.offenders,
+        vec![StormOffender {
+            id: children_ref.id().clone(),
+            kind: EntityKind::Children,
+            name: Some("flaky".to_string()),
+            restarts: 4,
+        }]
+    );
+
+    runtime.stop();
+    runtime.block_until_stopped();
+}
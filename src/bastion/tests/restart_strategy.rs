@@ -1,5 +1,11 @@
-use bastion::supervisor::{ActorRestartStrategy, RestartPolicy, RestartStrategy};
-use std::time::Duration;
+mod common;
+
+use bastion::prelude::*;
+use common::wait_until;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex};
+use std::thread;
+use std::time::{Duration, Instant};
 
 #[test]
 fn check_default_values() {
@@ -43,3 +49,154 @@ fn override_restart_strategy_and_policy() {
     assert_eq!(restart_strategy.restart_policy(), policy);
     assert_eq!(restart_strategy.strategy(), strategy);
 }
+
+#[test]
+fn on_error_restart_policy() {
+    let runtime = BastionRuntime::new(Config::new());
+
+    let (faulty, sibling) = (Arc::new(AtomicUsize::new(0)), Arc::new(AtomicUsize::new(0)));
+    let (faulty_inner, sibling_inner) = (faulty.clone(), sibling.clone());
+    let children_ref = Arc::new(Mutex::new(None));
+    let children_ref_inner = children_ref.clone();
+    let escalations = Arc::new(AtomicUsize::new(0));
+    let escalations_inner = escalations.clone();
+    runtime
+        .supervisor(move |sp| {
+            let (faulty, sibling) = (faulty_inner.clone(), sibling_inner.clone());
+            let children_ref = children_ref_inner.clone();
+            let escalations = escalations_inner.clone();
+            let callbacks = Callbacks::new().with_before_restart(move || {
+                escalations.fetch_add(1, Ordering::SeqCst);
+            });
+            let sp = sp
+                .with_restart_policy(RestartPolicy::OnError)
+                .with_callbacks(callbacks)
+                .children(move |children| {
+                    children.with_exec(move |ctx: BastionContext| {
+                        let sibling = sibling.clone();
+                        async move {
+                            sibling.fetch_add(1, Ordering::SeqCst);
+                            loop {
+                                ctx.recv().await?;
+                            }
+                        }
+                    })
+                });
+            let faulty_ref = sp.children_ref(move |children| {
+                children.with_exec(move |ctx: BastionContext| {
+                    let faulty = faulty.clone();
+                    async move {
+                        faulty.fetch_add(1, Ordering::SeqCst);
+                        msg! { ctx.recv().await?,
+                            ref msg: &'static str => {
+                                if *msg == "panic" {
+                                    panic!("panic");
+                                }
+                            };
+                            _: _ => ();
+                        }
+                        Err(())
+                    }
+                })
+            });
+            *children_ref.lock().unwrap() = Some(faulty_ref);
+            sp
+        })
+        .expect("Couldn't create the supervisor.");
+    runtime.start();
+    wait_until(|| faulty.load(Ordering::SeqCst) == 1 && sibling.load(Ordering::SeqCst) == 1);
+
+    // Returning an error only restarts the element...
+    let faulty_ref = children_ref.lock().unwrap().clone().unwrap();
+    faulty_ref.broadcast("error").unwrap();
+    wait_until(|| faulty.load(Ordering::SeqCst) == 2);
+    thread::sleep(Duration::from_millis(100));
+    assert_eq!(faulty.load(Ordering::SeqCst), 2);
+    assert_eq!(sibling.load(Ordering::SeqCst), 1);
+    assert_eq!(escalations.load(Ordering::SeqCst), 0);
+
+    // ...while panicking escalates, restarting the supervisor.
+    faulty_ref.broadcast("panic").unwrap();
+    wait_until(|| escalations.load(Ordering::SeqCst) == 1);
+    assert_eq!(escalations.load(Ordering::SeqCst), 1);
+
+    runtime.stop();
+    assert_eq!(runtime.block_until_stopped(), SystemExit::Stopped);
+}
+
+// Returns how long it took for the element of a supervisor
+// added after a faulty children group to get restarted, when
+// using a one-for-all strategy with backoff.
+fn subtree_restart_delay(guarantee: OrderGuarantee) -> Duration {
+    let runtime = BastionRuntime::new(Config::new());
+
+    let failed = Arc::new(Mutex::new(None));
+    let started = Arc::new(Mutex::new(Vec::new()));
+    let (failed_inner, started_inner) = (failed.clone(), started.clone());
+    runtime
+        .supervisor(move |sp| {
+            let (failed, started) = (failed_inner.clone(), started_inner.clone());
+            let restart_strategy = RestartStrategy::default().with_actor_restart_strategy(
+                ActorRestartStrategy::LinearBackOff {
+                    timeout: Duration::from_secs(1),
+                },
+            );
+            sp.with_strategy(SupervisionStrategy::OneForAll)
+                .with_restart_strategy(restart_strategy)
+                .with_order_guarantee(guarantee)
+                .children(move |children| {
+                    children.with_exec(move |ctx: BastionContext| {
+                        let failed = failed.clone();
+                        async move {
+                            let first_start = {
+                                let mut failed = failed.lock().unwrap();
+                                let first_start = failed.is_none();
+                                failed.get_or_insert_with(Instant::now);
+                                first_start
+                            };
+                            if first_start {
+                                return Err(());
+                            }
+
+                            loop {
+                                ctx.recv().await?;
+                            }
+                        }
+                    })
+                })
+                .supervisor(move |sp| {
+                    sp.children(move |children| {
+                        children.with_exec(move |ctx: BastionContext| {
+                            started.lock().unwrap().push(Instant::now());
+                            async move {
+                                loop {
+                                    ctx.recv().await?;
+                                }
+                            }
+                        })
+                    })
+                })
+        })
+        .expect("Couldn't create the supervisor.");
+    runtime.start();
+    wait_until(|| started.lock().unwrap().len() == 2);
+
+    let failed = failed.lock().unwrap().unwrap();
+    let restarted = started.lock().unwrap()[1];
+
+    runtime.stop();
+    runtime.block_until_stopped();
+
+    restarted - failed
+}
+
+#[test]
+fn order_guarantee() {
+    // The supervisor is restarted without waiting for the faulty
+    // children group's element, which gets restarted after a second...
+    assert!(subtree_restart_delay(OrderGuarantee::None) < Duration::from_millis(500));
+    assert!(subtree_restart_delay(OrderGuarantee::PerActor) < Duration::from_millis(500));
+
+    // ...unless all the restarts are ordered.
+    assert!(subtree_restart_delay(OrderGuarantee::Global) >= Duration::from_secs(1));
+}
@@ -0,0 +1,152 @@
+mod common;
+
+use bastion::prelude::*;
+use common::wait_until;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+use std::thread;
+use std::time::Duration;
+
+#[test]
+fn restart_window() {
+    Bastion::init();
+
+    let starts = Arc::new(AtomicUsize::new(0));
+    let escalations = Arc::new(AtomicUsize::new(0));
+
+    let starts_inner = starts.clone();
+    let escalations_inner = escalations.clone();
+    Bastion::supervisor(move |sp| {
+        let starts = starts_inner.clone();
+        let escalations = escalations_inner.clone();
+        let callbacks = Callbacks::new().with_before_restart(move || {
+            escalations.fetch_add(1, Ordering::SeqCst);
+        });
+
+        sp.with_restart_window(RestartWindow::new(2, Duration::from_secs(60)))
+            .with_callbacks(callbacks)
+            .children(move |children| {
+                children.with_exec(move |_| {
+                    let starts = starts.clone();
+                    async move {
+                        starts.fetch_add(1, Ordering::SeqCst);
+                        // Always faulting...
+                        Err(())
+                    }
+                })
+            })
+    })
+    .expect("Couldn't create the supervisor.");
+
+    Bastion::start();
+
+    wait_until(|| escalations.load(Ordering::SeqCst) > 0);
+    assert!(escalations.load(Ordering::SeqCst) > 0);
+    // The first start and the two restarts within the window
+    // happened before the supervisor escalated.
+    assert!(starts.load(Ordering::SeqCst) >= 3);
+
+    Bastion::stop();
+    Bastion::block_until_stopped();
+}
+
+#[test]
+fn restart_window_slides() {
+    let mut window = RestartWindow::new(1, Duration::from_millis(50));
+
+    assert!(window.record_and_check());
+    assert!(!window.record_and_check());
+
+    // Once the window elapsed, the previous restarts aren't counted.
+    thread::sleep(Duration::from_millis(60));
+    assert!(window.record_and_check());
+}
+
+// Returns a children group whose element faults when receiving a
+// message and one counting the messages it receives, supervised
+// by a supervisor escalating as soon as the first one faults.
+fn orphaning(
+    runtime: &BastionRuntime,
+    policy: OrphanPolicy,
+    received: Arc<AtomicUsize>,
+) -> (ChildrenRef, ChildrenRef) {
+    let sp_ref = runtime
+        .supervisor(|sp| {
+            sp.with_restart_window(RestartWindow::new(0, Duration::from_secs(60)))
+                .with_orphan_policy(policy)
+        })
+        .expect("Couldn't create the supervisor.");
+
+    let faulty = sp_ref
+        .children(|children| {
+            children.with_exec(|ctx: BastionContext| async move {
+                ctx.recv().await?;
+                Err(())
+            })
+        })
+        .expect("Couldn't create the children group.");
+    let orphan = sp_ref
+        .children(move |children| {
+            children.with_exec(move |ctx: BastionContext| {
+                let received = received.clone();
+                async move {
+                    loop {
+                        ctx.recv().await?;
+                        received.fetch_add(1, Ordering::SeqCst);
+                    }
+                }
+            })
+        })
+        .expect("Couldn't create the children group.");
+
+    (faulty, orphan)
+}
+
+#[test]
+fn restart_window_orphans() {
+    let runtime = BastionRuntime::new(Config::new());
+    let received = Arc::new(AtomicUsize::new(0));
+    let (faulty, killed) = orphaning(&runtime, OrphanPolicy::Kill, received.clone());
+    let backup = runtime
+        .supervisor(|sp| sp)
+        .expect("Couldn't create the supervisor.");
+    let (adopted_faulty, adopted) = orphaning(
+        &runtime,
+        OrphanPolicy::Adopt(backup.clone()),
+        received.clone(),
+    );
+    let (detached_faulty, detached) = orphaning(&runtime, OrphanPolicy::Detach, received.clone());
+
+    runtime.start();
+    wait_until(|| runtime.num_actors() == 6);
+
+    faulty.broadcast("fault").unwrap();
+    adopted_faulty.broadcast("fault").unwrap();
+    detached_faulty.broadcast("fault").unwrap();
+
+    // By default, the orphans are killed along with their
+    // supervisor...
+    wait_until(|| killed.is_empty());
+    assert!(killed.is_empty());
+
+    // ...but they can also keep running, supervised by another
+    // supervisor...
+    adopted.broadcast("ping").unwrap();
+    detached.broadcast("ping").unwrap();
+    wait_until(|| received.load(Ordering::SeqCst) == 2);
+    assert_eq!(received.load(Ordering::SeqCst), 2);
+    assert!(!adopted.is_empty());
+
+    backup.stop().unwrap();
+    wait_until(|| adopted.is_empty());
+    assert!(adopted.is_empty());
+    assert!(!detached.is_empty());
+
+    // ...or not being supervised at all.
+    runtime.stop();
+    runtime.block_until_stopped();
+    detached.broadcast("ping").unwrap();
+    wait_until(|| received.load(Ordering::SeqCst) == 3);
+    assert_eq!(received.load(Ordering::SeqCst), 3);
+    detached.kill().unwrap();
+}
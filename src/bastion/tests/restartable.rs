@@ -0,0 +1,53 @@
+mod common;
+
+use bastion::prelude::*;
+use common::wait_until;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+
+fn spawn_counter(received: Arc<AtomicUsize>) -> ChildrenRef {
+    Bastion::children(move |children| {
+        let received = received.clone();
+        children.with_exec(move |ctx: BastionContext| {
+            let received = received.clone();
+            async move {
+                loop {
+                    ctx.recv().await?;
+                    received.fetch_add(1, Ordering::SeqCst);
+                }
+            }
+        })
+    })
+    .expect("Couldn't create the children group.")
+}
+
+#[test]
+fn restartable() {
+    let received = Arc::new(AtomicUsize::new(0));
+
+    // Every cycle starts from scratch, whether the system gets
+    // initialized again or only started again.
+    let mut previous: Option<ChildrenRef> = None;
+    for cycle in 0..3 {
+        if cycle != 1 {
+            Bastion::init();
+        }
+        let children_ref = spawn_counter(received.clone());
+        Bastion::start();
+
+        wait_until(|| Bastion::num_actors() == 1);
+        assert_eq!(Bastion::num_actors(), 1);
+        children_ref.broadcast("ping").unwrap();
+        wait_until(|| received.load(Ordering::SeqCst) == cycle + 1);
+        assert_eq!(received.load(Ordering::SeqCst), cycle + 1);
+
+        // The groups of the stopped systems are gone.
+        if let Some(previous) = previous.take() {
+            assert!(previous.broadcast("ping").is_err());
+        }
+
+        Bastion::stop();
+        assert_eq!(Bastion::block_until_stopped(), SystemExit::Stopped);
+        previous = Some(children_ref);
+    }
+}
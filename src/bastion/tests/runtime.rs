@@ -0,0 +1,119 @@
+mod common;
+
+use bastion::prelude::*;
+use common::wait_until;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+use std::thread;
+use std::time::Duration;
+
+fn counting_children(runtime: &BastionRuntime, received: Arc<AtomicUsize>) -> ChildrenRef {
+    runtime
+        .children(move |children| {
+            let received = received.clone();
+            children
+                .with_redundancy(2)
+                .with_exec(move |ctx: BastionContext| {
+                    let received = received.clone();
+                    async move {
+                        loop {
+                            ctx.recv().await?;
+                            received.fetch_add(1, Ordering::SeqCst);
+                        }
+                    }
+                })
+        })
+        .expect("Couldn't create the children group.")
+}
+
+#[test]
+fn isolated_runtimes() {
+    let first = BastionRuntime::new(Config::new());
+    let second = BastionRuntime::new(Config::new());
+    assert_ne!(first.id(), second.id());
+
+    let (first_received, second_received) =
+        (Arc::new(AtomicUsize::new(0)), Arc::new(AtomicUsize::new(0)));
+    let first_ref = counting_children(&first, first_received.clone());
+    let second_ref = counting_children(&second, second_received.clone());
+    assert_eq!(first_ref.runtime_id(), first.id());
+    assert_eq!(second_ref.runtime_id(), second.id());
+
+    let supervisor_ref = second.supervisor(|sp| sp).unwrap();
+    assert_eq!(supervisor_ref.runtime_id(), second.id());
+
+    first.start();
+    second.start();
+    wait_until(|| {
+        first.num_actors() == 2 && second.num_actors() == 2 && second.num_supervisors() == 1
+    });
+    assert_eq!(first.num_actors(), 2);
+    assert_eq!(second.num_actors(), 2);
+    assert_eq!(first.num_supervisors(), 0);
+    assert_eq!(second.num_supervisors(), 1);
+    for elem in first_ref.elems() {
+        assert_eq!(elem.runtime_id(), first.id());
+    }
+
+    // Broadcasting to a runtime only reaches its own elements...
+    first.broadcast("first").unwrap();
+    wait_until(|| first_received.load(Ordering::SeqCst) == 2);
+    thread::sleep(Duration::from_millis(100));
+    assert_eq!(first_received.load(Ordering::SeqCst), 2);
+    assert_eq!(second_received.load(Ordering::SeqCst), 0);
+
+    // ...and stopping it leaves the other one running.
+    first.stop();
+    assert_eq!(first.block_until_stopped(), SystemExit::Stopped);
+    assert_eq!(first.num_actors(), 0);
+    assert_eq!(second.num_actors(), 2);
+
+    second.broadcast("second").unwrap();
+    wait_until(|| second_received.load(Ordering::SeqCst) == 2);
+    assert_eq!(second_received.load(Ordering::SeqCst), 2);
+
+    second.stop();
+    assert_eq!(second.block_until_stopped(), SystemExit::Stopped);
+}
+
+#[test]
+fn foreign_refs_are_rejected() {
+    let first = BastionRuntime::new(Config::new());
+    let second = BastionRuntime::new(Config::new());
+
+    let source = first.supervisor(|sp| sp).unwrap();
+    let children_ref = source.children(|children| children).unwrap();
+    let foreign = second.supervisor(|sp| sp).unwrap();
+    assert!(source.migrate(children_ref.id().clone(), &foreign).is_err());
+
+    let foreign_ref = counting_children(&second, Arc::new(AtomicUsize::new(0)));
+    let foreign_elem = foreign_ref.elems()[0].clone();
+    let results = Arc::new(std::sync::Mutex::new(None));
+    let results_cloned = results.clone();
+    first
+        .children(move |children| {
+            let foreign_elem = foreign_elem.clone();
+            let results = results_cloned.clone();
+            children.with_exec(move |ctx: BastionContext| {
+                let foreign_elem = foreign_elem.clone();
+                let results = results.clone();
+                async move {
+                    *results.lock().unwrap() =
+                        Some((ctx.link(&foreign_elem), ctx.monitor(&foreign_elem)));
+                    ctx.recv().await?;
+                    Ok(())
+                }
+            })
+        })
+        .unwrap();
+
+    first.start();
+    second.start();
+    wait_until(|| results.lock().unwrap().is_some());
+    assert_eq!(*results.lock().unwrap(), Some((Err(()), Err(()))));
+
+    first.stop();
+    second.stop();
+    first.block_until_stopped();
+    second.block_until_stopped();
+}
@@ -0,0 +1,83 @@
+mod common;
+
+use bastion::prelude::*;
+use common::wait_until;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+
+#[derive(Debug)]
+struct Tick;
+
+#[test]
+fn scheduled_messages_reach_every_element() {
+    Bastion::init();
+    Bastion::start();
+
+    let ticks = Arc::new(AtomicUsize::new(0));
+    let ticks_inner = ticks.clone();
+
+    Bastion::children(|children| {
+        children
+            .with_redundancy(2)
+            .with_scheduled_message(Duration::from_millis(10), || Tick)
+            .with_exec(move |ctx: BastionContext| {
+                let ticks = ticks_inner.clone();
+                async move {
+                    loop {
+                        msg! { ctx.recv().await?,
+                            _msg: Tick => {
+                                if ticks.fetch_add(1, Ordering::SeqCst) == 5 {
+                                    Bastion::stop();
+                                }
+                            };
+                            _: _ => ();
+                        }
+                    }
+                }
+            })
+    })
+    .expect("Couldn't create the children group.");
+
+    Bastion::block_until_stopped();
+
+    assert!(ticks.load(Ordering::SeqCst) >= 6);
+}
+
+#[test]
+fn zero_interval_doesnt_starve_the_group() {
+    // Using its own runtime, not to share the default one with the
+    // other test.
+    let runtime = BastionRuntime::new(Config::new());
+    runtime.start();
+
+    let ticks = Arc::new(AtomicUsize::new(0));
+    let ticks_inner = ticks.clone();
+
+    runtime
+        .children(|children| {
+            children
+                .with_scheduled_message(Duration::ZERO, || Tick)
+                .with_exec(move |ctx: BastionContext| {
+                    let ticks = ticks_inner.clone();
+                    async move {
+                        loop {
+                            msg! { ctx.recv().await?,
+                                _msg: Tick => {
+                                    ticks.fetch_add(1, Ordering::SeqCst);
+                                };
+                                _: _ => ();
+                            }
+                        }
+                    }
+                })
+        })
+        .expect("Couldn't create the children group.");
+
+    wait_until(|| ticks.load(Ordering::SeqCst) >= 6);
+    assert!(ticks.load(Ordering::SeqCst) >= 6);
+
+    // The group still handles the message asking it to stop.
+    runtime.stop();
+    assert_eq!(runtime.block_until_stopped(), SystemExit::Stopped);
+}
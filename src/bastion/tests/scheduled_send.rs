@@ -0,0 +1,80 @@
+use bastion::prelude::*;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+use std::thread;
+use std::time::{Duration, Instant};
+
+#[derive(Debug)]
+struct Retry;
+
+#[derive(Debug)]
+struct Cancelled;
+
+#[derive(Debug)]
+struct Delayed;
+
+#[derive(Debug)]
+struct Sent;
+
+#[test]
+fn delayed_messages_are_delivered_unless_cancelled() {
+    Bastion::init();
+    Bastion::start();
+
+    let retries = Arc::new(AtomicUsize::new(0));
+    let delayed = Arc::new(AtomicUsize::new(0));
+    let sent = Arc::new(AtomicUsize::new(0));
+    let (retries_inner, delayed_inner, sent_inner) =
+        (retries.clone(), delayed.clone(), sent.clone());
+
+    let children = Bastion::children(|children| {
+        children.with_exec(move |ctx: BastionContext| {
+            let retries = retries_inner.clone();
+            let delayed = delayed_inner.clone();
+            let sent = sent_inner.clone();
+            async move {
+                ctx.notify_self_after(Duration::from_millis(20), Retry);
+                ctx.notify_self_after(Duration::from_millis(20), Cancelled)
+                    .cancel();
+
+                loop {
+                    msg! { ctx.recv().await?,
+                        _msg: Retry => {
+                            retries.fetch_add(1, Ordering::SeqCst);
+                        };
+                        _msg: Cancelled => panic!("cancelled message delivered");
+                        ref _msg: Delayed => {
+                            delayed.fetch_add(1, Ordering::SeqCst);
+                        };
+                        _msg: Sent => {
+                            sent.fetch_add(1, Ordering::SeqCst);
+                        };
+                        _: _ => ();
+                    }
+                }
+            }
+        })
+    })
+    .expect("Couldn't create the children group.");
+
+    let start = Instant::now();
+    children.tell_after(Duration::from_millis(50), Delayed);
+    let child = &children.elems()[0];
+    child.send_after(Sent, Duration::from_millis(50));
+    child
+        .send_after(Cancelled, Duration::from_millis(50))
+        .cancel();
+
+    while (delayed.load(Ordering::SeqCst) == 0 || sent.load(Ordering::SeqCst) == 0)
+        && start.elapsed() < Duration::from_secs(5)
+    {
+        thread::sleep(Duration::from_millis(5));
+    }
+    assert!(start.elapsed() >= Duration::from_millis(50));
+    assert_eq!(delayed.load(Ordering::SeqCst), 1);
+    assert_eq!(sent.load(Ordering::SeqCst), 1);
+    assert_eq!(retries.load(Ordering::SeqCst), 1);
+
+    Bastion::stop();
+    Bastion::block_until_stopped();
+}
@@ -0,0 +1,36 @@
+use bastion::prelude::*;
+use futures::executor;
+use std::thread;
+use std::time::Duration;
+
+#[test]
+fn send_acked_reports_delivery() {
+    Bastion::init();
+
+    let children = Bastion::children(|children| {
+        children.with_exec(|ctx: BastionContext| async move {
+            loop {
+                ctx.recv().await?;
+            }
+        })
+    })
+    .expect("Couldn't create the children group.");
+
+    // Messages aren't placed into mailboxes before the system starts.
+    let delivery = children.send_acked("early", Duration::from_millis(50));
+    assert_eq!(executor::block_on(delivery), Err(DeliveryError::Timeout));
+
+    Bastion::start();
+
+    let delivery = children.send_acked("delivered", Duration::from_secs(5));
+    assert_eq!(executor::block_on(delivery), Ok(()));
+
+    children.stop().expect("Couldn't stop the children group.");
+    thread::sleep(Duration::from_millis(100));
+
+    let delivery = children.send_acked("too late", Duration::from_secs(5));
+    assert_eq!(executor::block_on(delivery), Err(DeliveryError::Stopped));
+
+    Bastion::stop();
+    Bastion::block_until_stopped();
+}
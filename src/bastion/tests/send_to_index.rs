@@ -0,0 +1,50 @@
+mod common;
+
+use bastion::prelude::*;
+use common::wait_until;
+use std::sync::{Arc, Mutex};
+use std::thread;
+use std::time::Duration;
+
+#[test]
+fn send_to_index() {
+    let runtime = BastionRuntime::new(Config::new());
+
+    let received = Arc::new(Mutex::new(Vec::new()));
+    let received_inner = received.clone();
+    let children_ref = runtime
+        .children(move |children| {
+            let received = received_inner.clone();
+            children
+                .with_redundancy(3)
+                .with_exec(move |ctx: BastionContext| {
+                    let received = received.clone();
+                    async move {
+                        loop {
+                            msg! { ctx.recv().await?,
+                                msg: usize => {
+                                    let id = ctx.current().id().clone();
+                                    received.lock().unwrap().push((id, msg));
+                                };
+                                _: _ => ();
+                            }
+                        }
+                    }
+                })
+        })
+        .expect("Couldn't create the children group.");
+    runtime.start();
+
+    // Only the element at the given position receives the message...
+    children_ref.send_to_index(1, 42usize).unwrap();
+    wait_until(|| !received.lock().unwrap().is_empty());
+    thread::sleep(Duration::from_millis(50));
+    let expected = (children_ref.elems()[1].id().clone(), 42);
+    assert_eq!(*received.lock().unwrap(), vec![expected]);
+
+    // ...and the message is given back when there is none.
+    assert_eq!(children_ref.send_to_index(3, 43usize), Err(43));
+
+    runtime.stop();
+    runtime.block_until_stopped();
+}
@@ -0,0 +1,55 @@
+use bastion::executor::ProcStack;
+use bastion::prelude::*;
+use bastion_executor::pool;
+use futures::channel::oneshot;
+use futures_timer::Delay;
+use std::time::Duration;
+
+#[test]
+fn executor_shutdown() {
+    let runtime = BastionRuntime::new(Config::new());
+    runtime
+        .children(|children| {
+            children
+                .with_redundancy(2)
+                .with_exec(|ctx: BastionContext| async move {
+                    loop {
+                        ctx.recv().await?;
+                    }
+                })
+        })
+        .expect("Couldn't create the children group.");
+    runtime.start();
+
+    // A process spawned by bastion still running once the system
+    // stopped...
+    let ticking = spawn!(async {
+        loop {
+            Delay::new(Duration::from_millis(10)).await;
+        }
+    });
+
+    // A process spawned directly onto the pool, unaffected by the
+    // system stopping.
+    let (sender, receiver) = oneshot::channel();
+    let direct = pool::spawn(receiver, ProcStack::default());
+
+    runtime.stop();
+    runtime.block_until_stopped();
+
+    // ...gets cancelled once the system stopped, unlike the one
+    // spawned directly onto the pool.
+    assert_eq!(run!(ticking), None);
+    sender.send(42).unwrap();
+    assert_eq!(run!(direct), Some(Ok(42)));
+
+    // The processes spawned afterwards are refused...
+    assert_eq!(run!(spawn!(async { 42 })), None);
+
+    // ...until another system is launched.
+    let runtime = BastionRuntime::new(Config::new());
+    runtime.start();
+    assert_eq!(run!(spawn!(async { 42 })), Some(42));
+    runtime.stop();
+    runtime.block_until_stopped();
+}
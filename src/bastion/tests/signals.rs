@@ -0,0 +1,32 @@
+#![cfg(feature = "signals")]
+
+use bastion::prelude::*;
+use std::process::{self, Command};
+
+#[test]
+fn signals() {
+    Bastion::init();
+    Bastion::handle_signals(None).expect("Couldn't install the signal handlers.");
+    // The handlers can only be installed once.
+    assert!(Bastion::handle_signals(None).is_err());
+
+    Bastion::children(|children| {
+        children.with_exec(|ctx: BastionContext| async move {
+            loop {
+                ctx.recv().await?;
+            }
+        })
+    })
+    .expect("Couldn't create the children group.");
+
+    Bastion::start();
+
+    let status = Command::new("kill")
+        .arg("-TERM")
+        .arg(process::id().to_string())
+        .status()
+        .expect("Couldn't send the signal.");
+    assert!(status.success());
+
+    assert_eq!(Bastion::block_until_stopped(), SystemExit::Stopped);
+}
@@ -0,0 +1,85 @@
+mod common;
+
+use bastion::prelude::*;
+use common::wait_until;
+use futures_timer::Delay;
+use std::sync::{Arc, Mutex};
+use std::thread;
+use std::time::Duration;
+
+#[derive(Debug, PartialEq, Eq)]
+enum Job {
+    Async,
+    Blocking,
+}
+
+// Records the slow elements and the type of the messages they
+// took too long to handle.
+#[derive(Debug, Default, Clone)]
+struct SlowLogger(Arc<Mutex<Vec<(BastionId, Duration, String)>>>);
+
+impl BastionLogger for SlowLogger {
+    fn log_slow_processing(&self, id: &BastionId, elapsed: Duration, msg_type: &str) {
+        self.0
+            .lock()
+            .unwrap()
+            .push((id.clone(), elapsed, msg_type.to_string()));
+    }
+}
+
+#[test]
+fn slow_processing() {
+    let logger = SlowLogger::default();
+    Bastion::init();
+    Bastion::with_logger(Box::new(logger.clone()));
+    Bastion::start();
+
+    let children_ref = Bastion::children(|children| {
+        children
+            .with_processing_warning_threshold(Duration::from_millis(50))
+            .with_exec(|ctx: BastionContext| async move {
+                loop {
+                    msg! { ctx.recv().await?,
+                        job: Job => match job {
+                            Job::Async => Delay::new(Duration::from_millis(300)).await,
+                            Job::Blocking => thread::sleep(Duration::from_millis(150)),
+                        };
+                        _: _ => ();
+                    }
+                }
+            })
+    })
+    .expect("Couldn't create the children group.");
+    let element = children_ref.elems()[0].clone();
+
+    // An element still handling a message is warned about once...
+    element.tell_anonymously(Job::Async).unwrap();
+    wait_until(|| !logger.0.lock().unwrap().is_empty());
+    thread::sleep(Duration::from_millis(400));
+    {
+        let warnings = logger.0.lock().unwrap();
+        assert_eq!(warnings.len(), 1);
+        let (id, elapsed, msg_type) = &warnings[0];
+        assert_eq!(id, element.id());
+        assert!(*elapsed >= Duration::from_millis(50));
+        assert!(*elapsed < Duration::from_millis(300));
+        assert!(msg_type.ends_with("Job"));
+    }
+
+    // ...and one blocking its thread once it is done handling it.
+    element.tell_anonymously(Job::Blocking).unwrap();
+    wait_until(|| logger.0.lock().unwrap().len() == 2);
+    {
+        let warnings = logger.0.lock().unwrap();
+        assert_eq!(warnings.len(), 2);
+        assert!(warnings[1].1 >= Duration::from_millis(150));
+    }
+
+    // Messages handled quickly aren't warned about.
+    element.tell_anonymously("quick").unwrap();
+    thread::sleep(Duration::from_millis(100));
+    assert_eq!(logger.0.lock().unwrap().len(), 2);
+
+    Bastion::stop();
+    Bastion::block_until_stopped();
+}
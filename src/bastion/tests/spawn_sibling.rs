@@ -0,0 +1,81 @@
+mod common;
+
+use bastion::prelude::*;
+use common::wait_until;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex};
+
+#[test]
+fn spawn_sibling() {
+    Bastion::init();
+    Bastion::start();
+
+    let starts = Arc::new(AtomicUsize::new(0));
+    let helper_starts = Arc::new(AtomicUsize::new(0));
+    let received = Arc::new(AtomicUsize::new(0));
+    let helper: Arc<Mutex<Option<ChildRef>>> = Arc::new(Mutex::new(None));
+
+    let (starts_inner, helper_starts_inner, received_inner, helper_inner) = (
+        starts.clone(),
+        helper_starts.clone(),
+        received.clone(),
+        helper.clone(),
+    );
+    Bastion::children(move |children| {
+        let (starts, helper_starts, received, helper) = (
+            starts_inner.clone(),
+            helper_starts_inner.clone(),
+            received_inner.clone(),
+            helper_inner.clone(),
+        );
+        children.with_exec(move |ctx: BastionContext| {
+            let (helper_starts, received, helper) =
+                (helper_starts.clone(), received.clone(), helper.clone());
+            starts.fetch_add(1, Ordering::SeqCst);
+            async move {
+                let helper_ref = ctx.spawn_sibling(move |ctx: BastionContext| {
+                    let received = received.clone();
+                    helper_starts.fetch_add(1, Ordering::SeqCst);
+                    async move {
+                        loop {
+                            msg! { ctx.recv().await?,
+                                msg: &'static str => {
+                                    if msg == "crash" {
+                                        panic!("crash");
+                                    }
+                                    received.fetch_add(1, Ordering::SeqCst);
+                                };
+                                _: _ => ();
+                            }
+                        }
+                    }
+                });
+                *helper.lock().unwrap() = Some(helper_ref);
+
+                loop {
+                    ctx.recv().await?;
+                }
+            }
+        })
+    })
+    .expect("Couldn't create the children group.");
+
+    wait_until(|| helper.lock().unwrap().is_some());
+    let helper_ref = helper.lock().unwrap().clone().unwrap();
+    helper_ref
+        .tell_anonymously("work")
+        .expect("Couldn't send the message to the helper.");
+    wait_until(|| received.load(Ordering::SeqCst) == 1);
+    assert_eq!(received.load(Ordering::SeqCst), 1);
+    assert_eq!(helper_starts.load(Ordering::SeqCst), 1);
+
+    // The helper gets restarted on its own, running its closure
+    // again.
+    helper_ref.tell_anonymously("crash").unwrap();
+    wait_until(|| helper_starts.load(Ordering::SeqCst) == 2);
+    assert_eq!(helper_starts.load(Ordering::SeqCst), 2);
+    assert_eq!(starts.load(Ordering::SeqCst), 1);
+
+    Bastion::stop();
+    Bastion::block_until_stopped();
+}
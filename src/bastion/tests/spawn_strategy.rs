@@ -0,0 +1,184 @@
+mod common;
+
+use bastion::prelude::*;
+use common::wait_until;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex};
+use std::thread;
+
+type Threads = Arc<Mutex<Vec<(thread::ThreadId, Option<String>)>>>;
+
+fn record_threads(strategy: SpawnStrategy, redundancy: usize) -> (ChildrenRef, Threads) {
+    let threads = Arc::new(Mutex::new(vec![]));
+
+    let threads_inner = threads.clone();
+    let children_ref = Bastion::children(move |children| {
+        let threads = threads_inner.clone();
+        children
+            .with_redundancy(redundancy)
+            .with_spawn_strategy(strategy)
+            .with_exec(move |ctx: BastionContext| {
+                let threads = threads.clone();
+                async move {
+                    let current = thread::current();
+                    let name = current.name().map(ToString::to_string);
+                    threads.lock().unwrap().push((current.id(), name));
+
+                    loop {
+                        ctx.recv().await?;
+                    }
+                }
+            })
+    })
+    .expect("Couldn't create the children group.");
+
+    (children_ref, threads)
+}
+
+#[test]
+fn spawn_strategy() {
+    Bastion::init();
+
+    let (_, dedicated) = record_threads(SpawnStrategy::DedicatedThread, 2);
+    let (_, pinned) = record_threads(SpawnStrategy::PinnedThread(0), 2);
+    let (_, default) = record_threads(SpawnStrategy::default(), 1);
+
+    Bastion::start();
+
+    wait_until(|| {
+        dedicated.lock().unwrap().len() == 2
+            && pinned.lock().unwrap().len() == 2
+            && default.lock().unwrap().len() == 1
+    });
+
+    // Each element runs on its own thread, outside of the pool...
+    let dedicated = dedicated.lock().unwrap();
+    assert_eq!(dedicated.len(), 2);
+    assert_ne!(dedicated[0].0, dedicated[1].0);
+    assert!(dedicated
+        .iter()
+        .all(|(_, name)| name.as_deref() == Some("bastion-dedicated")));
+
+    // ...while the pinned ones share the same thread of the pool.
+    let pinned = pinned.lock().unwrap();
+    assert_eq!(pinned.len(), 2);
+    assert_eq!(pinned[0].0, pinned[1].0);
+    assert_eq!(pinned[0].1.as_deref(), Some("bastion-async-thread"));
+
+    let default = default.lock().unwrap();
+    assert_eq!(default[0].1.as_deref(), Some("bastion-async-thread"));
+
+    Bastion::stop();
+    Bastion::block_until_stopped();
+}
+
+#[test]
+fn blocking_closure() {
+    let runtime = BastionRuntime::new(Config::new().with_blocking_threads(3));
+    assert_eq!(*bastion_executor::blocking::low_watermark(), 3);
+
+    let started = Arc::new(AtomicUsize::new(0));
+    let outputs = Arc::new(Mutex::new(vec![]));
+    let (started_inner, outputs_inner) = (started.clone(), outputs.clone());
+    let children_ref = runtime
+        .children(move |children| {
+            let (started, outputs) = (started_inner.clone(), outputs_inner.clone());
+            children.with_exec(move |ctx: BastionContext| {
+                let (started, outputs) = (started.clone(), outputs.clone());
+                async move {
+                    started.fetch_add(1, Ordering::SeqCst);
+                    loop {
+                        msg! { ctx.recv().await?,
+                            ref n: u32 => {
+                                let n = *n;
+                                let output = ctx.blocking(move || {
+                                    if n == 0 {
+                                        panic!("blocking panic");
+                                    }
+                                    let name = thread::current().name().map(ToString::to_string);
+                                    (name, n * 2)
+                                }).await;
+                                outputs.lock().unwrap().push(output);
+                            };
+                            _: _ => ();
+                        }
+                    }
+                }
+            })
+        })
+        .expect("Couldn't create the children group.");
+    runtime.start();
+
+    // The closure runs on the blocking pool...
+    children_ref.broadcast(1u32).unwrap();
+    wait_until(|| outputs.lock().unwrap().len() == 1);
+    assert_eq!(
+        outputs.lock().unwrap()[0],
+        (Some("bastion-blocking-driver".to_string()), 2)
+    );
+
+    // ...and its panics are faults of the element.
+    children_ref.broadcast(0u32).unwrap();
+    wait_until(|| started.load(Ordering::SeqCst) == 2);
+    assert_eq!(started.load(Ordering::SeqCst), 2);
+
+    children_ref.broadcast(2u32).unwrap();
+    wait_until(|| outputs.lock().unwrap().len() == 2);
+    assert_eq!(outputs.lock().unwrap()[1].1, 4);
+
+    runtime.stop();
+    assert_eq!(runtime.block_until_stopped(), SystemExit::Stopped);
+}
+
+#[test]
+fn affinity() {
+    let runtime = BastionRuntime::new(Config::new());
+
+    // Invalid cores are refused when creating the group...
+    assert!(runtime
+        .children(|children| children.with_affinity(&[usize::MAX]))
+        .is_err());
+    assert!(runtime
+        .supervisor(|sp| sp.children(|children| children.with_affinity(&[usize::MAX])))
+        .is_err());
+    assert!(runtime
+        .supervisor(|sp| {
+            sp.supervisor(|sp| sp.children(|children| children.with_affinity(&[usize::MAX])))
+        })
+        .is_err());
+    assert_eq!(Affinity::from(Vec::new()), Affinity::Any);
+
+    // ...while the elements of the others stay on the same thread.
+    let threads = Arc::new(Mutex::new(vec![]));
+    let threads_inner = threads.clone();
+    runtime
+        .children(move |children| {
+            let threads = threads_inner.clone();
+            children
+                .with_redundancy(3)
+                .with_affinity(&[0])
+                .with_exec(move |ctx: BastionContext| {
+                    let threads = threads.clone();
+                    async move {
+                        let current = thread::current();
+                        let name = current.name().map(ToString::to_string);
+                        threads.lock().unwrap().push((current.id(), name));
+
+                        loop {
+                            ctx.recv().await?;
+                        }
+                    }
+                })
+        })
+        .expect("Couldn't create the children group.");
+    runtime.start();
+
+    wait_until(|| threads.lock().unwrap().len() == 3);
+    let threads = threads.lock().unwrap();
+    assert_eq!(threads.len(), 3);
+    assert!(threads.iter().all(|thread| thread.0 == threads[0].0));
+    assert_eq!(threads[0].1.as_deref(), Some("bastion-async-thread"));
+
+    runtime.stop();
+    assert_eq!(runtime.block_until_stopped(), SystemExit::Stopped);
+}
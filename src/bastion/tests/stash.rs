@@ -0,0 +1,121 @@
+mod common;
+
+use bastion::prelude::*;
+use common::wait_until;
+use std::sync::{Arc, Mutex};
+
+#[test]
+fn stash_and_unstash_all() {
+    Bastion::init();
+
+    let seen = Arc::new(Mutex::new(vec![]));
+
+    let seen_inner = seen.clone();
+    let children_ref = Bastion::children(move |children| {
+        let seen = seen_inner.clone();
+        children
+            .with_stash_capacity(2)
+            .with_exec(move |ctx: BastionContext| {
+                let seen = seen.clone();
+                async move {
+                    // Stashing the messages received before "init"...
+                    loop {
+                        let msg = ctx.recv().await?;
+                        if msg.msg().peek::<&'static str>() == Some(&"init") {
+                            break;
+                        }
+
+                        if ctx.stash(msg).await.is_err() {
+                            seen.lock().unwrap().push("full".to_string());
+                        }
+                    }
+
+                    assert_eq!(ctx.unstash_all().await, 2);
+                    loop {
+                        msg! { ctx.recv().await?,
+                            ref msg: &'static str => seen.lock().unwrap().push(msg.to_string());
+                            _: _ => ();
+                        }
+                    }
+                }
+            })
+    })
+    .expect("Couldn't create the children group.");
+
+    // The messages are received before the element's future is
+    // first polled.
+    children_ref.broadcast("first").unwrap();
+    children_ref.broadcast("second").unwrap();
+    children_ref.broadcast("third").unwrap();
+    children_ref.broadcast("init").unwrap();
+    children_ref.broadcast("fourth").unwrap();
+    Bastion::start();
+
+    wait_until(|| seen.lock().unwrap().len() == 4);
+    // The stashed messages are received first, in order.
+    assert_eq!(
+        *seen.lock().unwrap(),
+        vec!["full", "first", "second", "fourth"]
+    );
+
+    Bastion::stop();
+    Bastion::block_until_stopped();
+}
+
+#[test]
+fn stash_and_unstash_one() {
+    let runtime = BastionRuntime::new(Config::new());
+
+    let seen = Arc::new(Mutex::new(vec![]));
+
+    let seen_inner = seen.clone();
+    let children_ref = runtime
+        .children(move |children| {
+            let seen = seen_inner.clone();
+            children.with_exec(move |ctx: BastionContext| {
+                let seen = seen.clone();
+                async move {
+                    let recv = || async {
+                        let msg = ctx.recv().await?;
+                        Ok(*msg.msg().peek::<&'static str>().unwrap())
+                    };
+
+                    // Stashing the messages received before "init"...
+                    loop {
+                        let msg = ctx.recv().await?;
+                        if msg.msg().peek::<&'static str>() == Some(&"init") {
+                            break;
+                        }
+
+                        ctx.stash(msg).await.unwrap();
+                    }
+
+                    // ...and receiving them one at a time, the
+                    // others staying stashed.
+                    let mut received = vec![];
+                    assert!(ctx.unstash_one().await);
+                    received.push(recv().await?);
+                    received.push(recv().await?);
+                    assert!(ctx.unstash_one().await);
+                    received.push(recv().await?);
+                    assert!(!ctx.unstash_one().await);
+
+                    *seen.lock().unwrap() = received;
+                    Ok(())
+                }
+            })
+        })
+        .expect("Couldn't create the children group.");
+
+    children_ref.broadcast("first").unwrap();
+    children_ref.broadcast("second").unwrap();
+    children_ref.broadcast("init").unwrap();
+    children_ref.broadcast("third").unwrap();
+    runtime.start();
+
+    wait_until(|| seen.lock().unwrap().len() == 3);
+    assert_eq!(*seen.lock().unwrap(), vec!["first", "third", "second"]);
+
+    runtime.stop();
+    runtime.block_until_stopped();
+}
@@ -0,0 +1,110 @@
+mod common;
+
+use bastion::prelude::*;
+use bastion::state_backend::FileStateBackend;
+use common::wait_until;
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+// Keeps the saved states in memory, counting the loads.
+#[derive(Debug, Default)]
+struct MemoryBackend {
+    states: Mutex<HashMap<BastionId, Vec<u8>>>,
+    loads: AtomicUsize,
+}
+
+impl StateBackend for MemoryBackend {
+    fn save(&self, id: &BastionId, state: &[u8]) -> Result<(), BackendError> {
+        self.states
+            .lock()
+            .unwrap()
+            .insert(id.clone(), state.to_vec());
+        Ok(())
+    }
+
+    fn load(&self, id: &BastionId) -> Result<Option<Vec<u8>>, BackendError> {
+        self.loads.fetch_add(1, Ordering::SeqCst);
+        Ok(self.states.lock().unwrap().get(id).cloned())
+    }
+}
+
+#[test]
+fn state_persistence() {
+    Bastion::init();
+
+    let backend = Arc::new(MemoryBackend::default());
+    let faults = Arc::new(AtomicUsize::new(0));
+    let restarts = Arc::new(AtomicUsize::new(0));
+
+    let (backend_inner, faults_inner, restarts_inner) =
+        (backend.clone(), faults.clone(), restarts.clone());
+    let sp_ref = Bastion::supervisor(move |sp| {
+        let faults = faults_inner.clone();
+        let restarts = restarts_inner.clone();
+        let callbacks = Callbacks::new().with_before_restart(move || {
+            restarts.fetch_add(1, Ordering::SeqCst);
+        });
+
+        // Escalating on the first fault gets the supervisor
+        // restarted by the system...
+        sp.with_state_persistence(backend_inner.clone())
+            .with_restart_window(RestartWindow::new(0, Duration::from_secs(60)))
+            .with_callbacks(callbacks)
+            .children(move |children| {
+                children.with_exec(move |ctx: BastionContext| {
+                    let faults = faults.clone();
+                    async move {
+                        msg! { ctx.recv().await?,
+                            ref _msg: &'static str => {
+                                if faults.fetch_add(1, Ordering::SeqCst) == 0 {
+                                    return Err(());
+                                }
+                            };
+                            _: _ => ();
+                        }
+
+                        loop {
+                            ctx.recv().await?;
+                        }
+                    }
+                })
+            })
+    })
+    .expect("Couldn't create the supervisor.");
+
+    Bastion::start();
+    sp_ref
+        .strategy(SupervisionStrategy::OneForAll)
+        .expect("Couldn't set the strategy.");
+    sp_ref.broadcast("fault").unwrap();
+
+    // ...which checkpoints its state under its identifier before
+    // restoring it.
+    wait_until(|| restarts.load(Ordering::SeqCst) == 1);
+    assert_eq!(restarts.load(Ordering::SeqCst), 1);
+    assert_eq!(backend.loads.load(Ordering::SeqCst), 1);
+    let state = backend.states.lock().unwrap()[sp_ref.id()].clone();
+    let state = String::from_utf8(state).unwrap();
+    assert!(state.contains("OneForAll"));
+
+    Bastion::stop();
+    Bastion::block_until_stopped();
+}
+
+#[test]
+fn file_state_backend() {
+    let dir = std::env::temp_dir().join(format!("bastion-states-{}", std::process::id()));
+    let backend = FileStateBackend::new(&dir);
+    let id = NIL_ID;
+
+    assert!(backend.load(&id).unwrap().is_none());
+    backend.save(&id, b"first").unwrap();
+    backend.save(&id, b"second").unwrap();
+    assert_eq!(backend.load(&id).unwrap(), Some(b"second".to_vec()));
+    // Only the saved state is left in the directory.
+    assert_eq!(std::fs::read_dir(&dir).unwrap().count(), 1);
+
+    std::fs::remove_dir_all(dir).ok();
+}
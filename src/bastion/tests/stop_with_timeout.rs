@@ -0,0 +1,111 @@
+mod common;
+
+use bastion::prelude::*;
+use common::wait_until;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::thread;
+use std::time::{Duration, Instant};
+
+#[test]
+fn stop_with_timeout_kills_stragglers() {
+    // With enough threads for the runtime to keep running while
+    // the elements of both tests keep some of them busy.
+    Bastion::init_with(Config::new().with_threads(4));
+
+    let release = Arc::new(AtomicBool::new(false));
+
+    let sp_ref = Bastion::supervisor(|sp| sp).expect("Couldn't create the supervisor.");
+    let release_inner = release.clone();
+    let stuck = sp_ref
+        .children(move |children| {
+            // Never yielding once stopped, keeping the thread it
+            // runs on busy...
+            let callbacks = Callbacks::new().with_after_stop(move || {
+                while !release_inner.load(Ordering::SeqCst) {
+                    thread::sleep(Duration::from_millis(1));
+                }
+            });
+
+            children
+                .with_callbacks(callbacks)
+                .with_exec(|ctx: BastionContext| async move {
+                    loop {
+                        ctx.recv().await?;
+                    }
+                })
+        })
+        .expect("Couldn't create the children group.");
+
+    Bastion::start();
+
+    let started = Instant::now();
+    let report = Bastion::stop_with_timeout(Duration::from_millis(200));
+    assert!(started.elapsed() < Duration::from_secs(5));
+
+    assert!(!report.is_clean());
+    assert!(report.killed().any(|info| {
+        info.id == *stuck.id()
+            && info.kind == SupervisedKind::Children
+            && info.stop_reason == StopReason::Killed
+    }));
+    assert!(report
+        .killed()
+        .any(|info| info.id == *sp_ref.id() && info.kind == SupervisedKind::Supervisor));
+    assert!(report
+        .stopped()
+        .all(|info| info.id != *stuck.id() && info.id != *sp_ref.id()));
+
+    release.store(true, Ordering::SeqCst);
+    // The system is considered stopped.
+    Bastion::block_until_stopped();
+}
+
+#[test]
+fn stop_with_timeout_kills_busy_elements() {
+    // Using its own runtime, not to share the default one with the
+    // other test.
+    let runtime = BastionRuntime::new(Config::new().with_threads(4));
+
+    let release = Arc::new(AtomicBool::new(false));
+    let started = Arc::new(AtomicBool::new(false));
+
+    let (release_inner, started_inner) = (release.clone(), started.clone());
+    let busy = runtime
+        .children(move |children| {
+            let (release, started) = (release_inner.clone(), started_inner.clone());
+            children.with_exec(move |_ctx: BastionContext| {
+                let (release, started) = (release.clone(), started.clone());
+                async move {
+                    started.store(true, Ordering::SeqCst);
+                    // Never yielding, and thus never handling the
+                    // message asking it to stop...
+                    while !release.load(Ordering::SeqCst) {
+                        std::hint::spin_loop();
+                    }
+
+                    Ok(())
+                }
+            })
+        })
+        .expect("Couldn't create the children group.");
+
+    runtime.start();
+    wait_until(|| started.load(Ordering::SeqCst));
+
+    // ...which doesn't prevent it from getting killed.
+    let timeout = Duration::from_millis(200);
+    let stopping = Instant::now();
+    let report = runtime.stop_with_timeout(timeout);
+    assert!(stopping.elapsed() < timeout + Duration::from_secs(1));
+
+    assert!(!report.is_clean());
+    assert!(report.killed().any(|info| {
+        info.id == *busy.id()
+            && info.kind == SupervisedKind::Children
+            && info.stop_reason == StopReason::Killed
+    }));
+
+    release.store(true, Ordering::SeqCst);
+    runtime.block_until_stopped();
+}
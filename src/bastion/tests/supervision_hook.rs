@@ -0,0 +1,160 @@
+mod common;
+
+use bastion::prelude::*;
+use common::wait_until;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex};
+use std::thread;
+use std::time::Duration;
+
+// Returns a children group whose element faults the first `faults`
+// times it runs (panicking if asked to), and then waits for messages.
+fn faulting_children(
+    children: Children,
+    starts: Arc<AtomicUsize>,
+    faults: usize,
+    panic: bool,
+) -> Children {
+    children.with_exec(move |ctx: BastionContext| {
+        let starts = starts.clone();
+        async move {
+            if starts.fetch_add(1, Ordering::SeqCst) < faults {
+                if panic {
+                    panic!("faulting");
+                }
+                return Err(());
+            }
+
+            loop {
+                ctx.recv().await?;
+            }
+        }
+    })
+}
+
+// Returns a children group whose element waits for messages,
+// counting how many times it started.
+fn looping_children(children: Children, starts: Arc<AtomicUsize>) -> Children {
+    children.with_exec(move |ctx: BastionContext| {
+        let starts = starts.clone();
+        async move {
+            starts.fetch_add(1, Ordering::SeqCst);
+            loop {
+                ctx.recv().await?;
+            }
+        }
+    })
+}
+
+#[test]
+fn supervision_hook() {
+    Bastion::init();
+    Bastion::start();
+
+    run_restart();
+    run_stop();
+    run_escalate();
+
+    Bastion::stop();
+    Bastion::block_until_stopped();
+}
+
+fn run_restart() {
+    let faulted = Arc::new(AtomicUsize::new(0));
+    let sibling = Arc::new(AtomicUsize::new(0));
+    let events = Arc::new(Mutex::new(Vec::new()));
+
+    let (faulted_inner, sibling_inner, events_inner) =
+        (faulted.clone(), sibling.clone(), events.clone());
+    Bastion::supervisor(move |sp| {
+        let (faulted, sibling, events) = (
+            faulted_inner.clone(),
+            sibling_inner.clone(),
+            events_inner.clone(),
+        );
+        sp.with_strategy(SupervisionStrategy::OneForAll)
+            .children(move |children| looping_children(children, sibling))
+            .children(move |children| {
+                faulting_children(children, faulted, 2, false).with_supervision_hook(
+                    move |event: FaultEvent| {
+                        events.lock().unwrap().push(event);
+                        SupervisionDecision::Restart
+                    },
+                )
+            })
+    })
+    .expect("Couldn't create the supervisor.");
+
+    wait_until(|| faulted.load(Ordering::SeqCst) == 3);
+    thread::sleep(Duration::from_millis(100));
+
+    // Only the faulted element was restarted, even though the
+    // supervisor uses the "one for all" strategy...
+    assert_eq!(faulted.load(Ordering::SeqCst), 3);
+    assert_eq!(sibling.load(Ordering::SeqCst), 1);
+    // ...and the hook was told how many times it was before.
+    let events = events.lock().unwrap();
+    assert_eq!(events.len(), 2);
+    assert_eq!(events[0].child_id, events[1].child_id);
+    assert_eq!(events[0].restart_count, 0);
+    assert_eq!(events[1].restart_count, 1);
+    assert!(events
+        .iter()
+        .all(|event| event.fault_kind == FaultKind::Error));
+}
+
+fn run_stop() {
+    let starts = Arc::new(AtomicUsize::new(0));
+    let kinds = Arc::new(Mutex::new(Vec::new()));
+
+    let (starts_inner, kinds_inner) = (starts.clone(), kinds.clone());
+    let children_ref = Bastion::children(move |children| {
+        let kinds = kinds_inner.clone();
+        faulting_children(children, starts_inner.clone(), 1, true).with_supervision_hook(
+            move |event: FaultEvent| {
+                kinds.lock().unwrap().push(event.fault_kind);
+                SupervisionDecision::Stop
+            },
+        )
+    })
+    .expect("Couldn't create the children group.");
+
+    wait_until(|| children_ref.is_empty());
+    thread::sleep(Duration::from_millis(100));
+    assert!(children_ref.is_empty());
+    assert_eq!(starts.load(Ordering::SeqCst), 1);
+    assert_eq!(*kinds.lock().unwrap(), vec![FaultKind::Panic]);
+}
+
+fn run_escalate() {
+    let faulted = Arc::new(AtomicUsize::new(0));
+    let sibling = Arc::new(AtomicUsize::new(0));
+    let called = Arc::new(AtomicUsize::new(0));
+
+    let (faulted_inner, sibling_inner, called_inner) =
+        (faulted.clone(), sibling.clone(), called.clone());
+    Bastion::supervisor(move |sp| {
+        let (faulted, sibling, called) = (
+            faulted_inner.clone(),
+            sibling_inner.clone(),
+            called_inner.clone(),
+        );
+        sp.with_strategy(SupervisionStrategy::OneForAll)
+            .children(move |children| looping_children(children, sibling))
+            .children(move |children| {
+                faulting_children(children, faulted, 1, false).with_supervision_hook(
+                    move |_: FaultEvent| {
+                        called.fetch_add(1, Ordering::SeqCst);
+                        SupervisionDecision::Escalate
+                    },
+                )
+            })
+    })
+    .expect("Couldn't create the supervisor.");
+
+    // The supervisor's strategy restarted every element.
+    wait_until(|| faulted.load(Ordering::SeqCst) == 2 && sibling.load(Ordering::SeqCst) == 2);
+    assert_eq!(faulted.load(Ordering::SeqCst), 2);
+    assert_eq!(sibling.load(Ordering::SeqCst), 2);
+    assert_eq!(called.load(Ordering::SeqCst), 1);
+}
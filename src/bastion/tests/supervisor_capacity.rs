@@ -0,0 +1,73 @@
+mod common;
+
+use bastion::prelude::*;
+use common::wait_until;
+
+async fn idle(ctx: BastionContext) -> Result<(), ()> {
+    loop {
+        ctx.recv().await?;
+    }
+}
+
+#[test]
+fn supervisor_capacity() {
+    let runtime = BastionRuntime::new(Config::new());
+    runtime.start();
+
+    let sp_ref = runtime
+        .supervisor(|sp| sp.with_capacity(2))
+        .expect("Couldn't create the supervisor.");
+    let first = sp_ref
+        .children(|children| children.with_exec(idle))
+        .expect("Couldn't create the children group.");
+    sp_ref
+        .supervisor(|sp| sp)
+        .expect("Couldn't create the supervisor.");
+
+    // The supervisor is full...
+    assert!(sp_ref
+        .children(|children| children.with_exec(idle))
+        .is_err());
+    assert!(sp_ref.supervisor(|sp| sp).is_err());
+    wait_until(|| matches!(run!(sp_ref.inspect()), Ok(report) if report.launched == 2));
+    let report = run!(sp_ref.inspect()).expect("Couldn't inspect the supervisor.");
+    assert_eq!(report.launched, 2);
+
+    // ...until one of its children groups stops.
+    first.stop().unwrap();
+    wait_until(|| matches!(run!(sp_ref.inspect()), Ok(report) if report.launched == 1));
+    assert!(sp_ref.children(|children| children.with_exec(idle)).is_ok());
+    assert!(sp_ref
+        .children(|children| children.with_exec(idle))
+        .is_err());
+
+    runtime.stop();
+    runtime.block_until_stopped();
+}
+
+#[test]
+fn refused_deployments() {
+    let runtime = BastionRuntime::new(Config::new());
+    runtime.start();
+
+    // The deployments beyond the capacity of a supervisor that is
+    // being built are refused once it handles them.
+    let sp_ref = runtime
+        .supervisor(|sp| {
+            sp.with_capacity(1)
+                .children(|children| children.with_exec(idle))
+                .children(|children| children.with_exec(idle).with_redundancy(2))
+        })
+        .expect("Couldn't create the supervisor.");
+    // The elements of the refused children group get killed.
+    wait_until(|| runtime.num_actors() == 1);
+    assert_eq!(runtime.num_actors(), 1);
+
+    let report = run!(sp_ref.inspect()).expect("Couldn't inspect the supervisor.");
+    assert_eq!(report.launched, 1);
+    assert_eq!(report.children.len(), 1);
+    assert!(sp_ref.children(|children| children).is_err());
+
+    runtime.stop();
+    runtime.block_until_stopped();
+}
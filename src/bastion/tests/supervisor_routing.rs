@@ -0,0 +1,110 @@
+use bastion::prelude::*;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+use std::thread;
+use std::time::Duration;
+
+fn counting_group(children: Children, counter: Arc<AtomicUsize>) -> Children {
+    children.with_exec(move |ctx: BastionContext| {
+        let counter = counter.clone();
+        async move {
+            loop {
+                msg! { ctx.recv().await?,
+                    _msg: u32 => {
+                        counter.fetch_add(1, Ordering::SeqCst);
+                    };
+                    ref _msg: u32 => {
+                        counter.fetch_add(1, Ordering::SeqCst);
+                    };
+                    _: _ => ();
+                }
+            }
+        }
+    })
+}
+
+fn wait_for(total: usize, counters: &[Arc<AtomicUsize>]) {
+    let mut tries = 0;
+    while counters
+        .iter()
+        .map(|c| c.load(Ordering::SeqCst))
+        .sum::<usize>()
+        < total
+        && tries < 200
+    {
+        thread::sleep(Duration::from_millis(10));
+        tries += 1;
+    }
+    // Making sure that no other message gets delivered.
+    thread::sleep(Duration::from_millis(50));
+}
+
+#[test]
+fn round_robin_key_and_tag_routing() {
+    Bastion::init();
+    Bastion::start();
+
+    let first = Arc::new(AtomicUsize::new(0));
+    let second = Arc::new(AtomicUsize::new(0));
+    let (first_inner, second_inner) = (first.clone(), second.clone());
+    let round_robin = Bastion::supervisor(move |sp| {
+        sp.with_routing(Routing::RoundRobin)
+            .children(|children| counting_group(children, first_inner))
+            .children(|children| counting_group(children, second_inner))
+    })
+    .expect("Couldn't create the supervisor.");
+
+    for i in 0..4u32 {
+        round_robin.broadcast(i).unwrap();
+    }
+    wait_for(4, &[first.clone(), second.clone()]);
+    assert_eq!(first.load(Ordering::SeqCst), 2);
+    assert_eq!(second.load(Ordering::SeqCst), 2);
+
+    let even = Arc::new(AtomicUsize::new(0));
+    let odd = Arc::new(AtomicUsize::new(0));
+    let (even_inner, odd_inner) = (even.clone(), odd.clone());
+    let by_key = Bastion::supervisor(move |sp| {
+        sp.with_routing(Routing::ByKey(|msg| {
+            msg.downcast_ref::<u32>().map(|n| *n as usize).unwrap_or(0)
+        }))
+        .children(|children| counting_group(children, even_inner))
+        .children(|children| counting_group(children, odd_inner))
+    })
+    .expect("Couldn't create the supervisor.");
+
+    for i in &[1u32, 3, 5, 2] {
+        by_key.broadcast(*i).unwrap();
+    }
+    wait_for(4, &[even.clone(), odd.clone()]);
+    assert_eq!(even.load(Ordering::SeqCst), 1);
+    assert_eq!(odd.load(Ordering::SeqCst), 3);
+
+    let eu = Arc::new(AtomicUsize::new(0));
+    let eu_db = Arc::new(AtomicUsize::new(0));
+    let us = Arc::new(AtomicUsize::new(0));
+    let (eu_inner, eu_db_inner, us_inner) = (eu.clone(), eu_db.clone(), us.clone());
+    let tag_based = Bastion::supervisor(move |sp| {
+        sp.with_routing(Routing::TagBased(Arc::new(|tags| {
+            tags.get("zone").map(String::as_str) == Some("eu")
+        })))
+        .children(|children| counting_group(children.with_tag("zone", "eu"), eu_inner))
+        .children(|children| {
+            let children = children.with_tag("zone", "eu").with_tag("tier", "db");
+            counting_group(children, eu_db_inner)
+        })
+        .children(|children| counting_group(children.with_tag("zone", "us"), us_inner))
+    })
+    .expect("Couldn't create the supervisor.");
+
+    for i in 0..2u32 {
+        tag_based.broadcast(i).unwrap();
+    }
+    wait_for(4, &[eu.clone(), eu_db.clone(), us.clone()]);
+    assert_eq!(eu.load(Ordering::SeqCst), 2);
+    assert_eq!(eu_db.load(Ordering::SeqCst), 2);
+    assert_eq!(us.load(Ordering::SeqCst), 0);
+
+    Bastion::stop();
+    Bastion::block_until_stopped();
+}
@@ -0,0 +1,60 @@
+mod common;
+
+use bastion::prelude::*;
+use bastion::sync::Barrier;
+use common::wait_until;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex};
+
+#[test]
+fn barrier() {
+    let runtime = BastionRuntime::new(Config::new().with_threads(4));
+
+    // Two elements of a group and one of another one going through
+    // the same barrier twice, each recording how many of them
+    // arrived before it left.
+    let barrier = Barrier::new(3);
+    let arrived = Arc::new(AtomicUsize::new(0));
+    let passed = Arc::new(Mutex::new(vec![]));
+    let leaders = Arc::new(AtomicUsize::new(0));
+    for redundancy in 1..=2 {
+        let (barrier, arrived) = (barrier.clone(), arrived.clone());
+        let (passed, leaders) = (passed.clone(), leaders.clone());
+        runtime
+            .children(move |children| {
+                children
+                    .with_redundancy(redundancy)
+                    .with_exec(move |ctx: BastionContext| {
+                        let (barrier, arrived) = (barrier.clone(), arrived.clone());
+                        let (passed, leaders) = (passed.clone(), leaders.clone());
+                        async move {
+                            for _ in 0..2 {
+                                arrived.fetch_add(1, Ordering::SeqCst);
+                                if ctx.wait_barrier(&barrier).await.is_leader() {
+                                    leaders.fetch_add(1, Ordering::SeqCst);
+                                }
+                                passed.lock().unwrap().push(arrived.load(Ordering::SeqCst));
+                            }
+
+                            Ok(())
+                        }
+                    })
+            })
+            .expect("Couldn't create the children group.");
+    }
+    runtime.start();
+
+    wait_until(|| passed.lock().unwrap().len() == 6);
+    let passed = passed.lock().unwrap().clone();
+    assert_eq!(passed.len(), 6);
+    // No element went through the barrier before the three of them
+    // arrived, and one of them led each time.
+    assert!(passed.iter().all(|arrived| *arrived >= 3));
+    assert_eq!(leaders.load(Ordering::SeqCst), 2);
+    // No element went through it again before the three of them
+    // arrived a second time.
+    assert_eq!(passed[3..], [6, 6, 6]);
+
+    runtime.stop();
+    assert_eq!(runtime.block_until_stopped(), SystemExit::Stopped);
+}
@@ -0,0 +1,99 @@
+mod common;
+
+use bastion::prelude::*;
+use bastion::sync::{signal, signal_with_capacity};
+use common::wait_until;
+use futures::{select, FutureExt, StreamExt};
+use std::sync::{Arc, Mutex};
+use std::thread;
+
+#[test]
+fn signal_element() {
+    let runtime = BastionRuntime::new(Config::new());
+    let (values, values_receiver) = signal::<u32>();
+    let (reload, reload_receiver) = signal::<()>();
+    let receivers = Arc::new(Mutex::new(Some((values_receiver, reload_receiver))));
+
+    let received = Arc::new(Mutex::new(Vec::new()));
+    let received_inner = received.clone();
+    let children_ref = runtime
+        .children(move |children| {
+            let (receivers, received) = (receivers.clone(), received_inner.clone());
+            children.with_exec(move |ctx: BastionContext| {
+                let receivers = receivers.lock().unwrap().take();
+                let received = received.clone();
+                async move {
+                    let (mut values, mut reload) = receivers.ok_or(())?;
+                    loop {
+                        select! {
+                            msg = ctx.recv().fuse() => {
+                                msg! { msg?,
+                                    msg: &'static str => {
+                                        received.lock().unwrap().push(msg.to_string());
+                                    };
+                                    _: _ => ();
+                                }
+                            }
+                            value = values.next() => {
+                                received.lock().unwrap().push(format!("{:?}", value));
+                            }
+                            _ = reload.next() => {
+                                received.lock().unwrap().push("reload".to_string());
+                            }
+                        }
+                    }
+                }
+            })
+        })
+        .expect("Couldn't create the children group.");
+    runtime.start();
+
+    // Signals are received alongside messages, from other threads.
+    children_ref.elems()[0].tell_anonymously("message").unwrap();
+    wait_until(|| received.lock().unwrap().len() == 1);
+    let (values_inner, reload_inner) = (values.clone(), reload.clone());
+    thread::spawn(move || {
+        values_inner.send(42).unwrap();
+        reload_inner.raise().unwrap();
+    })
+    .join()
+    .unwrap();
+    wait_until(|| received.lock().unwrap().len() == 3);
+    let mut received = received.lock().unwrap().clone();
+    received.sort();
+    assert_eq!(received, vec!["Some(42)", "message", "reload"]);
+    assert!(!values.is_closed());
+
+    runtime.stop();
+    runtime.block_until_stopped();
+}
+
+#[test]
+fn signal_capacity() {
+    let (sender, mut receiver) = signal_with_capacity::<u32>(2);
+
+    // Valued signals are bounded...
+    assert_eq!(sender.send(1), Ok(()));
+    assert_eq!(sender.clone().send(2), Ok(()));
+    assert_eq!(sender.send(3), Err(3));
+    assert_eq!(run!(receiver.next()), Some(1));
+    assert_eq!(sender.send(3), Ok(()));
+    assert_eq!(run!(receiver.next()), Some(2));
+    assert_eq!(run!(receiver.next()), Some(3));
+
+    // ...while unit ones are coalesced until received.
+    let (raise, mut raised) = signal::<()>();
+    raise.raise().unwrap();
+    raise.raise().unwrap();
+    assert_eq!(run!(raised.next()), Some(()));
+    assert!(raised.next().now_or_never().is_none());
+    raise.raise().unwrap();
+    drop(raise);
+    assert_eq!(run!(raised.next()), Some(()));
+    assert_eq!(run!(raised.next()), None);
+
+    // Dropped receivers can't be sent to anymore.
+    drop(receiver);
+    assert!(sender.is_closed());
+    assert_eq!(sender.send(4), Err(4));
+}
@@ -0,0 +1,115 @@
+mod common;
+
+use bastion::prelude::*;
+use common::wait_until;
+use futures::StreamExt;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+
+// Returns the lifecycle events among the given ones.
+fn lifecycle(events: &[SystemEvent]) -> Vec<&LifecycleEvent> {
+    events
+        .iter()
+        .filter_map(|event| match event {
+            SystemEvent::Lifecycle(event) => Some(event),
+            SystemEvent::Lagged(_) => None,
+        })
+        .collect()
+}
+
+#[test]
+fn events() {
+    let runtime = BastionRuntime::new(Config::new());
+    let events = runtime.events();
+
+    let starts = Arc::new(AtomicUsize::new(0));
+    let starts_inner = starts.clone();
+    let sp_ref = runtime.supervisor(|sp| sp).unwrap();
+    let children_ref = sp_ref
+        .children(move |children| {
+            let starts = starts_inner.clone();
+            children
+                .with_name("workers")
+                .with_exec(move |ctx: BastionContext| {
+                    let starts = starts.clone();
+                    async move {
+                        if starts.fetch_add(1, Ordering::SeqCst) == 0 {
+                            return Err(());
+                        }
+                        loop {
+                            ctx.recv().await?;
+                        }
+                    }
+                })
+        })
+        .expect("Couldn't create the children group.");
+    runtime.start();
+    wait_until(|| starts.load(Ordering::SeqCst) == 2);
+
+    runtime.stop();
+    assert_eq!(runtime.block_until_stopped(), SystemExit::Stopped);
+
+    // The stream ends once the system stopped.
+    let events = run!(events.collect::<Vec<_>>());
+    let events = lifecycle(&events);
+    let find = |transition, id: &BastionId| {
+        events
+            .iter()
+            .position(|event| event.transition == transition && &event.id == id)
+    };
+
+    let deployed = find(Transition::Deployed, children_ref.id()).unwrap();
+    let started = find(Transition::Started, children_ref.id()).unwrap();
+    let stopped = find(Transition::Stopped, children_ref.id()).unwrap();
+    assert!(deployed < started && started < stopped);
+    let event = events[deployed];
+    assert_eq!(event.entity, EntityKind::Children);
+    assert_eq!(event.parent.as_ref(), Some(sp_ref.id()));
+    assert_eq!(event.name.as_deref(), Some("workers"));
+
+    // Top-level supervisors don't have a parent.
+    let deployed = find(Transition::Deployed, sp_ref.id()).unwrap();
+    assert_eq!(events[deployed].entity, EntityKind::Supervisor);
+    assert_eq!(events[deployed].parent, None);
+    assert!(find(Transition::Started, sp_ref.id()).is_some());
+    assert!(find(Transition::Stopped, sp_ref.id()).is_some());
+
+    // The element faulted and got restarted.
+    let element = children_ref.elems()[0].id();
+    let faulted = find(Transition::Faulted, element).unwrap();
+    let restarted = find(Transition::Restarted, element).unwrap();
+    assert!(faulted < restarted);
+    assert_eq!(events[faulted].entity, EntityKind::Element);
+    assert_eq!(events[faulted].parent.as_ref(), Some(children_ref.id()));
+
+    // The timestamps follow the order of the events.
+    assert!(events
+        .windows(2)
+        .all(|pair| pair[0].timestamp <= pair[1].timestamp));
+}
+
+#[test]
+fn lagged() {
+    let runtime = BastionRuntime::new(Config::new());
+    let mut events = runtime.events_with_capacity(0);
+
+    let sp_ref = runtime.supervisor(|sp| sp).unwrap();
+    for _ in 0..3 {
+        sp_ref
+            .children(|children| children)
+            .expect("Couldn't create the children group.");
+    }
+    runtime.start();
+    wait_until(|| runtime.stats().children_groups == 3);
+
+    // The subscriber didn't keep up, but isn't waited for.
+    let first = run!(events.next()).unwrap();
+    assert!(matches!(first, SystemEvent::Lifecycle(_)));
+
+    runtime.stop();
+    assert_eq!(runtime.block_until_stopped(), SystemExit::Stopped);
+
+    // It is told how many events it missed once it catches up.
+    let rest = run!(events.collect::<Vec<_>>());
+    assert!(matches!(rest[0], SystemEvent::Lagged(missed) if missed > 0));
+}
@@ -0,0 +1,134 @@
+mod common;
+
+use bastion::prelude::*;
+use common::wait_until;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::fs;
+use std::path::PathBuf;
+use std::sync::Arc;
+use std::thread;
+use std::time::Duration;
+
+#[test]
+fn system_exit_faulted() {
+    Bastion::init_with(Config::new().with_threads(4));
+
+    let starts = Arc::new(AtomicUsize::new(0));
+
+    let sp_ref = Bastion::supervisor(|sp| sp.with_strategy(SupervisionStrategy::PanicForward))
+        .expect("Couldn't create the supervisor.");
+    let starts_inner = starts.clone();
+    let children_ref = sp_ref
+        .children(move |children| {
+            children.with_exec(move |ctx: BastionContext| {
+                let starts = starts_inner.clone();
+                async move {
+                    starts.fetch_add(1, Ordering::SeqCst);
+                    msg! { ctx.recv().await?,
+                        ref msg: &'static str => panic!("{}", msg);
+                        _: _ => ();
+                    }
+
+                    Ok(())
+                }
+            })
+        })
+        .expect("Couldn't create the children group.");
+
+    Bastion::start();
+
+    wait_until(|| starts.load(Ordering::SeqCst) == 1);
+    children_ref.broadcast("unrecoverable").unwrap();
+
+    // The system terminates by itself...
+    let exit = Bastion::block_until_stopped();
+    assert_eq!(
+        exit,
+        SystemExit::Faulted {
+            culprit: Some(sp_ref.id().clone()),
+            panic: Some("unrecoverable".to_string()),
+        }
+    );
+
+    // ...and stopping it afterwards doesn't change how it exited.
+    Bastion::stop();
+    assert_eq!(Bastion::block_until_stopped(), exit);
+}
+
+#[test]
+fn system_exit_stopped_after_callbacks() {
+    // Letting the callbacks run alongside the elements' parents.
+    let runtime = BastionRuntime::new(Config::new().with_threads(4));
+    let dir = std::env::temp_dir().join(format!("bastion-after-stop-{}", std::process::id()));
+    fs::create_dir_all(&dir).unwrap();
+
+    // Slowly flushing something to disk once stopped.
+    let flushing = |path: PathBuf| {
+        Callbacks::new().with_after_stop(move || {
+            thread::sleep(Duration::from_millis(50));
+            fs::write(&path, "flushed").unwrap();
+        })
+    };
+
+    let group = dir.join("group");
+    runtime
+        .children(|children| {
+            children
+                .with_callbacks(flushing(group.clone()))
+                .with_exec(|ctx: BastionContext| async move {
+                    loop {
+                        ctx.recv().await?;
+                    }
+                })
+        })
+        .expect("Couldn't create the children group.");
+    let nested = dir.join("nested");
+    let supervisor = dir.join("supervisor");
+    runtime
+        .supervisor(|sp| {
+            sp.with_callbacks(flushing(supervisor.clone()))
+                .children(|children| {
+                    children
+                        .with_callbacks(flushing(nested.clone()))
+                        .with_exec(|ctx: BastionContext| async move {
+                            loop {
+                                ctx.recv().await?;
+                            }
+                        })
+                })
+        })
+        .expect("Couldn't create the supervisor.");
+
+    let element = dir.join("element");
+    let stopping = runtime
+        .children(|children| {
+            children
+                .with_callbacks(flushing(element.clone()))
+                .with_exec(|ctx: BastionContext| async move {
+                    loop {
+                        ctx.recv().await?;
+                    }
+                })
+        })
+        .expect("Couldn't create the children group.");
+
+    runtime.start();
+    wait_until(|| runtime.num_actors() == 3);
+
+    // An element is only removed from its group once its
+    // `after_stop` returned...
+    stopping.elems()[0].stop().unwrap();
+    wait_until(|| stopping.is_empty());
+    assert!(stopping.is_empty());
+    assert_eq!(fs::read_to_string(&element).unwrap(), "flushed");
+
+    // ...and every children group's or supervisor's once the
+    // system stopped.
+    runtime.stop();
+    assert_eq!(runtime.block_until_stopped(), SystemExit::Stopped);
+    for path in &[group, nested, supervisor] {
+        assert_eq!(fs::read_to_string(path).unwrap(), "flushed");
+    }
+
+    fs::remove_dir_all(&dir).ok();
+}
@@ -0,0 +1,112 @@
+mod common;
+
+use bastion::prelude::*;
+use common::wait_until;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+
+#[test]
+fn system_stats() {
+    Bastion::init();
+    assert_eq!(Bastion::stats().uptime, Duration::from_secs(0));
+    Bastion::start();
+
+    let received = Arc::new(AtomicUsize::new(0));
+    let received_inner = received.clone();
+    let workers_ref = Bastion::children(move |children| {
+        let received = received_inner.clone();
+        children
+            .with_redundancy(2)
+            .with_exec(move |ctx: BastionContext| {
+                let received = received.clone();
+                async move {
+                    loop {
+                        msg! { ctx.recv().await?,
+                            ref _msg: &'static str => {
+                                received.fetch_add(1, Ordering::SeqCst);
+                            };
+                            msg: &'static str => {
+                                received.fetch_add(1, Ordering::SeqCst);
+                                if msg == "crash" {
+                                    panic!("crash");
+                                }
+                            };
+                            _: _ => ();
+                        }
+                    }
+                }
+            })
+    })
+    .expect("Couldn't create the children group.");
+    Bastion::supervisor(|sp| {
+        sp.children(|children| {
+            children.with_exec(|ctx: BastionContext| async move {
+                loop {
+                    ctx.recv().await?;
+                }
+            })
+        })
+    })
+    .expect("Couldn't create the supervisor.");
+    wait_until(|| Bastion::stats().elements == 3);
+
+    let stats = Bastion::stats();
+    assert_eq!(stats.supervisors, 1);
+    assert_eq!(stats.children_groups, 2);
+    assert_eq!(stats.elements, 3);
+    assert_eq!(stats.restarts, 0);
+    assert_eq!(stats.messages_delivered, 0);
+    assert!(stats.uptime > Duration::from_secs(0));
+
+    // Broadcasting a message delivers it to both elements...
+    workers_ref.broadcast("work").unwrap();
+    wait_until(|| received.load(Ordering::SeqCst) == 2);
+    assert_eq!(Bastion::stats().messages_delivered, 2);
+
+    // ...and crashing one of them gets it restarted.
+    workers_ref.elems()[0].tell_anonymously("crash").unwrap();
+    wait_until(|| Bastion::stats().restarts == 1);
+    let stats = Bastion::stats();
+    assert_eq!(stats.restarts, 1);
+    assert_eq!(stats.messages_delivered, 3);
+
+    workers_ref.stop().unwrap();
+    wait_until(|| Bastion::stats().children_groups == 1);
+    let stats = Bastion::stats();
+    assert_eq!(stats.children_groups, 1);
+    assert_eq!(stats.elements, 1);
+
+    Bastion::stop();
+    Bastion::block_until_stopped();
+}
+
+#[test]
+fn executor_stats() {
+    let runtime = BastionRuntime::new(Config::new());
+    let before = Bastion::executor_stats();
+
+    runtime
+        .children(|children| {
+            children
+                .with_redundancy(3)
+                .with_exec(|ctx: BastionContext| async move {
+                    loop {
+                        ctx.recv().await?;
+                    }
+                })
+        })
+        .expect("Couldn't create the children group.");
+    runtime.start();
+    wait_until(|| runtime.stats().elements == 3);
+
+    // The group and its elements were spawned onto the pool.
+    let stats = Bastion::executor_stats();
+    assert!(!stats.workers.is_empty());
+    assert!(stats.spawned >= before.spawned + 4);
+    assert!(stats.completed <= stats.spawned);
+    assert!(stats.parked <= stats.workers.len());
+
+    runtime.stop();
+    assert_eq!(runtime.block_until_stopped(), SystemExit::Stopped);
+}
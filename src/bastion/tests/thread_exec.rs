@@ -0,0 +1,89 @@
+mod common;
+
+use bastion::prelude::*;
+use common::wait_until;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex};
+
+#[test]
+fn thread_exec() {
+    let runtime = BastionRuntime::new(Config::new());
+    let starts = Arc::new(AtomicUsize::new(0));
+    let exits = Arc::new(AtomicUsize::new(0));
+    let received = Arc::new(Mutex::new(Vec::new()));
+    let (starts_inner, exits_inner, received_inner) =
+        (starts.clone(), exits.clone(), received.clone());
+    let children_ref = runtime
+        .children(move |children| {
+            let (starts, exits, received) = (
+                starts_inner.clone(),
+                exits_inner.clone(),
+                received_inner.clone(),
+            );
+            children.with_thread_exec(move |ctx: ThreadContext| {
+                starts.fetch_add(1, Ordering::SeqCst);
+                let res = loop {
+                    let msg = match ctx.recv() {
+                        Ok(msg) => msg,
+                        Err(()) => break Ok(()),
+                    };
+                    let msg = msg! { msg,
+                        ref msg: &'static str => *msg;
+                        _: _ => "";
+                    };
+                    match msg {
+                        "error" => break Err(()),
+                        "panic" => panic!("Asked to panic."),
+                        msg => received.lock().unwrap().push(msg),
+                    }
+                };
+                exits.fetch_add(1, Ordering::SeqCst);
+                res
+            })
+        })
+        .expect("Couldn't create the children group.");
+    runtime.start();
+    wait_until(|| starts.load(Ordering::SeqCst) == 1);
+
+    // Messages are received by the thread...
+    children_ref.broadcast("message").unwrap();
+    wait_until(|| received.lock().unwrap().len() == 1);
+
+    // ...whose faults and panics restart the element...
+    children_ref.broadcast("error").unwrap();
+    wait_until(|| starts.load(Ordering::SeqCst) == 2);
+    assert_eq!(exits.load(Ordering::SeqCst), 1);
+    children_ref.broadcast("panic").unwrap();
+    wait_until(|| starts.load(Ordering::SeqCst) == 3);
+
+    // ...and which gets interrupted when stopping.
+    runtime.stop();
+    runtime.block_until_stopped();
+    wait_until(|| exits.load(Ordering::SeqCst) == 2);
+    assert_eq!(*received.lock().unwrap(), vec!["message"]);
+}
+
+#[test]
+fn thread_exec_kill() {
+    let runtime = BastionRuntime::new(Config::new());
+    let exits = Arc::new(AtomicUsize::new(0));
+    let exits_inner = exits.clone();
+    let children_ref = runtime
+        .children(move |children| {
+            let exits = exits_inner.clone();
+            children.with_thread_exec(move |ctx: ThreadContext| {
+                while ctx.recv().is_ok() {}
+                exits.fetch_add(1, Ordering::SeqCst);
+                Ok(())
+            })
+        })
+        .expect("Couldn't create the children group.");
+    runtime.start();
+    wait_until(|| children_ref.elems().len() == 1);
+
+    children_ref.kill().unwrap();
+    wait_until(|| exits.load(Ordering::SeqCst) == 1);
+
+    runtime.stop();
+    runtime.block_until_stopped();
+}
@@ -0,0 +1,123 @@
+mod common;
+
+use bastion::prelude::*;
+use common::wait_until;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+use std::thread;
+use std::time::Duration;
+
+#[test]
+fn ticks() {
+    Bastion::init();
+    Bastion::start();
+
+    run_periodic_ticks();
+    run_coalesced_ticks();
+    run_cancelled_ticks();
+
+    Bastion::stop();
+    Bastion::block_until_stopped();
+}
+
+fn run_periodic_ticks() {
+    let ticks = Arc::new(AtomicUsize::new(0));
+
+    let ticks_inner = ticks.clone();
+    let children_ref = Bastion::children(move |children| {
+        let ticks = ticks_inner.clone();
+        children
+            .with_tick(Duration::from_millis(10))
+            .with_exec(move |ctx: BastionContext| {
+                let ticks = ticks.clone();
+                async move {
+                    loop {
+                        msg! { ctx.recv().await?,
+                            _tick: Tick => {
+                                ticks.fetch_add(1, Ordering::SeqCst);
+                            };
+                            _: _ => ();
+                        }
+                    }
+                }
+            })
+    })
+    .expect("Couldn't create the children group.");
+
+    wait_until(|| ticks.load(Ordering::SeqCst) >= 3);
+    assert!(ticks.load(Ordering::SeqCst) >= 3);
+
+    // Ticks aren't sent anymore once the element stopped.
+    children_ref.stop().unwrap();
+    wait_until(|| children_ref.is_empty());
+    let stopped_at = ticks.load(Ordering::SeqCst);
+    thread::sleep(Duration::from_millis(100));
+    assert_eq!(ticks.load(Ordering::SeqCst), stopped_at);
+}
+
+fn run_coalesced_ticks() {
+    let pending = Arc::new(AtomicUsize::new(usize::MAX));
+
+    let pending_inner = pending.clone();
+    Bastion::children(move |children| {
+        let pending = pending_inner.clone();
+        children
+            .with_tick(Duration::from_millis(10))
+            .with_exec(move |ctx: BastionContext| {
+                let pending = pending.clone();
+                async move {
+                    // Being slow to receive the ticks...
+                    thread::sleep(Duration::from_millis(200));
+
+                    let mut ticks = 0;
+                    msg! { ctx.recv().await?,
+                        _tick: Tick => ticks += 1;
+                        _: _ => ();
+                    }
+                    while let Some(msg) = ctx.try_recv().await {
+                        msg! { msg,
+                            _tick: Tick => ticks += 1;
+                            _: _ => ();
+                        }
+                    }
+                    pending.store(ticks, Ordering::SeqCst);
+
+                    Ok(())
+                }
+            })
+    })
+    .expect("Couldn't create the children group.");
+
+    wait_until(|| pending.load(Ordering::SeqCst) != usize::MAX);
+    assert_eq!(pending.load(Ordering::SeqCst), 1);
+}
+
+fn run_cancelled_ticks() {
+    let ticks = Arc::new(AtomicUsize::new(0));
+
+    let ticks_inner = ticks.clone();
+    Bastion::children(move |children| {
+        let ticks = ticks_inner.clone();
+        children
+            .with_tick(Duration::from_millis(10))
+            .with_exec(move |ctx: BastionContext| {
+                let ticks = ticks.clone();
+                async move {
+                    loop {
+                        msg! { ctx.recv().await?,
+                            _tick: Tick => {
+                                ticks.fetch_add(1, Ordering::SeqCst);
+                                ctx.set_tick(None);
+                            };
+                            _: _ => ();
+                        }
+                    }
+                }
+            })
+    })
+    .expect("Couldn't create the children group.");
+
+    wait_until(|| ticks.load(Ordering::SeqCst) >= 1);
+    thread::sleep(Duration::from_millis(100));
+    assert_eq!(ticks.load(Ordering::SeqCst), 1);
+}
@@ -0,0 +1,77 @@
+#![cfg(feature = "tokio-executor")]
+
+use bastion::executor::TokioExecutor;
+use bastion::prelude::*;
+use futures_timer::Delay;
+use std::fs;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+
+// Returns the names of the threads of the process.
+fn thread_names() -> Vec<String> {
+    fs::read_dir("/proc/self/task")
+        .map(|tasks| {
+            tasks
+                .filter_map(|task| fs::read_to_string(task.ok()?.path().join("comm")).ok())
+                .map(|name| name.trim().to_string())
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn tokio_app() {
+    Bastion::with_custom_executor(TokioExecutor::current());
+    Bastion::init();
+    Bastion::start();
+
+    let started = Arc::new(AtomicUsize::new(0));
+    let started_inner = started.clone();
+    let children_ref = Bastion::children(move |children| {
+        let started = started_inner.clone();
+        children.with_exec(move |ctx: BastionContext| {
+            let started = started.clone();
+            async move {
+                // Panicking gets the element restarted by its
+                // supervisor...
+                if started.fetch_add(1, Ordering::SeqCst) == 0 {
+                    panic!("first start");
+                }
+
+                loop {
+                    msg! { ctx.recv().await?,
+                        msg: &'static str =!> {
+                            answer!(ctx, msg).unwrap();
+                        };
+                        _: _ => ();
+                    }
+                }
+            }
+        })
+    })
+    .expect("Couldn't create the children group.");
+
+    // ...which then answers, even when blocking from inside the
+    // tokio runtime.
+    let mut tries = 0;
+    while started.load(Ordering::SeqCst) != 2 && tries < 500 {
+        Delay::new(Duration::from_millis(10)).await;
+        tries += 1;
+    }
+    let answer = run!(children_ref.ask_timeout("ping", Duration::from_secs(5)))
+        .expect("Couldn't receive the answer.");
+    msg! { answer,
+        msg: &'static str => assert_eq!(msg, "ping");
+        _: _ => panic!("Unexpected answer.");
+    }
+    assert_eq!(started.load(Ordering::SeqCst), 2);
+
+    // Bastion's own executor never spawned its threads.
+    assert!(thread_names()
+        .iter()
+        .all(|name| !name.starts_with("bastion-async")));
+
+    Bastion::stop();
+    assert_eq!(Bastion::block_until_stopped(), SystemExit::Stopped);
+}
@@ -0,0 +1,53 @@
+#![cfg(feature = "tokio-executor")]
+
+mod common;
+mod conformance;
+
+use bastion::executor::TokioExecutor;
+use bastion::prelude::*;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+use std::thread;
+use std::time::Duration;
+
+#[test]
+fn tokio_executor() {
+    let tokio = tokio::runtime::Builder::new_multi_thread()
+        .enable_all()
+        .build()
+        .unwrap();
+    Bastion::with_custom_executor(TokioExecutor::new(tokio.handle().clone()));
+    Bastion::init();
+    Bastion::start();
+
+    let received = Arc::new(AtomicUsize::new(0));
+    let received_inner = received.clone();
+    let children_ref = Bastion::children(move |children| {
+        let received = received_inner.clone();
+        children
+            .with_redundancy(2)
+            .with_exec(move |ctx: BastionContext| {
+                let received = received.clone();
+                async move {
+                    loop {
+                        ctx.recv().await?;
+                        received.fetch_add(1, Ordering::SeqCst);
+                    }
+                }
+            })
+    })
+    .expect("Couldn't create the children group.");
+
+    children_ref.broadcast("work").unwrap();
+    let mut tries = 0;
+    while received.load(Ordering::SeqCst) != 2 && tries < 500 {
+        thread::sleep(Duration::from_millis(10));
+        tries += 1;
+    }
+    assert_eq!(received.load(Ordering::SeqCst), 2);
+
+    conformance::check();
+
+    Bastion::stop();
+    assert_eq!(Bastion::block_until_stopped(), SystemExit::Stopped);
+}
@@ -0,0 +1,76 @@
+use bastion::prelude::*;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+use std::thread;
+use std::time::Duration;
+
+#[derive(Debug)]
+struct Invoice;
+
+fn spawn_subscribers(
+    redundancy: usize,
+    subscribed: Arc<AtomicUsize>,
+    received: Arc<AtomicUsize>,
+) -> ChildrenRef {
+    Bastion::children(|children| {
+        children
+            .with_redundancy(redundancy)
+            .with_exec(move |ctx: BastionContext| {
+                let subscribed = subscribed.clone();
+                let received = received.clone();
+                async move {
+                    ctx.subscribe("invoices");
+                    subscribed.fetch_add(1, Ordering::SeqCst);
+
+                    loop {
+                        msg! { ctx.recv().await?,
+                            ref _msg: Invoice => {
+                                received.fetch_add(1, Ordering::SeqCst);
+                            };
+                            _: _ => ();
+                        }
+                    }
+                }
+            })
+    })
+    .expect("Couldn't create the children group.")
+}
+
+#[test]
+fn publish_reaches_every_subscriber() {
+    Bastion::init();
+    Bastion::start();
+
+    assert!(Bastion::publish("invoices", Invoice).is_err());
+
+    let subscribed = Arc::new(AtomicUsize::new(0));
+    let received = Arc::new(AtomicUsize::new(0));
+
+    spawn_subscribers(2, subscribed.clone(), received.clone());
+    let stopped = spawn_subscribers(1, subscribed.clone(), received.clone());
+
+    while subscribed.load(Ordering::SeqCst) < 3 {
+        thread::sleep(Duration::from_millis(10));
+    }
+
+    assert!(Bastion::publish("invoices", Invoice).is_ok());
+    assert!(Bastion::publish("payments", Invoice).is_err());
+
+    while received.load(Ordering::SeqCst) < 3 {
+        thread::sleep(Duration::from_millis(10));
+    }
+
+    // The subscriptions of stopped elements are removed.
+    stopped.stop().expect("Couldn't stop the children group.");
+    thread::sleep(Duration::from_millis(100));
+
+    assert!(Bastion::publish("invoices", Invoice).is_ok());
+    while received.load(Ordering::SeqCst) < 5 {
+        thread::sleep(Duration::from_millis(10));
+    }
+    thread::sleep(Duration::from_millis(100));
+    assert_eq!(received.load(Ordering::SeqCst), 5);
+
+    Bastion::stop();
+    Bastion::block_until_stopped();
+}
@@ -0,0 +1,61 @@
+use bastion::prelude::*;
+use std::sync::{Arc, Mutex};
+
+#[derive(Debug)]
+struct Request;
+
+#[derive(Debug)]
+struct Forwarded;
+
+#[test]
+fn trace_id_is_propagated_to_sent_messages() {
+    Bastion::init();
+    Bastion::start();
+
+    let reported = Arc::new(Mutex::new(Vec::new()));
+    let reported_inner = reported.clone();
+
+    let receiver = Bastion::children(|children| {
+        children.with_exec(move |ctx: BastionContext| {
+            let reported = reported_inner.clone();
+            async move {
+                msg! { ctx.recv().await?,
+                    _msg: Forwarded => {
+                        reported.lock().unwrap().push(ctx.current_trace());
+                    };
+                    _: _ => ();
+                }
+
+                Bastion::stop();
+                Ok(())
+            }
+        })
+    })
+    .expect("Couldn't create the children group.");
+    let receiver_addr = receiver.elems()[0].addr();
+
+    let forwarder = Bastion::children(|children| {
+        children.with_exec(move |ctx: BastionContext| {
+            let receiver_addr = receiver_addr.clone();
+            async move {
+                msg! { ctx.recv().await?,
+                    ref _msg: Request => {
+                        ctx.tell(&receiver_addr, Forwarded).unwrap();
+                    };
+                    _: _ => ();
+                }
+
+                Ok(())
+            }
+        })
+    })
+    .expect("Couldn't create the children group.");
+
+    let trace = TraceId::new();
+    forwarder.broadcast_traced(Request, trace).unwrap();
+
+    Bastion::block_until_stopped();
+
+    assert_eq!(*reported.lock().unwrap(), vec![Some(trace)]);
+    assert_eq!(trace.to_string().len(), 32);
+}
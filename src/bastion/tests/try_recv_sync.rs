@@ -0,0 +1,112 @@
+mod common;
+
+use bastion::prelude::*;
+use common::wait_until;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex};
+
+#[test]
+fn try_recv_sync() {
+    // With enough threads for the runtimes to keep running while
+    // the element of the other test keeps one of them busy.
+    Bastion::init_with(Config::new().with_threads(4));
+
+    let seen = Arc::new(Mutex::new(None));
+
+    let seen_inner = seen.clone();
+    let children_ref = Bastion::children(move |children| {
+        let seen = seen_inner.clone();
+        children.with_exec(move |ctx: BastionContext| {
+            let seen = seen.clone();
+            async move {
+                let had_messages = ctx.has_messages();
+                let mut received = vec![];
+                while let Some(msg) = ctx.try_recv_sync() {
+                    msg! { msg,
+                        ref msg: &'static str => received.push(*msg);
+                        _: _ => ();
+                    }
+                }
+
+                *seen.lock().unwrap() = Some((had_messages, received, ctx.has_messages()));
+                Ok(())
+            }
+        })
+    })
+    .expect("Couldn't create the children group.");
+
+    // The messages are received before the element's future is
+    // first polled.
+    children_ref.broadcast("first").unwrap();
+    children_ref.broadcast("second").unwrap();
+    Bastion::start();
+
+    wait_until(|| seen.lock().unwrap().is_some());
+    let (had_messages, received, has_messages) = seen.lock().unwrap().take().unwrap();
+    assert!(had_messages);
+    assert_eq!(received, vec!["first", "second"]);
+    assert!(!has_messages);
+
+    Bastion::stop();
+    Bastion::block_until_stopped();
+}
+
+#[test]
+fn try_recv_sync_while_busy() {
+    // Using its own runtime, not to share the default one with the
+    // other test.
+    let runtime = BastionRuntime::new(Config::new().with_threads(4));
+
+    let busy = Arc::new(AtomicBool::new(false));
+    let seen = Arc::new(Mutex::new(Vec::new()));
+
+    let (busy_inner, seen_inner) = (busy.clone(), seen.clone());
+    let children_ref = runtime
+        .children(move |children| {
+            let (busy, seen) = (busy_inner.clone(), seen_inner.clone());
+            children.with_exec(move |ctx: BastionContext| {
+                let (busy, seen) = (busy.clone(), seen.clone());
+                async move {
+                    busy.store(true, Ordering::SeqCst);
+                    // Never yielding until a message is retrieved...
+                    let msg = loop {
+                        match ctx.try_recv_sync() {
+                            Some(msg) => break msg,
+                            None => std::hint::spin_loop(),
+                        }
+                    };
+                    msg! { msg,
+                        msg: &'static str => seen.lock().unwrap().push(msg.to_string());
+                        _: _ => ();
+                    }
+
+                    // ...while the message asking it to stop is only
+                    // handled once yielding.
+                    while !ctx.has_messages() {
+                        std::hint::spin_loop();
+                    }
+                    let retrieved = ctx.try_recv_sync().is_some();
+                    seen.lock()
+                        .unwrap()
+                        .push(format!("retrieved: {}", retrieved));
+                    ctx.recv().await?;
+
+                    Ok(())
+                }
+            })
+        })
+        .expect("Couldn't create the children group.");
+
+    runtime.start();
+    wait_until(|| busy.load(Ordering::SeqCst));
+
+    // The messages are sent while the element's future is busy.
+    children_ref.elems()[0].tell_anonymously("ping").unwrap();
+    wait_until(|| seen.lock().unwrap().len() == 1);
+    children_ref.stop().unwrap();
+    wait_until(|| seen.lock().unwrap().len() == 2);
+    assert_eq!(*seen.lock().unwrap(), vec!["ping", "retrieved: false"]);
+
+    runtime.stop();
+    runtime.block_until_stopped();
+}
@@ -0,0 +1,42 @@
+use bastion::prelude::*;
+use std::sync::{Arc, Mutex};
+
+#[test]
+fn unhandled_message_hook_receives_message_and_child_id() {
+    Bastion::init();
+
+    let reported = Arc::new(Mutex::new(Vec::new()));
+    let reported_inner = reported.clone();
+    Bastion::set_unhandled_message_hook(move |msg, id| {
+        reported_inner
+            .lock()
+            .unwrap()
+            .push((msg.is::<u32>(), id.clone()));
+    });
+
+    Bastion::start();
+
+    let children = Bastion::children(|children| {
+        children.with_exec(|ctx: BastionContext| async move {
+            ctx.tell(&ctx.current().addr(), 42u32).unwrap();
+            ctx.tell(&ctx.current().addr(), "handled").unwrap();
+
+            for _ in 0..2 {
+                msg! { ctx.recv().await?,
+                    _msg: &'static str => ();
+                    _: _ => ();
+                }
+            }
+
+            Bastion::stop();
+            Ok(())
+        })
+    })
+    .expect("Couldn't create the children group.");
+    let child_id = children.elems()[0].id().clone();
+
+    Bastion::block_until_stopped();
+
+    let reported = reported.lock().unwrap();
+    assert_eq!(*reported, vec![(true, child_id)]);
+}
@@ -0,0 +1,42 @@
+#![cfg(all(unix, feature = "unix-signals"))]
+
+use bastion::prelude::*;
+use std::process::{self, Command};
+
+fn signal(name: &str) {
+    let status = Command::new("kill")
+        .arg(format!("-{}", name))
+        .arg(process::id().to_string())
+        .status()
+        .expect("Couldn't send the signal.");
+    assert!(status.success());
+}
+
+#[test]
+fn unix_signals() {
+    Bastion::init();
+    Bastion::watch_signals().expect("Couldn't install the signal handlers.");
+    // The handlers can only be installed once.
+    assert!(Bastion::watch_signals().is_err());
+    // Neither can the ones handling `SIGINT` differently.
+    #[cfg(feature = "signals")]
+    assert!(Bastion::handle_signals(None).is_err());
+
+    Bastion::children(|children| {
+        children.with_exec(|ctx: BastionContext| async move {
+            loop {
+                ctx.recv().await?;
+            }
+        })
+    })
+    .expect("Couldn't create the children group.");
+
+    Bastion::start();
+
+    // Only dumping the stats...
+    signal("USR1");
+    // ...before stopping gracefully.
+    signal("TERM");
+
+    assert_eq!(Bastion::block_until_stopped(), SystemExit::Stopped);
+}
@@ -33,6 +33,12 @@ pub struct ProcStack {
 
     pub(crate) state: ProcState,
 
+    /// Whether the process should be run before the other ones
+    ///
+    /// Executors can use it to run the processes driving the others (e.g. supervisors)
+    /// without waiting for all the ready processes to get polled first.
+    pub(crate) high_priority: bool,
+
     /// Before start callback
     ///
     /// This callback is called before we start to inner future of the process
@@ -92,6 +98,23 @@ impl ProcStack {
         self
     }
 
+    /// Marks the process which is going to take this stack as a high priority one,
+    /// which executors can schedule before the other processes.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use lightproc::proc_stack::ProcStack;
+    ///
+    /// ProcStack::default()
+    ///     .with_pid(1)
+    ///     .with_high_priority(true);
+    /// ```
+    pub fn with_high_priority(mut self, high_priority: bool) -> Self {
+        self.high_priority = high_priority;
+        self
+    }
+
     /// Adds a callback that will be executed before polling inner future to the stack
     ///
     /// ```rust
@@ -159,6 +182,21 @@ impl ProcStack {
         self.pid.load(Ordering::Acquire)
     }
 
+    /// Utility function to check whether the process has a high priority, for the
+    /// implementation of executors.
+    ///
+    /// ```rust
+    /// use lightproc::proc_stack::ProcStack;
+    ///
+    /// let proc = ProcStack::default().with_high_priority(true);
+    ///
+    /// assert!(proc.is_high_priority());
+    /// assert!(!ProcStack::default().is_high_priority());
+    /// ```
+    pub fn is_high_priority(&self) -> bool {
+        self.high_priority
+    }
+
     /// Get the state which is embedded into this [ProcStack].
     ///
     /// ```rust
@@ -211,6 +249,7 @@ impl Default for ProcStack {
         ProcStack {
             pid: AtomicUsize::new(0xDEAD_BEEF),
             state: Arc::new(Mutex::new(EmptyState)),
+            high_priority: false,
             before_start: None,
             after_complete: None,
             after_panic: None,
@@ -223,6 +262,7 @@ impl Debug for ProcStack {
         fmt.debug_struct("ProcStack")
             .field("pid", &self.pid.load(Ordering::SeqCst))
             .field("state", &self.state)
+            .field("high_priority", &self.high_priority)
             .field("before_start", &self.before_start.is_some())
             .field("after_complete", &self.after_complete.is_some())
             .field("after_panic", &self.after_panic.is_some())
@@ -235,6 +275,7 @@ impl Clone for ProcStack {
         ProcStack {
             pid: AtomicUsize::new(self.pid.load(Ordering::Acquire)),
             state: self.state.clone(),
+            high_priority: self.high_priority,
             before_start: self.before_start.clone(),
             after_complete: self.after_complete.clone(),
             after_panic: self.after_panic.clone(),